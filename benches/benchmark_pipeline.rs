@@ -0,0 +1,171 @@
+//! End-to-end timings of `perform_cracking` on a fixed corpus
+//!
+//! `cargo bench --bench benchmark_pipeline` runs the corpus under Criterion.
+//! To catch regressions in the searcher, compare against the timings committed
+//! in `benches/baselines/pipeline.json`:
+//!
+//! ```text
+//! cargo bench --bench benchmark_pipeline -- --bench-compare
+//! ```
+//!
+//! This exits with an error if any case's median is more than 25% slower than
+//! its baseline (`ARES_BENCH_TOLERANCE=0.5` allows 50%). After a change which
+//! is meant to change the timings, or on a different machine, record new
+//! baselines with `-- --bench-record`.
+
+use ares::config::{CacheMode, Config};
+use ares::perform_cracking;
+use criterion::{criterion_group, BenchmarkId, Criterion};
+use env_logger::Builder;
+use log::LevelFilter;
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The corpus: a name for each case and the text to crack
+const CORPUS: &[(&str, &str)] = &[
+    // "hello there general kenobi" in Base64 three times
+    (
+        "base64_chain",
+        "WVVkV2MySkhPR2RrUjJoc1kyMVZaMW95Vm5WYVdFcG9Za05DY2xwWE5YWlpiV3M5",
+    ),
+    // "the quick brown fox jumps over the lazy dog" in ROT13, then Base64, then hexadecimal
+    (
+        "nested_encodings",
+        "5a3356794947526f646e42344947396c596d706849484e69617942336148706a5a69426961584a6c4947643163694235626d31734948466964413d3d",
+    ),
+    (
+        "vigenere",
+        "eznwxg kce yjmwuckgrttta ucixkb ceb sxkwfv tpkqwwj rnima qw ccvwlgu mg xvktpnixl bgor, xgktwugcz (jcv emi equkkcs mw) Jcjc64, Wxfifvaxfit, Erchtz kkgftk, ZWV13, LPA xvkqugcz, ivf dycr uwtv. Gi namu rbktvkgu yazwzkkfbl ivf ycjkqavzah mw qfvlibng vyc tgkwfzlv mgxg rls txxnp rwx ixrimekqivv btvwlkee bxbpqu, mummv jrlseqvi dsamqxnv jprmzu fd tgkwfzlv tcbqdyibkincw.",
+    ),
+    // Alice in Wonderland under a 7 column key
+    (
+        "columnar_transposition",
+        "HNSELTAIVHHHRAERTVAIOIKRUWABTFWIRKTDETMOYTIITSGRBADHOCTOESSANYAHITSUFTHTTEOOMENLNEOEEAYLEHERNRCIYTARBO",
+    ),
+    // "the quick brown fox jumps over the lazy dog" shifted by 3
+    ("caesar", "Wkh txlfn eurzq ira mxpsv ryhu wkh odcb grj"),
+];
+
+/// Where the committed baselines are kept
+const BASELINE_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/benches/baselines/pipeline.json");
+
+/// How many times each case is cracked for `--bench-compare` and `--bench-record`
+const COMPARE_RUNS: usize = 10;
+
+/// How much slower than its baseline a case may be, unless `ARES_BENCH_TOLERANCE` says
+const DEFAULT_TOLERANCE: f64 = 0.25;
+
+/// The config every case is cracked with: quiet, no human checker and no
+/// cache, so every run does the whole search. A case the searcher can't
+/// solve runs until the timeout, so its time only moves once it starts solving.
+fn config() -> Config {
+    Config {
+        timeout: 5,
+        human_checker_on: false,
+        api_mode: true,
+        verbose: 0,
+        cache_mode: CacheMode::Off,
+        seed: Some(1),
+        ..Config::default()
+    }
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pipeline");
+    group.measurement_time(Duration::from_secs(10));
+    group.sample_size(10);
+    for (name, text) in CORPUS {
+        group.bench_with_input(BenchmarkId::new(*name, text.len()), text, |b, text| {
+            b.iter(|| perform_cracking(black_box(text), config()))
+        });
+    }
+    group.finish();
+}
+
+/// The median time in milliseconds to crack `text`
+fn median_millis(text: &str) -> f64 {
+    let mut times: Vec<f64> = (0..COMPARE_RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(perform_cracking(text, config()));
+            start.elapsed().as_secs_f64() * 1000.0
+        })
+        .collect();
+    times.sort_by(f64::total_cmp);
+    times[times.len() / 2]
+}
+
+/// Times the corpus and writes the medians to the baseline file
+fn record() {
+    let timings: BTreeMap<&str, f64> = CORPUS
+        .iter()
+        .map(|(name, text)| {
+            let millis = median_millis(text);
+            println!("{name}: {millis:.1}ms");
+            (*name, (millis * 10.0).round() / 10.0)
+        })
+        .collect();
+    let json = serde_json::to_string_pretty(&timings).expect("timings are valid JSON");
+    std::fs::write(BASELINE_FILE, json + "\n").expect("could not write the baselines");
+    println!("Baselines written to {BASELINE_FILE}");
+}
+
+/// Times the corpus against the baseline file, exiting with an error if
+/// anything got slower than the tolerance allows
+fn compare() {
+    let baselines: BTreeMap<String, f64> = std::fs::read_to_string(BASELINE_FILE)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .expect("could not read the baselines, record them with --bench-record");
+    let tolerance = std::env::var("ARES_BENCH_TOLERANCE")
+        .ok()
+        .and_then(|tolerance| tolerance.parse().ok())
+        .unwrap_or(DEFAULT_TOLERANCE);
+
+    let mut regressions = 0;
+    for (name, text) in CORPUS {
+        let millis = median_millis(text);
+        let Some(&baseline) = baselines.get(*name) else {
+            println!("{name}: {millis:.1}ms (no baseline)");
+            continue;
+        };
+        let change = millis / baseline - 1.0;
+        let verdict = if change > tolerance {
+            regressions += 1;
+            "REGRESSED"
+        } else {
+            "ok"
+        };
+        println!(
+            "{name}: {millis:.1}ms against {baseline:.1}ms ({:+.0}%) {verdict}",
+            change * 100.0
+        );
+    }
+    if regressions > 0 {
+        eprintln!(
+            "{regressions} case(s) more than {:.0}% slower than the baseline",
+            tolerance * 100.0
+        );
+        std::process::exit(1);
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+
+fn main() {
+    // Initialize logger with only error level to suppress debug messages
+    let mut builder = Builder::new();
+    builder.filter_level(LevelFilter::Error);
+    builder.try_init().ok();
+
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--bench-record") {
+        record();
+    } else if args.iter().any(|arg| arg == "--bench-compare") {
+        compare();
+    } else {
+        benches();
+        Criterion::default().configure_from_args().final_summary();
+    }
+}
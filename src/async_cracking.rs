@@ -0,0 +1,65 @@
+//! Cracking from async code, with the `tokio` feature
+//!
+//! The search blocks a thread for up to the whole timeout, which would stall a
+//! tokio worker. [`perform_cracking_async`] runs it on tokio's blocking pool and
+//! stops it once the future is dropped, so a web service can cancel a request
+//! or wrap the call in its own timeout without leaving the search running.
+
+use crate::config::Config;
+use crate::{crack_input, Candidate, CrackContext, DecoderResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Extra time allowed over `config.timeout`, for the cache and setup around the search
+const TIMEOUT_GRACE: Duration = Duration::from_secs(1);
+
+/// Sets the flag when dropped, cancelling the search
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Cracks the text like [`crate::perform_cracking`] without blocking the runtime.
+///
+/// Returns None if no plaintext is found within `config.timeout` seconds.
+/// Dropping the future cancels the search. The human checker would wait on
+/// stdin, so services should turn it off.
+///
+/// ```rust
+/// # let _test_db = ares::TestDatabase::default();
+/// # ares::set_test_db_path();
+/// use ares::config::Config;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+/// let config = Config { human_checker_on: false, ..Default::default() };
+/// let result = runtime.block_on(ares::perform_cracking_async("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config));
+/// assert_eq!(result.unwrap().text[0], "hello there general");
+/// ```
+pub async fn perform_cracking_async(text: &str, config: Config) -> Option<DecoderResult> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let _cancel_on_drop = CancelOnDrop(cancel.clone());
+    let timeout = Duration::from_secs(config.timeout.into()) + TIMEOUT_GRACE;
+    let input = Candidate::from(text);
+    let search = tokio::task::spawn_blocking(move || {
+        let ctx = CrackContext {
+            cancel: Some(&cancel),
+            ..Default::default()
+        };
+        crack_input(input, config, ctx).ok()
+    });
+    match tokio::time::timeout(timeout, search).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => {
+            log::warn!("Search task failed: {}", e);
+            None
+        }
+        Err(_) => {
+            log::info!("Search did not finish within its timeout");
+            None
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Carrying raw bytes through the text pipeline
+//!
+//! XOR'd or compressed data is rarely valid UTF-8, so what decoders produce is
+//! a [`Candidate`]: text, or the bytes as they are. Decoders which work on
+//! bytes, like XOR, override [`Crack::crack_candidate`] to get them back
+//! untouched. The rest, and the checkers, get a text view with the bytes
+//! stored one char per byte (U+0000 to U+00FF, i.e. Latin-1), so no
+//! information is lost. Input given as bytes is turned into a [`Candidate`]
+//! the same way, and carried to the search as one, as a text view can't say
+//! whether "café" was typed or is four bytes.
+//!
+//! [`Crack::crack_candidate`]: crate::decoders::interface::Crack::crack_candidate
+
+use crate::text_info::TextInfo;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock};
+
+/// What a decoder produced: text, or bytes which aren't valid UTF-8.
+/// Both are shared, so the search can clone candidates and the paths
+/// holding them without copying the text. The [`TextInfo`] decoders want
+/// is worked out the first time one asks, and kept.
+#[derive(Debug, Clone, Default)]
+pub struct Candidate {
+    /// The text or bytes
+    content: Content,
+    /// What has been worked out about the text, once a decoder asks
+    info: OnceLock<Arc<TextInfo>>,
+}
+
+/// What a [`Candidate`] holds
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Content {
+    /// Valid UTF-8 text
+    Text(Arc<str>),
+    /// Bytes which aren't text, like the output of XOR or a decompressor
+    Bytes(Arc<[u8]>),
+}
+
+impl Default for Content {
+    fn default() -> Self {
+        Content::Text("".into())
+    }
+}
+
+impl Candidate {
+    /// Text if the bytes are valid UTF-8, otherwise the bytes themselves
+    ///
+    /// ```
+    /// use ares::byte_text::Candidate;
+    ///
+    /// assert_eq!(Candidate::from_bytes(b"hello".to_vec()), "hello");
+    /// assert!(Candidate::from_bytes(vec![0xff, 0x00]).is_bytes());
+    /// ```
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        match String::from_utf8(bytes) {
+            Ok(text) => text.into(),
+            Err(e) => Content::Bytes(e.into_bytes().into()).into(),
+        }
+    }
+
+    /// The text or bytes
+    pub fn content(&self) -> &Content {
+        &self.content
+    }
+
+    /// The text, with bytes one char per byte as [`bytes_to_text`] stores them
+    pub fn text(&self) -> Cow<'_, str> {
+        match &self.content {
+            Content::Text(text) => Cow::Borrowed(text),
+            Content::Bytes(bytes) => Cow::Owned(bytes.iter().map(|&b| b as char).collect()),
+        }
+    }
+
+    /// Like [`Candidate::text`], taking ownership
+    pub fn into_text(self) -> String {
+        match self.content {
+            Content::Text(text) => text.to_string(),
+            Content::Bytes(bytes) => bytes.iter().map(|&b| b as char).collect(),
+        }
+    }
+
+    /// The bytes: as they are, or the UTF-8 of the text
+    pub fn bytes(&self) -> &[u8] {
+        match &self.content {
+            Content::Text(text) => text.as_bytes(),
+            Content::Bytes(bytes) => bytes,
+        }
+    }
+
+    /// Whether these are bytes which aren't text
+    pub fn is_bytes(&self) -> bool {
+        matches!(self.content, Content::Bytes(_))
+    }
+
+    /// Whether there is nothing
+    pub fn is_empty(&self) -> bool {
+        self.bytes().is_empty()
+    }
+
+    /// How many bytes there are
+    pub fn len(&self) -> usize {
+        self.bytes().len()
+    }
+
+    /// What the decoders want to know about the text view, worked out the
+    /// first time it is asked for. Clones made after that share it.
+    ///
+    /// ```
+    /// use ares::byte_text::Candidate;
+    ///
+    /// let candidate = Candidate::from("Hello, World");
+    /// assert_eq!(candidate.info().uppercase_letters, "HELLOWORLD");
+    /// ```
+    pub fn info(&self) -> &TextInfo {
+        self.info.get_or_init(|| Arc::new(TextInfo::new(&self.text())))
+    }
+}
+
+/// Candidates are equal if they hold the same text or bytes
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.content == other.content
+    }
+}
+
+impl Eq for Candidate {}
+
+impl Hash for Candidate {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.content.hash(state);
+    }
+}
+
+impl From<Content> for Candidate {
+    fn from(content: Content) -> Self {
+        Candidate {
+            content,
+            info: OnceLock::new(),
+        }
+    }
+}
+
+impl From<String> for Candidate {
+    fn from(text: String) -> Self {
+        Content::Text(text.into()).into()
+    }
+}
+
+impl From<&str> for Candidate {
+    fn from(text: &str) -> Self {
+        Content::Text(text.into()).into()
+    }
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text())
+    }
+}
+
+impl PartialEq<str> for Candidate {
+    fn eq(&self, other: &str) -> bool {
+        self.text() == other
+    }
+}
+
+impl PartialEq<&str> for Candidate {
+    fn eq(&self, other: &&str) -> bool {
+        self.text() == *other
+    }
+}
+
+impl PartialEq<String> for Candidate {
+    fn eq(&self, other: &String) -> bool {
+        self.text() == other.as_str()
+    }
+}
+
+/// Serialized as its text view, so JSON output and the cache keep plain strings
+impl Serialize for Candidate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text())
+    }
+}
+
+impl<'de> Deserialize<'de> for Candidate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Candidate::from)
+    }
+}
+
+/// Turns bytes into text for the search.
+/// Valid UTF-8 is kept as is, anything else is mapped one char per byte.
+pub fn bytes_to_text(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_round_trips() {
+        let bytes = [0xcd, 0xc0, 0xc9, 0xc9, 0xca, 0x00, 0xff];
+        let text = bytes_to_text(&bytes);
+        assert_eq!(text.chars().count(), bytes.len());
+        assert!(text.chars().zip(bytes).all(|(c, b)| c as u32 == u32::from(b)));
+    }
+
+    #[test]
+    fn candidates_keep_bytes_and_view_them_as_text() {
+        let bytes = vec![0xcd, 0x00, 0xff];
+        let candidate = Candidate::from_bytes(bytes.clone());
+        assert_eq!(candidate.bytes(), bytes);
+        assert_eq!(candidate.text(), bytes_to_text(&bytes));
+        assert_eq!(serde_json::to_string(&candidate).unwrap(), "\"Í\\u0000ÿ\"");
+        assert_eq!(Candidate::from("hi"), "hi");
+        assert!(!Candidate::from("hi").is_bytes());
+    }
+
+    #[test]
+    fn utf8_is_left_alone() {
+        assert_eq!(bytes_to_text("hello".as_bytes()), "hello");
+        // Text which could pass for Latin-1 bytes is still text
+        let candidate = Candidate::from_bytes("café x²".as_bytes().to_vec());
+        assert!(!candidate.is_bytes());
+        assert_eq!(candidate.bytes(), "café x²".as_bytes());
+    }
+}
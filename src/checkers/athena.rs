@@ -3,12 +3,14 @@
 /// For a version that continues checking and collects all plaintexts, see WaitAthena.
 use crate::{checkers::checker_result::CheckResult, config::Config};
 use gibberish_or_not::Sensitivity;
-use lemmeknow::Identifier;
-use log::trace;
+use crate::checkers::Identifier;
+use tracing::{debug, trace};
 use once_cell::sync::Lazy;
 
 use super::{
     checker_type::{Check, Checker},
+    coordinates::CoordinatesChecker,
+    custom::custom_checkers,
     english::EnglishChecker,
     human_checker,
     lemmeknow_checker::LemmeKnow,
@@ -38,6 +40,11 @@ static PASSWORD_LOW: Lazy<Checker<PasswordChecker>> = Lazy::new(|| {
     Checker::<PasswordChecker>::new().with_sensitivity(Sensitivity::Low)
 });
 
+/// Static coordinates checker with low sensitivity
+static COORDINATES_LOW: Lazy<Checker<CoordinatesChecker>> = Lazy::new(|| {
+    Checker::<CoordinatesChecker>::new().with_sensitivity(Sensitivity::Low)
+});
+
 /// Static english checker with low sensitivity
 static ENGLISH_LOW: Lazy<Checker<EnglishChecker>> = Lazy::new(|| {
     Checker::<EnglishChecker>::new().with_sensitivity(Sensitivity::Low)
@@ -95,8 +102,8 @@ impl Check for Checker<Athena> {
                 return check_res;
             }
         } else {
-            // Run wordlist checker first if a wordlist is provided
-            if config.wordlist.is_some() {
+            // Run wordlist checker first if a wordlist or dictionary words are provided
+            if config.wordlist.is_some() || !config.dictionary_words.is_empty() {
                 trace!("running wordlist checker");
                 
                 let wordlist_checker_temp;
@@ -118,7 +125,7 @@ impl Check for Checker<Athena> {
                     check_res.is_identified = human_result;
                     check_res.text = wordlist_result.text;
                     check_res.description = wordlist_result.description;
-                    log::debug!(
+                    debug!(
                         "DEBUG: Athena wordlist checker - human_result: {}, check_res.is_identified: {}",
                         human_result, check_res.is_identified
                     );
@@ -128,7 +135,22 @@ impl Check for Checker<Athena> {
 
             // In Ciphey if the user uses the regex checker all the other checkers turn off
             // This is because they are looking for one specific bit of information so will not want the other checkers
-            
+
+            // Checkers registered by library users know their own plaintext best, so they go first
+            for custom_checker in custom_checkers() {
+                let mut custom_result = custom_checker.check(text, config);
+                if custom_result.is_identified {
+                    let human_result = human_checker::human_checker(&custom_result, config);
+                    trace!(
+                        "Human checker called from {} with result: {}",
+                        custom_result.checker_name,
+                        human_result
+                    );
+                    custom_result.is_identified = human_result;
+                    return custom_result;
+                }
+            }
+
             // LemmeKnow Checker
             let lemmeknow_temp;
             let lemmeknow_ref: &Checker<LemmeKnow> = if is_low {
@@ -150,7 +172,7 @@ impl Check for Checker<Athena> {
                 check_res.is_identified = human_result;
                 check_res.text = lemmeknow_result.text;
                 check_res.description = lemmeknow_result.description;
-                log::debug!("DEBUG: Athena lemmeknow checker - human_result: {}, check_res.is_identified: {}", human_result, check_res.is_identified);
+                debug!("DEBUG: Athena lemmeknow checker - human_result: {}, check_res.is_identified: {}", human_result, check_res.is_identified);
                 return check_res;
             }
 
@@ -174,7 +196,31 @@ impl Check for Checker<Athena> {
                 check_res.is_identified = human_result;
                 check_res.text = password_result.text;
                 check_res.description = password_result.description;
-                log::debug!("DEBUG: Athena password checker - human_result: {}, check_res.is_identified: {}", human_result, check_res.is_identified);
+                debug!("DEBUG: Athena password checker - human_result: {}, check_res.is_identified: {}", human_result, check_res.is_identified);
+                return check_res;
+            }
+
+            // Coordinates Checker
+            let coordinates_temp;
+            let coordinates_ref: &Checker<CoordinatesChecker> = if is_low {
+                &COORDINATES_LOW
+            } else {
+                coordinates_temp = Checker::<CoordinatesChecker>::new().with_sensitivity(self.sensitivity);
+                &coordinates_temp
+            };
+
+            let coordinates_result = coordinates_ref.check(text, config);
+            if coordinates_result.is_identified {
+                let mut check_res = CheckResult::new(coordinates_ref);
+                let human_result = human_checker::human_checker(&coordinates_result, config);
+                trace!(
+                    "Human checker called from coordinates checker with result: {}",
+                    human_result
+                );
+                check_res.is_identified = human_result;
+                check_res.text = coordinates_result.text;
+                check_res.description = coordinates_result.description;
+                debug!("DEBUG: Athena coordinates checker - human_result: {}, check_res.is_identified: {}", human_result, check_res.is_identified);
                 return check_res;
             }
 
@@ -198,7 +244,7 @@ impl Check for Checker<Athena> {
                 check_res.is_identified = human_result;
                 check_res.text = english_result.text;
                 check_res.description = english_result.description;
-                log::debug!(
+                debug!(
                     "DEBUG: Athena english checker - human_result: {}, check_res.is_identified: {}",
                     human_result, check_res.is_identified
                 );
@@ -3,7 +3,7 @@
 use crate::checkers::checker_result::CheckResult;
 use crate::config::Config;
 use gibberish_or_not::Sensitivity;
-use lemmeknow::Identifier;
+use crate::checkers::Identifier;
 
 /// Every checker is of type CheckerType
 /// This will let us pick & choose which checkers to use
@@ -47,6 +47,10 @@ pub trait CheckInfo {
     fn get_name(&self) -> &str;
     /// Returns the checker description
     fn get_description(&self) -> &str;
+    /// Returns the checker tags
+    fn get_tags(&self) -> &Vec<&str>;
+    /// Returns the checker popularity
+    fn get_popularity(&self) -> f32;
 }
 
 impl<Type> CheckInfo for Checker<Type> {
@@ -58,6 +62,14 @@ impl<Type> CheckInfo for Checker<Type> {
     fn get_description(&self) -> &str {
         self.description
     }
+    /// Returns the checker tags
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Returns the checker popularity
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
 }
 
 /// Every checker must implement this trait
@@ -0,0 +1,132 @@
+use crate::checkers::checker_result::CheckResult;
+use crate::config::Config;
+use gibberish_or_not::Sensitivity;
+use crate::checkers::Identifier;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::checkers::checker_type::{Check, Checker};
+
+/// Decimal degrees, e.g. `51.50073, -0.12463` or `51.5007° N, 0.1246° W`.
+/// Plain numbers need two decimal places, so a pair of small numbers isn't
+/// taken for a place.
+static DECIMAL_DEGREES: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^([-+]?)(\d{1,2}\.\d{2,})\s*°?\s*([NS]?)\s*[,;\s]\s*([-+]?)(\d{1,3}\.\d{2,})\s*°?\s*([EW]?)$",
+    )
+    .expect("the decimal degrees regex is valid")
+});
+
+/// Degrees, minutes and optionally seconds, e.g. `51°30'2.6"N 0°7'28.7"W`
+static DEGREES_MINUTES_SECONDS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^(\d{1,2})°\s*(\d{1,2}(?:\.\d+)?)['′]\s*(?:(\d{1,2}(?:\.\d+)?)["″]\s*)?([NS])\s*,?\s*(\d{1,3})°\s*(\d{1,2}(?:\.\d+)?)['′]\s*(?:(\d{1,2}(?:\.\d+)?)["″]\s*)?([EW])$"#,
+    )
+    .expect("the degrees, minutes and seconds regex is valid")
+});
+
+/// Checks if the input is a latitude and longitude, as the Coordinates
+/// decoder outputs and as found in puzzles and geocaches
+pub struct CoordinatesChecker;
+
+impl Check for Checker<CoordinatesChecker> {
+    fn new() -> Self {
+        Checker {
+            name: "Coordinates Checker",
+            description: "Checks if the input is a latitude and longitude, in decimal degrees or in degrees, minutes and seconds",
+            link: "https://en.wikipedia.org/wiki/Geographic_coordinate_system",
+            tags: vec!["coordinates", "gps", "geocaching"],
+            expected_runtime: 0.01,
+            popularity: 1.0,
+            lemmeknow_config: Identifier::default(),
+            sensitivity: Sensitivity::Medium,
+            enhanced_detector: None,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn check(&self, text: &str, _config: &Config) -> CheckResult {
+        CheckResult {
+            is_identified: is_coordinates(text),
+            text: text.to_string(),
+            checker_name: self.name,
+            checker_description: self.description,
+            description: "Latitude and longitude".to_string(),
+            link: self.link,
+        }
+    }
+
+    fn with_sensitivity(mut self, sensitivity: Sensitivity) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    fn get_sensitivity(&self) -> Sensitivity {
+        self.sensitivity
+    }
+}
+
+/// Whether all of `text` is a latitude and longitude on Earth
+pub fn is_coordinates(text: &str) -> bool {
+    let text = text.trim();
+    if let Some(captures) = DECIMAL_DEGREES.captures(text) {
+        // A sign and a hemisphere would say the same thing twice
+        let signed_and_lettered = (!captures[1].is_empty() && !captures[3].is_empty())
+            || (!captures[4].is_empty() && !captures[6].is_empty());
+        let latitude: f64 = captures[2].parse().unwrap_or(f64::INFINITY);
+        let longitude: f64 = captures[5].parse().unwrap_or(f64::INFINITY);
+        return !signed_and_lettered && latitude <= 90.0 && longitude <= 180.0;
+    }
+    if let Some(captures) = DEGREES_MINUTES_SECONDS.captures(text) {
+        let part = |index: usize| {
+            captures
+                .get(index)
+                .map_or(Some(0.0), |part| part.as_str().parse::<f64>().ok())
+        };
+        let (Some(lat_degrees), Some(lat_minutes), Some(lat_seconds)) = (part(1), part(2), part(3))
+        else {
+            return false;
+        };
+        let (Some(lon_degrees), Some(lon_minutes), Some(lon_seconds)) = (part(5), part(6), part(7))
+        else {
+            return false;
+        };
+        let latitude = lat_degrees + lat_minutes / 60.0 + lat_seconds / 3600.0;
+        let longitude = lon_degrees + lon_minutes / 60.0 + lon_seconds / 3600.0;
+        return lat_minutes < 60.0
+            && lat_seconds < 60.0
+            && lon_minutes < 60.0
+            && lon_seconds < 60.0
+            && latitude <= 90.0
+            && longitude <= 180.0;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinates_in_decimal_degrees() {
+        let checker = Checker::<CoordinatesChecker>::new();
+        let config = Config::default();
+        assert!(checker.check("51.50073, -0.12463", &config).is_identified);
+        assert!(
+            checker
+                .check("38.8977° N, 77.0365° W", &config)
+                .is_identified
+        );
+        assert!(!checker.check("1.5, 2.5", &config).is_identified);
+        assert!(!checker.check("95.00000, 10.00000", &config).is_identified);
+        assert!(!checker.check("-38.8977 N, 77.0365", &config).is_identified);
+    }
+
+    #[test]
+    fn coordinates_in_degrees_minutes_seconds() {
+        assert!(is_coordinates("51°30'2.6\"N 0°7'28.7\"W"));
+        assert!(is_coordinates("40° 41.405' N, 74° 2.668' W"));
+        assert!(!is_coordinates("51°75'N 0°7'W"));
+        assert!(!is_coordinates("the treasure is at 51°30'N 0°7'W"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Checkers added at runtime by library users
+//!
+//! Athena only knows what English, passwords and LemmeKnow's patterns look like.
+//! Applications often have their own idea of plaintext, such as "a valid license
+//! key for my product", so they can register checkers which Athena and WaitAthena
+//! run right after the wordlist checker.
+
+use super::checker_result::CheckResult;
+use super::checker_type::{Check, CheckInfo};
+use super::CHECKER_MAP;
+use crate::config::Config;
+use gibberish_or_not::Sensitivity;
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+
+/// A checker which can be registered with [`register_checker`].
+/// Implemented for every `Check + CheckInfo` type that can be shared between threads.
+pub trait CustomCheck: Check + CheckInfo + Send + Sync {}
+
+impl<T: Check + CheckInfo + Send + Sync> CustomCheck for T {}
+
+/// A checker shared by every search, see [`register_checker`]
+type SharedChecker = &'static dyn CustomCheck;
+
+/// Checkers added at runtime
+static CUSTOM_CHECKERS: Lazy<RwLock<Vec<SharedChecker>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Returns every registered checker, in the order they were registered
+pub fn custom_checkers() -> Vec<SharedChecker> {
+    match CUSTOM_CHECKERS.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Adds a checker which Athena runs on every candidate plaintext from now on.
+/// Registered checkers are kept for the rest of the process.
+///
+/// # Errors
+/// Returns an error if a checker with the same name already exists.
+pub fn register_checker(checker: Box<dyn CustomCheck>) -> Result<(), String> {
+    let mut custom = match CUSTOM_CHECKERS.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let name = checker.get_name();
+    if CHECKER_MAP.contains_key(name) || custom.iter().any(|c| c.get_name() == name) {
+        return Err(format!("A checker named '{}' already exists", name));
+    }
+    // Search results refer to their checker for as long as they live
+    custom.push(Box::leak(checker));
+    Ok(())
+}
+
+/// Registers a closure as a checker. It gets each candidate plaintext and
+/// returns true if it is the plaintext.
+///
+/// ```rust
+/// use ares::checkers::athena::Athena;
+/// use ares::checkers::checker_type::{Check, Checker};
+/// use ares::config::Config;
+///
+/// ares::register_checker_fn("Order number", "An order number from our shop", |text| {
+///     text.len() == 12 && text.starts_with("ORD-") && text[4..].chars().all(|c| c.is_ascii_digit())
+/// })
+/// .unwrap();
+///
+/// let config = Config { human_checker_on: false, ..Default::default() };
+/// let result = Checker::<Athena>::new().check("ORD-20240117", &config);
+/// assert!(result.is_identified);
+/// assert_eq!(result.checker_name, "Order number");
+/// ```
+///
+/// # Errors
+/// Returns an error if a checker with the same name already exists.
+pub fn register_checker_fn(
+    name: &'static str,
+    description: &'static str,
+    is_plaintext: impl Fn(&str) -> bool + Send + Sync + 'static,
+) -> Result<(), String> {
+    register_checker(Box::new(FnChecker {
+        name,
+        description,
+        tags: vec!["custom"],
+        is_plaintext: Box::new(is_plaintext),
+    }))
+}
+
+/// A checker backed by a closure, see [`register_checker_fn`]
+pub struct FnChecker {
+    /// Name of the checker
+    name: &'static str,
+    /// Description of what it accepts
+    description: &'static str,
+    /// Tags of the checker
+    tags: Vec<&'static str>,
+    /// Returns true if the text is plaintext
+    is_plaintext: Box<dyn Fn(&str) -> bool + Send + Sync>,
+}
+
+impl Check for FnChecker {
+    /// A checker which accepts nothing
+    fn new() -> Self {
+        FnChecker {
+            name: "Custom checker",
+            description: "Accepts nothing",
+            tags: vec!["custom"],
+            is_plaintext: Box::new(|_| false),
+        }
+    }
+
+    fn check(&self, text: &str, _config: &Config) -> CheckResult {
+        CheckResult {
+            is_identified: (self.is_plaintext)(text),
+            text: text.to_string(),
+            description: self.description.to_string(),
+            checker_name: self.name,
+            checker_description: self.description,
+            link: "",
+        }
+    }
+
+    /// Closures decide for themselves, so sensitivity is ignored
+    fn with_sensitivity(self, _sensitivity: Sensitivity) -> Self {
+        self
+    }
+
+    fn get_sensitivity(&self) -> Sensitivity {
+        Sensitivity::Low
+    }
+}
+
+impl CheckInfo for FnChecker {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_popularity(&self) -> f32 {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::athena::Athena;
+    use crate::checkers::checker_type::Checker;
+    use crate::checkers::find_checker_info;
+
+    /// Our pretend product's license keys: `KEY-` then digits summing to a multiple of 7
+    fn is_license_key(text: &str) -> bool {
+        text.strip_prefix("KEY-").is_some_and(|digits| {
+            !digits.is_empty()
+                && digits.chars().all(|c| c.is_ascii_digit())
+                && digits.bytes().map(|b| u32::from(b - b'0')).sum::<u32>() % 7 == 0
+        })
+    }
+
+    #[test]
+    fn athena_runs_registered_checkers() {
+        register_checker_fn("Test license key", "A license key", is_license_key).unwrap();
+        assert!(register_checker_fn("Test license key", "Again", |_| true).is_err());
+        assert!(register_checker_fn("English Checker", "Clash", |_| true).is_err());
+        assert!(find_checker_info("Test license key").is_some());
+
+        let config = Config {
+            human_checker_on: false,
+            ..Default::default()
+        };
+        let athena = Checker::<Athena>::new();
+        let result = athena.check("KEY-0700", &config);
+        assert!(result.is_identified);
+        assert_eq!(result.checker_name, "Test license key");
+        assert_ne!(
+            athena.check("KEY-0701", &config).checker_name,
+            "Test license key"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::Config;
 use gibberish_or_not::Sensitivity;
-use lemmeknow::Identifier;
+use crate::checkers::Identifier;
 
 use super::{
     checker_result::CheckResult,
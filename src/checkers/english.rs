@@ -1,7 +1,7 @@
 use crate::checkers::checker_result::CheckResult;
 use crate::cryptanalysis::{fitness_score, word_score, index_of_coincidence};
 use gibberish_or_not::{is_gibberish, Sensitivity};
-use lemmeknow::Identifier;
+use crate::checkers::Identifier;
 use log::trace;
 
 use crate::checkers::checker_type::{Check, Checker};
@@ -29,6 +29,18 @@ impl Check for Checker<EnglishChecker> {
     }
 
     fn check(&self, text: &str, config: &Config) -> CheckResult {
+        // Text with a flag prefix from `ares dict add --prefix` is taken as is
+        if let Some(prefix) = dictionary_prefix_in(text, config) {
+            return CheckResult {
+                is_identified: true,
+                text: text.to_string(),
+                checker_name: self.name,
+                checker_description: self.description,
+                description: format!("Contains the prefix {prefix}"),
+                link: self.link,
+            };
+        }
+
         // Normalize before checking
         let normalized = normalise_string(text);
 
@@ -115,6 +127,16 @@ fn normalise_string(input: &str) -> String {
         .collect()
 }
 
+/// The first of `config.dictionary_prefixes` in the text, ignoring case
+fn dictionary_prefix_in<'a>(text: &str, config: &'a Config) -> Option<&'a str> {
+    let lowercase = text.to_lowercase();
+    config
+        .dictionary_prefixes
+        .iter()
+        .find(|prefix| lowercase.contains(&prefix.to_lowercase()))
+        .map(String::as_str)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::checkers::english::normalise_string;
@@ -150,6 +172,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn text_with_a_dictionary_prefix_is_accepted() {
+        let checker = Checker::<EnglishChecker>::new();
+        let config = crate::config::Config {
+            dictionary_prefixes: vec!["kctf{".to_string()],
+            ..Default::default()
+        };
+        assert!(checker.check("KCTF{x7q_zz9}", &config).is_identified);
+        assert!(!checker.check("x7q_zz9", &config).is_identified);
+    }
+
     #[test]
     fn test_check_non_dictionary_word() {
         let checker = Checker::<EnglishChecker>::new();
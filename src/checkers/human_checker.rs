@@ -1,70 +1,307 @@
 use crate::checkers::checker_result::CheckResult;
+#[cfg(feature = "cli")]
 use crate::cli_pretty_printing::human_checker_check;
+#[cfg(feature = "cli")]
+use crate::cli_pretty_printing::i18n::is_yes;
+#[cfg(feature = "cache")]
+use crate::config::CacheMode;
 use crate::config::Config;
+#[cfg(feature = "cache")]
 use crate::storage::database;
 use crate::timer;
-use dashmap::DashSet;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::OnceLock;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+#[cfg(feature = "cli")]
 use text_io::read;
 
-/// Tracks which prompts have already been shown to the user to avoid duplicates
-static SEEN_PROMPTS: OnceLock<DashSet<String>> = OnceLock::new();
-// if human checker is called, we set this to true
-// so we dont call it again
-/// Tracks if a human has already confirmed a result to skip future checks
-static HUMAN_CONFIRMED: AtomicBool = AtomicBool::new(false);
+/// What the human checker was told during one crack, so nobody is asked
+/// about the same text twice, or about anything once they have accepted a
+/// plaintext. Each crack starts its own, see [`Config::human_answers`].
+/// Clones share the same answers.
+#[derive(Debug, Clone, Default)]
+pub struct HumanAnswers {
+    /// The answers, shared by every clone
+    inner: Arc<Answers>,
+}
+
+/// The shared state of a [`HumanAnswers`]
+#[derive(Debug, Default)]
+struct Answers {
+    /// Whether a human accepted a plaintext, after which every check passes
+    confirmed: AtomicBool,
+    /// The answer to each prompt. Locked while asking, so prompts from
+    /// different threads are asked one at a time.
+    prompts: Mutex<HashMap<String, bool>>,
+}
+
+impl HumanAnswers {
+    /// Whether a human accepted a plaintext during this crack
+    pub fn confirmed(&self) -> bool {
+        self.inner.confirmed.load(Ordering::Acquire)
+    }
+
+    /// The answer to `prompt`, from `ask` unless it was answered before
+    fn answer(&self, prompt: String, ask: impl FnOnce() -> bool) -> bool {
+        let mut prompts = self
+            .inner
+            .prompts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if self.confirmed() {
+            return true;
+        }
+        if let Some(answer) = prompts.get(&prompt) {
+            return *answer;
+        }
+        let answer = ask();
+        prompts.insert(prompt, answer);
+        if answer {
+            self.inner.confirmed.store(true, Ordering::Release);
+        }
+        answer
+    }
+}
+
+/// Decides whether a possible plaintext is the real one, in place of a
+/// human at the terminal. Library users install one with
+/// [`set_human_verifier`] to ask in a GUI dialog or web page, or to approve
+/// results by some policy, instead of Ares reading from stdin.
+///
+/// Closures taking the [`CheckResult`] are verifiers too.
+///
+/// ```
+/// use ares::checkers::athena::Athena;
+/// use ares::checkers::checker_result::CheckResult;
+/// use ares::checkers::checker_type::{Check, Checker};
+/// use ares::checkers::human_checker::set_human_verifier;
+/// use ares::config::Config;
+///
+/// // Only accept plaintexts without digits
+/// set_human_verifier(Some(Box::new(|check: &CheckResult| {
+///     !check.text.chars().any(|c| c.is_ascii_digit())
+/// })));
+///
+/// let config = Config { human_checker_on: true, api_mode: true, ..Default::default() };
+/// let result = Checker::<Athena>::new().check("192.168.0.1", &config);
+/// assert!(!result.is_identified);
+/// ```
+pub trait HumanVerifier: Send + Sync {
+    /// Returns true if `check` is the plaintext
+    fn verify(&self, check: &CheckResult, config: &Config) -> bool;
+}
+
+impl<F: Fn(&CheckResult) -> bool + Send + Sync> HumanVerifier for F {
+    fn verify(&self, check: &CheckResult, _config: &Config) -> bool {
+        self(check)
+    }
+}
+
+/// The default verifier: prints the possible plaintext and reads y/N from
+/// stdin. Only with the cli feature, without it nobody is asked unless a
+/// verifier is installed.
+#[cfg(feature = "cli")]
+pub struct StdinVerifier;
+
+#[cfg(feature = "cli")]
+impl HumanVerifier for StdinVerifier {
+    fn verify(&self, check: &CheckResult, config: &Config) -> bool {
+        human_checker_check(&check.description, &check.text, config);
+
+        let reply: String = read!("{}\n");
+        log::debug!("Human checker received reply: '{}'", reply);
+        is_yes(config, &reply)
+    }
+}
+
+/// A verifier which accepts every plaintext, for unattended runs which still
+/// want [`Config::human_checker_on`] semantics
+pub struct AutoApprove;
+
+impl HumanVerifier for AutoApprove {
+    fn verify(&self, _check: &CheckResult, _config: &Config) -> bool {
+        true
+    }
+}
+
+/// Asks the user whether a possible plaintext is real, returning their answer
+pub type PromptHandler = Box<dyn Fn(&CheckResult) -> bool + Send + Sync>;
+
+/// Replaces `StdinVerifier` when set
+static VERIFIER: RwLock<Option<Box<dyn HumanVerifier>>> = RwLock::new(None);
+
+/// Installs a verifier which decides instead of the stdin prompt.
+/// Pass None to go back to the stdin prompt.
+///
+/// A verifier is used even in API mode, since whoever installed it is
+/// deciding for the user themselves.
+pub fn set_human_verifier(verifier: Option<Box<dyn HumanVerifier>>) {
+    if let Ok(mut current) = VERIFIER.write() {
+        *current = verifier;
+    }
+}
 
-/// Gets the set of seen prompts, initializing it if necessary
-fn get_seen_prompts() -> &'static DashSet<String> {
-    SEEN_PROMPTS.get_or_init(DashSet::new)
+/// Installs a closure which asks the user instead of the stdin prompt, like
+/// [`set_human_verifier`]. Pass None to go back to the stdin prompt.
+pub fn set_prompt_handler(handler: Option<PromptHandler>) {
+    set_human_verifier(handler.map(|handler| Box::new(handler) as Box<dyn HumanVerifier>));
 }
 
 /// The Human Checker asks humans if the expected plaintext is real plaintext
 /// We can use all the automated checkers in the world, but sometimes they get false positives
-/// Humans have the last say.
-/// TODO: Add a way to specify a list of checkers to use in the library. This checker is not library friendly!
-// compile this if we are not running tests
+/// Humans have the last say, through the verifier installed with
+/// [`set_human_verifier`] or else `StdinVerifier` with the cli feature.
+/// Their answers are kept in [`Config::human_answers`] for the rest of the
+/// crack.
 pub fn human_checker(input: &CheckResult, config: &Config) -> bool {
     // Check if a human has already confirmed a result
-    if HUMAN_CONFIRMED.load(Ordering::Acquire) {
+    if config.human_answers.as_ref().is_some_and(HumanAnswers::confirmed) {
         return true;
     }
-    timer::pause();
-    // We still call human checker, just if config is false we return True
-    if !config.human_checker_on || config.api_mode {
-        timer::resume();
+    pause_clock(config);
+    let verifier = VERIFIER.read().ok();
+    let verifier = verifier.as_ref().and_then(|verifier| verifier.as_deref());
+    // We still call human checker, just if config is false we return True.
+    // Without the cli feature there is no prompt to fall back on either.
+    let can_prompt = !config.api_mode && cfg!(feature = "cli");
+    if !config.human_checker_on || (!can_prompt && verifier.is_none()) {
+        resume_clock(config);
         return true;
     }
 
-    // Check if we've already prompted for this text
-    let prompt_key = format!("{}{}", input.description, input.text);
-    if !get_seen_prompts().insert(prompt_key) {
-        return true; // Return true to allow the search to continue
+    // A human already said no to this text from this checker in an earlier run
+    #[cfg(feature = "cache")]
+    if previously_rejected(input, config) {
+        log::debug!("Human rejected \"{}\" before, skipping it", input.text);
+        resume_clock(config);
+        return false;
     }
-    human_checker_check(&input.description, &input.text, config);
 
-    let reply: String = read!("{}\n");
-    log::debug!("Human checker received reply: '{}'", reply);
-    let result = reply.to_ascii_lowercase().starts_with('y');
-    // If the user confirmed, set the atomic boolean to true
+    let ask = || {
+        let accepted = match verifier {
+            Some(verifier) => verifier.verify(input, config),
+            #[cfg(feature = "cli")]
+            None => StdinVerifier.verify(input, config),
+            // Returned early above, as there is no prompt
+            #[cfg(not(feature = "cli"))]
+            None => true,
+        };
+        if !accepted {
+            #[cfg(feature = "cache")]
+            record_rejection(input, config);
+        }
+        accepted
+    };
+    // A text already asked about this crack gets the same answer
+    let prompt_key = format!("{}{}", input.description, input.text);
+    let result = match &config.human_answers {
+        Some(answers) => answers.answer(prompt_key, ask),
+        // Outside of a crack there is nothing to remember the answer for
+        None => ask(),
+    };
     if result {
-        HUMAN_CONFIRMED.store(true, Ordering::Release);
         log::debug!("Human confirmed a result, future checks will be skipped");
     }
-    timer::resume();
+    resume_clock(config);
 
     log::debug!("Human checker returning: {}", result);
+    result
+}
 
-    if !result {
-        let fd_result = database::insert_human_rejection(uuid::Uuid::new_v4(), &input.text, input);
-        match fd_result {
-            Ok(_) => (),
-            Err(e) => {
-                log::warn!("Failed to write human checker rejection due to error: {}", e);
-            }
+/// Keeps the human's rejection of `input`, so later runs skip it, see
+/// [`previously_rejected`]
+#[cfg(feature = "cache")]
+fn record_rejection(input: &CheckResult, config: &Config) {
+    if config.cache_mode != CacheMode::ReadWrite {
+        return;
+    }
+    let fd_result = database::insert_human_feedback(
+        uuid::Uuid::new_v4(),
+        &input.text,
+        input,
+        &crate::filtration_system::current_decoder(),
+        &text_features(&input.text),
+    );
+    match fd_result {
+        Ok(_) => (),
+        Err(e) => {
+            log::warn!("Failed to write human checker rejection due to error: {}", e);
         }
+    }
+}
+
+/// Stops the clocks while the user is asked, so the time they take to
+/// answer isn't taken from the search
+fn pause_clock(config: &Config) {
+    timer::pause();
+    if let Some(deadline) = &config.deadline {
+        deadline.pause();
+    }
+}
+
+/// Starts the clocks stopped by [`pause_clock`] again
+fn resume_clock(config: &Config) {
+    timer::resume();
+    if let Some(deadline) = &config.deadline {
+        deadline.resume();
+    }
+}
+
+/// Returns true if the human rejected this text from this checker in an earlier run
+#[cfg(feature = "cache")]
+fn previously_rejected(input: &CheckResult, config: &Config) -> bool {
+    if config.cache_mode == CacheMode::Off {
         return false;
     }
-    true
+    database::was_rejected(&input.text, input.checker_name).unwrap_or_else(|e| {
+        log::warn!("Could not read earlier human rejections: {}", e);
+        false
+    })
+}
+
+/// A summary of a rejected text, stored with the rejection so the kinds of
+/// text checkers wrongly flag can be studied later
+#[cfg(feature = "cache")]
+fn text_features(text: &str) -> serde_json::Value {
+    let length = text.chars().count();
+    let printable = text
+        .chars()
+        .filter(|c| !c.is_control() || c.is_whitespace())
+        .count();
+    serde_json::json!({
+        "length": length,
+        "printable_ratio": printable as f64 / length.max(1) as f64,
+        "fitness": crate::cryptanalysis::fitness_score(text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn answers_are_kept_for_one_crack() {
+        let asked = Cell::new(0);
+        let ask = |answer: bool| {
+            let asked = &asked;
+            move || {
+                asked.set(asked.get() + 1);
+                answer
+            }
+        };
+        let answers = HumanAnswers::default();
+        assert!(!answers.answer("first".to_string(), ask(false)));
+        // Asked about again, it keeps its answer
+        assert!(!answers.answer("first".to_string(), ask(true)));
+        assert!(answers.answer("second".to_string(), ask(true)));
+        // Once one is accepted nothing else is asked about
+        assert!(answers.answer("third".to_string(), ask(false)));
+        assert_eq!(asked.get(), 2);
+
+        // The next crack asks again
+        let next = HumanAnswers::default();
+        assert!(!next.answer("third".to_string(), ask(false)));
+        assert_eq!(asked.get(), 3);
+    }
 }
@@ -2,7 +2,7 @@ use super::checker_type::{Check, Checker};
 use crate::checkers::checker_result::CheckResult;
 use crate::config::Config;
 use gibberish_or_not::Sensitivity;
-use lemmeknow::{Data, Identifier};
+use crate::checkers::{Data, Identifier};
 
 /// The LemmeKnow Checker checks if the text matches a known Regex pattern.
 /// This is the struct for it.
@@ -0,0 +1,53 @@
+//! A stand-in for lemmeknow in the browser
+//!
+//! lemmeknow 0.8 doesn't build for wasm32, as the `identify` it has there
+//! doesn't type check. This has the parts of its API Ares uses and identifies
+//! nothing, so in the browser the LemmeKnow checker never matches and the
+//! other checkers do the work.
+
+/// What `lemmeknow::Identifier` is configured with, kept so the config
+/// builds the same on every target
+#[derive(Debug, Default)]
+pub struct Identifier {
+    /// Keep patterns with at least this rarity
+    pub min_rarity: f32,
+    /// Keep patterns with at most this rarity
+    pub max_rarity: f32,
+    /// Only use patterns with one of these tags
+    pub tags: Vec<String>,
+    /// Don't use patterns with any of these tags
+    pub exclude_tags: Vec<String>,
+    /// Match anywhere in the text rather than the whole of it
+    pub boundaryless: bool,
+    /// Scan the file the text names
+    pub file_support: bool,
+}
+
+impl Identifier {
+    /// Sets the minimum rarity
+    pub fn min_rarity(mut self, rarity: f32) -> Self {
+        self.min_rarity = rarity;
+        self
+    }
+
+    /// Finds nothing, as there are no patterns to match
+    pub fn identify(&self, _text: &str) -> Vec<Match> {
+        Vec::new()
+    }
+}
+
+/// A pattern the text matched
+#[derive(Debug)]
+pub struct Match {
+    /// The text which matched
+    pub text: String,
+    /// The pattern it matched
+    pub data: Data,
+}
+
+/// A pattern, such as an IP address or a URL
+#[derive(Debug)]
+pub struct Data {
+    /// What the pattern is called
+    pub name: &'static str,
+}
@@ -2,6 +2,7 @@ use self::{
     athena::Athena,
     checker_result::CheckResult,
     checker_type::{Check, CheckInfo, Checker},
+    coordinates::CoordinatesChecker,
     english::EnglishChecker,
     lemmeknow_checker::LemmeKnow,
     password::PasswordChecker,
@@ -11,6 +12,12 @@ use self::{
 };
 
 use crate::config::Config;
+use crate::searchers::stats;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use lemmeknow::{Data, Identifier};
+#[cfg(target_arch = "wasm32")]
+pub(crate) use lemmeknow_wasm::{Data, Identifier};
+use crate::timer::Instant;
 use gibberish_or_not::Sensitivity;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
@@ -21,6 +28,10 @@ pub mod athena;
 pub mod checker_result;
 /// This is the base checker that all other checkers inherit from.
 pub mod checker_type;
+/// The Coordinates Checker checks if the text is a latitude and longitude
+pub mod coordinates;
+/// Checkers registered at runtime by library users
+pub mod custom;
 /// The default checker we use which simply calls all other checkers in order.
 pub mod default_checker;
 /// The English Checker is a checker that checks if the input is English
@@ -29,6 +40,9 @@ pub mod english;
 pub mod human_checker;
 /// The LemmeKnow Checker checks if the text matches a known Regex pattern.
 pub mod lemmeknow_checker;
+/// Stands in for lemmeknow, which doesn't build for wasm32
+#[cfg(target_arch = "wasm32")]
+mod lemmeknow_wasm;
 /// The Password checker checks if the text matches a known common password
 pub mod password;
 /// The Regex checker checks to see if the intended text matches the plaintext
@@ -54,12 +68,22 @@ pub enum CheckerTypes {
     CheckPassword(Checker<PasswordChecker>),
     /// Wrapper for Wordlist Checker
     CheckWordlist(Checker<WordlistChecker>),
+    /// Wrapper for Coordinates Checker
+    CheckCoordinates(Checker<CoordinatesChecker>),
 }
 
 impl CheckerTypes {
     /// This functions calls appropriate check function of Checker
     pub fn check(&self, text: &str, config: &Config) -> CheckResult {
-        match self {
+        let span = tracing::trace_span!(
+            "checker",
+            checker = self.name(),
+            duration_us = tracing::field::Empty
+        )
+        .entered();
+        stats::checker_called(config);
+        let start = Instant::now();
+        let result = match self {
             CheckerTypes::CheckLemmeKnow(lemmeknow_checker) => lemmeknow_checker.check(text, config),
             CheckerTypes::CheckEnglish(english_checker) => english_checker.check(text, config),
             CheckerTypes::CheckAthena(athena_checker) => athena_checker.check(text, config),
@@ -67,6 +91,25 @@ impl CheckerTypes {
             CheckerTypes::CheckRegex(regex_checker) => regex_checker.check(text, config),
             CheckerTypes::CheckPassword(password_checker) => password_checker.check(text, config),
             CheckerTypes::CheckWordlist(wordlist_checker) => wordlist_checker.check(text, config),
+            CheckerTypes::CheckCoordinates(coordinates_checker) => {
+                coordinates_checker.check(text, config)
+            }
+        };
+        span.record("duration_us", start.elapsed().as_micros() as u64);
+        result
+    }
+
+    /// The name of the checker, e.g. "Athena Checker"
+    pub fn name(&self) -> &'static str {
+        match self {
+            CheckerTypes::CheckLemmeKnow(checker) => checker.name,
+            CheckerTypes::CheckEnglish(checker) => checker.name,
+            CheckerTypes::CheckAthena(checker) => checker.name,
+            CheckerTypes::CheckWaitAthena(checker) => checker.name,
+            CheckerTypes::CheckRegex(checker) => checker.name,
+            CheckerTypes::CheckPassword(checker) => checker.name,
+            CheckerTypes::CheckWordlist(checker) => checker.name,
+            CheckerTypes::CheckCoordinates(checker) => checker.name,
         }
     }
 
@@ -108,6 +151,11 @@ impl CheckerTypes {
                 new_checker.sensitivity = sensitivity;
                 CheckerTypes::CheckWordlist(new_checker)
             }
+            CheckerTypes::CheckCoordinates(_checker) => {
+                let mut new_checker = Checker::<CoordinatesChecker>::new();
+                new_checker.sensitivity = sensitivity;
+                CheckerTypes::CheckCoordinates(new_checker)
+            }
         }
     }
 
@@ -121,6 +169,7 @@ impl CheckerTypes {
             CheckerTypes::CheckRegex(checker) => checker.get_sensitivity(),
             CheckerTypes::CheckPassword(checker) => checker.get_sensitivity(),
             CheckerTypes::CheckWordlist(checker) => checker.get_sensitivity(),
+            CheckerTypes::CheckCoordinates(checker) => checker.get_sensitivity(),
         }
     }
 }
@@ -150,6 +199,10 @@ impl CheckerBox {
 pub static CHECKER_MAP: Lazy<HashMap<&str, CheckerBox>> = Lazy::new(|| {
     HashMap::from([
         ("Athena Checker", CheckerBox::new(Checker::<Athena>::new())),
+        (
+            "Coordinates Checker",
+            CheckerBox::new(Checker::<CoordinatesChecker>::new()),
+        ),
         (
             "English Checker",
             CheckerBox::new(Checker::<EnglishChecker>::new()),
@@ -181,6 +234,26 @@ pub static CHECKER_MAP: Lazy<HashMap<&str, CheckerBox>> = Lazy::new(|| {
     ])
 });
 
+/// Returns every checker: the built in ones followed by any registered at runtime
+pub fn all_checker_infos() -> Vec<&'static (dyn CheckInfo + Sync + Send)> {
+    CHECKER_MAP
+        .values()
+        .map(|checker_box| checker_box.get())
+        .chain(
+            custom::custom_checkers()
+                .into_iter()
+                .map(|checker| checker as &'static (dyn CheckInfo + Sync + Send)),
+        )
+        .collect()
+}
+
+/// Looks up a built in or registered checker by its exact name
+pub fn find_checker_info(name: &str) -> Option<&'static (dyn CheckInfo + Sync + Send)> {
+    all_checker_infos()
+        .into_iter()
+        .find(|checker| checker.get_name() == name)
+}
+
 // test
 #[cfg(test)]
 mod tests {
@@ -1,7 +1,7 @@
 use crate::checkers::checker_result::CheckResult;
 use crate::config::Config;
 use gibberish_or_not::{is_password, Sensitivity};
-use lemmeknow::Identifier;
+use crate::checkers::Identifier;
 
 use crate::checkers::checker_type::{Check, Checker};
 
@@ -1,5 +1,5 @@
 use gibberish_or_not::Sensitivity;
-use lemmeknow::Identifier;
+use crate::checkers::Identifier;
 
 use super::checker_type::{Check, Checker};
 use crate::checkers::checker_result::CheckResult;
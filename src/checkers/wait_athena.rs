@@ -4,13 +4,15 @@
 /// Unlike Athena, WaitAthena does not use the human checker and automatically accepts all potential plaintexts.
 use crate::{checkers::checker_result::CheckResult, config::Config};
 use gibberish_or_not::Sensitivity;
-use lemmeknow::Identifier;
+use crate::checkers::Identifier;
 use log::trace;
 
 use crate::storage::wait_athena_storage;
 
 use super::{
     checker_type::{Check, Checker},
+    coordinates::CoordinatesChecker,
+    custom::custom_checkers,
     english::EnglishChecker,
     lemmeknow_checker::LemmeKnow,
     password::PasswordChecker,
@@ -55,6 +57,7 @@ impl Check for Checker<WaitAthena> {
 
                 // Store the result instead of returning immediately
                 wait_athena_storage::add_plaintext_result(
+                    config,
                     check_res.text.clone(),
                     check_res.description.clone(),
                     regex_checker.name.to_string(),
@@ -65,8 +68,8 @@ impl Check for Checker<WaitAthena> {
                 return check_res;
             }
         } else {
-            // Run wordlist checker first if a wordlist is provided
-            if config.wordlist.is_some() {
+            // Run wordlist checker first if a wordlist or dictionary words are provided
+            if config.wordlist.is_some() || !config.dictionary_words.is_empty() {
                 trace!("running wordlist checker");
                 let wordlist_checker =
                     Checker::<WordlistChecker>::new().with_sensitivity(self.sensitivity);
@@ -79,6 +82,7 @@ impl Check for Checker<WaitAthena> {
 
                     // Store the result instead of returning immediately
                     wait_athena_storage::add_plaintext_result(
+                        config,
                         check_res.text.clone(),
                         check_res.description.clone(),
                         wordlist_checker.name.to_string(),
@@ -92,6 +96,25 @@ impl Check for Checker<WaitAthena> {
 
             // In Ciphey if the user uses the regex checker all the other checkers turn off
             // This is because they are looking for one specific bit of information so will not want the other checkers
+
+            // Checkers registered by library users go first, as in Athena
+            for custom_checker in custom_checkers() {
+                let custom_result = custom_checker.check(text, config);
+                if custom_result.is_identified {
+                    // Store the result instead of returning immediately
+                    wait_athena_storage::add_plaintext_result(
+                        config,
+                        custom_result.text.clone(),
+                        custom_result.description.clone(),
+                        custom_result.checker_name.to_string(),
+                        "CustomChecker".to_string(),
+                    );
+
+                    // Continue checking by returning the result
+                    return custom_result;
+                }
+            }
+
             let lemmeknow = Checker::<LemmeKnow>::new().with_sensitivity(self.sensitivity);
             let lemmeknow_result = lemmeknow.check(text, config);
             if lemmeknow_result.is_identified {
@@ -102,6 +125,7 @@ impl Check for Checker<WaitAthena> {
 
                 // Store the result instead of returning immediately
                 wait_athena_storage::add_plaintext_result(
+                    config,
                     check_res.text.clone(),
                     check_res.description.clone(),
                     lemmeknow.name.to_string(),
@@ -122,6 +146,7 @@ impl Check for Checker<WaitAthena> {
 
                 // Store the result instead of returning immediately
                 wait_athena_storage::add_plaintext_result(
+                    config,
                     check_res.text.clone(),
                     check_res.description.clone(),
                     password.name.to_string(),
@@ -132,6 +157,28 @@ impl Check for Checker<WaitAthena> {
                 return check_res;
             }
 
+            let coordinates =
+                Checker::<CoordinatesChecker>::new().with_sensitivity(self.sensitivity);
+            let coordinates_result = coordinates.check(text, config);
+            if coordinates_result.is_identified {
+                let mut check_res = CheckResult::new(&coordinates);
+                check_res.is_identified = true; // No human checker involvement
+                check_res.text = coordinates_result.text;
+                check_res.description = coordinates_result.description;
+
+                // Store the result instead of returning immediately
+                wait_athena_storage::add_plaintext_result(
+                    config,
+                    check_res.text.clone(),
+                    check_res.description.clone(),
+                    coordinates.name.to_string(),
+                    "CoordinatesChecker".to_string(),
+                );
+
+                // Continue checking by returning the result
+                return check_res;
+            }
+
             let english = Checker::<EnglishChecker>::new().with_sensitivity(self.sensitivity);
             let english_result = english.check(text, config);
             if english_result.is_identified {
@@ -142,6 +189,7 @@ impl Check for Checker<WaitAthena> {
 
                 // Store the result instead of returning immediately
                 wait_athena_storage::add_plaintext_result(
+                    config,
                     check_res.text.clone(),
                     check_res.description.clone(),
                     english.name.to_string(),
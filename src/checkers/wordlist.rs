@@ -2,7 +2,7 @@ use crate::checkers::checker_result::CheckResult;
 use crate::checkers::checker_type::{Check, Checker};
 use crate::config::Config;
 use gibberish_or_not::Sensitivity;
-use lemmeknow::Identifier;
+use crate::checkers::Identifier;
 use log::trace;
 #[cfg(test)]
 use std::collections::HashSet;
@@ -29,11 +29,15 @@ impl Check for Checker<WordlistChecker> {
 
     fn check(&self, text: &str, config: &Config) -> CheckResult {
         // Only run this checker if a wordlist is provided
-        if let Some(wordlist) = &config.wordlist {
-            trace!("Running wordlist checker with {} entries", wordlist.len());
+        if config.wordlist.is_some() || !config.dictionary_words.is_empty() {
+            trace!("Running wordlist checker");
 
-            // Perform exact matching against the wordlist
-            let is_match = wordlist.contains(text);
+            // Perform exact matching against the wordlist and the words added with `ares dict add`
+            let is_match = config
+                .wordlist
+                .as_ref()
+                .is_some_and(|wordlist| wordlist.contains(text))
+                || config.dictionary_words.iter().any(|word| word == text);
 
             if is_match {
                 trace!("Found exact match in wordlist for: {}", text);
@@ -145,4 +149,15 @@ mod tests {
         );
         assert!(!result.is_identified);
     }
+
+    #[test]
+    fn dictionary_words_are_matched_without_a_wordlist() {
+        let checker = Checker::<WordlistChecker>::new();
+        let config = Config {
+            dictionary_words: vec!["kestrel".to_string()],
+            ..Default::default()
+        };
+        assert!(checker.check("kestrel", &config).is_identified);
+        assert!(!checker.check("kestrels", &config).is_identified);
+    }
 }
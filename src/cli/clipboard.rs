@@ -0,0 +1,53 @@
+//! System clipboard access for `--clipboard` and `--copy`
+//!
+//! Only available when ares is built with the `clipboard` feature, since it needs
+//! the platform's clipboard libraries. Without it both functions return an error
+//! telling the user how to rebuild.
+//!
+//! On Linux the clipboard is owned by the process which set it, so text copied
+//! with `--copy` only outlives ares if a clipboard manager is running.
+
+/// Error returned when ares was built without clipboard support
+#[cfg(not(feature = "clipboard"))]
+const NOT_BUILT: &str =
+    "This build of ares has no clipboard support. Rebuild with `--features clipboard`";
+
+/// Reads text from the system clipboard
+///
+/// # Errors
+/// Returns an error if the clipboard is unavailable, empty or does not hold text.
+#[cfg(feature = "clipboard")]
+pub fn read_clipboard() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| format!("Could not read the clipboard: {}", e))
+}
+
+/// Reads text from the system clipboard
+///
+/// # Errors
+/// Always returns an error, as ares was built without the `clipboard` feature.
+#[cfg(not(feature = "clipboard"))]
+pub fn read_clipboard() -> Result<String, String> {
+    Err(NOT_BUILT.to_string())
+}
+
+/// Places text on the system clipboard
+///
+/// # Errors
+/// Returns an error if the clipboard is unavailable.
+#[cfg(feature = "clipboard")]
+pub fn write_clipboard(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("Could not copy to the clipboard: {}", e))
+}
+
+/// Places text on the system clipboard
+///
+/// # Errors
+/// Always returns an error, as ares was built without the `clipboard` feature.
+#[cfg(not(feature = "clipboard"))]
+pub fn write_clipboard(_text: &str) -> Result<(), String> {
+    Err(NOT_BUILT.to_string())
+}
@@ -1,21 +1,58 @@
+/// System clipboard access for `--clipboard` and `--copy`
+pub mod clipboard;
+/// Runs a command or shows a desktop notification when cracking finishes, for `--notify`
+pub mod notify;
 // First-run configuration module
 mod first_run;
 pub use first_run::run_first_time_setup;
+/// Interactive shell started with `ares repl`
+mod repl;
+/// Reruns cracking when a file changes, started with `ares watch <file>`
+mod watch;
 
 use std::{fs::File, io::Read};
 
+use crate::byte_text::Candidate;
 use crate::cli_pretty_printing;
-use crate::cli_pretty_printing::panic_failure_both_input_and_fail_provided;
-use crate::config::{get_config_file_into_struct, load_wordlist, Config};
+use crate::cli_pretty_printing::i18n::Lang;
+use crate::cli_pretty_printing::report::ReportFormat;
+use crate::config::{get_config_file_into_struct, load_wordlist, CacheMode, Config, PathStyle, ProgressFormat};
+use crate::cryptanalysis::correlation::correlate;
+use crate::cryptanalysis::identify::identify;
+use crate::cryptanalysis::kpa::parse_letter_map;
+use crate::extract::crack_tokens;
+use crate::cryptanalysis::Language;
+use crate::decoders::prefix_code_decoder::parse_codebook;
+use crate::input_format::{preprocess_input, InputFormat};
+#[cfg(feature = "cache")]
+use crate::storage::database;
+use crate::storage::{models, wordlists};
 /// This doc string acts as a help message when the uses run '--help' in CLI mode
 /// as do all doc strings on fields
-use clap::Parser;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Args, Parser, Subcommand};
 use log::trace;
 
+/// Exit codes of the `ares` binary, so shell scripts can branch on the result
+pub mod exit_code {
+    /// A plaintext was found
+    pub const CRACKED: i32 = 0;
+    /// The search finished without finding a plaintext
+    pub const NOT_CRACKED: i32 = 1;
+    /// The arguments, input or config were invalid
+    pub const INVALID_INPUT: i32 = 2;
+    /// Ares itself failed, e.g. it panicked or could not use the terminal
+    pub const INTERNAL_ERROR: i32 = 3;
+}
+
 /// The struct for Clap CLI arguments
 #[derive(Parser)]
 #[command(author = "Bee <bee@skerritt.blog>", about, long_about = None)]
 pub struct Opts {
+    /// Run a single tool instead of searching for the plaintext
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Some input. Because this isn't an Option<T> it's required to be used
     #[arg(short, long)]
     text: Option<String>,
@@ -47,12 +84,13 @@ pub struct Opts {
     /// This turns off other checkers (English, LemmeKnow)
     #[arg(short, long)]
     regex: Option<String>,
-    /// Path to a wordlist file containing newline-separated words
+    /// Path to a wordlist file containing newline-separated words,
+    /// or the name of one added with `ares wordlist add`
     /// The checker will match input against these words exactly
     /// Takes precedence over config file if both specify a wordlist
     #[arg(
         long,
-        help = "Path to a wordlist file with newline-separated words for exact matching"
+        help = "Path or `ares wordlist` name of a wordlist with newline-separated words for exact matching"
     )]
     wordlist: Option<String>,
     /// Show all potential plaintexts found instead of exiting after the first one
@@ -62,14 +100,340 @@ pub struct Opts {
     /// Enables enhanced plaintext detection with BERT model.
     #[arg(long)]
     enable_enhanced_detection: bool,
+    /// Write a report of the run to this path.
+    /// The format is picked from the extension: .csv, .md or .html
+    #[arg(long)]
+    report: Option<String>,
+    /// Run this command when cracking finishes, with `cracked` or `failed`, the seconds
+    /// taken and the plaintext as $1, $2 and $3, and the result as JSON on stdin.
+    /// Without a command, shows a desktop notification
+    #[arg(long, value_name = "COMMAND", num_args = 0..=1, default_missing_value = notify::DESKTOP)]
+    notify: Option<String>,
+    /// Show the search in a terminal interface, with a live search tree, decoder
+    /// activity and candidates. Possible plaintexts are accepted or rejected with keys.
+    #[arg(long)]
+    tui: bool,
+    /// Show progress on stderr: a bar with nodes expanded, depth and time left,
+    /// or with `--progress json` one JSON object per line for other tools.
+    /// Stdout only gets the result, so piping the output is unaffected.
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "bar")]
+    progress: Option<ProgressFormat>,
+    /// Only print the plaintext, or nothing if it is not found. Disables the human checker.
+    /// Exit codes: 0 cracked, 1 not cracked, 2 invalid input, 3 internal error
+    #[arg(short, long)]
+    quiet: bool,
+    /// Write the plaintext to this file. Binary plaintexts, e.g. a ZIP hidden under
+    /// Base64, are written byte for byte
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Save binary files found while decoding (ZIP, PNG, gzip, ...) into this directory
+    #[arg(long)]
+    artifacts_dir: Option<String>,
+    /// How the input is encoded. Use hex or base64 to hand ares binary data,
+    /// the decoding is recorded as the first step of the path
+    #[arg(long, value_enum, default_value_t = InputFormat::Raw)]
+    input_format: InputFormat,
+    /// Read the ciphertext from the system clipboard.
+    /// Use instead of `--text` or `--file`. Needs the `clipboard` feature
+    #[arg(long)]
+    clipboard: bool,
+    /// Copy the plaintext to the system clipboard. Needs the `clipboard` feature
+    #[arg(long)]
+    copy: bool,
+    /// List every decoder with its tags, popularity and description, then exit.
+    /// Use the names or tags to build include/exclude lists.
+    #[arg(long)]
+    list_decoders: bool,
+    /// List every checker with its tags, popularity and description, then exit.
+    #[arg(long)]
+    list_checkers: bool,
+    /// Print `--list-decoders` and `--list-checkers` output as JSON
+    #[arg(long)]
+    json: bool,
+    /// Only run decoders matching these comma-separated names or tags, e.g. `--only base,rot`
+    #[arg(long, value_delimiter = ',')]
+    only: Vec<String>,
+    /// Never run decoders matching these comma-separated names or tags, e.g. `--exclude hash`
+    #[arg(long, value_delimiter = ',')]
+    exclude: Vec<String>,
+    /// Number of threads to run decoders on. Defaults to one per CPU
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Keep the text each decoder produced on its step of the result path, as
+    /// `intermediate_text` for library users. `-v` shows each step's output
+    /// with or without it
+    #[arg(long)]
+    keep_intermediates: bool,
+    /// Re-encode the plaintext back along the path to check each step gave
+    /// back its input, warning about lossy steps
+    #[arg(long)]
+    verify: bool,
+    /// Whether to read from and write to the cache, e.g. `--cache-mode read-only`
+    #[arg(long, value_enum)]
+    cache_mode: Option<CacheMode>,
+    /// Don't use the cache at all, the same as `--cache-mode off`
+    #[arg(long, conflicts_with = "cache_mode")]
+    no_cache: bool,
+    /// Search even if this input failed to crack before with the same settings
+    #[arg(long)]
+    retry_failed: bool,
+    /// Languages the plaintext may be in, e.g. `--languages english,french`
+    #[arg(long, value_parser = language_parser(), value_delimiter = ',')]
+    languages: Vec<Language>,
+    /// Seed for the random number generator of the hill-climbing attacks, for reproducible runs
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Text known to be in the plaintext, e.g. `--crib flag{`. Vigenère, XOR and substitution try keys which fit it first
+    #[arg(long)]
+    crib: Option<String>,
+    /// Letters known to stand for each other in a substitution, ciphertext first,
+    /// e.g. `--map E=X,T=Q` or `--map QVW=THE`. The substitution solver keeps them fixed
+    #[arg(long = "map", value_name = "HINTS")]
+    letter_map: Option<String>,
+    /// Decode the `--file` in chunks instead of reading it into memory, for very large files
+    #[arg(long, requires = "file", conflicts_with = "input_format")]
+    stream: bool,
+    /// Search the input as it is, without removing invisible characters or straightening quotes
+    #[arg(long)]
+    no_normalize: bool,
+    /// Show at most this many of the best plaintexts in `--top-results` mode
+    #[arg(long, requires = "top_results")]
+    top_results_count: Option<usize>,
+    /// Stop searching as soon as this many different plaintexts are found in
+    /// `--top-results` mode, instead of running until the timeout
+    #[arg(long, requires = "top_results", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    max_results: Option<usize>,
+    /// Show plaintexts with word spacing and sentence case restored, for ciphers which drop them
+    #[arg(long)]
+    readable: bool,
+    /// Show the steps of a multi-step path as a table, or as a tree from the input down
+    #[arg(long, value_enum, value_name = "STYLE")]
+    path_style: Option<PathStyle>,
+    /// The language of ares' messages. Picked from the system's locale if not given
+    #[arg(long, value_parser = lang_parser())]
+    lang: Option<Lang>,
+    /// Letters, in order, for the Caesar decoder to also shift around, e.g. "0123456789"
+    #[arg(long = "alphabet", value_name = "LETTERS")]
+    custom_alphabet: Option<String>,
+    /// File with a prefix code for the Prefix Code decoder, like `a=0,b=10,c=11`,
+    /// one `symbol=code` per line or separated by commas
+    #[arg(long, value_name = "FILE")]
+    codebook: Option<String>,
+}
+
+/// Subcommands which bypass the searcher
+#[derive(Subcommand)]
+enum Command {
+    /// Run exactly one decoder on the input, e.g. `ares decode --decoder base58 <input>`
+    Decode(DecodeArgs),
+    /// Encode the input with a reversible decoder, e.g. `ares encode --encoder base64 <input>`
+    Encode(EncodeArgs),
+    /// Start an interactive shell with commands like `crack <text>` and `set timeout 10`
+    Repl,
+    /// Crack a file again every time it changes, printing what changed in the result
+    Watch(WatchArgs),
+    /// Apply the steps in a YAML or CyberChef recipe to the input, without searching
+    RunRecipe(RunRecipeArgs),
+    /// Rank the ciphers and encodings the input looks like, most likely first
+    Identify(IdentifyArgs),
+    /// Find the Base64, hex and JWT tokens in a file and crack each, reporting where they were
+    Extract(ExtractArgs),
+    /// Check whether the ciphertexts in a file, one per line, share an XOR keystream or Vigenère key, and crack them together if so
+    Correlate(CorrelateArgs),
+    /// Manage the wordlists in `~/.ares/wordlists`, which `--wordlist` can use by name
+    #[command(subcommand)]
+    Wordlist(WordlistCommand),
+    /// Manage the n-gram models in `~/.ares/models`, used to score candidate plaintexts
+    #[command(subcommand)]
+    Models(ModelsCommand),
+    /// Show past cracks, newest first, with how each went and how long it took
+    #[cfg(feature = "cache")]
+    History(HistoryArgs),
+    /// Manage your own words and flag prefixes, which the checkers accept and keyword attacks try first
+    #[cfg(feature = "cache")]
+    #[command(subcommand)]
+    Dict(DictCommand),
+}
+
+/// Subcommands of `ares dict`
+#[cfg(feature = "cache")]
+#[derive(Subcommand)]
+enum DictCommand {
+    /// Add words, or import them from a file with one word per line
+    Add {
+        /// The words to add
+        words: Vec<String>,
+        /// A file with one word per line to add too
+        #[arg(short, long)]
+        file: Option<String>,
+        /// Accept plaintexts which start with these, like `flag{`, rather than only the words
+        #[arg(long)]
+        prefix: bool,
+    },
+    /// List the words and prefixes, oldest first
+    List,
+    /// Remove words or prefixes
+    Remove {
+        /// The words to remove
+        words: Vec<String>,
+    },
+}
+
+/// Subcommands of `ares wordlist`
+#[derive(Subcommand)]
+enum WordlistCommand {
+    /// Import a wordlist from a file, or download a curated one if no file is given
+    Add {
+        /// The name to use it by, e.g. `--wordlist <name>`
+        name: String,
+        /// The file with one word per line
+        file: Option<String>,
+    },
+    /// List the installed wordlists with how many words they have
+    List,
+    /// Delete an installed wordlist
+    Remove {
+        /// The wordlist to delete
+        name: String,
+    },
+    /// Download the installed curated wordlists again, or only the one named
+    Update {
+        /// The curated wordlist to update
+        name: Option<String>,
+    },
+}
+
+/// Subcommands of `ares models`
+#[derive(Subcommand)]
+enum ModelsCommand {
+    /// Download a model, e.g. `english-quadgrams`, checking it against its checksum
+    Download {
+        /// The model to download
+        name: String,
+    },
+    /// List the downloaded models, and the ones which can be downloaded
+    List,
+    /// Delete a downloaded model, going back to the built-in tables
+    Remove {
+        /// The model to delete
+        name: String,
+    },
+}
+
+/// Arguments for `ares decode`
+#[derive(Args)]
+struct DecodeArgs {
+    /// Name of the decoder to run. If no decoder has this name, every decoder
+    /// with this tag is run instead. See `--list-decoders`.
+    #[arg(short, long)]
+    decoder: String,
+    /// Key for keyed decoders, e.g. a Caesar shift (`13`), a Vigenère keyword,
+    /// an XOR byte (`0x41`), Railfence `rails,offset` or Affine `a,b`
+    #[arg(short, long)]
+    key: Option<String>,
+    /// The text to decode
+    input: String,
+}
+
+/// Arguments for `ares watch`
+#[derive(Args)]
+struct WatchArgs {
+    /// The file containing the ciphertext
+    file: String,
+}
+
+/// Arguments for `ares run-recipe`
+#[derive(Args)]
+struct RunRecipeArgs {
+    /// The recipe file, a YAML list of steps or a CyberChef recipe saved as JSON
+    recipe: String,
+    /// The text to decode
+    input: String,
+}
+
+/// Arguments for `ares identify`
+#[derive(Args)]
+struct IdentifyArgs {
+    /// Print the ranking as JSON
+    #[arg(long)]
+    json: bool,
+    /// The text to identify
+    input: String,
+}
+
+/// Arguments for `ares extract`
+#[derive(Args)]
+struct ExtractArgs {
+    /// Print the tokens and their plaintexts as JSON
+    #[arg(long)]
+    json: bool,
+    /// The file to search for tokens, such as a log or an email
+    file: String,
+}
+
+/// Arguments for `ares correlate`
+#[derive(Args)]
+struct CorrelateArgs {
+    /// How each line is encoded, e.g. hex for XOR'd bytes
+    #[arg(long, value_enum, default_value_t = InputFormat::Raw)]
+    input_format: InputFormat,
+    /// The file with one ciphertext per line
+    file: String,
+}
+
+/// Arguments for `ares history`
+#[cfg(feature = "cache")]
+#[derive(Args)]
+struct HistoryArgs {
+    /// Show at most this many runs
+    #[arg(short = 'n', long, default_value_t = 20)]
+    limit: usize,
+    /// Print the runs as JSON, with the settings each ran with
+    #[arg(long)]
+    json: bool,
+    /// Only show the runs on this input
+    input: Option<String>,
+}
+
+/// Arguments for `ares encode`
+#[derive(Args)]
+struct EncodeArgs {
+    /// Name of the encoder to use, e.g. base64, hexadecimal, a1z26, "morse code"
+    #[arg(short, long)]
+    encoder: String,
+    /// Key for keyed ciphers, e.g. a Caesar shift (`13` for ROT13) or an XOR byte (`0x41`)
+    #[arg(short, long)]
+    key: Option<String>,
+    /// The text to encode
+    input: String,
+}
+
+/// Parses `--languages` by the names in `Config.languages`, so
+/// [`Language`] doesn't need to know about clap
+fn language_parser() -> impl TypedValueParser<Value = Language> {
+    PossibleValuesParser::new(Language::ALL.map(Language::name)).map(|name| {
+        Language::ALL
+            .into_iter()
+            .find(|language| language.name() == name)
+            .expect("clap only lets through the names of languages")
+    })
+}
+
+/// Parses `--lang` by the language codes, like `de`
+fn lang_parser() -> impl TypedValueParser<Value = Lang> {
+    PossibleValuesParser::new(Lang::ALL.map(Lang::code)).map(|code| {
+        Lang::ALL
+            .into_iter()
+            .find(|lang| lang.code() == code)
+            .expect("clap only lets through the codes of languages")
+    })
 }
 
 /// Parse CLI Arguments turns a Clap Opts struct, seen above
 /// Into a library Struct for use within the program
 /// The library struct can be found in the [config](../config) folder.
-/// # Panics
-/// This function can panic when it gets both a file and text input at the same time.
-pub fn parse_cli_args() -> (String, Config) {
+/// Exits with [`exit_code::INVALID_INPUT`] when it gets more than one of file, text and clipboard input.
+pub fn parse_cli_args() -> (Candidate, Config) {
     let mut opts: Opts = Opts::parse();
     let min_log_level = match opts.verbose {
         0 => "Warn",
@@ -81,54 +445,143 @@ pub fn parse_cli_args() -> (String, Config) {
         env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, min_log_level),
     );
 
-    // If both the file and text are proivded, panic because we're not sure which one to use
-    if opts.file.is_some() && opts.text.is_some() {
+    // Plugins must be registered before decoder names are listed or validated
+    #[cfg(feature = "plugins")]
+    crate::plugins::load_default_plugins();
+
+    if opts.list_decoders || opts.list_checkers {
         let config = Config::default();
-        panic_failure_both_input_and_fail_provided(&config);
+        if opts.list_decoders {
+            cli_pretty_printing::display_components(
+                &cli_pretty_printing::registered_decoders(),
+                opts.json,
+                &config,
+            );
+        }
+        if opts.list_checkers {
+            cli_pretty_printing::display_components(
+                &cli_pretty_printing::registered_checkers(),
+                opts.json,
+                &config,
+            );
+        }
+        std::process::exit(0);
+    }
+
+    match opts.command.take() {
+        Some(Command::Decode(args)) => run_decode_command(args),
+        Some(Command::Encode(args)) => run_encode_command(args),
+        Some(Command::Repl) => {
+            if let Err(e) = repl::run_repl(get_config_file_into_struct()) {
+                eprintln!("Could not start the interactive shell: {}", e);
+                std::process::exit(exit_code::INTERNAL_ERROR);
+            }
+            std::process::exit(0);
+        }
+        Some(Command::RunRecipe(args)) => run_recipe_command(args),
+        Some(Command::Identify(args)) => run_identify_command(args),
+        Some(Command::Extract(args)) => run_extract_command(args),
+        Some(Command::Correlate(args)) => run_correlate_command(args),
+        Some(Command::Wordlist(command)) => run_wordlist_command(command),
+        Some(Command::Models(command)) => run_models_command(command),
+        #[cfg(feature = "cache")]
+        Some(Command::History(args)) => run_history_command(args),
+        #[cfg(feature = "cache")]
+        Some(Command::Dict(command)) => run_dict_command(command),
+        Some(Command::Watch(args)) => {
+            if let Err(e) = watch::run_watch(&args.file, get_config_file_into_struct()) {
+                eprintln!("Can't watch '{}': {}", args.file, e);
+                std::process::exit(exit_code::INVALID_INPUT);
+            }
+            std::process::exit(0);
+        }
+        None => (),
+    }
+
+    // If more than one input is provided, exit because we're not sure which one to use
+    let inputs_provided = [opts.file.is_some(), opts.text.is_some(), opts.clipboard]
+        .into_iter()
+        .filter(|provided| *provided)
+        .count();
+    if inputs_provided > 1 {
+        eprintln!("Failed -- more than one of file, text and clipboard were provided. Please only use one.");
+        std::process::exit(exit_code::INVALID_INPUT);
     }
 
-    let input_text: String = if opts.file.is_some() {
-        read_and_parse_file(opts.file.unwrap())
+    let mut stream_file = None;
+    let input_text: Candidate = if let Some(file_path) = opts.file.take() {
+        if opts.stream {
+            // The file is read while cracking, a chunk at a time
+            stream_file = Some(file_path);
+            Candidate::default()
+        } else {
+            read_and_parse_file(file_path)
+        }
+    } else if let Some(text) = opts.text.take() {
+        text.into()
+    } else if opts.clipboard {
+        match clipboard::read_clipboard() {
+            // Copying a line usually brings its newline along
+            Ok(text) => text.trim_end_matches(['\n', '\r']).into(),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(exit_code::INVALID_INPUT);
+            }
+        }
     } else {
-        opts.text
-            .expect("Error. No input was provided. Please use ares --help")
+        eprintln!("Error. No input was provided. Please use ares --help");
+        std::process::exit(exit_code::INVALID_INPUT);
     };
 
     // Fixes bug where opts.text and opts.file are partially borrowed
     opts.text = None;
     opts.file = None;
 
+    // Catch malformed hex or Base64 before the search rather than failing silently
+    if let Err(e) = preprocess_input(&input_text, opts.input_format) {
+        eprintln!("{}", e);
+        std::process::exit(exit_code::INVALID_INPUT);
+    }
+
     trace!("Program was called with CLI 😉");
     trace!("Parsed the arguments");
     trace!("The inputted text is {}", &input_text);
 
-    cli_args_into_config_struct(opts, input_text)
+    let (input_text, mut config) = cli_args_into_config_struct(opts, input_text);
+    config.stream_file = stream_file;
+    (input_text, config)
 }
 
 /// When the CLI is called with `-f` to open a file
-/// this function opens it
-/// # Panics
-/// This can panic when opening a file which does not exist!
-pub fn read_and_parse_file(file_path: String) -> String {
-    // TODO pretty match on the errors to provide better output
-    // Else it'll panic
-    let mut file = File::open(file_path).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+/// this function opens it. Binary files are kept as bytes, see [`crate::byte_text`].
+/// Exits with [`exit_code::INVALID_INPUT`] if the file cannot be read.
+pub fn read_and_parse_file(file_path: String) -> Candidate {
+    let mut contents = Vec::new();
+    if let Err(e) = File::open(&file_path).and_then(|mut file| file.read_to_end(&mut contents)) {
+        eprintln!("Can't read input file '{}': {}", file_path, e);
+        std::process::exit(exit_code::INVALID_INPUT);
+    }
     // We can just put the file into the `Opts.text` and the program will work as normal
-    // On Unix systems a line is defined as "\n{text}\n"
-    // https://stackoverflow.com/a/729795
-    // Which means if a user creates a file on Unix, it'll have a new line appended.
-    // This is probably not what they wanted to decode (it is not what I wanted) so we are removing them
-    if contents.ends_with(['\n', '\r']) {
-        contents.strip_suffix(['\n', '\r']).unwrap().to_owned()
-    } else {
-        contents
+    // The newline editors leave at the end is dropped, see `strip_trailing_newline`
+    if matches!(contents.last(), Some(b'\n' | b'\r')) {
+        contents.pop();
+    }
+    Candidate::from_bytes(contents)
+}
+
+/// On Unix systems a line is defined as "\n{text}\n"
+/// https://stackoverflow.com/a/729795
+/// Which means if a user creates a file on Unix, it'll have a new line appended.
+/// This is probably not what they wanted to decode (it is not what I wanted) so we are removing them
+fn strip_trailing_newline(contents: String) -> String {
+    match contents.strip_suffix(['\n', '\r']) {
+        Some(stripped) => stripped.to_owned(),
+        None => contents,
     }
 }
 
 /// Turns our CLI arguments into a config stuct
-fn cli_args_into_config_struct(opts: Opts, text: String) -> (String, Config) {
+fn cli_args_into_config_struct(opts: Opts, text: Candidate) -> (Candidate, Config) {
     // Get configuration from file first
     let mut config = get_config_file_into_struct();
 
@@ -144,6 +597,82 @@ fn cli_args_into_config_struct(opts: Opts, text: String) -> (String, Config) {
         config.api_mode = api_mode;
     }
 
+    if let Some(threads) = opts.threads {
+        config.threads = Some(threads);
+    }
+
+    if opts.keep_intermediates {
+        config.keep_intermediates = true;
+    }
+
+    if opts.verify {
+        config.verify = true;
+    }
+
+    if opts.no_cache {
+        config.cache_mode = CacheMode::Off;
+    } else if let Some(cache_mode) = opts.cache_mode {
+        config.cache_mode = cache_mode;
+    }
+
+    if opts.retry_failed {
+        config.retry_failed = true;
+    }
+
+    if !opts.languages.is_empty() {
+        config.languages = opts.languages;
+    }
+
+    if let Some(seed) = opts.seed {
+        config.seed = Some(seed);
+    }
+
+    if opts.no_normalize {
+        config.normalize_input = false;
+    }
+
+    if opts.readable {
+        config.readable = true;
+    }
+
+    if let Some(style) = opts.path_style {
+        config.path_style = style;
+    }
+
+    if let Some(lang) = opts.lang {
+        config.lang = Some(lang);
+    }
+
+    if let Some(alphabet) = opts.custom_alphabet {
+        config.custom_alphabet = Some(alphabet);
+    }
+
+    if let Some(crib) = opts.crib {
+        config.crib = Some(crib);
+    }
+
+    if let Some(letter_map) = opts.letter_map {
+        // Catch mistakes early, otherwise the solver silently ignores the hints
+        if let Err(e) = parse_letter_map(&letter_map) {
+            eprintln!("Can't read the letter map '{}': {}", letter_map, e);
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+        config.letter_map = Some(letter_map);
+    }
+
+    if let Some(codebook_path) = opts.codebook {
+        let codebook = std::fs::read_to_string(&codebook_path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| parse_codebook(&text));
+        match codebook {
+            Ok(codebook) => config.codebook = Some(codebook),
+            Err(e) => {
+                eprintln!("Can't load codebook at '{}': {}", codebook_path, e);
+                std::process::exit(exit_code::INVALID_INPUT);
+            }
+        }
+    }
+
     if let Some(regex) = opts.regex {
         config.regex = Some(regex);
     }
@@ -152,21 +681,72 @@ fn cli_args_into_config_struct(opts: Opts, text: String) -> (String, Config) {
     if let Some(wordlist_path) = opts.wordlist {
         config.wordlist_path = Some(wordlist_path.clone());
 
-        // Load the wordlist here in the CLI layer
-        match load_wordlist(&wordlist_path) {
+        // Load the wordlist here in the CLI layer, by name if it is a managed one
+        match load_wordlist(wordlists::resolve_default(&wordlist_path)) {
             Ok(wordlist) => {
                 config.wordlist = Some(wordlist);
             }
             Err(e) => {
                 // Critical error - exit if wordlist is specified but can't be loaded
                 eprintln!("Can't load wordlist at '{}': {}", wordlist_path, e);
-                std::process::exit(1);
+                std::process::exit(exit_code::INVALID_INPUT);
+            }
+        }
+    }
+
+    if let Some(report_path) = opts.report {
+        // Fail before cracking rather than after, so the user doesn't lose the run
+        if ReportFormat::from_path(&report_path).is_none() {
+            eprintln!(
+                "Unsupported report format for '{}'. Use a .csv, .md or .html file",
+                report_path
+            );
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+        config.report_path = Some(report_path);
+    }
+    if let Some(command) = opts.notify {
+        config.notify_command = Some(command);
+    }
+
+    if !opts.only.is_empty() || !opts.exclude.is_empty() {
+        // Catch typos early, otherwise a bad --only silently disables every decoder
+        for term in opts.only.iter().chain(opts.exclude.iter()) {
+            if !is_known_decoder_name_or_tag(term) {
+                eprintln!(
+                    "Unknown decoder name or tag '{}'. Use --list-decoders to see what is available",
+                    term
+                );
+                std::process::exit(exit_code::INVALID_INPUT);
             }
         }
+        config.decoders_only = opts.only;
+        config.decoders_exclude = opts.exclude;
     }
 
     // Set top_results mode if the flag is present
     config.top_results = opts.top_results;
+    if let Some(count) = opts.top_results_count {
+        config.top_results_count = Some(count);
+    }
+    if let Some(max) = opts.max_results {
+        config.max_results = Some(max);
+    }
+    config.tui = opts.tui;
+    config.progress = opts.progress;
+
+    config.output_path = opts.output;
+    config.copy_to_clipboard = opts.copy;
+    config.input_format = opts.input_format;
+    config.artifacts_dir = opts.artifacts_dir;
+
+    // Quiet mode prints nothing but the plaintext, so nothing may prompt the user either
+    if opts.quiet {
+        config.quiet = true;
+        config.api_mode = true;
+        config.human_checker_on = false;
+        config.progress = None;
+    }
 
     // If top_results is enabled, automatically disable the human checker
     if config.top_results {
@@ -186,3 +766,396 @@ fn cli_args_into_config_struct(opts: Opts, text: String) -> (String, Config) {
 
     (text, config)
 }
+
+/// Returns true if `term` is the name (case-insensitive) or a tag of any registered decoder
+fn is_known_decoder_name_or_tag(term: &str) -> bool {
+    crate::filtration_system::get_all_decoders()
+        .components
+        .iter()
+        .any(|decoder| {
+            decoder.get_name().eq_ignore_ascii_case(term) || decoder.get_tags().contains(&term)
+        })
+}
+
+/// Runs `ares decode` and exits
+fn run_decode_command(args: DecodeArgs) -> ! {
+    let mut config = get_config_file_into_struct();
+    // There is no search to verify, so never ask the user about candidates
+    config.human_checker_on = false;
+    match crate::perform_decoding(&args.input, &args.decoder, args.key.as_deref(), &config) {
+        Ok(results) => {
+            cli_pretty_printing::display_direct_decoding(&results, &config);
+            std::process::exit(exit_code::CRACKED);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+    }
+}
+
+/// Runs `ares run-recipe` and exits
+fn run_recipe_command(args: RunRecipeArgs) -> ! {
+    let config = get_config_file_into_struct();
+    let result = crate::recipe::Recipe::from_file(std::path::Path::new(&args.recipe))
+        .and_then(|recipe| recipe.run(&args.input, &config));
+    match result {
+        Ok(result) => {
+            cli_pretty_printing::program_exiting_successful_decoding(result, &config);
+            std::process::exit(exit_code::CRACKED);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+    }
+}
+
+/// Runs `ares identify` and exits
+fn run_identify_command(args: IdentifyArgs) -> ! {
+    let guesses = identify(&args.input);
+    if args.json {
+        let ranking: Vec<_> = guesses
+            .iter()
+            .map(|guess| {
+                serde_json::json!({
+                    "family": guess.family.name(),
+                    "score": guess.score,
+                    "reason": guess.reason,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::Value::Array(ranking));
+    } else {
+        for guess in &guesses {
+            println!(
+                "{:>3.0}%\t{}\t{}",
+                guess.score * 100.0,
+                guess.family.name(),
+                guess.reason
+            );
+        }
+    }
+    if guesses.is_empty() {
+        eprintln!("The input doesn't look like any cipher or encoding Ares knows");
+        std::process::exit(exit_code::NOT_CRACKED);
+    }
+    std::process::exit(0);
+}
+
+/// Runs `ares history` and exits
+#[cfg(feature = "cache")]
+fn run_history_command(args: HistoryArgs) -> ! {
+    let config = get_config_file_into_struct();
+    let runs = database::setup_database(&config)
+        .and_then(|()| database::read_runs(args.input.as_deref(), args.limit));
+    let runs = match runs {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Can't read the run history: {}", e);
+            std::process::exit(exit_code::INTERNAL_ERROR);
+        }
+    };
+    if args.json {
+        let runs: Vec<_> = runs
+            .iter()
+            .map(|run| {
+                serde_json::json!({
+                    "timestamp": run.timestamp,
+                    "input": run.input_preview,
+                    "input_hash": run.input_hash,
+                    "outcome": run.outcome,
+                    "duration_ms": run.duration_ms,
+                    "path": run.path,
+                    "plaintext": run.plaintext,
+                    "config": serde_json::from_str::<serde_json::Value>(&run.config)
+                        .unwrap_or_default(),
+                    "ares_version": run.ares_version,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::Value::Array(runs));
+    } else {
+        for run in &runs {
+            println!(
+                "{}\t{}\t{}ms\t{}\t{}",
+                run.timestamp,
+                run.outcome,
+                run.duration_ms,
+                run.path.join(" → "),
+                run.input_preview
+            );
+        }
+    }
+    std::process::exit(0);
+}
+
+/// Runs `ares dict` and exits
+#[cfg(feature = "cache")]
+fn run_dict_command(command: DictCommand) -> ! {
+    if let Err(e) = database::setup_database(&get_config_file_into_struct()) {
+        eprintln!("Can't open the database: {}", e);
+        std::process::exit(exit_code::INTERNAL_ERROR);
+    }
+    let result = match command {
+        DictCommand::Add {
+            mut words,
+            file,
+            prefix,
+        } => {
+            if let Some(file) = file {
+                match std::fs::read_to_string(&file) {
+                    Ok(contents) => words.extend(contents.lines().map(str::to_string)),
+                    Err(e) => {
+                        eprintln!("Can't read '{}': {}", file, e);
+                        std::process::exit(exit_code::INVALID_INPUT);
+                    }
+                }
+            }
+            let rows: Vec<database::DictionaryRow> = words
+                .iter()
+                .map(|word| word.trim())
+                .filter(|word| !word.is_empty())
+                .map(|word| database::DictionaryRow {
+                    word: word.to_string(),
+                    prefix,
+                })
+                .collect();
+            database::insert_dictionary_words(&rows).map(|added| {
+                println!("Added {} of {}", added, rows.len());
+            })
+        }
+        DictCommand::List => database::read_dictionary().map(|rows| {
+            for row in rows {
+                if row.prefix {
+                    println!("{}\tprefix", row.word);
+                } else {
+                    println!("{}", row.word);
+                }
+            }
+        }),
+        DictCommand::Remove { words } => database::delete_dictionary_words(&words)
+            .map(|removed| println!("Removed {}", removed)),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(exit_code::INTERNAL_ERROR);
+    }
+    std::process::exit(0);
+}
+
+/// Runs `ares extract` and exits, with [`exit_code::CRACKED`] if any token was cracked
+fn run_extract_command(args: ExtractArgs) -> ! {
+    let mut document = Vec::new();
+    if let Err(e) = File::open(&args.file).and_then(|mut file| file.read_to_end(&mut document)) {
+        eprintln!("Can't read input file '{}': {}", args.file, e);
+        std::process::exit(exit_code::INVALID_INPUT);
+    }
+    let mut config = get_config_file_into_struct();
+    // Each token is cracked on its own, so nothing may print or prompt in between
+    config.api_mode = true;
+    config.human_checker_on = false;
+
+    let extracted = crack_tokens(&document, &config);
+    if args.json {
+        let tokens: Vec<_> = extracted
+            .iter()
+            .map(|extracted| {
+                let mut json = serde_json::json!({
+                    "kind": extracted.token.kind,
+                    "start": extracted.token.start,
+                    "end": extracted.token.end,
+                    "token": extracted.token.text,
+                });
+                match &extracted.result {
+                    Ok(result) => {
+                        json["plaintext"] = result.text[0].to_string().into();
+                        json["path"] = result
+                            .path
+                            .iter()
+                            .map(|step| step.decoder)
+                            .collect::<Vec<_>>()
+                            .into();
+                    }
+                    Err(e) => json["error"] = e.to_string().into(),
+                }
+                json
+            })
+            .collect();
+        println!("{}", serde_json::Value::Array(tokens));
+    } else {
+        for extracted in &extracted {
+            let token = &extracted.token;
+            let outcome = match &extracted.result {
+                // One line per token, even for plaintexts with line breaks
+                Ok(result) => format!(
+                    "{}\t{}",
+                    result.text[0].text().replace('\n', "\\n"),
+                    result
+                        .path
+                        .iter()
+                        .map(|step| step.decoder)
+                        .collect::<Vec<_>>()
+                        .join(" → ")
+                ),
+                Err(e) => e.to_string(),
+            };
+            println!(
+                "{}-{}\t{}\t{}\t{}",
+                token.start,
+                token.end,
+                token.kind.name(),
+                token.text,
+                outcome
+            );
+        }
+    }
+    if extracted.is_empty() {
+        eprintln!("No encoded tokens were found in '{}'", args.file);
+    }
+    if extracted.iter().any(|extracted| extracted.result.is_ok()) {
+        std::process::exit(exit_code::CRACKED);
+    }
+    std::process::exit(exit_code::NOT_CRACKED);
+}
+
+/// Runs `ares correlate` and exits, with [`exit_code::CRACKED`] if the ciphertexts share a key
+fn run_correlate_command(args: CorrelateArgs) -> ! {
+    let contents = read_and_parse_file(args.file);
+    let mut ciphertexts = Vec::new();
+    // Split as bytes, as raw ciphertexts needn't be UTF-8
+    let lines = contents
+        .bytes()
+        .split(|&b| b == b'\n')
+        .map(|line| Candidate::from_bytes(line.strip_suffix(b"\r").unwrap_or(line).to_vec()))
+        .filter(|line| !line.text().trim().is_empty());
+    for line in lines {
+        match preprocess_input(&line, args.input_format) {
+            Ok(preprocessed) => ciphertexts.push(preprocessed.text.bytes().to_vec()),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(exit_code::INVALID_INPUT);
+            }
+        }
+    }
+    if ciphertexts.len() < 2 {
+        eprintln!("Correlating needs at least two ciphertexts, one per line");
+        std::process::exit(exit_code::INVALID_INPUT);
+    }
+    let config = get_config_file_into_struct();
+    let Some(shared) = correlate(&ciphertexts, &config.languages) else {
+        eprintln!("The ciphertexts don't look like they share a key, try cracking them one at a time");
+        std::process::exit(exit_code::NOT_CRACKED);
+    };
+    println!(
+        "The {} ciphertexts were encrypted with the same {} key: {}",
+        ciphertexts.len(),
+        shared.cipher.name(),
+        shared.key
+    );
+    for (number, plaintext) in shared.plaintexts.iter().enumerate() {
+        println!("{}\t{}", number + 1, plaintext);
+    }
+    std::process::exit(exit_code::CRACKED);
+}
+
+/// Runs `ares wordlist` and exits
+fn run_wordlist_command(command: WordlistCommand) -> ! {
+    let Some(dir) = wordlists::wordlists_dir() else {
+        eprintln!("Could not find home directory");
+        std::process::exit(exit_code::INTERNAL_ERROR);
+    };
+    let result = match command {
+        WordlistCommand::Add {
+            name,
+            file: Some(file),
+        } => wordlists::add(&dir, &name, std::path::Path::new(&file)).map(|info| vec![info]),
+        WordlistCommand::Add { name, file: None } => {
+            wordlists::download(&dir, &name).map(|info| vec![info])
+        }
+        WordlistCommand::List => wordlists::list(&dir),
+        WordlistCommand::Remove { name } => wordlists::remove(&dir, &name).map(|()| {
+            println!("Removed {}", name);
+            vec![]
+        }),
+        WordlistCommand::Update { name } => wordlists::update(&dir, name.as_deref()),
+    };
+    match result {
+        Ok(wordlists) => {
+            for wordlist in wordlists {
+                println!(
+                    "{}\t{} words\t{}",
+                    wordlist.name,
+                    wordlist.words,
+                    wordlist.path.display()
+                );
+            }
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+    }
+}
+
+/// Runs `ares models` and exits
+fn run_models_command(command: ModelsCommand) -> ! {
+    let Some(dir) = models::models_dir() else {
+        eprintln!("Could not find home directory");
+        std::process::exit(exit_code::INTERNAL_ERROR);
+    };
+    let result = match command {
+        ModelsCommand::Download { name } => models::download(&dir, &name).map(|info| vec![info]),
+        ModelsCommand::List => models::list(&dir).inspect(|downloaded| {
+            let available: Vec<&str> = models::CATALOGUE
+                .iter()
+                .filter(|name| !downloaded.iter().any(|model| model.name == **name))
+                .copied()
+                .collect();
+            if !available.is_empty() {
+                println!("Available to download: {}", available.join(", "));
+            }
+        }),
+        ModelsCommand::Remove { name } => models::remove(&dir, &name).map(|()| {
+            println!("Removed {}", name);
+            vec![]
+        }),
+    };
+    match result {
+        Ok(models) => {
+            for model in models {
+                println!(
+                    "{}\t{} n-grams\t{}\t{}",
+                    model.name,
+                    model.ngrams,
+                    if model.verified {
+                        "verified"
+                    } else {
+                        "checksum mismatch"
+                    },
+                    model.path.display()
+                );
+            }
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+    }
+}
+
+/// Runs `ares encode` and exits
+fn run_encode_command(args: EncodeArgs) -> ! {
+    match crate::perform_encoding(&args.input, &args.encoder, args.key.as_deref()) {
+        Ok(encoded) => {
+            println!("{}", encoded);
+            std::process::exit(exit_code::CRACKED);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+    }
+}
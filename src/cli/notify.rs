@@ -0,0 +1,169 @@
+//! Tells the user a run finished, for `--notify` and `notify_command`
+//!
+//! Long classical cipher hunts can take minutes, so instead of watching the
+//! terminal the user can have a command run when cracking finishes. The
+//! command is run by the shell with the outcome as arguments:
+//!
+//! - `$1` is `cracked` or `failed`
+//! - `$2` is how many seconds the run took
+//! - `$3` is the plaintext, empty if none was found
+//!
+//! and a JSON object with the outcome, the plaintext and its path on stdin.
+//! [`DESKTOP`] instead shows a desktop notification with `notify-send` on
+//! Linux or `osascript` on macOS.
+
+use crate::DecoderResult;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// The `notify_command` which shows a desktop notification, and what a bare
+/// `--notify` sets
+pub const DESKTOP: &str = "desktop";
+
+/// How many chars of the plaintext a desktop notification shows
+const SUMMARY_CHARS: usize = 100;
+
+/// Runs `command`, or shows a desktop notification if it is [`DESKTOP`],
+/// with the outcome of a run which took `elapsed`
+///
+/// # Errors
+/// Returns an error if the command can't be started or exits unsuccessfully,
+/// or if there is no way to show desktop notifications.
+pub fn notify(command: &str, result: Option<&DecoderResult>, elapsed: Duration) -> Result<(), String> {
+    if command == DESKTOP {
+        return notify_desktop(&summary(result, elapsed));
+    }
+    let outcome = if result.is_some() { "cracked" } else { "failed" };
+    let seconds = format!("{:.1}", elapsed.as_secs_f64());
+    let plaintext = result
+        .and_then(|result| result.text.first())
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let json = serde_json::json!({
+        "outcome": outcome,
+        "seconds": elapsed.as_secs_f64(),
+        "text": result.and_then(|result| result.text.first()),
+        "path": result.map(|result| &result.path),
+    });
+
+    let mut child = shell(command)
+        .args([outcome, &seconds, &plaintext])
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Could not run the notify command '{}': {}", command, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command which ignores its stdin may exit before reading it, which is fine
+        let _ = writeln!(stdin, "{}", json);
+    }
+    let status = child
+        .wait()
+        .map_err(|e| format!("Could not run the notify command '{}': {}", command, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("The notify command '{}' failed with {}", command, status))
+    }
+}
+
+/// The system's shell, ready to run `command` with extra arguments as `$1`...
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        // The name of the script, which `$0` is set to
+        shell.args(["-c", command, "ares"]);
+        shell
+    }
+}
+
+/// One line saying how the run went, for desktop notifications
+fn summary(result: Option<&DecoderResult>, elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs_f64();
+    match result.and_then(|result| result.text.first()) {
+        Some(plaintext) => {
+            let plaintext = plaintext.to_string();
+            let mut shown: String = plaintext.chars().take(SUMMARY_CHARS).collect();
+            if shown.len() < plaintext.len() {
+                shown.push('…');
+            }
+            format!("Cracked in {:.1}s: {}", seconds, shown)
+        }
+        None => format!("Failed to crack the text after {:.1}s", seconds),
+    }
+}
+
+/// Shows `message` as a desktop notification titled Ares
+fn notify_desktop(message: &str) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        // Passed as an argument so quotes in the plaintext can't break the script
+        command.args([
+            "-e",
+            "on run argv",
+            "-e",
+            "display notification (item 1 of argv) with title \"Ares\"",
+            "-e",
+            "end run",
+            message,
+        ]);
+        command
+    } else if cfg!(unix) {
+        let mut command = Command::new("notify-send");
+        command.args(["Ares", message]);
+        command
+    } else {
+        return Err(
+            "Desktop notifications aren't supported on this platform, give --notify a command instead"
+                .to_string(),
+        );
+    };
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command
+        .status()
+        .map_err(|e| format!("Could not show a desktop notification with {}: {}", program, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Could not show a desktop notification: {}", status))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::decoders::crack_results::CrackResult;
+    use std::fs;
+
+    #[test]
+    fn the_command_gets_the_outcome_as_arguments_and_json() {
+        let out = std::env::temp_dir().join(format!("ares-notify-{}.txt", std::process::id()));
+        let command = format!("echo \"$1 $2 $3\" > {0}; cat >> {0}", out.display());
+        let result = DecoderResult {
+            text: vec!["hello world".into()],
+            path: vec![CrackResult::from_decoder_info("Base64", "", "", "hello world")],
+            confidence: 0.0,
+        };
+        notify(&command, Some(&result), Duration::from_millis(2_340)).unwrap();
+        let written = fs::read_to_string(&out).unwrap();
+        let (arguments, json) = written.split_once('\n').unwrap();
+        assert_eq!(arguments, "cracked 2.3 hello world");
+        let json: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(json["outcome"], "cracked");
+        assert_eq!(json["text"], "hello world");
+        assert_eq!(json["path"][0]["decoder"], "Base64");
+
+        notify(&command, None, Duration::from_secs(5)).unwrap();
+        let written = fs::read_to_string(&out).unwrap();
+        assert!(written.starts_with("failed 5.0 \n"));
+        fs::remove_file(out).unwrap();
+    }
+
+    #[test]
+    fn failing_commands_are_errors() {
+        assert!(notify("exit 3", None, Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,366 @@
+//! Interactive shell for ares
+//!
+//! `ares repl` opens a readline prompt which keeps a single [`AresSession`]
+//! alive, so the decoder registry, the loaded word lists and models and the
+//! SQLite connection stay warm between commands. `set` rebuilds the session
+//! with the new config. Supported commands:
+//! - `crack <text>` runs the full search, like `ares -t <text>`
+//! - `decode <decoder> [-k <key>] <text>` runs a single decoder, like `ares decode`
+//! - `encode <encoder> [-k <key>] <text>` encodes text, like `ares encode`
+//! - `set <setting> <value>` changes the config for the rest of the session
+//! - `show` prints the current settings, `help` lists commands, `exit` quits
+//!
+//! Names containing spaces can be quoted, e.g. `decode "morse code" ... ---`.
+//! History is saved to `~/.ares/repl_history`.
+
+use crate::cli_pretty_printing;
+use crate::config::{get_config_file_path, Config};
+use crate::AresSession;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+/// A single line typed into the REPL, parsed
+#[derive(Debug, PartialEq)]
+enum ReplCommand {
+    /// Run the full search on the text
+    Crack(String),
+    /// Run one decoder on the text
+    Decode {
+        /// Name or tag of the decoder
+        decoder: String,
+        /// Optional key for keyed decoders
+        key: Option<String>,
+        /// The text to decode
+        text: String,
+    },
+    /// Encode the text
+    Encode {
+        /// Name of the encoder
+        encoder: String,
+        /// Optional key for keyed ciphers
+        key: Option<String>,
+        /// The text to encode
+        text: String,
+    },
+    /// Change a setting
+    Set {
+        /// Name of the setting
+        setting: String,
+        /// The new value, may contain spaces
+        value: String,
+    },
+    /// Print the current settings
+    Show,
+    /// Print the list of commands
+    Help,
+    /// Leave the REPL
+    Exit,
+    /// Blank line, do nothing
+    Empty,
+}
+
+/// Help text printed by the `help` command
+const HELP: &str = "Commands:
+  crack <text>                        Search for the plaintext
+  decode <decoder> [-k <key>] <text>  Run a single decoder (name or tag)
+  encode <encoder> [-k <key>] <text>  Encode text with a reversible decoder
+  set timeout <seconds>               Set the cracking timeout
+  set human_checker <on|off>          Ask before accepting a plaintext
+  set top_results <on|off>            Collect every plaintext until the timeout
+  set regex <pattern|off>             Only accept plaintexts matching a regex
+  set only <names/tags|off>           Only run these decoders (comma separated)
+  set exclude <names/tags|off>        Never run these decoders (comma separated)
+  show                                Print the current settings
+  help                                Print this message
+  exit                                Leave the shell";
+
+/// Runs the REPL until the user exits or presses Ctrl-D.
+///
+/// # Errors
+/// Returns an error if the terminal cannot be used for line editing.
+pub fn run_repl(mut config: Config) -> rustyline::Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    let history_path = get_config_file_path().with_file_name("repl_history");
+    // A missing history file just means this is the first session
+    let _ = editor.load_history(&history_path);
+
+    println!("Ares interactive shell. Type `help` for a list of commands.");
+    let mut session = AresSession::new(config.clone());
+    loop {
+        let line = match editor.readline("ares> ") {
+            Ok(line) => line,
+            // Ctrl-C clears the current line, like a shell
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e),
+        };
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+
+        match parse_command(&line) {
+            Ok(ReplCommand::Exit) => break,
+            Ok(command) => run_command(command, &mut config, &mut session),
+            Err(e) => eprintln!("{}", cli_pretty_printing::warning(&e, &config)),
+        }
+    }
+
+    if let Err(e) = editor.save_history(&history_path) {
+        log::warn!("Could not save REPL history: {}", e);
+    }
+    Ok(())
+}
+
+/// Executes a parsed command against the session config, rebuilding the
+/// session when a setting changes
+fn run_command(command: ReplCommand, config: &mut Config, session: &mut AresSession) {
+    match command {
+        ReplCommand::Crack(text) => match session.crack(&text) {
+            Some(result) => {
+                cli_pretty_printing::program_exiting_successful_decoding(result, config)
+            }
+            None => cli_pretty_printing::failed_to_decode(config),
+        },
+        ReplCommand::Decode { decoder, key, text } => {
+            match crate::perform_decoding(&text, &decoder, key.as_deref(), config) {
+                Ok(results) => cli_pretty_printing::display_direct_decoding(&results, config),
+                Err(e) => eprintln!("{}", cli_pretty_printing::warning(&e, config)),
+            }
+        }
+        ReplCommand::Encode { encoder, key, text } => {
+            match crate::perform_encoding(&text, &encoder, key.as_deref()) {
+                Ok(encoded) => println!("{}", encoded),
+                Err(e) => eprintln!("{}", cli_pretty_printing::warning(&e, config)),
+            }
+        }
+        ReplCommand::Set { setting, value } => match apply_setting(config, &setting, &value) {
+            Ok(()) => {
+                *session = AresSession::new(config.clone());
+                show_settings(config);
+            }
+            Err(e) => eprintln!("{}", cli_pretty_printing::warning(&e, config)),
+        },
+        ReplCommand::Show => show_settings(config),
+        ReplCommand::Help => println!("{}", HELP),
+        ReplCommand::Exit | ReplCommand::Empty => (),
+    }
+}
+
+/// Parses a line typed into the REPL
+fn parse_command(line: &str) -> Result<ReplCommand, String> {
+    let Some((command, rest)) = next_token(line) else {
+        return Ok(ReplCommand::Empty);
+    };
+    let rest = rest.trim();
+
+    match command.to_lowercase().as_str() {
+        "crack" => {
+            if rest.is_empty() {
+                return Err("Usage: crack <text>".to_string());
+            }
+            Ok(ReplCommand::Crack(rest.to_string()))
+        }
+        "decode" | "encode" => {
+            let usage = || format!("Usage: {} <name> [-k <key>] <text>", command);
+            let (name, rest) = next_token(rest).ok_or_else(usage)?;
+            let (key, text) = match next_token(rest) {
+                Some((flag, after_flag)) if flag == "-k" || flag == "--key" => {
+                    let (key, text) = next_token(after_flag).ok_or_else(usage)?;
+                    (Some(key), text.trim())
+                }
+                _ => (None, rest.trim()),
+            };
+            if text.is_empty() {
+                return Err(usage());
+            }
+            let text = text.to_string();
+            if command.eq_ignore_ascii_case("decode") {
+                Ok(ReplCommand::Decode {
+                    decoder: name,
+                    key,
+                    text,
+                })
+            } else {
+                Ok(ReplCommand::Encode {
+                    encoder: name,
+                    key,
+                    text,
+                })
+            }
+        }
+        "set" => {
+            let (setting, value) =
+                next_token(rest).ok_or_else(|| "Usage: set <setting> <value>".to_string())?;
+            Ok(ReplCommand::Set {
+                setting,
+                value: value.trim().to_string(),
+            })
+        }
+        "show" => Ok(ReplCommand::Show),
+        "help" | "?" => Ok(ReplCommand::Help),
+        "exit" | "quit" => Ok(ReplCommand::Exit),
+        _ => Err(format!(
+            "Unknown command '{}'. Type `help` for a list of commands",
+            command
+        )),
+    }
+}
+
+/// Splits the first whitespace separated token off the input.
+/// A token starting with a double quote runs until the closing quote.
+/// Returns None if the input is blank.
+fn next_token(input: &str) -> Option<(String, &str)> {
+    let input = input.trim_start();
+    if input.is_empty() {
+        return None;
+    }
+    if let Some(quoted) = input.strip_prefix('"') {
+        return Some(match quoted.split_once('"') {
+            Some((token, rest)) => (token.to_string(), rest),
+            None => (quoted.to_string(), ""),
+        });
+    }
+    let end = input.find(char::is_whitespace).unwrap_or(input.len());
+    Some((input[..end].to_string(), &input[end..]))
+}
+
+/// Parses `on`/`off` style values
+fn parse_switch(value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "on" | "true" | "yes" | "1" => Ok(true),
+        "off" | "false" | "no" | "0" => Ok(false),
+        _ => Err(format!("Expected on or off, got '{}'", value)),
+    }
+}
+
+/// Parses a comma separated list of decoder names or tags, where `off` or
+/// `none` clears it. Unknown names are rejected like `--only` and `--exclude`
+/// do, otherwise a typo would silently disable every decoder.
+fn parse_decoder_list(value: &str) -> Result<Vec<String>, String> {
+    if value.eq_ignore_ascii_case("off") || value.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            if super::is_known_decoder_name_or_tag(item) {
+                Ok(item.to_string())
+            } else {
+                Err(format!(
+                    "Unknown decoder name or tag '{}'. Use --list-decoders to see what is available",
+                    item
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Applies `set <setting> <value>` to the session config
+fn apply_setting(config: &mut Config, setting: &str, value: &str) -> Result<(), String> {
+    match setting.to_lowercase().as_str() {
+        "timeout" => {
+            config.timeout = value
+                .parse()
+                .map_err(|_| format!("Timeout must be a whole number of seconds, got '{}'", value))?;
+        }
+        "human_checker" => config.human_checker_on = parse_switch(value)?,
+        "top_results" => {
+            config.top_results = parse_switch(value)?;
+            // Same as the CLI flag, collecting results and asking the user don't mix
+            if config.top_results {
+                config.human_checker_on = false;
+            }
+        }
+        "regex" => {
+            config.regex = if value.eq_ignore_ascii_case("off") || value.is_empty() {
+                None
+            } else {
+                Some(value.to_string())
+            };
+        }
+        "only" => config.decoders_only = parse_decoder_list(value)?,
+        "exclude" => config.decoders_exclude = parse_decoder_list(value)?,
+        _ => {
+            return Err(format!(
+                "Unknown setting '{}'. Type `help` for a list of settings",
+                setting
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// Prints the settings the REPL can change
+fn show_settings(config: &Config) {
+    let switch = |on: bool| if on { "on" } else { "off" };
+    let list = |items: &Vec<String>| {
+        if items.is_empty() {
+            "off".to_string()
+        } else {
+            items.join(",")
+        }
+    };
+    println!("timeout = {}", config.timeout);
+    println!("human_checker = {}", switch(config.human_checker_on));
+    println!("top_results = {}", switch(config.top_results));
+    println!("regex = {}", config.regex.as_deref().unwrap_or("off"));
+    println!("only = {}", list(&config.decoders_only));
+    println!("exclude = {}", list(&config.decoders_exclude));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_crack() {
+        assert_eq!(
+            parse_command("crack  aGVsbG8= "),
+            Ok(ReplCommand::Crack("aGVsbG8=".to_string()))
+        );
+        assert!(parse_command("crack").is_err());
+        assert_eq!(parse_command("   "), Ok(ReplCommand::Empty));
+    }
+
+    #[test]
+    fn parses_decode_with_key_and_quotes() {
+        assert_eq!(
+            parse_command("decode caesar -k 13 uryyb jbeyq"),
+            Ok(ReplCommand::Decode {
+                decoder: "caesar".to_string(),
+                key: Some("13".to_string()),
+                text: "uryyb jbeyq".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_command("encode \"morse code\" sos"),
+            Ok(ReplCommand::Encode {
+                encoder: "morse code".to_string(),
+                key: None,
+                text: "sos".to_string(),
+            })
+        );
+        assert!(parse_command("decode base64").is_err());
+    }
+
+    #[test]
+    fn set_changes_config() {
+        let mut config = Config::default();
+        apply_setting(&mut config, "timeout", "10").unwrap();
+        apply_setting(&mut config, "only", "base, rot").unwrap();
+        apply_setting(&mut config, "top_results", "on").unwrap();
+        assert_eq!(config.timeout, 10);
+        assert_eq!(config.decoders_only, vec!["base", "rot"]);
+        assert!(config.top_results);
+        assert!(!config.human_checker_on);
+
+        apply_setting(&mut config, "only", "off").unwrap();
+        assert!(config.decoders_only.is_empty());
+        assert!(apply_setting(&mut config, "exclude", "base64, nosuchdecoder").is_err());
+        assert!(config.decoders_exclude.is_empty());
+        assert!(apply_setting(&mut config, "timeout", "soon").is_err());
+        assert!(apply_setting(&mut config, "colour", "red").is_err());
+    }
+}
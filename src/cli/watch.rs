@@ -0,0 +1,170 @@
+//! `ares watch <file>`, reruns cracking whenever a file changes
+//!
+//! Handy when iteratively trimming or editing a payload: save the file and the
+//! new result is printed along with what changed since the previous run.
+//! The file is polled rather than watched, which also copes with editors that
+//! save by replacing the file. Press Ctrl-C to stop.
+
+use super::strip_trailing_newline;
+use crate::cli_pretty_printing::{statement, success, warning};
+use crate::config::Config;
+use crate::DecoderResult;
+use std::fs;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// How often the file is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What a single cracking run produced
+#[derive(Debug, Clone, PartialEq)]
+struct RunSummary {
+    /// The plaintext, if one was found
+    plaintext: Option<String>,
+    /// Decoders used to reach the plaintext
+    path: Vec<String>,
+}
+
+impl RunSummary {
+    /// Summarises the result of `perform_cracking`
+    fn from_result(result: Option<&DecoderResult>) -> Self {
+        RunSummary {
+            plaintext: result.and_then(|result| result.text.first().map(ToString::to_string)),
+            path: result
+                .map(|result| {
+                    result
+                        .path
+                        .iter()
+                        .map(|step| step.decoder.to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// One line per field, as shown in the diff
+    fn lines(&self) -> [String; 2] {
+        [
+            format!(
+                "plaintext: {}",
+                self.plaintext.as_deref().unwrap_or("(not found)")
+            ),
+            format!("path: {}", self.path.join(" → ")),
+        ]
+    }
+}
+
+/// Describes how a run differs from the previous one.
+/// Returns `(removed, added)` lines, both empty if nothing changed.
+fn diff_runs(previous: Option<&RunSummary>, current: &RunSummary) -> (Vec<String>, Vec<String>) {
+    let current_lines = current.lines();
+    let Some(previous) = previous else {
+        return (Vec::new(), current_lines.to_vec());
+    };
+    let previous_lines = previous.lines();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for (old, new) in previous_lines.iter().zip(current_lines.iter()) {
+        if old != new {
+            removed.push(old.clone());
+            added.push(new.clone());
+        }
+    }
+    (removed, added)
+}
+
+/// Watches `file_path` and cracks its contents every time they change.
+/// Runs until the process is interrupted.
+///
+/// # Errors
+/// Returns an error if the file cannot be read when watching starts.
+pub fn run_watch(file_path: &str, mut config: Config) -> std::io::Result<()> {
+    // Each run prints its own summary, and prompting on every save would get in the way
+    config.api_mode = true;
+    config.human_checker_on = false;
+
+    let mut last_contents = strip_trailing_newline(fs::read_to_string(file_path)?);
+    let mut previous: Option<RunSummary> = None;
+    println!(
+        "{}",
+        statement(
+            &format!("Watching {} for changes. Press Ctrl-C to stop.", file_path),
+            None,
+            &config
+        )
+    );
+    loop {
+        let result = crate::perform_cracking(&last_contents, config.clone());
+        let current = RunSummary::from_result(result.as_ref());
+        let (removed, added) = diff_runs(previous.as_ref(), &current);
+        if removed.is_empty() && added.is_empty() {
+            println!("{}", statement("Result unchanged", None, &config));
+        }
+        for line in removed {
+            println!("{}", warning(&format!("- {}", line), &config));
+        }
+        for line in added {
+            println!("{}", success(&format!("+ {}", line), &config));
+        }
+        previous = Some(current);
+
+        // Wait for the contents to change. A missing file is expected while an editor saves
+        last_contents = loop {
+            sleep(POLL_INTERVAL);
+            match fs::read_to_string(file_path) {
+                Ok(contents) => {
+                    let contents = strip_trailing_newline(contents);
+                    if contents != last_contents {
+                        break contents;
+                    }
+                }
+                Err(e) => log::debug!("Could not read {}: {}", file_path, e),
+            }
+        };
+        println!();
+        println!(
+            "{}",
+            statement(
+                &format!("{} changed, cracking again", file_path),
+                None,
+                &config
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(plaintext: Option<&str>, path: &[&str]) -> RunSummary {
+        RunSummary {
+            plaintext: plaintext.map(str::to_string),
+            path: path.iter().map(|decoder| decoder.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn first_run_only_adds() {
+        let current = summary(Some("hello"), &["Base64"]);
+        let (removed, added) = diff_runs(None, &current);
+        assert!(removed.is_empty());
+        assert_eq!(added, vec!["plaintext: hello", "path: Base64"]);
+    }
+
+    #[test]
+    fn diff_only_shows_changed_lines() {
+        let previous = summary(Some("hello"), &["Base64"]);
+        let current = summary(Some("hello"), &["Base64", "rot13"]);
+        let (removed, added) = diff_runs(Some(&previous), &current);
+        assert_eq!(removed, vec!["path: Base64"]);
+        assert_eq!(added, vec!["path: Base64 → rot13"]);
+
+        let (removed, added) = diff_runs(Some(&current), &current.clone());
+        assert!(removed.is_empty() && added.is_empty());
+
+        let failed = summary(None, &[]);
+        let (removed, _) = diff_runs(Some(&current), &failed);
+        assert_eq!(removed.len(), 2);
+    }
+}
@@ -0,0 +1,220 @@
+//! Translations of the messages the CLI shows, for `--lang`
+//!
+//! Each language's messages are a [Fluent](https://projectfluent.org) file
+//! in `i18n/`, with the same message ids as `en.ftl`. The language is
+//! `config.lang` if set, otherwise the first one of the system's locales
+//! Ares is translated into, otherwise English. Messages missing from a
+//! translation are shown in English. Logs and debug output stay in English.
+//! The messages are only built with the cli feature, [`Lang`] always is so
+//! `Config.lang` is the same in every build.
+//!
+//! ```rust
+//! use ares::cli_pretty_printing::i18n::{message, message_with, Lang};
+//! use ares::config::Config;
+//! use fluent::fluent_args;
+//!
+//! let config = Config { lang: Some(Lang::De), ..Default::default() };
+//! assert_eq!(message(&config, "plaintext-is"), "Der Klartext ist:");
+//! let args = fluent_args!["count" => 1];
+//! assert_eq!(message_with(&config, "top-results-count", &args), "1 möglicher Klartext gefunden:");
+//! ```
+
+#[cfg(feature = "cli")]
+use crate::config::Config;
+#[cfg(feature = "cli")]
+use fluent::concurrent::FluentBundle;
+#[cfg(feature = "cli")]
+use fluent::{FluentArgs, FluentResource};
+#[cfg(feature = "cli")]
+use log::debug;
+#[cfg(feature = "cli")]
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "cli")]
+use std::collections::HashMap;
+
+/// The languages the CLI's messages are translated into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    /// English
+    En,
+    /// Spanish
+    Es,
+    /// French
+    Fr,
+    /// German
+    De,
+}
+
+impl Lang {
+    /// Every language, English first
+    pub const ALL: [Lang; 4] = [Lang::En, Lang::Es, Lang::Fr, Lang::De];
+
+    /// Its code, like `de`
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Es => "es",
+            Lang::Fr => "fr",
+            Lang::De => "de",
+        }
+    }
+
+    /// The language of a locale like `de_DE.UTF-8` or `fr-CA`, if the
+    /// messages are translated into it
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let code = locale.split(['-', '_', '.', '@']).next()?.to_ascii_lowercase();
+        Lang::ALL.into_iter().find(|lang| lang.code() == code)
+    }
+
+    /// Its messages
+    #[cfg(feature = "cli")]
+    fn source(self) -> &'static str {
+        match self {
+            Lang::En => include_str!("i18n/en.ftl"),
+            Lang::Es => include_str!("i18n/es.ftl"),
+            Lang::Fr => include_str!("i18n/fr.ftl"),
+            Lang::De => include_str!("i18n/de.ftl"),
+        }
+    }
+}
+
+/// Every language's messages, ready to be formatted
+#[cfg(feature = "cli")]
+static BUNDLES: Lazy<HashMap<Lang, FluentBundle<FluentResource>>> = Lazy::new(|| {
+    Lang::ALL
+        .into_iter()
+        .map(|lang| {
+            let resource = FluentResource::try_new(lang.source().to_string())
+                .unwrap_or_else(|(resource, errors)| {
+                    debug!("Errors parsing the {} messages: {:?}", lang.code(), errors);
+                    resource
+                });
+            let locale = lang.code().parse().unwrap_or_default();
+            let mut bundle = FluentBundle::new_concurrent(vec![locale]);
+            // Without this, arguments are wrapped in invisible direction marks
+            bundle.set_use_isolating(false);
+            if let Err(errors) = bundle.add_resource(resource) {
+                debug!("Errors adding the {} messages: {:?}", lang.code(), errors);
+            }
+            (lang, bundle)
+        })
+        .collect()
+});
+
+/// The language of the system's locale, if the messages are translated into it
+#[cfg(feature = "cli")]
+static SYSTEM_LANG: Lazy<Option<Lang>> =
+    Lazy::new(|| sys_locale::get_locales().find_map(|locale| Lang::from_locale(&locale)));
+
+/// The language messages are shown in: `config.lang` if set, otherwise the
+/// system's, otherwise English
+#[cfg(feature = "cli")]
+pub fn language(config: &Config) -> Lang {
+    config.lang.or(*SYSTEM_LANG).unwrap_or(Lang::En)
+}
+
+/// The message `id` in the config's language
+#[cfg(feature = "cli")]
+pub fn message(config: &Config, id: &str) -> String {
+    message_with(config, id, &FluentArgs::new())
+}
+
+/// The message `id` in the config's language, with its `{ $name }`s
+/// filled in from `args`. Unknown messages are shown as their id.
+#[cfg(feature = "cli")]
+pub fn message_with(config: &Config, id: &str, args: &FluentArgs) -> String {
+    let lang = language(config);
+    format(lang, id, args)
+        .or_else(|| format(Lang::En, id, args))
+        .unwrap_or_else(|| {
+            debug!("No message called {}", id);
+            id.to_string()
+        })
+}
+
+/// Whether `reply` answers yes to a (y/N) question, by starting with `y` or
+/// with the config's language's letter for yes
+#[cfg(feature = "cli")]
+pub fn is_yes(config: &Config, reply: &str) -> bool {
+    let reply = reply.trim_start().to_lowercase();
+    reply.starts_with('y') || reply.starts_with(&message(config, "yes"))
+}
+
+/// The message `id` in `lang`, if it has one
+#[cfg(feature = "cli")]
+fn format(lang: Lang, id: &str, args: &FluentArgs) -> Option<String> {
+    let bundle = BUNDLES.get(&lang)?;
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    let formatted = bundle.format_pattern(pattern, Some(args), &mut errors);
+    if !errors.is_empty() {
+        debug!("Errors formatting {} in {}: {:?}", id, lang.code(), errors);
+    }
+    Some(formatted.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "cli")]
+    use fluent::fluent_args;
+    #[cfg(feature = "cli")]
+    use std::collections::HashSet;
+
+    /// The message ids in a language's file, which start the lines defining them
+    #[cfg(feature = "cli")]
+    fn ids(lang: Lang) -> HashSet<&'static str> {
+        lang.source()
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id))
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn every_language_has_every_message() {
+        let english = ids(Lang::En);
+        for lang in Lang::ALL {
+            assert!(FluentResource::try_new(lang.source().to_string()).is_ok());
+            assert_eq!(ids(lang), english, "{} has different messages", lang.code());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn messages_are_shown_in_the_chosen_language() {
+        let config = |lang| Config {
+            lang: Some(lang),
+            ..Default::default()
+        };
+        let args = fluent_args!["count" => 3];
+        assert_eq!(
+            message_with(&config(Lang::En), "top-results-count", &args),
+            "Found 3 potential plaintext results:"
+        );
+        assert_eq!(
+            message_with(&config(Lang::Fr), "top-results-count", &args),
+            "3 textes en clair possibles trouvés :"
+        );
+        assert_eq!(
+            message(&config(Lang::Es), "plaintext-is"),
+            "El texto plano es:"
+        );
+        assert_eq!(message(&config(Lang::De), "no-such-message"), "no-such-message");
+        assert!(is_yes(&config(Lang::De), "Ja"));
+        assert!(is_yes(&config(Lang::De), "y"));
+        assert!(!is_yes(&config(Lang::En), "ja"));
+    }
+
+    #[test]
+    fn languages_are_read_from_locales() {
+        assert_eq!(Lang::from_locale("de_DE.UTF-8"), Some(Lang::De));
+        assert_eq!(Lang::from_locale("fr-CA"), Some(Lang::Fr));
+        assert_eq!(Lang::from_locale("es"), Some(Lang::Es));
+        assert_eq!(Lang::from_locale("C"), None);
+        assert_eq!(Lang::from_locale("ja_JP"), None);
+    }
+}
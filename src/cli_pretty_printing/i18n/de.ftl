@@ -0,0 +1,73 @@
+# Die Meldungen der Kommandozeile, auf Deutsch.
+
+## Antworten
+
+yes = j
+
+## Knacken
+
+decoded-times = 🥳 Ares hat { $count } Mal decodiert.
+countdown = { $passed } Sekunden vergangen. Noch { $remaining }
+human-checker-prompt =
+    🕵️ Ich glaube, der Klartext ist { $description }.
+    Möglicher Klartext: '{ $text }' (j/N):
+failed-to-decode =
+    ⛔️ Ares konnte den Text nicht decodieren.
+    Wenn du mehr Hilfe brauchst, frag in #coded-messages auf unserem Discord http://discord.skerritt.blog
+input-is-plaintext = Deine Eingabe ist bereits der Klartext 🥳
+unknown-config-key = Unbekannter Schlüssel in der Konfigurationsdatei: { $key }
+
+## Der Klartext und sein Pfad
+
+plaintext-is = Der Klartext ist:
+as-decoded = Wie decodiert: { $text }
+decoder-used = der verwendete Decoder ist { $decoder }
+decoders-used = die verwendeten Decoder sind:
+invisible-chars-prompt = { $percentage } des Klartexts sind unsichtbare Zeichen, möchtest du ihn stattdessen in einer Datei speichern? (j/N)
+enter-filename = Bitte gib einen Dateinamen ein: (Standard: { $default })
+outputting-to-file = Klartext wird in Datei gespeichert: { $path }
+verify-lossy = ⚠️ Erneutes Codieren ergab nicht die Eingabe von { $steps }, dem Klartext fehlt womöglich etwas
+verify-all = Erneutes Codieren des Klartexts ergibt die Eingabe jedes Schritts, der codieren kann
+verify-none = Kein Schritt des Pfads kann codieren, daher konnte keiner überprüft werden
+step-changes = Was jeder Schritt geändert hat:
+path-decoder = Decoder
+path-key = Schlüssel
+path-checker = Prüfer
+path-time = Zeit
+path-output = Ausgabe
+path-step-key = Schlüssel { $key }
+
+## Beste Ergebnisse
+
+top-result-found = [{ $seconds }s] Möglicher Klartext gefunden mit { $path }: { $plaintext }
+no-plaintexts-found = Keine möglichen Klartexte gefunden.
+top-results-title = 🎊 Liste möglicher Klartexte 🎊
+top-results-count =
+    { $count ->
+        [one] { $count } möglicher Klartext gefunden:
+       *[other] { $count } mögliche Klartexte gefunden:
+    }
+top-results-many = Es gibt mehr als 10 mögliche Klartexte. Ich denke, du solltest sie in eine Datei schreiben.
+write-to-file = Möchtest du sie in eine Datei schreiben? (j/N)
+results-written = Ergebnisse gespeichert in { $path }
+write-failed = Datei konnte nicht geschrieben werden: { $error }
+result-number = Ergebnis Nr. { $number }: { $text }
+result-decoder = Decoder: { $name }
+result-checker = Prüfer: { $name }
+result-description = Beschreibung: { $description }
+top-results-end = === Ende der besten Ergebnisse ===
+
+## Auflisten und Decodieren
+
+popularity = Beliebtheit { $popularity }
+decoder-with-key = { $decoder } (Schlüssel { $key })
+no-decoder-output = Der Decoder hat für diese Eingabe nichts ausgegeben.
+
+## Suchstatistik, im ausführlichen Modus
+
+stats-title = Was die Suche getan hat:
+stats-nodes = Erweiterte Knoten
+stats-decoders = Ausgeführte Decoder
+stats-checkers = Prüferaufrufe
+stats-cache-hits = Cache-Treffer
+stats-frontier = Größte Suchfront
@@ -0,0 +1,75 @@
+# The messages the command line shows, in English.
+# Every other language has the same messages, see i18n.rs.
+
+## Answers
+
+# The letter which answers yes to the (y/N) questions. y always does.
+yes = y
+
+## Cracking
+
+decoded-times = 🥳 Ares has decoded { $count } times.
+countdown = { $passed } seconds have passed. { $remaining } remaining
+human-checker-prompt =
+    🕵️ I think the plaintext is { $description }.
+    Possible plaintext: '{ $text }' (y/N):
+failed-to-decode =
+    ⛔️ Ares has failed to decode the text.
+    If you want more help, please ask in #coded-messages in our Discord http://discord.skerritt.blog
+input-is-plaintext = Your input text is the plaintext 🥳
+unknown-config-key = Unknown configuration key found in config file: { $key }
+
+## The plaintext and its path
+
+plaintext-is = The plaintext is:
+as-decoded = As decoded: { $text }
+decoder-used = the decoder used is { $decoder }
+decoders-used = the decoders used are:
+invisible-chars-prompt = { $percentage } of the plaintext is invisible characters, would you like to save to a file instead? (y/N)
+enter-filename = Please enter a filename: (default: { $default })
+outputting-to-file = Outputting plaintext to file: { $path }
+verify-lossy = ⚠️ Re-encoding didn't give back the input of { $steps }, so the plaintext may be missing something
+verify-all = Re-encoding the plaintext gives back the input of every step which can encode
+verify-none = No step on the path can encode, so none could be verified
+step-changes = What each step changed:
+path-decoder = Decoder
+path-key = Key
+path-checker = Checker
+path-time = Time
+path-output = Output
+path-step-key = key { $key }
+
+## Top results
+
+top-result-found = [{ $seconds }s] Found a possible plaintext with { $path }: { $plaintext }
+no-plaintexts-found = No potential plaintexts found.
+top-results-title = 🎊 List of Possible Plaintexts 🎊
+top-results-count =
+    { $count ->
+        [one] Found { $count } potential plaintext result:
+       *[other] Found { $count } potential plaintext results:
+    }
+top-results-many = There are more than 10 possible plaintexts. I think you should write them to a file.
+write-to-file = Would you like to write to a file? (y/N)
+results-written = Results written to { $path }
+write-failed = Failed to write to file: { $error }
+result-number = Result #{ $number }: { $text }
+result-decoder = Decoder: { $name }
+result-checker = Checker: { $name }
+result-description = Description: { $description }
+top-results-end = === End of Top Results ===
+
+## Listing and decoding
+
+popularity = popularity { $popularity }
+decoder-with-key = { $decoder } (key { $key })
+no-decoder-output = The decoder did not produce any output for this input.
+
+## Search statistics, in verbose mode
+
+stats-title = What the search did:
+stats-nodes = Nodes expanded
+stats-decoders = Decoders run
+stats-checkers = Checker calls
+stats-cache-hits = Cache hits
+stats-frontier = Peak frontier size
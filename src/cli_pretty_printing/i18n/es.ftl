@@ -0,0 +1,73 @@
+# Los mensajes de la línea de comandos, en español.
+
+## Respuestas
+
+yes = s
+
+## Descifrado
+
+decoded-times = 🥳 Ares ha decodificado { $count } veces.
+countdown = Han pasado { $passed } segundos. Quedan { $remaining }
+human-checker-prompt =
+    🕵️ Creo que el texto plano es { $description }.
+    Posible texto plano: '{ $text }' (s/N):
+failed-to-decode =
+    ⛔️ Ares no ha podido decodificar el texto.
+    Si necesitas más ayuda, pregunta en #coded-messages en nuestro Discord http://discord.skerritt.blog
+input-is-plaintext = El texto de entrada ya es el texto plano 🥳
+unknown-config-key = Clave de configuración desconocida en el archivo de configuración: { $key }
+
+## El texto plano y su ruta
+
+plaintext-is = El texto plano es:
+as-decoded = Tal como se decodificó: { $text }
+decoder-used = el decodificador usado es { $decoder }
+decoders-used = los decodificadores usados son:
+invisible-chars-prompt = El { $percentage } del texto plano son caracteres invisibles, ¿quieres guardarlo en un archivo? (s/N)
+enter-filename = Introduce un nombre de archivo: (por defecto: { $default })
+outputting-to-file = Guardando el texto plano en el archivo: { $path }
+verify-lossy = ⚠️ Al volver a codificar no se obtuvo la entrada de { $steps }, así que puede que al texto plano le falte algo
+verify-all = Al volver a codificar el texto plano se obtiene la entrada de cada paso que puede codificar
+verify-none = Ningún paso de la ruta puede codificar, así que no se pudo verificar ninguno
+step-changes = Lo que cambió cada paso:
+path-decoder = Decodificador
+path-key = Clave
+path-checker = Comprobador
+path-time = Tiempo
+path-output = Salida
+path-step-key = clave { $key }
+
+## Mejores resultados
+
+top-result-found = [{ $seconds }s] Posible texto plano encontrado con { $path }: { $plaintext }
+no-plaintexts-found = No se encontraron posibles textos planos.
+top-results-title = 🎊 Lista de posibles textos planos 🎊
+top-results-count =
+    { $count ->
+        [one] Se encontró { $count } posible texto plano:
+       *[other] Se encontraron { $count } posibles textos planos:
+    }
+top-results-many = Hay más de 10 posibles textos planos. Creo que deberías guardarlos en un archivo.
+write-to-file = ¿Quieres guardarlos en un archivo? (s/N)
+results-written = Resultados guardados en { $path }
+write-failed = No se pudo escribir el archivo: { $error }
+result-number = Resultado n.º { $number }: { $text }
+result-decoder = Decodificador: { $name }
+result-checker = Comprobador: { $name }
+result-description = Descripción: { $description }
+top-results-end = === Fin de los mejores resultados ===
+
+## Listados y decodificación
+
+popularity = popularidad { $popularity }
+decoder-with-key = { $decoder } (clave { $key })
+no-decoder-output = El decodificador no produjo ninguna salida para esta entrada.
+
+## Estadísticas de la búsqueda, en modo detallado
+
+stats-title = Lo que hizo la búsqueda:
+stats-nodes = Nodos expandidos
+stats-decoders = Decodificadores ejecutados
+stats-checkers = Llamadas a comprobadores
+stats-cache-hits = Aciertos de caché
+stats-frontier = Tamaño máximo de la frontera
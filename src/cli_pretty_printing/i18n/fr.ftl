@@ -0,0 +1,73 @@
+# Les messages de la ligne de commande, en français.
+
+## Réponses
+
+yes = o
+
+## Déchiffrement
+
+decoded-times = 🥳 Ares a décodé { $count } fois.
+countdown = { $passed } secondes écoulées. Encore { $remaining }
+human-checker-prompt =
+    🕵️ Je pense que le texte en clair est { $description }.
+    Texte en clair possible : '{ $text }' (o/N) :
+failed-to-decode =
+    ⛔️ Ares n'a pas réussi à décoder le texte.
+    Pour plus d'aide, demandez dans #coded-messages sur notre Discord http://discord.skerritt.blog
+input-is-plaintext = Le texte saisi est déjà le texte en clair 🥳
+unknown-config-key = Clé de configuration inconnue dans le fichier de configuration : { $key }
+
+## Le texte en clair et son chemin
+
+plaintext-is = Le texte en clair est :
+as-decoded = Tel que décodé : { $text }
+decoder-used = le décodeur utilisé est { $decoder }
+decoders-used = les décodeurs utilisés sont :
+invisible-chars-prompt = { $percentage } du texte en clair sont des caractères invisibles, voulez-vous plutôt l'enregistrer dans un fichier ? (o/N)
+enter-filename = Saisissez un nom de fichier : (par défaut : { $default })
+outputting-to-file = Enregistrement du texte en clair dans le fichier : { $path }
+verify-lossy = ⚠️ Le réencodage n'a pas redonné l'entrée de { $steps }, il manque peut-être quelque chose au texte en clair
+verify-all = Le réencodage du texte en clair redonne l'entrée de chaque étape capable d'encoder
+verify-none = Aucune étape du chemin ne sait encoder, donc aucune n'a pu être vérifiée
+step-changes = Ce que chaque étape a changé :
+path-decoder = Décodeur
+path-key = Clé
+path-checker = Vérificateur
+path-time = Durée
+path-output = Sortie
+path-step-key = clé { $key }
+
+## Meilleurs résultats
+
+top-result-found = [{ $seconds }s] Texte en clair possible trouvé avec { $path } : { $plaintext }
+no-plaintexts-found = Aucun texte en clair possible trouvé.
+top-results-title = 🎊 Liste des textes en clair possibles 🎊
+top-results-count =
+    { $count ->
+        [one] { $count } texte en clair possible trouvé :
+       *[other] { $count } textes en clair possibles trouvés :
+    }
+top-results-many = Il y a plus de 10 textes en clair possibles. Je vous conseille de les enregistrer dans un fichier.
+write-to-file = Voulez-vous les enregistrer dans un fichier ? (o/N)
+results-written = Résultats enregistrés dans { $path }
+write-failed = Impossible d'écrire le fichier : { $error }
+result-number = Résultat n° { $number } : { $text }
+result-decoder = Décodeur : { $name }
+result-checker = Vérificateur : { $name }
+result-description = Description : { $description }
+top-results-end = === Fin des meilleurs résultats ===
+
+## Listes et décodage
+
+popularity = popularité { $popularity }
+decoder-with-key = { $decoder } (clé { $key })
+no-decoder-output = Le décodeur n'a rien produit pour cette entrée.
+
+## Statistiques de la recherche, en mode verbeux
+
+stats-title = Ce qu'a fait la recherche :
+stats-nodes = Nœuds développés
+stats-decoders = Décodeurs exécutés
+stats-checkers = Appels aux vérificateurs
+stats-cache-hits = Succès du cache
+stats-frontier = Taille maximale de la frontière
@@ -27,15 +27,48 @@
 //! println!("{}", warning("Please check your input", &ares::config::Config::default()));
 //! ```
 
+/// Translations of the messages below, for `--lang`
+pub mod i18n;
+/// Progress bar or JSON lines on stderr for `--progress`
+#[cfg(feature = "cli")]
+pub mod progress;
+/// Report generation (CSV, Markdown, HTML) for `--report`
+pub mod report;
+/// The decode path drawn as a table or a tree, fitted to the terminal
+#[cfg(feature = "cli")]
+pub mod path_view;
+/// What each step of a path changed, highlighted, for `-v`
+#[cfg(feature = "cli")]
+pub mod step_diff;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "cli")]
+use crate::byte_text::Candidate;
+#[cfg(feature = "cli")]
+use crate::postprocess::make_readable;
+#[cfg(feature = "cli")]
 use crate::storage;
+#[cfg(feature = "cli")]
 use crate::storage::wait_athena_storage::PlaintextResult;
+#[cfg(feature = "cli")]
 use crate::DecoderResult;
+#[cfg(feature = "cli")]
+use crate::decoders::crack_results::CrackResult;
+#[cfg(feature = "cli")]
+use crate::searchers::stats::SearchStats;
 use crate::config::Config;
+#[cfg(feature = "cli")]
 use colored::Colorize;
+#[cfg(feature = "cli")]
+use fluent::fluent_args;
+#[cfg(feature = "cli")]
+use i18n::{is_yes, message, message_with};
+use serde::Serialize;
+#[cfg(feature = "cli")]
 use std::env;
+#[cfg(feature = "cli")]
 use std::fs::write;
+#[cfg(feature = "cli")]
 use text_io::read;
 
 /// Parse RGB string in format "r,g,b" to RGB values.
@@ -126,6 +159,7 @@ pub fn parse_rgb(rgb: &str) -> Option<(u8, u8, u8)> {
 /// - success: Used for success messages
 /// - question: Used for interactive prompts
 /// - statement: Used for neutral messages
+#[cfg(feature = "cli")]
 fn color_string(text: &str, role: &str, config: &Config) -> String {
     // config passed as argument
 
@@ -152,6 +186,12 @@ fn color_string(text: &str, role: &str, config: &Config) -> String {
     }
 }
 
+/// Without the cli feature there is no terminal to color for, so the text is left plain
+#[cfg(not(feature = "cli"))]
+fn color_string(text: &str, _role: &str, _config: &Config) -> String {
+    text.to_string()
+}
+
 /// Colors text based on its role, defaulting to statement color if no role is specified.
 ///
 /// # Arguments
@@ -230,6 +270,7 @@ fn error(text: &str, config: &Config) -> String {
 ///
 /// # Returns
 /// * `String` - The text colored in the question color
+#[cfg(feature = "cli")]
 fn question(text: &str, config: &Config) -> String {
     color_string(text, "question", config)
 }
@@ -244,12 +285,13 @@ fn question(text: &str, config: &Config) -> String {
 ///
 /// # Behavior
 /// - Checks for API mode and returns early if enabled
-/// - Formats the decoder path with arrows
+/// - Draws a path of several decoders as a table or a tree, see [`path_view`]
 /// - Handles invisible character detection and file output
 /// - Presents the decoded text with appropriate formatting
 ///
 /// # Panics
 /// Panics if there is an error writing to file when output_method is set to a file
+#[cfg(feature = "cli")]
 pub fn program_exiting_successful_decoding(result: DecoderResult, config: &Config) {
     // config passed as argument
     if config.api_mode {
@@ -258,31 +300,33 @@ pub fn program_exiting_successful_decoding(result: DecoderResult, config: &Confi
     if config.top_results {
         return;
     }
-    let plaintext = result.text;
-    // calculate path
-    let decoded_path = result
-        .path
-        .iter()
-        .map(|c| c.decoder)
-        .collect::<Vec<_>>()
-        .join(" → ");
-
-    let decoded_path_coloured = statement(&decoded_path, Some("informational"), config);
-    let decoded_path_string = if !decoded_path.contains('→') {
-        // handles case where only 1 decoder is used
-        format!("the decoder used is {decoded_path_coloured}")
+    // In verbose mode each step is shown, not just the decoders' names,
+    // along with what it changed
+    let explanation = (config.verbose > 0).then(|| result.explain());
+    let diffs = (config.verbose > 0).then(|| step_diff::render_step_diffs(&result, config));
+    let verification = config
+        .verify
+        .then(|| verification_statement(&result.path, config));
+    let decoded_path_string = if result.path.len() > 1 {
+        let view = path_view::render_path(&result, config, path_view::terminal_width());
+        format!(
+            "{}\n{}",
+            message(config, "decoders-used"),
+            statement(&view, Some("informational"), config)
+        )
     } else {
-        format!("the decoders used are {decoded_path_coloured}")
+        // handles case where only 1 decoder is used
+        let decoder = result.path.first().map(|c| c.decoder).unwrap_or_default();
+        let decoder = statement(decoder, Some("informational"), config);
+        message_with(config, "decoder-used", &fluent_args!["decoder" => decoder])
     };
+    let plaintext = result.text;
     /// If 30% of the characters are invisible characters, then prompt the
     /// user to save the resulting plaintext into a file
     const INVIS_CHARS_DETECTION_PERCENTAGE: f64 = 0.3;
     let mut invis_chars_found: f64 = 0.0;
-    for char in plaintext[0].chars() {
-        if storage::INVISIBLE_CHARS
-            .iter()
-            .any(|invis_chars| *invis_chars == char)
-        {
+    for char in plaintext[0].text().chars() {
+        if storage::invisible_chars::is_invisible(char) {
             invis_chars_found += 1.0;
         }
     }
@@ -290,43 +334,89 @@ pub fn program_exiting_successful_decoding(result: DecoderResult, config: &Confi
     // If the percentage of invisible characters in the plaintext exceeds
     // the detection percentage, prompt the user asking if they want to
     // save the plaintext into a file
-    let invis_char_percentage = invis_chars_found / plaintext[0].len() as f64;
+    let invis_char_percentage = invis_chars_found / plaintext[0].text().len() as f64;
     if invis_char_percentage > INVIS_CHARS_DETECTION_PERCENTAGE {
         let invis_char_percentage_string = format!("{:2.0}%", invis_char_percentage * 100.0);
+        let percentage = invis_char_percentage_string.white().bold().to_string();
         println!(
             "{}",
             question(
-                &format!(
-                    "{} of the plaintext is invisible characters, would you like to save to a file instead? (y/N)", 
-                    invis_char_percentage_string.white().bold()
-                )
-            , config)
+                &message_with(config, "invisible-chars-prompt", &fluent_args!["percentage" => percentage]),
+                config
+            )
         );
         let reply: String = read!("{}\n");
-        let result = reply.to_ascii_lowercase().starts_with('y');
-        if result {
-            println!(
-                "Please enter a filename: (default: {}/ares_text.txt)",
-                env::var("HOME").unwrap_or_default().white().bold()
-            );
+        if is_yes(config, &reply) {
+            let default_path = format!("{}/ares_text.txt", env::var("HOME").unwrap_or_default());
+            let default = default_path.white().bold().to_string();
+            println!("{}", message_with(config, "enter-filename", &fluent_args!["default" => default]));
             let mut file_path: String = read!("{}\n");
             if file_path.is_empty() {
-                file_path = format!("{}/ares_text.txt", env::var("HOME").unwrap_or_default());
+                file_path = default_path;
             }
+            let path = statement(&file_path, None, config);
             println!(
-                "Outputting plaintext to file: {}\n\n{}",
-                statement(&file_path, None, config),
+                "{}\n\n{}",
+                message_with(config, "outputting-to-file", &fluent_args!["path" => path]),
                 decoded_path_string
             );
-            write(file_path, &plaintext[0]).expect("Error writing to file.");
+            write(file_path, plaintext[0].bytes()).expect("Error writing to file.");
             return;
         }
     }
-    println!(
-        "The plaintext is:\n{}\n{}",
-        success(&plaintext[0], config),
-        decoded_path_string
-    );
+    let readable = config
+        .readable
+        .then(|| make_readable(&plaintext[0].text(), &config.languages))
+        .flatten();
+    match readable {
+        Some(readable) => println!(
+            "{}\n{}\n{}\n{}",
+            message(config, "plaintext-is"),
+            success(&readable, config),
+            statement(
+                &message_with(config, "as-decoded", &fluent_args!["text" => plaintext[0].text()]),
+                None,
+                config
+            ),
+            decoded_path_string
+        ),
+        None => println!(
+            "{}\n{}\n{}",
+            message(config, "plaintext-is"),
+            success(&plaintext[0].text(), config),
+            decoded_path_string
+        ),
+    }
+    if let Some(verification) = verification {
+        println!("{verification}");
+    }
+    if let Some(explanation) = explanation {
+        println!("\n{explanation}");
+    }
+    if let Some(diffs) = diffs {
+        println!("\n{}\n{diffs}", message(config, "step-changes"));
+    }
+}
+
+/// Says whether re-encoding the plaintext back along the path, with
+/// `--verify`, gave back each step's input, naming the steps which didn't
+#[cfg(feature = "cli")]
+fn verification_statement(path: &[CrackResult], config: &Config) -> String {
+    let lossy: Vec<&str> = path
+        .iter()
+        .filter(|step| step.verified == Some(false))
+        .map(|step| step.decoder)
+        .collect();
+    if !lossy.is_empty() {
+        warning(
+            &message_with(config, "verify-lossy", &fluent_args!["steps" => lossy.join(", ")]),
+            config,
+        )
+    } else if path.iter().any(|step| step.verified == Some(true)) {
+        success(&message(config, "verify-all"), config)
+    } else {
+        statement(&message(config, "verify-none"), None, config)
+    }
 }
 
 /// Prints the number of decoding attempts performed.
@@ -337,6 +427,7 @@ pub fn program_exiting_successful_decoding(result: DecoderResult, config: &Confi
 /// # Note
 /// This function automatically calculates the total number of attempts
 /// based on the available decoders and the depth parameter.
+#[cfg(feature = "cli")]
 pub fn decoded_how_many_times(depth: u32, config: &Config) {
     // config passed as argument
     if config.api_mode {
@@ -347,9 +438,10 @@ pub fn decoded_how_many_times(depth: u32, config: &Config) {
     // Then we add 25 for Caesar
     let decoders = crate::filtration_system::filter_and_get_decoders(&DecoderResult::default());
     let decoded_times_int = depth * (decoders.components.len() as u32 + 40); //TODO 40 is how many decoders we have. Calculate automatically
+    let count = statement(&decoded_times_int.to_string(), None, config);
     println!(
-        "\n🥳 Ares has decoded {} times.\n",
-        statement(&decoded_times_int.to_string(), None, config)
+        "\n{}\n",
+        message_with(config, "decoded-times", &fluent_args!["count" => count])
     );
 }
 
@@ -362,12 +454,13 @@ pub fn decoded_how_many_times(depth: u32, config: &Config) {
 /// # Note
 /// This function is only called when human checking is enabled and
 /// not in API mode.
+#[cfg(feature = "cli")]
 pub fn human_checker_check(description: &str, text: &str, config: &Config) {
-    println!(
-        "🕵️ I think the plaintext is {}.\nPossible plaintext: '{}' (y/N): ",
-        statement(description, Some("informational"), config),
-        statement(text, Some("informational"), config)
-    );
+    let args = fluent_args![
+        "description" => statement(description, Some("informational"), config),
+        "text" => statement(text, Some("informational"), config)
+    ];
+    println!("{} ", message_with(config, "human-checker-prompt", &args));
 }
 
 /// Prints a failure message when decoding was unsuccessful.
@@ -377,6 +470,7 @@ pub fn human_checker_check(description: &str, text: &str, config: &Config) {
 ///
 /// # Note
 /// This message is suppressed in API mode.
+#[cfg(feature = "cli")]
 pub fn failed_to_decode(config: &Config) {
     // config passed as argument
     if config.api_mode {
@@ -385,10 +479,37 @@ pub fn failed_to_decode(config: &Config) {
 
     println!(
         "{}",
-        warning("⛔️ Ares has failed to decode the text.\nIf you want more help, please ask in #coded-messages in our Discord http://discord.skerritt.blog", config)
+        warning(&message(config, "failed-to-decode"), config)
     );
 }
 
+/// Prints how many nodes, decoders, checkers and cache hits the search took,
+/// so the user can see why a run was slow.
+///
+/// # Note
+/// This is only shown in verbose mode, and never in API mode.
+#[cfg(feature = "cli")]
+pub fn search_stats(stats: &SearchStats, config: &Config) {
+    if config.verbose == 0 || config.api_mode {
+        return;
+    }
+    let counts = [
+        ("stats-nodes", stats.nodes_expanded),
+        ("stats-decoders", stats.decoders_run),
+        ("stats-checkers", stats.checker_calls),
+        ("stats-cache-hits", stats.cache_hits),
+        ("stats-frontier", stats.peak_frontier),
+    ];
+    println!("\n{}", message(config, "stats-title"));
+    for (id, count) in counts {
+        println!(
+            "  {}: {}",
+            message(config, id),
+            statement(&count.to_string(), None, config)
+        );
+    }
+}
+
 /// Updates the user on decoding progress with a countdown timer.
 ///
 /// # Arguments
@@ -397,21 +518,23 @@ pub fn failed_to_decode(config: &Config) {
 ///
 /// # Note
 /// Progress updates are shown every 5 seconds until the duration is reached.
+#[cfg(feature = "cli")]
 pub fn countdown_until_program_ends(seconds_spent_running: u32, duration: u32, config: &Config) {
     // config passed as argument
-    if config.api_mode {
+    // The progress bar already shows the time spent
+    if config.api_mode || config.progress.is_some() {
         return;
     }
-    if seconds_spent_running % 5 == 0 && seconds_spent_running != 0 {
+    if seconds_spent_running.is_multiple_of(5) && seconds_spent_running != 0 {
         let time_left = duration - seconds_spent_running;
         if time_left == 0 {
             return;
         }
-        println!(
-            "{} seconds have passed. {} remaining",
-            statement(&seconds_spent_running.to_string(), None, config),
-            statement(&time_left.to_string(), None, config)
-        );
+        let args = fluent_args![
+            "passed" => statement(&seconds_spent_running.to_string(), None, config),
+            "remaining" => statement(&time_left.to_string(), None, config)
+        ];
+        println!("{}", message_with(config, "countdown", &args));
     }
 }
 
@@ -419,12 +542,13 @@ pub fn countdown_until_program_ends(seconds_spent_running: u32, duration: u32, c
 ///
 /// This function is called when the input passes plaintext detection
 /// and no decoding is necessary.
+#[cfg(feature = "cli")]
 pub fn return_early_because_input_text_is_plaintext(config: &Config) {
     // config passed as argument
     if config.api_mode {
         return;
     }
-    println!("{}", success("Your input text is the plaintext 🥳", config));
+    println!("{}", success(&message(config, "input-is-plaintext"), config));
 }
 
 /// Handles the error case of receiving both file and text input.
@@ -460,6 +584,7 @@ pub fn panic_failure_no_input_provided(config: &Config) {
 ///
 /// # Note
 /// This warning is suppressed in API mode.
+#[cfg(feature = "cli")]
 pub fn warning_unknown_config_key(key: &str, config: &Config) {
     // config passed as argument
     if config.api_mode {
@@ -467,17 +592,49 @@ pub fn warning_unknown_config_key(key: &str, config: &Config) {
     }
     eprintln!(
         "{}",
-        warning(&format!(
-            "Unknown configuration key found in config file: {}",
-            key
-        ), config)
+        warning(
+            &message_with(config, "unknown-config-key", &fluent_args!["key" => key]),
+            config
+        )
     );
 }
 
+/// Shows a plaintext a top results search found as soon as it is found, with
+/// how far into the search it was and the decoders which led to it. The ranked
+/// list still comes at the end, but long searches, or ones stopped early,
+/// show what they have found along the way.
+#[cfg(feature = "cli")]
+pub fn top_result_found(result: &DecoderResult, elapsed: std::time::Duration, config: &Config) {
+    // config passed as argument
+    if config.api_mode {
+        return;
+    }
+    println!("{}", success(&top_result_line(result, elapsed, config), config));
+}
+
+/// The line [`top_result_found`] shows
+#[cfg(feature = "cli")]
+fn top_result_line(result: &DecoderResult, elapsed: std::time::Duration, config: &Config) -> String {
+    let path = result
+        .path
+        .iter()
+        .map(|step| step.decoder)
+        .collect::<Vec<_>>()
+        .join(" → ");
+    let plaintext = result.text.first().map(Candidate::text).unwrap_or_default();
+    let args = fluent_args![
+        "seconds" => format!("{:.1}", elapsed.as_secs_f32()),
+        "path" => path,
+        "plaintext" => plaintext
+    ];
+    message_with(config, "top-result-found", &args)
+}
+
 /// Display all plaintext results collected by WaitAthena
 ///
 /// # Panics
 /// Panics if it fails to read from stdin when prompting the user.
+#[cfg(feature = "cli")]
 pub fn display_top_results(results: &[PlaintextResult], config: &Config) {
     // config passed as argument
     if config.api_mode {
@@ -485,36 +642,36 @@ pub fn display_top_results(results: &[PlaintextResult], config: &Config) {
     }
 
     if results.is_empty() {
-        println!("{}", success("No potential plaintexts found.", config));
+        println!("{}", success(&message(config, "no-plaintexts-found"), config));
         return;
     }
 
-    println!("{}", success("\n🎊 List of Possible Plaintexts 🎊", config));
+    println!("{}", success(&format!("\n{}", message(config, "top-results-title")), config));
     println!(
         "{}",
-        success(&format!(
-            "Found {} potential plaintext results:",
-            results.len()
-        ), config)
+        success(
+            &message_with(config, "top-results-count", &fluent_args!["count" => results.len()]),
+            config
+        )
     );
 
     if results.len() > 10 {
         // ask the user if they want to write to a file
-        println!("{}", warning("There are more than 10 possible plaintexts. I think you should write them to a file.", config));
-        println!("{}", question("Would you like to write to a file? (y/N)", config));
+        println!("{}", warning(&message(config, "top-results-many"), config));
+        println!("{}", question(&message(config, "write-to-file"), config));
         let mut input = String::new();
         std::io::stdin()
             .read_line(&mut input)
             .expect("Failed to read input");
-        let result = input.trim().to_ascii_lowercase().starts_with('y');
-
-        if result {
+        if is_yes(config, &input) {
+            let default = statement(
+                &format!("{}/ares_text.txt", env::var("HOME").unwrap_or_default()),
+                None,
+                config,
+            );
             println!(
                 "{}",
-                question(&format!(
-                    "Please enter a filename: (default: {}/ares_text.txt)",
-                    statement(&env::var("HOME").unwrap_or_default(), None, config)
-                ), config)
+                question(&message_with(config, "enter-filename", &fluent_args!["default" => default]), config)
             );
 
             let mut file_path = String::new();
@@ -529,18 +686,28 @@ pub fn display_top_results(results: &[PlaintextResult], config: &Config) {
 
             let mut file_content = String::new();
             for (i, result) in results.iter().enumerate() {
-                file_content.push_str(&format!("Result #{}: {}\n", i + 1, result.text));
-                file_content.push_str(&format!("Decoder: {}\n", result.decoder_name));
-                file_content.push_str(&format!("Checker: {}\n", result.checker_name));
-                file_content.push_str(&format!("Description: {}\n", result.description));
+                let args = fluent_args!["number" => i + 1, "text" => result.text.as_str()];
+                file_content.push_str(&format!("{}\n", message_with(config, "result-number", &args)));
+                let args = fluent_args!["name" => result.decoder_name.as_str()];
+                file_content.push_str(&format!("{}\n", message_with(config, "result-decoder", &args)));
+                let args = fluent_args!["name" => result.checker_name.as_str()];
+                file_content.push_str(&format!("{}\n", message_with(config, "result-checker", &args)));
+                let args = fluent_args!["description" => result.description.as_str()];
+                file_content.push_str(&format!("{}\n", message_with(config, "result-description", &args)));
                 if results.len() > 1 {
                     file_content.push_str("---\n");
                 }
             }
 
             match write(&file_path, file_content) {
-                Ok(_) => println!("{}", success(&format!("Results written to {}", file_path), config)),
-                Err(e) => println!("{}", warning(&format!("Failed to write to file: {}", e), config)),
+                Ok(_) => println!(
+                    "{}",
+                    success(&message_with(config, "results-written", &fluent_args!["path" => file_path]), config)
+                ),
+                Err(e) => println!(
+                    "{}",
+                    warning(&message_with(config, "write-failed", &fluent_args!["error" => e.to_string()]), config)
+                ),
             }
 
             return;
@@ -548,23 +715,153 @@ pub fn display_top_results(results: &[PlaintextResult], config: &Config) {
     }
 
     for (i, result) in results.iter().enumerate() {
+        let readable = config
+            .readable
+            .then(|| make_readable(&result.text, &config.languages))
+            .flatten();
+        let args = fluent_args![
+            "number" => i + 1,
+            "text" => readable.as_deref().unwrap_or(&result.text)
+        ];
+        println!("{}", success(&message_with(config, "result-number", &args), config));
+        if readable.is_some() {
+            let args = fluent_args!["text" => result.text.as_str()];
+            println!("{}", success(&message_with(config, "as-decoded", &args), config));
+        }
+        let args = fluent_args!["name" => result.decoder_name.as_str()];
+        println!("{}", success(&message_with(config, "result-decoder", &args), config));
+        let args = fluent_args!["name" => result.checker_name.as_str()];
+        println!("{}", success(&message_with(config, "result-checker", &args), config));
+        let args = fluent_args!["description" => result.description.as_str()];
+        println!("{}", success(&message_with(config, "result-description", &args), config));
+        if results.len() > 1 {
+            // only print seperator if more than 1
+            println!("{}", success("---", config));
+        }
+    }
+
+    println!("{}", success(&format!("{}\n", message(config, "top-results-end")), config));
+}
+
+/// Information about a registered decoder or checker, used by
+/// `--list-decoders` and `--list-checkers`
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentInfo {
+    /// The name of the decoder or checker, as used in include/exclude lists
+    pub name: String,
+    /// The tags it has
+    pub tags: Vec<String>,
+    /// How popular it is, between 0.0 and 1.0
+    pub popularity: f32,
+    /// A short description
+    pub description: String,
+}
+
+/// Collects information about every registered decoder, sorted by name.
+///
+/// The placeholder "Default decoder" is skipped as it never decodes anything.
+pub fn registered_decoders() -> Vec<ComponentInfo> {
+    let mut decoders: Vec<ComponentInfo> = crate::filtration_system::get_all_decoders()
+        .components
+        .iter()
+        .filter(|decoder| decoder.get_name() != "Default decoder")
+        .map(|decoder| ComponentInfo {
+            name: decoder.get_name().to_string(),
+            tags: decoder.get_tags().iter().map(|tag| tag.to_string()).collect(),
+            popularity: decoder.get_popularity(),
+            description: decoder.get_description().to_string(),
+        })
+        .collect();
+    decoders.sort_by_key(|d| d.name.to_lowercase());
+    decoders
+}
+
+/// Collects information about every registered checker, sorted by name.
+///
+/// The "Template checker" is skipped as it is only an example for contributors.
+pub fn registered_checkers() -> Vec<ComponentInfo> {
+    let mut checkers: Vec<ComponentInfo> = crate::checkers::all_checker_infos()
+        .into_iter()
+        .filter(|checker| checker.get_name() != "Template checker")
+        .map(|checker| ComponentInfo {
+            name: checker.get_name().to_string(),
+            tags: checker.get_tags().iter().map(|tag| tag.to_string()).collect(),
+            popularity: checker.get_popularity(),
+            description: checker.get_description().to_string(),
+        })
+        .collect();
+    checkers.sort_by_key(|c| c.name.to_lowercase());
+    checkers
+}
+
+/// Prints a list of decoders or checkers, either as JSON or as coloured text.
+///
+/// # Panics
+/// Panics if the list cannot be serialized to JSON, which should not happen.
+#[cfg(feature = "cli")]
+pub fn display_components(components: &[ComponentInfo], as_json: bool, config: &Config) {
+    if as_json {
         println!(
             "{}",
-            success(&format!("Result #{}: {}", i + 1, result.text), config)
+            serde_json::to_string_pretty(components).expect("Could not serialize component list")
         );
-        println!("{}", success(&format!("Decoder: {}", result.decoder_name), config));
-        println!("{}", success(&format!("Checker: {}", result.checker_name), config));
+        return;
+    }
+
+    for component in components {
+        let popularity = format!("{:.2}", component.popularity);
         println!(
+            "{} [{}] ({})",
+            statement(&component.name, Some("informational"), config),
+            component.tags.join(", "),
+            message_with(config, "popularity", &fluent_args!["popularity" => popularity])
+        );
+        println!("    {}", component.description);
+    }
+}
+
+/// Prints the output of `ares decode`.
+///
+/// Plain text is printed on its own so it can be piped into other tools.
+/// When several decoders or candidates were produced, each line is prefixed
+/// with the decoder (and key, if any) that produced it.
+#[cfg(feature = "cli")]
+pub fn display_direct_decoding(results: &[CrackResult], config: &Config) {
+    let candidates: Vec<(&CrackResult, &Candidate)> = results
+        .iter()
+        .flat_map(|result| {
+            result
+                .unencrypted_text
+                .iter()
+                .flatten()
+                .map(move |text| (result, text))
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        eprintln!(
             "{}",
-            success(&format!("Description: {}", result.description), config)
+            warning(&message(config, "no-decoder-output"), config)
         );
-        if results.len() > 1 {
-            // only print seperator if more than 1
-            println!("{}", success("---", config));
-        }
+        return;
     }
 
-    println!("{}", success("=== End of Top Results ===\n", config));
+    if let [(_, text)] = candidates.as_slice() {
+        println!("{}", text);
+        return;
+    }
+
+    for (result, text) in candidates {
+        let label = match &result.key {
+            Some(key) => message_with(
+                config,
+                "decoder-with-key",
+                &fluent_args!["decoder" => result.decoder, "key" => key.as_str()],
+            ),
+            None => result.decoder.to_string(),
+        };
+        println!("{}: {}", statement(&label, Some("informational"), config), text);
+    }
 }
 
 #[test]
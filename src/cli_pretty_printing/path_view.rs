@@ -0,0 +1,263 @@
+//! The path of a plaintext found in several steps, drawn as a table or a tree
+//!
+//! Both show each step's decoder, key, the checker which identified the
+//! plaintext, how long the decoder took and the start of what the step
+//! produced. Long keys and texts are cut short so every line fits in the
+//! terminal, and the table leaves out the output column if there is no
+//! room for it. The headers and labels are in the config's language.
+
+use super::i18n::{message, message_with};
+use crate::config::{Config, PathStyle};
+use crate::DecoderResult;
+use fluent::fluent_args;
+
+/// Terminals narrower than this are drawn as if they were this wide
+const MIN_WIDTH: usize = 40;
+/// Keys longer than this, like substitution alphabets, are cut short
+const MAX_KEY_WIDTH: usize = 26;
+/// The table leaves out the output column if it would be narrower than this
+const MIN_OUTPUT_WIDTH: usize = 8;
+/// How many chars of a step's output are looked at, as no terminal is wider
+const MAX_PREVIEW_CHARS: usize = 500;
+/// Spaces between the table's columns
+const GAP: &str = "  ";
+/// Shown for a step without a key, checker or time
+const NONE: &str = "-";
+
+/// Draws the result's path in the config's `path_style`, each line at most
+/// `width` chars wide
+pub fn render_path(result: &DecoderResult, config: &Config, width: usize) -> String {
+    let width = width.max(MIN_WIDTH);
+    match config.path_style {
+        PathStyle::Table => table(result, config, width),
+        PathStyle::Tree => tree(result, config, width),
+    }
+}
+
+/// How many columns the terminal has: `COLUMNS` if it is set, otherwise what
+/// the terminal reports, otherwise 80
+pub fn terminal_width() -> usize {
+    let columns = std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok());
+    if let Some(columns) = columns.filter(|&c: &usize| c > 0) {
+        return columns;
+    }
+    #[cfg(feature = "cli")]
+    if let Ok((columns, _)) = ratatui::crossterm::terminal::size() {
+        if columns > 0 {
+            return columns.into();
+        }
+    }
+    80
+}
+
+/// What is shown for one step
+struct Step {
+    /// The decoder's name
+    decoder: &'static str,
+    /// The key, cut short if long
+    key: Option<String>,
+    /// The checker which identified the step's output as plaintext
+    checker: Option<&'static str>,
+    /// How long the decoder took
+    time: Option<String>,
+    /// The start of what the step produced
+    output: String,
+}
+
+/// The steps of the result's path
+fn steps(result: &DecoderResult) -> Vec<Step> {
+    result
+        .path
+        .iter()
+        .enumerate()
+        .map(|(i, step)| Step {
+            decoder: step.decoder,
+            key: step.key.as_deref().map(|key| fit(&printable(key), MAX_KEY_WIDTH)),
+            checker: (!step.checker_name.is_empty()).then_some(step.checker_name),
+            time: step.duration_us.map(format_duration),
+            output: printable(&result.step_output(i)),
+        })
+        .collect()
+}
+
+/// A header, a rule, then a row per step
+fn table(result: &DecoderResult, config: &Config, width: usize) -> String {
+    let headers = [
+        "#".to_string(),
+        message(config, "path-decoder"),
+        message(config, "path-key"),
+        message(config, "path-checker"),
+        message(config, "path-time"),
+    ];
+    let rows: Vec<([String; 5], String)> = steps(result)
+        .into_iter()
+        .enumerate()
+        .map(|(i, step)| {
+            let cells = [
+                (i + 1).to_string(),
+                step.decoder.to_string(),
+                step.key.unwrap_or_else(|| NONE.to_string()),
+                step.checker.unwrap_or(NONE).to_string(),
+                step.time.unwrap_or_else(|| NONE.to_string()),
+            ];
+            (cells, step.output)
+        })
+        .collect();
+
+    let mut widths = headers.each_ref().map(|header| header.chars().count());
+    for (cells, _) in &rows {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let fixed = widths.iter().sum::<usize>() + GAP.len() * widths.len();
+    let output_width = width.saturating_sub(fixed);
+    let show_output = output_width >= MIN_OUTPUT_WIDTH;
+
+    let line = |cells: &[String], output: &str| {
+        let mut line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(GAP);
+        if show_output {
+            line.push_str(GAP);
+            line.push_str(&fit(output, output_width));
+        }
+        fit(line.trim_end(), width)
+    };
+    let mut lines = vec![line(&headers, &message(config, "path-output"))];
+    lines.extend(rows.iter().map(|(cells, output)| line(cells, output)));
+    let rule_width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    lines.insert(1, "─".repeat(rule_width));
+    lines.join("\n")
+}
+
+/// The input, then each step below the one before with what it produced
+fn tree(result: &DecoderResult, config: &Config, width: usize) -> String {
+    let Some(first) = result.path.first() else {
+        return String::new();
+    };
+    let mut lines = vec![fit(&printable(&first.encrypted_text), width)];
+    for (depth, step) in steps(result).into_iter().enumerate() {
+        // Deep paths stop moving right, so there is always room for the text
+        let indent = " ".repeat((depth * 3).min(width / 3));
+        let mut label = vec![step.decoder.to_string()];
+        label.extend(
+            step.key
+                .map(|key| message_with(config, "path-step-key", &fluent_args!["key" => key])),
+        );
+        label.extend(step.time);
+        label.extend(step.checker.map(|checker| format!("✓ {checker}")));
+        lines.push(fit(&format!("{indent}└─ {}", label.join(" · ")), width));
+        lines.push(fit(&format!("{indent}   {}", step.output), width));
+    }
+    lines.join("\n")
+}
+
+/// Cuts `text` to at most `width` chars, ending it with `…` if anything was cut
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut fitted: String = text.chars().take(width.saturating_sub(1)).collect();
+    fitted.push('…');
+    fitted
+}
+
+/// The start of `text` on one line, with control characters like newlines escaped
+fn printable(text: &str) -> String {
+    let mut printable = String::new();
+    for c in text.chars().take(MAX_PREVIEW_CHARS) {
+        if c.is_control() {
+            printable.extend(c.escape_debug());
+        } else {
+            printable.push(c);
+        }
+    }
+    printable
+}
+
+/// Formats microseconds as µs, ms or s, whichever reads best
+fn format_duration(us: u64) -> String {
+    match us {
+        0..=999 => format!("{us} µs"),
+        1_000..=999_999 => format!("{:.1} ms", us as f64 / 1_000.0),
+        _ => format!("{:.2} s", us as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_pretty_printing::i18n::Lang;
+    use crate::decoders::crack_results::CrackResult;
+
+    /// The config drawing paths in `style`, in English
+    fn config(style: PathStyle) -> Config {
+        Config {
+            path_style: style,
+            lang: Some(Lang::En),
+            ..Default::default()
+        }
+    }
+
+    /// Base64 then Caesar, with the Caesar key, times and checker set
+    fn two_step_result() -> DecoderResult {
+        let mut base64 = CrackResult::from_decoder_info("Base64", "", "", "VXJ5eWIgamJleXE=");
+        base64.duration_us = Some(210);
+        let mut caesar = CrackResult::from_decoder_info("caesar", "", "", "Uryyb jbeyq");
+        caesar.key = Some("13".to_string());
+        caesar.checker_name = "English Checker";
+        caesar.duration_us = Some(1_340);
+        DecoderResult {
+            text: vec!["hello world".into()],
+            path: vec![base64, caesar],
+            confidence: 0.0,
+        }
+    }
+
+    #[test]
+    fn table_has_a_row_per_step() {
+        let table = render_path(&two_step_result(), &config(PathStyle::Table), 100);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("#  Decoder  Key  Checker"));
+        assert!(lines[0].ends_with("Output"));
+        assert!(lines[1].chars().all(|c| c == '─'));
+        assert!(lines[2].starts_with("1  Base64   -    -  "));
+        assert!(lines[2].contains("210 µs"));
+        assert!(lines[2].ends_with("Uryyb jbeyq"));
+        assert!(lines[3].contains("13   English Checker  1.3 ms"));
+        assert!(lines[3].ends_with("hello world"));
+    }
+
+    #[test]
+    fn tree_goes_from_the_input_to_the_plaintext() {
+        let tree = render_path(&two_step_result(), &config(PathStyle::Tree), 100);
+        assert_eq!(
+            tree,
+            "VXJ5eWIgamJleXE=\n\
+             └─ Base64 · 210 µs\n   \
+             Uryyb jbeyq\n   \
+             └─ caesar · key 13 · 1.3 ms · ✓ English Checker\n      \
+             hello world"
+        );
+    }
+
+    #[test]
+    fn lines_fit_narrow_terminals() {
+        let mut result = two_step_result();
+        result.text = vec!["a very long plaintext which goes on\nand on ".repeat(20).into()];
+        for style in [PathStyle::Table, PathStyle::Tree] {
+            let view = render_path(&result, &config(style), 50);
+            assert!(view.lines().all(|line| line.chars().count() <= 50), "{view}");
+            assert!(view.contains('…'));
+            assert!(!view.lines().any(|line| line.starts_with("and on")));
+        }
+        // Without room for the output, the table leaves it out
+        let table = render_path(&result, &config(PathStyle::Table), 40);
+        assert!(!table.contains("Output"));
+    }
+}
@@ -0,0 +1,281 @@
+//! Progress on stderr for `--progress`
+//!
+//! Listens to [`crate::searchers::events`] on a background thread and shows the
+//! number of nodes expanded, the deepest node and the time spent against the
+//! timeout. Progress goes to stderr, so piping stdout still only captures
+//! the plaintext.
+//!
+//! By default it is a bar, which is only drawn when stderr is a terminal.
+//! With `--progress json` it is one JSON object per line instead, which other
+//! tools can read to show their own progress:
+//!
+//! ```text
+//! {"event":"started","timeout":5}
+//! {"best_fitness":-41.5,"depth":2,"elapsed_ms":500,"event":"progress","nodes":120}
+//! {"best_fitness":112.0,"depth":3,"elapsed_ms":730,"event":"finished","nodes":161,"stats":{...},"success":true}
+//! ```
+//!
+//! The finished line's `stats` are the search's
+//! [`SearchStats`](crate::stats::SearchStats).
+
+use crate::config::ProgressFormat;
+use crate::searchers::events::{self, SearchEvent};
+use crate::searchers::stats::SearchStats;
+use crate::timer;
+use crossbeam::channel::{bounded, select, tick, Receiver, Sender};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde_json::json;
+use std::io::Write;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often the bar is redrawn
+const REFRESH: Duration = Duration::from_millis(100);
+
+/// How often a JSON progress line is written
+const JSON_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A progress bar following the current search
+pub struct ProgressDisplay {
+    /// Tells the drawing thread to stop
+    stop: Sender<()>,
+    /// The drawing thread
+    handle: JoinHandle<()>,
+}
+
+/// Counters shown next to the bar
+#[derive(Debug, Default, PartialEq)]
+struct ProgressStats {
+    /// Number of nodes expanded so far
+    nodes_expanded: u64,
+    /// Deepest node expanded so far
+    max_depth: u32,
+    /// The best fitness of any node expanded so far
+    best_fitness: Option<f64>,
+    /// Whether a plaintext was found, once the search has finished
+    success: Option<bool>,
+    /// What the search did, once it has finished
+    search_stats: Option<SearchStats>,
+}
+
+impl ProgressStats {
+    /// Updates the counters with an event, returning true once the search has finished
+    fn apply(&mut self, event: &SearchEvent) -> bool {
+        match event {
+            SearchEvent::NodeExpanded { depth, fitness, .. } => {
+                self.nodes_expanded += 1;
+                self.max_depth = self.max_depth.max(*depth);
+                if fitness.is_finite() && self.best_fitness.is_none_or(|best| *fitness > best) {
+                    self.best_fitness = Some(*fitness);
+                }
+            }
+            SearchEvent::Finished { success, stats } => {
+                self.success = Some(*success);
+                self.search_stats = Some(*stats);
+                return true;
+            }
+            _ => (),
+        }
+        false
+    }
+
+    /// The message shown after the bar
+    fn message(&self) -> String {
+        format!("nodes {}  depth {}", self.nodes_expanded, self.max_depth)
+    }
+
+    /// A JSON progress line, or the finished line once the search has finished
+    fn json_line(&self, elapsed: Duration) -> String {
+        let mut line = json!({
+            "event": "progress",
+            "elapsed_ms": elapsed.as_millis() as u64,
+            "nodes": self.nodes_expanded,
+            "depth": self.max_depth,
+            "best_fitness": self.best_fitness,
+        });
+        if let Some(success) = self.success {
+            line["event"] = json!("finished");
+            line["success"] = json!(success);
+        }
+        if let Some(stats) = self.search_stats {
+            line["stats"] = json!(stats);
+        }
+        line.to_string()
+    }
+}
+
+impl ProgressDisplay {
+    /// Starts showing the progress of a search which times out after `timeout` seconds.
+    /// Must be called before the search starts so no events are missed.
+    pub fn start(timeout: u32, format: ProgressFormat) -> Self {
+        let search_events = events::subscribe();
+        let (stop, stopped) = bounded(1);
+        let handle = thread::spawn(move || match format {
+            ProgressFormat::Bar => draw_until_finished(timeout, search_events, stopped),
+            ProgressFormat::Json => write_json_until_finished(timeout, search_events, stopped),
+        });
+        ProgressDisplay { stop, handle }
+    }
+
+    /// Stops and clears the progress bar
+    pub fn finish(self) {
+        // The thread may already have stopped on its own when the search finished
+        let _ = self.stop.send(());
+        let _ = self.handle.join();
+    }
+}
+
+/// Redraws the bar until the search finishes or the display is stopped
+fn draw_until_finished(timeout: u32, search_events: Receiver<SearchEvent>, stopped: Receiver<()>) {
+    let total = Duration::from_secs(u64::from(timeout));
+    let bar =
+        ProgressBar::with_draw_target(Some(total.as_millis() as u64), ProgressDrawTarget::stderr());
+    bar.set_style(
+        ProgressStyle::with_template("{spinner} [{elapsed_precise}] [{bar:30}] ETA {eta}  {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar())
+            .progress_chars("=> "),
+    );
+
+    let mut stats = ProgressStats::default();
+    let started = Instant::now();
+    // Time spent waiting on the human checker does not count towards the timeout
+    let mut paused_for = Duration::ZERO;
+    let ticks = tick(REFRESH);
+    loop {
+        select! {
+            recv(search_events) -> event => match event {
+                Ok(event) if stats.apply(&event) => break,
+                Ok(_) => continue,
+                Err(_) => break,
+            },
+            recv(stopped) -> _ => break,
+            recv(ticks) -> _ => (),
+        }
+        if timer::is_paused() {
+            // Keep the bar out of the way of the human checker prompt
+            let pause_started = Instant::now();
+            bar.suspend(|| {
+                while timer::is_paused() && stopped.is_empty() {
+                    thread::sleep(REFRESH);
+                }
+            });
+            paused_for += pause_started.elapsed();
+        }
+        let searching_for = started.elapsed().saturating_sub(paused_for).min(total);
+        bar.set_position(searching_for.as_millis() as u64);
+        bar.set_message(stats.message());
+        bar.tick();
+    }
+    bar.finish_and_clear();
+}
+
+/// Writes JSON progress lines to stderr until the search finishes or the
+/// display is stopped
+fn write_json_until_finished(
+    timeout: u32,
+    search_events: Receiver<SearchEvent>,
+    stopped: Receiver<()>,
+) {
+    let mut stderr = std::io::stderr();
+    let _ = writeln!(stderr, "{}", json!({"event": "started", "timeout": timeout}));
+
+    let mut stats = ProgressStats::default();
+    let started = Instant::now();
+    // Time spent waiting on the human checker does not count towards the timeout
+    let mut paused_for = Duration::ZERO;
+    let ticks = tick(JSON_INTERVAL);
+    loop {
+        select! {
+            recv(search_events) -> event => match event {
+                Ok(event) if stats.apply(&event) => break,
+                Ok(_) => continue,
+                Err(_) => break,
+            },
+            recv(stopped) -> _ => break,
+            recv(ticks) -> _ => (),
+        }
+        if timer::is_paused() {
+            let pause_started = Instant::now();
+            while timer::is_paused() && stopped.is_empty() {
+                thread::sleep(REFRESH);
+            }
+            paused_for += pause_started.elapsed();
+        }
+        let searching_for = started.elapsed().saturating_sub(paused_for);
+        let _ = writeln!(stderr, "{}", stats.json_line(searching_for));
+    }
+    // Stopped before the search said it finished, e.g. if it was cancelled
+    stats.success.get_or_insert(false);
+    let searching_for = started.elapsed().saturating_sub(paused_for);
+    let _ = writeln!(stderr, "{}", stats.json_line(searching_for));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_nodes_and_depth_until_finished() {
+        let mut stats = ProgressStats::default();
+        for depth in [0, 1, 3, 2] {
+            assert!(!stats.apply(&SearchEvent::NodeExpanded {
+                depth,
+                path: vec![],
+                text: String::new(),
+                fitness: 0.0,
+            }));
+        }
+        assert_eq!(stats.message(), "nodes 4  depth 3");
+        assert!(stats.apply(&SearchEvent::Finished {
+            success: true,
+            stats: SearchStats::default(),
+        }));
+    }
+
+    #[test]
+    fn json_lines_carry_the_best_fitness() {
+        let mut stats = ProgressStats::default();
+        let progress: serde_json::Value =
+            serde_json::from_str(&stats.json_line(Duration::from_millis(20))).unwrap();
+        assert_eq!(progress["event"], "progress");
+        assert!(progress["best_fitness"].is_null());
+        for fitness in [-50.0, 12.5, f64::NAN, 3.0] {
+            stats.apply(&SearchEvent::NodeExpanded {
+                depth: 1,
+                path: vec![],
+                text: String::new(),
+                fitness,
+            });
+        }
+        stats.apply(&SearchEvent::Finished {
+            success: false,
+            stats: SearchStats {
+                nodes_expanded: 4,
+                decoders_run: 90,
+                checker_calls: 95,
+                cache_hits: 2,
+                peak_frontier: 30,
+            },
+        });
+        let finished: serde_json::Value =
+            serde_json::from_str(&stats.json_line(Duration::from_millis(1500))).unwrap();
+        assert_eq!(
+            finished,
+            json!({
+                "event": "finished",
+                "success": false,
+                "elapsed_ms": 1500,
+                "nodes": 4,
+                "depth": 1,
+                "best_fitness": 12.5,
+                "stats": {
+                    "nodes_expanded": 4,
+                    "decoders_run": 90,
+                    "checker_calls": 95,
+                    "cache_hits": 2,
+                    "peak_frontier": 30,
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! Report generation for cracking runs
+//!
+//! `ares --report <path>` writes a summary of the run to disk. The format is
+//! picked from the file extension:
+//! - `.csv` produces a single header + row CSV file
+//! - `.md` / `.markdown` produces a Markdown summary with a per-step table
+//! - `.html` / `.htm` produces a standalone HTML page
+//!
+//! Every report contains the input, the plaintext, the decoder path, the keys
+//! used along that path and how long the run took.
+
+use crate::config::Config;
+use crate::DecoderResult;
+use std::fs::write;
+use std::path::Path;
+use std::time::Duration;
+
+/// The output formats a report can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Comma separated values
+    Csv,
+    /// Markdown document
+    Markdown,
+    /// Standalone HTML page
+    Html,
+}
+
+impl ReportFormat {
+    /// Picks the report format from the extension of the given path.
+    ///
+    /// Returns None if the extension is missing or unsupported.
+    ///
+    /// # Examples
+    /// ```
+    /// use ares::cli_pretty_printing::report::ReportFormat;
+    ///
+    /// assert_eq!(ReportFormat::from_path("out.csv"), Some(ReportFormat::Csv));
+    /// assert_eq!(ReportFormat::from_path("out.MD"), Some(ReportFormat::Markdown));
+    /// assert_eq!(ReportFormat::from_path("out.txt"), None);
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<ReportFormat> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(ReportFormat::Csv),
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            "html" | "htm" => Some(ReportFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+/// A flattened view of a cracking run, shared by every report format
+struct ReportSummary {
+    /// The text the user gave us
+    input: String,
+    /// The plaintext, empty if we failed to decode
+    plaintext: String,
+    /// Whether a plaintext was found
+    success: bool,
+    /// One entry per decoder used, in order: (decoder name, key)
+    steps: Vec<(String, Option<String>)>,
+    /// How long the run took
+    duration: Duration,
+}
+
+impl ReportSummary {
+    /// Builds a summary from the result of `perform_cracking`
+    fn new(input: &str, result: Option<&DecoderResult>, duration: Duration) -> Self {
+        match result {
+            Some(result) => ReportSummary {
+                input: input.to_string(),
+                plaintext: result.text.first().map(ToString::to_string).unwrap_or_default(),
+                success: true,
+                steps: result
+                    .path
+                    .iter()
+                    .map(|step| (step.decoder.to_string(), step.key.clone()))
+                    .collect(),
+                duration,
+            },
+            None => ReportSummary {
+                input: input.to_string(),
+                plaintext: String::new(),
+                success: false,
+                steps: Vec::new(),
+                duration,
+            },
+        }
+    }
+
+    /// The decoder path joined with arrows, like the CLI output
+    fn path(&self) -> String {
+        self.steps
+            .iter()
+            .map(|(decoder, _)| decoder.as_str())
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+
+    /// All keys used along the path in the form `decoder: key`
+    fn keys(&self) -> String {
+        self.steps
+            .iter()
+            .filter_map(|(decoder, key)| key.as_ref().map(|key| format!("{decoder}: {key}")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Duration of the run in milliseconds
+    fn duration_ms(&self) -> u128 {
+        self.duration.as_millis()
+    }
+}
+
+/// Escapes a single CSV field, quoting it if it contains special characters
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escapes text so it can be placed inside a Markdown table cell
+fn markdown_cell(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\n', '\r'], " ")
+}
+
+/// Renders the summary as CSV
+fn render_csv(summary: &ReportSummary) -> String {
+    let row = [
+        csv_field(&summary.input),
+        csv_field(&summary.plaintext),
+        csv_field(&summary.path()),
+        csv_field(&summary.keys()),
+        summary.duration_ms().to_string(),
+    ];
+    format!("input,plaintext,path,keys,duration_ms\n{}\n", row.join(","))
+}
+
+/// Renders the summary as Markdown
+fn render_markdown(summary: &ReportSummary) -> String {
+    let mut out = String::from("# Ares Report\n\n");
+    out.push_str("| Field | Value |\n|---|---|\n");
+    out.push_str(&format!(
+        "| Input | `{}` |\n",
+        markdown_cell(&summary.input)
+    ));
+    if summary.success {
+        out.push_str(&format!(
+            "| Plaintext | `{}` |\n",
+            markdown_cell(&summary.plaintext)
+        ));
+    } else {
+        out.push_str("| Plaintext | *Failed to decode* |\n");
+    }
+    out.push_str(&format!("| Path | {} |\n", markdown_cell(&summary.path())));
+    out.push_str(&format!("| Keys | {} |\n", markdown_cell(&summary.keys())));
+    out.push_str(&format!("| Duration | {} ms |\n", summary.duration_ms()));
+
+    if !summary.steps.is_empty() {
+        out.push_str("\n## Steps\n\n| # | Decoder | Key |\n|---|---|---|\n");
+        for (i, (decoder, key)) in summary.steps.iter().enumerate() {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                i + 1,
+                markdown_cell(decoder),
+                markdown_cell(key.as_deref().unwrap_or("-"))
+            ));
+        }
+    }
+    out
+}
+
+/// Renders the summary as a standalone HTML page
+fn render_html(summary: &ReportSummary) -> String {
+    let escape = |text: &str| html_escape::encode_text(text).to_string();
+    let plaintext = if summary.success {
+        format!("<code>{}</code>", escape(&summary.plaintext))
+    } else {
+        String::from("<em>Failed to decode</em>")
+    };
+
+    let mut steps = String::new();
+    for (i, (decoder, key)) in summary.steps.iter().enumerate() {
+        steps.push_str(&format!(
+            "      <tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            i + 1,
+            escape(decoder),
+            escape(key.as_deref().unwrap_or("-"))
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+  <meta charset=\"utf-8\">
+  <title>Ares Report</title>
+</head>
+<body>
+  <h1>Ares Report</h1>
+  <table>
+    <tr><th>Input</th><td><code>{input}</code></td></tr>
+    <tr><th>Plaintext</th><td>{plaintext}</td></tr>
+    <tr><th>Path</th><td>{path}</td></tr>
+    <tr><th>Keys</th><td>{keys}</td></tr>
+    <tr><th>Duration</th><td>{duration} ms</td></tr>
+  </table>
+  <h2>Steps</h2>
+  <table>
+    <thead><tr><th>#</th><th>Decoder</th><th>Key</th></tr></thead>
+    <tbody>
+{steps}    </tbody>
+  </table>
+</body>
+</html>
+",
+        input = escape(&summary.input),
+        plaintext = plaintext,
+        path = escape(&summary.path()),
+        keys = escape(&summary.keys()),
+        duration = summary.duration_ms(),
+        steps = steps,
+    )
+}
+
+/// Renders a report of a cracking run in the given format.
+///
+/// # Arguments
+/// * `input` - The text that was given to Ares
+/// * `result` - The result of `perform_cracking`, None if decoding failed
+/// * `duration` - How long the run took
+/// * `format` - The format to render the report in
+///
+/// # Examples
+/// ```
+/// use ares::cli_pretty_printing::report::{render_report, ReportFormat};
+/// use std::time::Duration;
+///
+/// let csv = render_report("aGVsbG8=", None, Duration::from_millis(5), ReportFormat::Csv);
+/// assert!(csv.starts_with("input,plaintext,path,keys,duration_ms"));
+/// ```
+pub fn render_report(
+    input: &str,
+    result: Option<&DecoderResult>,
+    duration: Duration,
+    format: ReportFormat,
+) -> String {
+    let summary = ReportSummary::new(input, result, duration);
+    match format {
+        ReportFormat::Csv => render_csv(&summary),
+        ReportFormat::Markdown => render_markdown(&summary),
+        ReportFormat::Html => render_html(&summary),
+    }
+}
+
+/// Writes a report of a cracking run to `path`, picking the format from its extension.
+///
+/// # Errors
+/// Returns an error if the extension is not one of `csv`, `md`, `markdown`,
+/// `html` or `htm`, or if the file cannot be written.
+pub fn write_report(
+    path: &str,
+    input: &str,
+    result: Option<&DecoderResult>,
+    duration: Duration,
+    config: &Config,
+) -> std::io::Result<()> {
+    let format = ReportFormat::from_path(path).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "Unsupported report format for '{}'. Use a .csv, .md or .html file",
+                path
+            ),
+        )
+    })?;
+    write(path, render_report(input, result, duration, format))?;
+    if !config.api_mode {
+        println!(
+            "{}",
+            super::statement(&format!("Report written to {}", path), None, config)
+        );
+    }
+    Ok(())
+}
+
+// The sample result has a Caesar step
+#[cfg(all(test, feature = "classical"))]
+mod tests {
+    use super::*;
+    use crate::decoders::base64_decoder::Base64Decoder;
+    use crate::decoders::caesar_decoder::CaesarDecoder;
+    use crate::decoders::crack_results::CrackResult;
+    use crate::decoders::interface::{Crack, Decoder};
+
+    fn sample_result() -> DecoderResult {
+        let base64 = CrackResult::new(&Decoder::<Base64Decoder>::new(), "dXJ5eWIsIGpiZXlx");
+        let mut caesar = CrackResult::new(&Decoder::<CaesarDecoder>::new(), "uryyb, jbeyq");
+        caesar.key = Some("13".to_string());
+        DecoderResult {
+            text: vec!["hello, world".into()],
+            path: vec![base64, caesar],
+            confidence: 0.0,
+        }
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(ReportFormat::from_path("a.csv"), Some(ReportFormat::Csv));
+        assert_eq!(
+            ReportFormat::from_path("a.markdown"),
+            Some(ReportFormat::Markdown)
+        );
+        assert_eq!(ReportFormat::from_path("a.HTM"), Some(ReportFormat::Html));
+        assert_eq!(ReportFormat::from_path("report"), None);
+    }
+
+    #[test]
+    fn csv_report_escapes_fields() {
+        let result = sample_result();
+        let csv = render_report(
+            "dXJ5eWIsIGpiZXlx",
+            Some(&result),
+            Duration::from_millis(42),
+            ReportFormat::Csv,
+        );
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            "dXJ5eWIsIGpiZXlx,\"hello, world\",Base64 → caesar,caesar: 13,42"
+        );
+    }
+
+    #[test]
+    fn markdown_report_lists_steps() {
+        let result = sample_result();
+        let md = render_report(
+            "in|put",
+            Some(&result),
+            Duration::from_millis(1),
+            ReportFormat::Markdown,
+        );
+        assert!(md.contains("`in\\|put`"));
+        assert!(md.contains("| 2 | caesar | 13 |"));
+    }
+
+    #[test]
+    fn html_report_escapes_text() {
+        let html = render_report(
+            "<script>",
+            None,
+            Duration::from_millis(1),
+            ReportFormat::Html,
+        );
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("Failed to decode"));
+    }
+}
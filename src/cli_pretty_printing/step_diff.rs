@@ -0,0 +1,175 @@
+//! What each step of a path changed, highlighted, for `-v`
+//!
+//! Each step's input is shown with the chars it removed or replaced
+//! highlighted, and its output with the chars it added. Steps which keep the
+//! length, like ROT13, XOR or normalization swapping lookalikes, are
+//! compared char by char, so every substituted char stands out. Other steps
+//! are compared by their longest common subsequence, so chars removed, like
+//! invisible characters, or inserted are picked out from those kept.
+
+use super::color_string;
+use super::path_view::terminal_width;
+use crate::config::Config;
+use crate::DecoderResult;
+
+/// Only the start of each text is compared, as only the start is shown
+const MAX_DIFF_CHARS: usize = 1000;
+
+/// Chars of a text, each marked with whether the step changed it
+pub type MarkedChars = Vec<(char, bool)>;
+
+/// Compares the start of a step's input with the start of its output.
+/// Returns the input's chars, marked if the step removed or replaced them,
+/// and the output's chars, marked if the step added them.
+pub fn char_diff(before: &str, after: &str) -> (MarkedChars, MarkedChars) {
+    let before: Vec<char> = before.chars().take(MAX_DIFF_CHARS).collect();
+    let after: Vec<char> = after.chars().take(MAX_DIFF_CHARS).collect();
+    if before.len() == after.len() {
+        // Substitutions keep every char in place
+        let changed: Vec<bool> = before.iter().zip(&after).map(|(a, b)| a != b).collect();
+        return (
+            before.into_iter().zip(changed.iter().copied()).collect(),
+            after.into_iter().zip(changed).collect(),
+        );
+    }
+
+    // lengths[i * (m + 1) + j] is the length of the longest common
+    // subsequence of before[i..] and after[j..]
+    let (n, m) = (before.len(), after.len());
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    let mut lengths = vec![0u32; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[at(i, j)] = if before[i] == after[j] {
+                lengths[at(i + 1, j + 1)] + 1
+            } else {
+                lengths[at(i + 1, j)].max(lengths[at(i, j + 1)])
+            };
+        }
+    }
+    let mut removed = vec![true; n];
+    let mut added = vec![true; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if before[i] == after[j] {
+            removed[i] = false;
+            added[j] = false;
+            i += 1;
+            j += 1;
+        } else if lengths[at(i + 1, j)] >= lengths[at(i, j + 1)] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    (
+        before.into_iter().zip(removed).collect(),
+        after.into_iter().zip(added).collect(),
+    )
+}
+
+/// Shows each step of the path as its number and decoder, then its input
+/// after `-` and its output after `+`, with what changed highlighted
+pub fn render_step_diffs(result: &DecoderResult, config: &Config) -> String {
+    // Room for the "  - " in front of each text
+    let width = terminal_width().saturating_sub(4).max(20);
+    let mut lines = Vec::with_capacity(result.path.len() * 3);
+    for (i, step) in result.path.iter().enumerate() {
+        let (removed, added) = char_diff(&step.encrypted_text, &result.step_output(i));
+        lines.push(format!("{}. {}", i + 1, step.decoder));
+        lines.push(format!("  - {}", highlight(&removed, "warning", width, config)));
+        lines.push(format!("  + {}", highlight(&added, "success", width, config)));
+    }
+    lines.join("\n")
+}
+
+/// Colours the marked chars in `role`'s colour, escaping control characters
+/// and cutting the text short with `…` at `width` chars
+fn highlight(chars: &[(char, bool)], role: &str, width: usize, config: &Config) -> String {
+    let mut highlighted = String::new();
+    let mut run = String::new();
+    let mut run_changed = false;
+    let mut shown = 0;
+    let mut flush = |run: &mut String, changed: bool| {
+        if changed {
+            highlighted.push_str(&color_string(run, role, config));
+        } else {
+            highlighted.push_str(run);
+        }
+        run.clear();
+    };
+    let mut cut = false;
+    for &(c, changed) in chars {
+        let printable: String = if c.is_control() {
+            c.escape_debug().collect()
+        } else {
+            c.to_string()
+        };
+        shown += printable.chars().count();
+        if shown >= width {
+            cut = true;
+            break;
+        }
+        if changed != run_changed {
+            flush(&mut run, run_changed);
+            run_changed = changed;
+        }
+        run.push_str(&printable);
+    }
+    flush(&mut run, run_changed);
+    if cut {
+        highlighted.push('…');
+    }
+    highlighted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The chars marked as changed
+    fn changed(chars: &[(char, bool)]) -> String {
+        chars.iter().filter(|(_, changed)| *changed).map(|(c, _)| c).collect()
+    }
+
+    #[test]
+    fn substitutions_are_compared_in_place() {
+        let (removed, added) = char_diff("Uryyb, jbeyq", "Hello, world");
+        assert_eq!(changed(&removed), "Uryybjbeyq");
+        assert_eq!(changed(&added), "Helloworld");
+    }
+
+    #[test]
+    fn removed_and_inserted_chars_are_picked_out() {
+        let (removed, added) = char_diff("he\u{200B}llo", "hello");
+        assert_eq!(changed(&removed), "\u{200B}");
+        assert_eq!(changed(&added), "");
+
+        let (removed, added) = char_diff("hllo wrld", "hello world!");
+        assert_eq!(changed(&removed), "");
+        assert_eq!(changed(&added), "eo!");
+    }
+
+    #[test]
+    fn every_step_is_shown_before_and_after() {
+        let config = Config::default();
+        let mut result = DecoderResult {
+            text: vec!["hello".into()],
+            path: vec![crate::decoders::crack_results::CrackResult::from_decoder_info(
+                "Reverse", "", "", "olleh",
+            )],
+            confidence: 0.0,
+        };
+        let diffs = render_step_diffs(&result, &config);
+        let lines: Vec<&str> = diffs.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "1. Reverse");
+        assert!(lines[1].starts_with("  - ") && lines[1].contains('o'));
+        assert!(lines[2].starts_with("  + ") && lines[2].contains('h'));
+
+        // Long texts are cut short
+        result.text = vec!["a".repeat(5000).into()];
+        let diffs = render_step_diffs(&result, &config);
+        assert!(diffs.lines().last().unwrap().ends_with('…'));
+    }
+}
@@ -1,11 +1,17 @@
-use crate::storage::INVISIBLE_CHARS;
+use super::{registered_checkers, registered_decoders};
+#[cfg(feature = "cli")]
+use {
+    super::i18n::Lang, super::top_result_line, crate::config::Config,
+    crate::decoders::crack_results::CrackResult, crate::DecoderResult, std::time::Duration,
+};
+use crate::storage::invisible_chars::is_invisible;
 
 /// Test that checks if the invisible character detection works correctly
 #[test]
 fn test_invisible_character_detection() {
     // Get a zero width space character
     let zero_width_space = char::from_u32(0x200B).unwrap();
-    assert!(INVISIBLE_CHARS.contains(&zero_width_space));
+    assert!(is_invisible(zero_width_space));
 
     // Create a string with 50% invisible characters (alternating normal and invisible)
     let mut test_string = String::new();
@@ -20,10 +26,7 @@ fn test_invisible_character_detection() {
     // Count invisible characters
     let mut invis_chars_found = 0.0;
     for char in test_string.chars() {
-        if INVISIBLE_CHARS
-            .iter()
-            .any(|invis_chars| *invis_chars == char)
-        {
+        if is_invisible(char) {
             invis_chars_found += 1.0;
         }
     }
@@ -45,10 +48,7 @@ fn test_no_invisible_characters() {
     // Count invisible characters
     let mut invis_chars_found = 0.0;
     for char in test_string.chars() {
-        if INVISIBLE_CHARS
-            .iter()
-            .any(|invis_chars| *invis_chars == char)
-        {
+        if is_invisible(char) {
             invis_chars_found += 1.0;
         }
     }
@@ -65,7 +65,7 @@ fn test_no_invisible_characters() {
     assert_eq!(invis_char_percentage, expected_percentage);
 
     // Verify that spaces are indeed counted as invisible
-    assert!(INVISIBLE_CHARS.contains(&' '));
+    assert!(is_invisible(' '));
 }
 
 /// Test with a string that has spaces (which are considered invisible)
@@ -76,10 +76,7 @@ fn test_spaces_as_invisible_characters() {
     // Count invisible characters
     let mut invis_chars_found = 0.0;
     for char in test_string.chars() {
-        if INVISIBLE_CHARS
-            .iter()
-            .any(|invis_chars| *invis_chars == char)
-        {
+        if is_invisible(char) {
             invis_chars_found += 1.0;
         }
     }
@@ -91,3 +88,44 @@ fn test_spaces_as_invisible_characters() {
 
     assert_eq!(invis_char_percentage, expected_percentage);
 }
+
+/// The decoder listing should include real decoders with their tags, but not the placeholder
+#[test]
+fn test_registered_decoders_listing() {
+    let decoders = registered_decoders();
+    let base64 = decoders
+        .iter()
+        .find(|d| d.name == "Base64")
+        .expect("Base64 should be listed");
+    assert!(base64.tags.contains(&"base".to_string()));
+    assert!(!decoders.iter().any(|d| d.name == "Default decoder"));
+}
+
+/// The checker listing should include the English checker but not the template checker
+#[test]
+fn test_registered_checkers_listing() {
+    let checkers = registered_checkers();
+    assert!(checkers.iter().any(|c| c.name == "English Checker"));
+    assert!(!checkers.iter().any(|c| c.name == "Template checker"));
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn top_results_are_shown_with_their_time_and_path() {
+    let result = DecoderResult {
+        text: vec!["hello world".into()],
+        path: vec![
+            CrackResult::from_decoder_info("Base64", "", "", "aGVsbG8gd29ybGQ="),
+            CrackResult::from_decoder_info("Reverse", "", "", "dlrow olleh"),
+        ],
+        confidence: 0.0,
+    };
+    let config = Config {
+        lang: Some(Lang::En),
+        ..Default::default()
+    };
+    assert_eq!(
+        top_result_line(&result, Duration::from_millis(2450), &config),
+        "[2.5s] Found a possible plaintext with Base64 → Reverse: hello world"
+    );
+}
@@ -0,0 +1,111 @@
+//! How sure Ares is that a result is the plaintext
+//!
+//! The score is between 0 and 1. It starts from how much the checker which
+//! identified the plaintext is trusted, blends in how English-like the text is,
+//! and drops a little for every decoder on the path, as long paths are more
+//! likely to have wandered into a false positive.
+
+use crate::cryptanalysis::fitness_score;
+use crate::DecoderResult;
+
+/// How much each step after the first lowers the score
+const PATH_LENGTH_PENALTY: f32 = 0.95;
+
+/// Scores how likely the result is to be the plaintext, from 0 to 1
+///
+/// ```
+/// use ares::confidence::score;
+/// use ares::decoders::crack_results::CrackResult;
+/// use ares::DecoderResult;
+///
+/// let mut step = CrackResult::from_decoder_info("Base64", "", "", "aGVsbG8=".to_string());
+/// step.checker_name = "English Checker";
+/// let english = DecoderResult {
+///     text: vec!["hello there general".into()],
+///     path: vec![step],
+///     confidence: 0.0,
+/// };
+/// assert!(score(&english) >= 0.8);
+/// ```
+pub fn score(result: &DecoderResult) -> f32 {
+    let Some(last) = result.path.last() else {
+        return 0.0;
+    };
+    let text = result.text.first().map(|text| text.text()).unwrap_or_default();
+    let steps = result.path.len().saturating_sub(1) as i32;
+    let score = checker_and_fitness(last.checker_name, fitness_score(&text))
+        * PATH_LENGTH_PENALTY.powi(steps);
+    score.clamp(0.0, 1.0)
+}
+
+/// How much the checker is trusted blended with how language-like the text
+/// is, from its fitness score, before any penalty for the path's length
+pub(crate) fn checker_and_fitness(checker_name: &str, fitness: f64) -> f32 {
+    let (trust, fitness_weight) = checker_trust(checker_name);
+    trust * (1.0 - fitness_weight) + fitness_to_unit(fitness) * fitness_weight
+}
+
+/// How much a checker's word is trusted, and how much the text's English
+/// fitness should count alongside it. Checkers which look for formats like
+/// IP addresses don't expect English, so fitness counts for less.
+fn checker_trust(checker_name: &str) -> (f32, f32) {
+    match checker_name {
+        // The user's own crib or wordlist matched
+        "Regex Checker" | "Wordlist Checker" => (0.95, 0.2),
+        "LemmeKnow Checker" | "Coordinates Checker" => (0.9, 0.2),
+        "English Checker" => (0.8, 0.5),
+        "Password Checker" => (0.6, 0.5),
+        // Nothing identified the text, e.g. a recipe applied without a checker
+        "" => (0.5, 0.5),
+        // Checkers registered by library users
+        _ => (0.8, 0.2),
+    }
+}
+
+/// Squashes [`fitness_score`], which is roughly -300 for gibberish and
+/// above 100 for English, into 0 to 1
+fn fitness_to_unit(fitness: f64) -> f32 {
+    if fitness.is_nan() {
+        return 0.0;
+    }
+    (1.0 / (1.0 + (-fitness / 100.0).exp())) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoders::crack_results::CrackResult;
+
+    fn result(text: &str, checker: &'static str, steps: usize) -> DecoderResult {
+        let mut step = CrackResult::from_decoder_info("Base64", "", "", String::new());
+        step.checker_name = checker;
+        DecoderResult {
+            text: vec![text.into()],
+            path: vec![step; steps],
+            confidence: 0.0,
+        }
+    }
+
+    #[test]
+    fn english_scores_higher_than_gibberish() {
+        let english = score(&result("hello there general", "English Checker", 1));
+        let gibberish = score(&result("xkqzvbpt qwmn", "English Checker", 1));
+        assert!(english > 0.8, "{english}");
+        assert!(gibberish < 0.5, "{gibberish}");
+    }
+
+    #[test]
+    fn longer_paths_score_lower() {
+        let short = score(&result("192.168.0.1", "LemmeKnow Checker", 1));
+        let long = score(&result("192.168.0.1", "LemmeKnow Checker", 5));
+        assert!(short > long);
+        assert!(short >= 0.7, "{short}");
+    }
+
+    #[test]
+    fn empty_path_has_no_confidence() {
+        let mut empty = result("hello", "English Checker", 1);
+        empty.path.clear();
+        assert_eq!(score(&empty), 0.0);
+    }
+}
@@ -1,18 +1,39 @@
 /// import general checker
-use lemmeknow::Identifier;
+use crate::input_format::InputFormat;
+use crate::checkers::Identifier;
 use memmap2::Mmap;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{self, BufRead, BufReader};
-use std::io::{Read, Write};
+use std::io::Write;
 use std::path::Path;
 
-/// Library input is the default API input
-/// The CLI turns its arguments into a LibraryInput struct
 /// The Config object is a default configuration object
 /// For the entire program
 /// It's access using a variable like configuration
+///
+/// There is no global config. Each crack passes its `&Config` down to the
+/// searcher, decoders and checkers, so cracks with different configs can run
+/// at the same time in one process. A few things are kept for the whole
+/// process rather than per config though:
+///
+/// - The cache database of [`crate::perform_cracking`] and the other free
+///   functions: the first `database_path` set up is used from then on. Give
+///   each [`crate::AresSession`] its own config instead, as a session keeps
+///   the database its config names.
+/// - The plaintexts the human checker rejected, which go to that first
+///   database, and the verifier set with [`crate::set_human_verifier`]
+/// - Subscribers to [`crate::events`]
+/// - Decoders and checkers added with [`crate::register_decoder`] and
+///   [`crate::register_checker`]
+/// - How often each decoder has succeeded, which steers every search
+/// - The binary artifacts recorded for `--artifacts-dir`
+/// - The CLI countdown, which stops while the human checker is asking
+///
+/// The plaintexts found in `top_results` mode, the stats and the human
+/// checker's answers are kept per crack, see `plaintext_results`, `stats`
+/// and `human_answers`.
 /// ```rust
 /// use ares::config::Config;
 /// let config = Config::default();
@@ -49,28 +70,208 @@ pub struct Config {
     /// This is in seconds
     pub timeout: u32,
     /// Whether to collect all plaintexts until timeout expires
-    /// instead of exiting after finding the first valid plaintext
+    /// instead of exiting after finding the first valid plaintext.
+    /// These are collected in storage shared by the whole process, so only
+    /// run one such crack at a time.
     pub top_results: bool,
+    /// How many plaintexts to show at most in top results mode, best first.
+    /// None shows all of them.
+    pub top_results_count: Option<usize>,
+    /// In top results mode, stop searching once this many plaintexts which
+    /// differ by more than case or whitespace are found, instead of at the
+    /// timeout. None searches until the timeout.
+    pub max_results: Option<usize>,
     /// Is the program being run in API mode?
     /// This is used to determine if we should print to stdout
     /// Or return the values
     pub api_mode: bool,
     /// Regex enables the user to search for a specific regex or crib
     pub regex: Option<String>,
-    /// Path to the wordlist file. Will be overridden by CLI argument if provided.
+    /// Path to the wordlist file, or the name of one added with `ares wordlist add`.
+    /// Will be overridden by CLI argument if provided.
     pub wordlist_path: Option<String>,
     /// Wordlist data structure (loaded from file). CLI takes precedence if both config and CLI specify a wordlist.
     #[serde(skip)]
     pub wordlist: Option<HashSet<String>>,
+    /// Words added with `ares dict add`, read from the database for each
+    /// crack. The wordlist checker matches them and keyword attacks like
+    /// Vigenère and Playfair try them as keys.
+    #[serde(skip)]
+    pub dictionary_words: Vec<String>,
+    /// Flag prefixes added with `ares dict add --prefix`, like `flag{`. The
+    /// English checker accepts any text containing one.
+    #[serde(skip)]
+    pub dictionary_prefixes: Vec<String>,
     /// Colourscheme hashmap
     pub colourscheme: HashMap<String, String>,
     /// Enables enhanced plaintext detection using a BERT model.
     pub enhanced_detection: bool,
     /// Path to the enhanced detection model. If None, will use the default path.
     pub model_path: Option<String>,
+    /// Path to write a run report to. The format is picked from the extension
+    /// (.csv, .md or .html). Only set from the CLI.
+    #[serde(skip)]
+    pub report_path: Option<String>,
+    /// Command run by the shell when cracking finishes, with the outcome as
+    /// arguments and JSON on stdin, or `desktop` for a desktop notification.
+    /// See [`crate::cli::notify`].
+    pub notify_command: Option<String>,
+    /// Show the search in a terminal interface instead of printing to stdout.
+    /// Only set from the CLI.
+    #[serde(skip)]
+    pub tui: bool,
+    /// Show progress on stderr while searching, as a bar or as JSON lines.
+    /// Only set from the CLI.
+    #[serde(skip)]
+    pub progress: Option<ProgressFormat>,
+    /// Only print the plaintext. Only set from the CLI.
+    #[serde(skip)]
+    pub quiet: bool,
+    /// Path to write the plaintext to, byte for byte. Only set from the CLI.
+    #[serde(skip)]
+    pub output_path: Option<String>,
+    /// Directory to save binary blobs found while decoding. Only set from the CLI.
+    #[serde(skip)]
+    pub artifacts_dir: Option<String>,
+    /// Copy the plaintext to the system clipboard. Only set from the CLI.
+    #[serde(skip)]
+    pub copy_to_clipboard: bool,
+    /// How the input is encoded, e.g. hex for binary data. Only set from the CLI.
+    #[serde(skip)]
+    pub input_format: InputFormat,
+    /// Only run decoders whose name or tags match one of these entries.
+    /// An empty list means every decoder is allowed.
+    pub decoders_only: Vec<String>,
+    /// Never run decoders whose name or tags match one of these entries.
+    pub decoders_exclude: Vec<String>,
+    /// Number of threads the search runs decoders on.
+    /// None (or 0) uses rayon's default of one thread per CPU.
+    pub threads: Option<usize>,
+    /// Keep the text each decoder produced on its step of the result path,
+    /// for debugging multi-step paths. Off by default as every search node
+    /// holds its own copy of its path.
+    pub keep_intermediates: bool,
+    /// Re-encode the plaintext back along the result path, marking each step
+    /// whose decoder can encode as `verified` if it gives back the step's
+    /// input. Steps which don't are lossy.
+    pub verify: bool,
+    /// How many seconds a cached result stays usable. None keeps results
+    /// until Ares is upgraded, as results from other versions are never used.
+    pub cache_ttl: Option<u64>,
+    /// The most results the cache keeps. Once it is full, the least recently
+    /// used results are removed. None lets the cache grow without limit.
+    pub cache_max_entries: Option<usize>,
+    /// Whether cracks read from and write to the cache database
+    pub cache_mode: CacheMode,
+    /// Where the cache database is kept. None uses the `ARES_DB_PATH`
+    /// environment variable, or `~/.ares/database.sqlite` if that isn't set.
+    /// `":memory:"` keeps the database in memory until the process exits.
+    pub database_path: Option<String>,
+    /// Search again even if the input failed to crack before with the same
+    /// settings and at least as long a timeout
+    pub retry_failed: bool,
+    /// Languages the plaintext may be in. Classical cipher attacks like
+    /// Vigenère and Playfair keep a key if the text looks like any of them.
+    pub languages: Vec<crate::cryptanalysis::Language>,
+    /// Seeds the random number generator of the hill-climbing attacks, so
+    /// runs can be reproduced. None seeds it from the operating system.
+    pub seed: Option<u64>,
+    /// Text known to be in the plaintext, like `flag{`. Vigenère, XOR and
+    /// substitution attacks first try the keys which would put it there.
+    pub crib: Option<String>,
+    /// Letters known to stand for each other in a substitution, written as
+    /// ciphertext letters `=` plaintext letters, like `E=X,T=Q` or `QVW=THE`.
+    /// The monoalphabetic solver keeps them fixed and searches the rest.
+    pub letter_map: Option<String>,
+    /// Inputs bigger than this many bytes have Base64, hex, quoted-printable
+    /// and gzip layers decoded in chunks before the search, and the search
+    /// skips decoders which can't stream on candidates this big.
+    pub stream_threshold: usize,
+    /// Remove invisible characters and replace ASCII lookalikes, like curly
+    /// quotes and fullwidth letters, before searching. See [`crate::normalization`].
+    pub normalize_input: bool,
+    /// Show plaintexts with word spacing and sentence case restored, like
+    /// "The quick brown fox" for "THEQUICKBROWNFOX". See [`crate::postprocess`].
+    pub readable: bool,
+    /// How the path of a plaintext found in several steps is shown: a table
+    /// with a row per step, or a tree from the input down to the plaintext
+    pub path_style: PathStyle,
+    /// The language of the CLI's messages. None picks it from the system's
+    /// locale, falling back to English. See [`crate::cli_pretty_printing::i18n`].
+    pub lang: Option<crate::cli_pretty_printing::i18n::Lang>,
+    /// Letters, in order, which the Caesar decoder also shifts around, for
+    /// ciphers over alphabets like "0123456789" or "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"
+    pub custom_alphabet: Option<String>,
+    /// The prefix code, like a Huffman table, which the Prefix Code decoder
+    /// reads text with. Set from the `--codebook` file.
+    #[serde(skip)]
+    pub codebook: Option<crate::decoders::prefix_code_decoder::Codebook>,
+    /// File to decode in chunks with [`crate::streaming`] instead of reading
+    /// it into memory. Only set from the CLI.
+    #[serde(skip)]
+    pub stream_file: Option<String>,
+    /// When the search has to stop. The search starts one from `timeout` if
+    /// this is None; library users can pass their own to share or cancel it.
+    #[serde(skip)]
+    pub deadline: Option<crate::deadline::Deadline>,
+    /// Counts what the search does, see [`crate::stats`]. The
+    /// search starts its own count if this is None; pass one to read the
+    /// counts after the crack, whether or not it found the plaintext.
+    #[serde(skip)]
+    pub stats: Option<crate::searchers::stats::StatsCounter>,
+    /// What the human checker was told, see
+    /// [`crate::checkers::human_checker::HumanAnswers`]. Each crack starts
+    /// its own if this is None, so one crack's "yes" isn't another's.
+    #[serde(skip)]
+    pub human_answers: Option<crate::checkers::human_checker::HumanAnswers>,
+    /// The plaintexts found in `top_results` mode, see
+    /// [`crate::storage::wait_athena_storage::PlaintextResults`]. Each such
+    /// crack starts its own if this is None; pass one to read them after the
+    /// crack.
+    #[serde(skip)]
+    pub plaintext_results: Option<crate::storage::wait_athena_storage::PlaintextResults>,
+}
+
+/// How a crack uses the cache database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheMode {
+    /// Never open the cache, e.g. when benchmarking the search
+    Off,
+    /// Reuse cached results but never change the database, e.g. for forensic runs
+    ReadOnly,
+    /// Reuse cached results and store new ones
+    #[default]
+    ReadWrite,
+}
+
+/// How `--progress` shows the search's progress on stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum ProgressFormat {
+    /// A progress bar, drawn when stderr is a terminal
+    #[default]
+    Bar,
+    /// One JSON object per line, for wrappers and CI tooling
+    Json,
+}
+
+/// How the path of a plaintext found in several steps is shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum PathStyle {
+    /// A row per step with its decoder, key, checker, time and output
+    #[default]
+    Table,
+    /// Each step below the one before, starting from the input
+    Tree,
 }
 
 /// Convert Config fields into an Identifier
+#[cfg(feature = "cli")]
 fn make_identifier_from_config(config: &Config) -> Identifier {
     Identifier {
         min_rarity: config.lemmeknow_min_rarity,
@@ -83,6 +284,7 @@ fn make_identifier_from_config(config: &Config) -> Identifier {
 }
 
 /// Update Config's Identifier field from its serialization fields
+#[cfg(feature = "cli")]
 fn update_identifier_in_config(config: &mut Config) {
     config.lemmeknow_config = make_identifier_from_config(config);
 }
@@ -100,13 +302,52 @@ impl Default for Config {
             human_checker_on: false,
             timeout: 5,
             top_results: false,
+            top_results_count: None,
+            max_results: None,
             api_mode: false,
             regex: None,
             wordlist_path: None,
             wordlist: None,
+            dictionary_words: Vec::new(),
+            dictionary_prefixes: Vec::new(),
             enhanced_detection: false,
             model_path: None,
             colourscheme: HashMap::new(),
+            report_path: None,
+            notify_command: None,
+            tui: false,
+            progress: None,
+            quiet: false,
+            output_path: None,
+            artifacts_dir: None,
+            copy_to_clipboard: false,
+            input_format: InputFormat::Raw,
+            decoders_only: vec![],
+            decoders_exclude: vec![],
+            threads: None,
+            keep_intermediates: false,
+            verify: false,
+            cache_ttl: None,
+            cache_max_entries: Some(10_000),
+            cache_mode: CacheMode::ReadWrite,
+            database_path: None,
+            retry_failed: false,
+            languages: vec![crate::cryptanalysis::Language::English],
+            seed: None,
+            crib: None,
+            letter_map: None,
+            stream_threshold: 16 * 1024 * 1024,
+            normalize_input: true,
+            readable: false,
+            path_style: PathStyle::Table,
+            lang: None,
+            custom_alphabet: None,
+            codebook: None,
+            stream_file: None,
+            deadline: None,
+            stats: None,
+            human_answers: None,
+            plaintext_results: None,
         };
 
         // Set default colors
@@ -165,41 +406,66 @@ pub fn create_default_config_file() -> std::io::Result<()> {
 }
 
 /// Read and parse the config file
+#[cfg(feature = "cli")]
 fn read_config_file() -> std::io::Result<String> {
-    let path = get_config_file_path();
-    let mut file = File::open(&path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    Ok(contents)
+    fs::read_to_string(get_config_file_path())
 }
 
+/// The keys the config file may set, each of which can also be set by an
+/// `ARES_` environment variable, like `ARES_TIMEOUT` for `timeout`
+#[cfg(feature = "cli")]
+const CONFIG_KEYS: [&str; 39] = [
+    "verbose",
+    "lemmeknow_min_rarity",
+    "enhanced_detection",
+    "model_path",
+    "notify_command",
+    "lemmeknow_max_rarity",
+    "lemmeknow_tags",
+    "lemmeknow_exclude_tags",
+    "lemmeknow_boundaryless",
+    "human_checker_on",
+    "timeout",
+    "top_results",
+    "top_results_count",
+    "max_results",
+    "api_mode",
+    "regex",
+    "wordlist_path",
+    "question",
+    "colourscheme",
+    "decoders_only",
+    "decoders_exclude",
+    "threads",
+    "keep_intermediates",
+    "verify",
+    "cache_ttl",
+    "cache_max_entries",
+    "cache_mode",
+    "database_path",
+    "retry_failed",
+    "languages",
+    "seed",
+    "crib",
+    "letter_map",
+    "stream_threshold",
+    "normalize_input",
+    "readable",
+    "path_style",
+    "lang",
+    "custom_alphabet",
+];
+
 /// Parse a TOML string into a Config struct, handling unknown keys
+#[cfg(feature = "cli")]
 fn parse_toml_with_unknown_keys(contents: &str) -> Config {
     // First parse into a generic Value to check for unknown keys
     let parsed_value: toml::Value = toml::from_str(contents).expect("Could not parse config file");
 
     // Check for unknown keys at the root level
     if let toml::Value::Table(table) = &parsed_value {
-        let known_keys = [
-            "verbose",
-            "lemmeknow_min_rarity",
-            "enhanced_detection",
-            "model_path",
-            "lemmeknow_max_rarity",
-            "lemmeknow_tags",
-            "lemmeknow_exclude_tags",
-            "lemmeknow_boundaryless",
-            "human_checker_on",
-            "timeout",
-            "top_results",
-            "api_mode",
-            "regex",
-            "wordlist_path",
-            "question",
-            "colourscheme",
-        ];
         for key in table.keys() {
-            if !known_keys.contains(&key.as_str()) {
+            if !CONFIG_KEYS.contains(&key.as_str()) {
                 crate::cli_pretty_printing::warning_unknown_config_key(key, &Config::default());
             }
         }
@@ -211,6 +477,99 @@ fn parse_toml_with_unknown_keys(contents: &str) -> Config {
     config
 }
 
+/// The prefix of the environment variables which set config keys
+pub const ENV_PREFIX: &str = "ARES_";
+
+/// Sets the config keys given by `ARES_` environment variables, so containers
+/// can be configured without a config file. They override the config file,
+/// and CLI flags override them. `ARES_DB_PATH` sets `database_path` too.
+#[cfg(feature = "cli")]
+fn apply_env_overrides(config: &mut Config) {
+    apply_overrides(config, |name| std::env::var(name).ok());
+}
+
+/// Sets each config key for which `var` gives a value. Values are read as
+/// TOML, like `10`, `true` or `["base64", "hex"]`, falling back to a plain
+/// string and then a comma-separated list, so `ARES_CACHE_MODE=read-only`
+/// and `ARES_DECODERS_ONLY=base64,hex` work as they look.
+#[cfg(feature = "cli")]
+fn apply_overrides(config: &mut Config, var: impl Fn(&str) -> Option<String>) {
+    let mut overrides: Vec<(String, String)> = CONFIG_KEYS
+        .iter()
+        .filter_map(|key| {
+            let name = format!("{}{}", ENV_PREFIX, key.to_uppercase());
+            var(&name).map(|value| (key.to_string(), value))
+        })
+        .collect();
+    if !overrides.iter().any(|(key, _)| key == "database_path") {
+        // The database's own variable, which the database also reads
+        if let Some(value) = var("ARES_DB_PATH") {
+            overrides.push(("database_path".to_string(), value));
+        }
+    }
+    if overrides.is_empty() {
+        return;
+    }
+    let mut table = match toml::Value::try_from(&*config) {
+        Ok(toml::Value::Table(table)) => table,
+        _ => return,
+    };
+    for (key, value) in overrides {
+        let previous = table.get(&key).cloned();
+        let accepted = env_value_candidates(&value).into_iter().any(|candidate| {
+            table.insert(key.clone(), candidate);
+            toml::Value::Table(table.clone()).try_into::<Config>().is_ok()
+        });
+        if !accepted {
+            eprintln!(
+                "{}",
+                crate::cli_pretty_printing::warning(
+                    &format!(
+                        "Ignoring {}{}: '{}' isn't a valid value for {}",
+                        ENV_PREFIX,
+                        key.to_uppercase(),
+                        value,
+                        key
+                    ),
+                    config
+                )
+            );
+            match previous {
+                Some(previous) => table.insert(key, previous),
+                None => table.remove(&key),
+            };
+        }
+    }
+    if let Ok(mut merged) = toml::Value::Table(table).try_into::<Config>() {
+        update_identifier_in_config(&mut merged);
+        // A wordlist already loaded is kept, unless it was swapped for another
+        if merged.wordlist_path == config.wordlist_path {
+            merged.wordlist = config.wordlist.take();
+        }
+        *config = merged;
+    }
+}
+
+/// The ways an environment variable's value may be meant, most specific first
+#[cfg(feature = "cli")]
+fn env_value_candidates(value: &str) -> Vec<toml::Value> {
+    let mut candidates = Vec::new();
+    if let Ok(mut parsed) = toml::from_str::<toml::Table>(&format!("value = {}", value)) {
+        if let Some(parsed) = parsed.remove("value") {
+            candidates.push(parsed);
+        }
+    }
+    candidates.push(toml::Value::String(value.to_string()));
+    candidates.push(toml::Value::Array(
+        value
+            .split(',')
+            .map(|item| toml::Value::String(item.trim().to_string()))
+            .filter(|item| item.as_str() != Some(""))
+            .collect(),
+    ));
+    candidates
+}
+
 /// Loads a wordlist from a file into a HashSet for efficient lookups
 /// Uses memory mapping for large files to improve performance and memory usage
 ///
@@ -279,6 +638,7 @@ pub fn load_wordlist<P: AsRef<Path>>(path: P) -> io::Result<HashSet<String>> {
 }
 
 /// Get configuration from file or create default if it doesn't exist
+#[cfg(feature = "cli")]
 pub fn get_config_file_into_struct() -> Config {
     let path = get_config_file_path();
 
@@ -320,17 +680,19 @@ pub fn get_config_file_into_struct() -> Config {
 
         // Save the config to file
         save_config_to_file(&config, &path);
+        apply_env_overrides(&mut config);
         config
     } else {
         // Existing config - read and parse it
         match read_config_file() {
             Ok(contents) => {
                 let mut config = parse_toml_with_unknown_keys(&contents);
+                apply_env_overrides(&mut config);
 
                 // If wordlist is specified in config file, set it in the config struct
                 if let Some(wordlist_path) = &config.wordlist_path {
-                    // Load the wordlist here in the config layer
-                    match load_wordlist(wordlist_path) {
+                    // Load the wordlist here in the config layer, by name if it is a managed one
+                    match load_wordlist(crate::storage::wordlists::resolve_default(wordlist_path)) {
                         Ok(wordlist) => {
                             config.wordlist = Some(wordlist);
                         }
@@ -338,7 +700,7 @@ pub fn get_config_file_into_struct() -> Config {
                             // Critical error - exit if config specifies wordlist but can't load it
                             eprintln!("Can't load wordlist at '{}'. Either fix or remove wordlist from config file at '{}'", 
                                 wordlist_path, path.display());
-                            std::process::exit(1);
+                            std::process::exit(crate::cli::exit_code::INVALID_INPUT);
                         }
                     }
                 }
@@ -354,6 +716,7 @@ pub fn get_config_file_into_struct() -> Config {
 }
 
 /// Save a Config struct to a file
+#[cfg(feature = "cli")]
 fn save_config_to_file(config: &Config, path: &std::path::Path) {
     let toml_string = toml::to_string_pretty(config).expect("Could not serialize config");
     let mut file = File::create(path).expect("Could not create config file");
@@ -381,13 +744,87 @@ impl Clone for Config {
             human_checker_on: self.human_checker_on,
             timeout: self.timeout,
             top_results: self.top_results,
+            top_results_count: self.top_results_count,
+            max_results: self.max_results,
             api_mode: self.api_mode,
             regex: self.regex.clone(),
             wordlist_path: self.wordlist_path.clone(),
             wordlist: self.wordlist.clone(),
+            dictionary_words: self.dictionary_words.clone(),
+            dictionary_prefixes: self.dictionary_prefixes.clone(),
             colourscheme: self.colourscheme.clone(),
             enhanced_detection: self.enhanced_detection,
             model_path: self.model_path.clone(),
+            report_path: self.report_path.clone(),
+            notify_command: self.notify_command.clone(),
+            tui: self.tui,
+            progress: self.progress,
+            quiet: self.quiet,
+            output_path: self.output_path.clone(),
+            artifacts_dir: self.artifacts_dir.clone(),
+            copy_to_clipboard: self.copy_to_clipboard,
+            input_format: self.input_format,
+            decoders_only: self.decoders_only.clone(),
+            decoders_exclude: self.decoders_exclude.clone(),
+            threads: self.threads,
+            keep_intermediates: self.keep_intermediates,
+            verify: self.verify,
+            cache_ttl: self.cache_ttl,
+            cache_max_entries: self.cache_max_entries,
+            cache_mode: self.cache_mode,
+            database_path: self.database_path.clone(),
+            retry_failed: self.retry_failed,
+            languages: self.languages.clone(),
+            seed: self.seed,
+            crib: self.crib.clone(),
+            letter_map: self.letter_map.clone(),
+            stream_threshold: self.stream_threshold,
+            normalize_input: self.normalize_input,
+            readable: self.readable,
+            path_style: self.path_style,
+            lang: self.lang,
+            custom_alphabet: self.custom_alphabet.clone(),
+            codebook: self.codebook.clone(),
+            stream_file: self.stream_file.clone(),
+            deadline: self.deadline.clone(),
+            stats: self.stats.clone(),
+            human_answers: self.human_answers.clone(),
+            plaintext_results: self.plaintext_results.clone(),
         }
     }
 }
+
+#[cfg(all(test, feature = "cli"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn environment_variables_override_the_config_file() {
+        let vars: HashMap<&str, &str> = [
+            ("ARES_TIMEOUT", "30"),
+            ("ARES_API_MODE", "true"),
+            ("ARES_CACHE_MODE", "read-only"),
+            ("ARES_DECODERS_ONLY", "base64, hex"),
+            ("ARES_CRIB", "1234"),
+            ("ARES_DB_PATH", "/data/ares.sqlite"),
+            ("ARES_THREADS", "lots"),
+        ]
+        .into();
+        let mut config = Config {
+            verbose: 2,
+            threads: Some(4),
+            ..Config::default()
+        };
+        apply_overrides(&mut config, |name| vars.get(name).map(|v| v.to_string()));
+
+        assert_eq!(config.timeout, 30);
+        assert!(config.api_mode);
+        assert_eq!(config.cache_mode, CacheMode::ReadOnly);
+        assert_eq!(config.decoders_only, ["base64", "hex"]);
+        assert_eq!(config.crib.as_deref(), Some("1234"));
+        assert_eq!(config.database_path.as_deref(), Some("/data/ares.sqlite"));
+        // Invalid values are ignored, and keys without a variable are left as they were
+        assert_eq!(config.threads, Some(4));
+        assert_eq!(config.verbose, 2);
+    }
+}
@@ -0,0 +1,337 @@
+//! Ciphertexts encrypted with the same key, attacked together
+//!
+//! Reusing a key is the classic mistake, and it makes the messages far weaker
+//! together than apart. Two XOR ciphertexts under the same keystream XOR to
+//! the XOR of their plaintexts, which for text never sets the top bit. And the
+//! letters under each Vigenère key letter can be pooled across messages,
+//! giving frequency analysis many more letters per column than any one
+//! message has.
+//!
+//! [`correlate`] checks for both and, if the messages share a key, works it
+//! out from all of them at once.
+
+use super::{index_of_coincidence, Language, ENGLISH_LETTER_FREQ};
+#[cfg(feature = "classical")]
+use super::{best_fitness_score, chi_squared_score_in, languages_or_english};
+#[cfg(feature = "classical")]
+use crate::decoders::interface::{Crack, Decoder};
+#[cfg(feature = "classical")]
+use crate::decoders::vigenere_decoder::VigenereDecoder;
+
+/// The longest Vigenère key looked for
+const MAX_VIGENERE_KEY_LEN: usize = 20;
+
+/// The pooled index of coincidence above which the columns are taken to be
+/// shifted plaintext, between random (0.038) and English (0.067)
+const SHARED_KEY_IC: f64 = 0.058;
+
+/// How many of the XORs of two ciphertexts must have the top bit clear for
+/// them to share a keystream. Text never sets it, random bytes do half the time.
+const SHARED_KEYSTREAM_ASCII: f64 = 0.95;
+
+/// The cipher a set of messages was found to share a key for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharedCipher {
+    /// XOR with the same keystream, or a repeating key as long as the messages
+    XorKeystream,
+    /// Vigenère with the same key, starting at the first letter of each message
+    Vigenere,
+}
+
+impl SharedCipher {
+    /// The name shown to users
+    pub fn name(self) -> &'static str {
+        match self {
+            SharedCipher::XorKeystream => "XOR",
+            SharedCipher::Vigenere => "Vigenère",
+        }
+    }
+}
+
+/// The key a set of messages shares and what it decrypts them to
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedKey {
+    /// What the messages were encrypted with
+    pub cipher: SharedCipher,
+    /// The key: hex for XOR, letters for Vigenère
+    pub key: String,
+    /// Each message decrypted with the key, in the order given
+    pub plaintexts: Vec<String>,
+}
+
+/// Checks whether the messages share an XOR keystream or a Vigenère key and,
+/// if they do, attacks them jointly
+///
+/// ```
+/// use ares::cryptanalysis::correlation::{correlate, SharedCipher};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::decoders::vigenere_decoder::VigenereDecoder;
+///
+/// // Decrypting with "QWC" encrypts with "KEY"
+/// let vigenere = Decoder::<VigenereDecoder>::new();
+/// let messages: Vec<Vec<u8>> = [
+///     "meet me at the station at noon and bring the documents",
+///     "we attack at dawn so have the soldiers ready by the mill",
+///     "the treasure is buried under the oak tree by the river",
+/// ]
+/// .iter()
+/// .map(|message| vigenere.decode_with_key(message, "QWC").unwrap().into_bytes())
+/// .collect();
+///
+/// let shared = correlate(&messages, &[]).unwrap();
+/// assert_eq!(shared.cipher, SharedCipher::Vigenere);
+/// assert_eq!(shared.key, "KEY");
+/// assert_eq!(shared.plaintexts[1], "we attack at dawn so have the soldiers ready by the mill");
+/// ```
+pub fn correlate(ciphertexts: &[Vec<u8>], languages: &[Language]) -> Option<SharedKey> {
+    if ciphertexts.len() < 2 {
+        return None;
+    }
+    if shares_xor_keystream(ciphertexts) {
+        let key = solve_xor_keystream(ciphertexts);
+        let plaintexts = ciphertexts
+            .iter()
+            .map(|ciphertext| crate::byte_text::bytes_to_text(&xor(ciphertext, &key)))
+            .collect();
+        return Some(SharedKey {
+            cipher: SharedCipher::XorKeystream,
+            key: hex::encode(key),
+            plaintexts,
+        });
+    }
+    shared_vigenere_key(ciphertexts, languages)
+}
+
+/// The Vigenère key the messages share and what it decrypts them to, if
+/// their pooled columns look like shifted text
+#[cfg(feature = "classical")]
+fn shared_vigenere_key(ciphertexts: &[Vec<u8>], languages: &[Language]) -> Option<SharedKey> {
+    let texts: Vec<String> = ciphertexts
+        .iter()
+        .map(|ciphertext| String::from_utf8_lossy(ciphertext).into_owned())
+        .collect();
+    let key_length = shared_vigenere_key_length(&texts)?;
+    let vigenere = Decoder::<VigenereDecoder>::new();
+    languages_or_english(languages)
+        .iter()
+        .map(|language| solve_shared_vigenere(&texts, key_length, *language))
+        // A key of all A's leaves the messages as they are, so isn't a key
+        .filter(|key| key.bytes().any(|b| b != b'A'))
+        .filter_map(|key| {
+            let plaintexts = texts
+                .iter()
+                .map(|text| vigenere.decode_with_key(text, &key))
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?;
+            Some(SharedKey {
+                cipher: SharedCipher::Vigenere,
+                key,
+                plaintexts,
+            })
+        })
+        .max_by(|a, b| {
+            let fitness = |shared: &SharedKey| {
+                best_fitness_score(&shared.plaintexts.concat(), languages)
+            };
+            fitness(a).total_cmp(&fitness(b))
+        })
+}
+
+/// Without the classical ciphers there is no Vigenère to decrypt with
+#[cfg(not(feature = "classical"))]
+fn shared_vigenere_key(_ciphertexts: &[Vec<u8>], _languages: &[Language]) -> Option<SharedKey> {
+    None
+}
+
+/// Whether every pair of ciphertexts XORs to bytes which look like the XOR
+/// of two texts. The ciphertexts must look random themselves, as the XOR of
+/// two texts which were never XOR'd looks like that too.
+pub fn shares_xor_keystream(ciphertexts: &[Vec<u8>]) -> bool {
+    let bytes: Vec<&u8> = ciphertexts.iter().flatten().collect();
+    let high = bytes.iter().filter(|b| ***b >= 0x80).count();
+    if bytes.is_empty() || (high as f64) < bytes.len() as f64 * 0.2 {
+        return false;
+    }
+    let (mut ascii, mut total) = (0, 0);
+    for (i, a) in ciphertexts.iter().enumerate() {
+        for b in &ciphertexts[i + 1..] {
+            for (x, y) in a.iter().zip(b) {
+                total += 1;
+                if x ^ y < 0x80 {
+                    ascii += 1;
+                }
+            }
+        }
+    }
+    total >= 8 && ascii as f64 >= total as f64 * SHARED_KEYSTREAM_ASCII
+}
+
+/// The log of how likely a byte is in English text, roughly. Logs make a
+/// single `z` or control byte in a column cost more than a common letter gains.
+fn english_byte_score(byte: u8) -> f64 {
+    let probability = match byte {
+        b' ' => 0.18,
+        b'a'..=b'z' => 0.75 * ENGLISH_LETTER_FREQ[(byte - b'a') as usize] / 100.0,
+        b'A'..=b'Z' => 0.03 * ENGLISH_LETTER_FREQ[(byte - b'A') as usize] / 100.0,
+        b'.' | b',' | b'\'' | b'"' | b'\n' | b'0'..=b'9' => 0.002,
+        0x21..=0x7e => 0.0002,
+        _ => 0.000_001,
+    };
+    probability.ln()
+}
+
+/// The keystream, as long as the longest ciphertext, whose every byte turns
+/// the bytes at its position in all the ciphertexts most into English
+pub fn solve_xor_keystream(ciphertexts: &[Vec<u8>]) -> Vec<u8> {
+    let longest = ciphertexts.iter().map(Vec::len).max().unwrap_or(0);
+    (0..longest)
+        .map(|position| {
+            let column: Vec<u8> = ciphertexts
+                .iter()
+                .filter_map(|ciphertext| ciphertext.get(position).copied())
+                .collect();
+            (0..=255u8)
+                .max_by(|a, b| {
+                    let score =
+                        |key: u8| -> f64 { column.iter().map(|c| english_byte_score(c ^ key)).sum() };
+                    score(*a).total_cmp(&score(*b))
+                })
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// XORs `bytes` with `key`, repeating the key if it is shorter
+fn xor(bytes: &[u8], key: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .zip(key.iter().cycle())
+        .map(|(byte, key)| byte ^ key)
+        .collect()
+}
+
+/// The letters of each text, uppercased
+fn letters(texts: &[String]) -> Vec<Vec<u8>> {
+    texts
+        .iter()
+        .map(|text| {
+            text.bytes()
+                .filter(u8::is_ascii_alphabetic)
+                .map(|b| b.to_ascii_uppercase())
+                .collect()
+        })
+        .collect()
+}
+
+/// The letters under key letter `column` of a key `key_length` long, from every text
+fn pooled_column(letters: &[Vec<u8>], key_length: usize, column: usize) -> String {
+    letters
+        .iter()
+        .flat_map(|text| text.iter().skip(column).step_by(key_length))
+        .map(|&b| b as char)
+        .collect()
+}
+
+/// The shortest Vigenère key length for which the letters under each key
+/// letter, pooled across all the texts, look like shifted plaintext
+pub fn shared_vigenere_key_length(texts: &[String]) -> Option<usize> {
+    let letters = letters(texts);
+    (1..=MAX_VIGENERE_KEY_LEN).find(|&key_length| {
+        let columns: Vec<String> = (0..key_length)
+            .map(|column| pooled_column(&letters, key_length, column))
+            .collect();
+        // Too few letters per column and any length looks right
+        columns.iter().all(|column| column.len() >= 6)
+            && columns.iter().map(|column| index_of_coincidence(column)).sum::<f64>()
+                / key_length as f64
+                >= SHARED_KEY_IC
+    })
+}
+
+/// The Vigenère key which makes each pooled column's letters closest to `language`
+#[cfg(feature = "classical")]
+fn solve_shared_vigenere(texts: &[String], key_length: usize, language: Language) -> String {
+    let letters = letters(texts);
+    (0..key_length)
+        .map(|column| {
+            let column = pooled_column(&letters, key_length, column);
+            let shift = (0..26u8)
+                .min_by(|a, b| {
+                    let score = |shift: u8| {
+                        let shifted: String = column
+                            .bytes()
+                            .map(|c| (b'A' + (c - b'A' + 26 - shift) % 26) as char)
+                            .collect();
+                        chi_squared_score_in(&shifted, language)
+                    };
+                    score(*a).total_cmp(&score(*b))
+                })
+                .unwrap_or(0);
+            (b'A' + shift) as char
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAINTEXTS: [&str; 4] = [
+        "the quick brown fox jumps over the lazy dog while the cat sleeps",
+        "we attack at dawn so have the soldiers ready by the old mill",
+        "meet me at the station at noon and bring the documents with you",
+        "the treasure is buried under the oak tree beside the river bank",
+    ];
+
+    #[test]
+    fn a_reused_xor_keystream_is_found() {
+        let keystream: Vec<u8> = (0..64u32).map(|i| (i * 167 + 89) as u8 ^ 0xa5).collect();
+        let ciphertexts: Vec<Vec<u8>> = PLAINTEXTS
+            .iter()
+            .map(|plaintext| xor(plaintext.as_bytes(), &keystream))
+            .collect();
+        let shared = correlate(&ciphertexts, &[]).unwrap();
+        assert_eq!(shared.cipher, SharedCipher::XorKeystream);
+        // Four messages are enough to read most of each one
+        let right = shared
+            .plaintexts
+            .iter()
+            .zip(PLAINTEXTS)
+            .map(|(found, plaintext)| found.chars().zip(plaintext.chars()).filter(|(a, b)| a == b).count())
+            .sum::<usize>();
+        assert!(right as f64 > PLAINTEXTS.concat().len() as f64 * 2.0 / 3.0);
+    }
+
+    #[test]
+    fn different_keystreams_are_not_shared() {
+        let ciphertexts: Vec<Vec<u8>> = PLAINTEXTS
+            .iter()
+            .enumerate()
+            .map(|(n, plaintext)| {
+                let keystream: Vec<u8> = (0..64u32).map(|i| (i * 131 + n as u32 * 71) as u8 ^ 0x96).collect();
+                xor(plaintext.as_bytes(), &keystream)
+            })
+            .collect();
+        assert!(!shares_xor_keystream(&ciphertexts));
+    }
+
+    #[test]
+    #[cfg(feature = "classical")]
+    fn a_shared_vigenere_key_is_found_from_the_pooled_columns() {
+        let vigenere = Decoder::<VigenereDecoder>::new();
+        let ciphertexts: Vec<Vec<u8>> = PLAINTEXTS
+            .iter()
+            // Decrypting with "QWC" encrypts with "KEY"
+            .map(|plaintext| vigenere.decode_with_key(plaintext, "QWC").unwrap().into_bytes())
+            .collect();
+        let shared = correlate(&ciphertexts, &[]).unwrap();
+        assert_eq!(shared.cipher, SharedCipher::Vigenere);
+        assert_eq!(shared.key, "KEY");
+        assert_eq!(shared.plaintexts[1], PLAINTEXTS[1]);
+    }
+
+    #[test]
+    fn one_message_is_not_correlated() {
+        assert!(correlate(&[b"lxfopv ef rnhr".to_vec()], &[]).is_none());
+    }
+}
@@ -0,0 +1,522 @@
+//! Guessing what kind of cipher or encoding text is in
+//!
+//! Encodings are told apart by their alphabet and length: hexadecimal is an
+//! even number of hex digits, Base64 a multiple of 4 characters and so on.
+//! Ciphers of letters are told apart by statistics:
+//!
+//! - Transpositions keep the letter frequencies of the language, so the
+//!   index of coincidence (IC) and chi-squared test both look like plaintext
+//! - Monoalphabetic substitution keeps the IC but not which letters are common
+//! - Polyalphabetic ciphers like Vigenère flatten the IC, except between
+//!   letters a key length apart, which the Kappa test finds
+//! - Digraphic ciphers like Playfair have an even number of letters, no J,
+//!   and never the same letter twice in a pair
+//!
+//! [`identify`] scores every family, for `ares identify` and so the searcher
+//! can try the most likely decoders first. [`identify_in`] also recognises
+//! plaintext in other languages.
+
+use super::{
+    chi_squared_score, index_of_coincidence, languages_or_english, word_score_in, Language,
+};
+use crate::decoders::interface::Crack;
+
+/// A kind of cipher or encoding, covering the decoders which undo it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CipherFamily {
+    /// Already readable
+    Plaintext,
+    /// Ones and zeroes
+    Binary,
+    /// Dots and dashes
+    Morse,
+    /// Numbers, like ASCII codes or A1Z26
+    Decimal,
+    /// Numbers using only the digits 0 to 7
+    Octal,
+    /// Hex digits, two for each byte
+    Hexadecimal,
+    /// Uppercase letters and the digits 2 to 7
+    Base32,
+    /// Letters and digits without 0, O, I and l
+    Base58,
+    /// Letters, digits, `+` and `/`
+    Base64,
+    /// Most of printable ASCII
+    Base85,
+    /// URL percent escapes or HTML entities
+    WebEncoding,
+    /// Caesar, ROT13, Atbash and Affine
+    Shift,
+    /// Monoalphabetic substitution with a mixed alphabet
+    Substitution,
+    /// Vigenère and Beaufort
+    Polyalphabetic,
+    /// Columnar transposition and Railfence
+    Transposition,
+    /// Playfair and Four-Square
+    Digraphic,
+    /// Bytes which aren't text, like the output of XOR
+    Xor,
+}
+
+impl CipherFamily {
+    /// Every family, in the order they are listed
+    pub const ALL: [CipherFamily; 17] = [
+        CipherFamily::Plaintext,
+        CipherFamily::Binary,
+        CipherFamily::Morse,
+        CipherFamily::Decimal,
+        CipherFamily::Octal,
+        CipherFamily::Hexadecimal,
+        CipherFamily::Base32,
+        CipherFamily::Base58,
+        CipherFamily::Base64,
+        CipherFamily::Base85,
+        CipherFamily::WebEncoding,
+        CipherFamily::Shift,
+        CipherFamily::Substitution,
+        CipherFamily::Polyalphabetic,
+        CipherFamily::Transposition,
+        CipherFamily::Digraphic,
+        CipherFamily::Xor,
+    ];
+
+    /// The name shown by `ares identify`
+    pub fn name(self) -> &'static str {
+        match self {
+            CipherFamily::Plaintext => "Plaintext",
+            CipherFamily::Binary => "Binary",
+            CipherFamily::Morse => "Morse code",
+            CipherFamily::Decimal => "Decimal",
+            CipherFamily::Octal => "Octal",
+            CipherFamily::Hexadecimal => "Hexadecimal",
+            CipherFamily::Base32 => "Base32",
+            CipherFamily::Base58 => "Base58",
+            CipherFamily::Base64 => "Base64",
+            CipherFamily::Base85 => "Base85",
+            CipherFamily::WebEncoding => "URL or HTML encoding",
+            CipherFamily::Shift => "Shift cipher",
+            CipherFamily::Substitution => "Monoalphabetic substitution",
+            CipherFamily::Polyalphabetic => "Polyalphabetic cipher",
+            CipherFamily::Transposition => "Transposition",
+            CipherFamily::Digraphic => "Digraphic cipher",
+            CipherFamily::Xor => "XOR",
+        }
+    }
+
+    /// Whether the family is an encoding, undone without a key
+    pub fn is_encoding(self) -> bool {
+        matches!(
+            self,
+            CipherFamily::Binary
+                | CipherFamily::Morse
+                | CipherFamily::Decimal
+                | CipherFamily::Octal
+                | CipherFamily::Hexadecimal
+                | CipherFamily::Base32
+                | CipherFamily::Base58
+                | CipherFamily::Base64
+                | CipherFamily::Base85
+                | CipherFamily::WebEncoding
+        )
+    }
+
+    /// Decoder names and tags which undo the family, matched case-insensitively
+    pub fn decoders(self) -> &'static [&'static str] {
+        match self {
+            CipherFamily::Plaintext => &[],
+            CipherFamily::Binary => &["binary"],
+            CipherFamily::Morse => &["morseCode"],
+            CipherFamily::Decimal => &["decimal", "a1z26"],
+            CipherFamily::Octal => &["octal"],
+            CipherFamily::Hexadecimal => &["hexadecimal"],
+            CipherFamily::Base32 => &["base32"],
+            CipherFamily::Base58 => &["base58", "base62"],
+            CipherFamily::Base64 => &["base64"],
+            CipherFamily::Base85 => &["base85", "base91"],
+            CipherFamily::WebEncoding => &["web"],
+            CipherFamily::Shift => &["caesar", "atbash", "affine", "rot47"],
+            CipherFamily::Substitution => &["monoalphabetic", "simplesubstitution"],
+            CipherFamily::Polyalphabetic => &["vigenere", "vigenere-variant"],
+            CipherFamily::Transposition => &["transposition", "reverse"],
+            CipherFamily::Digraphic => &["digraph"],
+            CipherFamily::Xor => &["xor"],
+        }
+    }
+
+    /// Whether `decoder` undoes this family, by its name or one of its tags
+    pub fn covers(self, decoder: &(dyn Crack + Sync + Send)) -> bool {
+        self.decoders().iter().any(|wanted| {
+            decoder.get_name().eq_ignore_ascii_case(wanted)
+                || decoder
+                    .get_tags()
+                    .iter()
+                    .any(|tag| tag.eq_ignore_ascii_case(wanted))
+        })
+    }
+}
+
+/// How much text looks like one family
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    /// The family
+    pub family: CipherFamily,
+    /// From 0 to 1, higher is more likely
+    pub score: f64,
+    /// What the guess is based on, like "IC 0.042, period 5"
+    pub reason: String,
+}
+
+/// Letters needed before their statistics say anything
+const MIN_LETTERS: usize = 20;
+
+/// The percentage of text in common words above which it reads as plaintext
+const PLAINTEXT_WORDS: f64 = 50.0;
+
+/// Below this IC text isn't in a language's letter frequencies
+const LANGUAGE_IC: f64 = 0.055;
+
+/// Above this chi-squared the letters aren't English's most common ones
+const ENGLISH_CHI_SQUARED: f64 = 150.0;
+
+/// The longest key period the Kappa test looks for
+const MAX_PERIOD: usize = 20;
+
+/// Scores every family which `text` looks like, most likely first
+///
+/// ```
+/// use ares::cryptanalysis::identify::{identify, CipherFamily};
+///
+/// assert_eq!(identify("48656c6c6f20776f726c64")[0].family, CipherFamily::Hexadecimal);
+/// assert_eq!(identify("SGVsbG8gd29ybGQ=")[0].family, CipherFamily::Base64);
+/// ```
+pub fn identify(text: &str) -> Vec<Guess> {
+    identify_in(text, &[Language::English])
+}
+
+/// Like [`identify`], taking text made of words in any of `languages` as
+/// plaintext
+pub fn identify_in(text: &str, languages: &[Language]) -> Vec<Guess> {
+    let text = text.trim();
+    let mut guesses = Vec::new();
+    if text.is_empty() {
+        return guesses;
+    }
+    let mut guess = |family: CipherFamily, score: f64, reason: String| {
+        guesses.push(Guess {
+            family,
+            score,
+            reason,
+        });
+    };
+
+    let compact: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let only = |allowed: &dyn Fn(char) -> bool| compact.iter().all(|&c| allowed(c));
+    let len = compact.len();
+
+    // Encodings, by alphabet and length
+    if only(&|c| c == '0' || c == '1') {
+        let score = if len.is_multiple_of(8) { 0.95 } else { 0.7 };
+        guess(CipherFamily::Binary, score, format!("{} bits", len));
+    }
+    if only(&|c| matches!(c, '.' | '-' | '/' | '_')) {
+        guess(
+            CipherFamily::Morse,
+            0.95,
+            "only dots and dashes".to_string(),
+        );
+    }
+    if only(&|c| c.is_ascii_digit() || matches!(c, ',' | '-')) {
+        let numbers: Vec<u32> = text
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|number| number.parse().ok())
+            .collect();
+        if numbers.len() > 1 && numbers.iter().all(|&n| (1..=26).contains(&n)) {
+            guess(
+                CipherFamily::Decimal,
+                0.8,
+                "numbers from 1 to 26".to_string(),
+            );
+        } else if numbers.len() > 1 && numbers.iter().all(|&n| n <= 255) {
+            guess(CipherFamily::Decimal, 0.75, "byte values".to_string());
+        }
+        if only(&|c| ('0'..='7').contains(&c)) && numbers.len() > 1 {
+            guess(
+                CipherFamily::Octal,
+                0.6,
+                "only the digits 0 to 7".to_string(),
+            );
+        }
+    }
+    let hex_digits: Vec<char> = compact
+        .iter()
+        .copied()
+        .filter(|&c| c != ':' && c != ',')
+        .collect();
+    if hex_digits.iter().all(char::is_ascii_hexdigit) && hex_digits.len().is_multiple_of(2) {
+        let letters = hex_digits.iter().any(char::is_ascii_alphabetic);
+        let score = if letters { 0.9 } else { 0.5 };
+        guess(
+            CipherFamily::Hexadecimal,
+            score,
+            "an even number of hex digits".to_string(),
+        );
+    }
+    let unpadded = text.trim_end_matches('=');
+    if !unpadded.is_empty()
+        && unpadded
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c))
+        && len.is_multiple_of(8)
+    {
+        guess(
+            CipherFamily::Base32,
+            0.85,
+            "uppercase and 2 to 7 in blocks of 8".to_string(),
+        );
+    }
+    let base64_alphabet = |c: char| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '-' | '_');
+    if !unpadded.is_empty()
+        && unpadded.chars().all(base64_alphabet)
+        && text.len() - unpadded.len() <= 2
+        && (len.is_multiple_of(4) || text.len() == unpadded.len())
+    {
+        let mixed = unpadded.chars().any(|c| c.is_ascii_lowercase())
+            && unpadded.chars().any(|c| c.is_ascii_uppercase());
+        let score = match (mixed, len.is_multiple_of(4)) {
+            (true, true) => 0.85,
+            (true, false) => 0.5,
+            (false, _) => 0.25,
+        };
+        guess(
+            CipherFamily::Base64,
+            score,
+            format!("{} Base64 characters", len),
+        );
+    }
+    if only(&|c| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l'))
+        && compact.iter().any(char::is_ascii_digit)
+    {
+        guess(CipherFamily::Base58, 0.45, "no 0, O, I or l".to_string());
+    }
+    let punctuation = compact.iter().filter(|c| c.is_ascii_punctuation()).count();
+    if only(&|c| ('!'..='u').contains(&c)) && punctuation * 5 > len {
+        guess(CipherFamily::Base85, 0.4, "lots of punctuation".to_string());
+    }
+    let escapes = text.matches('%').count() + text.matches('&').count();
+    if escapes > 0 && (has_percent_escape(text) || text.contains(';')) {
+        guess(
+            CipherFamily::WebEncoding,
+            0.9,
+            format!("{} escapes", escapes),
+        );
+    }
+    let control = text
+        .chars()
+        .filter(|c| c.is_control() && !c.is_whitespace())
+        .count();
+    if control * 10 > text.chars().count() {
+        guess(
+            CipherFamily::Xor,
+            0.6,
+            format!("{} control characters", control),
+        );
+    }
+
+    // Ciphers of letters, by their statistics
+    let letters: Vec<u8> = text
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase())
+        .collect();
+    let letter_ratio = letters.len() as f64 / len as f64;
+    if letters.len() >= MIN_LETTERS && letter_ratio > 0.9 {
+        classify_letters(text, &letters, languages, &mut guess);
+    }
+
+    guesses.sort_by(|a, b| b.score.total_cmp(&a.score));
+    guesses
+}
+
+/// Scores the families of ciphers of letters, from the statistics of `letters`
+fn classify_letters(
+    text: &str,
+    letters: &[u8],
+    languages: &[Language],
+    guess: &mut impl FnMut(CipherFamily, f64, String),
+) {
+    let ic = index_of_coincidence(text);
+    let chi_squared = chi_squared_score(text);
+    let words = languages_or_english(languages)
+        .iter()
+        .map(|&language| word_score_in(text, language))
+        .fold(0.0, f64::max);
+    if words >= PLAINTEXT_WORDS {
+        guess(CipherFamily::Plaintext, 0.9, format!("{:.0}% words", words));
+        return;
+    }
+
+    if ic >= LANGUAGE_IC {
+        if chi_squared < ENGLISH_CHI_SQUARED {
+            guess(
+                CipherFamily::Transposition,
+                0.75,
+                format!("IC {:.3} and the letters of English", ic),
+            );
+        } else if best_shift_chi_squared(letters) < ENGLISH_CHI_SQUARED {
+            guess(
+                CipherFamily::Shift,
+                0.8,
+                format!("IC {:.3}, the letters of English shifted", ic),
+            );
+        } else {
+            guess(
+                CipherFamily::Substitution,
+                0.7,
+                format!("IC {:.3}, letters rearranged", ic),
+            );
+        }
+    } else if let Some((period, kappa)) = kappa_period(letters) {
+        guess(
+            CipherFamily::Polyalphabetic,
+            0.8,
+            format!("IC {:.3}, period {} (kappa {:.3})", ic, period, kappa),
+        );
+    } else {
+        guess(
+            CipherFamily::Polyalphabetic,
+            0.5,
+            format!("IC {:.3}, no clear period", ic),
+        );
+    }
+
+    let pairs_differ = letters
+        .chunks(2)
+        .all(|pair| pair.len() == 2 && pair[0] != pair[1]);
+    let distinct = {
+        let mut seen = [false; 26];
+        letters
+            .iter()
+            .for_each(|&l| seen[usize::from(l - b'A')] = true);
+        seen.iter().filter(|&&s| s).count()
+    };
+    if pairs_differ && distinct <= 25 && !letters.contains(&b'J') {
+        guess(
+            CipherFamily::Digraphic,
+            0.85,
+            format!(
+                "{} letters in pairs of different letters, no J",
+                letters.len()
+            ),
+        );
+    }
+}
+
+/// Whether there is a `%` followed by two hex digits
+fn has_percent_escape(text: &str) -> bool {
+    text.as_bytes()
+        .windows(3)
+        .any(|w| w[0] == b'%' && w[1].is_ascii_hexdigit() && w[2].is_ascii_hexdigit())
+}
+
+/// The lowest chi-squared against English of any Caesar shift of `letters`
+fn best_shift_chi_squared(letters: &[u8]) -> f64 {
+    (0..26u8)
+        .map(|shift| {
+            let shifted: String = letters
+                .iter()
+                .map(|&l| char::from((l - b'A' + shift) % 26 + b'A'))
+                .collect();
+            chi_squared_score(&shifted)
+        })
+        .fold(f64::MAX, f64::min)
+}
+
+/// How often a letter is the same as the one `shift` letters on
+fn kappa(letters: &[u8], shift: usize) -> f64 {
+    let pairs = letters.len().saturating_sub(shift);
+    if pairs == 0 {
+        return 0.0;
+    }
+    let same = letters
+        .iter()
+        .zip(&letters[shift..])
+        .filter(|(a, b)| a == b)
+        .count();
+    same as f64 / pairs as f64
+}
+
+/// The Kappa test: the shortest period at which letters match as often as
+/// in plaintext, when most shifts between don't, with its kappa. Each period
+/// is judged by the average kappa of its multiples, as one shift of a short
+/// text can match by chance.
+fn kappa_period(letters: &[u8]) -> Option<(usize, f64)> {
+    let max_period = MAX_PERIOD.min(letters.len() / 4);
+    let kappas: Vec<f64> = (1..=max_period)
+        .map(|shift| kappa(letters, shift))
+        .collect();
+    let average = kappas.iter().sum::<f64>() / kappas.len().max(1) as f64;
+    (2..=max_period)
+        .map(|period| {
+            let multiples: Vec<f64> = kappas
+                .iter()
+                .skip(period - 1)
+                .step_by(period)
+                .copied()
+                .collect();
+            (
+                period,
+                multiples.iter().sum::<f64>() / multiples.len() as f64,
+            )
+        })
+        // Plaintext letters match about 6.6% of the time, random ones 3.8%
+        .find(|&(_, kappa)| kappa >= LANGUAGE_IC && kappa > average * 1.25)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The first sentence of Alice in Wonderland
+    const ALICE: &str = "Alice was beginning to get very tired of sitting by her sister on the bank and of having nothing to do";
+
+    #[test]
+    fn encodings_are_told_apart_by_alphabet_and_length() {
+        let top = |text: &str| identify(text)[0].family;
+        assert_eq!(top("01001000 01101001"), CipherFamily::Binary);
+        assert_eq!(top(".... . .-.. .-.. ---"), CipherFamily::Morse);
+        assert_eq!(top("8 5 12 12 15"), CipherFamily::Decimal);
+        assert_eq!(top("JBSWY3DPEBLW64TMMQ======"), CipherFamily::Base32);
+        assert_eq!(top("hello%20world"), CipherFamily::WebEncoding);
+        assert!(identify("").is_empty());
+    }
+
+    #[test]
+    fn letter_ciphers_are_told_apart_by_statistics() {
+        let top = |text: &str| identify(text)[0].family;
+        assert_eq!(top(ALICE), CipherFamily::Plaintext);
+        // ROT13
+        assert_eq!(
+            top("Nyvpr jnf ortvaavat gb trg irel gverq bs fvggvat ol ure fvfgre ba gur onax naq bs univat abguvat gb qb"),
+            CipherFamily::Shift
+        );
+        // Reversed
+        let reversed: String = ALICE.chars().rev().collect();
+        assert_eq!(top(&reversed), CipherFamily::Transposition);
+        // Vigenère with the key "lemon"
+        let vigenere = identify(
+            "LPUQRHEEPRRMZBVYKFCTPXHSEJXUFROSRGVEXUBTMCTSEDMEHRCSZHUPFMBXLRPCSSEHWARRAHUTRSHBOS",
+        );
+        assert_eq!(vigenere[0].family, CipherFamily::Polyalphabetic);
+        assert!(
+            vigenere[0].reason.contains("period 5"),
+            "{}",
+            vigenere[0].reason
+        );
+        assert_eq!(
+            top("GMJXB EGY QBVJIIJIV PW VBP LBCS PJCBO WF YJPPJIV QS KBC YJYPBC WI PKB QGIZ GIO WF KGLJIV IWPKJIV PW OW"),
+            CipherFamily::Substitution
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Known-plaintext attacks: keys from a crib
+//!
+//! A crib is text known, or guessed, to be in the plaintext, like `flag{` or
+//! `attack at dawn`. Sliding it along the ciphertext, each position says what
+//! the key has to be for the crib to be there, and positions where no key
+//! fits are ruled out. Decoders try these keys first when `Config.crib` is set.
+//! Letters known outright can be given with `Config.letter_map` instead.
+//!
+//! Letters are counted without spaces and punctuation for Vigenère and
+//! substitution, as those ciphers skip them, and positions are counted in
+//! letters. XOR works on bytes.
+
+use std::collections::HashSet;
+
+/// What the Vigenère key must be for the crib to start at a letter of the
+/// ciphertext
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyFragment {
+    /// Which letter of the ciphertext the crib starts at
+    pub position: usize,
+    /// The key letters used for the crib, in order
+    pub key: String,
+}
+
+/// A substitution key with only some letters known. `mapping[i]` is the
+/// plaintext letter of the i-th letter of the alphabet, if the crib says.
+pub type PartialMapping = [Option<char>; 26];
+
+/// The key fragment for every position the crib could start at
+///
+/// ```
+/// use ares::cryptanalysis::kpa::vigenere_key_fragments;
+///
+/// // "hello" with the key "key"
+/// let fragments = vigenere_key_fragments("rijvs", "hello");
+/// assert_eq!(fragments[0].key, "KEYKE");
+/// ```
+pub fn vigenere_key_fragments(ciphertext: &str, crib: &str) -> Vec<KeyFragment> {
+    let ciphertext = letters(ciphertext);
+    let crib = letters(crib);
+    if crib.is_empty() || crib.len() > ciphertext.len() {
+        return Vec::new();
+    }
+    (0..=ciphertext.len() - crib.len())
+        .map(|position| KeyFragment {
+            position,
+            key: ciphertext[position..]
+                .iter()
+                .zip(&crib)
+                .map(|(c, p)| char::from(b'A' + (c + 26 - p) % 26))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Whole Vigenère keys of up to `max_len` letters which put the crib
+/// somewhere in the plaintext, shortest first. Keys longer than the crib
+/// can't be worked out from it, so aren't returned.
+///
+/// ```
+/// use ares::cryptanalysis::kpa::vigenere_keys;
+///
+/// // "the secret is hello" with the key "key"
+/// let keys = vigenere_keys("dlc ciabir sw fopjy", "hello", 3);
+/// assert!(keys.contains(&"KEY".to_string()));
+/// ```
+pub fn vigenere_keys(ciphertext: &str, crib: &str, max_len: usize) -> Vec<String> {
+    let fragments = vigenere_key_fragments(ciphertext, crib);
+    let shifts: Vec<Vec<u8>> = fragments
+        .iter()
+        .map(|fragment| fragment.key.bytes().map(|b| b - b'A').collect())
+        .collect();
+    let positions: Vec<usize> = fragments.iter().map(|fragment| fragment.position).collect();
+    repeating_keys(&shifts, &positions, max_len)
+        .into_iter()
+        .map(|key| key.iter().map(|shift| char::from(b'A' + shift)).collect())
+        .collect()
+}
+
+/// Repeating XOR keys of up to `max_len` bytes which put the crib somewhere
+/// in the plaintext, shortest first. Keys of only zeroes are left out, as
+/// they don't change anything.
+///
+/// ```
+/// use ares::cryptanalysis::kpa::xor_keys;
+///
+/// let plaintext = b"the flag is flag{xor}";
+/// let ciphertext: Vec<u8> = plaintext.iter().zip(b"ab".iter().cycle()).map(|(p, k)| p ^ k).collect();
+/// assert_eq!(xor_keys(&ciphertext, b"flag{", 2)[0], b"ab".to_vec());
+/// ```
+pub fn xor_keys(ciphertext: &[u8], crib: &[u8], max_len: usize) -> Vec<Vec<u8>> {
+    if crib.is_empty() || crib.len() > ciphertext.len() {
+        return Vec::new();
+    }
+    let fragments: Vec<Vec<u8>> = ciphertext
+        .windows(crib.len())
+        .map(|window| window.iter().zip(crib).map(|(c, p)| c ^ p).collect())
+        .collect();
+    let positions: Vec<usize> = (0..fragments.len()).collect();
+    repeating_keys(&fragments, &positions, max_len)
+        .into_iter()
+        .filter(|key| key.iter().any(|&b| b != 0))
+        .collect()
+}
+
+/// The partial substitution keys which would turn part of the ciphertext
+/// into the crib, one for each place the crib fits, in order. A place only
+/// fits if the same ciphertext letter always stands for the same crib letter
+/// and different letters for different ones.
+///
+/// ```
+/// use ares::cryptanalysis::kpa::substitution_mappings;
+///
+/// // "hello" with A->Z, B->Y, ... (Atbash): "svool"
+/// let mappings = substitution_mappings("xx svool", "hello");
+/// assert_eq!(mappings.len(), 1);
+/// assert_eq!(mappings[0][usize::from(b'S' - b'A')], Some('H'));
+/// ```
+pub fn substitution_mappings(ciphertext: &str, crib: &str) -> Vec<PartialMapping> {
+    let ciphertext = letters(ciphertext);
+    let crib = letters(crib);
+    if crib.is_empty() || crib.len() > ciphertext.len() {
+        return Vec::new();
+    }
+    ciphertext
+        .windows(crib.len())
+        .filter_map(|window| {
+            let mut mapping: PartialMapping = [None; 26];
+            let mut used = [false; 26];
+            for (&c, &p) in window.iter().zip(&crib) {
+                let plain = char::from(b'A' + p);
+                match mapping[usize::from(c)] {
+                    Some(mapped) if mapped != plain => return None,
+                    Some(_) => {}
+                    None if used[usize::from(p)] => return None,
+                    None => {
+                        mapping[usize::from(c)] = Some(plain);
+                        used[usize::from(p)] = true;
+                    }
+                }
+            }
+            Some(mapping)
+        })
+        .collect()
+}
+
+/// Reads letters known to stand for each other in a substitution, written
+/// as ciphertext letters `=` plaintext letters and separated by commas, so
+/// `E=X,T=Q` says ciphertext E is plaintext X, and `QVW=THE` says QVW is THE
+///
+/// ```
+/// use ares::cryptanalysis::kpa::parse_letter_map;
+///
+/// let mapping = parse_letter_map("E=X, qvw=the").unwrap();
+/// assert_eq!(mapping[4], Some('X'));
+/// assert_eq!(mapping[16], Some('T'));
+/// assert!(parse_letter_map("E=X,E=Y").is_err());
+/// ```
+///
+/// # Errors
+/// Returns a message if a hint isn't letters `=` as many letters, or the
+/// hints give a letter two meanings.
+pub fn parse_letter_map(text: &str) -> Result<PartialMapping, String> {
+    let mut mapping: PartialMapping = [None; 26];
+    for hint in text.split(',').map(str::trim).filter(|hint| !hint.is_empty()) {
+        let (cipher, plain) = hint
+            .split_once('=')
+            .map(|(cipher, plain)| (cipher.trim(), plain.trim()))
+            .filter(|(cipher, plain)| {
+                !cipher.is_empty()
+                    && cipher.len() == plain.len()
+                    && cipher.bytes().chain(plain.bytes()).all(|b| b.is_ascii_alphabetic())
+            })
+            .ok_or_else(|| format!("Expected letters=letters, like E=X, got '{hint}'"))?;
+        for (c, p) in letters(cipher).into_iter().zip(letters(plain)) {
+            if !insert_letter(&mut mapping, c, char::from(b'A' + p)) {
+                return Err(format!("'{hint}' disagrees with an earlier letter"));
+            }
+        }
+    }
+    Ok(mapping)
+}
+
+/// `mapping` with the letters `other` knows added, or None if they disagree
+pub fn merge_mappings(mapping: &PartialMapping, other: &PartialMapping) -> Option<PartialMapping> {
+    let mut merged = *mapping;
+    for (c, plain) in (0u8..).zip(other) {
+        if let Some(plain) = plain {
+            if !insert_letter(&mut merged, c, *plain) {
+                return None;
+            }
+        }
+    }
+    Some(merged)
+}
+
+/// Maps ciphertext letter `c`, as 0 to 25, to `plain`, returning false if
+/// either already stands for another letter
+fn insert_letter(mapping: &mut PartialMapping, c: u8, plain: char) -> bool {
+    match mapping[usize::from(c)] {
+        Some(mapped) => mapped == plain,
+        None if mapping.contains(&Some(plain)) => false,
+        None => {
+            mapping[usize::from(c)] = Some(plain);
+            true
+        }
+    }
+}
+
+/// The letters of `text` as 0 to 25, ignoring everything else
+fn letters(text: &str) -> Vec<u8> {
+    text.bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase() - b'A')
+        .collect()
+}
+
+/// Turns the key fragments the crib gives at each position into the whole
+/// repeating keys of up to `max_len` they are consistent with, shortest first
+fn repeating_keys(fragments: &[Vec<u8>], positions: &[usize], max_len: usize) -> Vec<Vec<u8>> {
+    let crib_len = fragments.first().map_or(0, Vec::len);
+    let mut seen = HashSet::new();
+    let mut keys = Vec::new();
+    for key_len in 1..=max_len.min(crib_len) {
+        for (fragment, &position) in fragments.iter().zip(positions) {
+            // The key repeats every key_len, so the fragment must too
+            if fragment
+                .iter()
+                .zip(&fragment[key_len..])
+                .any(|(a, b)| a != b)
+            {
+                continue;
+            }
+            let mut key = vec![0; key_len];
+            for (i, &k) in fragment.iter().take(key_len).enumerate() {
+                key[(position + i) % key_len] = k;
+            }
+            if seen.insert(key.clone()) {
+                keys.push(key);
+            }
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vigenere_keys_repeat_and_line_up_with_the_crib() {
+        // "attack at dawn" with "lemon", the crib starting on the key's third letter
+        let ciphertext = "lxfopv ef rnhr";
+        let keys = vigenere_keys(ciphertext, "tackatdawn", 5);
+        assert_eq!(keys, vec!["LEMON".to_string()]);
+        assert!(vigenere_keys(ciphertext, "", 5).is_empty());
+        assert!(vigenere_keys("ab", "longer crib", 5).is_empty());
+    }
+
+    #[test]
+    fn substitution_cribs_must_fit_the_letter_pattern() {
+        // "hello" can't be where the ciphertext has no repeated letter pair
+        assert!(substitution_mappings("abcde", "hello").is_empty());
+        let mappings = substitution_mappings("qwxyyz", "hello");
+        assert_eq!(mappings.len(), 1);
+        assert_eq!(mappings[0][usize::from(b'Y' - b'A')], Some('L'));
+        // Two ciphertext letters can't both be "l"
+        assert!(substitution_mappings("abcde", "ll").is_empty());
+    }
+
+    #[test]
+    fn letter_maps_merge_unless_they_disagree() {
+        let hints = parse_letter_map("E=X,T=Q").unwrap();
+        let crib = substitution_mappings("qwxyyz", "hello")[0];
+        let merged = merge_mappings(&crib, &hints).unwrap();
+        assert_eq!(merged[usize::from(b'E' - b'A')], Some('X'));
+        assert_eq!(merged[usize::from(b'Y' - b'A')], Some('L'));
+        // Ciphertext W is already plaintext H
+        assert!(merge_mappings(&crib, &parse_letter_map("W=A").unwrap()).is_none());
+        assert!(parse_letter_map("E=XY").is_err());
+        assert!(parse_letter_map("A=X,B=X").is_err());
+    }
+
+    #[test]
+    fn zero_xor_keys_are_left_out() {
+        assert!(xor_keys(b"flag", b"flag", 4).is_empty());
+    }
+}
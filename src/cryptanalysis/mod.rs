@@ -1,15 +1,63 @@
 //! Cryptanalysis module for advanced cipher breaking
 //! 
 //! This module provides tools for:
-//! - Text scoring and fitness functions using the `common-words-all` crate
+//! - Text scoring and fitness functions using the `common-words-all` crate,
+//!   including quadgram scoring
 //! - Dictionary/wordlist management for key attacks
 //! - Frequency analysis utilities
 //! - Hill climbing and optimization algorithms
 //! - Index of Coincidence calculations
+//! 
+//! It is part of Ares' public API, so other tools can use the same
+//! statistics as Ares' own decoders. Functions without a language use
+//! English, and have a `_in` variant taking a [`Language`]:
+//! 
+//! ```
+//! use ares::cryptanalysis::{estimate_key_length, fitness_score, index_of_coincidence};
+//! 
+//! let english = "the quick brown fox jumps over the lazy dog";
+//! let shifted = "gur dhvpx oebja sbk whzcf bire gur ynml qbt";
+//! assert!(fitness_score(english) > fitness_score(shifted));
+//! // Shifting every letter by the same amount keeps the IC
+//! assert_eq!(index_of_coincidence(english), index_of_coincidence(shifted));
+//! 
+//! // Vigenère with a 3 letter key: every third letter is shifted the same way
+//! let ciphertext = "LXFOPVEFRNHRLXFOPVEFRNHRLXFOPVEFRNHR";
+//! assert_eq!(estimate_key_length(ciphertext, 6)[0].0 % 3, 0);
+//! ```
+
+/// Ciphertexts sharing a key, attacked together
+pub mod correlation;
+/// Guessing the cipher or encoding of a text
+pub mod identify;
+/// Keys worked out from text known to be in the plaintext
+pub mod kpa;
+/// Column orders of columnar transpositions
+pub mod transposition;
 
 use common_words_all::generated::cwa_1grams_english::cwa_1grams_english;
+use common_words_all::generated::{
+    cwa_1grams_french, cwa_1grams_german, cwa_1grams_italian, cwa_1grams_spanish,
+    cwa_2grams_french, cwa_2grams_german, cwa_2grams_italian, cwa_2grams_spanish,
+};
+use common_words_all::Record;
+use flate2::read::MultiGzDecoder;
+use serde::{Deserialize, Serialize};
+use lru::LruCache;
 use once_cell::sync::Lazy;
-use std::collections::{HashMap, HashSet};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use crate::deadline::{self, Deadline};
+use crate::storage::dictionary::Dictionary;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Read;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 /// Load common English words from the third-party crate (common-words-all)
 /// These are the 10,000 most common English words sorted by frequency
@@ -17,9 +65,9 @@ pub static COMMON_ENGLISH_WORDS: Lazy<Vec<&'static str>> = Lazy::new(|| {
     cwa_1grams_english.iter().map(|r| r.ngram).collect()
 });
 
-/// HashSet for fast word lookup
-pub static COMMON_ENGLISH_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
-    COMMON_ENGLISH_WORDS.iter().cloned().collect()
+/// Dictionary for fast word and prefix lookup
+pub static COMMON_ENGLISH_SET: Lazy<Dictionary> = Lazy::new(|| {
+    Dictionary::new(COMMON_ENGLISH_WORDS.iter())
 });
 
 /// Attack wordlist - words suitable for cipher key attacks (4-15 chars)
@@ -59,6 +107,24 @@ pub static ATTACK_WORDLIST: Lazy<Vec<String>> = Lazy::new(|| {
     words
 });
 
+/// The keywords to attack with: the user's own `words`, like those added with
+/// `ares dict add`, in lowercase and uppercase, then [`ATTACK_WORDLIST`].
+/// Words with anything but letters can't be keys and are left out.
+pub fn attack_keywords(words: &[String]) -> Cow<'static, [String]> {
+    if words.is_empty() {
+        return Cow::Borrowed(ATTACK_WORDLIST.as_slice());
+    }
+    let mut seen = HashSet::new();
+    words
+        .iter()
+        .filter(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_alphabetic()))
+        .flat_map(|word| [word.to_lowercase(), word.to_uppercase()])
+        .chain(ATTACK_WORDLIST.iter().cloned())
+        .filter(|word| seen.insert(word.clone()))
+        .collect::<Vec<_>>()
+        .into()
+}
+
 /// English letter frequencies (A-Z) as percentages
 pub const ENGLISH_LETTER_FREQ: [f64; 26] = [
     8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015,  // A-G
@@ -105,19 +171,19 @@ pub static ENGLISH_BIGRAM_SCORES: Lazy<HashMap<(char, char), f64>> = Lazy::new(|
 });
 
 /// Calculate the Index of Coincidence for a text
-/// IC ≈ 0.0667 for English, ≈ 0.0385 for random text
+/// IC ≈ 0.0667 for English, ≈ 0.0385 for random text, see
+/// [`Language::expected_ic`] for other languages. Accented letters count as
+/// the letter they are based on.
 pub fn index_of_coincidence(text: &str) -> f64 {
-    let text: String = text.to_uppercase().chars()
-        .filter(|c| c.is_ascii_alphabetic())
-        .collect();
+    let text = uppercase_letters(text);
     
     if text.len() < 2 {
         return 0.0;
     }
     
     let mut freq = [0u64; 26];
-    for c in text.chars() {
-        let idx = (c as u8 - b'A') as usize;
+    for c in &text {
+        let idx = (c - b'A') as usize;
         freq[idx] += 1;
     }
     
@@ -132,9 +198,12 @@ pub fn index_of_coincidence(text: &str) -> f64 {
 /// Calculate chi-squared statistic comparing text frequencies to English
 /// Lower values indicate closer match to English
 pub fn chi_squared_score(text: &str) -> f64 {
-    let text: String = text.to_uppercase().chars()
-        .filter(|c| c.is_ascii_alphabetic())
-        .collect();
+    chi_squared_score_in(text, Language::English)
+}
+
+/// Like [`chi_squared_score`], comparing against the letter frequencies of `language`
+pub fn chi_squared_score_in(text: &str, language: Language) -> f64 {
+    let text = uppercase_letters(text);
     
     if text.is_empty() {
         return f64::MAX;
@@ -143,15 +212,16 @@ pub fn chi_squared_score(text: &str) -> f64 {
     let n = text.len() as f64;
     let mut freq = [0u64; 26];
     
-    for c in text.chars() {
-        let idx = (c as u8 - b'A') as usize;
+    for c in &text {
+        let idx = (c - b'A') as usize;
         freq[idx] += 1;
     }
     
+    let letter_freqs = language.letter_freqs();
     let mut chi_sq = 0.0;
     for i in 0..26 {
         let observed = freq[i] as f64;
-        let expected = n * (ENGLISH_LETTER_FREQ[i] / 100.0);
+        let expected = n * (letter_freqs[i] / 100.0);
         if expected > 0.0 {
             chi_sq += (observed - expected).powi(2) / expected;
         }
@@ -188,9 +258,574 @@ pub fn bigram_score(text: &str) -> f64 {
     }
 }
 
+/// Letter n-gram log probabilities, loaded from a file of n-grams and counts
+/// such as `storage/ngrams/english_bigrams.txt` or a downloaded model
+#[derive(Debug, Clone)]
+pub struct NgramModel {
+    /// How many letters each n-gram has
+    pub n: usize,
+    /// log10 of how often each n-gram occurs
+    log_probs: HashMap<String, f64>,
+    /// The log probability given to n-grams the model hasn't seen
+    floor: f64,
+}
+
+impl NgramModel {
+    /// Parses lines of `NGRAM COUNT`, skipping blank lines
+    ///
+    /// # Errors
+    /// Returns an error if a line can't be parsed, the n-grams have different
+    /// lengths, or there are none.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut counts = HashMap::new();
+        let mut n = 0;
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let mut parts = line.split_ascii_whitespace();
+            let (Some(ngram), Some(count)) = (parts.next(), parts.next()) else {
+                return Err(format!("Expected an n-gram and a count, got '{}'", line));
+            };
+            let count: f64 = count
+                .parse()
+                .map_err(|_| format!("'{}' isn't a count", count))?;
+            let ngram = ngram.to_ascii_uppercase();
+            if n == 0 {
+                n = ngram.len();
+            } else if ngram.len() != n {
+                return Err(format!("'{}' isn't {} letters long", ngram, n));
+            }
+            counts.insert(ngram, count);
+        }
+        Self::from_counts(n, counts)
+    }
+
+    /// Turns how often each uppercase n-gram was seen into a model
+    ///
+    /// # Errors
+    /// Returns an error if there are no n-grams.
+    pub fn from_counts(n: usize, counts: HashMap<String, f64>) -> Result<Self, String> {
+        let total: f64 = counts.values().sum();
+        if total <= 0.0 {
+            return Err("The model has no n-grams".to_string());
+        }
+        let log_probs = counts
+            .into_iter()
+            .map(|(ngram, count)| (ngram, (count / total).log10()))
+            .collect();
+        Ok(NgramModel {
+            n,
+            log_probs,
+            // Unseen n-grams count as a hundredth of a single sighting
+            floor: (0.01 / total).log10(),
+        })
+    }
+
+    /// The average log probability of the letters of `text`, per n-gram.
+    /// Higher is more like the model's language.
+    pub fn score(&self, text: &str) -> f64 {
+        let letters: Vec<u8> = text
+            .bytes()
+            .filter(u8::is_ascii_alphabetic)
+            .map(|b| b.to_ascii_uppercase())
+            .collect();
+        if letters.len() < self.n {
+            return f64::MIN;
+        }
+        let windows = letters.windows(self.n);
+        let count = windows.len();
+        let total: f64 = windows
+            .map(|window| {
+                std::str::from_utf8(window)
+                    .ok()
+                    .and_then(|ngram| self.log_probs.get(ngram))
+                    .copied()
+                    .unwrap_or(self.floor)
+            })
+            .sum();
+        total / count as f64
+    }
+}
+
+/// Loads the model called `name`, such as `english-quadgrams`, from the ones
+/// downloaded with `ares models download`, falling back to the tables built
+/// into Ares. Returns `None` if neither has it.
+pub fn ngram_model(name: &str) -> Option<NgramModel> {
+    let downloaded = crate::storage::models::models_dir()
+        .ok_or_else(|| "Could not find home directory".to_string())
+        .and_then(|dir| crate::storage::models::load(&dir, name))
+        .and_then(|text| NgramModel::parse(&text));
+    match downloaded {
+        Ok(model) => Some(model),
+        Err(e) => {
+            log::debug!("Using the built-in tables for {}: {}", name, e);
+            embedded_ngram_model(name)
+        }
+    }
+}
+
+/// The n-gram tables built into Ares
+fn embedded_ngram_model(name: &str) -> Option<NgramModel> {
+    match name {
+        "english-bigrams" => {
+            NgramModel::parse(include_str!("../storage/ngrams/english_bigrams.txt")).ok()
+        }
+        _ => None,
+    }
+}
+
+/// How much less likely an n-gram which was never seen is than its
+/// shorter suffix suggests, for stupid backoff
+const BACKOFF_PENALTY: f64 = 0.1;
+
+/// Counts the 1- to 4-letter n-grams of a `common-words-all` word list, each
+/// in a flat array indexed like [`quadgram_index`], weighting each word by how
+/// often it is used. Word pairs add the n-grams which span a space, such as
+/// `ofth` in "of the".
+fn letter_counts(words: &[Record], pairs: &[Record]) -> [Vec<f64>; 4] {
+    let mut counts = [1, 2, 3, 4].map(|n| vec![0.0; 26usize.pow(n)]);
+    let mut add = |letters: &[u8], freq: f64, gap: Option<usize>| {
+        for (n, counts) in counts.iter_mut().enumerate() {
+            for (start, window) in letters.windows(n + 1).enumerate() {
+                // Only the windows crossing the space, the rest came from single words
+                if gap.is_some_and(|gap| start >= gap || start + n < gap) {
+                    continue;
+                }
+                if let Some(index) = quadgram_index(window) {
+                    counts[index] += freq;
+                }
+            }
+        }
+    };
+    for record in words {
+        add(&uppercase_letters(record.ngram), record.freq, None);
+    }
+    for record in pairs {
+        let Some((first, second)) = record.ngram.split_once(' ') else {
+            continue;
+        };
+        let first = uppercase_letters(first);
+        let letters = [first.as_slice(), &uppercase_letters(second)].concat();
+        add(&letters, record.freq, Some(first.len()));
+    }
+    counts
+}
+
+/// The letters of `text` as uppercase ASCII, with accents removed so
+/// "é" counts as an E
+fn uppercase_letters(text: &str) -> Vec<u8> {
+    text.chars().filter_map(fold_letter).collect()
+}
+
+/// The uppercase ASCII letter `c` is, or is an accented form of
+fn fold_letter(c: char) -> Option<u8> {
+    if c.is_ascii_alphabetic() {
+        return Some(c.to_ascii_uppercase() as u8);
+    }
+    let base = match c.to_lowercase().next()? {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => b'A',
+        'ç' => b'C',
+        'è' | 'é' | 'ê' | 'ë' => b'E',
+        'ì' | 'í' | 'î' | 'ï' => b'I',
+        'ñ' => b'N',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => b'O',
+        'ß' => b'S',
+        'ù' | 'ú' | 'û' | 'ü' => b'U',
+        'ý' | 'ÿ' => b'Y',
+        _ => return None,
+    };
+    Some(base)
+}
+
+/// Quadgram log probabilities in a flat array indexed by the four letters,
+/// for scoring in tight loops like hill climbing
+#[derive(Debug, Clone)]
+pub struct QuadgramTable {
+    /// log10 probability of each quadgram, at `((a * 26 + b) * 26 + c) * 26 + d`
+    scores: Box<[f32]>,
+}
+
+impl QuadgramTable {
+    /// Lays out a quadgram model as a flat array
+    ///
+    /// # Errors
+    /// Returns an error if the model isn't of quadgrams.
+    pub fn from_model(model: &NgramModel) -> Result<Self, String> {
+        if model.n != 4 {
+            return Err(format!("Expected a quadgram model, got {}-grams", model.n));
+        }
+        let mut scores = vec![model.floor as f32; 26 * 26 * 26 * 26].into_boxed_slice();
+        for (quadgram, log_prob) in &model.log_probs {
+            if let Some(index) = quadgram_index(quadgram.as_bytes()) {
+                scores[index] = *log_prob as f32;
+            }
+        }
+        Ok(QuadgramTable { scores })
+    }
+
+    /// Decodes the English quadgrams built into Ares, counted from running
+    /// text as described in `storage/ngrams/README.md`
+    ///
+    /// # Panics
+    /// Panics if the built-in table isn't a gzipped byte per quadgram, which
+    /// the tests check.
+    pub fn english() -> Self {
+        let mut tenths = Vec::with_capacity(26 * 26 * 26 * 26);
+        MultiGzDecoder::new(ENGLISH_QUADGRAM_TENTHS)
+            .read_to_end(&mut tenths)
+            .expect("the built-in English quadgrams are gzipped");
+        assert_eq!(tenths.len(), 26 * 26 * 26 * 26, "one byte per quadgram");
+        // Each byte is tenths of -log10 P, so 42 is a log probability of -4.2
+        let scores = tenths.iter().map(|&tenths| -f32::from(tenths) / 10.0).collect();
+        QuadgramTable { scores }
+    }
+
+    /// Estimates the table from a `common-words-all` word list and its word
+    /// pairs, for languages without a table built in. The English one only
+    /// has about 13,000 distinct quadgrams, so the rest of the 456,976 are
+    /// estimated from their trigrams, bigrams and letters with stupid
+    /// backoff. Plausible but unseen quadgrams then score well above
+    /// gibberish.
+    pub fn from_word_list(words: &[Record], pairs: &[Record]) -> Self {
+        let counts = letter_counts(words, pairs);
+        let letters: f64 = counts[0].iter().sum();
+        // How likely `index`'s last letter is after the rest of it
+        let next_letter = |n: usize, index: usize| -> f64 {
+            let mut penalty = 1.0;
+            for context in (1..n).rev() {
+                let suffix = index % 26usize.pow(context as u32 + 1);
+                let seen = counts[context][suffix];
+                let before = counts[context - 1][suffix / 26];
+                if seen > 0.0 && before > 0.0 {
+                    return penalty * seen / before;
+                }
+                penalty *= BACKOFF_PENALTY;
+            }
+            // Every letter gets a little probability, even if never seen
+            penalty * (counts[0][index % 26] + letters * 1e-4) / (letters * (1.0 + 26e-4))
+        };
+        let scores = (0..26usize.pow(4))
+            .map(|index| {
+                let probability: f64 = (1..=4)
+                    .map(|n| next_letter(n, index / 26usize.pow(4 - n as u32)))
+                    .product();
+                probability.log10() as f32
+            })
+            .collect();
+        QuadgramTable { scores }
+    }
+
+    /// The average log probability of the quadgrams in the letters of `text`,
+    /// ignoring everything else. Returns `f64::MIN` for fewer than four letters.
+    pub fn score(&self, text: &str) -> f64 {
+        self.score_letters(&uppercase_letters(text))
+    }
+
+    /// Like [`QuadgramTable::score`], for text which is already only
+    /// uppercase ASCII letters, which saves hill climbers from building a
+    /// string for every key they try
+    pub fn score_letters(&self, letters: &[u8]) -> f64 {
+        if letters.len() < 4 {
+            return f64::MIN;
+        }
+        let windows = letters.windows(4);
+        let count = windows.len();
+        let total: f64 = windows
+            .filter_map(quadgram_index)
+            .map(|index| f64::from(self.scores[index]))
+            .sum();
+        total / count as f64
+    }
+
+    /// The log10 probability of each letter pair, at `[first][second]`,
+    /// summed from the quadgrams starting with it. Worked out from the whole
+    /// table, so best kept rather than called for every text.
+    pub fn bigram_scores(&self) -> [[f64; 26]; 26] {
+        let mut probabilities = [[0.0; 26]; 26];
+        for (index, score) in self.scores.iter().enumerate() {
+            let pair = index / (26 * 26);
+            probabilities[pair / 26][pair % 26] += 10f64.powf(f64::from(*score));
+        }
+        let total: f64 = probabilities.iter().flatten().sum();
+        probabilities.map(|row| row.map(|probability| (probability / total).log10()))
+    }
+}
+
+/// Where the uppercase quadgram goes in [`QuadgramTable::scores`]
+fn quadgram_index(quadgram: &[u8]) -> Option<usize> {
+    quadgram.iter().try_fold(0, |index, &letter| {
+        letter
+            .is_ascii_uppercase()
+            .then(|| index * 26 + usize::from(letter - b'A'))
+    })
+}
+
+/// The built-in English quadgram table, gzipped
+const ENGLISH_QUADGRAM_TENTHS: &[u8] = include_bytes!("../storage/ngrams/english_quadgrams.bin.gz");
+
+/// English quadgrams, from `ares models download english-quadgrams` if it has
+/// been downloaded, otherwise the built-in table
+pub static ENGLISH_QUADGRAMS: Lazy<QuadgramTable> = Lazy::new(|| {
+    ngram_model("english-quadgrams")
+        .and_then(|model| QuadgramTable::from_model(&model).ok())
+        .unwrap_or_else(QuadgramTable::english)
+});
+
+/// Score text using English quadgram statistics, the average log10
+/// probability per quadgram. Around -4 for English and below -7 for gibberish.
+pub fn quadgram_score(text: &str) -> f64 {
+    ENGLISH_QUADGRAMS.score(text)
+}
+
+/// Like [`quadgram_score`], with the quadgrams of `language`
+pub fn quadgram_score_in(text: &str, language: Language) -> f64 {
+    language.quadgrams().score(text)
+}
+
+/// `languages`, or English if there are none
+pub fn languages_or_english(languages: &[Language]) -> &[Language] {
+    if languages.is_empty() {
+        &[Language::English]
+    } else {
+        languages
+    }
+}
+
+/// The best [`fitness_score_in`] of any of `languages`, or English if there are none
+pub fn best_fitness_score(text: &str, languages: &[Language]) -> f64 {
+    languages_or_english(languages)
+        .iter()
+        .map(|language| fitness_score_in(text, *language))
+        .fold(f64::MIN, f64::max)
+}
+
+/// How much looking like an encoding adds to [`candidate_score`], for each
+/// point of the identifier's confidence. Enough to lift encoded text above
+/// most wrong keys, whose letters read no better than its do.
+const ENCODING_BONUS: f64 = 300.0;
+
+/// How worth following `text` is, as one of many candidates from a cipher
+/// like XOR: its [`best_fitness_score`], raised if it looks like an encoding
+/// such as Base64 which another decoder can undo. Higher is better.
+pub fn candidate_score(text: &str, languages: &[Language]) -> f64 {
+    let encoding = identify::identify_in(text, languages)
+        .iter()
+        .filter(|guess| guess.family.is_encoding())
+        .map(|guess| guess.score)
+        .fold(0.0, f64::max);
+    best_fitness_score(text, languages) + ENCODING_BONUS * encoding
+}
+
+/// Whether `text` is likely plaintext in any of `languages`, or English if
+/// there are none
+pub fn is_likely_plaintext(text: &str, languages: &[Language]) -> bool {
+    languages_or_english(languages)
+        .iter()
+        .any(|language| is_likely_language(text, *language))
+}
+
+/// Languages plaintext can be scored in, set with `Config.languages`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// The default
+    #[default]
+    English,
+    /// French
+    French,
+    /// German
+    German,
+    /// Italian
+    Italian,
+    /// Spanish
+    Spanish,
+}
+
+impl Language {
+    /// Every language plaintext can be scored in
+    pub const ALL: [Language; 5] = [
+        Language::English,
+        Language::French,
+        Language::German,
+        Language::Italian,
+        Language::Spanish,
+    ];
+
+    /// The lowercase name, as used in `Config.languages` and model names like
+    /// `french-quadgrams`
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::French => "french",
+            Language::German => "german",
+            Language::Italian => "italian",
+            Language::Spanish => "spanish",
+        }
+    }
+
+    /// Letter frequencies (A-Z) as percentages, with accented letters counted
+    /// as the letter they are based on
+    pub fn letter_freqs(self) -> &'static [f64; 26] {
+        match self.word_list_tables() {
+            Some(tables) => &tables.letter_freqs,
+            None => &ENGLISH_LETTER_FREQ,
+        }
+    }
+
+    /// The index of coincidence of a long plaintext in the language
+    pub fn expected_ic(self) -> f64 {
+        match self {
+            Language::English => 0.0667,
+            _ => self
+                .letter_freqs()
+                .iter()
+                .map(|percent| (percent / 100.0).powi(2))
+                .sum(),
+        }
+    }
+
+    /// The quadgram table of the language, downloaded with
+    /// `ares models download <language>-quadgrams` or estimated from its word list
+    pub fn quadgrams(self) -> &'static QuadgramTable {
+        match self.word_list_tables() {
+            Some(tables) => &tables.quadgrams,
+            None => &ENGLISH_QUADGRAMS,
+        }
+    }
+
+    /// The 10,000 most common words of the language, lowercase
+    pub fn common_words(self) -> &'static Dictionary {
+        match self.word_list_tables() {
+            Some(tables) => &tables.common_words,
+            None => &COMMON_ENGLISH_SET,
+        }
+    }
+
+    /// The same words most common first, for weighing one split of text
+    /// into words against another
+    pub fn words_by_frequency(self) -> &'static [&'static str] {
+        match self.word_list_tables() {
+            Some(tables) => &tables.words_by_frequency,
+            None => &COMMON_ENGLISH_WORDS,
+        }
+    }
+
+    /// The tables built from the language's `common-words-all` word list,
+    /// or `None` for English, which has its own statics
+    fn word_list_tables(self) -> Option<&'static WordListTables> {
+        match self {
+            Language::English => None,
+            Language::French => Some(&FRENCH_TABLES),
+            Language::German => Some(&GERMAN_TABLES),
+            Language::Italian => Some(&ITALIAN_TABLES),
+            Language::Spanish => Some(&SPANISH_TABLES),
+        }
+    }
+}
+
+/// The tables of a language other than English
+struct WordListTables {
+    /// Letter frequencies (A-Z) as percentages
+    letter_freqs: [f64; 26],
+    /// Quadgram log probabilities
+    quadgrams: QuadgramTable,
+    /// The most common words, lowercase
+    common_words: Dictionary,
+    /// The same words most common first
+    words_by_frequency: Vec<&'static str>,
+}
+
+impl WordListTables {
+    /// Counts the letters, quadgrams and words of a language's word list and
+    /// word pairs, preferring a downloaded quadgram model
+    fn new(language: Language, words: &'static [Record<'static>], pairs: &[Record]) -> Self {
+        let counts = letter_counts(words, &[]);
+        let letters: f64 = counts[0].iter().sum();
+        let mut letter_freqs = [0.0; 26];
+        for (freq, count) in letter_freqs.iter_mut().zip(&counts[0]) {
+            *freq = count / letters * 100.0;
+        }
+        let quadgrams = ngram_model(&format!("{}-quadgrams", language.name()))
+            .and_then(|model| QuadgramTable::from_model(&model).ok())
+            .unwrap_or_else(|| QuadgramTable::from_word_list(words, pairs));
+        let common_words = Dictionary::new(words.iter().map(|record| record.ngram));
+        let words_by_frequency = words.iter().map(|record| record.ngram).collect();
+        WordListTables {
+            letter_freqs,
+            quadgrams,
+            common_words,
+            words_by_frequency,
+        }
+    }
+}
+
+/// French letter, quadgram and word tables
+static FRENCH_TABLES: Lazy<WordListTables> =
+    Lazy::new(|| WordListTables::new(Language::French, &cwa_1grams_french, &cwa_2grams_french));
+
+/// German letter, quadgram and word tables
+static GERMAN_TABLES: Lazy<WordListTables> =
+    Lazy::new(|| WordListTables::new(Language::German, &cwa_1grams_german, &cwa_2grams_german));
+
+/// Italian letter, quadgram and word tables
+static ITALIAN_TABLES: Lazy<WordListTables> = Lazy::new(|| {
+    WordListTables::new(Language::Italian, &cwa_1grams_italian, &cwa_2grams_italian)
+});
+
+/// Spanish letter, quadgram and word tables
+static SPANISH_TABLES: Lazy<WordListTables> = Lazy::new(|| {
+    WordListTables::new(Language::Spanish, &cwa_1grams_spanish, &cwa_2grams_spanish)
+});
+
+/// Tokens at least this long which aren't words are split into words
+const SEGMENT_MIN_TOKEN_LEN: usize = 8;
+
+/// The shortest word counted when splitting tokens, as short words match
+/// by chance in any text
+const SEGMENT_MIN_WORD_LEN: usize = 5;
+
+/// How many texts' scores each of the score caches remembers
+const SCORE_CACHE_SIZE: usize = 4096;
+
+/// Scores remembered by text hash and language, as hill climbers and
+/// dictionary attacks in different decoders score the same candidates again
+type ScoreCache<T> = Lazy<Mutex<LruCache<(u64, Language), T>>>;
+
+/// Remembered [`fitness_score_in`] results
+static FITNESS_CACHE: ScoreCache<f64> =
+    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(SCORE_CACHE_SIZE).unwrap())));
+
+/// Remembered [`is_likely_language`] results
+static LIKELY_CACHE: ScoreCache<bool> =
+    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(SCORE_CACHE_SIZE).unwrap())));
+
+/// Looks `text` up in `cache`, scoring it with `compute` if it isn't there.
+/// The lock isn't held while scoring, so threads only wait for each other
+/// to read and write the cache.
+fn memoized<T: Copy>(
+    cache: &ScoreCache<T>,
+    text: &str,
+    language: Language,
+    compute: fn(&str, Language) -> T,
+) -> T {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    let key = (hasher.finish(), language);
+    if let Some(score) = cache.lock().unwrap().get(&key) {
+        return *score;
+    }
+    let score = compute(text, language);
+    cache.lock().unwrap().put(key, score);
+    score
+}
+
 /// Score text based on English word detection
 /// Returns percentage of text that consists of recognized words
 pub fn word_score(text: &str) -> f64 {
+    word_score_in(text, Language::English)
+}
+
+/// Like [`word_score`], recognising the common words of `language`
+pub fn word_score_in(text: &str, language: Language) -> f64 {
+    let common_words = language.common_words();
     let text_lower = text.to_lowercase();
     let words: Vec<&str> = text_lower
         .split(|c: char| !c.is_alphabetic())
@@ -201,9 +836,16 @@ pub fn word_score(text: &str) -> f64 {
         return 0.0;
     }
     
+    // Words run together, like Playfair output, count as far as they can
+    // be split into common words
     let recognized: usize = words.iter()
-        .filter(|w| COMMON_ENGLISH_SET.contains(*w))
-        .map(|w| w.len())
+        .map(|w| if common_words.contains(w) {
+            w.len()
+        } else if w.len() >= SEGMENT_MIN_TOKEN_LEN {
+            common_words.segment(w, SEGMENT_MIN_WORD_LEN).iter().map(|word| word.len()).sum()
+        } else {
+            0
+        })
         .sum();
     
     let total: usize = words.iter().map(|w| w.len()).sum();
@@ -218,17 +860,27 @@ pub fn word_score(text: &str) -> f64 {
 /// Combined fitness score for plaintext detection
 /// Higher scores indicate more likely plaintext
 pub fn fitness_score(text: &str) -> f64 {
+    fitness_score_in(text, Language::English)
+}
+
+/// Like [`fitness_score`], for plaintext in `language`
+pub fn fitness_score_in(text: &str, language: Language) -> f64 {
+    memoized(&FITNESS_CACHE, text, language, compute_fitness_score)
+}
+
+/// [`fitness_score_in`] without the cache
+fn compute_fitness_score(text: &str, language: Language) -> f64 {
     if text.is_empty() {
         return f64::MIN;
     }
     
     let ic = index_of_coincidence(text);
-    let chi_sq = chi_squared_score(text);
-    let word_pct = word_score(text);
-    let bigram = bigram_score(text);
+    let chi_sq = chi_squared_score_in(text, language);
+    let word_pct = word_score_in(text, language);
+    let quadgram = quadgram_score_in(text, language);
     
-    // IC close to 0.0667 is good (English)
-    let ic_score = -((ic - 0.0667).abs() * 500.0);
+    // IC close to the language's (0.0667 for English) is good
+    let ic_score = -((ic - language.expected_ic()).abs() * 500.0);
     
     // Lower chi-squared is better (max penalty -100)
     let chi_score = -(chi_sq.min(100.0));
@@ -236,27 +888,43 @@ pub fn fitness_score(text: &str) -> f64 {
     // Higher word percentage is better
     let word_bonus = word_pct * 5.0;
     
-    // Bigram score (already normalized)
-    let bigram_bonus = bigram * 20.0;
+    // Quadgram score (already normalized), texts with under four letters
+    // fall back to bigrams, which only English has
+    let ngram_bonus = match (quadgram == f64::MIN, language) {
+        (false, _) => quadgram * 20.0,
+        (true, Language::English) => bigram_score(text) * 20.0,
+        // As if every bigram was unseen
+        (true, _) => -10.0 * 20.0,
+    };
     
     // Combine all scores
-    ic_score + chi_score + word_bonus + bigram_bonus
+    ic_score + chi_score + word_bonus + ngram_bonus
 }
 
 /// Check if text is likely English plaintext
 /// Handles both spaced text and concatenated text (like from Playfair cipher)
 pub fn is_likely_english(text: &str) -> bool {
+    is_likely_language(text, Language::English)
+}
+
+/// Like [`is_likely_english`], for plaintext in `language`
+pub fn is_likely_language(text: &str, language: Language) -> bool {
+    memoized(&LIKELY_CACHE, text, language, compute_is_likely_language)
+}
+
+/// [`is_likely_language`] without the cache
+fn compute_is_likely_language(text: &str, language: Language) -> bool {
     if text.len() < 10 {
         return false;
     }
     
     let ic = index_of_coincidence(text);
-    let chi_sq = chi_squared_score(text);
-    let word_pct = word_score(text);
-    let bigram = bigram_score(text);
+    let chi_sq = chi_squared_score_in(text, language);
+    let word_pct = word_score_in(text, language);
     
-    // IC should be close to English (0.0667)
-    let ic_ok = ic > 0.045 && ic < 0.085;
+    // IC should be close to the language's (0.045 to 0.085 for English)
+    let expected_ic = language.expected_ic();
+    let ic_ok = ic > expected_ic - 0.0217 && ic < expected_ic + 0.0183;
     
     // Chi-squared should be relatively low
     let chi_ok = chi_sq < 80.0;
@@ -265,8 +933,12 @@ pub fn is_likely_english(text: &str) -> bool {
     let words_ok = word_pct > 10.0;
     
     // Bigram score should be reasonable (for concatenated text)
-    // Good English bigram score is around -4 to -6
-    let bigram_ok = bigram > -7.0;
+    // Good English bigram score is around -4 to -6. Other languages only
+    // have quadgrams, which score around -4 to -5.5 for plaintext.
+    let bigram_ok = match language {
+        Language::English => bigram_score(text) > -7.0,
+        _ => quadgram_score_in(text, language) > -6.0,
+    };
     
     // For text with spaces, prioritize word detection
     let has_spaces = text.contains(' ');
@@ -280,6 +952,89 @@ pub fn is_likely_english(text: &str) -> bool {
     }
 }
 
+/// A keyword a dictionary attack tried and the text it decrypted to
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordCandidate {
+    /// The keyword, as it appears in the wordlist
+    pub keyword: String,
+    /// The text the keyword decrypted to
+    pub plaintext: String,
+    /// The plaintext's [`best_fitness_score`]
+    pub score: f64,
+}
+
+/// What [`dictionary_attack`] found
+#[derive(Debug)]
+pub struct DictionaryAttack<T> {
+    /// The keyword which was confirmed, with what confirmed it
+    pub hit: Option<(KeywordCandidate, T)>,
+    /// The best scoring keyword tried, for falling back on cryptanalysis
+    /// when nothing was confirmed
+    pub best: Option<KeywordCandidate>,
+}
+
+impl<T> Default for DictionaryAttack<T> {
+    fn default() -> Self {
+        DictionaryAttack {
+            hit: None,
+            best: None,
+        }
+    }
+}
+
+impl<T> DictionaryAttack<T> {
+    /// Combines the results of two parts of the wordlist, preferring the
+    /// earlier part when both have a hit or their best scores tie
+    fn merge(self, other: Self) -> Self {
+        let best = match (self.best, other.best) {
+            (Some(a), Some(b)) => Some(if b.score > a.score { b } else { a }),
+            (a, b) => a.or(b),
+        };
+        DictionaryAttack {
+            hit: self.hit.or(other.hit),
+            best,
+        }
+    }
+}
+
+/// Tries every keyword in parallel. `decrypt` returns None for keywords
+/// which can't be used, and `confirm` returns Some once a plaintext is real,
+/// usually because a checker identified it. The first confirmation, or
+/// `deadline` passing, stops the remaining keywords from being tried.
+pub fn dictionary_attack<T: Send>(
+    keywords: &[String],
+    languages: &[Language],
+    deadline: Option<&Deadline>,
+    decrypt: impl Fn(&str) -> Option<String> + Sync,
+    confirm: impl Fn(&str) -> Option<T> + Sync,
+) -> DictionaryAttack<T> {
+    let found = AtomicBool::new(false);
+    keywords
+        .par_iter()
+        .fold(DictionaryAttack::default, |mut attack, keyword| {
+            if found.load(Ordering::Relaxed) || deadline::expired(deadline) {
+                return attack;
+            }
+            let Some(plaintext) = decrypt(keyword) else {
+                return attack;
+            };
+            let candidate = KeywordCandidate {
+                keyword: keyword.clone(),
+                score: best_fitness_score(&plaintext, languages),
+                plaintext,
+            };
+            if let Some(confirmed) = confirm(&candidate.plaintext) {
+                found.store(true, Ordering::Relaxed);
+                attack.hit.get_or_insert((candidate.clone(), confirmed));
+            }
+            if attack.best.as_ref().is_none_or(|best| candidate.score > best.score) {
+                attack.best = Some(candidate);
+            }
+            attack
+        })
+        .reduce(DictionaryAttack::default, DictionaryAttack::merge)
+}
+
 /// Estimate key length for polyalphabetic ciphers using IC
 pub fn estimate_key_length(ciphertext: &str, max_length: usize) -> Vec<(usize, f64)> {
     let text: String = ciphertext.to_uppercase().chars()
@@ -333,12 +1088,34 @@ pub fn get_frequency_distribution(text: &str) -> [f64; 26] {
     dist
 }
 
+/// How many steps of a first-improvement climb go between checks of the deadline
+const DEADLINE_CHECK_INTERVAL: usize = 64;
+
 /// Hill climbing optimizer for key search
+/// 
+/// Keys are permutations of an alphabet, and each step swaps two letters.
+/// By default a climb takes the first random swap which raises the score.
+/// With `best_neighbor` it tries every swap and takes the best one, even if
+/// it lowers the score, with a tabu list of recent swaps which may not be
+/// made again so it doesn't walk straight back up the same hill. That is
+/// slower per step, but finds keys for short texts which have many false
+/// peaks far more often.
+#[derive(Debug, Clone)]
 pub struct HillClimber {
     /// Maximum iterations per restart
     pub max_iterations: usize,
     /// Maximum number of restarts
     pub max_restarts: usize,
+    /// Steps in a row which may fail to improve the best score before a
+    /// climb gives up and restarts
+    pub max_plateau: usize,
+    /// How many of the most recent swaps may not be made again, 0 for none
+    pub tabu_size: usize,
+    /// Take the best of every possible swap each step instead of the first
+    /// random swap which improves the score
+    pub best_neighbor: bool,
+    /// When to stop climbing and return the best key so far
+    pub deadline: Option<Deadline>,
 }
 
 impl Default for HillClimber {
@@ -346,6 +1123,10 @@ impl Default for HillClimber {
         HillClimber {
             max_iterations: 10000,
             max_restarts: 10,
+            max_plateau: 500,
+            tabu_size: 0,
+            best_neighbor: false,
+            deadline: None,
         }
     }
 }
@@ -356,21 +1137,271 @@ impl HillClimber {
         HillClimber {
             max_iterations,
             max_restarts,
+            ..Default::default()
+        }
+    }
+
+    /// A climber for short texts: tabu search taking the best swap each step.
+    /// Each step tries every swap, so far fewer steps are needed.
+    pub fn tabu(max_iterations: usize, max_restarts: usize) -> Self {
+        HillClimber {
+            max_iterations,
+            max_restarts,
+            max_plateau: 60,
+            tabu_size: 60,
+            best_neighbor: true,
+            deadline: None,
         }
     }
+
+    /// Stops climbing once `deadline` passes, as decoders pass
+    /// `config.deadline` so a search's timeout stops them
+    pub fn with_deadline(mut self, deadline: Option<Deadline>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Whether the deadline has passed, checked every so many steps as
+    /// reading the clock costs more than a step
+    fn out_of_time(&self, step: usize) -> bool {
+        step.is_multiple_of(DEADLINE_CHECK_INTERVAL) && deadline::expired(self.deadline.as_ref())
+    }
+
+    /// Searches for the substitution key whose decryption of `ciphertext`
+    /// scores highest. The first climb starts from `start` if given, and the
+    /// others from random keys. Returns the key, as for [`apply_substitution`],
+    /// with its plaintext and score.
+    /// 
+    /// ```
+    /// use ares::cryptanalysis::{quadgram_score, seeded_rng, HillClimber};
+    /// 
+    /// let mut rng = seeded_rng(Some(1));
+    /// let climber = HillClimber::new(2000, 3);
+    /// let (key, _plaintext, score) =
+    ///     climber.optimize_substitution("GSV JFRXP YILDM ULC", None, quadgram_score, &mut rng);
+    /// assert_eq!(key.len(), 26);
+    /// assert!(score > quadgram_score("GSV JFRXP YILDM ULC"));
+    /// ```
+    pub fn optimize_substitution(
+        &self,
+        ciphertext: &str,
+        start: Option<Vec<char>>,
+        score: impl Fn(&str) -> f64,
+        rng: &mut StdRng,
+    ) -> (Vec<char>, String, f64) {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let (key, best_score) = self.optimize_key(
+            &alphabet,
+            start,
+            |key| score(&apply_substitution(ciphertext, key)),
+            rng,
+        );
+        let plaintext = apply_substitution(ciphertext, &key);
+        (key, plaintext, best_score)
+    }
+
+    /// Searches for the ordering of `alphabet` which `score` rates highest,
+    /// such as a Playfair key square read row by row. The first climb starts
+    /// from `start` if given, and the others from random orderings.
+    pub fn optimize_key(
+        &self,
+        alphabet: &[char],
+        start: Option<Vec<char>>,
+        score: impl Fn(&[char]) -> f64,
+        rng: &mut StdRng,
+    ) -> (Vec<char>, f64) {
+        let mut best_key = alphabet.to_vec();
+        let mut best_score = score(&best_key);
+        if alphabet.len() < 2 {
+            return (best_key, best_score);
+        }
+        
+        for restart in 0..self.max_restarts.max(1) {
+            if restart > 0 && deadline::expired(self.deadline.as_ref()) {
+                break;
+            }
+            let key = match (restart, &start) {
+                (0, Some(key)) => key.clone(),
+                _ => {
+                    let mut key = alphabet.to_vec();
+                    key.shuffle(rng);
+                    key
+                }
+            };
+            let (key, key_score) = if self.best_neighbor {
+                self.climb_best_neighbor(key, &score)
+            } else {
+                self.climb_first_improvement(key, &score, rng)
+            };
+            if key_score > best_score {
+                best_score = key_score;
+                best_key = key;
+            }
+        }
+        
+        (best_key, best_score)
+    }
+
+    /// One climb taking random swaps which raise the score
+    fn climb_first_improvement(
+        &self,
+        mut key: Vec<char>,
+        score: &impl Fn(&[char]) -> f64,
+        rng: &mut StdRng,
+    ) -> (Vec<char>, f64) {
+        let mut current_score = score(&key);
+        let mut tabu = TabuList::new(self.tabu_size);
+        let mut plateau_count = 0;
+        
+        for step in 0..self.max_iterations {
+            if self.out_of_time(step) {
+                break;
+            }
+            // Try swapping two random letters in the key
+            let i = rng.random_range(0..key.len());
+            let j = rng.random_range(0..key.len());
+            if i == j || tabu.contains(i, j) {
+                continue;
+            }
+            
+            key.swap(i, j);
+            let new_score = score(&key);
+            if new_score > current_score {
+                current_score = new_score;
+                tabu.push(i, j);
+                plateau_count = 0;
+            } else {
+                // Undo swap
+                key.swap(i, j);
+                plateau_count += 1;
+            }
+            
+            // Early exit on plateau
+            if plateau_count > self.max_plateau {
+                break;
+            }
+        }
+        
+        (key, current_score)
+    }
+
+    /// One climb taking the best swap each step, tabu search style
+    fn climb_best_neighbor(
+        &self,
+        mut key: Vec<char>,
+        score: &impl Fn(&[char]) -> f64,
+    ) -> (Vec<char>, f64) {
+        let mut best_score = score(&key);
+        let mut best_key = key.clone();
+        let mut tabu = TabuList::new(self.tabu_size);
+        let mut plateau_count = 0;
+        
+        for _ in 0..self.max_iterations {
+            // Each step tries every swap, so the clock is worth reading every step
+            if deadline::expired(self.deadline.as_ref()) {
+                break;
+            }
+            let mut best_move: Option<(usize, usize, f64)> = None;
+            for i in 0..key.len() {
+                for j in i + 1..key.len() {
+                    key.swap(i, j);
+                    let new_score = score(&key);
+                    key.swap(i, j);
+                    // Tabu swaps are allowed if they beat every key so far
+                    if tabu.contains(i, j) && new_score <= best_score {
+                        continue;
+                    }
+                    if best_move.is_none_or(|(_, _, move_score)| new_score > move_score) {
+                        best_move = Some((i, j, new_score));
+                    }
+                }
+            }
+            let Some((i, j, new_score)) = best_move else {
+                break;
+            };
+            
+            key.swap(i, j);
+            tabu.push(i, j);
+            if new_score > best_score {
+                best_score = new_score;
+                best_key.clone_from(&key);
+                plateau_count = 0;
+            } else {
+                plateau_count += 1;
+                if plateau_count > self.max_plateau {
+                    break;
+                }
+            }
+        }
+        
+        (best_key, best_score)
+    }
 }
 
-/// Simple LCG for pseudo-random numbers
-pub fn lcg_next(state: u64) -> u64 {
-    state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407)
+/// The most recent swaps a climb made, which it may not make again
+struct TabuList {
+    /// The swaps, oldest first, with the lower position first
+    swaps: VecDeque<(usize, usize)>,
+    /// How many swaps are remembered
+    size: usize,
 }
 
-/// Get current time as seed
-pub fn time_seed() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_nanos() as u64)
-        .unwrap_or(12345)
+impl TabuList {
+    /// An empty list remembering `size` swaps
+    fn new(size: usize) -> Self {
+        TabuList {
+            swaps: VecDeque::with_capacity(size),
+            size,
+        }
+    }
+
+    /// Whether swapping `i` and `j` is tabu
+    fn contains(&self, i: usize, j: usize) -> bool {
+        self.swaps.contains(&(i.min(j), i.max(j)))
+    }
+
+    /// Remembers a swap, forgetting the oldest one if the list is full
+    fn push(&mut self, i: usize, j: usize) {
+        if self.size == 0 {
+            return;
+        }
+        if self.swaps.len() == self.size {
+            self.swaps.pop_front();
+        }
+        self.swaps.push_back((i.min(j), i.max(j)));
+    }
+}
+
+/// Decrypts a simple substitution. `key[i]` is the plaintext letter for the
+/// i-th letter of the alphabet, and only uppercase letters are replaced.
+/// 
+/// ```
+/// use ares::cryptanalysis::apply_substitution;
+/// 
+/// let key: Vec<char> = "ZYXWVUTSRQPONMLKJIHGFEDCBA".chars().collect();
+/// assert_eq!(apply_substitution("SVOOL, dliow!", &key), "HELLO, dliow!");
+/// ```
+pub fn apply_substitution(ciphertext: &str, key: &[char]) -> String {
+    ciphertext.chars()
+        .map(|c| {
+            if c.is_ascii_uppercase() {
+                let idx = (c as u8 - b'A') as usize;
+                key[idx]
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Random number generator for randomised searches like hill climbing.
+/// The same seed always gives the same numbers, so runs with `Config.seed`
+/// set can be reproduced; without one it is seeded from the operating system.
+pub fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    }
 }
 
 #[cfg(test)]
@@ -449,6 +1480,186 @@ mod tests {
             "Should detect concatenated English text");
     }
 
+    #[test]
+    fn ngram_models_score_english_above_gibberish() {
+        let model = NgramModel::parse("TH 100\nHE 90\nIN 50\n\nER 40\n").unwrap();
+        assert_eq!(model.n, 2);
+        assert!(model.score("the other") > model.score("xqzvkj"));
+        assert_eq!(model.score("t"), f64::MIN);
+        assert!(NgramModel::parse("TH 100\nTHE 5\n").is_err());
+        assert!(NgramModel::parse("TH lots\n").is_err());
+    }
+
+    #[test]
+    fn embedded_models_are_used_without_downloads() {
+        assert_eq!(embedded_ngram_model("english-bigrams").unwrap().n, 2);
+        assert!(ngram_model("english-bigrams").is_some());
+        assert!(ngram_model("klingon-quadgrams").is_none());
+    }
+
+    #[test]
+    fn quadgrams_rank_english_above_shifted_text_and_gibberish() {
+        let english = quadgram_score("Mutley you snickering floppy eared hound");
+        let rot13 = quadgram_score("Zhgyrl lbh favpxrevat sybccl rnerq ubhaq");
+        let gibberish = quadgram_score("xkqjzpfmwlcbndyahgortevius");
+        assert!(english > rot13, "{english} should beat {rot13}");
+        assert!(rot13 > gibberish, "{rot13} should beat {gibberish}");
+        assert_eq!(quadgram_score("abc"), f64::MIN);
+        // Every quadgram has a score, seen in the corpora or not
+        assert!(ENGLISH_QUADGRAMS.scores.iter().all(|score| score.is_finite()));
+    }
+
+    #[test]
+    fn built_in_english_quadgrams_span_words() {
+        let table = QuadgramTable::english();
+        // OFTH only turns up between words, as in "of the"
+        assert!(table.score("ofth") > -3.5);
+        assert!(table.score("tion") > table.score("tiox"));
+        assert!(table.score("tiox") > table.score("qzxj"));
+        let english = table.score("the quick brown fox jumps over the lazy dog");
+        assert!((-6.0..-4.0).contains(&english), "{english} isn't like English");
+    }
+
+    #[test]
+    fn quadgram_tables_need_quadgram_models() {
+        let bigrams = NgramModel::parse("TH 100\nHE 90\n").unwrap();
+        assert!(QuadgramTable::from_model(&bigrams).is_err());
+        let quadgrams = NgramModel::parse("TION 100\nTHER 90\n").unwrap();
+        let table = QuadgramTable::from_model(&quadgrams).unwrap();
+        assert!(table.score("tion") > table.score("zzzz"));
+        assert_eq!(quadgram_index(b"AAAB"), Some(1));
+        assert_eq!(quadgram_index(b"AAA!"), None);
+    }
+
+    #[test]
+    fn each_language_prefers_its_own_text() {
+        let french = "le chat est sur la table et nous avons mangé avec les enfants de la maison";
+        let german = "der hund und die katze sind in dem haus und nicht auf der straße mit uns";
+        assert!(fitness_score_in(french, Language::French) > fitness_score_in(french, Language::English));
+        assert!(fitness_score_in(german, Language::German) > fitness_score_in(german, Language::English));
+        assert!(is_likely_plaintext(french, &[Language::English, Language::French]));
+        
+        for language in Language::ALL {
+            let ic = language.expected_ic();
+            assert!((0.06..0.09).contains(&ic), "{} has an IC of {ic}", language.name());
+        }
+    }
+
+    #[test]
+    fn no_languages_means_english() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        assert_eq!(best_fitness_score(text, &[]), fitness_score(text));
+        assert_eq!(is_likely_plaintext(text, &[]), is_likely_english(text));
+    }
+
+    #[test]
+    fn user_words_are_tried_first_as_keywords() {
+        assert_eq!(attack_keywords(&[]).len(), ATTACK_WORDLIST.len());
+        let words = ["Kestrel".to_string(), "flag{".to_string(), "secret".to_string()];
+        let keywords = attack_keywords(&words);
+        assert_eq!(keywords[..4], ["kestrel", "KESTREL", "secret", "SECRET"]);
+        assert_eq!(keywords.len(), ATTACK_WORDLIST.len() + 2);
+    }
+
+    #[test]
+    fn dictionary_attack_finds_the_confirmed_keyword() {
+        let keywords: Vec<String> = ["apple", "secret", "zebra"].iter().map(|w| w.to_string()).collect();
+        let decrypt = |keyword: &str| Some(format!("{keyword} is the key"));
+        let attack = dictionary_attack(&keywords, &[], None, decrypt, |text| text.starts_with("secret").then_some(()));
+        let (hit, ()) = attack.hit.unwrap();
+        assert_eq!(hit.keyword, "secret");
+        assert_eq!(hit.plaintext, "secret is the key");
+        
+        let attack = dictionary_attack(&keywords, &[], None, decrypt, |_| None::<()>);
+        assert!(attack.hit.is_none());
+        let best = attack.best.unwrap();
+        assert_eq!(best.score, best_fitness_score(&best.plaintext, &[]));
+        assert!(dictionary_attack(&keywords, &[], None, |_| None, |_| Some(())).best.is_none());
+    }
+
+    #[test]
+    fn dictionary_attacks_stop_at_the_deadline() {
+        let keywords: Vec<String> = (0..10_000).map(|i| format!("keyword{i}")).collect();
+        let deadline = Deadline::after(std::time::Duration::from_secs(60));
+        let tried = std::sync::atomic::AtomicUsize::new(0);
+        let decrypt = |keyword: &str| {
+            // The time runs out part way through the attack
+            if tried.fetch_add(1, Ordering::Relaxed) == 100 {
+                deadline.cancel();
+            }
+            Some(keyword.to_string())
+        };
+        let attack = dictionary_attack(&keywords, &[], Some(&deadline), decrypt, |_| None::<()>);
+        assert!(attack.hit.is_none());
+        // Keywords already being tried on other threads still finish
+        assert!(tried.load(Ordering::Relaxed) < 1000, "{}", tried.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn run_together_words_are_recognised() {
+        let concatenated = word_score("thequickbrownfoxjumpsoverthelazydogandkeepsrunning");
+        assert!(concatenated > 40.0, "{concatenated}");
+        assert_eq!(word_score("xkqzvbptqwmnrlxa"), 0.0);
+        assert_eq!(word_score("hello world"), 100.0);
+    }
+
+    #[test]
+    fn scores_are_memoized_by_text_and_language() {
+        let text = "memoized scores are the same as computed ones";
+        assert_eq!(fitness_score_in(text, Language::French), compute_fitness_score(text, Language::French));
+        assert_eq!(fitness_score_in(text, Language::French), compute_fitness_score(text, Language::French));
+        assert_eq!(is_likely_language(text, Language::English), compute_is_likely_language(text, Language::English));
+        
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let key = (hasher.finish(), Language::French);
+        assert!(FITNESS_CACHE.lock().unwrap().contains(&key));
+        assert!(!LIKELY_CACHE.lock().unwrap().contains(&key));
+    }
+
+    #[test]
+    fn tabu_search_finds_the_best_key() {
+        let alphabet: Vec<char> = "ABCDEFGH".chars().collect();
+        let target: Vec<char> = "HGFEDCBA".chars().collect();
+        let score = |key: &[char]| key.iter().zip(&target).filter(|(a, b)| a == b).count() as f64;
+        let mut rng = seeded_rng(Some(5));
+        let (key, best) = HillClimber::tabu(50, 1).optimize_key(&alphabet, None, score, &mut rng);
+        assert_eq!(key, target);
+        assert_eq!(best, 8.0);
+        let (key, _) = HillClimber::new(5000, 3).optimize_key(&alphabet, Some(target.clone()), score, &mut rng);
+        assert_eq!(key, target);
+    }
+
+    #[test]
+    fn climbers_stop_at_the_deadline() {
+        let alphabet: Vec<char> = "ABCDEFGH".chars().collect();
+        let target: Vec<char> = "HGFEDCBA".chars().collect();
+        let start: Vec<char> = "GHFEDCBA".chars().collect();
+        let score = |key: &[char]| key.iter().zip(&target).filter(|(a, b)| a == b).count() as f64;
+        let deadline = Deadline::after(std::time::Duration::from_secs(60));
+        deadline.cancel();
+        for climber in [HillClimber::new(5000, 3), HillClimber::tabu(50, 3)] {
+            let climber = climber.with_deadline(Some(deadline.clone()));
+            let (key, best) = climber.optimize_key(&alphabet, Some(start.clone()), score, &mut seeded_rng(Some(5)));
+            assert_eq!(key, start);
+            assert_eq!(best, 6.0);
+        }
+    }
+
+    #[test]
+    fn tabu_lists_forget_the_oldest_swaps() {
+        let mut tabu = TabuList::new(2);
+        tabu.push(1, 2);
+        tabu.push(4, 3);
+        assert!(tabu.contains(2, 1));
+        assert!(tabu.contains(3, 4));
+        tabu.push(5, 6);
+        assert!(!tabu.contains(1, 2));
+        let mut off = TabuList::new(0);
+        off.push(1, 2);
+        assert!(!off.contains(1, 2));
+    }
+
     #[test]
     fn test_bigram_score() {
         let english = "THE QUICK BROWN FOX";
@@ -0,0 +1,243 @@
+//! Columnar transposition: recovering the column order
+//!
+//! The plaintext is written in rows under the key, and the columns read out
+//! top to bottom in the order of the key's letters. With the right order the
+//! columns line up into rows of ordinary text, so each pair of columns is
+//! scored by how likely the letter pairs side by side in them are. A greedy
+//! chain of the best neighbours gives a start, and hill climbing over the
+//! whole plaintext's quadgrams fixes what it got wrong.
+//!
+//! Keys are given as the read position of each column: `[2, 0, 1]` means the
+//! first column was read out third, the second first and the third second,
+//! as with the keyword `CAB`.
+
+use super::{HillClimber, Language};
+use once_cell::sync::OnceCell;
+use rand::rngs::StdRng;
+
+/// Letter pair scores of each language, in the order of [`Language::ALL`],
+/// worked out the first time they are needed
+static BIGRAMS: [OnceCell<[[f64; 26]; 26]>; 5] = [const { OnceCell::new() }; 5];
+
+/// Undoes a columnar transposition of `ciphertext` with `key` columns, where
+/// `key[i]` is the position column `i` was read out in. If the last row isn't
+/// full, the columns on its left are a letter longer.
+///
+/// ```
+/// use ares::cryptanalysis::transposition::columnar_decrypt;
+///
+/// // "WEAREDISCOVERED" under the keyword "CAB", read out as the A column
+/// // "EESVE", then B "ADCED" and C "WRIOR"
+/// assert_eq!(
+///     columnar_decrypt("EESVEADCEDWRIOR", &[2, 0, 1]),
+///     "WEAREDISCOVERED"
+/// );
+/// ```
+pub fn columnar_decrypt(ciphertext: &str, key: &[usize]) -> String {
+    let chars: Vec<char> = ciphertext.chars().collect();
+    decrypt_chars(&chars, key).into_iter().collect()
+}
+
+/// Scores every ordered pair of columns: `[a][b]` is the log probability of
+/// the letter pairs across the rows if column `b` comes right after `a`.
+/// Returns `None` unless the text fills every row, as otherwise where each
+/// column starts in the ciphertext depends on the order.
+pub fn column_adjacency(
+    ciphertext: &str,
+    num_cols: usize,
+    language: Language,
+) -> Option<Vec<Vec<f64>>> {
+    let letters = letters(ciphertext);
+    if num_cols < 2 || letters.is_empty() || !letters.len().is_multiple_of(num_cols) {
+        return None;
+    }
+    let bigrams = bigram_scores(language);
+    let columns: Vec<&[u8]> = letters.chunks(letters.len() / num_cols).collect();
+    Some(
+        columns
+            .iter()
+            .map(|left| {
+                columns
+                    .iter()
+                    .map(|right| {
+                        left.iter()
+                            .zip(right.iter())
+                            .map(|(&a, &b)| bigrams[usize::from(a)][usize::from(b)])
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// Chains the columns from left to right, each followed by the unused column
+/// which scores best after it, trying every column first. Returns the key of
+/// the best chain.
+pub fn greedy_column_order(adjacency: &[Vec<f64>]) -> Vec<usize> {
+    let num_cols = adjacency.len();
+    let mut best: Option<(Vec<usize>, f64)> = None;
+    for first in 0..num_cols {
+        let mut chain = vec![first];
+        let mut total = 0.0;
+        while chain.len() < num_cols {
+            let last = chain[chain.len() - 1];
+            let Some(next) = (0..num_cols)
+                .filter(|column| !chain.contains(column))
+                .max_by(|&a, &b| adjacency[last][a].total_cmp(&adjacency[last][b]))
+            else {
+                break;
+            };
+            total += adjacency[last][next];
+            chain.push(next);
+        }
+        if best.as_ref().is_none_or(|(_, best_total)| total > *best_total) {
+            best = Some((chain, total));
+        }
+    }
+    // The i-th column of the plaintext is the chain[i]-th read out
+    best.map(|(chain, _)| chain).unwrap_or_default()
+}
+
+/// Finds the key with `num_cols` columns whose plaintext scores best in
+/// `language`. Returns the key, plaintext in uppercase letters and its
+/// quadgram score, or `None` if there aren't at least two full rows.
+pub fn solve_columnar(
+    ciphertext: &str,
+    num_cols: usize,
+    language: Language,
+    climber: &HillClimber,
+    rng: &mut StdRng,
+) -> Option<(Vec<usize>, String, f64)> {
+    let letters = letters(ciphertext);
+    if !(2..=26).contains(&num_cols) || letters.len() < num_cols * 2 {
+        return None;
+    }
+    let uppercase: String = letters.iter().map(|&l| char::from(b'A' + l)).collect();
+    let start = column_adjacency(&uppercase, num_cols, language)
+        .map(|adjacency| to_chars(&greedy_column_order(&adjacency)));
+
+    // The climber orders letters, so column i is the letter i after A
+    let alphabet = to_chars(&(0..num_cols).collect::<Vec<_>>());
+    let quadgrams = language.quadgrams();
+    let bytes: Vec<char> = uppercase.chars().collect();
+    let score = |key: &[char]| {
+        let plaintext: Vec<u8> = decrypt_chars(&bytes, &from_chars(key))
+            .into_iter()
+            .map(|c| c as u8)
+            .collect();
+        quadgrams.score_letters(&plaintext)
+    };
+    let (key, score) = climber.optimize_key(&alphabet, start, score, rng);
+    let key = from_chars(&key);
+    let plaintext = columnar_decrypt(&uppercase, &key);
+    Some((key, plaintext, score))
+}
+
+/// The letter pair scores of `language`
+fn bigram_scores(language: Language) -> &'static [[f64; 26]; 26] {
+    let index = Language::ALL
+        .iter()
+        .position(|&other| other == language)
+        .unwrap_or(0);
+    BIGRAMS[index].get_or_init(|| language.quadgrams().bigram_scores())
+}
+
+/// Undoes the transposition of `chars`, see [`columnar_decrypt`]
+fn decrypt_chars(chars: &[char], key: &[usize]) -> Vec<char> {
+    let num_cols = key.len();
+    if num_cols == 0 || chars.is_empty() {
+        return chars.to_vec();
+    }
+    let rows = chars.len().div_ceil(num_cols);
+    let long_cols = match chars.len() % num_cols {
+        0 => num_cols,
+        long => long,
+    };
+    let height = |column: usize| if column < long_cols { rows } else { rows - 1 };
+
+    // Where each column starts, going through them in the order they were read
+    let mut read_order: Vec<usize> = (0..num_cols).collect();
+    read_order.sort_by_key(|&column| key[column]);
+    let mut starts = vec![0; num_cols];
+    let mut position = 0;
+    for column in read_order {
+        starts[column] = position;
+        position += height(column);
+    }
+
+    let mut plaintext = Vec::with_capacity(chars.len());
+    for row in 0..rows {
+        for (column, start) in starts.iter().enumerate() {
+            if row < height(column) {
+                plaintext.push(chars[start + row]);
+            }
+        }
+    }
+    plaintext
+}
+
+/// The letters of `text` as 0 to 25, ignoring everything else
+fn letters(text: &str) -> Vec<u8> {
+    text.bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase() - b'A')
+        .collect()
+}
+
+/// A key as letters for [`HillClimber::optimize_key`], 0 as `A`
+fn to_chars(key: &[usize]) -> Vec<char> {
+    key.iter().map(|&i| char::from(b'A' + i as u8)).collect()
+}
+
+/// The key [`to_chars`] turned into letters
+fn from_chars(key: &[char]) -> Vec<usize> {
+    key.iter().map(|&c| usize::from(c as u8 - b'A')).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptanalysis::seeded_rng;
+
+    /// The start of Alice in Wonderland's rabbit hole paragraph
+    const PLAINTEXT: &str = "THEREWASNOTHINGSOVERYREMARKABLEINTHATNORDIDALICETHINKITSOVERYMUCHOUTOFTHEWAYTOHEARTHERABBITSAYTOITSELF";
+
+    #[test]
+    fn full_rows_are_ordered_by_adjacency() {
+        let ciphertext = "RHRAAANRTYHSHOVRTIHVOWRIEIYBTLKYOTEAAGEEOCTUTHATTNOANDTOHEABSSMIRESCHEBOWNRLNIIMFORYETEKHDIEUATT";
+        let adjacency = column_adjacency(ciphertext, 8, Language::English).unwrap();
+        let key = vec![4, 1, 7, 0, 2, 6, 3, 5];
+        assert_eq!(columnar_decrypt(ciphertext, &key), PLAINTEXT[..96]);
+        // Column 1 follows column 0, so should score better than after it
+        assert!(adjacency[key[0]][key[1]] > adjacency[key[1]][key[0]]);
+
+        let (solved, plaintext, _) = solve_columnar(
+            ciphertext,
+            8,
+            Language::English,
+            &HillClimber::tabu(100, 10),
+            &mut seeded_rng(Some(1)),
+        )
+        .unwrap();
+        assert_eq!(solved, key);
+        assert_eq!(plaintext, PLAINTEXT[..96]);
+    }
+
+    #[test]
+    fn partial_rows_are_found_by_climbing() {
+        let ciphertext = "HNSELTAIVHHHRAERTVAIOIKRUWABTFWIRKTDETMOYTIITSGRBADHOCTOESSANYAHITSUFTHTTEOOMENLNEOEEAYLEHERNRCIYTARBO";
+        assert!(column_adjacency(ciphertext, 7, Language::English).is_none());
+        let (key, plaintext, _) = solve_columnar(
+            ciphertext,
+            7,
+            Language::English,
+            &HillClimber::tabu(100, 10),
+            &mut seeded_rng(Some(1)),
+        )
+        .unwrap();
+        assert_eq!(key, vec![3, 0, 5, 1, 6, 2, 4]);
+        assert_eq!(plaintext, PLAINTEXT);
+        assert!(solve_columnar("ABC", 2, Language::English, &HillClimber::default(), &mut seeded_rng(Some(1))).is_none());
+    }
+}
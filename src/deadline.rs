@@ -0,0 +1,178 @@
+//! When a search has to stop, shared by everything the search runs
+//!
+//! A [`Deadline`] is started with the search and travels with it in
+//! [`Config::deadline`](crate::config::Config::deadline), so the searcher,
+//! the filtration system and long-running decoders like hill climbers can
+//! all ask whether time is up and stop what they are doing. Time spent
+//! waiting for the human checker doesn't count.
+
+use crate::timer::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long a search may run, shared between threads. Clones share the
+/// same clock, so pausing or cancelling one pauses or cancels them all.
+///
+/// ```
+/// use ares::deadline::Deadline;
+/// use std::time::Duration;
+///
+/// let deadline = Deadline::after(Duration::from_secs(60));
+/// assert!(!deadline.expired());
+/// deadline.clone().cancel();
+/// assert!(deadline.expired() && deadline.is_cancelled());
+/// ```
+#[derive(Clone)]
+pub struct Deadline {
+    /// The clock, shared by every clone
+    inner: Arc<Inner>,
+}
+
+/// The shared state of a [`Deadline`]
+struct Inner {
+    /// How long the search may run, not counting pauses
+    budget: Duration,
+    /// When it started and how long it has been paused
+    clock: Mutex<Clock>,
+    /// Set once the time is up or the search was cancelled, so checks
+    /// after that don't need the clock
+    expired: AtomicBool,
+    /// Set if the time was ended early with [`Deadline::cancel`]
+    cancelled: AtomicBool,
+}
+
+/// Running time, not counting pauses
+struct Clock {
+    /// When the deadline was started
+    started: Instant,
+    /// How long it was paused for, not counting a pause still going on
+    paused_for: Duration,
+    /// When the pause still going on started, if there is one
+    paused_at: Option<Instant>,
+}
+
+impl Deadline {
+    /// A deadline `budget` from now
+    pub fn after(budget: Duration) -> Self {
+        Deadline {
+            inner: Arc::new(Inner {
+                budget,
+                clock: Mutex::new(Clock {
+                    started: Instant::now(),
+                    paused_for: Duration::ZERO,
+                    paused_at: None,
+                }),
+                expired: AtomicBool::new(false),
+                cancelled: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// A deadline `seconds` from now, as in [`Config::timeout`](crate::config::Config::timeout)
+    pub fn after_secs(seconds: u32) -> Self {
+        Self::after(Duration::from_secs(seconds.into()))
+    }
+
+    /// How long the search may run in all
+    pub fn budget(&self) -> Duration {
+        self.inner.budget
+    }
+
+    /// How long it has run, not counting pauses
+    pub fn elapsed(&self) -> Duration {
+        let clock = match self.inner.clock.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let end = clock.paused_at.unwrap_or_else(Instant::now);
+        end.duration_since(clock.started)
+            .saturating_sub(clock.paused_for)
+    }
+
+    /// How long it may still run
+    pub fn remaining(&self) -> Duration {
+        if self.inner.expired.load(Ordering::Relaxed) {
+            return Duration::ZERO;
+        }
+        self.inner.budget.saturating_sub(self.elapsed())
+    }
+
+    /// Whether the time is up or the search was cancelled
+    pub fn expired(&self) -> bool {
+        if self.inner.expired.load(Ordering::Relaxed) {
+            return true;
+        }
+        if self.elapsed() >= self.inner.budget {
+            self.inner.expired.store(true, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+
+    /// Ends the time now, so everything checking the deadline stops
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+        self.inner.expired.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the time was ended early with [`Deadline::cancel`]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Stops the clock, e.g. while waiting for the user to answer.
+    /// Pausing while paused does nothing.
+    pub fn pause(&self) {
+        let mut clock = match self.inner.clock.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if clock.paused_at.is_none() {
+            clock.paused_at = Some(Instant::now());
+        }
+    }
+
+    /// Starts the clock again after [`Deadline::pause`]
+    pub fn resume(&self) {
+        let mut clock = match self.inner.clock.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(paused_at) = clock.paused_at.take() {
+            clock.paused_for += paused_at.elapsed();
+        }
+    }
+}
+
+impl std::fmt::Debug for Deadline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Deadline")
+            .field("budget", &self.inner.budget)
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+/// Whether the search `deadline` belongs to should stop. No deadline never
+/// expires.
+pub fn expired(deadline: Option<&Deadline>) -> bool {
+    deadline.is_some_and(Deadline::expired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pauses_do_not_count() {
+        let deadline = Deadline::after(Duration::from_millis(50));
+        deadline.pause();
+        std::thread::sleep(Duration::from_millis(80));
+        assert!(!deadline.expired());
+        deadline.resume();
+        std::thread::sleep(Duration::from_millis(80));
+        assert!(deadline.expired());
+        assert_eq!(deadline.remaining(), Duration::ZERO);
+    }
+}
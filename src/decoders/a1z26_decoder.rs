@@ -4,6 +4,7 @@ use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -47,7 +48,7 @@ impl Crack for Decoder<A1Z26Decoder> {
         let decoded_text = decode_a1z26(text);
         trace!("Decoded text for A1Z26: {:?}", decoded_text);
 
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_none() {
             debug!("Failed to decode A1Z26");
@@ -64,7 +65,7 @@ impl Crack for Decoder<A1Z26Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -80,6 +81,10 @@ impl Crack for Decoder<A1Z26Decoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
 
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
@@ -92,6 +97,24 @@ impl Crack for Decoder<A1Z26Decoder> {
     }
 }
 
+impl Encode for Decoder<A1Z26Decoder> {
+    /// Encodes each letter as its position in the alphabet, separated by spaces.
+    /// Whitespace is dropped as A1Z26 has no way to represent it.
+    fn encode(&self, text: &str) -> Result<String, String> {
+        text.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| {
+                if c.is_ascii_alphabetic() {
+                    Ok((c.to_ascii_uppercase() as u8 - b'A' + 1).to_string())
+                } else {
+                    Err(format!("A1Z26 can only encode letters, found '{}'", c))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|numbers| numbers.join(" "))
+    }
+}
+
 /// This function does the actual decoding
 /// It returns an Option<string> if it was successful
 /// Else the Option returns nothing and the error is logged in Trace
@@ -134,7 +157,7 @@ mod tests {
     use crate::checkers::athena::Athena;
     use crate::checkers::checker_type::{Check, Checker};
     use crate::checkers::CheckerTypes;
-    use crate::decoders::interface::Crack;
+    use crate::decoders::interface::{Crack, Encode};
 
     // helper for tests
     fn get_athena_checker() -> CheckerTypes {
@@ -272,4 +295,11 @@ mod tests {
         let result = decoder.crack(", 8-5:12,12;15\t23\r15\n18:,12-;4-", &get_athena_checker(), &crate::config::Config::default());
         assert_eq!(result.unencrypted_text.unwrap()[0], "HELLOWORLD");
     }
+
+    #[test]
+    fn a1z26_encode() {
+        let decoder = Decoder::<A1Z26Decoder>::new();
+        assert_eq!(decoder.encode("Hi there").unwrap(), "8 9 20 8 5 18 5");
+        assert!(decoder.encode("r2d2").is_err());
+    }
 }
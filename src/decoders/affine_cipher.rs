@@ -1,6 +1,8 @@
 //! Decode Affine Cipher
 //! Performs error handling and returns a string
-//! Brute forces all possible keys.
+//! Brute forces all possible keys over A-Z, over A-Z0-9 for text with
+//! digits, and over printable ASCII. Keys over the bigger alphabets name
+//! them, like `7,3 (printable)`.
 
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
@@ -8,6 +10,7 @@ use crate::decoders::interface::check_string_success;
 use crate::decoders::crack_results::CrackResult;
 use crate::decoders::interface::Crack;
 use crate::decoders::interface::Decoder;
+use crate::cryptanalysis::quadgram_score;
 use gibberish_or_not::Sensitivity;
 use log::trace;
 
@@ -30,33 +33,43 @@ impl Crack for Decoder<AffineCipherDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Affine Cipher with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
-
-        // Coprimes to 26: 1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25
-        let _coprimes = [1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25];
+        let mut results = CrackResult::new(self, text);
         let mut best_candidates = Vec::new();
-
-        // Calculate modular multiplicative inverses for valid 'a'
-        // a * a_inv = 1 mod 26
-        // 1->1, 3->9, 5->21, 7->15, 9->3, 11->19, 15->7, 17->23, 19->11, 21->5, 23->17, 25->25
-        let inverses: Vec<(i32, i32)> = vec![
-            (1, 1), (3, 9), (5, 21), (7, 15), (9, 3), (11, 19),
-            (15, 7), (17, 23), (19, 11), (21, 5), (23, 17), (25, 25)
-        ];
+        let mut first_key = None;
 
         let checker_with_sensitivity = checker.with_sensitivity(Sensitivity::Medium);
 
-        for (_a, a_inv) in inverses {
-            for b in 0..26 {
-                let decoded = decrypt_affine(text, a_inv, b);
-                if check_string_success(&decoded, text) {
-                     // Check if it looks like English
-                     let check_res = checker_with_sensitivity.check(&decoded, config);
-                     if check_res.is_identified {
-                         best_candidates.push(decoded);
-                         // If we find a very good match, maybe stop? But short strings might match multiple.
-                         // Affine space is small (312), checking all is fast.
-                     }
+        for alphabet in AffineAlphabet::ALL {
+            if !alphabet.could_have_encrypted(text) {
+                continue;
+            }
+            // A-Z has few enough keys to check them all. The bigger alphabets
+            // have thousands, so only those which read most like text are,
+            // judging first by how the start of the text decrypts.
+            let extended = alphabet != AffineAlphabet::Letters;
+            let prefix: String = text.chars().take(PREFIX_LEN).collect();
+            let mut candidates: Vec<(String, i32, i32)> = Vec::new();
+            for (a, a_inv) in alphabet.inverses() {
+                for b in 0..alphabet.modulus() {
+                    if extended && !looks_like_text(&alphabet.decrypt(&prefix, a_inv, b)) {
+                        continue;
+                    }
+                    let decoded = alphabet.decrypt(text, a_inv, b);
+                    if check_string_success(&decoded, text) && (!extended || looks_like_text(&decoded)) {
+                        candidates.push((decoded, a, b));
+                    }
+                }
+            }
+            if extended {
+                candidates.sort_by(|x, y| quadgram_score(&y.0).total_cmp(&quadgram_score(&x.0)));
+                candidates.truncate(MAX_CHECKED_PER_ALPHABET);
+            }
+            for (decoded, a, b) in candidates {
+                // Check if it looks like English
+                let check_res = checker_with_sensitivity.check(&decoded, config);
+                if check_res.is_identified {
+                    first_key.get_or_insert_with(|| alphabet.key(a, b));
+                    best_candidates.push(decoded);
                 }
             }
         }
@@ -64,8 +77,9 @@ impl Crack for Decoder<AffineCipherDecoder> {
         if !best_candidates.is_empty() {
              // Use first one for update_checker but return all?
              let checker_result = checker.check(&best_candidates[0], config);
-             results.unencrypted_text = Some(best_candidates);
+             results.unencrypted_text = Some(best_candidates.into_iter().map(Into::into).collect());
              results.update_checker(&checker_result);
+             results.key = first_key;
         }
 
         results
@@ -74,23 +88,164 @@ impl Crack for Decoder<AffineCipherDecoder> {
     fn get_tags(&self) -> &Vec<&str> { &self.tags }
     fn get_name(&self) -> &str { self.name }
     fn get_popularity(&self) -> f32 { self.popularity }
+    /// Tries all 312 keys
+    fn expected_cost(&self) -> f32 { 10.0 }
     fn get_description(&self) -> &str { self.description }
     fn get_link(&self) -> &str { self.link }
+    /// Decodes with a known key pair `a,b`, where `a` must be coprime to the
+    /// alphabet's size. A-Z is used unless the key names another alphabet,
+    /// as in `7,3 (A-Z0-9)` or `7,3 (printable)`.
+    fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        let invalid = || format!(
+            "Affine key must be `a,b` with a coprime to the alphabet size, optionally followed by (A-Z0-9) or (printable), got '{}'",
+            key
+        );
+        let key = key.trim();
+        let (pair, alphabet) = match key.strip_suffix(')').and_then(|key| key.split_once(" (")) {
+            Some((pair, name)) => (
+                pair,
+                AffineAlphabet::ALL
+                    .into_iter()
+                    .find(|alphabet| alphabet.name() == name)
+                    .ok_or_else(invalid)?,
+            ),
+            None => (key, AffineAlphabet::Letters),
+        };
+        let (a, b) = pair.split_once(',').ok_or_else(invalid)?;
+        let a: i32 = a.trim().parse().map_err(|_| invalid())?;
+        let b: i32 = b.trim().parse().map_err(|_| invalid())?;
+        let m = alphabet.modulus();
+        let a_inv = modular_inverse(a.rem_euclid(m), m).ok_or_else(invalid)?;
+        Ok(alphabet.decrypt(text, a_inv, b.rem_euclid(m)))
+    }
 }
 
-/// Helper function to decrypt affine cipher
-fn decrypt_affine(text: &str, a_inv: i32, b: i32) -> String {
-    text.chars().map(|c| {
-        if c.is_ascii_alphabetic() {
-            let base = if c.is_ascii_uppercase() { b'A' } else { b'a' } as i32;
-            let y = c as i32 - base;
-            // D(x) = a_inv * (y - b) mod 26
-            let val = (a_inv * (y - b)).rem_euclid(26);
-            ((base + val) as u8) as char
-        } else {
-            c
+/// How many keys of the A-Z0-9 and printable alphabets are checked, best first
+const MAX_CHECKED_PER_ALPHABET: usize = 10;
+
+/// How many characters are decrypted to rule out keys of the bigger alphabets
+const PREFIX_LEN: usize = 64;
+
+/// The share of characters which must be letters, spaces or common
+/// punctuation for a candidate from a bigger alphabet to be checked
+const MIN_TEXT_SHARE: f64 = 0.9;
+
+/// The alphabets the Affine cipher is applied over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AffineAlphabet {
+    /// A-Z, keeping the case of each letter
+    Letters,
+    /// A-Z then 0-9, keeping the case of each letter
+    Alphanumeric,
+    /// Printable ASCII, from space to `~`
+    Printable,
+}
+
+impl AffineAlphabet {
+    /// Every alphabet, in the order they are tried
+    const ALL: [AffineAlphabet; 3] = [
+        AffineAlphabet::Letters,
+        AffineAlphabet::Alphanumeric,
+        AffineAlphabet::Printable,
+    ];
+
+    /// The name shown in keys. Keys over A-Z don't name it.
+    fn name(self) -> &'static str {
+        match self {
+            AffineAlphabet::Letters => "A-Z",
+            AffineAlphabet::Alphanumeric => "A-Z0-9",
+            AffineAlphabet::Printable => "printable",
+        }
+    }
+
+    /// The number of characters
+    fn modulus(self) -> i32 {
+        match self {
+            AffineAlphabet::Letters => 26,
+            AffineAlphabet::Alphanumeric => 36,
+            AffineAlphabet::Printable => 95,
+        }
+    }
+
+    /// The key as reported, like `5,8` or `7,3 (printable)`
+    fn key(self, a: i32, b: i32) -> String {
+        match self {
+            AffineAlphabet::Letters => format!("{a},{b}"),
+            _ => format!("{a},{b} ({})", self.name()),
+        }
+    }
+
+    /// Every valid `a` with its inverse `a_inv`, where a * a_inv = 1 mod m
+    fn inverses(self) -> Vec<(i32, i32)> {
+        let m = self.modulus();
+        (1..m)
+            .filter_map(|a| modular_inverse(a, m).map(|a_inv| (a, a_inv)))
+            .collect()
+    }
+
+    /// Whether `text` could be ciphertext over the alphabet. A-Z needs
+    /// letters, A-Z0-9 letters and digits, and printable ASCII nothing else.
+    fn could_have_encrypted(self, text: &str) -> bool {
+        match self {
+            AffineAlphabet::Letters => text.chars().any(|c| c.is_ascii_alphabetic()),
+            AffineAlphabet::Alphanumeric => {
+                text.chars().any(|c| c.is_ascii_alphabetic())
+                    && text.chars().any(|c| c.is_ascii_digit())
+            }
+            AffineAlphabet::Printable => {
+                !text.is_empty() && text.chars().all(|c| (' '..='~').contains(&c))
+            }
+        }
+    }
+
+    /// D(y) = a_inv * (y - b) mod m for each character in the alphabet
+    fn decrypt(self, text: &str, a_inv: i32, b: i32) -> String {
+        let m = self.modulus();
+        text.chars().map(|c| {
+            let Some((y, lowercase)) = self.position(c) else {
+                return c;
+            };
+            let decrypted = self.character((a_inv * (y - b)).rem_euclid(m));
+            if lowercase { decrypted.to_ascii_lowercase() } else { decrypted }
+        }).collect()
+    }
+
+    /// Where `c` is in the alphabet, and whether it is a lowercase letter
+    /// standing in for its uppercase form
+    fn position(self, c: char) -> Option<(i32, bool)> {
+        match self {
+            AffineAlphabet::Letters | AffineAlphabet::Alphanumeric if c.is_ascii_alphabetic() => {
+                Some(((c.to_ascii_uppercase() as u8 - b'A') as i32, c.is_ascii_lowercase()))
+            }
+            AffineAlphabet::Alphanumeric if c.is_ascii_digit() => Some(((c as u8 - b'0') as i32 + 26, false)),
+            AffineAlphabet::Printable if (' '..='~').contains(&c) => Some(((c as u8 - b' ') as i32, false)),
+            _ => None,
         }
-    }).collect()
+    }
+
+    /// The character at `position`
+    fn character(self, position: i32) -> char {
+        match self {
+            AffineAlphabet::Printable => (b' ' + position as u8) as char,
+            _ if position < 26 => (b'A' + position as u8) as char,
+            _ => (b'0' + (position - 26) as u8) as char,
+        }
+    }
+}
+
+/// The inverse of `a` mod `m`, if `a` is coprime to `m`
+fn modular_inverse(a: i32, m: i32) -> Option<i32> {
+    (1..m).find(|candidate| (a * candidate) % m == 1)
+}
+
+/// Whether nearly all of `text` is letters, spaces and common punctuation
+fn looks_like_text(text: &str) -> bool {
+    let total = text.chars().count();
+    let texty = text
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic() || matches!(c, ' ' | '.' | ',' | '\'' | '!' | '?' | '-'))
+        .count();
+    total > 0 && texty as f64 >= total as f64 * MIN_TEXT_SHARE
 }
 
 #[cfg(test)]
@@ -114,6 +269,48 @@ mod tests {
         let decoder = Decoder::<AffineCipherDecoder>::new();
         let result = decoder.crack("IHHWVC SWFRCP", &get_checker(), &crate::config::Config::default());
         assert!(result.unencrypted_text.is_some());
-        assert!(result.unencrypted_text.unwrap().contains(&"AFFINE CIPHER".to_string()));
+        assert!(result.unencrypted_text.unwrap().contains(&"AFFINE CIPHER".into()));
+    }
+
+    #[test]
+    fn affine_decode_with_key() {
+        let decoder = Decoder::<AffineCipherDecoder>::new();
+        assert_eq!(decoder.decode_with_key("IHHWVC SWFRCP", "5,8").unwrap(), "AFFINE CIPHER");
+        // 13 shares a factor with 26 so it has no inverse
+        assert!(decoder.decode_with_key("IHHWVC SWFRCP", "13,8").is_err());
+    }
+
+    #[test]
+    fn affine_alphanumeric() {
+        let decoder = Decoder::<AffineCipherDecoder>::new();
+        let result = decoder.crack(
+            "P552 P5 D2 MF 3W AID2C3OP 6 NX2Q 2Q5 Y3R9P5W2V",
+            &get_checker(),
+            &crate::config::Config::default(),
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "MEET ME AT 10 ON PLATFORM 9 WITH THE DOCUMENTS"
+        );
+        assert_eq!(result.key.as_deref(), Some("7,3 (A-Z0-9)"));
+    }
+
+    #[test]
+    fn affine_printable() {
+        let decoder = Decoder::<AffineCipherDecoder>::new();
+        let ciphertext = r"r@+# <G|U#u'qJj#2qQ#N<cx.#qC+'#5@+#\n_X#$q9&";
+        let result = decoder.crack(ciphertext, &get_checker(), &crate::config::Config::default());
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "The quick brown fox jumps over the lazy dog."
+        );
+        assert_eq!(result.key.as_deref(), Some("7,3 (printable)"));
+        assert_eq!(
+            decoder.decode_with_key(ciphertext, "7,3 (printable)").unwrap(),
+            "The quick brown fox jumps over the lazy dog."
+        );
+        assert!(decoder.decode_with_key(ciphertext, "5,3 (printable)").is_err());
     }
 }
@@ -6,6 +6,7 @@
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 use crate::decoders::interface::check_string_success;
+use crate::storage::artifacts;
 use crate::decoders::crack_results::CrackResult;
 use crate::decoders::interface::Crack;
 use crate::decoders::interface::Decoder;
@@ -49,7 +50,7 @@ impl Crack for Decoder<Ascii85Decoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Ascii85 with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let decoded_text = decode_ascii85_no_error_handling(text);
 
         if decoded_text.is_none() {
@@ -67,7 +68,7 @@ impl Crack for Decoder<Ascii85Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results.update_checker(&checker_result);
 
         results
@@ -176,6 +177,7 @@ fn decode_ascii85_no_error_handling(input: &str) -> Option<String> {
         i += 5;
     }
 
+    artifacts::inspect("Ascii85", &output);
     String::from_utf8(output).ok()
 }
 
@@ -259,6 +261,6 @@ mod tests {
         let decoder = Decoder::<Ascii85Decoder>::new();
         let result = decoder.crack(encoded, &get_athena_checker(), &crate::config::Config::default());
         assert!(result.unencrypted_text.is_some());
-        assert!(result.unencrypted_text.unwrap()[0].starts_with("Man is distinguished"));
+        assert!(result.unencrypted_text.unwrap()[0].text().starts_with("Man is distinguished"));
     }
 }
@@ -4,6 +4,7 @@ use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{info, trace};
@@ -31,7 +32,7 @@ impl Crack for Decoder<AtbashDecoder> {
         let decoded_text = atbash_to_alphabet(text);
 
         trace!("Decoded text for atbash: {:?}", decoded_text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if !check_string_success(&decoded_text, text) {
             info!(
@@ -42,7 +43,7 @@ impl Crack for Decoder<AtbashDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -56,6 +57,10 @@ impl Crack for Decoder<AtbashDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
 
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
@@ -68,6 +73,13 @@ impl Crack for Decoder<AtbashDecoder> {
     }
 }
 
+impl Encode for Decoder<AtbashDecoder> {
+    /// Atbash is its own inverse
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(atbash_to_alphabet(text))
+    }
+}
+
 /// Maps atbash to the alphabet
 fn atbash_to_alphabet(text: &str) -> String {
     text.chars()
@@ -34,7 +34,7 @@ impl Crack for Decoder<BaconCipherDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Bacon Cipher with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // We try both alphabets
         let mut potential_results = Vec::new();
@@ -60,7 +60,7 @@ impl Crack for Decoder<BaconCipherDecoder> {
 
         if !valid_results.is_empty() {
             let checker_result = checker.check(&valid_results[0], config); // Check first
-            results.unencrypted_text = Some(valid_results);
+            results.unencrypted_text = Some(valid_results.into_iter().map(Into::into).collect());
             results.update_checker(&checker_result);
         }
 
@@ -6,9 +6,11 @@
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 use crate::decoders::interface::check_string_success;
+use crate::storage::artifacts;
 
 use super::crack_results::CrackResult;
-use super::interface::Crack;
+use super::interface::{Alphabet, Crack};
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use data_encoding::BASE32_NOPAD;
@@ -52,7 +54,7 @@ impl Crack for Decoder<Base32Decoder> {
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Base32 with text {:?}", text);
         let decoded_text = decode_base32_no_error_handling(text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_none() {
             debug!("Failed to decode base32 because Base32Decoder::decode_base32_no_error_handling returned None");
@@ -69,7 +71,7 @@ impl Crack for Decoder<Base32Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -83,6 +85,10 @@ impl Crack for Decoder<Base32Decoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
 
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
@@ -93,6 +99,21 @@ impl Crack for Decoder<Base32Decoder> {
     fn get_link(&self) -> &str {
         self.link
     }
+    /// Base32 is uppercase letters, the digits 2 to 7 and padding
+    fn alphabet(&self) -> Alphabet {
+        Alphabet::Only("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567=")
+    }
+    /// Every byte takes at least two characters
+    fn min_length(&self) -> usize {
+        2
+    }
+}
+
+impl Encode for Decoder<Base32Decoder> {
+    /// Encodes with the RFC 4648 Base32 alphabet and padding
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(data_encoding::BASE32.encode(text.as_bytes()))
+    }
 }
 
 /// helper function
@@ -102,6 +123,7 @@ fn decode_base32_no_error_handling(text: &str) -> Option<String> {
     // Runs the code to decode base32
     // Doesn't perform error handling, call from_base32
     if let Ok(decoded_text) = &BASE32_NOPAD.decode(text.as_bytes()) {
+        artifacts::inspect("Base32", decoded_text);
         return Some(String::from_utf8_lossy(decoded_text).to_string());
     }
     None
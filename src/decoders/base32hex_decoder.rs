@@ -30,14 +30,14 @@ impl Crack for Decoder<Base32HexDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Base32Hex with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         let text_no_pad = text.replace('=', "");
         if let Ok(decoded_bytes) = BASE32HEX_NOPAD.decode(text_no_pad.as_bytes()) {
              if let Ok(decoded) = String::from_utf8(decoded_bytes) {
                   if check_string_success(&decoded, text) {
                       let checker_result = checker.check(&decoded, config);
-                      results.unencrypted_text = Some(vec![decoded]);
+                      results.unencrypted_text = Some(vec![decoded.into()]);
                       results.update_checker(&checker_result);
                   }
              }
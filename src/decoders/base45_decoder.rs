@@ -1,5 +1,10 @@
 //! Decode Base45
 //! Performs error handling and returns a string
+//!
+//! EU Digital COVID Certificates are Base45 too, prefixed with `HC1:`, but
+//! what they encode is a zlib compressed CBOR Web Token rather than text.
+//! With the `compression` feature, those are inflated and parsed, and the
+//! certificate's contents shown as JSON.
 
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
@@ -7,7 +12,32 @@ use crate::decoders::interface::check_string_success;
 use crate::decoders::crack_results::CrackResult;
 use crate::decoders::interface::Crack;
 use crate::decoders::interface::Decoder;
+#[cfg(feature = "compression")]
+use ciborium::Value as Cbor;
+#[cfg(feature = "compression")]
+use flate2::read::ZlibDecoder;
 use log::trace;
+#[cfg(feature = "compression")]
+use serde_json::{json, Map, Value};
+#[cfg(feature = "compression")]
+use std::io::Read;
+
+/// What EU Digital COVID Certificates start with, before the Base45
+#[cfg(feature = "compression")]
+const HEALTH_CERTIFICATE_PREFIX: &str = "HC1:";
+
+/// The most bytes a certificate is inflated to, so a zlib bomb can't use
+/// up the memory
+#[cfg(feature = "compression")]
+const MAX_INFLATED_LEN: u64 = 1024 * 1024;
+
+/// The CBOR tag of a COSE_Sign1 message, the signed envelope of the token
+#[cfg(feature = "compression")]
+const COSE_SIGN1_TAG: u64 = 18;
+
+/// The claim of the CBOR Web Token with the health certificate in it
+#[cfg(feature = "compression")]
+const HCERT_CLAIM: i128 = -260;
 
 /// The Base45 decoder, call:
 /// `let base45_decoder = Decoder::<Base45Decoder>::new()` to create a new instance
@@ -28,13 +58,28 @@ impl Crack for Decoder<Base45Decoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Base45 with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
+
+        #[cfg(feature = "compression")]
+        if let Some(certificate) = text
+            .trim()
+            .strip_prefix(HEALTH_CERTIFICATE_PREFIX)
+            .and_then(|encoded| base45::decode(encoded).ok())
+            .and_then(|bytes| health_certificate(&bytes))
+        {
+            let mut checker_result = checker.check(&certificate, config);
+            // A certificate which parses is what was encoded, whatever the checker thinks of JSON
+            checker_result.is_identified = true;
+            results.unencrypted_text = Some(vec![certificate.into()]);
+            results.update_checker(&checker_result);
+            return results;
+        }
 
         if let Ok(bytes) = base45::decode(text) {
              if let Ok(decoded) = String::from_utf8(bytes) {
                  if check_string_success(&decoded, text) {
                     let checker_result = checker.check(&decoded, config);
-                    results.unencrypted_text = Some(vec![decoded]);
+                    results.unencrypted_text = Some(vec![decoded.into()]);
                     results.update_checker(&checker_result);
                  }
              }
@@ -50,6 +95,97 @@ impl Crack for Decoder<Base45Decoder> {
     fn get_link(&self) -> &str { self.link }
 }
 
+/// The contents of a zlib compressed COSE_Sign1 CBOR Web Token, as in EU
+/// Digital COVID Certificates: the issuer, when it was issued and expires,
+/// then the certificate as pretty-printed JSON. None if it isn't one.
+#[cfg(feature = "compression")]
+fn health_certificate(compressed: &[u8]) -> Option<String> {
+    let mut inflated = Vec::new();
+    ZlibDecoder::new(compressed)
+        .take(MAX_INFLATED_LEN)
+        .read_to_end(&mut inflated)
+        .ok()?;
+    let message: Cbor = ciborium::from_reader(inflated.as_slice()).ok()?;
+    // Signers may leave the tag off
+    let message = match message {
+        Cbor::Tag(COSE_SIGN1_TAG, inner) => *inner,
+        other => other,
+    };
+    let Cbor::Array(parts) = message else {
+        return None;
+    };
+    // Protected header, unprotected header, payload, signature
+    let Some(Cbor::Bytes(payload)) = parts.get(2) else {
+        return None;
+    };
+    let Cbor::Map(claims) = ciborium::from_reader(payload.as_slice()).ok()? else {
+        return None;
+    };
+    let claim = |key: i128| {
+        claims
+            .iter()
+            .find(|(k, _)| k.as_integer().is_some_and(|k| i128::from(k) == key))
+            .map(|(_, v)| v)
+    };
+
+    // The health certificate claim holds version 1 of it under key 1
+    let Some(Cbor::Map(versions)) = claim(HCERT_CLAIM) else {
+        return None;
+    };
+    let certificate = versions
+        .iter()
+        .find(|(k, _)| k.as_integer().is_some_and(|k| i128::from(k) == 1))
+        .map(|(_, v)| cbor_to_json(v))?;
+
+    let mut lines = vec!["EU Digital COVID Certificate".to_string()];
+    if let Some(Cbor::Text(issuer)) = claim(1) {
+        lines.push(format!("Issuer: {issuer}"));
+    }
+    for (key, label) in [(6, "Issued"), (4, "Expires")] {
+        if let Some(time) = claim(key).and_then(|v| v.as_integer()).and_then(timestamp) {
+            lines.push(format!("{label}: {time}"));
+        }
+    }
+    lines.push(serde_json::to_string_pretty(&certificate).ok()?);
+    Some(lines.join("\n"))
+}
+
+/// A CWT time claim, in seconds since the epoch, as a UTC date and time
+#[cfg(feature = "compression")]
+fn timestamp(seconds: ciborium::value::Integer) -> Option<String> {
+    let seconds = i64::try_from(seconds).ok()?;
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+}
+
+/// The JSON for a CBOR value. Byte strings become hex, and map keys which
+/// aren't text become their JSON.
+#[cfg(feature = "compression")]
+fn cbor_to_json(value: &Cbor) -> Value {
+    match value {
+        Cbor::Integer(i) => json!(i128::from(*i) as i64),
+        Cbor::Float(f) => json!(f),
+        Cbor::Text(s) => json!(s),
+        Cbor::Bool(b) => json!(b),
+        Cbor::Bytes(bytes) => json!(hex::encode(bytes)),
+        Cbor::Tag(_, inner) => cbor_to_json(inner),
+        Cbor::Array(items) => Value::Array(items.iter().map(cbor_to_json).collect()),
+        Cbor::Map(entries) => Value::Object(
+            entries
+                .iter()
+                .map(|(k, v)| {
+                    let key = match k {
+                        Cbor::Text(s) => s.clone(),
+                        other => cbor_to_json(other).to_string(),
+                    };
+                    (key, cbor_to_json(v))
+                })
+                .collect::<Map<String, Value>>(),
+        ),
+        _ => Value::Null,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Base45Decoder;
@@ -69,4 +205,59 @@ mod tests {
         let result = decoder.crack("QED8WEX0", &get_checker(), &crate::config::Config::default());
         assert_eq!(result.unencrypted_text.unwrap()[0], "ietf!");
     }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn base45_health_certificate() {
+        use ciborium::Value as Cbor;
+        use flate2::{write::ZlibEncoder, Compression};
+        use std::io::Write;
+
+        let certificate = Cbor::Map(vec![
+            (Cbor::Text("ver".into()), Cbor::Text("1.3.0".into())),
+            (
+                Cbor::Text("nam".into()),
+                Cbor::Map(vec![
+                    (Cbor::Text("fn".into()), Cbor::Text("Musterfrau".into())),
+                    (Cbor::Text("gn".into()), Cbor::Text("Erika".into())),
+                ]),
+            ),
+            (Cbor::Text("dob".into()), Cbor::Text("1964-08-12".into())),
+        ]);
+        let claims = Cbor::Map(vec![
+            (Cbor::Integer(1.into()), Cbor::Text("DE".into())),
+            (Cbor::Integer(4.into()), Cbor::Integer(1_700_000_000.into())),
+            (Cbor::Integer(6.into()), Cbor::Integer(1_600_000_000.into())),
+            (
+                Cbor::Integer((-260).into()),
+                Cbor::Map(vec![(Cbor::Integer(1.into()), certificate)]),
+            ),
+        ]);
+        let mut payload = Vec::new();
+        ciborium::into_writer(&claims, &mut payload).unwrap();
+        let message = Cbor::Tag(
+            18,
+            Box::new(Cbor::Array(vec![
+                Cbor::Bytes(vec![0xa1, 0x01, 0x26]),
+                Cbor::Map(vec![]),
+                Cbor::Bytes(payload),
+                Cbor::Bytes(vec![0; 64]),
+            ])),
+        );
+        let mut cose = Vec::new();
+        ciborium::into_writer(&message, &mut cose).unwrap();
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(&cose).unwrap();
+        let qr = format!("HC1:{}", base45::encode(zlib.finish().unwrap()));
+
+        let decoder = Decoder::<Base45Decoder>::new();
+        let result = decoder.crack(&qr, &get_checker(), &crate::config::Config::default());
+        assert!(result.success);
+        let shown = result.unencrypted_text.unwrap()[0].to_string();
+        assert!(shown.starts_with("EU Digital COVID Certificate\nIssuer: DE\n"));
+        assert!(shown.contains("Issued: 2020-09-13 12:26:40 UTC"));
+        assert!(shown.contains("Expires: 2023-11-14 22:13:20 UTC"));
+        assert!(shown.contains("\"fn\": \"Musterfrau\""));
+        assert!(shown.contains("\"dob\": \"1964-08-12\""));
+    }
 }
@@ -6,9 +6,11 @@
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 use crate::decoders::interface::check_string_success;
+use crate::storage::artifacts;
 
 use super::crack_results::CrackResult;
-use super::interface::Crack;
+use super::interface::{Alphabet, Crack};
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -51,7 +53,7 @@ impl Crack for Decoder<Base58BitcoinDecoder> {
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Base58_bitcoin with text {:?}", text);
         let decoded_text = decode_base58_bitcoin_no_error_handling(text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_none() {
             debug!("Failed to decode base58_bitcoin because Base58BitcoinDecoder::decode_base58_bitcoin_no_error_handling returned None");
@@ -68,7 +70,7 @@ impl Crack for Decoder<Base58BitcoinDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -82,6 +84,10 @@ impl Crack for Decoder<Base58BitcoinDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -90,6 +96,17 @@ impl Crack for Decoder<Base58BitcoinDecoder> {
     fn get_link(&self) -> &str {
         self.link
     }
+    /// Only the characters of the alphabet, with no whitespace
+    fn alphabet(&self) -> Alphabet {
+        Alphabet::Only("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz")
+    }
+}
+
+impl Encode for Decoder<Base58BitcoinDecoder> {
+    /// Encodes with the Bitcoin Base58 alphabet
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(bs58::encode(text).into_string())
+    }
 }
 
 /// helper function
@@ -100,6 +117,7 @@ fn decode_base58_bitcoin_no_error_handling(text: &str) -> Option<String> {
         .with_alphabet(bs58::Alphabet::BITCOIN)
         .into_vec()
     {
+        artifacts::inspect("Base58 Bitcoin", &decoded_text);
         return Some(String::from_utf8_lossy(&decoded_text).to_string());
     }
     None
@@ -8,7 +8,7 @@ use crate::config::Config;
 use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
-use super::interface::Crack;
+use super::interface::{Alphabet, Crack};
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -51,7 +51,7 @@ impl Crack for Decoder<Base58FlickrDecoder> {
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Base58_flickr with text {:?}", text);
         let decoded_text = decode_base58_flickr_no_error_handling(text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_none() {
             debug!("Failed to decode base58_flickr because Base58FlickrDecoder::decode_base58_flickr_no_error_handling returned None");
@@ -68,7 +68,7 @@ impl Crack for Decoder<Base58FlickrDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -82,6 +82,10 @@ impl Crack for Decoder<Base58FlickrDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -90,6 +94,10 @@ impl Crack for Decoder<Base58FlickrDecoder> {
     fn get_link(&self) -> &str {
         self.link
     }
+    /// Only the characters of the alphabet, with no whitespace
+    fn alphabet(&self) -> Alphabet {
+        Alphabet::Only("123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ")
+    }
 }
 
 /// helper function
@@ -49,7 +49,7 @@ impl Crack for Decoder<Base62Decoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Base62 with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let decoded_text = decode_base62_no_error_handling(text);
 
         if decoded_text.is_none() {
@@ -67,7 +67,7 @@ impl Crack for Decoder<Base62Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results.update_checker(&checker_result);
 
         results
@@ -114,10 +114,7 @@ fn decode_base62_no_error_handling(text: &str) -> Option<String> {
     match base_x::decode(alphabet, text) {
         Ok(bytes) => {
              // Convert bytes to string
-             match String::from_utf8(bytes) {
-                 Ok(s) => Some(s),
-                 Err(_) => None,
-             }
+             String::from_utf8(bytes).ok()
         },
         Err(_) => None,
     }
@@ -3,13 +3,16 @@
 //! Call base64_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 use crate::decoders::interface::check_string_success;
+use crate::storage::artifacts;
 use base64::{engine::general_purpose, Engine as _};
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -52,7 +55,7 @@ impl Crack for Decoder<Base64Decoder> {
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Base64 with text {:?}", text);
 
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Determine which decoder to use based on the characters present
         let uses_standard_chars = text.contains('+') || text.contains('=') || text.contains('/');
@@ -70,7 +73,9 @@ impl Crack for Decoder<Base64Decoder> {
             return results;
         }
 
-        let decoded_text = decoded_text.unwrap();
+        // Bytes which aren't UTF-8, like XOR'd data, are handed on as they are
+        let candidate = Candidate::from_bytes(decoded_text.unwrap());
+        let decoded_text = candidate.text();
         if !check_string_success(&decoded_text, text) {
             info!(
                 "Failed to decode base64 because check_string_success returned false on string {}",
@@ -80,7 +85,7 @@ impl Crack for Decoder<Base64Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![candidate.clone()]);
         results.update_checker(&checker_result);
 
         results
@@ -108,8 +113,15 @@ impl Crack for Decoder<Base64Decoder> {
     }
 }
 
+impl Encode for Decoder<Base64Decoder> {
+    /// Encodes with the standard Base64 alphabet and padding
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(general_purpose::STANDARD.encode(text))
+    }
+}
+
 /// helper function for standard base64
-fn decode_base64_no_error_handling(text: &str) -> Option<String> {
+fn decode_base64_no_error_handling(text: &str) -> Option<Vec<u8>> {
     // Strip all padding
     let text = text.replace('=', "");
     // Runs the code to decode base64
@@ -117,11 +129,11 @@ fn decode_base64_no_error_handling(text: &str) -> Option<String> {
     general_purpose::STANDARD_NO_PAD
         .decode(text.as_bytes())
         .ok()
-        .map(|inner| String::from_utf8(inner).ok())?
+        .inspect(|inner| artifacts::inspect("Base64", inner))
 }
 
 /// helper function for url-safe base64
-fn decode_base64_url_no_error_handling(text: &str) -> Option<String> {
+fn decode_base64_url_no_error_handling(text: &str) -> Option<Vec<u8>> {
     // Strip all padding
     let text = text.replace('=', "");
 
@@ -129,7 +141,7 @@ fn decode_base64_url_no_error_handling(text: &str) -> Option<String> {
     general_purpose::URL_SAFE_NO_PAD
         .decode(text.as_bytes())
         .ok()
-        .map(|inner| String::from_utf8(inner).ok())?
+        .inspect(|inner| artifacts::inspect("Base64", inner))
 }
 
 #[cfg(test)]
@@ -231,7 +243,7 @@ mod tests {
                 result.unencrypted_text.is_some(),
                 "Failed to decode base64 layer"
             );
-            current = result.unencrypted_text.unwrap()[0].clone();
+            current = result.unencrypted_text.unwrap()[0].to_string();
             assert!(!current.is_empty(), "Decoded to empty string");
             assert!(current.is_ascii(), "Decoded to non-ASCII content");
         }
@@ -0,0 +1,111 @@
+//! Decode Base64 and decompress the gzip inside it in one step
+//! Performs error handling and returns a string
+//! Call base64_gzip_decoder.crack to use.
+//!
+//! gzip data always starts with the same three bytes, so its Base64 always
+//! starts with `H4sI`. Text starting like that is decoded and decompressed at
+//! once, rather than leaving the search to find the Base64 and the gzip
+//! separately, and the path shows both steps.
+
+use crate::byte_text::{bytes_to_text, Candidate};
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use base64::{engine::general_purpose, Engine as _};
+
+use super::base64_decoder::Base64Decoder;
+use super::crack_results::CrackResult;
+use super::gzip_decoder::{gunzip, GzipDecoder};
+use super::interface::Crack;
+use super::interface::Decoder;
+use log::trace;
+
+/// What the Base64 of every gzip member starts with
+const BASE64_GZIP_PREFIX: &str = "H4sI";
+
+/// The Base64 then gzip decoder, call:
+/// `let base64_gzip_decoder = Decoder::<Base64GzipDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = base64_gzip_decoder.crack(input)` to decode and decompress `H4sI...` text
+/// ```
+/// use ares::decoders::base64_gzip_decoder::Base64GzipDecoder;
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decoder = Decoder::<Base64GzipDecoder>::new();
+/// let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+///
+/// // "hello world", gzipped then Base64 encoded
+/// let result = decoder.crack("H4sIAAAAAAACA8tIzcnJVyjPL8pJAQCFEUoNCwAAAA==", &checker, &ares::config::Config::default());
+/// assert_eq!(result.unencrypted_text.unwrap()[0], "hello world");
+/// let steps: Vec<_> = result.inner_steps.iter().map(|step| step.decoder).collect();
+/// assert_eq!(steps, ["Base64", "Gzip"]);
+/// ```
+pub struct Base64GzipDecoder;
+
+impl Crack for Decoder<Base64GzipDecoder> {
+    fn new() -> Decoder<Base64GzipDecoder> {
+        Decoder {
+            name: "Base64 Gzip",
+            description: "gzip compressed data encoded in Base64, recognised by the H4sI it always starts with and decoded in one go.",
+            link: "https://en.wikipedia.org/wiki/Gzip",
+            tags: vec!["base64", "gzip", "compression", "decoder"],
+            popularity: 0.9,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Base64 Gzip with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let trimmed = text.trim();
+        if !trimmed.starts_with(BASE64_GZIP_PREFIX) {
+            return results;
+        }
+        // Base64 which was wrapped onto several lines
+        let base64: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+        let Some(compressed) = [
+            general_purpose::STANDARD,
+            general_purpose::STANDARD_NO_PAD,
+            general_purpose::URL_SAFE,
+            general_purpose::URL_SAFE_NO_PAD,
+        ]
+        .iter()
+        .find_map(|engine| engine.decode(&base64).ok()) else {
+            return results;
+        };
+        let Some(decompressed) = gunzip(&compressed) else {
+            return results;
+        };
+
+        let compressed_text = bytes_to_text(&compressed);
+        let candidate = Candidate::from_bytes(decompressed);
+        let checker_result = checker.check(&candidate.text(), config);
+
+        let mut base64_step = CrackResult::new(&Decoder::<Base64Decoder>::new(), text);
+        base64_step.unencrypted_text = Some(vec![Candidate::from_bytes(compressed)]);
+        let mut gzip_step = CrackResult::new(&Decoder::<GzipDecoder>::new(), compressed_text);
+        gzip_step.unencrypted_text = Some(vec![candidate.clone()]);
+        gzip_step.update_checker(&checker_result);
+
+        results.unencrypted_text = Some(vec![candidate]);
+        results.update_checker(&checker_result);
+        results.inner_steps = vec![base64_step, gzip_step];
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
@@ -53,7 +53,7 @@ impl Crack for Decoder<Base65536Decoder> {
         let decoded_text: Option<String> = decode_base65536_no_error_handling(text);
 
         trace!("Decoded text for base65536: {:?}", decoded_text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_none() {
             debug!("Failed to decode base65536 because Base65536Decoder::decode_base65536_no_error_handling returned None");
@@ -70,7 +70,7 @@ impl Crack for Decoder<Base65536Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -84,6 +84,10 @@ impl Crack for Decoder<Base65536Decoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
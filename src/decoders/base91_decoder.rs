@@ -6,6 +6,7 @@
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 use crate::decoders::interface::check_string_success;
+use crate::storage::artifacts;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
@@ -51,7 +52,7 @@ impl Crack for Decoder<Base91Decoder> {
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Base91 with text {:?}", text);
         let decoded_text = decode_base91_no_error_handling(text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_none() {
             debug!("Failed to decode base91 because Base91Decoder::decode_base91_no_error_handling returned None");
@@ -68,7 +69,7 @@ impl Crack for Decoder<Base91Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -82,6 +83,10 @@ impl Crack for Decoder<Base91Decoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -97,6 +102,7 @@ fn decode_base91_no_error_handling(text: &str) -> Option<String> {
     // Runs the code to decode base91
     // Doesn't perform error handling, call from_base91
     let decoded_text = base91::slice_decode(text.as_bytes());
+    artifacts::inspect("Base91", &decoded_text);
     Some(String::from_utf8_lossy(&decoded_text).to_string())
 }
 
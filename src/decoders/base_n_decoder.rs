@@ -0,0 +1,195 @@
+//! Decode a big number written in any base from 2 to 36
+//! Performs error handling and returns a string
+//! Call base_n_decoder.crack to use.
+//!
+//! Some encoders turn the whole message into one big integer and print it in
+//! an unusual base, like Python's `int.from_bytes` followed by a base 7 or
+//! base 36 conversion. This tries every base the digits allow and keeps the
+//! numbers whose bytes read as text. The result's key says which base it was.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::trace;
+use num::BigUint;
+
+/// Bases which have their own decoder: binary, octal and hexadecimal
+const DEDICATED_BASES: [u32; 3] = [2, 8, 16];
+
+/// Numbers shorter than this are too easily something else
+const MIN_DIGITS: usize = 6;
+
+/// Numbers longer than this take too long to try in every base
+const MAX_DIGITS: usize = 4096;
+
+/// Messages shorter than this, in bytes, are too often a fluke
+const MIN_MESSAGE_LEN: usize = 4;
+
+/// The Base-N decoder, call:
+/// `let base_n_decoder = Decoder::<BaseNDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = base_n_decoder.crack(input)` to decode a number in any base
+/// ```
+/// use ares::decoders::base_n_decoder::BaseNDecoder;
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decoder = Decoder::<BaseNDecoder>::new();
+/// let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+///
+/// let result = decoder.crack("fuvrsivvnfrbjwajo", &checker, &ares::config::Config::default());
+/// assert_eq!(result.unencrypted_text.unwrap()[0], "hello world");
+/// assert_eq!(result.key.as_deref(), Some("base 36"));
+/// ```
+pub struct BaseNDecoder;
+
+impl Crack for Decoder<BaseNDecoder> {
+    fn new() -> Decoder<BaseNDecoder> {
+        Decoder {
+            name: "Base-N",
+            description: "Reads the text as one big number in a base from 2 to 36 and turns the number into bytes, catching unusual bases no other decoder covers.",
+            link: "https://en.wikipedia.org/wiki/Radix",
+            tags: vec!["base", "radix", "decoder", "bruteforce"],
+            popularity: 0.2,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Base-N with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let Some(min_base) = min_base(text.trim()) else {
+            return results;
+        };
+        let readings: Vec<(u32, String)> = (min_base..=36)
+            .filter(|base| !DEDICATED_BASES.contains(base))
+            .filter_map(|base| Some((base, decode_base_n(text.trim(), base)?)))
+            .filter(|(_, decoded)| check_string_success(decoded, text))
+            .collect();
+        for (base, decoded) in &readings {
+            let checker_result = checker.check(decoded, config);
+            if checker_result.is_identified {
+                results.unencrypted_text = Some(vec![decoded.clone().into()]);
+                results.key = Some(format!("base {base}"));
+                results.update_checker(&checker_result);
+                return results;
+            }
+        }
+
+        // No base was recognised, but any may be encoded again
+        if !readings.is_empty() {
+            results.unencrypted_text = Some(
+                readings
+                    .into_iter()
+                    .map(|(_, decoded)| decoded.into())
+                    .collect(),
+            );
+        }
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The smallest base `text` can be a number in. Letters must be all one
+/// case, as a mix of both is some other encoding, like Base62 or Base64.
+fn min_base(text: &str) -> Option<u32> {
+    if !(MIN_DIGITS..=MAX_DIGITS).contains(&text.len())
+        || (text.bytes().any(|byte| byte.is_ascii_lowercase())
+            && text.bytes().any(|byte| byte.is_ascii_uppercase()))
+    {
+        return None;
+    }
+    let largest_digit = text
+        .chars()
+        .map(|c| c.to_digit(36))
+        .try_fold(0, |largest, digit| Some(largest.max(digit?)))?;
+    Some((largest_digit + 1).max(2))
+}
+
+/// The number's bytes as text, if they are a message: printable UTF-8,
+/// mostly letters, digits and spaces
+fn decode_base_n(text: &str, base: u32) -> Option<String> {
+    let bytes = BigUint::parse_bytes(text.as_bytes(), base)?.to_bytes_be();
+    if bytes.len() < MIN_MESSAGE_LEN {
+        return None;
+    }
+    let decoded = String::from_utf8(bytes).ok()?;
+    if decoded
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+    {
+        return None;
+    }
+    let wordy = decoded
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .count();
+    (wordy * 4 >= decoded.chars().count() * 3).then_some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn base_n_finds_the_base() {
+        let decoder = Decoder::<BaseNDecoder>::new();
+        let config = Config::default();
+        let result = decoder.crack(
+            "2340509926146499719798124187838309",
+            &get_checker(),
+            &config,
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "secret message");
+        assert_eq!(result.key.as_deref(), Some("base 10"));
+        let result = decoder.crack(
+            "234233113211442030020034430114330011102001301100",
+            &get_checker(),
+            &config,
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "attack at dawn");
+        assert_eq!(result.key.as_deref(), Some("base 5"));
+    }
+
+    #[test]
+    fn base_n_filters_noise() {
+        assert_eq!(min_base("HelloWorld"), None);
+        assert_eq!(min_base("12345"), None);
+        assert_eq!(min_base("zz top!"), None);
+        assert_eq!(min_base("1010110"), Some(2));
+        // 123456789 is 0x075bcd15, which isn't text
+        assert_eq!(decode_base_n("123456789", 10), None);
+    }
+}
@@ -1,8 +1,10 @@
 //! Decode Beaufort Cipher
 //! Performs error handling and returns a string
 
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
+use crate::text_info::TextInfo;
 use crate::decoders::crack_results::CrackResult;
 use crate::decoders::interface::Crack;
 use crate::decoders::interface::Decoder;
@@ -50,11 +52,48 @@ impl Crack for Decoder<BeaufortDecoder> {
     }
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        self.crack_with_info(text, &TextInfo::new(text), checker, config)
+    }
+
+    /// The letters of the candidate are only picked out once, for all decoders
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        self.crack_with_info(&candidate.text(), candidate.info(), checker, config)
+    }
+
+    fn get_tags(&self) -> &Vec<&str> { &self.tags }
+    fn get_name(&self) -> &str { self.name }
+    fn get_popularity(&self) -> f32 { self.popularity }
+    /// Finds the key length, then each letter of the key
+    fn expected_cost(&self) -> f32 { 50.0 }
+    fn get_description(&self) -> &str { self.description }
+    fn get_link(&self) -> &str { self.link }
+    /// Decodes with a known alphabetic keyword
+    fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("Beaufort key must be alphabetic, got '{}'", key));
+        }
+        Ok(decrypt_beaufort(text, key))
+    }
+}
+
+impl Decoder<BeaufortDecoder> {
+    /// Cracks `text`, given what has been worked out about it
+    fn crack_with_info(
+        &self,
+        text: &str,
+        info: &TextInfo,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
         trace!("Trying Beaufort Cipher with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
-        let clean_text: String = text.chars().filter(|c| c.is_ascii_alphabetic()).collect();
-        if clean_text.is_empty() { return results; }
+        if info.letters.is_empty() { return results; }
 
         // Use default sensitivity
         let checker_with_sensitivity = checker;
@@ -66,7 +105,7 @@ impl Crack for Decoder<BeaufortDecoder> {
              let decoded = decrypt_beaufort(text, &key);
              let check_res = checker_with_sensitivity.check(&decoded, config);
              if check_res.is_identified {
-                 results.unencrypted_text = Some(vec![decoded]);
+                 results.unencrypted_text = Some(vec![decoded.into()]);
                  results.key = Some(key);
                  results.update_checker(&check_res);
                  return results;
@@ -75,12 +114,6 @@ impl Crack for Decoder<BeaufortDecoder> {
 
         results
     }
-
-    fn get_tags(&self) -> &Vec<&str> { &self.tags }
-    fn get_name(&self) -> &str { self.name }
-    fn get_popularity(&self) -> f32 { self.popularity }
-    fn get_description(&self) -> &str { self.description }
-    fn get_link(&self) -> &str { self.link }
 }
 
 // Beaufort Decryption: M = (K - C) mod 26
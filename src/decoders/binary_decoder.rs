@@ -4,6 +4,7 @@ use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -28,7 +29,7 @@ impl Crack for Decoder<BinaryDecoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying binary with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let mut decoded_strings = Vec::new();
 
         for shift in 1..25 {
@@ -47,12 +48,12 @@ impl Crack for Decoder<BinaryDecoder> {
             // If checkers return true, exit early with the correct result
             if checker_result.is_identified {
                 info!("Found a match with binary bit {}", shift);
-                results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string()]);
+                results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string().into()]);
                 results.update_checker(&checker_result);
                 return results;
             }
         }
-        results.unencrypted_text = Some(decoded_strings);
+        results.unencrypted_text = Some(decoded_strings.into_iter().map(Into::into).collect());
         results
     }
     /// Gets all tags for this decoder
@@ -63,6 +64,10 @@ impl Crack for Decoder<BinaryDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -73,6 +78,21 @@ impl Crack for Decoder<BinaryDecoder> {
     }
 }
 
+impl Encode for Decoder<BinaryDecoder> {
+    /// Encodes each byte as 8 bits, separated by spaces.
+    /// Only ASCII is supported as the decoder turns each byte into one character.
+    fn encode(&self, text: &str) -> Result<String, String> {
+        if !text.is_ascii() {
+            return Err("Binary can only encode ASCII text".to_string());
+        }
+        Ok(text
+            .bytes()
+            .map(|byte| format!("{:08b}", byte))
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+}
+
 /// Decodes binary to string
 /// bit is the byte length
 fn binary_to_string(binary: &str, bit: u8) -> String {
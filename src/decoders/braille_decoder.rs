@@ -24,7 +24,7 @@ impl Crack for Decoder<BrailleDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying braille with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if text.is_empty() {
             return results;
@@ -40,12 +40,12 @@ impl Crack for Decoder<BrailleDecoder> {
         let checker_result = checker.check(&decoded_text, config);
         if checker_result.is_identified {
             trace!("Found a match with braille");
-            results.unencrypted_text = Some(vec![decoded_text]);
+            results.unencrypted_text = Some(vec![decoded_text.into()]);
             results.update_checker(&checker_result);
             return results;
         }
 
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results
     }
 
@@ -57,6 +57,10 @@ impl Crack for Decoder<BrailleDecoder> {
         self.name
     }
 
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -150,7 +154,7 @@ mod tests {
         let result = braille_decoder.crack(test_string, &get_athena_checker(), &crate::config::Config::default());
 
         assert!(result.unencrypted_text.is_some());
-        assert_eq!(result.unencrypted_text.unwrap()[0].to_lowercase(), expected);
+        assert_eq!(result.unencrypted_text.unwrap()[0].text().to_lowercase(), expected);
     }
 
     #[test]
@@ -49,7 +49,7 @@ impl Crack for Decoder<BrainfuckInterpreter> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying brainfuck with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Guard against text that realistically won't be a Brainfuck program
         if text.contains(',') {
@@ -67,7 +67,7 @@ impl Crack for Decoder<BrainfuckInterpreter> {
             Ok(_) => {
                 let decoded_text = String::from_utf8(buf).unwrap_or_default();
                 let checker_result = checker.check(&decoded_text, config);
-                results.unencrypted_text = Some(vec![decoded_text]);
+                results.unencrypted_text = Some(vec![decoded_text.into()]);
                 results.update_checker(&checker_result);
 
                 results
@@ -86,6 +86,10 @@ impl Crack for Decoder<BrainfuckInterpreter> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
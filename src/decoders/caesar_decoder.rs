@@ -3,18 +3,189 @@
 //! Call caesar_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 //! Uses Low sensitivity for gibberish detection.
+//!
+//! As well as A-Z, letters are shifted around the Russian Cyrillic and the
+//! Greek alphabets, and around `Config.custom_alphabet` if one is set. No
+//! checker knows Russian or Greek, so their shifts are tried in order of how
+//! well the letter frequencies fit, and a shift whose fit is clearly that of
+//! plaintext is accepted on that alone.
+//!
+//! If no shift is recognised, all of them are returned, those which read most
+//! like plaintext first, so the search carries on from the most promising.
 
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
+use crate::cryptanalysis::candidate_score;
 use crate::decoders::interface::check_string_success;
 use gibberish_or_not::Sensitivity;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{info, trace};
 
+/// The Russian alphabet, with Ё in its usual place
+const CYRILLIC: &str = "абвгдеёжзийклмнопрстуфхцчшщъыьэюя";
+
+/// How often each letter of [`CYRILLIC`] appears in Russian, in percent
+const CYRILLIC_FREQS: [f64; 33] = [
+    8.01, 1.59, 4.54, 1.70, 2.98, 8.45, 0.04, 0.94, 1.65, 7.35, 1.21, 3.49, 4.40, 3.21, 6.70,
+    10.97, 2.81, 4.73, 5.47, 6.26, 2.62, 0.26, 0.97, 0.48, 1.44, 0.73, 0.36, 0.04, 1.90, 1.74,
+    0.32, 0.64, 2.01,
+];
+
+/// The Greek alphabet. Final sigma and accented vowels are left as they are.
+const GREEK: &str = "αβγδεζηθικλμνξοπρστυφχψω";
+
+/// How often each letter of [`GREEK`] appears in Greek, in percent
+const GREEK_FREQS: [f64; 24] = [
+    12.1, 0.8, 2.0, 1.8, 8.0, 0.4, 5.2, 1.3, 9.2, 4.2, 2.9, 3.4, 6.6, 0.6, 9.8, 4.4, 4.8, 7.6,
+    8.5, 4.1, 0.8, 1.2, 0.1, 1.9,
+];
+
+/// Texts need at least this many letters of an alphabet for their letter
+/// frequencies to say whether they are plaintext
+const MIN_FREQUENCY_LETTERS: usize = 20;
+
+/// The most a shift's chi-squared statistic per letter may be for it to be
+/// accepted as plaintext by its letter frequencies
+const MAX_CHI_SQUARED_PER_LETTER: f64 = 1.5;
+
+/// How many times worse the next best shift's chi-squared statistic must be
+/// for the best shift to be accepted by its letter frequencies
+const MIN_RUNNER_UP_RATIO: f64 = 2.0;
+
+/// Name of the check accepting Cyrillic and Greek shifts by letter frequencies
+const FREQUENCY_CHECKER_NAME: &str = "Letter Frequency Checker";
+
+/// Description of the check accepting shifts by letter frequencies
+const FREQUENCY_CHECKER_DESCRIPTION: &str =
+    "The letters occur about as often as in plaintext of the alphabet's language";
+
+/// An alphabet other than A-Z which letters can be shifted around
+struct Alphabet {
+    /// Shown in the key, e.g. "Cyrillic". Custom alphabets are named by
+    /// their letters, so their keys can be decoded with again.
+    name: String,
+    /// The letters in order. Lowercase if `cased`.
+    letters: Vec<char>,
+    /// Whether uppercase letters are shifted like their lowercase forms
+    cased: bool,
+    /// How often each letter appears in plaintext, if known
+    freqs: Option<&'static [f64]>,
+}
+
+impl Alphabet {
+    /// The position of `c` in the alphabet, and whether it was uppercase
+    fn position(&self, c: char) -> Option<(usize, bool)> {
+        if let Some(i) = self.letters.iter().position(|&l| l == c) {
+            return Some((i, false));
+        }
+        if !self.cased {
+            return None;
+        }
+        let mut lower = c.to_lowercase();
+        let (Some(lower), None) = (lower.next(), lower.next()) else {
+            return None;
+        };
+        self.letters
+            .iter()
+            .position(|&l| l == lower)
+            .map(|i| (i, true))
+    }
+
+    /// Shifts every letter of the alphabet in `text` back by `shift`
+    fn shift_back(&self, text: &str, shift: usize) -> String {
+        let len = self.letters.len();
+        text.chars()
+            .map(|c| match self.position(c) {
+                Some((i, upper)) => {
+                    let shifted = self.letters[(i + len - shift % len) % len];
+                    if upper {
+                        shifted.to_uppercase().next().unwrap_or(shifted)
+                    } else {
+                        shifted
+                    }
+                }
+                None => c,
+            })
+            .collect()
+    }
+
+    /// How many letters of `text` are in the alphabet
+    fn count_in(&self, text: &str) -> usize {
+        text.chars().filter(|&c| self.position(c).is_some()).count()
+    }
+
+    /// Pearson's chi-squared statistic of the letter counts of `text`
+    /// against the alphabet's letter frequencies
+    fn chi_squared(&self, text: &str, freqs: &[f64]) -> f64 {
+        let mut counts = vec![0usize; self.letters.len()];
+        for c in text.chars() {
+            if let Some((i, _)) = self.position(c) {
+                counts[i] += 1;
+            }
+        }
+        let total: usize = counts.iter().sum();
+        let freq_total: f64 = freqs.iter().sum();
+        counts
+            .iter()
+            .zip(freqs)
+            .map(|(&observed, &freq)| {
+                let expected = total as f64 * freq / freq_total;
+                (observed as f64 - expected).powi(2) / expected
+            })
+            .sum()
+    }
+}
+
+/// The Cyrillic and Greek alphabets
+fn builtin_alphabets() -> Vec<Alphabet> {
+    vec![
+        Alphabet {
+            name: "Cyrillic".to_string(),
+            letters: CYRILLIC.chars().collect(),
+            cased: true,
+            freqs: Some(&CYRILLIC_FREQS),
+        },
+        Alphabet {
+            name: "Greek".to_string(),
+            letters: GREEK.chars().collect(),
+            cased: true,
+            freqs: Some(&GREEK_FREQS),
+        },
+    ]
+}
+
+/// An alphabet of the given letters in order, case-sensitive. Repeated
+/// letters are dropped. None if fewer than two letters are left.
+fn custom_alphabet(custom: &str) -> Option<Alphabet> {
+    let mut letters: Vec<char> = Vec::new();
+    for c in custom.chars() {
+        if !letters.contains(&c) {
+            letters.push(c);
+        }
+    }
+    (letters.len() >= 2).then(|| Alphabet {
+        name: letters.iter().collect(),
+        letters,
+        cased: false,
+        freqs: None,
+    })
+}
+
+/// The alphabets besides A-Z with letters in `text`
+fn alphabets_in(text: &str, config: &Config) -> Vec<Alphabet> {
+    let mut alphabets = builtin_alphabets();
+    if let Some(custom) = config.custom_alphabet.as_deref().and_then(custom_alphabet) {
+        alphabets.push(custom);
+    }
+    alphabets.retain(|alphabet| alphabet.count_in(text) > 0);
+    alphabets
+}
+
 /// The caesar decoder, call:
 /// `let caesar_decoder = Decoder::<caesarDecoder>::new()` to create a new instance
 /// And then call:
@@ -32,7 +203,7 @@ use log::{info, trace};
 /// let result = decode_caesar.crack("uryyb guvf vf ybat grkg", &checker, &ares::config::Config::default()).unencrypted_text;
 /// assert!(result.is_some());
 /// // If it succeeds, the 0th element is the plaintext else it'll contain 25 elements
-/// // of unsuccessfully decoded text
+/// // of unsuccessfully decoded text, the most plaintext-like first
 /// assert_eq!(result.unwrap()[0], "hello this is long text");
 /// ```
 pub struct CaesarDecoder;
@@ -54,13 +225,14 @@ impl Crack for Decoder<CaesarDecoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Caesar Cipher with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let mut decoded_strings = Vec::new();
 
         // Use the checker with Low sensitivity for Caesar cipher
         let checker_with_sensitivity = checker.with_sensitivity(Sensitivity::Low);
 
-        for shift in 1..=25 {
+        let has_latin = text.chars().any(|c| c.is_ascii_alphabetic());
+        for shift in (1..=25).filter(|_| has_latin) {
             let decoded_text = caesar(text, shift);
             decoded_strings.push(decoded_text);
             let borrowed_decoded_text = &decoded_strings[decoded_strings.len() - 1];
@@ -75,13 +247,62 @@ impl Crack for Decoder<CaesarDecoder> {
             // If checkers return true, exit early with the correct result
             if checker_result.is_identified {
                 trace!("Found a match with caesar shift {}", shift);
-                results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string()]);
+                results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string().into()]);
                 results.update_checker(&checker_result);
                 results.key = Some(shift.to_string());
                 return results;
             }
         }
-        results.unencrypted_text = Some(decoded_strings);
+
+        for alphabet in alphabets_in(text, config) {
+            let mut shifts: Vec<(usize, String, f64)> = (1..alphabet.letters.len())
+                .map(|shift| {
+                    let decoded = alphabet.shift_back(text, shift);
+                    let fit = alphabet
+                        .freqs
+                        .map_or(0.0, |freqs| alphabet.chi_squared(&decoded, freqs));
+                    (shift, decoded, fit)
+                })
+                .collect();
+            // Best fitting first, so they are checked first
+            shifts.sort_by(|a, b| a.2.total_cmp(&b.2));
+            let letters = alphabet.count_in(text);
+            let runner_up = shifts.get(1).map_or(f64::INFINITY, |shift| shift.2);
+            for (rank, (shift, decoded, fit)) in shifts.iter().enumerate() {
+                let key = format!("{shift} ({})", alphabet.name);
+                let checker_result = checker_with_sensitivity.check(decoded, config);
+                if checker_result.is_identified {
+                    trace!("Found a match with caesar shift {}", key);
+                    results.unencrypted_text = Some(vec![decoded.clone().into()]);
+                    results.update_checker(&checker_result);
+                    results.key = Some(key);
+                    return results;
+                }
+                let fits = alphabet.freqs.is_some()
+                    && rank == 0
+                    && letters >= MIN_FREQUENCY_LETTERS
+                    && *fit <= MAX_CHI_SQUARED_PER_LETTER * letters as f64
+                    && *fit * MIN_RUNNER_UP_RATIO <= runner_up;
+                if fits {
+                    trace!("Letter frequencies fit caesar shift {}", key);
+                    results.unencrypted_text = Some(vec![decoded.clone().into()]);
+                    results.checker_name = FREQUENCY_CHECKER_NAME;
+                    results.checker_description = FREQUENCY_CHECKER_DESCRIPTION;
+                    results.success = true;
+                    results.key = Some(key);
+                    return results;
+                }
+            }
+            decoded_strings.extend(shifts.into_iter().map(|(_, decoded, _)| decoded));
+        }
+
+        // Shifts which read most like plaintext in one of `config.languages`
+        // come first, and the search follows only the best few
+        let scored = decoded_strings
+            .into_iter()
+            .map(|decoded| (candidate_score(&decoded, &config.languages), decoded.into()))
+            .collect();
+        results.set_ranked_candidates(scored);
         results
     }
     /// Gets all tags for this decoder
@@ -92,6 +313,14 @@ impl Crack for Decoder<CaesarDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    /// Tries 25 shifts and scores the ones it doesn't recognise
+    fn expected_cost(&self) -> f32 {
+        5.0
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -100,6 +329,60 @@ impl Crack for Decoder<CaesarDecoder> {
     fn get_link(&self) -> &str {
         self.link
     }
+    /// Decodes with a known shift between 0 and 25, or a shift around
+    /// another alphabet like "5 (Cyrillic)" or "5 (0123456789)"
+    fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        if let Some(alphabet_key) = parse_alphabet_key(key) {
+            let (alphabet, shift) = alphabet_key?;
+            return Ok(alphabet.shift_back(text, shift));
+        }
+        match key.trim().parse::<u8>() {
+            Ok(shift) if shift < 26 => Ok(caesar(text, shift)),
+            _ => Err(format!(
+                "Caesar key must be a shift between 0 and 25, got '{}'",
+                key
+            )),
+        }
+    }
+}
+
+impl Encode for Decoder<CaesarDecoder> {
+    /// Caesar needs a shift, see `encode_with_key`
+    fn encode(&self, _text: &str) -> Result<String, String> {
+        Err("caesar needs a key, e.g. `--key 13` for ROT13".to_string())
+    }
+    /// Shifts every letter forward by the key, so `--key 13` is ROT13
+    fn encode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        if let Some(alphabet_key) = parse_alphabet_key(key) {
+            let (alphabet, shift) = alphabet_key?;
+            let len = alphabet.letters.len();
+            return Ok(alphabet.shift_back(text, len - shift % len));
+        }
+        match key.trim().parse::<u8>() {
+            Ok(shift) if shift < 26 => Ok(caesar(text, (26 - shift) % 26)),
+            _ => Err(format!(
+                "Caesar key must be a shift between 0 and 25, got '{}'",
+                key
+            )),
+        }
+    }
+}
+
+/// Parses keys like "5 (Cyrillic)" or "5 (0123456789)" into the alphabet
+/// and the shift. None if the key doesn't name an alphabet.
+fn parse_alphabet_key(key: &str) -> Option<Result<(Alphabet, usize), String>> {
+    let (shift, name) = key.trim_start().strip_suffix(')')?.split_once(" (")?;
+    let alphabet = builtin_alphabets()
+        .into_iter()
+        .find(|alphabet| alphabet.name == name)
+        .or_else(|| custom_alphabet(name));
+    let parsed = alphabet.zip(shift.trim().parse::<usize>().ok());
+    Some(parsed.ok_or_else(|| {
+        format!(
+            "Caesar key must be a shift like '5', '5 (Cyrillic)', '5 (Greek)' or '5 (<alphabet>)', got '{}'",
+            key
+        )
+    }))
 }
 
 /// Caesar cipher to rotate cipher text by shift and return an owned String.
@@ -126,6 +409,7 @@ mod tests {
         checkers::{
             athena::Athena,
             checker_type::{Check, Checker},
+            coordinates::CoordinatesChecker,
             english::EnglishChecker,
             CheckerTypes,
         },
@@ -296,4 +580,93 @@ mod tests {
         // The test passes if we reach this point, as we're verifying the code structure
         // rather than specific behavior that might be affected by the gibberish detection
     }
+
+    #[test]
+    fn unrecognised_shifts_are_ranked() {
+        let caesar_decoder = Decoder::<CaesarDecoder>::new();
+        // The Coordinates checker never recognises a sentence, so every
+        // shift comes back
+        let checker = CheckerTypes::CheckCoordinates(Checker::<CoordinatesChecker>::new());
+        let result = caesar_decoder.crack(
+            "Wkh vhfuhw phhwlqj lv dw wkh rog ploo",
+            &checker,
+            &Config::default(),
+        );
+        assert!(!result.success);
+        let decoded = result.unencrypted_text.unwrap();
+        assert_eq!(decoded.len(), 25);
+        assert_eq!(decoded[0], "The secret meeting is at the old mill");
+        let scores = result.candidate_scores.unwrap();
+        assert_eq!(scores.len(), 25);
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn caesar_decode_with_key() {
+        let caesar_decoder = Decoder::<CaesarDecoder>::new();
+        assert_eq!(caesar_decoder.decode_with_key("uryyb", "13").unwrap(), "hello");
+        assert!(caesar_decoder.decode_with_key("uryyb", "26").is_err());
+        assert!(caesar_decoder.decode_with_key("uryyb", "abc").is_err());
+    }
+
+    #[test]
+    fn cracks_shifted_russian() {
+        let caesar_decoder = Decoder::<CaesarDecoder>::new();
+        let plaintext = "Широкая электрификация южных губерний даст мощный толчок подъёму сельского хозяйства.";
+        let cipher = caesar_decoder
+            .encode_with_key(plaintext, "3 (Cyrillic)")
+            .unwrap();
+        let result = caesar_decoder.crack(&cipher, &get_athena_checker(), &Config::default());
+        assert!(result.success);
+        assert_eq!(result.unencrypted_text.unwrap()[0], plaintext);
+        assert_eq!(result.key.as_deref(), Some("3 (Cyrillic)"));
+        assert_eq!(
+            caesar_decoder.decode_with_key(&cipher, "3 (Cyrillic)").unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn cracks_shifted_greek() {
+        let caesar_decoder = Decoder::<CaesarDecoder>::new();
+        let plaintext = "Όλοι οι άνθρωποι γεννιούνται ελεύθεροι και ίσοι στην αξιοπρέπεια και τα δικαιώματα. Είναι προικισμένοι με λογική και συνείδηση.";
+        let cipher = caesar_decoder.encode_with_key(plaintext, "7 (Greek)").unwrap();
+        let result = caesar_decoder.crack(&cipher, &get_athena_checker(), &Config::default());
+        assert!(result.success);
+        assert_eq!(result.unencrypted_text.unwrap()[0], plaintext);
+        assert_eq!(result.key.as_deref(), Some("7 (Greek)"));
+    }
+
+    #[test]
+    fn shifts_around_a_custom_alphabet() {
+        let caesar_decoder = Decoder::<CaesarDecoder>::new();
+        let config = Config {
+            custom_alphabet: Some("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 ".to_string()),
+            ..Config::default()
+        };
+        // "HELLO WORLD 2024" shifted by 5 around the alphabet above
+        let result = caesar_decoder.crack("MJQQTE1TWQIE7579", &get_athena_checker(), &config);
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "HELLO WORLD 2024"
+        );
+        let key = result.key.unwrap();
+        assert_eq!(key, "5 (ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 )");
+        assert_eq!(
+            caesar_decoder.decode_with_key("MJQQTE1TWQIE7579", &key).unwrap(),
+            "HELLO WORLD 2024"
+        );
+    }
+
+    #[test]
+    fn random_cyrillic_is_not_accepted() {
+        let caesar_decoder = Decoder::<CaesarDecoder>::new();
+        let result = caesar_decoder.crack(
+            "щфъжэ ёцшюх ъщэжф йцёыш ъэюфщ жёшцъ ыфэщю",
+            &get_athena_checker(),
+            &Config::default(),
+        );
+        assert!(!result.success);
+    }
 }
@@ -0,0 +1,222 @@
+//! Chess move cipher decoder
+//! Hides a message in the moves of a chess game, one character per move.
+//! Each move's destination square stands for a character of an 8x8 square
+//! holding A-Z, a-z, 0-9, space and full stop, read from a1 either rank by
+//! rank (a1 = A, b1 = B, ..., a2 = I) or file by file (a1 = A, a2 = B, ...).
+//! Castling has no destination square and stands for nothing.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{info, trace};
+
+/// The Chess Moves decoder
+/// Call: `let decoder = Decoder::<ChessDecoder>::new()` to create a new instance
+/// And then call: `result = decoder.crack(input)` to decode
+pub struct ChessDecoder;
+
+/// The characters of the board's 64 squares, from a1
+const BOARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789 .";
+
+/// Games with fewer moves than this are too short to hold a message
+const MIN_MOVES: usize = 4;
+
+impl Crack for Decoder<ChessDecoder> {
+    fn new() -> Decoder<ChessDecoder> {
+        Decoder {
+            name: "Chess Moves",
+            description: "Hides one character in each move of a chess game: the move's destination square picks a character from an 8x8 square of letters, digits, space and full stop.",
+            link: "https://en.wikipedia.org/wiki/Portable_Game_Notation",
+            tags: vec!["chess", "hobbyist", "substitution", "cipher"],
+            popularity: 0.3,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Chess Moves with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let Some(squares) = destination_squares(text) else {
+            info!("Failed to read chess moves");
+            return results;
+        };
+
+        let readings: Vec<String> = [true, false]
+            .iter()
+            .map(|by_rank| read_squares(&squares, *by_rank))
+            .filter(|decoded| check_string_success(decoded, text))
+            .collect();
+        for decoded in &readings {
+            let checker_result = checker.check(decoded, config);
+            if checker_result.is_identified {
+                results.unencrypted_text = Some(vec![decoded.clone().into()]);
+                results.update_checker(&checker_result);
+                return results;
+            }
+        }
+
+        // Neither reading was recognised, but either may be encoded again
+        if !readings.is_empty() {
+            results.unencrypted_text = Some(readings.into_iter().map(Into::into).collect());
+        }
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The destination squares of the moves in PGN movetext, as (file, rank)
+/// from 0, if the text is all moves. Tag pairs, comments, variations, move
+/// numbers, annotations and the result are skipped.
+fn destination_squares(text: &str) -> Option<Vec<(u8, u8)>> {
+    let mut movetext = String::with_capacity(text.len());
+    let mut nesting = 0usize;
+    for line in text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('['))
+    {
+        for c in line.chars() {
+            match c {
+                '{' | '(' => nesting += 1,
+                '}' | ')' => nesting = nesting.checked_sub(1)?,
+                _ if nesting == 0 => movetext.push(c),
+                _ => {}
+            }
+        }
+        movetext.push(' ');
+    }
+
+    let mut squares = Vec::new();
+    let mut moves = 0;
+    for token in movetext.split_whitespace() {
+        let token = token.trim_end_matches(['+', '#', '!', '?']);
+        if matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*") || token.starts_with('$') {
+            continue;
+        }
+        if matches!(token, "O-O" | "O-O-O" | "0-0" | "0-0-0") {
+            moves += 1;
+            continue;
+        }
+        // Move numbers may be glued to the move, as in "1.e4" or "3...Nf6"
+        let token = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+        if token.is_empty() {
+            continue;
+        }
+        squares.push(san_destination(token)?);
+        moves += 1;
+    }
+    (moves >= MIN_MOVES && !squares.is_empty()).then_some(squares)
+}
+
+/// The destination square of a move in standard algebraic notation, like
+/// `e4`, `Nbd7`, `exd5` or `e8=Q`, or in long algebraic notation, like `e2e4`
+fn san_destination(token: &str) -> Option<(u8, u8)> {
+    let token = match token.find('=') {
+        Some(promotion) => {
+            let piece = &token[promotion + 1..];
+            if !matches!(piece, "Q" | "R" | "B" | "N") {
+                return None;
+            }
+            &token[..promotion]
+        }
+        None => token,
+    };
+    let bytes = token.as_bytes();
+    let rest = match bytes.first()? {
+        b'K' | b'Q' | b'R' | b'B' | b'N' => &bytes[1..],
+        _ => bytes,
+    };
+    // What is left is an optional origin file, rank or square, an optional
+    // capture or dash, then the destination square
+    if !(2..=5).contains(&rest.len()) {
+        return None;
+    }
+    let (origin, destination) = rest.split_at(rest.len() - 2);
+    let origin = origin
+        .strip_suffix(b"x")
+        .or_else(|| origin.strip_suffix(b"-"))
+        .unwrap_or(origin);
+    let is_file = |byte: &u8| (b'a'..=b'h').contains(byte);
+    let is_rank = |byte: &u8| (b'1'..=b'8').contains(byte);
+    let origin_valid = match origin {
+        [] => true,
+        [one] => is_file(one) || is_rank(one),
+        [file, rank] => is_file(file) && is_rank(rank),
+        _ => false,
+    };
+    let (file, rank) = (destination[0], destination[1]);
+    (origin_valid && is_file(&file) && is_rank(&rank)).then(|| (file - b'a', rank - b'1'))
+}
+
+/// The characters the squares stand for, reading the board rank by rank or
+/// file by file
+fn read_squares(squares: &[(u8, u8)], by_rank: bool) -> String {
+    squares
+        .iter()
+        .map(|(file, rank)| {
+            let index = if by_rank {
+                rank * 8 + file
+            } else {
+                file * 8 + rank
+            };
+            char::from(BOARD[usize::from(index)])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn chess_moves_read_rank_by_rank() {
+        // "Hello world" rank by rank: H = h1, e = g4, l = f5, o = a6, space = g8, ...
+        let game = "[Event \"Cipher\"]\n\n1. h1 g4 2. f5 f5 {a comment} 3. Na6 g8 4. Qxa7 a6 5. Bd6 f5 6. f4 1-0";
+        let decoded = read_squares(&destination_squares(game).unwrap(), true);
+        assert_eq!(decoded, "Hello world");
+        let decoder = Decoder::<ChessDecoder>::new();
+        let result = decoder.crack(game, &get_checker(), &Config::default());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "Hello world");
+    }
+
+    #[test]
+    fn chess_moves_need_a_game() {
+        assert!(destination_squares("e4 e5").is_none());
+        assert!(destination_squares("the quick brown fox").is_none());
+        assert_eq!(san_destination("exd8=Q"), Some((3, 7)));
+        assert_eq!(san_destination("Nbd7"), Some((3, 6)));
+        assert_eq!(san_destination("e2e4"), Some((4, 3)));
+        assert_eq!(san_destination("Ke9"), None);
+    }
+}
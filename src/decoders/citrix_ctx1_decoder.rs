@@ -43,7 +43,7 @@ impl Crack for Decoder<CitrixCTX1Decoder> {
         trace!("Trying citrix_ctx1 with text {:?}", text);
         let decoded_text: Result<String, Error> = decode_citrix_ctx1(text);
 
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_err() {
             debug!("Failed to decode citrix_ctx1: {:?}", decoded_text);
@@ -62,7 +62,7 @@ impl Crack for Decoder<CitrixCTX1Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -76,6 +76,10 @@ impl Crack for Decoder<CitrixCTX1Decoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -88,7 +92,7 @@ impl Crack for Decoder<CitrixCTX1Decoder> {
 
 /// Decodes Citrix CTX1
 fn decode_citrix_ctx1(text: &str) -> Result<String, Error> {
-    if text.len() % 4 != 0 {
+    if !text.len().is_multiple_of(4) {
         return Err(Error::InvalidLength);
     }
 
@@ -1,10 +1,15 @@
 //! Columnar Transposition cipher decoder
 //! A transposition cipher that writes the plaintext out in rows of a fixed length,
 //! and then reads it out column by column, with the columns chosen in some scrambled order.
-//! This implementation tries common column lengths to crack the cipher.
+//! This implementation tries common column lengths to crack the cipher, first
+//! reading the columns in order and then searching for the keyed order.
 
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
+use crate::text_info::TextInfo;
+use crate::cryptanalysis::transposition::solve_columnar;
+use crate::cryptanalysis::{is_likely_language, languages_or_english, seeded_rng, HillClimber};
 use crate::decoders::interface::check_string_success;
 use gibberish_or_not::Sensitivity;
 
@@ -14,6 +19,14 @@ use super::interface::Decoder;
 
 use log::{info, trace};
 
+/// The most columns a keyed order is searched for, as longer keys need more
+/// text than is usually given to tell the right order from the rest
+const MAX_KEYED_COLUMNS: usize = 12;
+
+/// Keyed orders are only searched for with at least this many letters,
+/// below which too many orders read like text
+const MIN_KEYED_LETTERS: usize = 30;
+
 /// The Columnar Transposition decoder
 pub struct ColumnarTranspositionDecoder;
 
@@ -30,11 +43,59 @@ impl Crack for Decoder<ColumnarTranspositionDecoder> {
     }
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        self.crack_with_info(text, &TextInfo::new(text), checker, config)
+    }
+
+    /// The letters of the candidate are only picked out once, for all decoders
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        self.crack_with_info(&candidate.text(), candidate.info(), checker, config)
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    /// Tries the orders of the columns for each key length
+    fn expected_cost(&self) -> f32 {
+        30.0
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+impl Decoder<ColumnarTranspositionDecoder> {
+    /// Cracks `text`, given what has been worked out about it
+    fn crack_with_info(
+        &self,
+        text: &str,
+        info: &TextInfo,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
         trace!("Trying Columnar Transposition cipher with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Only process alphabetic characters (preserve for checking)
-        let clean_text: String = text.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+        let clean_text = &info.letters;
         
         if clean_text.is_empty() {
             info!("No valid characters found for Columnar Transposition");
@@ -48,11 +109,11 @@ impl Crack for Decoder<ColumnarTranspositionDecoder> {
         
         for num_cols in 2..=max_cols {
             // Try simple columnar (reading columns in order)
-            if let Some(decoded) = decode_columnar(&clean_text, num_cols) {
+            if let Some(decoded) = decode_columnar(clean_text, num_cols) {
                 if check_string_success(&decoded, text) {
                     let checker_result = checker_with_sensitivity.check(&decoded, config);
                     if checker_result.is_identified {
-                        results.unencrypted_text = Some(vec![decoded]);
+                        results.unencrypted_text = Some(vec![decoded.into()]);
                         results.update_checker(&checker_result);
                         results.key = Some(num_cols.to_string());
                         return results;
@@ -61,11 +122,11 @@ impl Crack for Decoder<ColumnarTranspositionDecoder> {
             }
             
             // Try reverse columnar (reading columns in reverse)
-            if let Some(decoded) = decode_columnar_reverse(&clean_text, num_cols) {
+            if let Some(decoded) = decode_columnar_reverse(clean_text, num_cols) {
                 if check_string_success(&decoded, text) {
                     let checker_result = checker_with_sensitivity.check(&decoded, config);
                     if checker_result.is_identified {
-                        results.unencrypted_text = Some(vec![decoded]);
+                        results.unencrypted_text = Some(vec![decoded.into()]);
                         results.update_checker(&checker_result);
                         results.key = Some(format!("{} (reverse)", num_cols));
                         return results;
@@ -73,26 +134,36 @@ impl Crack for Decoder<ColumnarTranspositionDecoder> {
                 }
             }
         }
+        
+        // Search for the keyed order of the columns
+        if clean_text.len() >= MIN_KEYED_LETTERS {
+            let climber = HillClimber::tabu(100, 10).with_deadline(config.deadline.clone());
+            let mut rng = seeded_rng(config.seed);
+            for num_cols in 3..=max_cols.min(MAX_KEYED_COLUMNS) {
+                for language in languages_or_english(&config.languages) {
+                    let Some((key, plaintext, _)) = solve_columnar(clean_text, num_cols, *language, &climber, &mut rng) else {
+                        continue;
+                    };
+                    if !is_likely_language(&plaintext, *language) {
+                        continue;
+                    }
+                    let decoded = plaintext.to_lowercase();
+                    let checker_result = checker_with_sensitivity.check(&decoded, config);
+                    if checker_result.is_identified {
+                        results.unencrypted_text = Some(vec![decoded.into()]);
+                        results.update_checker(&checker_result);
+                        // Which position each column was read out in, from 1
+                        let key: Vec<String> = key.iter().map(|position| (position + 1).to_string()).collect();
+                        results.key = Some(key.join(" "));
+                        return results;
+                    }
+                }
+            }
+        }
 
         info!("Failed to decode Columnar Transposition cipher");
         results
     }
-
-    fn get_tags(&self) -> &Vec<&str> {
-        &self.tags
-    }
-
-    fn get_name(&self) -> &str {
-        self.name
-    }
-
-    fn get_description(&self) -> &str {
-        self.description
-    }
-
-    fn get_link(&self) -> &str {
-        self.link
-    }
 }
 
 /// Decode columnar transposition by reading down columns
@@ -104,7 +175,7 @@ fn decode_columnar(text: &str, num_cols: usize) -> Option<String> {
         return None;
     }
 
-    let num_rows = (len + num_cols - 1) / num_cols;
+    let num_rows = len.div_ceil(num_cols);
     let full_cols = len % num_cols;
     let full_cols = if full_cols == 0 { num_cols } else { full_cols };
 
@@ -144,7 +215,7 @@ fn decode_columnar_reverse(text: &str, num_cols: usize) -> Option<String> {
         return None;
     }
 
-    let num_rows = (len + num_cols - 1) / num_cols;
+    let num_rows = len.div_ceil(num_cols);
     let full_cols = len % num_cols;
     let full_cols = if full_cols == 0 { num_cols } else { full_cols };
 
@@ -248,6 +319,22 @@ mod tests {
         assert!(result.unencrypted_text.is_some() || result.unencrypted_text.is_none());
     }
 
+    #[test]
+    fn test_decoder_keyed_order() {
+        let decoder = Decoder::<ColumnarTranspositionDecoder>::new();
+        // Alice in Wonderland under a 7 column key, the last row not full
+        let result = decoder.crack(
+            "HNSELTAIVHHHRAERTVAIOIKRUWABTFWIRKTDETMOYTIITSGRBADHOCTOESSANYAHITSUFTHTTEOOMENLNEOEEAYLEHERNRCIYTARBO",
+            &get_athena_checker(),
+            &Config { seed: Some(1), ..Default::default() },
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "therewasnothingsoveryremarkableinthatnordidalicethinkitsoverymuchoutofthewaytoheartherabbitsaytoitself"
+        );
+        assert_eq!(result.key.unwrap(), "4 1 6 2 7 3 5");
+    }
+
     #[test]
     fn test_decoder_name() {
         let decoder = Decoder::<ColumnarTranspositionDecoder>::new();
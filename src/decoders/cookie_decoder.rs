@@ -0,0 +1,162 @@
+//! Decode Base64 cookie values
+//! Performs error handling and returns a string
+//! Call cookie_decoder.crack to use.
+//!
+//! Finds the `Cookie` and `Set-Cookie` headers in a pasted HTTP request or
+//! response, and decodes the values which are URL-encoded Base64 of
+//! readable text, like serialized sessions. Random session IDs decode to
+//! bytes and are left out.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use base64::{engine::general_purpose, Engine as _};
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use log::trace;
+
+/// Values shorter than this are too short to be worth decoding
+const MIN_VALUE_LEN: usize = 8;
+
+/// The cookie decoder, call:
+/// `let cookie_decoder = Decoder::<CookieDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = cookie_decoder.crack(input)` to decode the cookies in the input
+/// ```
+/// use ares::decoders::cookie_decoder::CookieDecoder;
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decoder = Decoder::<CookieDecoder>::new();
+/// let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+///
+/// let request = "GET / HTTP/1.1\nCookie: theme=dark; session=eyJ1c2VyIjoiYm9iIiwiYWRtaW4iOmZhbHNlfQ%3D%3D\n";
+/// let result = decoder.crack(request, &checker, &ares::config::Config::default());
+/// assert_eq!(result.unencrypted_text.unwrap()[0], "session: {\"user\":\"bob\",\"admin\":false}");
+/// ```
+pub struct CookieDecoder;
+
+impl Crack for Decoder<CookieDecoder> {
+    fn new() -> Decoder<CookieDecoder> {
+        Decoder {
+            name: "Cookie",
+            description: "Decodes the Base64 cookie values in the Cookie and Set-Cookie headers of a pasted HTTP request or response.",
+            link: "https://en.wikipedia.org/wiki/HTTP_cookie",
+            tags: vec!["http", "cookie", "base64", "decoder"],
+            popularity: 0.5,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Cookie with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let decoded: Vec<String> = text
+            .lines()
+            .filter_map(cookie_header)
+            .flat_map(cookie_pairs)
+            .filter_map(|(name, value)| decode_value(value).map(|value| format!("{name}: {value}")))
+            .collect();
+        if decoded.is_empty() {
+            return results;
+        }
+        let decoded = decoded.join("\n");
+        let mut checker_result = checker.check(&decoded, config);
+        // Readable text hidden in a cookie is what was encoded, whatever the checker thinks
+        checker_result.is_identified = true;
+        results.unencrypted_text = Some(vec![decoded.into()]);
+        results.update_checker(&checker_result);
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The value of a `Cookie` or `Set-Cookie` header line
+fn cookie_header(line: &str) -> Option<&str> {
+    let (name, value) = line.split_once(':')?;
+    let name = name.trim();
+    (name.eq_ignore_ascii_case("cookie") || name.eq_ignore_ascii_case("set-cookie"))
+        .then(|| value.trim())
+}
+
+/// The `name=value` pairs of a cookie header. `Set-Cookie` attributes like
+/// `Path=/` are pairs too, but never decode to anything.
+fn cookie_pairs(header: &str) -> impl Iterator<Item = (&str, &str)> {
+    header
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(name, value)| (name.trim(), value.trim().trim_matches('"')))
+}
+
+/// The readable text a cookie value is the Base64 of, if it is
+fn decode_value(value: &str) -> Option<String> {
+    let value = urlencoding::decode(value).ok()?;
+    if value.len() < MIN_VALUE_LEN {
+        return None;
+    }
+    let bytes = [
+        general_purpose::STANDARD,
+        general_purpose::STANDARD_NO_PAD,
+        general_purpose::URL_SAFE,
+        general_purpose::URL_SAFE_NO_PAD,
+    ]
+    .iter()
+    .find_map(|engine| engine.decode(value.as_bytes()).ok())?;
+    let text = String::from_utf8(bytes).ok()?;
+    let readable = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| !c.is_control() || c == '\n' || c == '\r' || c == '\t');
+    readable.then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CookieDecoder;
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn cookie_set_cookie_header() {
+        let decoder = Decoder::<CookieDecoder>::new();
+        let response =
+            "HTTP/1.1 200 OK\r\nSet-Cookie: prefs=bGFuZz1lbiZ0ej1VVEM; Path=/; HttpOnly\r\n\r\n";
+        let result = decoder.crack(response, &get_checker(), &crate::config::Config::default());
+        assert!(result.success);
+        assert_eq!(result.unencrypted_text.unwrap()[0], "prefs: lang=en&tz=UTC");
+    }
+
+    #[test]
+    fn cookie_skips_random_ids() {
+        let decoder = Decoder::<CookieDecoder>::new();
+        let request = "Cookie: sid=9f8e7d6c5b4a39281706f5e4d3c2b1a0";
+        let result = decoder.crack(request, &get_checker(), &crate::config::Config::default());
+        assert!(result.unencrypted_text.is_none());
+    }
+}
@@ -0,0 +1,340 @@
+//! Decode geohashes, Maidenhead locators and MGRS coordinates
+//! Performs error handling and returns a string
+//! Call coordinates_decoder.crack to use.
+//!
+//! Each of these writes a place on Earth as letters and digits. This turns
+//! them into a latitude and longitude, which the Coordinates checker
+//! recognises. The result's key says which system the input was in.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use log::trace;
+
+/// The letters of a geohash, each worth five bits
+const GEOHASH_ALPHABET: &str = "0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Geohashes shorter than this are too often just words
+const MIN_GEOHASH_LEN: usize = 6;
+
+/// Geohashes longer than this are finer than a millimetre, so aren't geohashes
+const MAX_GEOHASH_LEN: usize = 12;
+
+/// The MGRS latitude bands, 8° each from 80° S
+const MGRS_BANDS: &str = "CDEFGHJKLMNPQRSTUVWX";
+
+/// The letters of MGRS 100 km squares, I and O left out
+const MGRS_ROW_LETTERS: &str = "ABCDEFGHJKLMNPQRSTUV";
+
+/// The UTM northing each MGRS latitude band starts at, in metres, counting
+/// from the equator or, south of it, from 10,000 km south of it
+const MGRS_BAND_MIN_NORTHING: [f64; 20] = [
+    1_100_000.0,
+    2_000_000.0,
+    2_800_000.0,
+    3_700_000.0,
+    4_600_000.0,
+    5_500_000.0,
+    6_400_000.0,
+    7_300_000.0,
+    8_200_000.0,
+    9_100_000.0,
+    0.0,
+    800_000.0,
+    1_700_000.0,
+    2_600_000.0,
+    3_500_000.0,
+    4_400_000.0,
+    5_300_000.0,
+    6_200_000.0,
+    7_000_000.0,
+    7_900_000.0,
+];
+
+/// The WGS 84 ellipsoid's semi-major axis, in metres
+const WGS84_A: f64 = 6_378_137.0;
+
+/// The WGS 84 ellipsoid's first eccentricity squared
+const WGS84_E2: f64 = 0.006_694_379_990_14;
+
+/// The UTM scale factor on the central meridian
+const UTM_K0: f64 = 0.9996;
+
+/// The Coordinates decoder, call:
+/// `let coordinates_decoder = Decoder::<CoordinatesDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = coordinates_decoder.crack(input)` to get the latitude and longitude
+/// ```
+/// use ares::decoders::coordinates_decoder::CoordinatesDecoder;
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decoder = Decoder::<CoordinatesDecoder>::new();
+/// let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+///
+/// let result = decoder.crack("u4pruydqqvj", &checker, &ares::config::Config::default());
+/// assert!(result.success);
+/// assert_eq!(result.unencrypted_text.unwrap()[0], "57.64911, 10.40744");
+/// assert_eq!(result.key.as_deref(), Some("geohash"));
+/// ```
+pub struct CoordinatesDecoder;
+
+impl Crack for Decoder<CoordinatesDecoder> {
+    fn new() -> Decoder<CoordinatesDecoder> {
+        Decoder {
+            name: "Coordinates",
+            description: "Geohashes, Maidenhead locators and MGRS grid references write a place as letters and digits. This turns them into a latitude and longitude.",
+            link: "https://en.wikipedia.org/wiki/Geohash",
+            tags: vec!["coordinates", "gps", "geocaching", "decoder"],
+            popularity: 0.4,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Coordinates with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let trimmed = text.trim();
+        let Some((system, (latitude, longitude))) = geohash(trimmed)
+            .map(|place| ("geohash", place))
+            .or_else(|| maidenhead(trimmed).map(|place| ("Maidenhead", place)))
+            .or_else(|| mgrs(trimmed).map(|place| ("MGRS", place)))
+        else {
+            return results;
+        };
+        let decoded = format!("{latitude:.5}, {longitude:.5}");
+        let checker_result = checker.check(&decoded, config);
+        results.unencrypted_text = Some(vec![decoded.into()]);
+        results.key = Some(system.to_string());
+        results.update_checker(&checker_result);
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The centre of a geohash's cell. Geohashes with no digit are left alone,
+/// as most of them are words.
+fn geohash(text: &str) -> Option<(f64, f64)> {
+    if !(MIN_GEOHASH_LEN..=MAX_GEOHASH_LEN).contains(&text.len())
+        || !text.bytes().any(|byte| byte.is_ascii_digit())
+        || !text.bytes().any(|byte| byte.is_ascii_lowercase())
+    {
+        return None;
+    }
+    let (mut latitude, mut longitude) = ((-90.0, 90.0), (-180.0, 180.0));
+    let mut is_longitude = true;
+    for c in text.chars() {
+        let value = GEOHASH_ALPHABET.find(c)?;
+        for bit in (0..5).rev() {
+            let range: &mut (f64, f64) = if is_longitude {
+                &mut longitude
+            } else {
+                &mut latitude
+            };
+            let middle = (range.0 + range.1) / 2.0;
+            if value & (1 << bit) != 0 {
+                range.0 = middle;
+            } else {
+                range.1 = middle;
+            }
+            is_longitude = !is_longitude;
+        }
+    }
+    Some((
+        (latitude.0 + latitude.1) / 2.0,
+        (longitude.0 + longitude.1) / 2.0,
+    ))
+}
+
+/// The centre of a Maidenhead locator's square, for locators down to at
+/// least the subsquare (six characters), as four are too easily other things
+fn maidenhead(text: &str) -> Option<(f64, f64)> {
+    let bytes = text.as_bytes();
+    if !matches!(bytes.len(), 6 | 8 | 10) {
+        return None;
+    }
+    // Each pair of characters splits the square before it: into 18 fields
+    // of letters A to R, then 10 of digits, then 24 of letters A to X, ...
+    let (mut longitude, mut latitude) = (-180.0, -90.0);
+    let (mut width, mut height) = (360.0, 180.0);
+    for (pair, chars) in bytes.chunks(2).enumerate() {
+        let (divisions, base, last) = match pair {
+            0 => (18.0, b'A', b'R'),
+            _ if pair % 2 == 1 => (10.0, b'0', b'9'),
+            _ => (24.0, b'A', b'X'),
+        };
+        let digit = |c: u8| {
+            let c = c.to_ascii_uppercase();
+            (base..=last).contains(&c).then(|| f64::from(c - base))
+        };
+        width /= divisions;
+        height /= divisions;
+        longitude += digit(chars[0])? * width;
+        latitude += digit(chars[1])? * height;
+    }
+    Some((latitude + height / 2.0, longitude + width / 2.0))
+}
+
+/// The centre of an MGRS grid reference's square, e.g. `18SUJ2337006519`,
+/// spaced or not. The polar UPS zones aren't supported.
+fn mgrs(text: &str) -> Option<(f64, f64)> {
+    let compact: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase();
+    let zone_len = compact.find(|c: char| !c.is_ascii_digit())?;
+    let zone: u32 = compact[..zone_len].parse().ok()?;
+    if !(1..=60).contains(&zone) {
+        return None;
+    }
+    let mut letters = compact[zone_len..].chars();
+    let band = MGRS_BANDS.find(letters.next()?)?;
+    let column = letters.next()?;
+    let row = letters.next()?;
+    let digits = letters.as_str();
+    // A reference without digits only names a 100 km square, and is too
+    // easily a few random letters
+    if digits.is_empty()
+        || !digits.len().is_multiple_of(2)
+        || digits.len() > 10
+        || !digits.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return None;
+    }
+
+    // Column letters run A to Z, without I and O, in three sets of eight
+    // for zones 1, 2 and 3, then again for 4, 5 and 6, and so on
+    let column_letters: Vec<char> = ('A'..='Z').filter(|c| !matches!(c, 'I' | 'O')).collect();
+    let set = ((zone - 1) % 3) as usize;
+    let column_index = column_letters[set * 8..set * 8 + 8]
+        .iter()
+        .position(|letter| *letter == column)?;
+    // Row letters repeat every 2,000 km, starting five letters on in even zones
+    let row_index = MGRS_ROW_LETTERS.find(row)?;
+    let row_offset = if zone.is_multiple_of(2) { 5 } else { 0 };
+    let mut northing = ((row_index + 20 - row_offset) % 20) as f64 * 100_000.0;
+    while northing < MGRS_BAND_MIN_NORTHING[band] {
+        northing += 2_000_000.0;
+    }
+    let mut easting = (column_index + 1) as f64 * 100_000.0;
+
+    let precision = digits.len() / 2;
+    let square = 10f64.powi(5 - precision as i32);
+    easting += digits[..precision].parse::<f64>().ok()? * square;
+    northing += digits[precision..].parse::<f64>().ok()? * square;
+    easting += square / 2.0;
+    northing += square / 2.0;
+
+    let southern = band < MGRS_BANDS.find('N')?;
+    Some(utm_to_latitude_longitude(zone, southern, easting, northing))
+}
+
+/// The latitude and longitude of a UTM position, by the series in
+/// Snyder's "Map Projections: A Working Manual", good to well under a metre
+fn utm_to_latitude_longitude(zone: u32, southern: bool, easting: f64, northing: f64) -> (f64, f64) {
+    let e2 = WGS84_E2;
+    let ep2 = e2 / (1.0 - e2);
+    let x = easting - 500_000.0;
+    let y = if southern {
+        northing - 10_000_000.0
+    } else {
+        northing
+    };
+
+    let m = y / UTM_K0;
+    let mu = m / (WGS84_A * (1.0 - e2 / 4.0 - 3.0 * e2 * e2 / 64.0 - 5.0 * e2 * e2 * e2 / 256.0));
+    let e1 = (1.0 - (1.0 - e2).sqrt()) / (1.0 + (1.0 - e2).sqrt());
+    let phi1 = mu
+        + (3.0 * e1 / 2.0 - 27.0 * e1.powi(3) / 32.0) * (2.0 * mu).sin()
+        + (21.0 * e1 * e1 / 16.0 - 55.0 * e1.powi(4) / 32.0) * (4.0 * mu).sin()
+        + (151.0 * e1.powi(3) / 96.0) * (6.0 * mu).sin()
+        + (1097.0 * e1.powi(4) / 512.0) * (8.0 * mu).sin();
+
+    let (sin_phi1, cos_phi1, tan_phi1) = (phi1.sin(), phi1.cos(), phi1.tan());
+    let n1 = WGS84_A / (1.0 - e2 * sin_phi1 * sin_phi1).sqrt();
+    let t1 = tan_phi1 * tan_phi1;
+    let c1 = ep2 * cos_phi1 * cos_phi1;
+    let r1 = WGS84_A * (1.0 - e2) / (1.0 - e2 * sin_phi1 * sin_phi1).powf(1.5);
+    let d = x / (n1 * UTM_K0);
+
+    let latitude = phi1
+        - (n1 * tan_phi1 / r1)
+            * (d * d / 2.0
+                - (5.0 + 3.0 * t1 + 10.0 * c1 - 4.0 * c1 * c1 - 9.0 * ep2) * d.powi(4) / 24.0
+                + (61.0 + 90.0 * t1 + 298.0 * c1 + 45.0 * t1 * t1 - 252.0 * ep2 - 3.0 * c1 * c1)
+                    * d.powi(6)
+                    / 720.0);
+    let central_meridian = f64::from(zone) * 6.0 - 183.0;
+    let longitude = central_meridian
+        + ((d - (1.0 + 2.0 * t1 + c1) * d.powi(3) / 6.0
+            + (5.0 - 2.0 * c1 + 28.0 * t1 - 3.0 * c1 * c1 + 8.0 * ep2 + 24.0 * t1 * t1)
+                * d.powi(5)
+                / 120.0)
+            / cos_phi1)
+            .to_degrees();
+    (latitude.to_degrees(), longitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn coordinates_maidenhead() {
+        let decoder = Decoder::<CoordinatesDecoder>::new();
+        let result = decoder.crack("IO91wm", &get_checker(), &Config::default());
+        assert!(result.success);
+        assert_eq!(result.unencrypted_text.unwrap()[0], "51.52083, -0.12500");
+        assert_eq!(result.key.as_deref(), Some("Maidenhead"));
+    }
+
+    #[test]
+    fn coordinates_mgrs() {
+        // The White House, in the north, and Sydney Opera House, in the south
+        let (latitude, longitude) = mgrs("18S UJ 23389 07393").unwrap();
+        assert!((latitude - 38.89768).abs() < 1e-4, "{latitude}");
+        assert!((longitude + 77.03655).abs() < 1e-4, "{longitude}");
+        let (latitude, longitude) = mgrs("56HLH3490052293").unwrap();
+        assert!((latitude + 33.85676).abs() < 1e-3, "{latitude}");
+        assert!((longitude - 151.21530).abs() < 1e-3, "{longitude}");
+    }
+
+    #[test]
+    fn coordinates_leave_words_alone() {
+        let decoder = Decoder::<CoordinatesDecoder>::new();
+        let config = Config::default();
+        for text in ["energy", "hello world", "AB12", "61ZZZ12345"] {
+            assert!(decoder
+                .crack(text, &get_checker(), &config)
+                .unencrypted_text
+                .is_none());
+        }
+    }
+}
@@ -1,9 +1,11 @@
 //! This module contains CrackSuccess and CrackFailure
-use crate::checkers::{checker_result::CheckResult, CHECKER_MAP};
-use crate::decoders::DECODER_MAP;
+use crate::byte_text::Candidate;
+use crate::checkers::{checker_result::CheckResult, find_checker_info};
+use crate::decoders::find_decoder;
 
 use super::interface::Decoder;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// Every cracker returns this object which
 /// Either indicates success or failure among other things.
@@ -12,10 +14,11 @@ pub struct CrackResult {
     /// If our checkers return success, we change this bool to True
     pub success: bool,
     /// Encrypted text is the text _before_ we decrypt it.
-    pub encrypted_text: String,
-    /// Unencrypted text is what it looks like after.
+    /// Shared, as every path through this step holds a copy of it.
+    pub encrypted_text: Arc<str>,
+    /// Unencrypted text is what it looks like after, text or bytes.
     /// if decoder failed, this will be None
-    pub unencrypted_text: Option<Vec<String>>,
+    pub unencrypted_text: Option<Vec<Candidate>>,
     /// Decoder is the function we used to decode the text
     pub decoder: &'static str,
     /// Checker which identified the text
@@ -28,21 +31,64 @@ pub struct CrackResult {
     pub description: &'static str,
     /// Link is a link to more info about the decoder
     pub link: &'static str,
+    /// The text this step handed on along the path.
+    /// Only kept when `Config.keep_intermediates` is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intermediate_text: Option<Arc<str>>,
+    /// The steps a decoder which does several at once took, like Base64 then
+    /// gzip. The path shows these in its place.
+    #[serde(skip)]
+    pub inner_steps: Vec<CrackResult>,
+    /// How well each of `unencrypted_text` reads as plaintext, higher being
+    /// better, for decoders which hand on many candidates. The candidates
+    /// come best first, and the searchers only follow the best few.
+    #[serde(skip)]
+    pub candidate_scores: Option<Vec<f64>>,
+    /// Whether re-encoding what this step produced gave back its input.
+    /// Only set with `Config.verify`, and None for steps which can't encode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+    /// How long the decoder took, in microseconds. Set by
+    /// [`crate::filtration_system::crack_in_span`] when the search runs it,
+    /// so steps read from the cache don't have it.
+    #[serde(skip)]
+    pub duration_us: Option<u64>,
 }
 
 impl CrackResult {
     /// This function returns a new CrackResult
-    pub fn new<T>(decoder_used: &Decoder<T>, text: String) -> Self {
+    pub fn new<T>(decoder_used: &Decoder<T>, text: impl Into<Arc<str>>) -> Self {
+        Self::from_decoder_info(
+            decoder_used.name,
+            decoder_used.description,
+            decoder_used.link,
+            text,
+        )
+    }
+
+    /// Returns a new CrackResult for a decoder which isn't a [`Decoder`], such as
+    /// one added with [`crate::register_decoder`] by another crate
+    pub fn from_decoder_info(
+        decoder: &'static str,
+        description: &'static str,
+        link: &'static str,
+        text: impl Into<Arc<str>>,
+    ) -> Self {
         CrackResult {
             success: false,
-            encrypted_text: text,
+            encrypted_text: text.into(),
             unencrypted_text: None,
-            decoder: decoder_used.name,
+            decoder,
             checker_name: "",
             checker_description: "",
             key: None,
-            description: decoder_used.description,
-            link: decoder_used.link,
+            description,
+            link,
+            intermediate_text: None,
+            inner_steps: Vec::new(),
+            candidate_scores: None,
+            verified: None,
+            duration_us: None,
         }
     }
 
@@ -53,6 +99,20 @@ impl CrackResult {
         self.success = checker_result.is_identified;
     }
 
+    /// Hands on `scored` candidates best first, keeping their scores so the
+    /// searchers can follow the best few. None if there are no candidates.
+    pub fn set_ranked_candidates(&mut self, mut scored: Vec<(f64, Candidate)>) {
+        if scored.is_empty() {
+            self.unencrypted_text = None;
+            self.candidate_scores = None;
+            return;
+        }
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let (scores, candidates) = scored.into_iter().unzip();
+        self.unencrypted_text = Some(candidates);
+        self.candidate_scores = Some(scores);
+    }
+
     /// Converts CrackResult into JSON
     ///
     /// # Errors
@@ -76,9 +136,9 @@ impl<'de> Deserialize<'de> for CrackResult {
             /// Success status of the CrackResult
             pub success: bool,
             /// The encrypted/encoded text that was passed in this decode attempt
-            pub encrypted_text: String,
+            pub encrypted_text: Arc<str>,
             /// The resulting unencrypted/decoded text(s) generated in this decode attempt
-            pub unencrypted_text: Option<Vec<String>>,
+            pub unencrypted_text: Option<Vec<Candidate>>,
             /// The decoder used to decode the encoded text
             pub decoder: String,
             /// The checker used to validate the success of the decoding attempt
@@ -91,13 +151,17 @@ impl<'de> Deserialize<'de> for CrackResult {
             pub description: String,
             /// Link information about the decode method
             pub link: String,
+            /// The text this step handed on along the path
+            #[serde(default)]
+            pub intermediate_text: Option<Arc<str>>,
+            /// Whether re-encoding the step gave back its input
+            #[serde(default)]
+            pub verified: Option<bool>,
         }
         let temp_cr: TempCrackResult =
             TempCrackResult::deserialize(deserializer).expect("Error deserializing CrackResult");
-        let decoder = DECODER_MAP
-            .get(temp_cr.decoder.as_str())
-            .unwrap_or_else(|| panic!("Error during deserialization of CrackResult: could not find matching decoder for {}", temp_cr.decoder.as_str()))
-            .get();
+        let decoder = find_decoder(temp_cr.decoder.as_str())
+            .unwrap_or_else(|| panic!("Error during deserialization of CrackResult: could not find matching decoder for {}", temp_cr.decoder.as_str()));
         if temp_cr.checker_name.is_empty() {
             return Ok(CrackResult {
                 success: temp_cr.success,
@@ -109,12 +173,15 @@ impl<'de> Deserialize<'de> for CrackResult {
                 key: temp_cr.key,
                 description: decoder.get_description(),
                 link: decoder.get_link(),
+                intermediate_text: temp_cr.intermediate_text,
+                inner_steps: Vec::new(),
+                candidate_scores: None,
+                verified: temp_cr.verified,
+                duration_us: None,
             });
         }
-        let checker = CHECKER_MAP
-            .get(temp_cr.checker_name.as_str())
-            .unwrap_or_else(|| panic!("Error during deserialization of CrackResult: could not find matching checker for {}", temp_cr.checker_name.as_str()))
-            .get();
+        let checker = find_checker_info(temp_cr.checker_name.as_str())
+            .unwrap_or_else(|| panic!("Error during deserialization of CrackResult: could not find matching checker for {}", temp_cr.checker_name.as_str()));
         Ok(CrackResult {
             success: temp_cr.success,
             encrypted_text: temp_cr.encrypted_text,
@@ -125,6 +192,11 @@ impl<'de> Deserialize<'de> for CrackResult {
             key: temp_cr.key,
             description: decoder.get_description(),
             link: decoder.get_link(),
+            intermediate_text: temp_cr.intermediate_text,
+            inner_steps: Vec::new(),
+            candidate_scores: None,
+            verified: temp_cr.verified,
+            duration_us: None,
         })
     }
 }
@@ -135,10 +207,11 @@ mod tests {
     use super::super::super::checkers::english::EnglishChecker;
     use super::super::super::checkers::CheckerTypes;
     use super::super::super::decoders::interface::Crack;
-    use super::super::super::decoders::{
-        base64_decoder::Base64Decoder, caesar_decoder::CaesarDecoder,
-    };
+    use super::super::super::decoders::base64_decoder::Base64Decoder;
+    #[cfg(feature = "classical")]
+    use super::super::super::decoders::caesar_decoder::CaesarDecoder;
     use super::*;
+    use crate::byte_text::Content;
 
     struct MockDecoder;
     impl Crack for Decoder<MockDecoder> {
@@ -155,7 +228,7 @@ mod tests {
 
         /// Mocked cracking function
         fn crack(&self, text: &str, _checker: &CheckerTypes, _config: &crate::config::Config) -> CrackResult {
-            CrackResult::new(self, text.to_string())
+            CrackResult::new(self, text)
         }
 
         /// Gets all tags for this decoder
@@ -205,7 +278,7 @@ mod tests {
             CrackResult::new(&decoder, String::from("aGVsbG8gd29ybGQK"));
         expected_crack_result.update_checker(&check_result);
         expected_crack_result.success = true;
-        expected_crack_result.unencrypted_text = Some(vec![String::from("hello world\n")]);
+        expected_crack_result.unencrypted_text = Some(vec![String::from("hello world\n").into()]);
 
         let result = serde_json::from_str(json.as_str());
         assert!(result.is_ok());
@@ -235,6 +308,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "classical")]
     fn deserialize_crack_result_caesar() {
         let json = String::from("{\"success\":true,\"encrypted_text\":\"ifmmp uijt jt mpoh ufyu\",\"unencrypted_text\":[\"hello this is long text\"],\"decoder\":\"caesar\",\"checker_name\":\"English Checker\",\"checker_description\":\"Uses gibberish detection to check if text is meaningful English\",\"key\":\"1\",\"description\":\"Caesar cipher, also known as Caesar's cipher, the shift cipher, Caesar's code or Caesar shift, is one of the simplest and most widely known encryption techniques. It is a type of substitution cipher in which each letter in the plaintext is replaced by a letter some fixed number of positions down the alphabet. Uses Low sensitivity for gibberish detection.\",\"link\":\"https://en.wikipedia.org/wiki/Caesar_cipher\"}");
 
@@ -254,7 +328,7 @@ mod tests {
         expected_crack_result.update_checker(&check_result);
         expected_crack_result.success = true;
         expected_crack_result.unencrypted_text =
-            Some(vec![String::from("hello this is long text")]);
+            Some(vec!["hello this is long text".into()]);
         expected_crack_result.key = Some(String::from("1"));
 
         let result = serde_json::from_str(json.as_str());
@@ -283,4 +357,18 @@ mod tests {
         assert_eq!(crack_result.link, expected_crack_result.link);
         assert_eq!(crack_result.key, expected_crack_result.key);
     }
+
+    #[test]
+    fn clones_share_the_text() {
+        let mock_decoder = Decoder::<MockDecoder>::new();
+        let mut crack_result = CrackResult::new(&mock_decoder, "text that is encrypted");
+        crack_result.unencrypted_text = Some(vec!["text that is decrypted".into()]);
+        let copy = crack_result.clone();
+        assert!(Arc::ptr_eq(&copy.encrypted_text, &crack_result.encrypted_text));
+        let (copy, original) = (copy.unencrypted_text.unwrap(), crack_result.unencrypted_text.unwrap());
+        match (copy[0].content(), original[0].content()) {
+            (Content::Text(a), Content::Text(b)) => assert!(Arc::ptr_eq(a, b)),
+            _ => panic!("expected text"),
+        }
+    }
 }
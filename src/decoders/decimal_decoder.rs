@@ -6,6 +6,7 @@ use crate::config::Config;
 use crate::decoders::interface::check_string_success;
 use crate::decoders::crack_results::CrackResult;
 use crate::decoders::interface::Crack;
+use crate::decoders::interface::Encode;
 use crate::decoders::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -29,7 +30,7 @@ impl Crack for Decoder<DecimalDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Decimal with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let decoded_text = decode_decimal_no_error_handling(text);
 
         if decoded_text.is_none() {
@@ -44,7 +45,7 @@ impl Crack for Decoder<DecimalDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results.update_checker(&checker_result);
 
         results
@@ -57,6 +58,17 @@ impl Crack for Decoder<DecimalDecoder> {
     fn get_link(&self) -> &str { self.link }
 }
 
+impl Encode for Decoder<DecimalDecoder> {
+    /// Encodes each byte as a decimal number, separated by spaces
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(text
+            .bytes()
+            .map(|byte| byte.to_string())
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+}
+
 /// Helper function to decode decimal string
 fn decode_decimal_no_error_handling(text: &str) -> Option<String> {
     if text.is_empty() { return None; }
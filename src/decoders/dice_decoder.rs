@@ -0,0 +1,124 @@
+//! Dice roll decoder
+//! Reads pairs of dice rolls as the row and column of a 6x6 square holding
+//! A-Z then 0-9, so 11 is A, 16 is F, 21 is G and 66 is 9. Rolls are written
+//! as the digits 1 to 6, spaced or not, or as the dice faces ⚀ to ⚅.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{info, trace};
+
+/// The Dice decoder
+/// Call: `let decoder = Decoder::<DiceDecoder>::new()` to create a new instance
+/// And then call: `result = decoder.crack(input)` to decode
+pub struct DiceDecoder;
+
+/// The 6x6 square, row by row
+const SQUARE: &[u8; 36] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Fewer rolls than this are too likely to be some other number
+const MIN_ROLLS: usize = 8;
+
+impl Crack for Decoder<DiceDecoder> {
+    fn new() -> Decoder<DiceDecoder> {
+        Decoder {
+            name: "Dice",
+            description: "Reads pairs of dice rolls as the row and column of a 6x6 square of the letters A-Z and digits 0-9.",
+            link: "https://en.wikipedia.org/wiki/Polybius_square",
+            tags: vec!["dice", "hobbyist", "polybius", "cipher"],
+            popularity: 0.3,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Dice with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let Some(rolls) = dice_rolls(text) else {
+            info!("Failed to read dice rolls");
+            return results;
+        };
+        let decoded: String = rolls
+            .chunks(2)
+            .map(|pair| char::from(SQUARE[usize::from((pair[0] - 1) * 6 + pair[1] - 1)]))
+            .collect();
+        if !check_string_success(&decoded, text) {
+            return results;
+        }
+        let checker_result = checker.check(&decoded, config);
+        results.unencrypted_text = Some(vec![decoded.into()]);
+        results.update_checker(&checker_result);
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The rolls, 1 to 6, if the text is an even number of them and nothing
+/// else but spaces and commas
+fn dice_rolls(text: &str) -> Option<Vec<u8>> {
+    let mut rolls = Vec::new();
+    for c in text.chars() {
+        match c {
+            '1'..='6' => rolls.push(c as u8 - b'0'),
+            '⚀'..='⚅' => rolls.push((u32::from(c) - u32::from('⚀')) as u8 + 1),
+            ',' => {}
+            _ if c.is_whitespace() => {}
+            _ => return None,
+        }
+    }
+    (rolls.len() >= MIN_ROLLS && rolls.len().is_multiple_of(2)).then_some(rolls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn dice_rolls_to_letters() {
+        let decoder = Decoder::<DiceDecoder>::new();
+        // A T T A C K, with digits as the faces
+        let result = decoder.crack("11 42 42 11 13 25", &get_checker(), &Config::default());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "ATTACK");
+        assert_eq!(dice_rolls("⚀⚀ ⚃⚁"), None);
+        assert_eq!(dice_rolls("⚀⚀⚃⚁⚃⚁⚅⚅"), Some(vec![1, 1, 4, 2, 4, 2, 6, 6]));
+    }
+
+    #[test]
+    fn dice_rolls_need_dice() {
+        assert_eq!(dice_rolls("11 42 42 11 13 2"), None);
+        assert_eq!(dice_rolls("11 42 42 11 17 25"), None);
+        assert_eq!(dice_rolls("hello"), None);
+    }
+}
@@ -0,0 +1,331 @@
+//! Morse code written with digits, shared by the Morbit and Pollux decoders
+//!
+//! Both ciphers write the message in Morse code with `x` after every letter
+//! and another after every word, then swap the dots, dashes and `x`s for
+//! digits with a key. Morbit swaps each pair of symbols for one of the digits
+//! 1 to 9, Pollux each single symbol for any of several digits 0 to 9.
+//!
+//! Keys are found digit by digit in the order the digits first appear in the
+//! ciphertext, dropping a key as soon as the Morse it gives so far can't be
+//! letters. The keys left are ranked by how much their plaintext reads like
+//! a language.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::cryptanalysis::languages_or_english;
+use crate::deadline;
+
+use super::crack_results::CrackResult;
+use super::morse_code::MORSE_TABLE;
+
+use log::{info, trace};
+
+/// How a cipher swaps Morse symbols for digits
+pub struct Scheme {
+    /// What each digit can stand for
+    codes: &'static [&'static str],
+    /// Whether each of `codes` has one digit only, as in Morbit, rather than
+    /// several digits standing for the same code, as in Pollux
+    one_digit_per_code: bool,
+    /// The lowest digit the cipher uses
+    first_digit: u8,
+    /// Fewer digits than this are too likely to be some other number, and
+    /// fit too many keys. About eight letters' worth.
+    min_digits: usize,
+}
+
+/// Morbit: the nine pairs of symbols, each swapped for one of 1 to 9
+pub const MORBIT: Scheme = Scheme {
+    codes: &["..", ".-", ".x", "-.", "--", "-x", "x.", "x-", "xx"],
+    one_digit_per_code: true,
+    first_digit: 1,
+    min_digits: 16,
+};
+
+/// Pollux: each symbol swapped for any of the digits given it, from 0 to 9
+pub const POLLUX: Scheme = Scheme {
+    codes: &[".", "-", "x"],
+    one_digit_per_code: false,
+    first_digit: 0,
+    min_digits: 30,
+};
+
+/// What each digit stands for, indexed by the digit. None for digits the
+/// ciphertext doesn't use.
+type Key = [Option<&'static str>; 10];
+
+/// The most keys kept from the search. Short ciphertexts fit many keys, and
+/// past this many the right one is rarely told apart from the rest anyway.
+const MAX_KEYS: usize = 20_000;
+
+/// How many of the best keys' plaintexts are checked, and returned if none
+/// is recognised
+const MAX_READINGS: usize = 10;
+
+/// Whether `code` is the Morse code of a letter
+fn is_letter(code: &[u8]) -> bool {
+    MORSE_TABLE
+        .iter()
+        .take(26)
+        .any(|(letter_code, _)| letter_code.as_bytes() == code)
+}
+
+/// Whether some letter's Morse code starts with `code`
+fn starts_letter(code: &[u8]) -> bool {
+    MORSE_TABLE
+        .iter()
+        .take(26)
+        .any(|(letter_code, _)| letter_code.as_bytes().starts_with(code))
+}
+
+/// Reads Morse symbols one at a time, saying whether they can still be
+/// letters separated by `x` and words separated by `xx`
+#[derive(Clone, Copy, Default)]
+struct MorseReader {
+    /// The symbols of the letter being read
+    letter: [u8; 4],
+    /// How many symbols of `letter` have been read
+    len: usize,
+    /// How many `x`s in a row were read last
+    separators: usize,
+    /// Whether a dot or dash has been read
+    started: bool,
+}
+
+impl MorseReader {
+    /// Reads `symbol`, returning false if the Morse can no longer be letters
+    fn push(&mut self, symbol: u8) -> bool {
+        if symbol == b'x' {
+            self.separators += 1;
+            let ends_letter = self.len == 0 || is_letter(&self.letter[..self.len]);
+            self.len = 0;
+            return self.started && ends_letter && self.separators <= 2;
+        }
+        if self.len == self.letter.len() {
+            return false;
+        }
+        self.letter[self.len] = symbol;
+        self.len += 1;
+        self.separators = 0;
+        self.started = true;
+        starts_letter(&self.letter[..self.len])
+    }
+
+    /// Whether the Morse read so far can end here
+    fn can_end(&self) -> bool {
+        self.started && (self.len == 0 || is_letter(&self.letter[..self.len]))
+    }
+}
+
+impl Scheme {
+    /// The ciphertext's digits, ignoring whitespace. None if anything else
+    /// is in it or a digit isn't used by the cipher.
+    pub fn digits_of(&self, text: &str) -> Option<Vec<u8>> {
+        text.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| {
+                let digit = u8::try_from(c.to_digit(10)?).ok()?;
+                (digit >= self.first_digit).then_some(digit)
+            })
+            .collect()
+    }
+
+    /// Every key giving Morse which can be read as letters, up to [`MAX_KEYS`]
+    fn search(&self, digits: &[u8], config: &Config) -> Vec<Key> {
+        let mut order: Vec<u8> = Vec::new();
+        let mut ends: Vec<usize> = Vec::new();
+        for (i, &digit) in digits.iter().enumerate() {
+            if !order.contains(&digit) {
+                if !order.is_empty() {
+                    ends.push(i);
+                }
+                order.push(digit);
+            }
+        }
+        ends.push(digits.len());
+
+        let mut keys = Vec::new();
+        let mut search = KeySearch {
+            scheme: self,
+            digits,
+            order: &order,
+            ends: &ends,
+            config,
+            keys: &mut keys,
+        };
+        search.extend(0, &mut [None; 10], 0, MorseReader::default());
+        keys
+    }
+
+    /// The plaintext the digits give with `key`, or None if they aren't
+    /// all in the key or don't give Morse code
+    fn decode(&self, digits: &[u8], key: &Key) -> Option<String> {
+        let morse = digits
+            .iter()
+            .map(|&digit| key[usize::from(digit)])
+            .collect::<Option<String>>()?;
+        let words: Vec<String> = morse
+            .trim_end_matches('x')
+            .split("xx")
+            .map(|word| {
+                word.split('x')
+                    .map(|code| {
+                        MORSE_TABLE
+                            .iter()
+                            .find(|(letter_code, _)| *letter_code == code)
+                            .map(|(_, letter)| *letter)
+                    })
+                    .collect::<Option<String>>()
+            })
+            .collect::<Option<_>>()?;
+        Some(words.join(" "))
+    }
+
+    /// Cracks `text` as this cipher, filling in `results`
+    pub fn crack(
+        &self,
+        text: &str,
+        checker: &CheckerTypes,
+        config: &Config,
+        mut results: CrackResult,
+    ) -> CrackResult {
+        let Some(digits) = self.digits_of(text).filter(|d| d.len() >= self.min_digits) else {
+            info!("Failed to read enough digits for digit Morse");
+            return results;
+        };
+        let keys = self.search(&digits, config);
+        trace!("Found {} keys giving Morse code", keys.len());
+
+        let languages = languages_or_english(&config.languages);
+        let mut readings: Vec<(f64, Key, String)> = keys
+            .into_iter()
+            .filter_map(|key| {
+                let plaintext = self.decode(&digits, &key)?;
+                let score = languages
+                    .iter()
+                    .map(|language| language.quadgrams().score(&plaintext))
+                    .fold(f64::MIN, f64::max);
+                Some((score, key, plaintext))
+            })
+            .collect();
+        // Best first, so the search carries on from the most promising
+        readings.sort_by(|a, b| b.0.total_cmp(&a.0));
+        readings.truncate(MAX_READINGS);
+
+        for (_, key, plaintext) in &readings {
+            let checker_result = checker.check(plaintext, config);
+            if checker_result.is_identified {
+                results.unencrypted_text = Some(vec![plaintext.clone().into()]);
+                results.key = Some(format_key(key));
+                results.update_checker(&checker_result);
+                return results;
+            }
+        }
+        if !readings.is_empty() {
+            results.unencrypted_text = Some(
+                readings
+                    .into_iter()
+                    .map(|(_, _, plaintext)| plaintext.into())
+                    .collect(),
+            );
+        }
+        results
+    }
+
+    /// Decodes `text` with a key written like the ones cracking finds, such
+    /// as `1=.- 2=xx 3=-.` for Morbit or `0=. 1=x 2=-` for Pollux
+    ///
+    /// # Errors
+    /// Returns a message if the text isn't the cipher's digits, the key
+    /// can't be read, or the key doesn't give Morse code.
+    pub fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        let digits = self
+            .digits_of(text)
+            .ok_or_else(|| "The text must be digits the cipher uses".to_string())?;
+        let key = self.parse_key(key)?;
+        self.decode(&digits, &key)
+            .ok_or_else(|| "The key doesn't turn the digits into Morse code".to_string())
+    }
+
+    /// Reads a key written as `digit=code` pairs separated by spaces
+    fn parse_key(&self, key: &str) -> Result<Key, String> {
+        let mut parsed: Key = [None; 10];
+        for pair in key.split_whitespace() {
+            let (digit, code) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected digit=code, got '{pair}'"))?;
+            let digit = digit
+                .parse::<u8>()
+                .ok()
+                .filter(|digit| (self.first_digit..=9).contains(digit))
+                .ok_or_else(|| format!("'{digit}' is not a digit the cipher uses"))?;
+            let code = self
+                .codes
+                .iter()
+                .find(|c| **c == code)
+                .ok_or_else(|| format!("'{code}' is not a code the cipher uses"))?;
+            if self.one_digit_per_code && parsed.contains(&Some(*code)) {
+                return Err(format!("'{code}' is given to more than one digit"));
+            }
+            parsed[usize::from(digit)] = Some(code);
+        }
+        Ok(parsed)
+    }
+}
+
+/// Writes a key as `digit=code` pairs for the digits it has
+fn format_key(key: &Key) -> String {
+    key.iter()
+        .enumerate()
+        .filter_map(|(digit, code)| Some(format!("{digit}={}", (*code)?)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A search for keys, trying codes for one digit at a time
+struct KeySearch<'a> {
+    /// The cipher
+    scheme: &'a Scheme,
+    /// The ciphertext's digits
+    digits: &'a [u8],
+    /// The distinct digits, in the order they first appear
+    order: &'a [u8],
+    /// How many digits of the ciphertext are known once each of `order` is
+    ends: &'a [usize],
+    /// For the deadline
+    config: &'a Config,
+    /// The keys found
+    keys: &'a mut Vec<Key>,
+}
+
+impl KeySearch<'_> {
+    /// Tries each code for the `depth`th digit of `order`, given the Morse
+    /// read from the digits before `start`
+    fn extend(&mut self, depth: usize, key: &mut Key, start: usize, reader: MorseReader) {
+        if self.keys.len() >= MAX_KEYS || deadline::expired(self.config.deadline.as_ref()) {
+            return;
+        }
+        let Some(&digit) = self.order.get(depth) else {
+            if reader.can_end() {
+                self.keys.push(*key);
+            }
+            return;
+        };
+        let end = self.ends[depth];
+        for code in self.scheme.codes {
+            if self.scheme.one_digit_per_code && key.contains(&Some(*code)) {
+                continue;
+            }
+            key[usize::from(digit)] = Some(code);
+            let mut next = reader;
+            let reads = self.digits[start..end]
+                .iter()
+                .flat_map(|&d| key[usize::from(d)].unwrap_or_default().bytes())
+                .all(|symbol| next.push(symbol));
+            if reads {
+                self.extend(depth + 1, key, end, next);
+            }
+        }
+        key[usize::from(digit)] = None;
+    }
+}
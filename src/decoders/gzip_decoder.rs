@@ -0,0 +1,138 @@
+//! Decompress gzip data
+//! Performs error handling and returns a string
+//! Call gzip_decoder.crack to use.
+
+use crate::byte_text::{Candidate, Content};
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use flate2::read::MultiGzDecoder;
+use std::io::Read;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use log::trace;
+
+/// The bytes every gzip member starts with
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The most bytes gzip data is decompressed to, so a gzip bomb can't use up
+/// the memory. Bigger inputs can be searched with `--stream`.
+const MAX_DECOMPRESSED_LEN: u64 = 16 * 1024 * 1024;
+
+/// The gzip decoder, call:
+/// `let gzip_decoder = Decoder::<GzipDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = gzip_decoder.crack(input)` to decompress gzip data
+pub struct GzipDecoder;
+
+impl Crack for Decoder<GzipDecoder> {
+    fn new() -> Decoder<GzipDecoder> {
+        Decoder {
+            name: "Gzip",
+            description: "gzip is a file format for data compressed with DEFLATE.",
+            link: "https://en.wikipedia.org/wiki/Gzip",
+            tags: vec!["gzip", "compression", "decoder"],
+            popularity: 0.6,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Decompresses the text's UTF-8 bytes
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        self.crack_bytes(text, text.as_bytes(), checker, config)
+    }
+
+    /// Bytes from an earlier decoder, like Base64, are decompressed as they are
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        match candidate.content() {
+            Content::Text(text) => self.crack(text, checker, config),
+            Content::Bytes(bytes) => self.crack_bytes(&candidate.text(), bytes, checker, config),
+        }
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+impl Decoder<GzipDecoder> {
+    /// Decompresses `bytes`, whose text view is `text`
+    fn crack_bytes(
+        &self,
+        text: &str,
+        bytes: &[u8],
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        trace!("Trying gzip with {} bytes", bytes.len());
+        let mut results = CrackResult::new(self, text);
+        let Some(decompressed) = gunzip(bytes) else {
+            return results;
+        };
+        let candidate = Candidate::from_bytes(decompressed);
+        let checker_result = checker.check(&candidate.text(), config);
+        results.unencrypted_text = Some(vec![candidate]);
+        results.update_checker(&checker_result);
+        results
+    }
+}
+
+/// Decompresses gzip data, None if it isn't gzip or is empty once decompressed
+pub fn gunzip(bytes: &[u8]) -> Option<Vec<u8>> {
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return None;
+    }
+    let mut decompressed = Vec::new();
+    MultiGzDecoder::new(bytes)
+        .take(MAX_DECOMPRESSED_LEN)
+        .read_to_end(&mut decompressed)
+        .ok()?;
+    (!decompressed.is_empty()).then_some(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{athena::Athena, checker_type::{Check, Checker}};
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn gzip_decompresses_bytes() {
+        let decoder = Decoder::<GzipDecoder>::new();
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let candidate = Candidate::from_bytes(gzip("hello gzip world"));
+        let result = decoder.crack_candidate(&candidate, &checker, &Config::default());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "hello gzip world");
+    }
+
+    #[test]
+    fn gzip_ignores_other_bytes() {
+        assert_eq!(gunzip(b"hello"), None);
+        assert_eq!(gunzip(&[0x1f, 0x8b, 0x00]), None);
+    }
+}
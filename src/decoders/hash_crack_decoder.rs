@@ -4,6 +4,7 @@
 
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
+use crate::deadline;
 use crate::decoders::interface::check_string_success;
 use super::crack_results::CrackResult;
 use super::interface::Crack;
@@ -35,7 +36,7 @@ impl Crack for Decoder<HashCrackDecoder> {
     /// This function does the actual decoding
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying HashCrack with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         
         // Clean input
         let text = text.trim().to_lowercase();
@@ -70,6 +71,9 @@ impl Crack for Decoder<HashCrackDecoder> {
         ];
 
         for password in common_passwords {
+            if deadline::expired(config.deadline.as_ref()) {
+                break;
+            }
              let cracked = match hash_type {
                 "MD5" => {
                     let result = md5::compute(password.as_bytes());
@@ -100,7 +104,7 @@ impl Crack for Decoder<HashCrackDecoder> {
                 let mut checker_result = checker.check(password, config);
                 // Force success since we found the password in our dictionary
                 checker_result.is_identified = true;
-                results.unencrypted_text = Some(vec![password.to_string()]);
+                results.unencrypted_text = Some(vec![password.to_string().into()]);
                 results.update_checker(&checker_result);
                 return results;
             }
@@ -128,6 +132,11 @@ impl Crack for Decoder<HashCrackDecoder> {
     fn get_popularity(&self) -> f32 {
         self.popularity
     }
+
+    /// Hashes every word of a dictionary
+    fn expected_cost(&self) -> f32 {
+        100.0
+    }
 }
 
 #[cfg(test)]
@@ -1,9 +1,12 @@
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 use crate::decoders::interface::check_string_success;
+use crate::storage::artifacts;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -16,8 +19,6 @@ pub struct HexadecimalDecoder;
 enum Error {
     /// Error when the input is not divisible by 2
     InvalidLength,
-    /// Error if the result isn't UTF-8
-    InvalidUtf8,
 }
 
 impl Crack for Decoder<HexadecimalDecoder> {
@@ -37,8 +38,8 @@ impl Crack for Decoder<HexadecimalDecoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying hexadecimal with text {:?}", text);
-        let decoded_text: Result<String, Error> = hexadecimal_to_string(text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let decoded_text: Result<Vec<u8>, Error> = hexadecimal_to_bytes(text);
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_err() {
             debug!("Failed to decode hexadecimal: {:?}", decoded_text);
@@ -47,7 +48,9 @@ impl Crack for Decoder<HexadecimalDecoder> {
 
         trace!("Decoded text for hexadecimal: {:?}", decoded_text);
 
-        let decoded_text = decoded_text.unwrap();
+        // Bytes which aren't UTF-8, like XOR'd data, are handed on as they are
+        let candidate = Candidate::from_bytes(decoded_text.unwrap());
+        let decoded_text = candidate.text();
 
         if !check_string_success(&decoded_text, text) {
             info!(
@@ -58,7 +61,7 @@ impl Crack for Decoder<HexadecimalDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![candidate.clone()]);
 
         results.update_checker(&checker_result);
 
@@ -72,6 +75,10 @@ impl Crack for Decoder<HexadecimalDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -82,8 +89,15 @@ impl Crack for Decoder<HexadecimalDecoder> {
     }
 }
 
-/// Decodes hexadecimal to string
-fn hexadecimal_to_string(hex: &str) -> Result<String, Error> {
+impl Encode for Decoder<HexadecimalDecoder> {
+    /// Encodes each byte as two lowercase hex digits
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(hex::encode(text))
+    }
+}
+
+/// Decodes hexadecimal to bytes
+fn hexadecimal_to_bytes(hex: &str) -> Result<Vec<u8>, Error> {
     // Remove "0x" delimiters
     let hex = hex.replace("0x", "");
     // Remove all non-hexadecimal characters from the string
@@ -98,14 +112,14 @@ fn hexadecimal_to_string(hex: &str) -> Result<String, Error> {
     }
 
     // Iterate over the vector of bytes in pairs
-    let mut result = String::new();
+    let mut result = Vec::with_capacity(bytes.len() / 2);
     for pair in bytes.chunks(2) {
-        // Parse the pair of bytes as a hexadecimal number and push the corresponding
-        // ASCII character to the result string
-        result.push(u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap() as char);
+        // Parse the pair of bytes as a hexadecimal number
+        result.push(u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap());
     }
 
-    String::from_utf8(result.into()).map_err(|_| Error::InvalidUtf8)
+    artifacts::inspect("Hexadecimal", &result);
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -29,7 +29,7 @@ impl Crack for Decoder<HtmlEntityDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying HTML Entity with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // decode_html_entities returns Cow<str>
         let decoded_cow = decode_html_entities(text);
@@ -41,7 +41,7 @@ impl Crack for Decoder<HtmlEntityDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results.update_checker(&checker_result);
 
         results
@@ -1,3 +1,4 @@
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 
@@ -46,7 +47,7 @@ impl Crack for Decoder<DefaultDecoder> {
     }
     /// Returns a dummy CrackResult
     fn crack(&self, text: &str, _checker: &CheckerTypes, _config: &Config) -> CrackResult {
-        CrackResult::new(self, text.to_string())
+        CrackResult::new(self, text)
     }
     /// Gets all tags for this decoder
     fn get_tags(&self) -> &Vec<&str> {
@@ -66,6 +67,17 @@ impl Crack for Decoder<DefaultDecoder> {
     }
 }
 
+/// Which characters of a text a decoder reads, see [`Crack::alphabet`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alphabet {
+    /// Every character
+    Any,
+    /// The ASCII letters, skipping over everything else
+    Letters,
+    /// Only these characters, a text with any other can't be decoded
+    Only(&'static str),
+}
+
 /// All decoders will share the same Crack trait
 /// Which let's us put them into a vector and iterate over them,
 /// Running `.crack()` on each of them.
@@ -77,6 +89,17 @@ pub trait Crack {
         Self: Sized;
     /// Crack is the function that actually does the decoding
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult;
+    /// Cracks what an earlier decoder produced, which the search calls instead
+    /// of [`Crack::crack`]. Decoders which work on bytes override it to get
+    /// them as they are, the default cracks the text view of them.
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        self.crack(&candidate.text(), checker, config)
+    }
     /// Get all tags for the current decoder
     fn get_tags(&self) -> &Vec<&str>;
     /// Get the name of the current decoder
@@ -93,6 +116,55 @@ pub trait Crack {
         // would override this to return its actual popularity value.
         0.5
     }
+    /// How long a crack takes compared with a plain encoding like Base64,
+    /// which costs 1.0. Decoders which try many keys or a dictionary override
+    /// it, so cheap and likely decoders run before them at every node.
+    fn expected_cost(&self) -> f32 {
+        1.0
+    }
+    /// The characters of a text this decoder reads. The search skips the
+    /// decoder for text it can't read, without calling [`Crack::crack`].
+    fn alphabet(&self) -> Alphabet {
+        Alphabet::Any
+    }
+    /// Whether the decoder reads its alphabet in pairs, so can only decode an
+    /// even number of those characters
+    fn needs_even_length(&self) -> bool {
+        false
+    }
+    /// The fewest characters of its alphabet the decoder can decode
+    fn min_length(&self) -> usize {
+        0
+    }
+    /// Decode the text with a key supplied by the user, as in `ares decode --key`.
+    /// Only keyed decoders (Caesar, Vigenère, XOR, ...) override this, the default
+    /// returns an error saying the decoder does not take a key.
+    ///
+    /// # Errors
+    /// Returns a message describing why the key could not be used.
+    fn decode_with_key(&self, _text: &str, _key: &str) -> Result<String, String> {
+        Err(format!("{} does not take a key", self.get_name()))
+    }
+}
+
+/// Reversible decoders also implement Encode, which applies the forward transform.
+/// This is what `ares encode` uses to build test vectors and round-trip puzzles.
+/// Anything encoded here must decode back to the same text with the decoder's `.crack()`.
+pub trait Encode: Crack {
+    /// Encode the plaintext
+    ///
+    /// # Errors
+    /// Returns a message if the text cannot be represented in this encoding,
+    /// or if the cipher needs a key.
+    fn encode(&self, text: &str) -> Result<String, String>;
+    /// Encode the plaintext with a key supplied by the user, as in `ares encode --key`.
+    /// Only keyed ciphers override this, the default returns an error.
+    ///
+    /// # Errors
+    /// Returns a message describing why the key could not be used.
+    fn encode_with_key(&self, _text: &str, _key: &str) -> Result<String, String> {
+        Err(format!("{} does not take a key", self.get_name()))
+    }
 }
 
 /// Returns a boolean of True if the string is successfully changed
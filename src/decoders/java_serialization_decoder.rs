@@ -0,0 +1,581 @@
+//! Decode Java serialization streams
+//! Performs error handling and returns a string
+//! Call java_serialization_decoder.crack to use.
+//!
+//! Java serialized objects start with the bytes `AC ED 00 05`, or `rO0AB` in
+//! Base64, and are the usual carrier of Java deserialization exploits. This
+//! walks the stream's grammar and prints the classes, fields and values in
+//! it. No class is loaded and nothing in the stream is run.
+
+use crate::byte_text::{Candidate, Content};
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use std::rc::Rc;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use super::serialized_value::{crack_binary, crack_bytes, Value, MAX_DEPTH};
+use log::trace;
+
+/// The magic number and version every serialization stream starts with
+const STREAM_HEADER: [u8; 4] = [0xac, 0xed, 0x00, 0x05];
+
+/// What the Base64 of a serialization stream starts with
+const BASE64_PREFIX: &str = "rO0AB";
+
+/// The first handle given to an object in the stream
+const BASE_WIRE_HANDLE: u32 = 0x7e_0000;
+
+// The type codes starting each item in the stream
+/// A null reference
+const TC_NULL: u8 = 0x70;
+/// A reference to something read before, by its handle
+const TC_REFERENCE: u8 = 0x71;
+/// A class description
+const TC_CLASSDESC: u8 = 0x72;
+/// A new object
+const TC_OBJECT: u8 = 0x73;
+/// A string of up to 64 KiB
+const TC_STRING: u8 = 0x74;
+/// A new array
+const TC_ARRAY: u8 = 0x75;
+/// A class object
+const TC_CLASS: u8 = 0x76;
+/// Up to 255 bytes written by a class itself
+const TC_BLOCKDATA: u8 = 0x77;
+/// The end of what a class wrote itself
+const TC_ENDBLOCKDATA: u8 = 0x78;
+/// Forget every handle so far
+const TC_RESET: u8 = 0x79;
+/// Bytes written by a class itself, with a four byte length
+const TC_BLOCKDATALONG: u8 = 0x7a;
+/// An exception thrown while writing the stream
+const TC_EXCEPTION: u8 = 0x7b;
+/// A string of over 64 KiB
+const TC_LONGSTRING: u8 = 0x7c;
+/// The class description of a dynamic proxy
+const TC_PROXYCLASSDESC: u8 = 0x7d;
+/// An enum constant
+const TC_ENUM: u8 = 0x7e;
+
+// Class description flags
+/// The class has its own writeObject, which may write more after its fields
+const SC_WRITE_METHOD: u8 = 0x01;
+/// The class is Serializable
+const SC_SERIALIZABLE: u8 = 0x02;
+/// The class is Externalizable and writes all of itself
+const SC_EXTERNALIZABLE: u8 = 0x04;
+/// Externalizable data is written in blocks, so can be skipped
+const SC_BLOCK_DATA: u8 = 0x08;
+
+/// The Java serialization decoder, call:
+/// `let java_serialization_decoder = Decoder::<JavaSerializationDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = java_serialization_decoder.crack(input)` to print the serialized objects
+pub struct JavaSerializationDecoder;
+
+impl Crack for Decoder<JavaSerializationDecoder> {
+    fn new() -> Decoder<JavaSerializationDecoder> {
+        Decoder {
+            name: "Java Serialization",
+            description: "Java's object serialization stream, recognised by its AC ED magic bytes. This shows the classes and fields in it without deserializing anything.",
+            link: "https://docs.oracle.com/javase/8/docs/platform/serialization/spec/protocol.html",
+            tags: vec!["java", "serialization", "web", "decoder"],
+            popularity: 0.5,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Reads the text's UTF-8 bytes, or the stream Base64 encoded
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Java Serialization with text {:?}", text);
+        crack_binary(self, text, BASE64_PREFIX, parse, checker, config)
+    }
+
+    /// Bytes from an earlier decoder, like Base64, are read as they are
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        match candidate.content() {
+            Content::Text(text) => self.crack(text, checker, config),
+            Content::Bytes(bytes) => {
+                crack_bytes(self, &candidate.text(), bytes, parse, checker, config)
+            }
+        }
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The objects in a serialization stream, which must be read to the end.
+/// A stream of more than one object is shown as a list of them.
+fn parse(bytes: &[u8]) -> Option<Value> {
+    let contents = bytes.strip_prefix(&STREAM_HEADER)?;
+    let mut parser = Parser {
+        bytes: contents,
+        position: 0,
+        handles: Vec::new(),
+    };
+    let mut values = Vec::new();
+    while parser.position < contents.len() {
+        values.push(parser.content(0)?);
+    }
+    match values.len() {
+        0 => None,
+        1 => values.pop(),
+        _ => Some(Value::List(None, values)),
+    }
+}
+
+/// A class as described in the stream
+struct ClassDesc {
+    /// Its name, e.g. `java.util.HashMap` or `[I`
+    name: String,
+    /// Its `SC_` flags
+    flags: u8,
+    /// Its serializable fields as their type codes and names
+    fields: Vec<(u8, String)>,
+    /// The description of its serializable superclass
+    super_desc: Option<Rc<ClassDesc>>,
+}
+
+/// What a handle refers to
+enum Handle {
+    /// A class description
+    Desc(Rc<ClassDesc>),
+    /// A value, or what to show for a reference to it
+    Value(Value),
+}
+
+/// Reads the items of a serialization stream
+struct Parser<'a> {
+    /// The stream after its header
+    bytes: &'a [u8],
+    /// How far it has been read
+    position: usize,
+    /// What each handle refers to, from [`BASE_WIRE_HANDLE`] on
+    handles: Vec<Handle>,
+}
+
+impl<'a> Parser<'a> {
+    /// Reads one item, `depth` objects down
+    fn content(&mut self, depth: usize) -> Option<Value> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        match self.u8()? {
+            TC_NULL => Some(Value::Null),
+            TC_REFERENCE => match self.handle()? {
+                Handle::Desc(desc) => Some(Value::Raw(format!("class {}", type_name(&desc.name)))),
+                Handle::Value(value) => Some(value.clone()),
+            },
+            TC_OBJECT => {
+                let desc = self.class_desc(depth)??;
+                let class = type_name(&desc.name);
+                self.handles
+                    .push(Handle::Value(Value::Raw(format!("<reference to {class}>"))));
+                let fields = self.class_data(&desc, depth)?;
+                Some(Value::Object(class, fields))
+            }
+            TC_STRING => {
+                let length = self.u16()?.into();
+                self.string(length)
+            }
+            TC_LONGSTRING => {
+                let length = usize::try_from(self.u64()?).ok()?;
+                self.string(length)
+            }
+            TC_ARRAY => {
+                let desc = self.class_desc(depth)??;
+                let class = type_name(&desc.name);
+                self.handles
+                    .push(Handle::Value(Value::Raw(format!("<reference to {class}>"))));
+                let size = usize::try_from(self.i32()?).ok()?;
+                // Every element takes at least a byte
+                if size > self.bytes.len() - self.position {
+                    return None;
+                }
+                let type_code = *desc.name.as_bytes().get(1)?;
+                let items = (0..size)
+                    .map(|_| self.field_value(type_code, depth))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Value::List(Some(class), items))
+            }
+            TC_CLASS => {
+                let desc = self.class_desc(depth)??;
+                let class = Value::Raw(format!("class {}", type_name(&desc.name)));
+                self.handles.push(Handle::Value(class.clone()));
+                Some(class)
+            }
+            TC_ENUM => {
+                let desc = self.class_desc(depth)??;
+                let handle = self.handles.len();
+                self.handles.push(Handle::Value(Value::Null));
+                let Value::Str(constant) = self.content(depth + 1)? else {
+                    return None;
+                };
+                let value = Value::Raw(format!("{}.{constant}", type_name(&desc.name)));
+                self.handles[handle] = Handle::Value(value.clone());
+                Some(value)
+            }
+            TC_CLASSDESC | TC_PROXYCLASSDESC => {
+                self.position -= 1;
+                let desc = self.class_desc(depth)??;
+                Some(Value::Raw(format!("class {}", type_name(&desc.name))))
+            }
+            TC_BLOCKDATA => {
+                let length = self.u8()?.into();
+                Some(Value::Bytes(self.take(length)?.to_vec()))
+            }
+            TC_BLOCKDATALONG => {
+                let length = usize::try_from(self.i32()?).ok()?;
+                Some(Value::Bytes(self.take(length)?.to_vec()))
+            }
+            TC_RESET => {
+                self.handles.clear();
+                self.content(depth)
+            }
+            TC_EXCEPTION => {
+                self.handles.clear();
+                let throwable = self.content(depth + 1)?;
+                self.handles.clear();
+                Some(Value::Object(
+                    "exception".to_string(),
+                    vec![("thrown".to_string(), throwable)],
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads a class description, which may be null (`Some(None)`) or a
+    /// reference to one read before
+    fn class_desc(&mut self, depth: usize) -> Option<Option<Rc<ClassDesc>>> {
+        match self.u8()? {
+            TC_NULL => Some(None),
+            TC_REFERENCE => match self.handle()? {
+                Handle::Desc(desc) => Some(Some(Rc::clone(desc))),
+                Handle::Value(_) => None,
+            },
+            TC_CLASSDESC => {
+                let name = self.utf()?;
+                let _serial_version_uid = self.u64()?;
+                let handle = self.handles.len();
+                self.handles.push(Handle::Value(Value::Null));
+                let flags = self.u8()?;
+                let count = self.u16()?;
+                let mut fields = Vec::with_capacity(count.into());
+                for _ in 0..count {
+                    let type_code = self.u8()?;
+                    let field_name = self.utf()?;
+                    if matches!(type_code, b'[' | b'L') {
+                        // The field's class name, as a string or a reference to one
+                        if !matches!(self.content(depth + 1)?, Value::Str(_)) {
+                            return None;
+                        }
+                    }
+                    fields.push((type_code, field_name));
+                }
+                self.annotation(depth)?;
+                let super_desc = self.class_desc(depth + 1)?;
+                let desc = Rc::new(ClassDesc {
+                    name,
+                    flags,
+                    fields,
+                    super_desc,
+                });
+                self.handles[handle] = Handle::Desc(Rc::clone(&desc));
+                Some(Some(desc))
+            }
+            TC_PROXYCLASSDESC => {
+                let handle = self.handles.len();
+                self.handles.push(Handle::Value(Value::Null));
+                let count = usize::try_from(self.i32()?).ok()?;
+                if count > self.bytes.len() - self.position {
+                    return None;
+                }
+                let interfaces = (0..count).map(|_| self.utf()).collect::<Option<Vec<_>>>()?;
+                self.annotation(depth)?;
+                let super_desc = self.class_desc(depth + 1)?;
+                let desc = Rc::new(ClassDesc {
+                    name: format!("Proxy({})", interfaces.join(", ")),
+                    flags: SC_SERIALIZABLE,
+                    fields: Vec::new(),
+                    super_desc,
+                });
+                self.handles[handle] = Handle::Desc(Rc::clone(&desc));
+                Some(Some(desc))
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads the fields of an object of class `desc`, superclasses first
+    fn class_data(&mut self, desc: &Rc<ClassDesc>, depth: usize) -> Option<Vec<(String, Value)>> {
+        let mut hierarchy = vec![Rc::clone(desc)];
+        while let Some(super_desc) = &hierarchy[hierarchy.len() - 1].super_desc {
+            hierarchy.push(Rc::clone(super_desc));
+        }
+        let mut fields = Vec::new();
+        for class in hierarchy.iter().rev() {
+            if class.flags & SC_EXTERNALIZABLE != 0 {
+                // Externalizable data without block data markers can't be
+                // read without the class
+                if class.flags & SC_BLOCK_DATA == 0 {
+                    return None;
+                }
+                let data = self.annotation(depth)?;
+                fields.push(("external data".to_string(), Value::List(None, data)));
+                continue;
+            }
+            if class.flags & SC_SERIALIZABLE == 0 {
+                continue;
+            }
+            for (type_code, name) in &class.fields {
+                let value = self.field_value(*type_code, depth)?;
+                fields.push((name.clone(), value));
+            }
+            if class.flags & SC_WRITE_METHOD != 0 {
+                let data = self.annotation(depth)?;
+                if !data.is_empty() {
+                    let label = format!("written by {}.writeObject", type_name(&class.name));
+                    fields.push((label, Value::List(None, data)));
+                }
+            }
+        }
+        Some(fields)
+    }
+
+    /// Reads a value of the type with `type_code`, as in a field or array
+    fn field_value(&mut self, type_code: u8, depth: usize) -> Option<Value> {
+        let value = match type_code {
+            b'B' => Value::Int((self.u8()? as i8).to_string()),
+            b'C' => Value::Str(char::from_u32(self.u16()?.into())?.to_string()),
+            b'D' => Value::Float(f64::from_bits(self.u64()?)),
+            b'F' => Value::Float(f32::from_bits(self.u32()?).into()),
+            b'I' => Value::Int(self.i32()?.to_string()),
+            b'J' => Value::Int((self.u64()? as i64).to_string()),
+            b'S' => Value::Int((self.u16()? as i16).to_string()),
+            b'Z' => Value::Bool(self.u8()? != 0),
+            b'[' | b'L' => self.content(depth + 1)?,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Reads items up to the end of block data marker
+    fn annotation(&mut self, depth: usize) -> Option<Vec<Value>> {
+        let mut items = Vec::new();
+        loop {
+            if *self.bytes.get(self.position)? == TC_ENDBLOCKDATA {
+                self.position += 1;
+                return Some(items);
+            }
+            items.push(self.content(depth + 1)?);
+        }
+    }
+
+    /// Reads a string's modified UTF-8 and gives it a handle
+    fn string(&mut self, length: usize) -> Option<Value> {
+        let bytes = self.take(length)?;
+        let value = Value::Str(modified_utf8(bytes)?);
+        let handle = self.handles.len();
+        self.handles.push(Handle::Value(
+            value.referenced(|| format!("<reference to string {handle}>")),
+        ));
+        Some(value)
+    }
+
+    /// Reads a handle and what it refers to
+    fn handle(&mut self) -> Option<&Handle> {
+        let index = self.u32()?.checked_sub(BASE_WIRE_HANDLE)?;
+        self.handles.get(usize::try_from(index).ok()?)
+    }
+
+    /// Reads a string with a two byte length, as class and field names are
+    fn utf(&mut self) -> Option<String> {
+        let length = self.u16()?.into();
+        modified_utf8(self.take(length)?)
+    }
+
+    /// The next `length` bytes
+    fn take(&mut self, length: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(length)?;
+        let taken = self.bytes.get(self.position..end)?;
+        self.position = end;
+        Some(taken)
+    }
+
+    /// The next `N` bytes as an array
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    /// Reads a byte
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.array::<1>()?[0])
+    }
+
+    /// Reads a big-endian u16
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.array()?))
+    }
+
+    /// Reads a big-endian u32
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.array()?))
+    }
+
+    /// Reads a big-endian i32
+    fn i32(&mut self) -> Option<i32> {
+        Some(i32::from_be_bytes(self.array()?))
+    }
+
+    /// Reads a big-endian u64
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_be_bytes(self.array()?))
+    }
+}
+
+/// Java's modified UTF-8, which only differs from UTF-8 in writing NUL as
+/// two bytes and characters outside the BMP as surrogate pairs
+fn modified_utf8(bytes: &[u8]) -> Option<String> {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Some(text.to_string());
+    }
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut rest = bytes;
+    while let Some((&first, tail)) = rest.split_first() {
+        let (unit, used) = match first {
+            0x00..=0x7f => (u16::from(first), 0),
+            0xc0..=0xdf => {
+                let second = *tail.first()?;
+                ((u16::from(first & 0x1f) << 6) | u16::from(second & 0x3f), 1)
+            }
+            0xe0..=0xef => {
+                let (second, third) = (*tail.first()?, *tail.get(1)?);
+                (
+                    (u16::from(first & 0x0f) << 12)
+                        | (u16::from(second & 0x3f) << 6)
+                        | u16::from(third & 0x3f),
+                    2,
+                )
+            }
+            _ => return None,
+        };
+        units.push(unit);
+        rest = &tail[used..];
+    }
+    String::from_utf16(&units).ok()
+}
+
+/// A class name as Java source writes it, e.g. `int[]` for `[I` and
+/// `java.lang.String[]` for `[Ljava.lang.String;`
+fn type_name(name: &str) -> String {
+    let Some(component) = name.strip_prefix('[') else {
+        return name.to_string();
+    };
+    let component = match component.as_bytes().first() {
+        Some(b'B') => "byte".to_string(),
+        Some(b'C') => "char".to_string(),
+        Some(b'D') => "double".to_string(),
+        Some(b'F') => "float".to_string(),
+        Some(b'I') => "int".to_string(),
+        Some(b'J') => "long".to_string(),
+        Some(b'S') => "short".to_string(),
+        Some(b'Z') => "boolean".to_string(),
+        Some(b'[') => type_name(component),
+        _ => component
+            .strip_prefix('L')
+            .and_then(|class| class.strip_suffix(';'))
+            .unwrap_or(component)
+            .to_string(),
+    };
+    format!("{component}[]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    /// A stream holding one `Point` with an int `x` and a String `label`,
+    /// then an int[] of 1 and 2
+    fn point_stream() -> Vec<u8> {
+        let mut stream = STREAM_HEADER.to_vec();
+        stream.extend([TC_OBJECT, TC_CLASSDESC, 0x00, 0x05]);
+        stream.extend(b"Point");
+        stream.extend([0; 8]);
+        stream.extend([SC_SERIALIZABLE, 0x00, 0x02]);
+        stream.extend([b'I', 0x00, 0x01, b'x']);
+        stream.extend([b'L', 0x00, 0x05]);
+        stream.extend(b"label");
+        stream.extend([TC_STRING, 0x00, 0x12]);
+        stream.extend(b"Ljava/lang/String;");
+        stream.extend([TC_ENDBLOCKDATA, TC_NULL]);
+        stream.extend([0x00, 0x00, 0x00, 0x07]);
+        stream.extend([TC_STRING, 0x00, 0x06]);
+        stream.extend(b"origin");
+        stream.extend([TC_ARRAY, TC_CLASSDESC, 0x00, 0x02, b'[', b'I']);
+        stream.extend([0; 8]);
+        stream.extend([SC_SERIALIZABLE, 0x00, 0x00, TC_ENDBLOCKDATA, TC_NULL]);
+        stream.extend([0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2]);
+        stream
+    }
+
+    #[test]
+    fn java_serialization_dumps_objects_and_arrays() {
+        let decoder = Decoder::<JavaSerializationDecoder>::new();
+        let candidate = Candidate::from_bytes(point_stream());
+        let result = decoder.crack_candidate(&candidate, &get_checker(), &Config::default());
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "[\n  Point {\n    x: 7\n    label: \"origin\"\n  }\n  int[] [\n    1\n    2\n  ]\n]"
+        );
+    }
+
+    #[test]
+    fn java_serialization_in_base64() {
+        use base64::{engine::general_purpose, Engine as _};
+        let decoder = Decoder::<JavaSerializationDecoder>::new();
+        let base64 = general_purpose::STANDARD.encode(point_stream());
+        assert!(base64.starts_with(BASE64_PREFIX));
+        let result = decoder.crack(&base64, &get_checker(), &Config::default());
+        assert!(result.success);
+        let steps: Vec<_> = result.inner_steps.iter().map(|step| step.decoder).collect();
+        assert_eq!(steps, ["Base64", "Java Serialization"]);
+    }
+
+    #[test]
+    fn java_serialization_needs_the_magic() {
+        assert!(parse(b"\xac\xed\x00\x05").is_none());
+        assert!(parse(&point_stream()[1..]).is_none());
+        assert_eq!(type_name("[[Ljava.lang.String;"), "java.lang.String[][]");
+    }
+}
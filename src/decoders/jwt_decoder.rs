@@ -32,7 +32,7 @@ impl Crack for Decoder<JwtDecoder> {
     /// This function does the actual decoding
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying JWT with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         
         let parts: Vec<&str> = text.split('.').collect();
         if parts.len() != 3 {
@@ -60,7 +60,7 @@ impl Crack for Decoder<JwtDecoder> {
                 let mut checker_result = checker.check(&decoded, config);
                 // Force success since we successfully decoded a JWT structure
                 checker_result.is_identified = true;
-                results.unencrypted_text = Some(vec![decoded]);
+                results.unencrypted_text = Some(vec![decoded.into()]);
                 results.update_checker(&checker_result);
                 return results;
             }
@@ -139,7 +139,7 @@ mod tests {
         
         let result = decoder.crack(jwt, &get_athena_checker(), &crate::config::Config::default());
         assert!(result.unencrypted_text.is_some());
-        let text = &result.unencrypted_text.unwrap()[0];
+        let text = result.unencrypted_text.unwrap()[0].to_string();
         assert!(text.contains("John Doe"));
         assert!(text.contains("HS256"));
     }
@@ -0,0 +1,141 @@
+//! Keyboard layout decoder
+//! Undoes text typed for one keyboard layout on a computer set to another,
+//! as when a Dvorak typist's "hello" comes out of a QWERTY machine as
+//! "jdpps". Tries QWERTY against Dvorak and against French AZERTY, both
+//! ways round. The result's key says which layouts the text was typed between.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use super::keyboard_layouts::{retype, Layout, AZERTY, DVORAK, QWERTY};
+
+use log::trace;
+
+/// The Keyboard Layout decoder
+/// Call: `let decoder = Decoder::<KeyboardLayoutDecoder>::new()` to create a new instance
+/// And then call: `result = decoder.crack(input)` to decode
+pub struct KeyboardLayoutDecoder;
+
+/// The layouts the text may have come out of, each with the one it was
+/// meant for
+const MIXUPS: [(&Layout, &Layout); 4] = [
+    (&QWERTY, &DVORAK),
+    (&DVORAK, &QWERTY),
+    (&QWERTY, &AZERTY),
+    (&AZERTY, &QWERTY),
+];
+
+impl Crack for Decoder<KeyboardLayoutDecoder> {
+    fn new() -> Decoder<KeyboardLayoutDecoder> {
+        Decoder {
+            name: "Keyboard Layout",
+            description: "Undoes text typed for one keyboard layout on a computer set to another, between QWERTY and Dvorak or French AZERTY.",
+            link: "https://en.wikipedia.org/wiki/Keyboard_layout",
+            tags: vec!["keyboard", "qwerty", "dvorak", "azerty", "substitution", "cipher"],
+            popularity: 0.3,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Keyboard Layout with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let readings: Vec<(String, String)> = MIXUPS
+            .iter()
+            .filter_map(|(typed, meant)| {
+                let decoded = retype(text, typed, meant)?;
+                Some((format!("{} to {}", typed.name, meant.name), decoded))
+            })
+            .filter(|(_, decoded)| check_string_success(decoded, text))
+            .collect();
+        for (mixup, decoded) in &readings {
+            let checker_result = checker.check(decoded, config);
+            if checker_result.is_identified {
+                results.unencrypted_text = Some(vec![decoded.clone().into()]);
+                results.key = Some(mixup.clone());
+                results.update_checker(&checker_result);
+                return results;
+            }
+        }
+
+        // No reading was recognised, but any may be encoded again
+        if !readings.is_empty() {
+            results.unencrypted_text = Some(
+                readings
+                    .into_iter()
+                    .map(|(_, decoded)| decoded.into())
+                    .collect(),
+            );
+        }
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn keyboard_layout_dvorak_on_qwerty() {
+        let decoder = Decoder::<KeyboardLayoutDecoder>::new();
+        let result = decoder.crack(
+            "Mddk md ak kjd sph noghud ak mghlgujk",
+            &get_checker(),
+            &Config::default(),
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "Meet me at the old bridge at midnight"
+        );
+        assert_eq!(result.key.as_deref(), Some("QWERTY to Dvorak"));
+    }
+
+    #[test]
+    fn keyboard_layout_azerty_on_qwerty() {
+        let decoder = Decoder::<KeyboardLayoutDecoder>::new();
+        let result = decoder.crack(
+            ":eet ;e qt the old bridge qt ;idnight",
+            &get_checker(),
+            &Config::default(),
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "Meet me at the old bridge at midnight"
+        );
+        assert_eq!(result.key.as_deref(), Some("QWERTY to AZERTY"));
+    }
+}
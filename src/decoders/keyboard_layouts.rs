@@ -0,0 +1,133 @@
+//! Keyboard layouts, shared by the Keyboard Shift and Keyboard Layout decoders
+//!
+//! A layout is written as the characters of the 47 keys of a US keyboard,
+//! row by row from the number row, once without and once with shift held.
+//! Two layouts line up key for key, so retyping text from one to the other is
+//! looking each character up in one and reading the other at the same place.
+
+/// How many keys each row has, from the number row down
+const ROWS: [usize; 4] = [13, 13, 11, 10];
+
+/// A keyboard layout
+pub struct Layout {
+    /// The layout's name, e.g. `QWERTY`
+    pub name: &'static str,
+    /// The characters of each key, without and with shift
+    keys: [&'static str; 2],
+}
+
+/// The US QWERTY layout
+pub const QWERTY: Layout = Layout {
+    name: "QWERTY",
+    keys: [
+        "`1234567890-=qwertyuiop[]\\asdfghjkl;'zxcvbnm,./",
+        "~!@#$%^&*()_+QWERTYUIOP{}|ASDFGHJKL:\"ZXCVBNM<>?",
+    ],
+};
+
+/// The US Dvorak layout
+pub const DVORAK: Layout = Layout {
+    name: "Dvorak",
+    keys: [
+        "`1234567890[]',.pyfgcrl/=\\aoeuidhtns-;qjkxbmwvz",
+        "~!@#$%^&*(){}\"<>PYFGCRL?+|AOEUIDHTNS_:QJKXBMWVZ",
+    ],
+};
+
+/// The French AZERTY layout, its keys placed where they sit on a US keyboard
+pub const AZERTY: Layout = Layout {
+    name: "AZERTY",
+    keys: [
+        "²&é\"'(-è_çà)=azertyuiop^$*qsdfghjklmùwxcvbn,;:!",
+        "³1234567890°+AZERTYUIOP¨£µQSDFGHJKLM%WXCVBN?./§",
+    ],
+};
+
+impl Layout {
+    /// The key `c` is on and whether shift is held for it
+    fn find(&self, c: char) -> Option<(usize, usize)> {
+        self.keys.iter().enumerate().find_map(|(level, keys)| {
+            keys.chars()
+                .position(|key| key == c)
+                .map(|key| (key, level))
+        })
+    }
+
+    /// The character of a key, with or without shift
+    fn char_at(&self, key: usize, level: usize) -> Option<char> {
+        self.keys[level].chars().nth(key)
+    }
+}
+
+/// The text as it was meant to be typed on `to`, when it was typed on a
+/// keyboard set to `from`. Characters which aren't on keys, like spaces, are
+/// kept, but any other character `from` can't type means it wasn't typed on
+/// it, so there's no answer.
+pub fn retype(text: &str, from: &Layout, to: &Layout) -> Option<String> {
+    text.chars()
+        .map(|c| match from.find(c) {
+            Some((key, level)) => to.char_at(key, level),
+            None if c.is_ascii() => Some(c),
+            None => None,
+        })
+        .collect()
+}
+
+/// The text as it was meant to be typed, when every key was typed `offset`
+/// keys to the right (or left, if negative) of the one meant. Typing off the
+/// end of a row can't happen, so text which would need it has no answer.
+pub fn shift(text: &str, layout: &Layout, offset: isize) -> Option<String> {
+    text.chars()
+        .map(|c| {
+            let Some((key, level)) = layout.find(c) else {
+                return Some(c);
+            };
+            let (mut row_start, mut row) = (0, 0);
+            while key >= row_start + ROWS[row] {
+                row_start += ROWS[row];
+                row += 1;
+            }
+            let meant = (key - row_start).checked_add_signed(-offset)?;
+            if meant >= ROWS[row] {
+                return None;
+            }
+            layout.char_at(row_start + meant, level)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layouts_have_every_key_once() {
+        for layout in [QWERTY, DVORAK, AZERTY] {
+            let keys: Vec<char> = layout.keys.concat().chars().collect();
+            assert_eq!(
+                keys.len(),
+                2 * ROWS.iter().sum::<usize>(),
+                "{}",
+                layout.name
+            );
+            for (index, key) in keys.iter().enumerate() {
+                assert!(
+                    !keys[index + 1..].contains(key),
+                    "{} has {key} twice",
+                    layout.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn shifting_stays_in_the_row() {
+        assert_eq!(
+            shift("jr;;p ept;f", &QWERTY, 1).as_deref(),
+            Some("hello world")
+        );
+        assert_eq!(shift("Gwkki", &QWERTY, -1).as_deref(), Some("Hello"));
+        // Nothing typed one key right is the first key of a row
+        assert_eq!(shift("aqua", &QWERTY, 1), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Keyboard shift decoder
+//! Undoes text typed with the hands one key to the right or left on a QWERTY
+//! keyboard, so "hello" became "jr;;p" or "gwkki". The result's key says
+//! which way the text was typed.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use super::keyboard_layouts::{shift, QWERTY};
+
+use log::trace;
+
+/// The Keyboard Shift decoder
+/// Call: `let decoder = Decoder::<KeyboardShiftDecoder>::new()` to create a new instance
+/// And then call: `result = decoder.crack(input)` to decode
+pub struct KeyboardShiftDecoder;
+
+/// The shifts tried, as how many keys right they were typed and what the
+/// result's key calls them
+const SHIFTS: [(isize, &str); 2] = [(1, "one key right"), (-1, "one key left")];
+
+impl Crack for Decoder<KeyboardShiftDecoder> {
+    fn new() -> Decoder<KeyboardShiftDecoder> {
+        Decoder {
+            name: "Keyboard Shift",
+            description: "Undoes text typed with the hands one key to the right or left of where they should be on a QWERTY keyboard.",
+            link: "https://www.dcode.fr/keyboard-shift-cipher",
+            tags: vec!["keyboard", "qwerty", "substitution", "cipher"],
+            popularity: 0.4,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Keyboard Shift with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let readings: Vec<(&str, String)> = SHIFTS
+            .iter()
+            .filter_map(|(offset, name)| Some((*name, shift(text, &QWERTY, *offset)?)))
+            .filter(|(_, decoded)| check_string_success(decoded, text))
+            .collect();
+        for (name, decoded) in &readings {
+            let checker_result = checker.check(decoded, config);
+            if checker_result.is_identified {
+                results.unencrypted_text = Some(vec![decoded.clone().into()]);
+                results.key = Some(name.to_string());
+                results.update_checker(&checker_result);
+                return results;
+            }
+        }
+
+        // Neither way was recognised, but either may be encoded again
+        if !readings.is_empty() {
+            results.unencrypted_text = Some(
+                readings
+                    .into_iter()
+                    .map(|(_, decoded)| decoded.into())
+                    .collect(),
+            );
+        }
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn keyboard_shift_right() {
+        let decoder = Decoder::<KeyboardShiftDecoder>::new();
+        let result = decoder.crack(
+            "Yjr wiovl ntpem gpc ki,[d pbrt yjr ;sxu fph",
+            &get_checker(),
+            &Config::default(),
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "The quick brown fox jumps over the lazy dog"
+        );
+        assert_eq!(result.key.as_deref(), Some("one key right"));
+    }
+
+    #[test]
+    fn keyboard_shift_left() {
+        let decoder = Decoder::<KeyboardShiftDecoder>::new();
+        let result = decoder.crack(
+            "U rgubj rgw oweaib qgi sus rgua ua tiye veirgwe",
+            &get_checker(),
+            &Config::default(),
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "I think the person who did this is your brother"
+        );
+        assert_eq!(result.key.as_deref(), Some("one key left"));
+    }
+}
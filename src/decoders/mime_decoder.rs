@@ -0,0 +1,257 @@
+//! Decode data: URIs and MIME parts
+//! Performs error handling and returns a string
+//!
+//! Both declare what their payload is and how it was encoded: a data: URI
+//! like `data:text/plain;base64,aGk=` in the URI, a MIME part in its
+//! `Content-Type` and `Content-Transfer-Encoding` headers. The payload is
+//! decoded as declared, and the media type is reported as the step's key.
+
+use crate::byte_text::Candidate;
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use base64::{engine::general_purpose, Engine as _};
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use log::trace;
+
+/// The media type of data: URIs which don't declare one
+const DEFAULT_DATA_URI_TYPE: &str = "text/plain;charset=US-ASCII";
+
+/// The media type of MIME parts which don't declare one
+const DEFAULT_MIME_TYPE: &str = "text/plain";
+
+/// The data: URI and MIME part decoder, call:
+/// `let mime_decoder = Decoder::<MimeDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = mime_decoder.crack(input)` to decode the payload
+/// ```
+/// use ares::decoders::mime_decoder::MimeDecoder;
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decoder = Decoder::<MimeDecoder>::new();
+/// let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+///
+/// let result = decoder.crack("data:text/plain;base64,aGVsbG8gd29ybGQ=", &checker, &ares::config::Config::default());
+/// assert_eq!(result.unencrypted_text.unwrap()[0], "hello world");
+/// assert_eq!(result.key.as_deref(), Some("text/plain"));
+/// ```
+pub struct MimeDecoder;
+
+impl Crack for Decoder<MimeDecoder> {
+    fn new() -> Decoder<MimeDecoder> {
+        Decoder {
+            name: "MIME",
+            description: "Decodes the payload of a data: URI or a MIME part, such as an email attachment, as its headers declare it was encoded, and reports its media type.",
+            link: "https://en.wikipedia.org/wiki/Data_URI_scheme",
+            tags: vec!["mime", "data-uri", "email", "decoder"],
+            popularity: 0.6,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying MIME with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let Some((media_type, payload)) = decode_payload(text) else {
+            return results;
+        };
+        let candidate = Candidate::from_bytes(payload);
+        let checker_result = checker.check(&candidate.text(), config);
+        results.unencrypted_text = Some(vec![candidate]);
+        results.update_checker(&checker_result);
+        results.key = Some(media_type);
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+    /// The key is the media type reported when cracking, so recipes made
+    /// from a path can be run again. The payload is decoded as the text
+    /// itself declares, whatever the key.
+    fn decode_with_key(&self, text: &str, _key: &str) -> Result<String, String> {
+        decode_payload(text)
+            .map(|(_, payload)| Candidate::from_bytes(payload).into_text())
+            .ok_or_else(|| "MIME needs a data: URI or a MIME part with headers".to_string())
+    }
+}
+
+/// The declared media type and the decoded payload of a data: URI or MIME part
+fn decode_payload(text: &str) -> Option<(String, Vec<u8>)> {
+    let text = text.trim();
+    if text
+        .get(..5)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+    {
+        decode_data_uri(&text[5..])
+    } else {
+        decode_mime_part(text)
+    }
+}
+
+/// Decodes what follows `data:`, i.e. `[<media type>][;base64],<data>`
+fn decode_data_uri(uri: &str) -> Option<(String, Vec<u8>)> {
+    let (header, data) = uri.split_once(',')?;
+    let (media_type, is_base64) = match header.strip_suffix(";base64") {
+        Some(media_type) => (media_type, true),
+        None => (header, false),
+    };
+    let payload = if is_base64 {
+        let data: String = urlencoding::decode(data)
+            .ok()?
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        general_purpose::STANDARD
+            .decode(&data)
+            .or_else(|_| general_purpose::STANDARD_NO_PAD.decode(&data))
+            .or_else(|_| general_purpose::URL_SAFE.decode(&data))
+            .ok()?
+    } else {
+        urlencoding::decode_binary(data.as_bytes()).into_owned()
+    };
+    if payload.is_empty() {
+        return None;
+    }
+    let media_type = if media_type.is_empty() {
+        DEFAULT_DATA_URI_TYPE.to_string()
+    } else {
+        media_type.to_string()
+    };
+    Some((media_type, payload))
+}
+
+/// Decodes the body of a single MIME part, as its Content-Transfer-Encoding
+/// says. Parts need a Content-Type or Content-Transfer-Encoding header, and
+/// multipart ones are left alone.
+fn decode_mime_part(part: &str) -> Option<(String, Vec<u8>)> {
+    let (head, body) = part
+        .split_once("\r\n\r\n")
+        .or_else(|| part.split_once("\n\n"))?;
+
+    let mut content_type = None;
+    let mut encoding = None;
+    // Headers may be folded onto the lines after them
+    let unfolded = head
+        .replace("\r\n", "\n")
+        .replace("\n ", " ")
+        .replace("\n\t", " ");
+    for line in unfolded.lines() {
+        let (name, value) = line.split_once(':')?;
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-type" => content_type = Some(value.trim().to_string()),
+            "content-transfer-encoding" => encoding = Some(value.trim().to_ascii_lowercase()),
+            _ => {}
+        }
+    }
+    if content_type.is_none() && encoding.is_none() {
+        return None;
+    }
+    let content_type = content_type.unwrap_or_else(|| DEFAULT_MIME_TYPE.to_string());
+    if content_type.to_ascii_lowercase().starts_with("multipart/") {
+        return None;
+    }
+
+    let payload = match encoding.as_deref() {
+        Some("base64") => {
+            let data: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+            general_purpose::STANDARD.decode(data).ok()?
+        }
+        Some("quoted-printable") => {
+            quoted_printable::decode(body, quoted_printable::ParseMode::Robust).ok()?
+        }
+        None | Some("7bit" | "8bit" | "binary") => body.as_bytes().to_vec(),
+        Some(_) => return None,
+    };
+    if payload.is_empty() {
+        return None;
+    }
+    // Parameters like the charset aren't part of the media type
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    Some((media_type, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MimeDecoder;
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn mime_percent_encoded_data_uri() {
+        let decoder = Decoder::<MimeDecoder>::new();
+        let result = decoder.crack(
+            "data:,Hello%2C%20World%21",
+            &get_checker(),
+            &crate::config::Config::default(),
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "Hello, World!");
+        assert_eq!(result.key.as_deref(), Some("text/plain;charset=US-ASCII"));
+    }
+
+    #[test]
+    fn mime_base64_part() {
+        let decoder = Decoder::<MimeDecoder>::new();
+        let part = "Content-Type: text/plain;\r\n charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\naGVsbG8g\r\nd29ybGQ=\r\n";
+        let result = decoder.crack(part, &get_checker(), &crate::config::Config::default());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "hello world");
+        assert_eq!(result.key.as_deref(), Some("text/plain"));
+    }
+
+    #[test]
+    fn mime_quoted_printable_part() {
+        let decoder = Decoder::<MimeDecoder>::new();
+        let part = "Content-Transfer-Encoding: quoted-printable\n\nCaf=C3=A9 au lait";
+        let result = decoder.crack(part, &get_checker(), &crate::config::Config::default());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "Café au lait");
+        assert_eq!(result.key.as_deref(), Some("text/plain"));
+    }
+
+    #[test]
+    fn mime_ignores_other_text() {
+        let decoder = Decoder::<MimeDecoder>::new();
+        let config = crate::config::Config::default();
+        assert!(decoder
+            .crack("hello world", &get_checker(), &config)
+            .unencrypted_text
+            .is_none());
+        assert!(decoder
+            .crack(
+                "Subject: hi\n\nno encoding headers here",
+                &get_checker(),
+                &config
+            )
+            .unencrypted_text
+            .is_none());
+    }
+}
@@ -4,10 +4,16 @@
 //! Once you have made a decoder you need to add it to the filtration system's
 //! mod.rs file
 //! you will also need to make it a public module in this file.
+//!
+//! Decoders which need extra crates or which only some users want are behind
+//! features: `classical`, `compression`, `crypto`, `esolang` and `hashes`, all
+//! on by default. A decoder behind one is left out of the maps without it.
 
 /// The a1z26_decoder module decodes A1Z26
+#[cfg(feature = "classical")]
 pub mod a1z26_decoder;
 /// The atbash_decoder module decodes atbash
+#[cfg(feature = "classical")]
 pub mod atbash_decoder;
 /// The base32_decoder module decodes base32
 pub mod base32_decoder;
@@ -34,6 +40,7 @@ pub mod base65536_decoder;
 /// The base91_decoder module decodes base91
 pub mod base91_decoder;
 /// The citrix_ctx1_decoder module decodes citrix ctx1
+#[cfg(feature = "crypto")]
 pub mod citrix_ctx1_decoder;
 /// The crack_results module defines the CrackResult
 /// Each and every decoder return same CrackResult
@@ -55,11 +62,14 @@ pub mod reverse_decoder;
 pub mod morse_code;
 
 /// For the caesar cipher decoder
+#[cfg(feature = "classical")]
 pub mod caesar_decoder;
 
 /// For the railfence cipher decoder
+#[cfg(feature = "classical")]
 pub mod railfence_decoder;
 /// For the rot47 decoder
+#[cfg(feature = "classical")]
 pub mod rot47_decoder;
 
 /// For the z85 cipher decoder
@@ -69,12 +79,15 @@ pub mod z85_decoder;
 pub mod braille_decoder;
 
 /// The substitution_generic_decoder module handles generic substitution ciphers
+#[cfg(feature = "classical")]
 pub mod substitution_generic_decoder;
 
 /// A brainfuck interpreter
+#[cfg(feature = "esolang")]
 pub mod brainfuck_interpreter;
 
 /// The vigenere_decoder module decodes Vigenère cipher text
+#[cfg(feature = "classical")]
 pub mod vigenere_decoder;
 
 // New Decoders
@@ -87,32 +100,102 @@ pub mod punycode_decoder;
 pub mod quoted_printable_decoder;
 pub mod uuencode_decoder;
 pub mod base45_decoder;
+#[cfg(feature = "classical")]
 pub mod bacon_cipher_decoder;
 pub mod base32hex_decoder;
+#[cfg(feature = "classical")]
 pub mod affine_cipher;
+#[cfg(feature = "classical")]
 pub mod beaufort_decoder;
+#[cfg(feature = "crypto")]
 pub mod xor_decoder;
+#[cfg(feature = "hashes")]
 pub mod hash_crack_decoder;
 pub mod jwt_decoder;
+/// Decompresses gzip data
+#[cfg(feature = "compression")]
+pub mod gzip_decoder;
+/// Decodes Base64 and decompresses the gzip inside it in one step
+#[cfg(feature = "compression")]
+pub mod base64_gzip_decoder;
+/// Decodes the payloads of data: URIs and MIME parts
+pub mod mime_decoder;
+/// Decodes HTTP Basic authentication credentials
+pub mod basic_auth_decoder;
+/// Decodes SAML requests and responses
+#[cfg(feature = "compression")]
+pub mod saml_decoder;
+/// Decodes Base64 cookie values
+pub mod cookie_decoder;
+/// Values read from serialized objects, and how they are printed
+pub mod serialized_value;
+/// Prints PHP serialize() data
+pub mod php_serialize_decoder;
+/// Prints Java serialization streams
+pub mod java_serialization_decoder;
+/// Prints Python pickles without loading them
+pub mod pickle_decoder;
+/// Decodes geohashes, Maidenhead locators and MGRS to latitude and longitude
+pub mod coordinates_decoder;
+/// Reads a big number in any base from 2 to 36 as bytes
+pub mod base_n_decoder;
+/// Reads text written with a prefix code, like a Huffman table
+pub mod prefix_code_decoder;
 
 // Classical cipher decoders
 /// Polybius Square cipher decoder
+#[cfg(feature = "classical")]
 pub mod polybius_square_decoder;
 /// Columnar Transposition cipher decoder
+#[cfg(feature = "classical")]
 pub mod columnar_transposition_decoder;
 /// Tap Code (Prisoner's tap code) decoder
+#[cfg(feature = "classical")]
 pub mod tap_code_decoder;
 /// ROT5 decoder (rotates digits by 5)
+#[cfg(feature = "classical")]
 pub mod rot5_decoder;
 /// ROT18 decoder (combines ROT13 and ROT5)
+#[cfg(feature = "classical")]
 pub mod rot18_decoder;
 /// Playfair cipher decoder
+#[cfg(feature = "classical")]
 pub mod playfair_decoder;
 /// Four Square cipher decoder
+#[cfg(feature = "classical")]
 pub mod four_square_decoder;
 /// Monoalphabetic substitution cipher solver using hill climbing
+#[cfg(feature = "classical")]
 pub mod monoalphabetic_solver;
+/// Chess move cipher decoder
+#[cfg(feature = "classical")]
+pub mod chess_decoder;
+/// Playing card decoder
+#[cfg(feature = "classical")]
+pub mod playing_card_decoder;
+/// Dice roll decoder
+#[cfg(feature = "classical")]
+pub mod dice_decoder;
+/// Keyboard layouts, shared by the keyboard decoders
+#[cfg(feature = "classical")]
+pub mod keyboard_layouts;
+/// Keyboard shift decoder
+#[cfg(feature = "classical")]
+pub mod keyboard_shift_decoder;
+/// Keyboard layout decoder
+#[cfg(feature = "classical")]
+pub mod keyboard_layout_decoder;
+/// Morse code written with digits, shared by the Morbit and Pollux decoders
+#[cfg(feature = "classical")]
+pub mod digit_morse;
+/// Morbit cipher decoder
+#[cfg(feature = "classical")]
+pub mod morbit_decoder;
+/// Pollux cipher decoder
+#[cfg(feature = "classical")]
+pub mod pollux_decoder;
 
+#[cfg(feature = "classical")]
 use atbash_decoder::AtbashDecoder;
 use base32_decoder::Base32Decoder;
 use base58_bitcoin_decoder::Base58BitcoinDecoder;
@@ -121,24 +204,32 @@ use base58_monero_decoder::Base58MoneroDecoder;
 use base58_ripple_decoder::Base58RippleDecoder;
 use binary_decoder::BinaryDecoder;
 use hexadecimal_decoder::HexadecimalDecoder;
-use interface::{Crack, Decoder};
+use interface::{Crack, Decoder, Encode};
 
+#[cfg(feature = "classical")]
 use a1z26_decoder::A1Z26Decoder;
 use base64_decoder::Base64Decoder;
 use base65536_decoder::Base65536Decoder;
 use base91_decoder::Base91Decoder;
 use braille_decoder::BrailleDecoder;
+#[cfg(feature = "classical")]
 use caesar_decoder::CaesarDecoder;
+#[cfg(feature = "crypto")]
 use citrix_ctx1_decoder::CitrixCTX1Decoder;
 use morse_code::MorseCodeDecoder;
+#[cfg(feature = "classical")]
 use railfence_decoder::RailfenceDecoder;
 use reverse_decoder::ReverseDecoder;
+#[cfg(feature = "classical")]
 use rot47_decoder::ROT47Decoder;
+#[cfg(feature = "classical")]
 use substitution_generic_decoder::SubstitutionGenericDecoder;
 use url_decoder::URLDecoder;
+#[cfg(feature = "classical")]
 use vigenere_decoder::VigenereDecoder;
 use z85_decoder::Z85Decoder;
 
+#[cfg(feature = "esolang")]
 use brainfuck_interpreter::BrainfuckInterpreter;
 
 // Use new decoders
@@ -151,26 +242,69 @@ use punycode_decoder::PunycodeDecoder;
 use quoted_printable_decoder::QuotedPrintableDecoder;
 use uuencode_decoder::UUEncodeDecoder;
 use base45_decoder::Base45Decoder;
+#[cfg(feature = "classical")]
 use bacon_cipher_decoder::BaconCipherDecoder;
 use base32hex_decoder::Base32HexDecoder;
+#[cfg(feature = "classical")]
 use affine_cipher::AffineCipherDecoder;
+#[cfg(feature = "classical")]
 use beaufort_decoder::BeaufortDecoder;
+#[cfg(feature = "crypto")]
 use xor_decoder::XorDecoder;
+#[cfg(feature = "hashes")]
 use hash_crack_decoder::HashCrackDecoder;
 use jwt_decoder::JwtDecoder;
+#[cfg(feature = "compression")]
+use gzip_decoder::GzipDecoder;
+#[cfg(feature = "compression")]
+use base64_gzip_decoder::Base64GzipDecoder;
+use mime_decoder::MimeDecoder;
+use basic_auth_decoder::BasicAuthDecoder;
+#[cfg(feature = "compression")]
+use saml_decoder::SamlDecoder;
+use cookie_decoder::CookieDecoder;
+use php_serialize_decoder::PhpSerializeDecoder;
+use java_serialization_decoder::JavaSerializationDecoder;
+use pickle_decoder::PickleDecoder;
+use coordinates_decoder::CoordinatesDecoder;
+use base_n_decoder::BaseNDecoder;
+use prefix_code_decoder::PrefixCodeDecoder;
 
 // Use classical cipher decoders
+#[cfg(feature = "classical")]
 use polybius_square_decoder::PolybiusSquareDecoder;
+#[cfg(feature = "classical")]
 use columnar_transposition_decoder::ColumnarTranspositionDecoder;
+#[cfg(feature = "classical")]
 use tap_code_decoder::TapCodeDecoder;
+#[cfg(feature = "classical")]
 use rot5_decoder::Rot5Decoder;
+#[cfg(feature = "classical")]
 use rot18_decoder::Rot18Decoder;
+#[cfg(feature = "classical")]
 use playfair_decoder::PlayfairDecoder;
+#[cfg(feature = "classical")]
 use four_square_decoder::FourSquareDecoder;
+#[cfg(feature = "classical")]
 use monoalphabetic_solver::MonoalphabeticSolver;
+#[cfg(feature = "classical")]
+use chess_decoder::ChessDecoder;
+#[cfg(feature = "classical")]
+use playing_card_decoder::PlayingCardDecoder;
+#[cfg(feature = "classical")]
+use dice_decoder::DiceDecoder;
+#[cfg(feature = "classical")]
+use keyboard_shift_decoder::KeyboardShiftDecoder;
+#[cfg(feature = "classical")]
+use keyboard_layout_decoder::KeyboardLayoutDecoder;
+#[cfg(feature = "classical")]
+use morbit_decoder::MorbitDecoder;
+#[cfg(feature = "classical")]
+use pollux_decoder::PolluxDecoder;
 
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 /// Enum for annotating Decoder types, specifically for retrieving decoders from
 /// DECODER_MAP
@@ -178,8 +312,10 @@ pub enum DecoderType {
     /// default decoder
     DefaultDecoder(interface::DefaultDecoder),
     /// a1z26 decoder
+    #[cfg(feature = "classical")]
     A1z26Decoder(a1z26_decoder::A1Z26Decoder),
     /// atbash decoder
+    #[cfg(feature = "classical")]
     AtbashDecoder(atbash_decoder::AtbashDecoder),
     /// base32 decoder
     Base32Decoder(base32_decoder::Base32Decoder),
@@ -202,6 +338,7 @@ pub enum DecoderType {
     /// base91 decoder
     Base91Decoder(base91_decoder::Base91Decoder),
     /// citrix ctx1 decoder
+    #[cfg(feature = "crypto")]
     CitrixCtx1Decoder(citrix_ctx1_decoder::CitrixCTX1Decoder),
     /// url decoder
     UrlDecoder(url_decoder::URLDecoder),
@@ -210,20 +347,26 @@ pub enum DecoderType {
     /// morse decoder
     MorseCode(morse_code::MorseCodeDecoder),
     /// caesar decoder
+    #[cfg(feature = "classical")]
     CaesarDecoder(caesar_decoder::CaesarDecoder),
     /// railfence decoder
+    #[cfg(feature = "classical")]
     RailfenceDecoder(railfence_decoder::RailfenceDecoder),
     /// rot47 decoder
+    #[cfg(feature = "classical")]
     Rot47Decoder(rot47_decoder::ROT47Decoder),
     /// z85 decoder
     Z85Decoder(z85_decoder::Z85Decoder),
     /// braille decoder
     BrailleDecoder(braille_decoder::BrailleDecoder),
     /// substitution decoder
+    #[cfg(feature = "classical")]
     SubstitutionGenericDecoder(substitution_generic_decoder::SubstitutionGenericDecoder),
     /// brainfuck interpreter
+    #[cfg(feature = "esolang")]
     BrainfuckInterpreter(brainfuck_interpreter::BrainfuckInterpreter),
     /// vigenere decoder
+    #[cfg(feature = "classical")]
     VigenereDecoder(vigenere_decoder::VigenereDecoder),
     /// base62 decoder
     Base62Decoder(base62_decoder::Base62Decoder),
@@ -244,35 +387,96 @@ pub enum DecoderType {
     /// base45 decoder
     Base45Decoder(base45_decoder::Base45Decoder),
     /// bacon cipher decoder
+    #[cfg(feature = "classical")]
     BaconCipherDecoder(bacon_cipher_decoder::BaconCipherDecoder),
     /// base32hex decoder
     Base32HexDecoder(base32hex_decoder::Base32HexDecoder),
     /// affine cipher decoder
+    #[cfg(feature = "classical")]
     AffineCipherDecoder(affine_cipher::AffineCipherDecoder),
     /// beaufort decoder
+    #[cfg(feature = "classical")]
     BeaufortDecoder(beaufort_decoder::BeaufortDecoder),
     /// xor decoder
+    #[cfg(feature = "crypto")]
     XorDecoder(xor_decoder::XorDecoder),
     /// hash crack decoder
+    #[cfg(feature = "hashes")]
     HashCrackDecoder(hash_crack_decoder::HashCrackDecoder),
     /// jwt decoder
     JwtDecoder(jwt_decoder::JwtDecoder),
+    /// gzip decoder
+    #[cfg(feature = "compression")]
+    GzipDecoder(gzip_decoder::GzipDecoder),
+    /// base64 gzip decoder
+    #[cfg(feature = "compression")]
+    Base64GzipDecoder(base64_gzip_decoder::Base64GzipDecoder),
+    /// data: URI and MIME part decoder
+    MimeDecoder(mime_decoder::MimeDecoder),
+    /// basic auth decoder
+    BasicAuthDecoder(basic_auth_decoder::BasicAuthDecoder),
+    /// saml decoder
+    #[cfg(feature = "compression")]
+    SamlDecoder(saml_decoder::SamlDecoder),
+    /// cookie decoder
+    CookieDecoder(cookie_decoder::CookieDecoder),
+    /// php serialize decoder
+    PhpSerializeDecoder(php_serialize_decoder::PhpSerializeDecoder),
+    /// java serialization decoder
+    JavaSerializationDecoder(java_serialization_decoder::JavaSerializationDecoder),
+    /// pickle decoder
+    PickleDecoder(pickle_decoder::PickleDecoder),
+    /// coordinates decoder
+    CoordinatesDecoder(coordinates_decoder::CoordinatesDecoder),
+    /// base-n decoder
+    BaseNDecoder(base_n_decoder::BaseNDecoder),
+    /// prefix code decoder
+    PrefixCodeDecoder(prefix_code_decoder::PrefixCodeDecoder),
     /// polybius square decoder
+    #[cfg(feature = "classical")]
     PolybiusSquareDecoder(polybius_square_decoder::PolybiusSquareDecoder),
     /// columnar transposition decoder
+    #[cfg(feature = "classical")]
     ColumnarTranspositionDecoder(columnar_transposition_decoder::ColumnarTranspositionDecoder),
     /// tap code decoder
+    #[cfg(feature = "classical")]
     TapCodeDecoder(tap_code_decoder::TapCodeDecoder),
     /// rot5 decoder
+    #[cfg(feature = "classical")]
     Rot5Decoder(rot5_decoder::Rot5Decoder),
     /// rot18 decoder
+    #[cfg(feature = "classical")]
     Rot18Decoder(rot18_decoder::Rot18Decoder),
     /// playfair decoder
+    #[cfg(feature = "classical")]
     PlayfairDecoder(playfair_decoder::PlayfairDecoder),
     /// four square decoder
+    #[cfg(feature = "classical")]
     FourSquareDecoder(four_square_decoder::FourSquareDecoder),
     /// monoalphabetic solver
+    #[cfg(feature = "classical")]
     MonoalphabeticSolver(monoalphabetic_solver::MonoalphabeticSolver),
+    /// chess moves decoder
+    #[cfg(feature = "classical")]
+    ChessDecoder(chess_decoder::ChessDecoder),
+    /// playing cards decoder
+    #[cfg(feature = "classical")]
+    PlayingCardDecoder(playing_card_decoder::PlayingCardDecoder),
+    /// dice decoder
+    #[cfg(feature = "classical")]
+    DiceDecoder(dice_decoder::DiceDecoder),
+    /// keyboard shift decoder
+    #[cfg(feature = "classical")]
+    KeyboardShiftDecoder(keyboard_shift_decoder::KeyboardShiftDecoder),
+    /// keyboard layout decoder
+    #[cfg(feature = "classical")]
+    KeyboardLayoutDecoder(keyboard_layout_decoder::KeyboardLayoutDecoder),
+    /// morbit decoder
+    #[cfg(feature = "classical")]
+    MorbitDecoder(morbit_decoder::MorbitDecoder),
+    /// pollux decoder
+    #[cfg(feature = "classical")]
+    PolluxDecoder(pollux_decoder::PolluxDecoder),
 }
 
 /// Wrapper struct to hold Decoders for DECODER_MAP
@@ -303,6 +507,7 @@ pub static DECODER_MAP: Lazy<HashMap<&str, DecoderBox>> = Lazy::new(|| {
             "Default decoder",
             DecoderBox::new(Decoder::<interface::DefaultDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Vigenere",
             DecoderBox::new(Decoder::<VigenereDecoder>::new()),
@@ -334,6 +539,7 @@ pub static DECODER_MAP: Lazy<HashMap<&str, DecoderBox>> = Lazy::new(|| {
             "Base65536",
             DecoderBox::new(Decoder::<Base65536Decoder>::new()),
         ),
+        #[cfg(feature = "crypto")]
         (
             "Citrix Ctx1",
             DecoderBox::new(Decoder::<CitrixCTX1Decoder>::new()),
@@ -345,20 +551,27 @@ pub static DECODER_MAP: Lazy<HashMap<&str, DecoderBox>> = Lazy::new(|| {
             "Morse Code",
             DecoderBox::new(Decoder::<MorseCodeDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         ("atbash", DecoderBox::new(Decoder::<AtbashDecoder>::new())),
+        #[cfg(feature = "classical")]
         ("caesar", DecoderBox::new(Decoder::<CaesarDecoder>::new())),
+        #[cfg(feature = "classical")]
         (
             "railfence",
             DecoderBox::new(Decoder::<RailfenceDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         ("rot47", DecoderBox::new(Decoder::<ROT47Decoder>::new())),
         ("Z85", DecoderBox::new(Decoder::<Z85Decoder>::new())),
+        #[cfg(feature = "classical")]
         ("a1z26", DecoderBox::new(Decoder::<A1Z26Decoder>::new())),
         ("Braille", DecoderBox::new(Decoder::<BrailleDecoder>::new())),
+        #[cfg(feature = "classical")]
         (
             "simplesubstitution",
             DecoderBox::new(Decoder::<SubstitutionGenericDecoder>::new()),
         ),
+        #[cfg(feature = "esolang")]
         (
             "Brainfuck",
             DecoderBox::new(Decoder::<BrainfuckInterpreter>::new()),
@@ -399,6 +612,7 @@ pub static DECODER_MAP: Lazy<HashMap<&str, DecoderBox>> = Lazy::new(|| {
             "Base45",
             DecoderBox::new(Decoder::<Base45Decoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Bacon Cipher",
             DecoderBox::new(Decoder::<BaconCipherDecoder>::new()),
@@ -407,18 +621,22 @@ pub static DECODER_MAP: Lazy<HashMap<&str, DecoderBox>> = Lazy::new(|| {
             "Base32Hex",
             DecoderBox::new(Decoder::<Base32HexDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Affine Cipher",
             DecoderBox::new(Decoder::<AffineCipherDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Beaufort Cipher",
             DecoderBox::new(Decoder::<BeaufortDecoder>::new()),
         ),
+        #[cfg(feature = "crypto")]
         (
             "XOR",
             DecoderBox::new(Decoder::<XorDecoder>::new()),
         ),
+        #[cfg(feature = "hashes")]
         (
             "HashCrack",
             DecoderBox::new(Decoder::<HashCrackDecoder>::new()),
@@ -427,37 +645,301 @@ pub static DECODER_MAP: Lazy<HashMap<&str, DecoderBox>> = Lazy::new(|| {
             "JWT",
             DecoderBox::new(Decoder::<JwtDecoder>::new()),
         ),
+        #[cfg(feature = "compression")]
+        (
+            "Gzip",
+            DecoderBox::new(Decoder::<GzipDecoder>::new()),
+        ),
+        #[cfg(feature = "compression")]
+        (
+            "Base64 Gzip",
+            DecoderBox::new(Decoder::<Base64GzipDecoder>::new()),
+        ),
+        (
+            "MIME",
+            DecoderBox::new(Decoder::<MimeDecoder>::new()),
+        ),
+        (
+            "Basic Auth",
+            DecoderBox::new(Decoder::<BasicAuthDecoder>::new()),
+        ),
+        #[cfg(feature = "compression")]
+        ("SAML", DecoderBox::new(Decoder::<SamlDecoder>::new())),
+        ("Cookie", DecoderBox::new(Decoder::<CookieDecoder>::new())),
+        (
+            "PHP Serialize",
+            DecoderBox::new(Decoder::<PhpSerializeDecoder>::new()),
+        ),
+        (
+            "Java Serialization",
+            DecoderBox::new(Decoder::<JavaSerializationDecoder>::new()),
+        ),
+        ("Pickle", DecoderBox::new(Decoder::<PickleDecoder>::new())),
+        (
+            "Coordinates",
+            DecoderBox::new(Decoder::<CoordinatesDecoder>::new()),
+        ),
+        ("Base-N", DecoderBox::new(Decoder::<BaseNDecoder>::new())),
+        (
+            "Prefix Code",
+            DecoderBox::new(Decoder::<PrefixCodeDecoder>::new()),
+        ),
+        #[cfg(feature = "classical")]
         (
             "Polybius Square",
             DecoderBox::new(Decoder::<PolybiusSquareDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Columnar Transposition",
             DecoderBox::new(Decoder::<ColumnarTranspositionDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Tap Code",
             DecoderBox::new(Decoder::<TapCodeDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "ROT5",
             DecoderBox::new(Decoder::<Rot5Decoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "ROT18",
             DecoderBox::new(Decoder::<Rot18Decoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Playfair",
             DecoderBox::new(Decoder::<PlayfairDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Four Square",
             DecoderBox::new(Decoder::<FourSquareDecoder>::new()),
         ),
+        #[cfg(feature = "classical")]
         (
             "Monoalphabetic",
             DecoderBox::new(Decoder::<MonoalphabeticSolver>::new()),
         ),
+        #[cfg(feature = "classical")]
+        ("Chess Moves", DecoderBox::new(Decoder::<ChessDecoder>::new())),
+        #[cfg(feature = "classical")]
+        (
+            "Playing Cards",
+            DecoderBox::new(Decoder::<PlayingCardDecoder>::new()),
+        ),
+        #[cfg(feature = "classical")]
+        ("Dice", DecoderBox::new(Decoder::<DiceDecoder>::new())),
+        #[cfg(feature = "classical")]
+        (
+            "Keyboard Shift",
+            DecoderBox::new(Decoder::<KeyboardShiftDecoder>::new()),
+        ),
+        #[cfg(feature = "classical")]
+        (
+            "Keyboard Layout",
+            DecoderBox::new(Decoder::<KeyboardLayoutDecoder>::new()),
+        ),
+        #[cfg(feature = "classical")]
+        ("Morbit", DecoderBox::new(Decoder::<MorbitDecoder>::new())),
+        #[cfg(feature = "classical")]
+        ("Pollux", DecoderBox::new(Decoder::<PolluxDecoder>::new())),
     ])
 });
+
+/// A decoder shared by every search, see [`register_decoder`]
+type SharedDecoder = &'static (dyn Crack + Sync + Send);
+
+/// Decoders added at runtime with [`register_decoder`]
+static CUSTOM_DECODERS: Lazy<RwLock<Vec<SharedDecoder>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Returns the custom decoders, recovering from a poisoned lock
+fn custom_decoder_list() -> Vec<SharedDecoder> {
+    match CUSTOM_DECODERS.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Adds a decoder to every search from now on, alongside the built in ones in
+/// DECODER_MAP. This lets crates which depend on ares add their own decoders.
+///
+/// Registered decoders are kept for the rest of the process. They are run by the
+/// searcher, listed by `--list-decoders`, can be picked with `--only`/`--exclude`
+/// and run directly with [`crate::perform_decoding`].
+///
+/// ```rust
+/// use ares::checkers::CheckerTypes;
+/// use ares::config::Config;
+/// use ares::decoders::crack_results::CrackResult;
+/// use ares::decoders::interface::Crack;
+///
+/// struct ShoutDecoder {
+///     tags: Vec<&'static str>,
+/// }
+///
+/// impl Crack for ShoutDecoder {
+///     fn new() -> Self {
+///         ShoutDecoder { tags: vec!["shout", "decoder"] }
+///     }
+///     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+///         let mut result = CrackResult::from_decoder_info(
+///             "Shout",
+///             "Lowercases text that was shouted",
+///             "https://example.com",
+///             text.to_string(),
+///         );
+///         let lowered = text.to_lowercase();
+///         result.update_checker(&checker.check(&lowered, config));
+///         result.unencrypted_text = Some(vec![lowered.into()]);
+///         result
+///     }
+///     fn get_tags(&self) -> &Vec<&str> { &self.tags }
+///     fn get_name(&self) -> &str { "Shout" }
+///     fn get_description(&self) -> &str { "Lowercases text that was shouted" }
+///     fn get_link(&self) -> &str { "https://example.com" }
+/// }
+///
+/// ares::register_decoder(Box::new(ShoutDecoder::new())).unwrap();
+/// let results = ares::perform_decoding("HELLO", "Shout", None, &Config::default()).unwrap();
+/// assert_eq!(results[0].unencrypted_text.as_ref().unwrap()[0], "hello");
+/// ```
+///
+/// # Errors
+/// Returns an error if a decoder with the same name already exists.
+pub fn register_decoder(decoder: Box<dyn Crack + Send + Sync>) -> Result<(), String> {
+    let mut custom = match CUSTOM_DECODERS.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let name = decoder.get_name();
+    if DECODER_MAP.contains_key(name) || custom.iter().any(|d| d.get_name() == name) {
+        return Err(format!("A decoder named '{}' already exists", name));
+    }
+    // Searches hold on to decoders for as long as they run, like the ones in DECODER_MAP
+    custom.push(Box::leak(decoder));
+    Ok(())
+}
+
+/// Returns every decoder: the built in ones followed by any registered at runtime
+pub fn all_decoders() -> Vec<SharedDecoder> {
+    DECODER_MAP
+        .values()
+        .map(|decoder_box| decoder_box.get())
+        .chain(custom_decoder_list())
+        .collect()
+}
+
+/// Looks up a built in or registered decoder by its exact name
+pub fn find_decoder(name: &str) -> Option<SharedDecoder> {
+    match DECODER_MAP.get(name) {
+        Some(decoder_box) => Some(decoder_box.get()),
+        None => custom_decoder_list()
+            .into_iter()
+            .find(|decoder| decoder.get_name() == name),
+    }
+}
+
+/// Wrapper struct to hold Encoders for ENCODER_MAP
+pub struct EncoderBox {
+    /// Wrapper box to hold Encoders for ENCODER_MAP
+    value: Box<dyn Encode + Sync + Send>,
+}
+
+impl EncoderBox {
+    /// Constructor for EncoderBox. Takes in a Decoder which implements Encode
+    /// and stores it as the internal value
+    fn new<T: 'static + Encode + Sync + Send>(value: T) -> Self {
+        Self {
+            value: Box::new(value),
+        }
+    }
+
+    /// Getter method for EncoderBox to return the internal Box
+    pub fn get(&self) -> &(dyn Encode + Sync + Send) {
+        self.value.as_ref()
+    }
+}
+
+/// Global hashmap of every decoder which can also encode, keyed by decoder name.
+/// Used by `ares encode`.
+pub static ENCODER_MAP: Lazy<HashMap<&str, EncoderBox>> = Lazy::new(|| {
+    HashMap::from([
+        #[cfg(feature = "classical")]
+        ("a1z26", EncoderBox::new(Decoder::<A1Z26Decoder>::new())),
+        #[cfg(feature = "classical")]
+        ("atbash", EncoderBox::new(Decoder::<AtbashDecoder>::new())),
+        ("Base32", EncoderBox::new(Decoder::<Base32Decoder>::new())),
+        (
+            "Base58 Bitcoin",
+            EncoderBox::new(Decoder::<Base58BitcoinDecoder>::new()),
+        ),
+        ("Base64", EncoderBox::new(Decoder::<Base64Decoder>::new())),
+        ("Binary", EncoderBox::new(Decoder::<BinaryDecoder>::new())),
+        #[cfg(feature = "classical")]
+        ("caesar", EncoderBox::new(Decoder::<CaesarDecoder>::new())),
+        ("Decimal", EncoderBox::new(Decoder::<DecimalDecoder>::new())),
+        (
+            "Hexadecimal",
+            EncoderBox::new(Decoder::<HexadecimalDecoder>::new()),
+        ),
+        ("Morse Code", EncoderBox::new(Decoder::<MorseCodeDecoder>::new())),
+        ("Octal", EncoderBox::new(Decoder::<OctalDecoder>::new())),
+        ("Reverse", EncoderBox::new(Decoder::<ReverseDecoder>::new())),
+        #[cfg(feature = "classical")]
+        ("rot47", EncoderBox::new(Decoder::<ROT47Decoder>::new())),
+        #[cfg(feature = "classical")]
+        ("ROT5", EncoderBox::new(Decoder::<Rot5Decoder>::new())),
+        #[cfg(feature = "classical")]
+        ("ROT18", EncoderBox::new(Decoder::<Rot18Decoder>::new())),
+        ("URL", EncoderBox::new(Decoder::<URLDecoder>::new())),
+        #[cfg(feature = "crypto")]
+        ("XOR", EncoderBox::new(Decoder::<XorDecoder>::new())),
+    ])
+});
+
+#[cfg(test)]
+mod tests {
+    use super::{DECODER_MAP, ENCODER_MAP};
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+        CheckerTypes,
+    };
+    use crate::config::Config;
+
+    #[test]
+    fn encoder_map_keys_match_names() {
+        for (name, encoder) in ENCODER_MAP.iter() {
+            assert_eq!(*name, encoder.get().get_name());
+            assert!(DECODER_MAP.contains_key(name), "{} is not a decoder", name);
+        }
+    }
+
+    #[test]
+    fn every_keyless_encoder_round_trips() {
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let plaintext = "Hello world 123";
+        for (name, encoder) in ENCODER_MAP.iter() {
+            let Ok(encoded) = encoder.get().encode(plaintext) else {
+                // Keyed ciphers and encodings with a restricted alphabet are tested in their own files
+                continue;
+            };
+            let decoded = DECODER_MAP[name]
+                .get()
+                .crack(&encoded, &checker, &Config::default())
+                .unencrypted_text
+                .unwrap_or_default();
+            assert!(
+                decoded.iter().any(|text| text.text().eq_ignore_ascii_case(plaintext)),
+                "{} did not round trip: {:?} -> {:?}",
+                name,
+                encoded,
+                decoded
+            );
+        }
+    }
+}
@@ -5,15 +5,30 @@
 //! A monoalphabetic cipher replaces each letter with another letter consistently
 //! throughout the message. This solver uses statistical analysis to find the
 //! correct key mapping.
+//!
+//! Letters already known, from `Config.letter_map` (`--map E=X,T=Q`) or from
+//! where `Config.crib` fits, are kept fixed and only the rest are searched,
+//! which recovers the keys of short texts far more often.
 
 use super::crack_results::CrackResult;
-use super::interface::{Crack, Decoder};
+use super::interface::{Alphabet, Crack, Decoder};
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
-use crate::cryptanalysis::{fitness_score, is_likely_english};
+use crate::deadline::Deadline;
+use crate::text_info::TextInfo;
+use crate::cryptanalysis::{
+    apply_substitution, is_likely_language, kpa, kpa::PartialMapping, languages_or_english, seeded_rng,
+    HillClimber, Language,
+};
+use rand::rngs::StdRng;
+use std::cell::RefCell;
 use gibberish_or_not::Sensitivity;
 use log::{debug, trace};
 
+/// The fewest letters frequency analysis can go on
+const MIN_LETTERS: usize = 30;
+
 /// Monoalphabetic substitution cipher solver
 pub struct MonoalphabeticSolver;
 
@@ -30,33 +45,129 @@ impl Crack for Decoder<MonoalphabeticSolver> {
     }
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        self.crack_with_info(text, &TextInfo::new(text), checker, config)
+    }
+
+    /// The letters of the candidate are only picked out once, for all decoders
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        self.crack_with_info(&candidate.text(), candidate.info(), checker, config)
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    /// Hill-climbs over whole alphabets
+    fn expected_cost(&self) -> f32 {
+        100.0
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+    /// Only the letters are substituted
+    fn alphabet(&self) -> Alphabet {
+        Alphabet::Letters
+    }
+    /// Frequency analysis needs enough letters to go on
+    fn min_length(&self) -> usize {
+        MIN_LETTERS
+    }
+}
+
+impl Decoder<MonoalphabeticSolver> {
+    /// Cracks `text`, given what has been worked out about it
+    fn crack_with_info(
+        &self,
+        text: &str,
+        info: &TextInfo,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
         trace!("Trying Monoalphabetic solver with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Clean text - only alphabetic
-        let clean_text: String = text.to_uppercase()
-            .chars()
-            .filter(|c| c.is_ascii_alphabetic())
-            .collect();
+        let clean_text = &info.uppercase_letters;
 
         // Need enough text for statistical analysis
-        if clean_text.len() < 30 {
+        if clean_text.len() < MIN_LETTERS {
             debug!("Text too short for monoalphabetic analysis (need at least 30 chars)");
             return results;
         }
 
         let checker_with_sensitivity = checker.with_sensitivity(Sensitivity::Medium);
 
-        // PHASE 1: Try frequency analysis first
+        let languages = languages_or_english(&config.languages);
+
+        // Letters the user says are known stay fixed in every phase
+        let hints = match config.letter_map.as_deref().map(kpa::parse_letter_map) {
+            Some(Ok(hints)) => hints,
+            Some(Err(e)) => {
+                debug!("Ignoring the letter map: {}", e);
+                [None; 26]
+            }
+            None => [None; 26],
+        };
+
+        // PHASE 0: Climb with the letters the crib gives fixed
+        if let Some(crib) = &config.crib {
+            let mappings: Vec<PartialMapping> = kpa::substitution_mappings(clean_text, crib)
+                .iter()
+                .filter_map(|mapping| kpa::merge_mappings(mapping, &hints))
+                .collect();
+            trace!("Phase 0: The crib fits in {} places", mappings.len());
+            let mut rng = seeded_rng(config.seed);
+            for mapping in mappings.iter().take(MAX_CRIB_PLACEMENTS) {
+                for language in languages {
+                    let Some((key, _decoded)) = hill_climb_solve(clean_text, *language, mapping, 5000, 5, &mut rng, config.deadline.as_ref()) else {
+                        continue;
+                    };
+                    let decoded_lower = apply_key_preserve_case(text, &key).to_lowercase();
+                    let checker_result = checker_with_sensitivity.check(&decoded_lower, config);
+                    if checker_result.is_identified {
+                        debug!("Crib attack succeeded with key: {}", key);
+                        results.unencrypted_text = Some(vec![decoded_lower.into()]);
+                        results.update_checker(&checker_result);
+                        results.key = Some(key);
+                        return results;
+                    }
+                }
+            }
+        }
+
+        // PHASE 1: Try frequency analysis first. It would overrule the
+        // letters the user knows, so with those the climb starts from it instead.
         trace!("Phase 1: Frequency analysis");
-        if let Some((key, _decoded)) = frequency_analysis_solve(&clean_text) {
+        let has_hints = hints.iter().any(Option::is_some);
+        for language in languages.iter().filter(|_| !has_hints) {
+            let Some((key, _decoded)) = frequency_analysis_solve(clean_text, *language) else {
+                continue;
+            };
             let decoded_with_case = apply_key_preserve_case(text, &key);
             let decoded_lower = decoded_with_case.to_lowercase();
             
             let checker_result = checker_with_sensitivity.check(&decoded_lower, config);
             if checker_result.is_identified {
                 debug!("Frequency analysis succeeded");
-                results.unencrypted_text = Some(vec![decoded_lower]);
+                results.unencrypted_text = Some(vec![decoded_lower.into()]);
                 results.update_checker(&checker_result);
                 results.key = Some(key);
                 return results;
@@ -65,15 +176,19 @@ impl Crack for Decoder<MonoalphabeticSolver> {
 
         // PHASE 2: Hill climbing optimization
         trace!("Phase 2: Hill climbing optimization");
-        if let Some((key, _decoded)) = hill_climb_solve(&clean_text, 5000, 5) {
+        let mut rng = seeded_rng(config.seed);
+        for language in languages {
+            let Some((key, _decoded)) = hill_climb_solve(clean_text, *language, &hints, 5000, 5, &mut rng, config.deadline.as_ref()) else {
+                continue;
+            };
             let decoded_with_case = apply_key_preserve_case(text, &key);
             let decoded_lower = decoded_with_case.to_lowercase();
             
-            if is_likely_english(&decoded_lower) {
+            if is_likely_language(&decoded_lower, *language) {
                 let checker_result = checker_with_sensitivity.check(&decoded_lower, config);
                 if checker_result.is_identified {
                     debug!("Hill climbing succeeded with key: {}", key);
-                    results.unencrypted_text = Some(vec![decoded_lower]);
+                    results.unencrypted_text = Some(vec![decoded_lower.into()]);
                     results.update_checker(&checker_result);
                     results.key = Some(key);
                     return results;
@@ -84,27 +199,11 @@ impl Crack for Decoder<MonoalphabeticSolver> {
         debug!("Failed to decode monoalphabetic cipher");
         results
     }
-
-    fn get_tags(&self) -> &Vec<&str> {
-        &self.tags
-    }
-
-    fn get_name(&self) -> &str {
-        self.name
-    }
-
-    fn get_description(&self) -> &str {
-        self.description
-    }
-
-    fn get_link(&self) -> &str {
-        self.link
-    }
 }
 
 /// Solve using frequency analysis
 /// Returns the key and decoded text if successful
-fn frequency_analysis_solve(ciphertext: &str) -> Option<(String, String)> {
+fn frequency_analysis_solve(ciphertext: &str, language: Language) -> Option<(String, String)> {
     // Count letter frequencies in ciphertext
     let mut freq = [0u32; 26];
     for c in ciphertext.chars() {
@@ -119,11 +218,15 @@ fn frequency_analysis_solve(ciphertext: &str) -> Option<(String, String)> {
         .enumerate()
         .map(|(i, &f)| (i, f))
         .collect();
-    cipher_order.sort_by(|a, b| b.1.cmp(&a.1));
+    cipher_order.sort_by_key(|b| std::cmp::Reverse(b.1));
     
-    // Standard English letter frequency order (E, T, A, O, I, N, S, H, R, ...)
-    let english_order = "ETAOINSHRDLCUMWFGYPBVKJXQZ";
-    let english_chars: Vec<char> = english_order.chars().collect();
+    // The language's letters, most frequent first (E, T, A, O, I, N, ... for English)
+    let letter_freqs = language.letter_freqs();
+    let mut english_chars: Vec<char> = ('A'..='Z').collect();
+    english_chars.sort_by(|a, b| {
+        let freq = |c: &char| letter_freqs[(*c as u8 - b'A') as usize];
+        freq(b).total_cmp(&freq(a))
+    });
     
     // Create initial key mapping based on frequency
     let mut key = ['A'; 26];
@@ -137,93 +240,71 @@ fn frequency_analysis_solve(ciphertext: &str) -> Option<(String, String)> {
     Some((key_str, decoded))
 }
 
-/// Solve using hill climbing optimization
-fn hill_climb_solve(ciphertext: &str, max_iterations: usize, restarts: usize) -> Option<(String, String)> {
-    let mut best_key = String::new();
-    let mut best_score = f64::MIN;
-    let mut best_decoded = String::new();
-    
-    // Get seed for pseudo-random
-    let base_seed = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_nanos() as u64)
-        .unwrap_or(12345);
-    
-    for restart in 0..restarts {
-        let mut rng = base_seed.wrapping_add(restart as u64);
-        
-        // Start with frequency analysis key
-        let mut current_key: Vec<char> = if restart == 0 {
-            if let Some((key, _)) = frequency_analysis_solve(ciphertext) {
-                key.chars().collect()
-            } else {
-                ('A'..='Z').collect()
-            }
-        } else {
-            // Random key for other restarts
-            let mut key: Vec<char> = ('A'..='Z').collect();
-            for i in (1..26).rev() {
-                rng = lcg_next(rng);
-                let j = (rng as usize) % (i + 1);
-                key.swap(i, j);
-            }
-            key
-        };
-        
-        let mut current_decoded = apply_key_vec(ciphertext, &current_key);
-        let mut current_score = fitness_score(&current_decoded);
-        
-        let mut plateau_count = 0;
-        
-        for _ in 0..max_iterations {
-            // Try swapping two random letters in the key
-            rng = lcg_next(rng);
-            let i = (rng as usize) % 26;
-            rng = lcg_next(rng);
-            let j = (rng as usize) % 26;
-            
-            if i == j {
-                continue;
-            }
-            
-            // Swap
-            current_key.swap(i, j);
-            let new_decoded = apply_key_vec(ciphertext, &current_key);
-            let new_score = fitness_score(&new_decoded);
-            
-            if new_score > current_score {
-                current_decoded = new_decoded;
-                current_score = new_score;
-                plateau_count = 0;
-            } else {
-                // Undo swap
-                current_key.swap(i, j);
-                plateau_count += 1;
-            }
-            
-            // Early exit on plateau
-            if plateau_count > 500 {
-                break;
-            }
-        }
-        
-        if current_score > best_score {
-            best_score = current_score;
-            best_key = current_key.iter().collect();
-            best_decoded = current_decoded;
-        }
-    }
-    
-    if best_key.is_empty() {
-        None
+/// The most places the crib is tried in, as short cribs fit almost anywhere
+const MAX_CRIB_PLACEMENTS: usize = 5;
+
+/// Texts with fewer letters than this are solved with tabu search, which
+/// recovers their keys far more often than plain hill climbing
+const TABU_SEARCH_MAX_LETTERS: usize = 200;
+
+/// Solve using hill climbing optimization, keeping the letters `fixed`
+/// says as they are
+fn hill_climb_solve(
+    ciphertext: &str,
+    language: Language,
+    fixed: &PartialMapping,
+    max_iterations: usize,
+    restarts: usize,
+    rng: &mut StdRng,
+    deadline: Option<&Deadline>,
+) -> Option<(String, String)> {
+    let letters: Vec<u8> = ciphertext.bytes().filter(u8::is_ascii_uppercase).collect();
+    let climber = if letters.len() < TABU_SEARCH_MAX_LETTERS {
+        HillClimber::tabu(300, restarts.min(3))
     } else {
-        Some((best_key, best_decoded))
+        HillClimber::new(max_iterations, restarts)
     }
+    .with_deadline(deadline.cloned());
+
+    // Only the letters which aren't fixed are climbed over
+    let free: Vec<usize> = (0..26).filter(|&i| fixed[i].is_none()).collect();
+    let full_key = |climbed: &[char]| {
+        let mut key = fixed.map(|letter| letter.unwrap_or('A'));
+        for (&i, &letter) in free.iter().zip(climbed) {
+            key[i] = letter;
+        }
+        key
+    };
+
+    // Start with frequency analysis key
+    let start = frequency_analysis_solve(ciphertext, language).map(|(key, _)| {
+        let mut key: Vec<char> = key.chars().collect();
+        fix_letters(&mut key, fixed);
+        free.iter().map(|&i| key[i]).collect()
+    });
+    // Decrypt into one buffer rather than a new string for every key
+    let quadgrams = language.quadgrams();
+    let plaintext = RefCell::new(Vec::with_capacity(letters.len()));
+    let score = |climbed: &[char]| {
+        let key = full_key(climbed);
+        let mut plaintext = plaintext.borrow_mut();
+        plaintext.clear();
+        plaintext.extend(letters.iter().map(|&c| key[usize::from(c - b'A')] as u8));
+        quadgrams.score_letters(&plaintext)
+    };
+    let alphabet: Vec<char> = ('A'..='Z').filter(|&letter| !fixed.contains(&Some(letter))).collect();
+    let (climbed, _) = climber.optimize_key(&alphabet, start, score, rng);
+    let key = full_key(&climbed);
+    Some((key.iter().collect(), apply_substitution(ciphertext, &key)))
 }
 
-/// Simple LCG for pseudo-random numbers
-fn lcg_next(state: u64) -> u64 {
-    state.wrapping_mul(6364136223846793005).wrapping_add(1)
+/// Swaps letters of a whole key so it agrees with the letters `fixed` says
+fn fix_letters(key: &mut [char], fixed: &PartialMapping) {
+    for (i, letter) in fixed.iter().enumerate() {
+        if let Some(j) = letter.and_then(|letter| key.iter().position(|&k| k == letter)) {
+            key.swap(i, j);
+        }
+    }
 }
 
 /// Apply a substitution key to ciphertext (uppercase only)
@@ -241,20 +322,6 @@ fn apply_key(ciphertext: &str, key: &str) -> String {
         .collect()
 }
 
-/// Apply a substitution key (vector version)
-fn apply_key_vec(ciphertext: &str, key: &[char]) -> String {
-    ciphertext.chars()
-        .map(|c| {
-            if c.is_ascii_uppercase() {
-                let idx = (c as u8 - b'A') as usize;
-                key[idx]
-            } else {
-                c
-            }
-        })
-        .collect()
-}
-
 /// Apply key while preserving original case
 fn apply_key_preserve_case(original: &str, key: &str) -> String {
     let key_chars: Vec<char> = key.chars().collect();
@@ -315,13 +382,56 @@ mod tests {
     #[test]
     fn test_frequency_analysis_returns_key() {
         let text = "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG";
-        if let Some((key, _decoded)) = frequency_analysis_solve(text) {
+        if let Some((key, _decoded)) = frequency_analysis_solve(text, Language::English) {
             assert_eq!(key.len(), 26);
         } else {
             panic!("Frequency analysis should return a key");
         }
     }
 
+    #[test]
+    fn hill_climbing_is_reproducible_with_a_seed() {
+        let text = "WKH TXLFN EURZQ IRA MXPSV RYHU WKH ODCB GRJ";
+        let clean: String = text.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+        let first = hill_climb_solve(&clean, Language::English, &[None; 26], 500, 3, &mut seeded_rng(Some(7)), None);
+        let second = hill_climb_solve(&clean, Language::English, &[None; 26], 500, 3, &mut seeded_rng(Some(7)), None);
+        assert!(first.is_some());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn hill_climbing_keeps_the_letters_a_crib_fixes() {
+        let text = "WKH TXLFN EURZQ IRA MXPSV RYHU WKH ODCB GRJ";
+        let clean: String = text.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+        let fixed = kpa::substitution_mappings(&clean, "quick")[0];
+        let (key, _) = hill_climb_solve(&clean, Language::English, &fixed, 500, 3, &mut seeded_rng(Some(7)), None).unwrap();
+        let key: Vec<char> = key.chars().collect();
+        for (i, letter) in fixed.iter().enumerate() {
+            if let Some(letter) = letter {
+                assert_eq!(key[i], *letter);
+            }
+        }
+    }
+
+    #[test]
+    fn letter_map_cracks_short_texts() {
+        let decoder = Decoder::<MonoalphabeticSolver>::new();
+        let config = Config {
+            letter_map: Some("AWJ=THE,SD=IL,C=A,LU=WV".to_string()),
+            seed: Some(2),
+            ..Config::default()
+        };
+        let result = decoder.crack(
+            "AWJ FCOOLVHM NVH AWJ UCQDA SO WSMMJP QPMJH AWJ RCA",
+            &get_athena_checker(),
+            &config,
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "the password for the vault is hidden under the mat"
+        );
+    }
+
     #[test]
     fn test_short_text_rejected() {
         let decoder = Decoder::<MonoalphabeticSolver>::new();
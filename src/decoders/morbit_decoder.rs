@@ -0,0 +1,105 @@
+//! Morbit decoder
+//! Morbit writes the message in Morse code with `x` between letters and `xx`
+//! between words, then swaps each pair of symbols for one of the digits 1 to
+//! 9 given by a keyword. The key is found by trying the pairs for each digit,
+//! see [`super::digit_morse`], and is given as the pair for each digit, like
+//! `1=.- 2=xx 3=-.`.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+
+use super::crack_results::CrackResult;
+use super::digit_morse::MORBIT;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::trace;
+
+/// The Morbit decoder
+/// Call: `let decoder = Decoder::<MorbitDecoder>::new()` to create a new instance
+/// And then call: `result = decoder.crack(input)` to decode
+pub struct MorbitDecoder;
+
+impl Crack for Decoder<MorbitDecoder> {
+    fn new() -> Decoder<MorbitDecoder> {
+        Decoder {
+            name: "Morbit",
+            description: "The ACA's Morbit cipher writes the message in Morse code, then swaps each pair of dots, dashes and letter breaks for one of the digits 1 to 9.",
+            link: "https://www.cryptogram.org/downloads/aca.info/ciphers/Morbit.pdf",
+            tags: vec!["morbit", "morse", "cipher", "classic"],
+            popularity: 0.2,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Morbit with text {:?}", text);
+        MORBIT.crack(text, checker, config, CrackResult::new(self, text))
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    /// Searches the keys digit by digit
+    fn expected_cost(&self) -> f32 {
+        30.0
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+
+    /// Decodes with the pair for each digit, like `1=.- 2=xx 3=-.`
+    fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        MORBIT.decode_with_key(text, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn morbit_cracks() {
+        let decoder = Decoder::<MorbitDecoder>::new();
+        let result = decoder.crack(
+            "21136216536799162759396491418482881794311462184841828993",
+            &get_checker(),
+            &Config::default(),
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "MEET ME AT THE OLD BRIDGE AFTER MIDNIGHT"
+        );
+    }
+
+    #[test]
+    fn morbit_decodes_with_key() {
+        let decoder = Decoder::<MorbitDecoder>::new();
+        let key = "1=x. 2=-- 3=x- 4=-. 5=.- 6=xx 7=-x 8=.x 9=..";
+        assert_eq!(decoder.decode_with_key("7991", key).unwrap(), "THE");
+        assert!(decoder.decode_with_key("12", "1=.. 2=..").is_err());
+    }
+}
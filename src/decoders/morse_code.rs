@@ -4,10 +4,13 @@ use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
+use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashMap;
 
 /// Morse Code Decoder
 /// Does not support decoding of morse code with / instead of a space
@@ -40,7 +43,7 @@ impl Crack for Decoder<MorseCodeDecoder> {
         let decoded_text = decoded_text.map(|s| re.replace_all(s.trim(), " ").into_owned());
 
         trace!("Decoded text for morse code: {:?}", decoded_text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text.as_str());
 
         if decoded_text.is_none() {
             debug!("Failed to decode Morse Code because a character was not in the dictionary");
@@ -57,7 +60,7 @@ impl Crack for Decoder<MorseCodeDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -71,6 +74,10 @@ impl Crack for Decoder<MorseCodeDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -81,6 +88,24 @@ impl Crack for Decoder<MorseCodeDecoder> {
     }
 }
 
+impl Encode for Decoder<MorseCodeDecoder> {
+    /// Encodes letters separated by spaces and words separated by ` / `
+    fn encode(&self, text: &str) -> Result<String, String> {
+        text.split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .map(|c| {
+                        alphanumeric_to_morse(c)
+                            .ok_or_else(|| format!("Morse code cannot encode '{}'", c))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|codes| codes.join(" "))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|words| words.join(" / "))
+    }
+}
+
 /// Replace new lines, line breaks, and other delimiters with the standard delimiter '/'
 fn normalise_morse_string(text: &str) -> String {
     // The replace function supports patterns https://doc.rust-lang.org/std/str/pattern/trait.Pattern.html#impl-Pattern%3C%27a%3E-3
@@ -89,75 +114,90 @@ fn normalise_morse_string(text: &str) -> String {
         .replace(['/', '\\', ':', ',', '\n', '\r'], " / ")
 }
 
+/// Morse code and the character it stands for
+pub(crate) const MORSE_TABLE: [(&str, &str); 51] = [
+    (".-", "A"),
+    ("-...", "B"),
+    ("-.-.", "C"),
+    ("-..", "D"),
+    (".", "E"),
+    ("..-.", "F"),
+    ("--.", "G"),
+    ("....", "H"),
+    ("..", "I"),
+    (".---", "J"),
+    ("-.-", "K"),
+    (".-..", "L"),
+    ("--", "M"),
+    ("-.", "N"),
+    ("---", "O"),
+    (".--.", "P"),
+    ("--.-", "Q"),
+    (".-.", "R"),
+    ("...", "S"),
+    ("-", "T"),
+    ("..-", "U"),
+    ("...-", "V"),
+    (".--", "W"),
+    ("-..-", "X"),
+    ("-.--", "Y"),
+    ("--..", "Z"),
+    (".----", "1"),
+    ("..---", "2"),
+    ("...--", "3"),
+    ("....-", "4"),
+    (".....", "5"),
+    ("-....", "6"),
+    ("--...", "7"),
+    ("---..", "8"),
+    ("----.", "9"),
+    ("-----", "0"),
+    (".-...", "&"),
+    (".--.-.", "@"),
+    ("---...", ":"),
+    ("--..--", ","),
+    (".-.-.-", "."),
+    (".----.", "'"),
+    (".-..-.", "\""),
+    ("..--..", "?"),
+    ("-..-.", "/"),
+    ("-...-", "="),
+    (".-.-.", "+"),
+    ("-....-", "-"),
+    ("-.--.", "("),
+    ("-.--.-", ")"),
+    ("-.-.--", "!"),
+];
+
+/// [`MORSE_TABLE`] by morse code, as every symbol of the input is looked up
+static MORSE_TO_CHARACTER: Lazy<HashMap<&str, &str>> =
+    Lazy::new(|| MORSE_TABLE.iter().copied().collect());
+
 /// Maps morse code to its alphanumeric character, returns None for invalid morse-code
 fn morse_to_alphanumeric(text: &str) -> Option<&str> {
     let result = match text {
-        ".-" => "A",
-        "-..." => "B",
-        "-.-." => "C",
-        "-.." => "D",
-        "." => "E",
-        "..-." => "F",
-        "--." => "G",
-        "...." => "H",
-        ".." => "I",
-        ".---" => "J",
-        "-.-" => "K",
-        ".-.." => "L",
-        "--" => "M",
-        "-." => "N",
-        "---" => "O",
-        ".--." => "P",
-        "--.-" => "Q",
-        ".-." => "R",
-        "..." => "S",
-        "-" => "T",
-        "..-" => "U",
-        "...-" => "V",
-        ".--" => "W",
-        "-..-" => "X",
-        "-.--" => "Y",
-        "--.." => "Z",
-
-        ".----" => "1",
-        "..---" => "2",
-        "...--" => "3",
-        "....-" => "4",
-        "....." => "5",
-        "-...." => "6",
-        "--..." => "7",
-        "---.." => "8",
-        "----." => "9",
-        "-----" => "0",
-
-        ".-..." => "&",
-        ".--.-." => "@",
-        "---..." => ":",
-        "--..--" => ",",
-        ".-.-.-" => ".",
-        ".----." => "'",
-        ".-..-." => "\"",
-        "..--.." => "?",
-        "-..-." => "/",
-        "-...-" => "=",
-        ".-.-." => "+",
-        "-....-" => "-",
-        "-.--." => "(",
-        "-.--.-" => ")",
         "/" => " ",
-        "-.-.--" => "!",
         " " => " ",
         "" => "",
         // Turns line breaks and new lines into space. This may break what the plaintext is supposed to be
         // But enables us to support them
         "\n" => " ",
         "\r" => " ",
-        _ => return None,
+        _ => MORSE_TO_CHARACTER.get(text)?,
     };
 
     Some(result)
 }
 
+/// Maps a single character to its morse code, returns None if morse code can't represent it
+fn alphanumeric_to_morse(character: char) -> Option<&'static str> {
+    let character = character.to_ascii_uppercase();
+    MORSE_TABLE
+        .iter()
+        .find(|(_, plain)| plain.chars().eq(std::iter::once(character)))
+        .map(|(code, _)| *code)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,4 +323,11 @@ mod tests {
             r".... . .-.. .-.. ---:.-- --- .-. .-.. -.. -.-.--", &get_athena_checker(), &crate::config::Config::default());
         assert_eq!(result.unencrypted_text.unwrap()[0], "HELLO WORLD!");
     }
+
+    #[test]
+    fn test_morse_code_encode() {
+        let decoder = Decoder::<MorseCodeDecoder>::new();
+        assert_eq!(decoder.encode("sos 1").unwrap(), "... --- ... / .----");
+        assert!(decoder.encode("a#b").is_err());
+    }
 }
@@ -6,6 +6,7 @@ use crate::config::Config;
 use crate::decoders::interface::check_string_success;
 use crate::decoders::crack_results::CrackResult;
 use crate::decoders::interface::Crack;
+use crate::decoders::interface::Encode;
 use crate::decoders::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -29,7 +30,7 @@ impl Crack for Decoder<OctalDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Octal with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let decoded_text = decode_octal_no_error_handling(text);
 
         if decoded_text.is_none() {
@@ -44,7 +45,7 @@ impl Crack for Decoder<OctalDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results.update_checker(&checker_result);
 
         results
@@ -57,6 +58,17 @@ impl Crack for Decoder<OctalDecoder> {
     fn get_link(&self) -> &str { self.link }
 }
 
+impl Encode for Decoder<OctalDecoder> {
+    /// Encodes each byte as an octal number, separated by spaces
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(text
+            .bytes()
+            .map(|byte| format!("{:03o}", byte))
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+}
+
 /// Helper function to decode octal string
 fn decode_octal_no_error_handling(text: &str) -> Option<String> {
     if text.is_empty() { return None; }
@@ -78,7 +90,7 @@ fn decode_octal_no_error_handling(text: &str) -> Option<String> {
     } else {
         // Try chunks of 3 if length is divisible by 3 and looks like octal
         let input = text.replace('\\', ""); // basic cleanup
-        if !input.is_empty() && input.len() % 3 == 0 {
+        if !input.is_empty() && input.len().is_multiple_of(3) {
              let chars: Vec<char> = input.chars().collect();
              for chunk in chars.chunks(3) {
                  let s: String = chunk.iter().collect();
@@ -0,0 +1,297 @@
+//! Decode PHP's serialize() format
+//! Performs error handling and returns a string
+//! Call php_serialize_decoder.crack to use.
+//!
+//! PHP serializes values as text like `a:1:{s:4:"name";s:5:"alice";}`, which
+//! turns up in cookies and form fields and is the way into PHP object
+//! injection. This prints what is in it: nothing is unserialized, so no
+//! `__wakeup` or `__destruct` can run.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use super::serialized_value::{crack_bytes, string_or_bytes, Value, MAX_DEPTH};
+use log::trace;
+
+/// The PHP serialize() decoder, call:
+/// `let php_serialize_decoder = Decoder::<PhpSerializeDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = php_serialize_decoder.crack(input)` to print the serialized value
+/// ```
+/// use ares::decoders::php_serialize_decoder::PhpSerializeDecoder;
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decoder = Decoder::<PhpSerializeDecoder>::new();
+/// let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+///
+/// let serialized = r#"O:4:"User":2:{s:4:"name";s:5:"alice";s:8:"is_admin";b:0;}"#;
+/// let result = decoder.crack(serialized, &checker, &ares::config::Config::default());
+/// assert_eq!(
+///     result.unencrypted_text.unwrap()[0],
+///     "User {\n  name: \"alice\"\n  is_admin: false\n}"
+/// );
+/// ```
+pub struct PhpSerializeDecoder;
+
+impl Crack for Decoder<PhpSerializeDecoder> {
+    fn new() -> Decoder<PhpSerializeDecoder> {
+        Decoder {
+            name: "PHP Serialize",
+            description: "PHP's serialize() turns values and objects into text. This shows what a serialized value holds without unserializing it.",
+            link: "https://www.php.net/manual/en/function.serialize.php",
+            tags: vec!["php", "serialization", "web", "decoder"],
+            popularity: 0.5,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying PHP Serialize with text {:?}", text);
+        crack_bytes(self, text, text.trim().as_bytes(), parse, checker, config)
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The value serialized in `bytes`, which must be an array or an object and
+/// nothing after it
+fn parse(bytes: &[u8]) -> Option<Value> {
+    if !matches!(bytes.first(), Some(b'a' | b'O' | b'C')) {
+        return None;
+    }
+    let mut parser = Parser { bytes, position: 0 };
+    let value = parser.value(0)?;
+    (parser.position == bytes.len()).then_some(value)
+}
+
+/// Reads serialized values from the front of the bytes
+struct Parser<'a> {
+    /// All of the serialized bytes
+    bytes: &'a [u8],
+    /// How far they have been read
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Reads one value, `depth` arrays or objects down
+    fn value(&mut self, depth: usize) -> Option<Value> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let kind = self.byte()?;
+        if kind == b'N' {
+            self.expect(b';')?;
+            return Some(Value::Null);
+        }
+        self.expect(b':')?;
+        match kind {
+            b'b' => match self.until(b';')? {
+                b"0" => Some(Value::Bool(false)),
+                b"1" => Some(Value::Bool(true)),
+                _ => None,
+            },
+            b'i' => {
+                let digits = std::str::from_utf8(self.until(b';')?).ok()?;
+                digits.parse::<i64>().ok()?;
+                Some(Value::Int(digits.to_string()))
+            }
+            b'd' => {
+                let number = std::str::from_utf8(self.until(b';')?).ok()?;
+                let value = match number {
+                    "INF" => f64::INFINITY,
+                    "-INF" => f64::NEG_INFINITY,
+                    "NAN" => f64::NAN,
+                    _ => number.parse().ok()?,
+                };
+                Some(Value::Float(value))
+            }
+            b's' => {
+                let string = self.string()?;
+                self.expect(b';')?;
+                Some(string_or_bytes(string))
+            }
+            b'E' => {
+                // A PHP 8.1 enum case, as "Enum:Case"
+                let case = std::str::from_utf8(self.string()?).ok()?.to_string();
+                self.expect(b';')?;
+                Some(Value::Raw(case))
+            }
+            b'r' | b'R' => {
+                let index = self.number()?;
+                self.expect(b';')?;
+                Some(Value::Raw(format!("<reference to value {index}>")))
+            }
+            b'a' => {
+                let count = self.number()?;
+                self.expect(b':')?;
+                self.expect(b'{')?;
+                let mut entries = Vec::new();
+                for _ in 0..count {
+                    let key = self.value(depth + 1)?;
+                    if !matches!(key, Value::Int(_) | Value::Str(_) | Value::Bytes(_)) {
+                        return None;
+                    }
+                    entries.push((key, self.value(depth + 1)?));
+                }
+                self.expect(b'}')?;
+                Some(Value::Map(entries))
+            }
+            b'O' => {
+                let class = std::str::from_utf8(self.string()?).ok()?.to_string();
+                self.expect(b':')?;
+                let count = self.number()?;
+                self.expect(b':')?;
+                self.expect(b'{')?;
+                let mut fields = Vec::new();
+                for _ in 0..count {
+                    let name = match self.value(depth + 1)? {
+                        Value::Str(name) => property_name(&name),
+                        Value::Bytes(name) => property_name(&String::from_utf8_lossy(&name)),
+                        Value::Int(index) => index,
+                        _ => return None,
+                    };
+                    fields.push((name, self.value(depth + 1)?));
+                }
+                self.expect(b'}')?;
+                Some(Value::Object(class, fields))
+            }
+            b'C' => {
+                // A class with its own Serializable format, kept as it is
+                let class = std::str::from_utf8(self.string()?).ok()?.to_string();
+                self.expect(b':')?;
+                let length = self.number()?;
+                self.expect(b':')?;
+                self.expect(b'{')?;
+                let data = self.take(length)?;
+                self.expect(b'}')?;
+                Some(Value::Object(
+                    class,
+                    vec![("serialized".to_string(), string_or_bytes(data))],
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// A `LENGTH:"..."` string, which may hold any bytes
+    fn string(&mut self) -> Option<&'a [u8]> {
+        let length = self.number()?;
+        self.expect(b':')?;
+        self.expect(b'"')?;
+        let string = self.take(length)?;
+        self.expect(b'"')?;
+        Some(string)
+    }
+
+    /// A count or length, up to the next `:` or `;`
+    fn number(&mut self) -> Option<usize> {
+        let end = self.bytes[self.position..]
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())?;
+        let number = std::str::from_utf8(&self.bytes[self.position..self.position + end])
+            .ok()?
+            .parse()
+            .ok()?;
+        self.position += end;
+        Some(number)
+    }
+
+    /// The bytes up to `end`, skipping past it
+    fn until(&mut self, end: u8) -> Option<&'a [u8]> {
+        let length = self.bytes[self.position..]
+            .iter()
+            .position(|byte| *byte == end)?;
+        let found = &self.bytes[self.position..self.position + length];
+        self.position += length + 1;
+        Some(found)
+    }
+
+    /// The next `length` bytes
+    fn take(&mut self, length: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(length)?;
+        let taken = self.bytes.get(self.position..end)?;
+        self.position = end;
+        Some(taken)
+    }
+
+    /// The next byte
+    fn byte(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.position)?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    /// Skips `expected`, None if something else is next
+    fn expect(&mut self, expected: u8) -> Option<()> {
+        (self.byte()? == expected).then_some(())
+    }
+}
+
+/// A property's name without the NUL-wrapped prefix PHP gives protected
+/// (`\0*\0name`) and private (`\0Class\0name`) properties, marked instead
+fn property_name(name: &str) -> String {
+    match name
+        .strip_prefix('\0')
+        .and_then(|rest| rest.split_once('\0'))
+    {
+        Some(("*", name)) => format!("{name} (protected)"),
+        Some((class, name)) => format!("{name} (private to {class})"),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn php_serialize_arrays_and_visibility() {
+        let decoder = Decoder::<PhpSerializeDecoder>::new();
+        let serialized = "a:2:{i:0;d:1.5;s:4:\"user\";O:5:\"Admin\":2:{s:7:\"\0*\0role\";s:4:\"root\";s:11:\"\0Admin\0pass\";N;}}";
+        let result = decoder.crack(serialized, &get_checker(), &Config::default());
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "{\n  0: 1.5\n  \"user\": Admin {\n    role (protected): \"root\"\n    pass (private to Admin): null\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn php_serialize_needs_all_of_the_input() {
+        let decoder = Decoder::<PhpSerializeDecoder>::new();
+        let config = Config::default();
+        // The string is shorter than it says, and scalars on their own are too easily text
+        for text in ["a:1:{i:0;s:9:\"short\";}", "i:5;", "a:0:{}trailing"] {
+            assert!(decoder
+                .crack(text, &get_checker(), &config)
+                .unencrypted_text
+                .is_none());
+        }
+    }
+}
@@ -0,0 +1,636 @@
+//! Decode Python pickles
+//! Performs error handling and returns a string
+//! Call pickle_decoder.crack to use.
+//!
+//! A pickle is a little program for a stack machine, which is why loading an
+//! untrusted one can run anything. This follows the program without running
+//! it: globals stay names and calls stay calls, so a dump shows plainly what
+//! loading the pickle would do, e.g. `os.system("id")`.
+
+use crate::byte_text::{Candidate, Content};
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use std::collections::HashMap;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use super::serialized_value::{crack_binary, crack_bytes, string_or_bytes, Value, MAX_DEPTH};
+use log::trace;
+
+/// What the Base64 of a pickle of protocol 2 or later starts with
+const BASE64_PREFIX: &str = "gA";
+
+/// The newest pickle protocol
+const HIGHEST_PROTOCOL: u8 = 5;
+
+/// The Python pickle decoder, call:
+/// `let pickle_decoder = Decoder::<PickleDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = pickle_decoder.crack(input)` to print what the pickle builds
+/// ```
+/// use ares::decoders::pickle_decoder::PickleDecoder;
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decoder = Decoder::<PickleDecoder>::new();
+/// let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+///
+/// // A protocol 0 pickle which runs a command when loaded
+/// let pickle = "cos\nsystem\n(S'id'\ntR.";
+/// let result = decoder.crack(pickle, &checker, &ares::config::Config::default());
+/// assert_eq!(result.unencrypted_text.unwrap()[0], "os.system(\"id\")");
+/// ```
+pub struct PickleDecoder;
+
+impl Crack for Decoder<PickleDecoder> {
+    fn new() -> Decoder<PickleDecoder> {
+        Decoder {
+            name: "Pickle",
+            description: "Python's pickle format for serializing objects. This follows the pickle's opcodes without running them, showing the objects it builds and the functions it would call.",
+            link: "https://docs.python.org/3/library/pickle.html",
+            tags: vec!["python", "serialization", "web", "decoder"],
+            popularity: 0.5,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Reads the text's UTF-8 bytes, or the pickle Base64 encoded
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Pickle with text {:?}", text);
+        crack_binary(self, text, BASE64_PREFIX, parse, checker, config)
+    }
+
+    /// Bytes from an earlier decoder, like Base64, are read as they are
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        match candidate.content() {
+            Content::Text(text) => self.crack(text, checker, config),
+            Content::Bytes(bytes) => {
+                crack_bytes(self, &candidate.text(), bytes, parse, checker, config)
+            }
+        }
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// What a pickle builds, if all of `bytes` is one pickle building more than
+/// a single number or string
+fn parse(bytes: &[u8]) -> Option<Value> {
+    let mut machine = Machine {
+        bytes,
+        position: 0,
+        stack: Vec::new(),
+        memo: HashMap::new(),
+    };
+    let value = machine.run()?;
+    (machine.position == bytes.len() && value.is_structured()).then_some(value)
+}
+
+/// An item on the pickle machine's stack
+enum Slot {
+    /// Where the items of a tuple, list or call start
+    Mark,
+    /// A value and how deeply it nests
+    Value(Value, usize),
+}
+
+/// Follows a pickle's opcodes, building values instead of objects
+struct Machine<'a> {
+    /// The pickle
+    bytes: &'a [u8],
+    /// How far it has been read
+    position: usize,
+    /// The stack of values and marks
+    stack: Vec<Slot>,
+    /// What GET shows for each value stored by PUT: short values as they
+    /// are, and the rest as a reference, see [`Value::referenced`]
+    memo: HashMap<u64, Value>,
+}
+
+impl<'a> Machine<'a> {
+    /// Runs the pickle to its STOP, giving what is left on the stack
+    fn run(&mut self) -> Option<Value> {
+        loop {
+            let opcode = self.u8()?;
+            match opcode {
+                b'.' => {
+                    let (value, _) = self.pop()?;
+                    return self.stack.is_empty().then_some(value);
+                }
+                0x80 => {
+                    if self.u8()? > HIGHEST_PROTOCOL {
+                        return None;
+                    }
+                }
+                0x95 => {
+                    self.take(8)?;
+                }
+                b'(' => self.stack.push(Slot::Mark),
+                b'0' => {
+                    self.stack.pop()?;
+                }
+                b'1' => {
+                    self.pop_mark()?;
+                }
+                b'2' => {
+                    let (value, depth) = self.top()?;
+                    self.push(value.clone(), depth)?;
+                }
+                b'N' => self.push(Value::Null, 0)?,
+                0x88 => self.push(Value::Bool(true), 0)?,
+                0x89 => self.push(Value::Bool(false), 0)?,
+                b'I' => {
+                    let value = match self.line()? {
+                        "00" => Value::Bool(false),
+                        "01" => Value::Bool(true),
+                        digits => Value::Int(digits.parse::<i64>().ok()?.to_string()),
+                    };
+                    self.push(value, 0)?;
+                }
+                b'J' => {
+                    let value = i32::from_le_bytes(self.array()?);
+                    self.push(Value::Int(value.to_string()), 0)?;
+                }
+                b'K' => {
+                    let value = self.u8()?;
+                    self.push(Value::Int(value.to_string()), 0)?;
+                }
+                b'M' => {
+                    let value = u16::from_le_bytes(self.array()?);
+                    self.push(Value::Int(value.to_string()), 0)?;
+                }
+                b'L' => {
+                    let line = self.line()?;
+                    let digits = line.strip_suffix('L').unwrap_or(line);
+                    digits.parse::<i128>().ok()?;
+                    let value = Value::Int(digits.to_string());
+                    self.push(value, 0)?;
+                }
+                0x8a => {
+                    let length = self.u8()?.into();
+                    let value = long(self.take(length)?);
+                    self.push(value, 0)?;
+                }
+                0x8b => {
+                    let length = usize::try_from(i32::from_le_bytes(self.array()?)).ok()?;
+                    let value = long(self.take(length)?);
+                    self.push(value, 0)?;
+                }
+                b'F' => {
+                    let value = self.line()?.parse().ok()?;
+                    self.push(Value::Float(value), 0)?;
+                }
+                b'G' => {
+                    let value = f64::from_be_bytes(self.array()?);
+                    self.push(Value::Float(value), 0)?;
+                }
+                b'S' => {
+                    let value = unquote(self.line()?)?;
+                    self.push(string_or_bytes(&value), 0)?;
+                }
+                b'V' => {
+                    let value = unescape_unicode(self.line()?)?;
+                    self.push(Value::Str(value), 0)?;
+                }
+                b'T' | b'U' | b'X' | b'B' | b'C' | 0x8c | 0x8d | 0x8e | 0x96 => {
+                    let length = match opcode {
+                        b'U' | b'C' | 0x8c => self.u8()?.into(),
+                        b'T' | b'X' | b'B' => u32::from_le_bytes(self.array()?) as usize,
+                        _ => usize::try_from(u64::from_le_bytes(self.array()?)).ok()?,
+                    };
+                    let data = self.take(length)?;
+                    let value = match opcode {
+                        b'X' | 0x8c | 0x8d => {
+                            Value::Str(std::str::from_utf8(data).ok()?.to_string())
+                        }
+                        b'T' | b'U' => string_or_bytes(data),
+                        _ => Value::Bytes(data.to_vec()),
+                    };
+                    self.push(value, 0)?;
+                }
+                b']' => self.push(Value::List(None, Vec::new()), 1)?,
+                b')' => self.push(Value::List(Some("tuple".to_string()), Vec::new()), 1)?,
+                b'}' => self.push(Value::Map(Vec::new()), 1)?,
+                0x8f => self.push(Value::List(Some("set".to_string()), Vec::new()), 1)?,
+                b'l' | b't' | 0x91 => {
+                    let kind = match opcode {
+                        b'l' => None,
+                        b't' => Some("tuple".to_string()),
+                        _ => Some("frozenset".to_string()),
+                    };
+                    let (items, depth) = self.pop_mark()?;
+                    self.push(Value::List(kind, items), depth + 1)?;
+                }
+                0x85..=0x87 => {
+                    let count = usize::from(opcode - 0x84);
+                    let mut items = Vec::with_capacity(count);
+                    let mut depth = 0;
+                    for _ in 0..count {
+                        let (item, item_depth) = self.pop()?;
+                        items.push(item);
+                        depth = depth.max(item_depth);
+                    }
+                    items.reverse();
+                    self.push(Value::List(Some("tuple".to_string()), items), depth + 1)?;
+                }
+                b'a' | b'e' | 0x90 => {
+                    let (items, depth) = if opcode == b'a' {
+                        let (item, depth) = self.pop()?;
+                        (vec![item], depth)
+                    } else {
+                        self.pop_mark()?
+                    };
+                    let (list, list_depth) = self.top_mut()?;
+                    let Value::List(_, existing) = list else {
+                        return None;
+                    };
+                    existing.extend(items);
+                    *list_depth = (*list_depth).max(depth + 1);
+                    if *list_depth > MAX_DEPTH {
+                        return None;
+                    }
+                }
+                b'd' | b's' | b'u' => {
+                    let (items, depth) = if opcode == b's' {
+                        let (value, value_depth) = self.pop()?;
+                        let (key, key_depth) = self.pop()?;
+                        (vec![key, value], key_depth.max(value_depth))
+                    } else {
+                        self.pop_mark()?
+                    };
+                    if items.len() % 2 != 0 {
+                        return None;
+                    }
+                    let mut items = items.into_iter();
+                    let mut entries = Vec::new();
+                    while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                        entries.push((key, value));
+                    }
+                    if opcode == b'd' {
+                        self.push(Value::Map(entries), depth + 1)?;
+                    } else {
+                        let (map, map_depth) = self.top_mut()?;
+                        let Value::Map(existing) = map else {
+                            return None;
+                        };
+                        existing.extend(entries);
+                        *map_depth = (*map_depth).max(depth + 1);
+                        if *map_depth > MAX_DEPTH {
+                            return None;
+                        }
+                    }
+                }
+                b'g' | b'h' | b'j' => {
+                    let id = match opcode {
+                        b'g' => self.line()?.parse().ok()?,
+                        b'h' => self.u8()?.into(),
+                        _ => u32::from_le_bytes(self.array()?).into(),
+                    };
+                    let value = self.memo.get(&id)?.clone();
+                    self.push(value, 0)?;
+                }
+                b'p' | b'q' | b'r' | 0x94 => {
+                    let id = match opcode {
+                        b'p' => self.line()?.parse().ok()?,
+                        b'q' => self.u8()?.into(),
+                        b'r' => u32::from_le_bytes(self.array()?).into(),
+                        _ => self.memo.len() as u64,
+                    };
+                    let (value, _) = self.top()?;
+                    let value = value.referenced(|| format!("<reference to value {id}>"));
+                    self.memo.insert(id, value);
+                }
+                b'c' | b'i' => {
+                    let module = self.line()?.to_string();
+                    let name = self.line()?;
+                    let global = Value::Raw(format!("{module}.{name}"));
+                    if opcode == b'c' {
+                        self.push(global, 0)?;
+                    } else {
+                        let (args, depth) = self.pop_mark()?;
+                        self.push(Value::Call(Box::new(global), args), depth + 1)?;
+                    }
+                }
+                0x93 => {
+                    let (Value::Str(name), _) = self.pop()? else {
+                        return None;
+                    };
+                    let (Value::Str(module), _) = self.pop()? else {
+                        return None;
+                    };
+                    self.push(Value::Raw(format!("{module}.{name}")), 0)?;
+                }
+                b'o' => {
+                    let (mut items, depth) = self.pop_mark()?;
+                    if items.is_empty() {
+                        return None;
+                    }
+                    let class = items.remove(0);
+                    self.push(Value::Call(Box::new(class), items), depth + 1)?;
+                }
+                b'R' | 0x81 | 0x92 => {
+                    let kwargs = if opcode == 0x92 {
+                        let (Value::Map(kwargs), _) = self.pop()? else {
+                            return None;
+                        };
+                        kwargs
+                    } else {
+                        Vec::new()
+                    };
+                    let (Value::List(Some(_), mut args), args_depth) = self.pop()? else {
+                        return None;
+                    };
+                    let (callable, callable_depth) = self.pop()?;
+                    for (key, value) in kwargs {
+                        let key = match key {
+                            Value::Str(key) => key,
+                            other => other.dump(),
+                        };
+                        args.push(Value::Raw(format!("{key}={}", value.dump())));
+                    }
+                    let depth = args_depth.max(callable_depth) + 1;
+                    self.push(Value::Call(Box::new(callable), args), depth)?;
+                }
+                b'b' => {
+                    let (state, state_depth) = self.pop()?;
+                    let (object, object_depth) = self.pop()?;
+                    let class = match object {
+                        Value::Call(callable, args) if args.is_empty() => callable.dump(),
+                        other => other.dump(),
+                    };
+                    let fields = match state {
+                        Value::Map(entries)
+                            if entries.iter().all(|(key, _)| matches!(key, Value::Str(_))) =>
+                        {
+                            entries
+                                .into_iter()
+                                .map(|(key, value)| match key {
+                                    Value::Str(key) => (key, value),
+                                    _ => unreachable!("keys were checked to be strings"),
+                                })
+                                .collect()
+                        }
+                        state => vec![("state".to_string(), state)],
+                    };
+                    let depth = state_depth.max(object_depth) + 1;
+                    self.push(Value::Object(class, fields), depth)?;
+                }
+                b'P' => {
+                    let id = self.line()?.to_string();
+                    self.push(Value::Raw(format!("<persistent id {id}>")), 0)?;
+                }
+                b'Q' => {
+                    let (id, _) = self.pop()?;
+                    self.push(Value::Raw(format!("<persistent id {}>", id.dump())), 0)?;
+                }
+                0x82..=0x84 => {
+                    let code = match opcode {
+                        0x82 => u32::from(self.u8()?),
+                        0x83 => u16::from_le_bytes(self.array()?).into(),
+                        _ => i32::from_le_bytes(self.array()?).try_into().ok()?,
+                    };
+                    self.push(Value::Raw(format!("<extension {code}>")), 0)?;
+                }
+                0x97 => self.push(Value::Raw("<out-of-band buffer>".to_string()), 0)?,
+                0x98 => {
+                    self.top()?;
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Pushes a value which nests `depth` deep, None if that is too deep
+    fn push(&mut self, value: Value, depth: usize) -> Option<()> {
+        (depth <= MAX_DEPTH).then(|| self.stack.push(Slot::Value(value, depth)))
+    }
+
+    /// Pops a value, None if the stack is empty or has a mark on top
+    fn pop(&mut self) -> Option<(Value, usize)> {
+        match self.stack.pop()? {
+            Slot::Value(value, depth) => Some((value, depth)),
+            Slot::Mark => None,
+        }
+    }
+
+    /// Pops the values down to the topmost mark, and the mark, giving the
+    /// values in order and how deeply the deepest nests
+    fn pop_mark(&mut self) -> Option<(Vec<Value>, usize)> {
+        let mark = self
+            .stack
+            .iter()
+            .rposition(|slot| matches!(slot, Slot::Mark))?;
+        let mut depth = 0;
+        let values = self
+            .stack
+            .drain(mark..)
+            .skip(1)
+            .map(|slot| match slot {
+                Slot::Value(value, value_depth) => {
+                    depth = depth.max(value_depth);
+                    value
+                }
+                Slot::Mark => unreachable!("the mark found is the topmost"),
+            })
+            .collect();
+        Some((values, depth))
+    }
+
+    /// The value on top of the stack
+    fn top(&self) -> Option<(&Value, usize)> {
+        match self.stack.last()? {
+            Slot::Value(value, depth) => Some((value, *depth)),
+            Slot::Mark => None,
+        }
+    }
+
+    /// The value on top of the stack, to add items to
+    fn top_mut(&mut self) -> Option<(&mut Value, &mut usize)> {
+        match self.stack.last_mut()? {
+            Slot::Value(value, depth) => Some((value, depth)),
+            Slot::Mark => None,
+        }
+    }
+
+    /// Reads a line, as protocol 0 writes its arguments
+    fn line(&mut self) -> Option<&'a str> {
+        let rest = &self.bytes[self.position..];
+        let length = rest.iter().position(|byte| *byte == b'\n')?;
+        self.position += length + 1;
+        std::str::from_utf8(&rest[..length]).ok()
+    }
+
+    /// The next `length` bytes
+    fn take(&mut self, length: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(length)?;
+        let taken = self.bytes.get(self.position..end)?;
+        self.position = end;
+        Some(taken)
+    }
+
+    /// The next `N` bytes as an array
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    /// Reads a byte
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.array::<1>()?[0])
+    }
+}
+
+/// A LONG1 or LONG4 integer: little-endian two's complement of any length
+fn long(bytes: &[u8]) -> Value {
+    if bytes.len() > 16 {
+        return Value::Raw(format!("<{}-byte integer>", bytes.len()));
+    }
+    let negative = bytes.last().is_some_and(|byte| byte & 0x80 != 0);
+    let mut padded = [if negative { 0xff } else { 0x00 }; 16];
+    padded[..bytes.len()].copy_from_slice(bytes);
+    Value::Int(i128::from_le_bytes(padded).to_string())
+}
+
+/// The bytes of a protocol 0 STRING argument, a Python `repr` of a string
+fn unquote(line: &str) -> Option<Vec<u8>> {
+    let quote = line.chars().next().filter(|c| matches!(c, '\'' | '"'))?;
+    let inner = line.strip_prefix(quote)?.strip_suffix(quote)?;
+    let mut bytes = Vec::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        let byte = match chars.next()? {
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            '0' => b'\0',
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                u8::from_str_radix(&hex, 16).ok()?
+            }
+            escaped if escaped.is_ascii() => escaped as u8,
+            _ => return None,
+        };
+        bytes.push(byte);
+    }
+    Some(bytes)
+}
+
+/// The text of a protocol 0 UNICODE argument, which escapes only
+/// backslashes, newlines and characters written as `\uXXXX` or `\UXXXXXXXX`
+fn unescape_unicode(line: &str) -> Option<String> {
+    let mut text = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        let digits = match chars.next()? {
+            'u' => 4,
+            'U' => 8,
+            other => {
+                text.push('\\');
+                text.push(other);
+                continue;
+            }
+        };
+        let hex: String = chars.by_ref().take(digits).collect();
+        text.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    /// `pickle.dumps({"user": "alice", "ids": [1, 300, -2], "ok": True}, protocol=4)`
+    const DICT_PICKLE: &[u8] = b"\x80\x04\x95.\x00\x00\x00\x00\x00\x00\x00}\x94(\x8c\x04user\x94\x8c\x05alice\x94\x8c\x03ids\x94]\x94(K\x01M,\x01J\xfe\xff\xff\xffe\x8c\x02ok\x94\x88u.";
+
+    #[test]
+    fn pickle_dumps_containers() {
+        let decoder = Decoder::<PickleDecoder>::new();
+        let candidate = Candidate::from_bytes(DICT_PICKLE.to_vec());
+        let result = decoder.crack_candidate(&candidate, &get_checker(), &Config::default());
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "{\n  \"user\": \"alice\"\n  \"ids\": [\n    1\n    300\n    -2\n  ]\n  \"ok\": true\n}"
+        );
+    }
+
+    #[test]
+    fn pickle_shows_objects_and_calls_without_running_them() {
+        // A protocol 2 pickle of an object with state, and a REDUCE in Base64
+        let object = b"\x80\x02c__main__\nUser\nq\x00)\x81q\x01}q\x02X\x04\x00\x00\x00nameq\x03X\x03\x00\x00\x00bobq\x04sb.";
+        assert_eq!(
+            parse(object).unwrap().dump(),
+            "__main__.User {\n  name: \"bob\"\n}"
+        );
+        let decoder = Decoder::<PickleDecoder>::new();
+        // pickle.dumps of a __reduce__ to os.system("id"), protocol 4, Base64 encoded
+        let result = decoder.crack(
+            "gASVHQAAAAAAAACMBXBvc2l4lIwGc3lzdGVtlJOUjAJpZJSFlFKULg==",
+            &get_checker(),
+            &Config::default(),
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "posix.system(\"id\")");
+        let steps: Vec<_> = result.inner_steps.iter().map(|step| step.decoder).collect();
+        assert_eq!(steps, ["Base64", "Pickle"]);
+    }
+
+    #[test]
+    fn pickle_references_do_not_copy_containers() {
+        // Each tuple holds the one before it twice, which copied out would
+        // double at every level and run out of memory
+        let mut pickle = b"(lp0\n0".to_vec();
+        for n in 0..23 {
+            pickle.extend(format!("(g{n}\ng{n}\ntp{}\n0", n + 1).bytes());
+        }
+        pickle.extend(b"(g23\nl.");
+        assert_eq!(parse(&pickle).unwrap().dump(), "[\n  <reference to value 23>\n]");
+
+        // Short strings are still shown where they are referred back to
+        let shared = b"(lp0\nVbob\np1\nag1\na.";
+        assert_eq!(parse(shared).unwrap().dump(), "[\n  \"bob\"\n  \"bob\"\n]");
+    }
+
+    #[test]
+    fn pickle_rejects_text_and_scalars() {
+        assert!(parse(b"Hello world.").is_none());
+        assert!(parse(b"I1\n.").is_none());
+        assert!(parse(b"\x80\x04K\x01.trailing").is_none());
+    }
+}
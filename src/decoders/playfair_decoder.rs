@@ -3,14 +3,16 @@
 //! This implementation attempts to crack Playfair using dictionary attacks
 //! with a comprehensive 10000+ word wordlist and frequency analysis for scoring.
 
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
-use crate::cryptanalysis::{ATTACK_WORDLIST, fitness_score, is_likely_english};
+use crate::text_info::TextInfo;
+use crate::cryptanalysis::{attack_keywords, dictionary_attack, is_likely_plaintext};
 use crate::decoders::interface::check_string_success;
 use gibberish_or_not::Sensitivity;
 
 use super::crack_results::CrackResult;
-use super::interface::Crack;
+use super::interface::{Alphabet, Crack};
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -31,16 +33,71 @@ impl Crack for Decoder<PlayfairDecoder> {
     }
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        self.crack_with_info(text, &TextInfo::new(text), checker, config)
+    }
+
+    /// The letters of the candidate are only picked out once, for all decoders
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        self.crack_with_info(&candidate.text(), candidate.info(), checker, config)
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    /// Hill-climbs over key squares
+    fn expected_cost(&self) -> f32 {
+        100.0
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+    /// Playfair only reads the letters
+    fn alphabet(&self) -> Alphabet {
+        Alphabet::Letters
+    }
+    /// Letters are read in pairs
+    fn needs_even_length(&self) -> bool {
+        true
+    }
+    /// At least one pair
+    fn min_length(&self) -> usize {
+        2
+    }
+}
+
+impl Decoder<PlayfairDecoder> {
+    /// Cracks `text`, given what has been worked out about it
+    fn crack_with_info(
+        &self,
+        text: &str,
+        info: &TextInfo,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
         trace!("Trying Playfair cipher with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Clean text - only alphabetic, uppercase
-        let clean_text: String = text
-            .to_uppercase()
-            .chars()
-            .filter(|c| c.is_ascii_alphabetic())
-            .map(|c| if c == 'J' { 'I' } else { c })
-            .collect();
+        let clean_text = info.uppercase_letters.replace('J', "I");
 
         if clean_text.len() < 2 {
             info!("Text too short for Playfair");
@@ -48,79 +105,57 @@ impl Crack for Decoder<PlayfairDecoder> {
         }
 
         // Playfair requires even number of characters
-        if clean_text.len() % 2 != 0 {
+        if !clean_text.len().is_multiple_of(2) {
             info!("Playfair requires even number of characters");
             return results;
         }
 
         let checker_with_sensitivity = checker.with_sensitivity(Sensitivity::Low);
-        
-        // Track best result for cryptanalysis fallback
-        let mut best_score = f64::MIN;
-        let mut best_plaintext = String::new();
-        let mut best_key = String::new();
 
         // Use the comprehensive wordlist from cryptanalysis module
-        trace!("Trying {} keywords for Playfair", ATTACK_WORDLIST.len());
-        for keyword in ATTACK_WORDLIST.iter() {
-            // Skip very short keywords
-            if keyword.len() < 4 {
-                continue;
-            }
-            
-            if let Some(decoded) = decrypt_playfair(&clean_text, keyword) {
-                let decoded_lower = decoded.to_lowercase();
-                
-                // Score the result using cryptanalysis
-                let score = fitness_score(&decoded_lower);
-                if score > best_score {
-                    best_score = score;
-                    best_plaintext = decoded_lower.clone();
-                    best_key = keyword.clone();
+        let keywords = attack_keywords(&config.dictionary_words);
+        trace!("Trying {} keywords for Playfair", keywords.len());
+        let attack = dictionary_attack(
+            &keywords,
+            &config.languages,
+            config.deadline.as_ref(),
+            |keyword| {
+                // Skip very short keywords
+                if keyword.len() < 4 {
+                    return None;
                 }
-                
-                if check_string_success(&decoded_lower, text) {
-                    let checker_result = checker_with_sensitivity.check(&decoded_lower, config);
-                    if checker_result.is_identified {
-                        debug!("Playfair dictionary attack succeeded with key: {}", keyword);
-                        results.unencrypted_text = Some(vec![decoded_lower]);
-                        results.update_checker(&checker_result);
-                        results.key = Some(keyword.to_uppercase());
-                        return results;
-                    }
+                decrypt_playfair(&clean_text, keyword).map(|decoded| decoded.to_lowercase())
+            },
+            |decoded| {
+                if !check_string_success(decoded, text) {
+                    return None;
                 }
-            }
+                let checker_result = checker_with_sensitivity.check(decoded, config);
+                checker_result.is_identified.then_some(checker_result)
+            },
+        );
+
+        if let Some((hit, checker_result)) = attack.hit {
+            debug!("Playfair dictionary attack succeeded with key: {}", hit.keyword);
+            results.unencrypted_text = Some(vec![hit.plaintext.into()]);
+            results.update_checker(&checker_result);
+            results.key = Some(hit.keyword.to_uppercase());
+            return results;
         }
-        
+
         // If cryptanalysis found a good result, return it
-        if is_likely_english(&best_plaintext) && !best_key.is_empty() {
-            debug!("Using best cryptanalysis result for Playfair with key: {}", best_key);
-            let checker_result = checker_with_sensitivity.check(&best_plaintext, config);
-            results.unencrypted_text = Some(vec![best_plaintext]);
+        if let Some(best) = attack.best.filter(|best| is_likely_plaintext(&best.plaintext, &config.languages)) {
+            debug!("Using best cryptanalysis result for Playfair with key: {}", best.keyword);
+            let checker_result = checker_with_sensitivity.check(&best.plaintext, config);
+            results.unencrypted_text = Some(vec![best.plaintext.into()]);
             results.update_checker(&checker_result);
-            results.key = Some(best_key.to_uppercase());
+            results.key = Some(best.keyword.to_uppercase());
             return results;
         }
 
         info!("Failed to decode Playfair cipher");
         results
     }
-
-    fn get_tags(&self) -> &Vec<&str> {
-        &self.tags
-    }
-
-    fn get_name(&self) -> &str {
-        self.name
-    }
-
-    fn get_description(&self) -> &str {
-        self.description
-    }
-
-    fn get_link(&self) -> &str {
-        self.link
-    }
 }
 
 /// Generate the Playfair key square from a keyword
@@ -0,0 +1,174 @@
+//! Playing card decoder
+//! Reads a sequence of playing cards as letters, the way Bruce Schneier's
+//! Solitaire cipher turns cards into its keystream: in bridge order clubs are
+//! worth 1 to 13, diamonds 14 to 26, hearts 27 to 39 and spades 40 to 52, and
+//! a card's letter is its value counted round the alphabet, so A♣ and A♥
+//! are both A and K♠ is Z. Cards are written like `AC`, `10H`, `TD`, `Q♠`
+//! or as the Unicode playing card characters, such as 🂡.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{info, trace};
+
+/// The Playing Cards decoder
+/// Call: `let decoder = Decoder::<PlayingCardDecoder>::new()` to create a new instance
+/// And then call: `result = decoder.crack(input)` to decode
+pub struct PlayingCardDecoder;
+
+/// Fewer cards than this are too likely to be something else
+const MIN_CARDS: usize = 4;
+
+impl Crack for Decoder<PlayingCardDecoder> {
+    fn new() -> Decoder<PlayingCardDecoder> {
+        Decoder {
+            name: "Playing Cards",
+            description: "Reads playing cards as letters by their value in bridge order, as the Solitaire cipher does: clubs 1-13, diamonds 14-26, hearts 27-39, spades 40-52, counted round the alphabet.",
+            link: "https://www.schneier.com/academic/solitaire/",
+            tags: vec!["cards", "solitaire", "hobbyist", "substitution", "cipher"],
+            popularity: 0.3,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Playing Cards with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let Some(values) = card_values(text) else {
+            info!("Failed to read playing cards");
+            return results;
+        };
+        let decoded: String = values
+            .iter()
+            .map(|value| char::from(b'A' + (value - 1) % 26))
+            .collect();
+        if !check_string_success(&decoded, text) {
+            return results;
+        }
+        let checker_result = checker.check(&decoded, config);
+        results.unencrypted_text = Some(vec![decoded.into()]);
+        results.update_checker(&checker_result);
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The bridge order values, 1 to 52, of the cards in the text, if it is all
+/// cards separated by spaces or commas. Unicode cards need no separators.
+fn card_values(text: &str) -> Option<Vec<u8>> {
+    let mut values = Vec::new();
+    for token in text.split(|c: char| c.is_whitespace() || c == ',') {
+        if token.is_empty() {
+            continue;
+        }
+        match written_card(token) {
+            Some(value) => values.push(value),
+            None => {
+                for c in token.chars() {
+                    values.push(unicode_card(c)?);
+                }
+            }
+        }
+    }
+    (values.len() >= MIN_CARDS).then_some(values)
+}
+
+/// The value of a card written as its rank then its suit, e.g. `QS` or `10♦`
+fn written_card(token: &str) -> Option<u8> {
+    let suit = token.chars().next_back()?;
+    let rank = &token[..token.len() - suit.len_utf8()];
+    let rank = match rank.to_ascii_uppercase().as_str() {
+        "A" => 1,
+        "T" | "10" => 10,
+        "J" => 11,
+        "Q" => 12,
+        "K" => 13,
+        digit => digit
+            .parse::<u8>()
+            .ok()
+            .filter(|rank| (2..=9).contains(rank))?,
+    };
+    let suit = match suit.to_ascii_uppercase() {
+        'C' | '♣' | '♧' => 0,
+        'D' | '♦' | '♢' => 1,
+        'H' | '♥' | '♡' => 2,
+        'S' | '♠' | '♤' => 3,
+        _ => return None,
+    };
+    Some(suit * 13 + rank)
+}
+
+/// The value of a card from the Unicode Playing Cards block, e.g. 🂡 for
+/// the ace of spades. Knights, which only tarot decks have, aren't cards here.
+fn unicode_card(c: char) -> Option<u8> {
+    let code = u32::from(c);
+    // Spades, hearts, diamonds then clubs, sixteen code points apart
+    let suit = match code {
+        0x1f0a1..=0x1f0ae => 3,
+        0x1f0b1..=0x1f0be => 2,
+        0x1f0c1..=0x1f0ce => 1,
+        0x1f0d1..=0x1f0de => 0,
+        _ => return None,
+    };
+    let rank = match (code & 0xf) as u8 {
+        12 => return None,
+        rank @ 1..=11 => rank,
+        rank => rank - 1,
+    };
+    Some(suit * 13 + rank)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn playing_cards_to_letters() {
+        let decoder = Decoder::<PlayingCardDecoder>::new();
+        // H = 8, E = 31, L = 12 or 38 and O = 41
+        let result = decoder.crack("8C, 5H, QC, QH, 2S", &get_checker(), &Config::default());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "HELLO");
+        assert_eq!(card_values("A♣ K♦ A♥ K♠"), Some(vec![1, 26, 27, 52]));
+        assert_eq!(card_values("🂡🂱🃁🃑🂮"), Some(vec![40, 27, 14, 1, 52]));
+    }
+
+    #[test]
+    fn playing_cards_need_cards() {
+        assert_eq!(card_values("AC 2D"), None);
+        assert_eq!(card_values("1C 2D 3H 4S"), None);
+        assert_eq!(card_values("hello there my friend"), None);
+        assert_eq!(card_values("🂡🂬🃁🃑"), None);
+    }
+}
@@ -0,0 +1,116 @@
+//! Pollux decoder
+//! Pollux writes the message in Morse code with `x` between letters and `xx`
+//! between words, then swaps each dot, dash and `x` for any of the digits 0 to
+//! 9 given to it by the key. The key is found by trying each symbol for each
+//! digit, see [`super::digit_morse`], and is given as the symbol for each
+//! digit, like `0=. 1=x 2=-`.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+
+use super::crack_results::CrackResult;
+use super::digit_morse::POLLUX;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::trace;
+
+/// The Pollux decoder
+/// Call: `let decoder = Decoder::<PolluxDecoder>::new()` to create a new instance
+/// And then call: `result = decoder.crack(input)` to decode
+pub struct PolluxDecoder;
+
+impl Crack for Decoder<PolluxDecoder> {
+    fn new() -> Decoder<PolluxDecoder> {
+        Decoder {
+            name: "Pollux",
+            description: "The ACA's Pollux cipher writes the message in Morse code, then swaps each dot, dash and letter break for one of several digits given to it.",
+            link: "https://www.cryptogram.org/downloads/aca.info/ciphers/Pollux.pdf",
+            tags: vec!["pollux", "morse", "cipher", "classic"],
+            popularity: 0.2,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Pollux with text {:?}", text);
+        POLLUX.crack(text, checker, config, CrackResult::new(self, text))
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    /// Searches the keys digit by digit
+    fn expected_cost(&self) -> f32 {
+        30.0
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+
+    /// Decodes with the symbol for each digit, like `0=. 1=x 2=-`
+    fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        POLLUX.decode_with_key(text, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn pollux_cracks() {
+        let decoder = Decoder::<PolluxDecoder>::new();
+        let result = decoder.crack(
+            "27343 86596 22606 60765 93734 00090 69257 31581 62186 35014 34789 14678 89251 98691 59405 03764 60506 97798 13710 37164 46570 61180 65",
+            &get_checker(),
+            &Config::default(),
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "MEET ME AT THE OLD BRIDGE AFTER MIDNIGHT"
+        );
+    }
+
+    #[test]
+    fn pollux_decodes_with_key() {
+        let decoder = Decoder::<PolluxDecoder>::new();
+        assert_eq!(
+            decoder
+                .decode_with_key("080612 25", "0=. 1=- 2=x 5=. 6=x 8=.")
+                .unwrap(),
+            "ST E"
+        );
+        assert!(decoder.decode_with_key("0123", "0=.").is_err());
+    }
+
+    #[test]
+    fn pollux_rejects_other_text() {
+        let decoder = Decoder::<PolluxDecoder>::new();
+        let result = decoder.crack("hello world", &get_checker(), &Config::default());
+        assert!(result.unencrypted_text.is_none());
+    }
+}
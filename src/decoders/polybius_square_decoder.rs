@@ -42,14 +42,14 @@ impl Crack for Decoder<PolybiusSquareDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Polybius Square cipher with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Try decoding with numeric format (11-55)
         if let Some(decoded) = decode_polybius_numeric(text) {
             if check_string_success(&decoded, text) {
                 let checker_result = checker.check(&decoded, config);
                 if checker_result.is_identified {
-                    results.unencrypted_text = Some(vec![decoded]);
+                    results.unencrypted_text = Some(vec![decoded.into()]);
                     results.update_checker(&checker_result);
                     return results;
                 }
@@ -61,7 +61,7 @@ impl Crack for Decoder<PolybiusSquareDecoder> {
             if check_string_success(&decoded, text) {
                 let checker_result = checker.check(&decoded, config);
                 if checker_result.is_identified {
-                    results.unencrypted_text = Some(vec![decoded]);
+                    results.unencrypted_text = Some(vec![decoded.into()]);
                     results.update_checker(&checker_result);
                     return results;
                 }
@@ -80,6 +80,10 @@ impl Crack for Decoder<PolybiusSquareDecoder> {
         self.name
     }
 
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
     fn get_description(&self) -> &str {
         self.description
     }
@@ -95,7 +99,7 @@ fn decode_polybius_numeric(text: &str) -> Option<String> {
     let digits: Vec<char> = cleaned.chars().filter(|c| c.is_ascii_digit()).collect();
 
     // Must have even number of digits
-    if digits.len() % 2 != 0 || digits.is_empty() {
+    if !digits.len().is_multiple_of(2) || digits.is_empty() {
         return None;
     }
 
@@ -129,7 +133,7 @@ fn decode_polybius_letters(text: &str) -> Option<String> {
     let letters: Vec<char> = cleaned.chars().filter(|c| c.is_ascii_alphabetic()).collect();
 
     // Must have even number of letters
-    if letters.len() % 2 != 0 || letters.is_empty() {
+    if !letters.len().is_multiple_of(2) || letters.is_empty() {
         return None;
     }
 
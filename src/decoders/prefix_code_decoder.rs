@@ -0,0 +1,221 @@
+//! Prefix code decoder
+//! Reads text written with a prefix code, like a Huffman table handed out
+//! with a challenge as `a=0,b=10,c=11`. No code starts another, so the codes
+//! can be read back one after another without separators.
+//!
+//! The codebook comes from `Config.codebook`, set with `--codebook`. Without
+//! one, an input whose last line is the encoded text and whose lines before
+//! it are a codebook is read with that codebook.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{info, trace};
+
+/// A prefix code: each symbol and the code standing for it
+pub type Codebook = Vec<(String, String)>;
+
+/// The Prefix Code decoder
+/// Call: `let decoder = Decoder::<PrefixCodeDecoder>::new()` to create a new instance
+/// And then call: `result = decoder.crack(input)` to decode
+pub struct PrefixCodeDecoder;
+
+impl Crack for Decoder<PrefixCodeDecoder> {
+    fn new() -> Decoder<PrefixCodeDecoder> {
+        Decoder {
+            name: "Prefix Code",
+            description: "Reads text written with a prefix code such as a Huffman table, given with --codebook or on the lines before the text.",
+            link: "https://en.wikipedia.org/wiki/Prefix_code",
+            tags: vec!["prefix", "huffman", "decoder"],
+            popularity: 0.3,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying Prefix Code with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let decoded = match &config.codebook {
+            Some(codebook) => decode(text, codebook),
+            None => {
+                included_codebook(text).and_then(|(codebook, encoded)| decode(encoded, &codebook))
+            }
+        };
+        let Some(decoded) = decoded else {
+            info!("Failed to read the text with a prefix code");
+            return results;
+        };
+        let checker_result = checker.check(&decoded, config);
+        results.unencrypted_text = Some(vec![decoded.into()]);
+        results.update_checker(&checker_result);
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+
+    /// Decodes with a codebook written like `a=0,b=10,c=11`
+    fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        let codebook = parse_codebook(key)?;
+        decode(text, &codebook)
+            .ok_or_else(|| "The text isn't written with the codebook's codes".to_string())
+    }
+}
+
+/// Reads a codebook of `symbol=code` entries separated by commas or new
+/// lines, like `a=0,b=10,c=11`. The symbol `space` stands for a space and
+/// `=` may be a symbol too, as in `==110`.
+///
+/// # Errors
+/// Returns a message if an entry isn't `symbol=code`, a code is empty or has
+/// whitespace, a code is given twice, or a code starts another.
+pub fn parse_codebook(text: &str) -> Result<Codebook, String> {
+    let mut codebook: Codebook = Vec::new();
+    for entry in text
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+    {
+        let (symbol, code) = match entry.strip_prefix('=') {
+            Some(rest) if rest.starts_with('=') => ("=", &rest[1..]),
+            _ => entry
+                .split_once('=')
+                .ok_or_else(|| format!("Expected symbol=code, got '{entry}'"))?,
+        };
+        let symbol = match symbol.trim() {
+            "space" => " ",
+            symbol => symbol,
+        };
+        let code = code.trim();
+        if symbol.is_empty() || code.is_empty() || code.contains(char::is_whitespace) {
+            return Err(format!("Expected symbol=code, got '{entry}'"));
+        }
+        if let Some((other, _)) = codebook
+            .iter()
+            .find(|(_, other)| other.starts_with(code) || code.starts_with(other.as_str()))
+        {
+            return Err(format!(
+                "The codes of '{other}' and '{symbol}' can't be told apart, as one starts the other"
+            ));
+        }
+        codebook.push((symbol.to_string(), code.to_string()));
+    }
+    if codebook.len() < 2 {
+        return Err("A codebook needs at least two codes".to_string());
+    }
+    Ok(codebook)
+}
+
+/// Reads `text` with `codebook`, ignoring whitespace. A last code cut short
+/// is taken to be padding, as when the bits were packed into bytes. None if
+/// anything else isn't one of the codes.
+fn decode(text: &str, codebook: &Codebook) -> Option<String> {
+    let encoded: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut rest = encoded.as_str();
+    let mut decoded = String::new();
+    while !rest.is_empty() {
+        match codebook
+            .iter()
+            .find(|(_, code)| rest.starts_with(code.as_str()))
+        {
+            Some((symbol, code)) => {
+                decoded.push_str(symbol);
+                rest = &rest[code.len()..];
+            }
+            None if codebook.iter().any(|(_, code)| code.starts_with(rest)) => break,
+            None => return None,
+        }
+    }
+    (!decoded.is_empty()).then_some(decoded)
+}
+
+/// Splits text into a codebook on the lines before its last line and the
+/// encoded text on its last line, if the lines before are a codebook
+fn included_codebook(text: &str) -> Option<(Codebook, &str)> {
+    let (table, encoded) = text.trim().rsplit_once('\n')?;
+    Some((parse_codebook(table).ok()?, encoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    /// A Huffman table for "hello world"
+    const HELLO_CODEBOOK: &str = "l=00,o=01,h=100,e=101,space=1100,w=1101,r=1110,d=1111";
+
+    #[test]
+    fn prefix_code_with_codebook() {
+        let decoder = Decoder::<PrefixCodeDecoder>::new();
+        let config = Config {
+            codebook: Some(parse_codebook(HELLO_CODEBOOK).unwrap()),
+            ..Config::default()
+        };
+        let result = decoder.crack(
+            "10010100 00011100 11010111 10001111",
+            &get_checker(),
+            &config,
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "hello world");
+    }
+
+    #[test]
+    fn prefix_code_with_codebook_in_input() {
+        let decoder = Decoder::<PrefixCodeDecoder>::new();
+        let result = decoder.crack(
+            &format!("{HELLO_CODEBOOK}\n1001010000011100110101111000111111"),
+            &get_checker(),
+            &Config::default(),
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "hello world");
+    }
+
+    #[test]
+    fn prefix_code_without_codebook() {
+        let decoder = Decoder::<PrefixCodeDecoder>::new();
+        let result = decoder.crack("0101", &get_checker(), &Config::default());
+        assert!(result.unencrypted_text.is_none());
+    }
+
+    #[test]
+    fn codebook_must_be_a_prefix_code() {
+        assert!(parse_codebook("a=0,b=01").is_err());
+        assert!(parse_codebook("a=0,b=0").is_err());
+        assert!(parse_codebook("a0,b=1").is_err());
+        assert_eq!(
+            parse_codebook("==0\nspace=1").unwrap(),
+            vec![
+                ("=".to_string(), "0".to_string()),
+                (" ".to_string(), "1".to_string())
+            ]
+        );
+    }
+}
@@ -28,7 +28,7 @@ impl Crack for Decoder<PunycodeDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Punycode with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         let decoded_text = decode_punycode_no_error_handling(text);
 
@@ -42,7 +42,7 @@ impl Crack for Decoder<PunycodeDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results.update_checker(&checker_result);
 
         results
@@ -70,10 +70,7 @@ fn decode_punycode_no_error_handling(text: &str) -> Option<String> {
         text
     };
 
-    match punycode::decode(input) {
-        Ok(s) => Some(s),
-        Err(_) => None,
-    }
+    punycode::decode(input).ok()
 }
 
 #[cfg(test)]
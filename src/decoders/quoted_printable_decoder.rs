@@ -28,7 +28,7 @@ impl Crack for Decoder<QuotedPrintableDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Quoted-Printable with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // quoted_printable crate expects ParseMode.
         // We can use decode(input, mode)
@@ -36,7 +36,7 @@ impl Crack for Decoder<QuotedPrintableDecoder> {
             if let Ok(decoded_text) = String::from_utf8(bytes) {
                  if check_string_success(&decoded_text, text) {
                     let checker_result = checker.check(&decoded_text, config);
-                    results.unencrypted_text = Some(vec![decoded_text]);
+                    results.unencrypted_text = Some(vec![decoded_text.into()]);
                     results.update_checker(&checker_result);
                  }
             }
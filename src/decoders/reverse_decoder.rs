@@ -8,6 +8,7 @@ use crate::config::Config;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::trace;
@@ -47,14 +48,14 @@ impl Crack for Decoder<ReverseDecoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Running reverse string");
-        let mut result = CrackResult::new(self, text.to_string());
+        let mut result = CrackResult::new(self, text);
         if text.is_empty() {
             return result;
         }
         let rev_str: String = text.chars().rev().collect();
         let checker_res = checker.check(&rev_str, config);
 
-        result.unencrypted_text = Some(vec![rev_str]);
+        result.unencrypted_text = Some(vec![rev_str.into()]);
         result.update_checker(&checker_res);
         result
     }
@@ -66,6 +67,10 @@ impl Crack for Decoder<ReverseDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -76,6 +81,13 @@ impl Crack for Decoder<ReverseDecoder> {
     }
 }
 
+impl Encode for Decoder<ReverseDecoder> {
+    /// Reversing is its own inverse
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(text.chars().rev().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
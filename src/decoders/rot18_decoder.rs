@@ -8,6 +8,7 @@ use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{info, trace};
@@ -29,7 +30,7 @@ impl Crack for Decoder<Rot18Decoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying ROT18 with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Check if text contains letters or digits
         if !text.chars().any(|c| c.is_ascii_alphanumeric()) {
@@ -60,7 +61,7 @@ impl Crack for Decoder<Rot18Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results.update_checker(&checker_result);
 
         results
@@ -74,6 +75,10 @@ impl Crack for Decoder<Rot18Decoder> {
         self.name
     }
 
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
     fn get_description(&self) -> &str {
         self.description
     }
@@ -83,6 +88,13 @@ impl Crack for Decoder<Rot18Decoder> {
     }
 }
 
+impl Encode for Decoder<Rot18Decoder> {
+    /// ROT18 is its own inverse
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(rot18(text))
+    }
+}
+
 /// Apply ROT18 transformation (ROT13 for letters, ROT5 for digits)
 fn rot18(text: &str) -> String {
     text.chars()
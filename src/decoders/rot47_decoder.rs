@@ -11,6 +11,7 @@ use gibberish_or_not::Sensitivity;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{info, trace};
@@ -24,7 +25,9 @@ impl Crack for Decoder<ROT47Decoder> {
             description: "ROT47 is a derivative of ROT13 which, in addition to scrambling the basic letters, treats numbers and common symbols. Instead of using the sequence A–Z as the alphabet, ROT47 uses a larger set of characters from the common character encoding known as ASCII. Specifically, the 7-bit printable characters, excluding space, from decimal 33 '!' through 126 '~', 94 in total.",
             link: "https://en.wikipedia.org/wiki/ROT13#Variants",
             tags: vec!["rot47", "substitution", "decoder", "reciprocal"],
-            popularity: 1.0,
+            // Any printable text decodes, so like ROT5 and ROT18 it shouldn't
+            // run before the encodings which can tell they don't apply
+            popularity: 0.4,
             phantom: std::marker::PhantomData,
         }
     }
@@ -34,7 +37,7 @@ impl Crack for Decoder<ROT47Decoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying rot47 with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let mut decoded_strings = Vec::new();
 
         // Use the checker with Low sensitivity for ROT47 cipher
@@ -56,12 +59,12 @@ impl Crack for Decoder<ROT47Decoder> {
             // If checkers return true, exit early with the correct result
             if checker_result.is_identified {
                 trace!("Found a match with rot47 shift {}", shift);
-                results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string()]);
+                results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string().into()]);
                 results.update_checker(&checker_result);
                 return results;
             }
         }
-        results.unencrypted_text = Some(decoded_strings);
+        results.unencrypted_text = Some(decoded_strings.into_iter().map(Into::into).collect());
         results
     }
     /// Gets all tags for this decoder
@@ -72,6 +75,10 @@ impl Crack for Decoder<ROT47Decoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -82,6 +89,13 @@ impl Crack for Decoder<ROT47Decoder> {
     }
 }
 
+impl Encode for Decoder<ROT47Decoder> {
+    /// ROT47 is its own inverse
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(rot47_to_alphabet(text, 47))
+    }
+}
+
 /// Maps rot47 to the alphabet (up to ROT94 with the ROT47 alphabet)
 fn rot47_to_alphabet(text: &str, shift: u8) -> String {
     let mut result = String::new();
@@ -8,6 +8,7 @@ use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{info, trace};
@@ -29,7 +30,7 @@ impl Crack for Decoder<Rot5Decoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying ROT5 with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Check if text contains any digits
         if !text.chars().any(|c| c.is_ascii_digit()) {
@@ -50,7 +51,7 @@ impl Crack for Decoder<Rot5Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
         results.update_checker(&checker_result);
 
         results
@@ -64,6 +65,10 @@ impl Crack for Decoder<Rot5Decoder> {
         self.name
     }
 
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
     fn get_description(&self) -> &str {
         self.description
     }
@@ -73,6 +78,13 @@ impl Crack for Decoder<Rot5Decoder> {
     }
 }
 
+impl Encode for Decoder<Rot5Decoder> {
+    /// ROT5 is its own inverse
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(rot5(text))
+    }
+}
+
 /// Apply ROT5 transformation to a string (only affects digits)
 fn rot5(text: &str) -> String {
     text.chars()
@@ -0,0 +1,145 @@
+//! Decode SAML messages
+//! Performs error handling and returns a string
+//! Call saml_decoder.crack to use.
+//!
+//! Finds `SAMLRequest=` and `SAMLResponse=` parameters in a pasted URL or
+//! HTTP request. Their values are URL-encoded Base64, and with the redirect
+//! binding the XML was also DEFLATE compressed, so that is undone too.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use base64::{engine::general_purpose, Engine as _};
+use flate2::read::DeflateDecoder;
+use std::io::Read;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use log::trace;
+
+/// The parameters SAML messages are sent in
+const SAML_PARAMETERS: [&str; 2] = ["SAMLRequest=", "SAMLResponse="];
+
+/// The most bytes a message is inflated to, so a DEFLATE bomb can't use up
+/// the memory
+const MAX_INFLATED_LEN: u64 = 1024 * 1024;
+
+/// The SAML decoder, call:
+/// `let saml_decoder = Decoder::<SamlDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = saml_decoder.crack(input)` to decode the SAML messages in the input
+pub struct SamlDecoder;
+
+impl Crack for Decoder<SamlDecoder> {
+    fn new() -> Decoder<SamlDecoder> {
+        Decoder {
+            name: "SAML",
+            description: "Decodes SAMLRequest and SAMLResponse parameters from a pasted URL or HTTP request: URL decoding, Base64 and, for the redirect binding, DEFLATE.",
+            link: "https://en.wikipedia.org/wiki/SAML_2.0#HTTP_Redirect_Binding",
+            tags: vec!["http", "saml", "base64", "compression", "decoder"],
+            popularity: 0.5,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Trying SAML with text {:?}", text);
+        let mut results = CrackResult::new(self, text);
+        let messages: Vec<String> = saml_values(text).filter_map(decode_message).collect();
+        if messages.is_empty() {
+            return results;
+        }
+        let decoded = messages.join("\n");
+        let mut checker_result = checker.check(&decoded, config);
+        // A SAML message is what was encoded, however little XML looks like English
+        checker_result.is_identified = true;
+        results.unencrypted_text = Some(vec![decoded.into()]);
+        results.update_checker(&checker_result);
+        results
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    fn get_link(&self) -> &str {
+        self.link
+    }
+}
+
+/// The value of every SAML parameter in the text, up to the next `&`, quote
+/// or whitespace
+fn saml_values(text: &str) -> impl Iterator<Item = &str> {
+    SAML_PARAMETERS.iter().flat_map(move |parameter| {
+        text.match_indices(parameter).map(move |(start, _)| {
+            let value = &text[start + parameter.len()..];
+            let end = value
+                .find(|c: char| c == '&' || c == '"' || c.is_whitespace())
+                .unwrap_or(value.len());
+            &value[..end]
+        })
+    })
+}
+
+/// The XML of a SAML parameter's value
+fn decode_message(value: &str) -> Option<String> {
+    let base64 = urlencoding::decode(value).ok()?;
+    let bytes = general_purpose::STANDARD.decode(base64.as_bytes()).ok()?;
+    let mut inflated = Vec::new();
+    let xml = match DeflateDecoder::new(bytes.as_slice())
+        .take(MAX_INFLATED_LEN)
+        .read_to_end(&mut inflated)
+    {
+        Ok(_) if !inflated.is_empty() => String::from_utf8(inflated).ok()?,
+        // The POST binding doesn't compress
+        _ => String::from_utf8(bytes).ok()?,
+    };
+    xml.trim_start().starts_with('<').then_some(xml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SamlDecoder;
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    fn get_checker() -> CheckerTypes {
+        CheckerTypes::CheckAthena(Checker::<Athena>::new())
+    }
+
+    #[test]
+    fn saml_redirect_binding() {
+        let decoder = Decoder::<SamlDecoder>::new();
+        let url = "https://idp.example.com/sso?SAMLRequest=fc%2B9CsJAEATgVwn7AInGbkkOAjaCNgr2R1xI4P682YM8vlGb2FgOM3wwHax3iYeiU7jKswi0WrwL4E%2FRU8mBo8UMDtYLWEe%2BDZczt%2FWOU44ax%2BioOh17mh%2F79kDVXTLmGHpaF2S6N8MnoEjewP9dC0jWFSEzqSZw0yDVslifnNRj9F2zYc03%2FZ4wLw%3D%3D&RelayState=xyz";
+        let result = decoder.crack(url, &get_checker(), &crate::config::Config::default());
+        assert!(result.success);
+        let xml = result.unencrypted_text.unwrap()[0].to_string();
+        assert!(xml.starts_with("<samlp:AuthnRequest"));
+        assert!(xml.contains("https://sp.example.com"));
+    }
+
+    #[test]
+    fn saml_post_binding() {
+        let decoder = Decoder::<SamlDecoder>::new();
+        let body = "RelayState=xyz&SAMLResponse=PHNhbWxwOlJlc3BvbnNlIElEPSJyMSI%2Bb2s8L3NhbWxwOlJlc3BvbnNlPg%3D%3D";
+        let result = decoder.crack(body, &get_checker(), &crate::config::Config::default());
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "<samlp:Response ID=\"r1\">ok</samlp:Response>"
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! What a serialized object holds, shared by the PHP, Java and pickle decoders
+//!
+//! Those decoders never run what they decode: they walk the bytes and build a
+//! [`Value`], which is only ever printed. [`Value::dump`] prints it indented,
+//! one field or item per line, so a serialized object can be read at a glance.
+
+use crate::byte_text::{bytes_to_text, Candidate};
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use base64::{engine::general_purpose, Engine as _};
+use std::fmt::Write as _;
+
+use super::base64_decoder::Base64Decoder;
+use super::crack_results::CrackResult;
+use super::interface::{Crack, Decoder};
+
+/// How deeply serialized values may nest before a decoder gives up, so a
+/// crafted blob can't overflow the stack
+pub const MAX_DEPTH: usize = 64;
+
+/// How long a number, string or bytes may be to be shown again where a
+/// serialized object refers back to it. Longer ones, and every list, map,
+/// object and call, are shown as a placeholder instead, so references to
+/// values holding references can't double the output at every level.
+pub const MAX_REFERENCED_LEN: usize = 64;
+
+/// A value read from a serialized object
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// PHP's `null`, Java's `null` or Python's `None`
+    Null,
+    /// A boolean
+    Bool(bool),
+    /// An integer, kept as its digits as pickles can hold any size
+    Int(String),
+    /// A floating point number
+    Float(f64),
+    /// A string
+    Str(String),
+    /// Bytes which aren't a string
+    Bytes(Vec<u8>),
+    /// A list, array, tuple or set, with what kind it is, e.g. `int[]`
+    List(Option<String>, Vec<Value>),
+    /// A map from keys to values
+    Map(Vec<(Value, Value)>),
+    /// An object of a class, with its fields
+    Object(String, Vec<(String, Value)>),
+    /// A pickled call, e.g. `os.system("id")`: the one thing a pickle can
+    /// do when loaded which is worth knowing before loading it
+    Call(Box<Value>, Vec<Value>),
+    /// Anything shown as it is, e.g. a class or a back reference
+    Raw(String),
+}
+
+impl Value {
+    /// The value, indented two spaces per level
+    ///
+    /// ```
+    /// use ares::decoders::serialized_value::Value;
+    ///
+    /// let user = Value::Object(
+    ///     "User".to_string(),
+    ///     vec![
+    ///         ("name".to_string(), Value::Str("alice".to_string())),
+    ///         ("admin".to_string(), Value::Bool(false)),
+    ///     ],
+    /// );
+    /// assert_eq!(user.dump(), "User {\n  name: \"alice\"\n  admin: false\n}");
+    /// ```
+    pub fn dump(&self) -> String {
+        let mut dumped = String::new();
+        self.write(&mut dumped, 0);
+        dumped
+    }
+
+    /// Writes the value at `depth` levels of indentation
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth + 1);
+        let close = "  ".repeat(depth);
+        match self {
+            Value::Null => out.push_str("null"),
+            Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Value::Int(digits) => out.push_str(digits),
+            Value::Float(value) => {
+                let _ = write!(out, "{value:?}");
+            }
+            Value::Str(text) => {
+                let _ = write!(out, "{text:?}");
+            }
+            Value::Bytes(bytes) => {
+                let _ = write!(out, "b\"{}\"", bytes.escape_ascii());
+            }
+            Value::List(kind, items) => {
+                if let Some(kind) = kind {
+                    out.push_str(kind);
+                    out.push(' ');
+                }
+                if items.is_empty() {
+                    out.push_str("[]");
+                    return;
+                }
+                out.push_str("[\n");
+                for item in items {
+                    out.push_str(&indent);
+                    item.write(out, depth + 1);
+                    out.push('\n');
+                }
+                out.push_str(&close);
+                out.push(']');
+            }
+            Value::Map(entries) => {
+                if entries.is_empty() {
+                    out.push_str("{}");
+                    return;
+                }
+                out.push_str("{\n");
+                for (key, value) in entries {
+                    out.push_str(&indent);
+                    key.write(out, depth + 1);
+                    out.push_str(": ");
+                    value.write(out, depth + 1);
+                    out.push('\n');
+                }
+                out.push_str(&close);
+                out.push('}');
+            }
+            Value::Object(class, fields) => {
+                out.push_str(class);
+                if fields.is_empty() {
+                    out.push_str(" {}");
+                    return;
+                }
+                out.push_str(" {\n");
+                for (name, value) in fields {
+                    out.push_str(&indent);
+                    out.push_str(name);
+                    out.push_str(": ");
+                    value.write(out, depth + 1);
+                    out.push('\n');
+                }
+                out.push_str(&close);
+                out.push('}');
+            }
+            Value::Call(callable, args) => {
+                callable.write(out, depth);
+                out.push('(');
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    arg.write(out, depth);
+                }
+                out.push(')');
+            }
+            Value::Raw(text) => out.push_str(text),
+        }
+    }
+
+    /// Whether the value holds other values, rather than being one number
+    /// or string, which a few stray bytes could also be read as
+    pub fn is_structured(&self) -> bool {
+        matches!(
+            self,
+            Value::List(..) | Value::Map(_) | Value::Object(..) | Value::Call(..)
+        )
+    }
+
+    /// What to show where a serialized object refers back to this value:
+    /// the value again if it is short, or else `placeholder`
+    ///
+    /// ```
+    /// use ares::decoders::serialized_value::Value;
+    ///
+    /// let name = Value::Str("alice".to_string());
+    /// assert_eq!(name.referenced(|| "<reference>".to_string()), name);
+    /// let names = Value::List(None, vec![name]);
+    /// assert_eq!(
+    ///     names.referenced(|| "<reference>".to_string()),
+    ///     Value::Raw("<reference>".to_string())
+    /// );
+    /// ```
+    pub fn referenced(&self, placeholder: impl FnOnce() -> String) -> Value {
+        let short = match self {
+            Value::Null | Value::Bool(_) | Value::Float(_) => true,
+            Value::Int(text) | Value::Str(text) | Value::Raw(text) => {
+                text.len() <= MAX_REFERENCED_LEN
+            }
+            Value::Bytes(bytes) => bytes.len() <= MAX_REFERENCED_LEN,
+            Value::List(..) | Value::Map(_) | Value::Object(..) | Value::Call(..) => false,
+        };
+        if short {
+            self.clone()
+        } else {
+            Value::Raw(placeholder())
+        }
+    }
+}
+
+/// A string read from bytes: text if it is UTF-8, bytes if not
+pub fn string_or_bytes(bytes: &[u8]) -> Value {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Value::Str(text.to_string()),
+        Err(_) => Value::Bytes(bytes.to_vec()),
+    }
+}
+
+/// The bytes of `text` Base64 decoded, if it starts with `prefix` and is
+/// Base64, in lines or not
+fn base64_with_prefix(text: &str, prefix: &str) -> Option<Vec<u8>> {
+    let trimmed = text.trim();
+    if !trimmed.starts_with(prefix) {
+        return None;
+    }
+    let base64: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    [
+        general_purpose::STANDARD,
+        general_purpose::STANDARD_NO_PAD,
+        general_purpose::URL_SAFE,
+        general_purpose::URL_SAFE_NO_PAD,
+    ]
+    .iter()
+    .find_map(|engine| engine.decode(&base64).ok())
+}
+
+/// Cracks a binary serialization format with `parse`, given `text` as its
+/// raw bytes or, if it starts with `base64_prefix`, as Base64. Decoding the
+/// Base64 shows in the path as a step of its own.
+pub(crate) fn crack_binary<T>(
+    decoder: &Decoder<T>,
+    text: &str,
+    base64_prefix: &str,
+    parse: fn(&[u8]) -> Option<Value>,
+    checker: &CheckerTypes,
+    config: &Config,
+) -> CrackResult
+where
+    Decoder<T>: Crack,
+{
+    if let Some(bytes) = base64_with_prefix(text, base64_prefix) {
+        let mut results = crack_bytes(
+            decoder,
+            &bytes_to_text(&bytes),
+            &bytes,
+            parse,
+            checker,
+            config,
+        );
+        if results.success {
+            let mut base64_step = CrackResult::new(&Decoder::<Base64Decoder>::new(), text);
+            base64_step.unencrypted_text = Some(vec![Candidate::from_bytes(bytes.clone())]);
+            let mut decoder_step = results.clone();
+            decoder_step.encrypted_text = bytes_to_text(&bytes).into();
+            results.encrypted_text = text.into();
+            results.inner_steps = vec![base64_step, decoder_step];
+            return results;
+        }
+    }
+    crack_bytes(decoder, text, text.as_bytes(), parse, checker, config)
+}
+
+/// Cracks `bytes`, whose text view is `text`, with `parse`. What is parsed
+/// is the serialized object, so the result succeeds whatever the checker
+/// makes of the dump.
+pub(crate) fn crack_bytes<T>(
+    decoder: &Decoder<T>,
+    text: &str,
+    bytes: &[u8],
+    parse: fn(&[u8]) -> Option<Value>,
+    checker: &CheckerTypes,
+    config: &Config,
+) -> CrackResult
+where
+    Decoder<T>: Crack,
+{
+    let mut results = CrackResult::new(decoder, text);
+    let Some(value) = parse(bytes) else {
+        return results;
+    };
+    let dump = value.dump();
+    let mut checker_result = checker.check(&dump, config);
+    checker_result.is_identified = true;
+    results.unencrypted_text = Some(vec![dump.into()]);
+    results.update_checker(&checker_result);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_nests_and_calls() {
+        let value = Value::List(
+            None,
+            vec![
+                Value::Map(vec![(Value::Int("1".to_string()), Value::Null)]),
+                Value::Call(
+                    Box::new(Value::Raw("os.system".to_string())),
+                    vec![Value::Str("id".to_string())],
+                ),
+                Value::List(Some("int[]".to_string()), vec![]),
+            ],
+        );
+        assert_eq!(
+            value.dump(),
+            "[\n  {\n    1: null\n  }\n  os.system(\"id\")\n  int[] []\n]"
+        );
+    }
+}
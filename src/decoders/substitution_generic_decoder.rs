@@ -25,7 +25,7 @@ impl Crack for Decoder<SubstitutionGenericDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, _config: &Config) -> CrackResult {
         trace!("Trying SubstitutionGenericDecoder with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let unique_symbols: Vec<char> = text.chars().collect::<HashSet<_>>().into_iter().collect();
         let num_symbols = unique_symbols.len();
 
@@ -72,14 +72,14 @@ impl Crack for Decoder<SubstitutionGenericDecoder> {
             if let Some(texts) = decoder_result.unencrypted_text {
                 for text in texts {
                     trace!("Found potential decoded string: {}", text);
-                    decoded_strings.insert(text);
+                    decoded_strings.insert(text.content().clone());
                 }
             }
         }
 
         if !decoded_strings.is_empty() {
             results.success = true;
-            results.unencrypted_text = Some(decoded_strings.into_iter().collect());
+            results.unencrypted_text = Some(decoded_strings.into_iter().map(Into::into).collect());
         }
 
         results
@@ -93,6 +93,10 @@ impl Crack for Decoder<SubstitutionGenericDecoder> {
         self.name
     }
 
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -160,7 +164,7 @@ mod tests {
 
         // Check if any of the decoded strings contains "HELLO"
         if let Some(texts) = result.unencrypted_text {
-            let contains_hello = texts.iter().any(|s| s.contains("HELLO"));
+            let contains_hello = texts.iter().any(|s| s.text().contains("HELLO"));
             assert!(
                 contains_hello,
                 "Expected to find 'HELLO' in decoded texts: {:?}",
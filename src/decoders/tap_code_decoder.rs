@@ -39,14 +39,14 @@ impl Crack for Decoder<TapCodeDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Tap Code with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Try dot format (. .. ... .... .....)
         if let Some(decoded) = decode_tap_dots(text) {
             if check_string_success(&decoded, text) {
                 let checker_result = checker.check(&decoded, config);
                 if checker_result.is_identified {
-                    results.unencrypted_text = Some(vec![decoded]);
+                    results.unencrypted_text = Some(vec![decoded.into()]);
                     results.update_checker(&checker_result);
                     return results;
                 }
@@ -58,7 +58,7 @@ impl Crack for Decoder<TapCodeDecoder> {
             if check_string_success(&decoded, text) {
                 let checker_result = checker.check(&decoded, config);
                 if checker_result.is_identified {
-                    results.unencrypted_text = Some(vec![decoded]);
+                    results.unencrypted_text = Some(vec![decoded.into()]);
                     results.update_checker(&checker_result);
                     return results;
                 }
@@ -70,7 +70,7 @@ impl Crack for Decoder<TapCodeDecoder> {
             if check_string_success(&decoded, text) {
                 let checker_result = checker.check(&decoded, config);
                 if checker_result.is_identified {
-                    results.unencrypted_text = Some(vec![decoded]);
+                    results.unencrypted_text = Some(vec![decoded.into()]);
                     results.update_checker(&checker_result);
                     return results;
                 }
@@ -89,6 +89,10 @@ impl Crack for Decoder<TapCodeDecoder> {
         self.name
     }
 
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
     fn get_description(&self) -> &str {
         self.description
     }
@@ -105,7 +109,7 @@ fn decode_tap_dots(text: &str) -> Option<String> {
         .filter(|s| !s.is_empty())
         .collect();
 
-    if groups.len() < 2 || groups.len() % 2 != 0 {
+    if groups.len() < 2 || !groups.len().is_multiple_of(2) {
         return None;
     }
 
@@ -144,7 +148,7 @@ fn decode_tap_numeric(text: &str) -> Option<String> {
         .filter_map(|s| s.parse().ok())
         .collect();
 
-    if numbers.len() < 2 || numbers.len() % 2 != 0 {
+    if numbers.len() < 2 || !numbers.len().is_multiple_of(2) {
         return None;
     }
 
@@ -177,7 +181,7 @@ fn decode_tap_x(text: &str) -> Option<String> {
         .filter(|s| !s.is_empty())
         .collect();
 
-    if groups.len() < 2 || groups.len() % 2 != 0 {
+    if groups.len() < 2 || !groups.len().is_multiple_of(2) {
         return None;
     }
 
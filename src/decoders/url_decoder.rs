@@ -9,6 +9,7 @@ use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 
 use log::{debug, info, trace};
@@ -53,7 +54,7 @@ impl Crack for Decoder<URLDecoder> {
         let decoded_text: Option<String> = decode_url_no_error_handling(text);
 
         trace!("Decoded text for url: {:?}", decoded_text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_none() {
             debug!("Failed to decode url because URLDecoder::decode_url_no_error_handling returned None");
@@ -70,7 +71,7 @@ impl Crack for Decoder<URLDecoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -84,6 +85,10 @@ impl Crack for Decoder<URLDecoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -94,6 +99,13 @@ impl Crack for Decoder<URLDecoder> {
     }
 }
 
+impl Encode for Decoder<URLDecoder> {
+    /// Percent-encodes everything except unreserved characters
+    fn encode(&self, text: &str) -> Result<String, String> {
+        Ok(urlencoding::encode(text).into_owned())
+    }
+}
+
 /// helper function
 fn decode_url_no_error_handling(text: &str) -> Option<String> {
     // Runs the code to decode url
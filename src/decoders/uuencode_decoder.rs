@@ -4,6 +4,7 @@
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 use crate::decoders::interface::check_string_success;
+use crate::storage::artifacts;
 use crate::decoders::crack_results::CrackResult;
 use crate::decoders::interface::Crack;
 use crate::decoders::interface::Decoder;
@@ -28,7 +29,7 @@ impl Crack for Decoder<UUEncodeDecoder> {
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying UUEncode with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         // Manual implementation or check if crate supports "uudecode"
         // The crate `uuencode` 0.1.1 has `uudecode` function typically.
@@ -43,7 +44,7 @@ impl Crack for Decoder<UUEncodeDecoder> {
         if let Some(decoded) = decoded_text {
             if check_string_success(&decoded, text) {
                 let checker_result = checker.check(&decoded, config);
-                results.unencrypted_text = Some(vec![decoded]);
+                results.unencrypted_text = Some(vec![decoded.into()]);
                 results.update_checker(&checker_result);
             }
         }
@@ -138,6 +139,7 @@ fn decode_uuencode_no_error_handling(text: &str) -> Option<String> {
     }
 
     if decoded_bytes.is_empty() { return None; }
+    artifacts::inspect("UUEncode", &decoded_bytes);
     String::from_utf8(decoded_bytes).ok()
 }
 
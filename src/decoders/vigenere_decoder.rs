@@ -6,9 +6,15 @@
 
 use super::crack_results::CrackResult;
 use super::interface::{Crack, Decoder};
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
-use crate::cryptanalysis::{ATTACK_WORDLIST, fitness_score, is_likely_english};
+use crate::text_info::TextInfo;
+use crate::cryptanalysis::{
+    attack_keywords, best_fitness_score, dictionary_attack, is_likely_plaintext, kpa,
+    languages_or_english,
+};
+use crate::deadline;
 use gibberish_or_not::Sensitivity;
 use log::{debug, trace};
 use once_cell::sync::Lazy;
@@ -61,6 +67,17 @@ static ENGLISH_BIGRAMS: Lazy<Vec<Vec<i64>>> = Lazy::new(|| {
     bigrams_vec
 });
 
+/// How many times at most every letter of a key is tried again by
+/// [`refine_key`]. Each pass which changes nothing ends it early.
+const MAX_REFINE_PASSES: usize = 4;
+
+/// The fewest ciphertext letters each letter of a key must cover. With fewer,
+/// refining a long key can make nearly any text read like a language.
+const MIN_LETTERS_PER_KEY_LETTER: usize = 7;
+
+/// The longest key tried from the letter statistics
+const MAX_KEY_LENGTH: usize = 29;
+
 /// The Vigenère decoder struct
 pub struct VigenereDecoder;
 
@@ -77,13 +94,67 @@ impl Crack for Decoder<VigenereDecoder> {
     }
 
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
-        trace!("Attempting Vigenère decryption on text: {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        self.crack_with_info(text, &TextInfo::new(text), checker, config)
+    }
+
+    /// The letters of the candidate are only picked out once, for all decoders
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        self.crack_with_info(&candidate.text(), candidate.info(), checker, config)
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    /// Finds the key length, then refines each letter of the key
+    fn expected_cost(&self) -> f32 {
+        50.0
+    }
+
+    /// Gets the description for the current decoder
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    /// Gets the link for the current decoder
+    fn get_link(&self) -> &str {
+        self.link
+    }
+    /// Decodes with a known alphabetic keyword
+    fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("Vigenère key must be alphabetic, got '{}'", key));
+        }
+        Ok(decrypt(text, key))
+    }
+}
 
-        // Clean the input text (remove non-alphabetic characters)
-        let clean_text: String = text.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+impl Decoder<VigenereDecoder> {
+    /// Cracks `text`, given what has been worked out about it
+    fn crack_with_info(
+        &self,
+        text: &str,
+        info: &TextInfo,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        trace!("Attempting Vigenère decryption on text: {:?}", text);
+        let mut results = CrackResult::new(self, text);
 
-        if clean_text.is_empty() {
+        if info.letters.is_empty() {
             debug!("No valid characters found in input text");
             return results;
         }
@@ -96,28 +167,56 @@ impl Crack for Decoder<VigenereDecoder> {
         let mut best_plaintext = String::new();
         let mut best_key = String::new();
 
-        // PHASE 1: Traditional bigram-based key breaking (most reliable for long texts)
-        // This is the original algorithm that works well
-        trace!("Phase 1: Bigram-based key length search");
-        for key_length in 3..30 {
-            let key = break_vigenere(text, key_length);
-            let key_str = key.as_str().trim();
-            if key_str.is_empty() {
-                continue;
-            }
-            let decode_attempt = decrypt(text, key_str);
-            
-            // Track best result using cryptanalysis
-            let score = fitness_score(&decode_attempt);
-            if score > best_score {
-                best_score = score;
-                best_plaintext = decode_attempt.clone();
-                best_key = key.clone();
+        // PHASE 0: Keys which would put the crib in the plaintext
+        if let Some(crib) = &config.crib {
+            let keys = kpa::vigenere_keys(text, crib, 30);
+            trace!("Phase 0: Trying {} keys which fit the crib", keys.len());
+            for key in keys {
+                let decode_attempt = decrypt(text, &key);
+                checker_result = checker_with_sensitivity.check(&decode_attempt, config);
+                if checker_result.is_identified {
+                    debug!("Crib attack succeeded with key: {}", key);
+                    results.unencrypted_text = Some(vec![decode_attempt.into()]);
+                    results.update_checker(&checker_result);
+                    results.key = Some(key);
+                    return results;
+                }
             }
-            
+        }
+
+        // PHASE 1: Traditional bigram-based key breaking (most reliable for long texts),
+        // with each key's letters then refined by quadgram scores. Refined keys
+        // of the wrong length read nearly like text too, so the keys are
+        // checked best first rather than shortest first.
+        trace!("Phase 1: Bigram-based key length search");
+        let max_key_length =
+            (info.uppercase_letters.len() / MIN_LETTERS_PER_KEY_LETTER).min(MAX_KEY_LENGTH);
+        let mut attempts: Vec<(f64, String, String)> = (3..=max_key_length)
+            .filter_map(|key_length| {
+                let key = break_vigenere(text, key_length);
+                let key_str = key.as_str().trim();
+                if key_str.is_empty() {
+                    return None;
+                }
+                let key = refine_key(info.uppercase_letters.as_bytes(), key_str, config);
+                let decode_attempt = decrypt(text, &key);
+                let score = best_fitness_score(&decode_attempt, &config.languages);
+                Some((score, key, decode_attempt))
+            })
+            .collect();
+        attempts.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        // Track best result using cryptanalysis
+        if let Some((score, key, decode_attempt)) = attempts.first() {
+            best_score = *score;
+            best_plaintext = decode_attempt.clone();
+            best_key = key.clone();
+        }
+
+        for (_, key, decode_attempt) in attempts {
             checker_result = checker_with_sensitivity.check(&decode_attempt, config);
             if checker_result.is_identified {
-                results.unencrypted_text = Some(vec![decode_attempt]);
+                results.unencrypted_text = Some(vec![decode_attempt.into()]);
                 results.update_checker(&checker_result);
                 results.key = Some(key);
                 return results;
@@ -126,65 +225,53 @@ impl Crack for Decoder<VigenereDecoder> {
 
         // PHASE 2: Try dictionary attack with common keywords (for CTF-style puzzles)
         // Only if the bigram analysis didn't find anything
-        trace!("Phase 2: Dictionary attack with {} keywords", ATTACK_WORDLIST.len());
-        for keyword in ATTACK_WORDLIST.iter() {
-            if keyword.len() < 3 || keyword.len() > 15 {
-                continue;
-            }
-            
-            let decode_attempt = decrypt(text, keyword);
-            
-            // Quick check with cryptanalysis
-            let score = fitness_score(&decode_attempt);
-            if score > best_score {
-                best_score = score;
-                best_plaintext = decode_attempt.clone();
-                best_key = keyword.to_uppercase();
-            }
-            
+        let keywords = attack_keywords(&config.dictionary_words);
+        trace!("Phase 2: Dictionary attack with {} keywords", keywords.len());
+        let attack = dictionary_attack(
+            &keywords,
+            &config.languages,
+            config.deadline.as_ref(),
+            |keyword| {
+                if keyword.len() < 3 || keyword.len() > 15 {
+                    return None;
+                }
+                Some(decrypt(text, keyword))
+            },
             // Check if this is valid plaintext
-            checker_result = checker_with_sensitivity.check(&decode_attempt, config);
-            if checker_result.is_identified {
-                debug!("Dictionary attack succeeded with key: {}", keyword);
-                results.unencrypted_text = Some(vec![decode_attempt]);
-                results.update_checker(&checker_result);
-                results.key = Some(keyword.to_uppercase());
-                return results;
-            }
+            |decode_attempt| {
+                let checker_result = checker_with_sensitivity.check(decode_attempt, config);
+                checker_result.is_identified.then_some(checker_result)
+            },
+        );
+
+        if let Some((hit, checker_result)) = attack.hit {
+            debug!("Dictionary attack succeeded with key: {}", hit.keyword);
+            results.unencrypted_text = Some(vec![hit.plaintext.into()]);
+            results.update_checker(&checker_result);
+            results.key = Some(hit.keyword.to_uppercase());
+            return results;
+        }
+
+        // Quick check with cryptanalysis
+        if let Some(best) = attack.best.filter(|best| best.score > best_score) {
+            best_plaintext = best.plaintext;
+            best_key = best.keyword.to_uppercase();
         }
 
         // PHASE 3: If we have a good candidate from cryptanalysis, return it
-        if is_likely_english(&best_plaintext) && !best_key.is_empty() {
+        if is_likely_plaintext(&best_plaintext, &config.languages) && !best_key.is_empty() {
             debug!("Using best cryptanalysis result with key: {}", best_key);
             checker_result = checker_with_sensitivity.check(&best_plaintext, config);
-            results.unencrypted_text = Some(vec![best_plaintext]);
+            results.unencrypted_text = Some(vec![best_plaintext.into()]);
             results.update_checker(&checker_result);
             results.key = Some(best_key);
             return results;
         }
 
-        results.unencrypted_text = Some(vec![String::new()]);
+        results.unencrypted_text = Some(vec![String::new().into()]);
         results.update_checker(&checker_result);
         results
     }
-
-    fn get_tags(&self) -> &Vec<&str> {
-        &self.tags
-    }
-
-    fn get_name(&self) -> &str {
-        self.name
-    }
-
-    /// Gets the description for the current decoder
-    fn get_description(&self) -> &str {
-        self.description
-    }
-
-    /// Gets the link for the current decoder
-    fn get_link(&self) -> &str {
-        self.link
-    }
 }
 
 /// Ported from the PHP implementation shown in https://www.guballa.de/bits-and-bytes/implementierung-des-vigenere-solvers
@@ -246,6 +333,55 @@ fn break_vigenere(text: &str, key_length: usize) -> String {
     key.into_iter().collect()
 }
 
+/// Puts right the letters of a key guessed column by column. On short
+/// texts each column has only a few letters, so the guess is often a letter
+/// or two out. Each key letter in turn is set to whichever of A-Z gives the
+/// plaintext the best quadgram score, keeping the rest of the key, until a
+/// pass changes nothing. `letters` is the ciphertext's letters, uppercased.
+fn refine_key(letters: &[u8], key: &str, config: &Config) -> String {
+    let mut shifts: Vec<u8> = key.bytes().map(|c| c.wrapping_sub(b'A')).collect();
+    if shifts.iter().any(|&shift| shift >= 26) || letters.len() < 2 * shifts.len() {
+        return key.to_string();
+    }
+    let languages = languages_or_english(&config.languages);
+    let mut plaintext = vec![0; letters.len()];
+    let mut score = |shifts: &[u8]| {
+        for (i, (plain, &c)) in plaintext.iter_mut().zip(letters).enumerate() {
+            *plain = (c - b'A' + 26 - shifts[i % shifts.len()]) % 26 + b'A';
+        }
+        languages
+            .iter()
+            .map(|language| language.quadgrams().score_letters(&plaintext))
+            .fold(f64::MIN, f64::max)
+    };
+
+    let mut best_score = score(&shifts);
+    for _ in 0..MAX_REFINE_PASSES {
+        if deadline::expired(config.deadline.as_ref()) {
+            break;
+        }
+        let mut changed = false;
+        for position in 0..shifts.len() {
+            let current = shifts[position];
+            let mut best_shift = current;
+            for shift in (0..26).filter(|&shift| shift != current) {
+                shifts[position] = shift;
+                let new_score = score(&shifts);
+                if new_score > best_score {
+                    best_score = new_score;
+                    best_shift = shift;
+                }
+            }
+            shifts[position] = best_shift;
+            changed |= best_shift != current;
+        }
+        if !changed {
+            break;
+        }
+    }
+    shifts.iter().map(|shift| char::from(shift + b'A')).collect()
+}
+
 /// Decrypt text using the found key
 fn decrypt(text: &str, key: &str) -> String {
     // Normalize key to uppercase bytes
@@ -261,7 +397,7 @@ fn decrypt(text: &str, key: &str) -> String {
         if c.is_ascii_alphabetic() {
             let key_byte = key_bytes[key_idx % key_bytes.len()];
             // Only use valid uppercase letters as key characters
-            if key_byte >= b'A' && key_byte <= b'Z' {
+            if key_byte.is_ascii_uppercase() {
                 let shift = (key_byte - b'A') as i8;
                 let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
                 let pos = ((c as u8) - base) as i8;
@@ -411,6 +547,49 @@ mod tests {
         assert_eq!(result, "HELLO");
     }
 
+    #[test]
+    fn test_vigenere_short_key_refined() {
+        // The column by column guesses are FECRET and ORCHKRD
+        let vigenere_decoder = Decoder::<VigenereDecoder>::new();
+        for (ciphertext, key, plaintext) in [
+            (
+                "Eigk qx sx vyi hdh dimwyi cwxxj qkurbylv rrw tvkek mzi ffgneipkw",
+                "SECRET",
+                "Meet me at the old bridge after midnight and bring the documents",
+            ),
+            (
+                "Hyg arvdgltl ij eiikld lqrvt ahv wvztk orn higl bp wvv tpvvu pvpk",
+                "ORCHARD",
+                "The treasure is buried under the third oak tree by the river bend",
+            ),
+        ] {
+            let result =
+                vigenere_decoder.crack(ciphertext, &get_athena_checker(), &Config::default());
+            assert_eq!(result.key.as_deref(), Some(key));
+            assert_eq!(result.unencrypted_text.unwrap()[0], plaintext);
+        }
+    }
+
+    #[test]
+    fn test_vigenere_key_from_crib() {
+        let vigenere_decoder = Decoder::<VigenereDecoder>::new();
+        let config = crate::config::Config {
+            crib: Some("buried".to_string()),
+            ..Default::default()
+        };
+        let result = vigenere_decoder.crack(
+            "jgb phdxokqb ei arnyda qdcbn jgb kbc lwa soau av pxd oeldo",
+            &get_athena_checker(),
+            &config,
+        );
+
+        assert_eq!(
+            result.unencrypted_text.expect("No unencrypted text for Vigenere decoder")[0],
+            "the treasure is buried under the old oak tree by the river"
+        );
+        assert_eq!(result.key.expect("No key for Vigenere decoder"), "QZXW");
+    }
+
     #[test]
     fn test_empty_input() {
         let vigenere_decoder = Decoder::<VigenereDecoder>::new();
@@ -429,6 +608,19 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_vigenere_short_gibberish_is_not_cracked() {
+        // 37 letters: a key of 18 would have two letters to refine each key
+        // letter with, which can make nearly any text read like English
+        let vigenere_decoder = Decoder::<VigenereDecoder>::new();
+        let result = vigenere_decoder.crack(
+            "qxjvwzpkqlxmvbzrtyqwplkjhgfdsazxcvbnm",
+            &get_athena_checker(),
+            &crate::config::Config::default(),
+        );
+        assert!(!result.success, "cracked with key {:?}", result.key);
+    }
+
     #[test]
     fn test_vigenere_square_aa() {
         assert_eq!(VIGENERE_SQUARE[0][0], 'A');
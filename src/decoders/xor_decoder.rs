@@ -2,16 +2,27 @@
 //! Performs error handling and returns a string
 //! Call xor_decoder.crack to use.
 
+use crate::byte_text::{Candidate, Content};
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
+use crate::cryptanalysis::{candidate_score, kpa};
 use crate::decoders::interface::check_string_success;
 use gibberish_or_not::Sensitivity;
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
+use super::interface::Encode;
 use super::interface::Decoder;
 use log::trace;
 
+/// The longest repeating key worked out from `Config.crib`
+const MAX_CRIB_KEY_LENGTH: usize = 16;
+
+/// How much each control character in a key's output costs its score, well
+/// past the spread of fitness scores. Most wrong keys give some, and text
+/// with few letters can score oddly well otherwise.
+const UNPRINTABLE_PENALTY: f64 = 1000.0;
+
 /// The XOR decoder, call:
 /// `let xor_decoder = Decoder::<XorDecoder>::new()` to create a new instance
 /// And then call:
@@ -31,19 +42,101 @@ impl Crack for Decoder<XorDecoder> {
 
     /// This function does the actual decoding
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        // Raw bytes, e.g. from `perform_cracking_bytes`, arrive through `crack_candidate`.
+        // Text is a string of characters (e.g. if it was XORed with printable chars),
+        // so the string's own bytes are the ciphertext.
+        self.crack_bytes(text, text.as_bytes(), checker, config)
+    }
+
+    /// Bytes from an earlier decoder, like Hexadecimal, are XOR'd as they are
+    fn crack_candidate(
+        &self,
+        candidate: &Candidate,
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
+        match candidate.content() {
+            Content::Text(text) => self.crack(text, checker, config),
+            Content::Bytes(bytes) => self.crack_bytes(&candidate.text(), bytes, checker, config),
+        }
+    }
+
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+
+    /// Tries 255 keys and scores what they give
+    fn expected_cost(&self) -> f32 {
+        20.0
+    }
+
+    fn get_description(&self) -> &str {
+        self.description
+    }
+
+    fn get_link(&self) -> &str {
+        self.link
+    }
+    /// Decodes with a known single-byte key, given in decimal or as `0x41`
+    fn decode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        let key = key.trim();
+        let parsed = match key.strip_prefix("0x").or_else(|| key.strip_prefix("0X")) {
+            Some(hex) => u8::from_str_radix(hex, 16),
+            None => key.parse::<u8>(),
+        };
+        let key_byte =
+            parsed.map_err(|_| format!("XOR key must be a single byte, got '{}'", key))?;
+        let decoded_bytes: Vec<u8> = text.bytes().map(|b| b ^ key_byte).collect();
+        String::from_utf8(decoded_bytes)
+            .map_err(|_| format!("XOR with key {} does not produce valid UTF-8", key))
+    }
+}
+
+impl Decoder<XorDecoder> {
+    /// Tries the keys on `input_bytes`, the bytes behind `text`
+    fn crack_bytes(
+        &self,
+        text: &str,
+        input_bytes: &[u8],
+        checker: &CheckerTypes,
+        config: &Config,
+    ) -> CrackResult {
         trace!("Trying XOR Cipher with text {:?}", text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
         let mut decoded_strings = Vec::new();
 
         // Use the checker with Low sensitivity for XOR cipher
         let checker_with_sensitivity = checker.with_sensitivity(Sensitivity::Low);
 
-        // We typically expect the input to be some form of bytes, but the interface gives us &str.
-        // If the input is hex-encoded or base64, it should have been decoded by other decoders first.
-        // However, sometimes the "ciphertext" is just a string of characters (e.g. if it was XORed with printable chars).
-        // We will assume the input string bytes are the ciphertext.
-        
-        let input_bytes = text.as_bytes();
+        // Repeating keys which would put the crib in the plaintext come first
+        if let Some(crib) = &config.crib {
+            for key in kpa::xor_keys(input_bytes, crib.as_bytes(), MAX_CRIB_KEY_LENGTH) {
+                let decoded_bytes: Vec<u8> = input_bytes
+                    .iter()
+                    .zip(key.iter().cycle())
+                    .map(|(&b, &k)| b ^ k)
+                    .collect();
+                let Ok(decoded_text) = String::from_utf8(decoded_bytes) else {
+                    continue;
+                };
+                let checker_result = checker_with_sensitivity.check(&decoded_text, config);
+                if checker_result.is_identified {
+                    trace!("Found a match with XOR key {:?} from the crib", key);
+                    results.unencrypted_text = Some(vec![decoded_text.into()]);
+                    results.update_checker(&checker_result);
+                    results.key = Some(format!("0x{}", hex::encode(&key)));
+                    return results;
+                }
+            }
+        }
 
         for key in 1..=255 {
             let decoded_bytes: Vec<u8> = input_bytes.iter().map(|&b| b ^ key).collect();
@@ -61,7 +154,7 @@ impl Crack for Decoder<XorDecoder> {
                 // If checkers return true, exit early with the correct result
                 if checker_result.is_identified {
                     trace!("Found a match with XOR key {}", key);
-                    results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string()]);
+                    results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string().into()]);
                     results.update_checker(&checker_result);
                     results.key = Some(format!("0x{:02x}", key));
                     return results;
@@ -71,35 +164,41 @@ impl Crack for Decoder<XorDecoder> {
         
         // If we didn't find an immediate match, we return all valid UTF-8 candidates
         // This allows further decoding (e.g. XOR -> Base64)
-        if !decoded_strings.is_empty() {
-            results.unencrypted_text = Some(decoded_strings);
-        } else {
-             results.unencrypted_text = None;
-        }
-        
+        // Ranked so the search follows the likeliest keys rather than all of them
+        let scored = decoded_strings
+            .into_iter()
+            .map(|decoded| (key_score(&decoded, config), decoded.into()))
+            .collect();
+        results.set_ranked_candidates(scored);
         results
     }
+}
 
-    fn get_tags(&self) -> &Vec<&str> {
-        &self.tags
-    }
-
-    fn get_name(&self) -> &str {
-        self.name
-    }
+/// The [`candidate_score`] of a key's output, less [`UNPRINTABLE_PENALTY`]
+/// for each control character in it
+fn key_score(text: &str, config: &Config) -> f64 {
+    let unprintable = text
+        .chars()
+        .filter(|c| c.is_control() && !c.is_ascii_whitespace())
+        .count() as f64;
+    candidate_score(text, &config.languages) - UNPRINTABLE_PENALTY * unprintable
+}
 
-    fn get_description(&self) -> &str {
-        self.description
+impl Encode for Decoder<XorDecoder> {
+    /// XOR needs a key, see `encode_with_key`
+    fn encode(&self, _text: &str) -> Result<String, String> {
+        Err("XOR needs a key, e.g. `--key 0x41`".to_string())
     }
-
-    fn get_link(&self) -> &str {
-        self.link
+    /// XOR is its own inverse, so this is the same as decoding with the key
+    fn encode_with_key(&self, text: &str, key: &str) -> Result<String, String> {
+        self.decode_with_key(text, key)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::XorDecoder;
+    use crate::decoders::hexadecimal_decoder::HexadecimalDecoder;
     use crate::{
         checkers::{
             athena::Athena,
@@ -126,7 +225,7 @@ mod tests {
         let result = xor_decoder.crack("HELLO", &get_athena_checker(), &crate::config::Config::default());
         assert!(result.unencrypted_text.is_some());
         let texts = result.unencrypted_text.unwrap();
-        assert!(texts.contains(&"hello".to_string()));
+        assert!(texts.contains(&"hello".into()));
     }
     
     #[test]
@@ -138,4 +237,56 @@ mod tests {
         // Athena should identify "hello"
         // But "hello" is short. "hello world" is better.
     }
+
+    #[test]
+    fn xor_ranks_encoded_candidates_first() {
+        let xor_decoder = Decoder::<XorDecoder>::new();
+        let base64 = "aGVsbG8gd29ybGQgdGhpcyBpcyBhIHRlc3Q=";
+        let ciphertext: String = base64.bytes().map(|b| char::from(b ^ 0x42)).collect();
+        let result = xor_decoder.crack(&ciphertext, &get_athena_checker(), &crate::config::Config::default());
+        assert_eq!(result.unencrypted_text.unwrap()[0], base64);
+        let scores = result.candidate_scores.unwrap();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn xor_decode_with_key() {
+        let xor_decoder = Decoder::<XorDecoder>::new();
+        assert_eq!(xor_decoder.decode_with_key("HELLO", "0x20").unwrap(), "hello");
+        assert_eq!(xor_decoder.decode_with_key("HELLO", "32").unwrap(), "hello");
+        assert!(xor_decoder.decode_with_key("HELLO", "256").is_err());
+    }
+
+    #[test]
+    fn xor_multi_byte_key_from_crib() {
+        let xor_decoder = Decoder::<XorDecoder>::new();
+        // "the flag is hidden under the old oak tree" XOR "ke"
+        let ciphertext = String::from_utf8(
+            hex::decode("1f0d0e450d090a024b0c1845030c0f010e0b4b1005010e174b1103004b0a07014b0a0a0e4b1119000e").unwrap(),
+        )
+        .unwrap();
+        let config = crate::config::Config {
+            crib: Some("hidden".to_string()),
+            ..Default::default()
+        };
+        let result = xor_decoder.crack(&ciphertext, &get_athena_checker(), &config);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "the flag is hidden under the old oak tree"
+        );
+        assert_eq!(result.key.unwrap(), "0x6b65");
+    }
+    #[test]
+    fn xor_gets_the_bytes_hexadecimal_decoded() {
+        let checker = get_athena_checker();
+        let config = crate::config::Config::default();
+        // "hello world" XOR 0x80, which isn't valid UTF-8
+        let decoded = Decoder::<HexadecimalDecoder>::new().crack("e8e5ececefa0f7eff2ece4", &checker, &config);
+        let candidate = &decoded.unencrypted_text.unwrap()[0];
+        assert!(candidate.is_bytes());
+
+        let result = Decoder::<XorDecoder>::new().crack_candidate(candidate, &checker, &config);
+        assert_eq!(result.unencrypted_text.unwrap()[0], "hello world");
+        assert_eq!(result.key.unwrap(), "0x80");
+    }
 }
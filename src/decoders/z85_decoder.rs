@@ -5,6 +5,7 @@
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
 use crate::decoders::interface::check_string_success;
+use crate::storage::artifacts;
 use z85;
 
 use super::crack_results::CrackResult;
@@ -51,7 +52,7 @@ impl Crack for Decoder<Z85Decoder> {
     fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
         trace!("Trying Z85 with text {:?}", text);
         let decoded_text = decode_z85_no_error_handling(text);
-        let mut results = CrackResult::new(self, text.to_string());
+        let mut results = CrackResult::new(self, text);
 
         if decoded_text.is_none() {
             debug!("Failed to decode z85 because Z85Decoder::decode_z85_no_error_handling returned None");
@@ -68,7 +69,7 @@ impl Crack for Decoder<Z85Decoder> {
         }
 
         let checker_result = checker.check(&decoded_text, config);
-        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_text = Some(vec![decoded_text.into()]);
 
         results.update_checker(&checker_result);
 
@@ -82,6 +83,10 @@ impl Crack for Decoder<Z85Decoder> {
     fn get_name(&self) -> &str {
         self.name
     }
+    /// Gets the popularity for the current decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
     /// Gets the description for the current decoder
     fn get_description(&self) -> &str {
         self.description
@@ -98,7 +103,10 @@ fn decode_z85_no_error_handling(text: &str) -> Option<String> {
     // Doesn't perform error handling, call from_z85
     z85::decode(text.as_bytes())
         .ok()
-        .map(|inner| String::from_utf8(inner).ok())?
+        .map(|inner| {
+            artifacts::inspect("Z85", &inner);
+            String::from_utf8(inner).ok()
+        })?
 }
 
 #[cfg(test)]
@@ -0,0 +1,65 @@
+//! Why a crack did not return a plaintext
+//!
+//! [`crate::perform_cracking`] returns None for every failure, so callers can't
+//! tell a search which ran out of time from input which could never be cracked.
+//! [`crate::try_perform_cracking`] returns an [`AresError`] instead.
+
+use std::fmt;
+
+/// The reasons a crack can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AresError {
+    /// The search ran for `config.timeout` seconds without finding the plaintext
+    Timeout,
+    /// The search tried everything it could without finding the plaintext
+    NoDecodingFound,
+    /// The input can't be cracked, e.g. it is empty or isn't valid for `config.input_format`
+    InvalidInput(String),
+    /// The cache database could not be used
+    DatabaseError(String),
+    /// The search was stopped by its caller before it finished
+    Cancelled,
+    /// The input failed to crack before with the same settings, after searching
+    /// for this many seconds. Set `config.retry_failed` to search again.
+    PreviouslyFailed(u32),
+}
+
+impl fmt::Display for AresError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AresError::Timeout => write!(f, "The search timed out before finding the plaintext"),
+            AresError::NoDecodingFound => write!(f, "No decoding of the input was found"),
+            AresError::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+            AresError::DatabaseError(reason) => write!(f, "Database error: {}", reason),
+            AresError::Cancelled => write!(f, "The search was cancelled"),
+            AresError::PreviouslyFailed(seconds) => write!(
+                f,
+                "This input previously failed to crack after {} seconds, use --retry-failed to search again",
+                seconds
+            ),
+        }
+    }
+}
+
+impl AresError {
+    /// A short name for the kind of failure, as kept in the run history
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AresError::Timeout => "timeout",
+            AresError::NoDecodingFound => "no_decoding_found",
+            AresError::InvalidInput(_) => "invalid_input",
+            AresError::DatabaseError(_) => "database_error",
+            AresError::Cancelled => "cancelled",
+            AresError::PreviouslyFailed(_) => "previously_failed",
+        }
+    }
+}
+
+impl std::error::Error for AresError {}
+
+#[cfg(feature = "cache")]
+impl From<rusqlite::Error> for AresError {
+    fn from(e: rusqlite::Error) -> Self {
+        AresError::DatabaseError(e.to_string())
+    }
+}
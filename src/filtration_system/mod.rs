@@ -1,16 +1,26 @@
 //! Proposal: https://broadleaf-angora-7db.notion.site/Filtration-System-7143b36a42f1466faea3077bfc7e859e
 //! Given a filter object, return an array of decoders/crackers which have been filtered
 
-use std::sync::mpsc::channel;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+use crate::byte_text::Candidate;
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
+use crate::cryptanalysis::identify::identify_in;
+use crate::deadline;
 use crate::decoders::crack_results::CrackResult;
-use crate::decoders::interface::Crack;
-use crate::decoders::DECODER_MAP;
+use crate::decoders::interface::{Alphabet, Crack};
+use crate::decoders::{all_decoders, find_decoder};
+use crate::streaming::StreamEncoding;
+use crate::text_info::TextInfo;
+use crate::searchers::stats;
 use crate::DecoderResult;
 
-use log::trace;
+use crate::timer::Instant;
+use crossbeam::channel::bounded;
+use tracing::{debug, trace, trace_span};
 use rayon::prelude::*;
 
 /// The struct which contains all of the decoders
@@ -32,48 +42,136 @@ impl Decoders {
     /// Which allows us to have multiple different structs in the same vector
     /// But each struct shares the same `.crack()` method, so it's fine.
     ///
+    /// Decoders which can't read the text, see [`can_read`], are skipped. The
+    /// text is only looked over once for all of them.
+    ///
+    /// Once a decoder succeeds, decoders which haven't started yet are skipped,
+    /// unless `config.top_results` wants every plaintext. So are all of them
+    /// once `config.deadline` has passed. The successes of decoders which had
+    /// already started are kept, and all come back in the decoders' order,
+    /// so which is first doesn't depend on which thread finished first.
+    ///
     /// # Panics
     /// Panics if the channel sender fails to send a result, which should not happen in normal operation.
-    pub fn run(&self, text: &str, checker: &CheckerTypes, config: &Config) -> MyResults {
+    pub fn run(&self, text: &Candidate, checker: &CheckerTypes, config: &Config) -> MyResults {
         trace!("Running .crack() on all decoders");
-        let (sender, receiver) = channel();
+        // Each decoder sends at most one result, so with room for one per
+        // decoder a send never blocks. Blocking would tie up a rayon thread
+        // which the searchers may need to run the other decoders.
+        let (sender, receiver) = bounded(self.components.len());
+        let found = AtomicBool::new(false);
         self.components
             .par_iter()
             .for_each_with(sender, |s, i| {
-                let results = i.crack(text, checker, config);
+                if found.load(Ordering::Relaxed) || deadline::expired(config.deadline.as_ref()) {
+                    return;
+                }
+                if too_big_to_decode(*i, text, config) || !can_read(*i, text.info()) {
+                    return;
+                }
+                stats::decoder_ran(config);
+                let results = crack_in_span(*i, text, checker, config);
                 if results.success {
-                    log::debug!(
+                    debug!(
                         "DEBUG: filtration_system - Decoder {} succeeded",
                         results.decoder
                     );
-                    s.send(results.clone()).expect("expected no send error!");
+                    if !config.top_results {
+                        found.store(true, Ordering::Relaxed);
+                    }
+                    s.send(results).expect("expected no send error!");
                 }
             });
 
         let mut all_results: Vec<CrackResult> = Vec::new();
         let mut successful_results: Vec<CrackResult> = Vec::new();
 
+        // All results are only returned when none succeeded,
+        // so each result only needs to go in one of the two
         while let Ok(result) = receiver.recv() {
             if result.success {
-                successful_results.push(result.clone());
+                successful_results.push(result);
+            } else {
+                all_results.push(result)
             }
-            all_results.push(result)
         }
 
         if !successful_results.is_empty() {
-             log::debug!(
+            successful_results.sort_by_key(|result| {
+                self.components
+                    .iter()
+                    .position(|decoder| decoder.get_name() == result.decoder)
+            });
+            debug!(
                 "DEBUG: filtration_system - Received {} successful results, returning Break",
                 successful_results.len()
             );
             return MyResults::Break(successful_results);
         }
 
-        log::debug!(
+        debug!(
             "DEBUG: filtration_system - No successful results, returning Continue with {} results",
             all_results.len()
         );
         MyResults::Continue(all_results)
     }
+
+    /// Remove every decoder which does not match the filter
+    pub fn restrict(&mut self, filter: &DecoderFilter) {
+        self.components.retain(|decoder| filter.matches(*decoder));
+    }
+
+    /// Remove every decoder which can't read `text`, see [`can_read`]
+    pub fn retain_readable(&mut self, text: &Candidate) {
+        let info = text.info();
+        self.components.retain(|decoder| can_read(*decoder, info));
+    }
+
+    /// Order the decoders by how likely they are for what they cost, their
+    /// [`Crack::get_popularity`] over their [`Crack::expected_cost`], so
+    /// cheap common decoders like Base64 run before key searches like
+    /// Vigenère. The searchers then call [`Decoders::order_by_identification`],
+    /// so this only orders the decoders for what the text doesn't look like.
+    pub fn order_by_value(&mut self) {
+        self.components.sort_by(|a, b| value(*b).total_cmp(&value(*a)));
+    }
+
+    /// Move the decoders for the ciphers and encodings `text` looks most like
+    /// to the front, keeping the order of the rest. See [`identify_in`].
+    pub fn order_by_identification(&mut self, text: &str, config: &Config) {
+        let guesses = identify_in(text, &config.languages);
+        self.components.sort_by_cached_key(|decoder| {
+            guesses
+                .iter()
+                .position(|guess| guess.family.covers(*decoder))
+                .unwrap_or(guesses.len())
+        });
+    }
+}
+
+/// How worth running a decoder is, see [`Decoders::order_by_value`]
+fn value(decoder: &(dyn Crack + Sync + Send)) -> f32 {
+    decoder.get_popularity() / decoder.expected_cost().max(f32::EPSILON)
+}
+
+/// Whether `text` is over `config.stream_threshold` and `decoder` can't
+/// stream, so would hold several copies of it in memory
+fn too_big_to_decode(decoder: &(dyn Crack + Sync + Send), text: &Candidate, config: &Config) -> bool {
+    text.len() > config.stream_threshold && StreamEncoding::of_decoder(decoder.get_name()).is_none()
+}
+
+/// Whether `decoder` can read a text, going by its [`Crack::alphabet`],
+/// [`Crack::needs_even_length`] and [`Crack::min_length`]. Those are checked
+/// against the [`TextInfo`] the candidate keeps, so working out which
+/// decoders apply to a node is a few bit operations each.
+pub fn can_read(decoder: &(dyn Crack + Sync + Send), info: &TextInfo) -> bool {
+    let length = match decoder.alphabet() {
+        Alphabet::Any => info.chars,
+        Alphabet::Letters => info.letter_count(),
+        Alphabet::Only(alphabet) if info.charset.is_within(alphabet) => info.chars,
+        Alphabet::Only(_) => return false,
+    };
+    length >= decoder.min_length() && (!decoder.needs_even_length() || length % 2 == 0)
 }
 
 /// [`Enum`] for our custom results.
@@ -103,6 +201,11 @@ pub struct DecoderFilter {
     include_tags: Vec<String>,
     /// Tags to exclude from the filter - decoders must not have any of these tags
     exclude_tags: Vec<String>,
+    /// Decoder names to include, matched case-insensitively.
+    /// A decoder passes the include check if it has an included tag or an included name
+    include_names: Vec<String>,
+    /// Decoder names to exclude, matched case-insensitively
+    exclude_names: Vec<String>,
 }
 
 impl Default for DecoderFilter {
@@ -117,9 +220,28 @@ impl DecoderFilter {
         DecoderFilter {
             include_tags: Vec::new(),
             exclude_tags: Vec::new(),
+            include_names: Vec::new(),
+            exclude_names: Vec::new(),
         }
     }
 
+    /// Build a filter from the `--only` and `--exclude` lists in the config.
+    /// Each entry may be either a decoder name or a tag.
+    /// Returns None if neither list is set, so callers can skip filtering entirely.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if config.decoders_only.is_empty() && config.decoders_exclude.is_empty() {
+            return None;
+        }
+        let mut filter = DecoderFilter::new();
+        for term in &config.decoders_only {
+            filter = filter.include_tag(term).include_name(term);
+        }
+        for term in &config.decoders_exclude {
+            filter = filter.exclude_tag(term).exclude_name(term);
+        }
+        Some(filter)
+    }
+
     /// Add a tag to include
     pub fn include_tag(mut self, tag: &str) -> Self {
         self.include_tags.push(tag.to_string());
@@ -132,22 +254,48 @@ impl DecoderFilter {
         self
     }
 
+    /// Add a decoder name to include
+    pub fn include_name(mut self, name: &str) -> Self {
+        self.include_names.push(name.to_string());
+        self
+    }
+
+    /// Add a decoder name to exclude
+    pub fn exclude_name(mut self, name: &str) -> Self {
+        self.exclude_names.push(name.to_string());
+        self
+    }
+
     /// Check if a decoder matches the filter
     pub fn matches(&self, decoder: &(dyn Crack + Sync + Send)) -> bool {
         let tags = decoder.get_tags();
+        let name = decoder.get_name();
 
-        // If include_tags is not empty, at least one tag must match
-        if !self.include_tags.is_empty() {
+        // If include_tags or include_names is not empty, at least one tag or the name must match
+        if !self.include_tags.is_empty() || !self.include_names.is_empty() {
             let has_included_tag = self
                 .include_tags
                 .iter()
                 .any(|include_tag| tags.iter().any(|tag| *tag == include_tag));
+            let has_included_name = self
+                .include_names
+                .iter()
+                .any(|include_name| include_name.eq_ignore_ascii_case(name));
 
-            if !has_included_tag {
+            if !has_included_tag && !has_included_name {
                 return false;
             }
         }
 
+        // Excluded names always win over included tags
+        if self
+            .exclude_names
+            .iter()
+            .any(|exclude_name| exclude_name.eq_ignore_ascii_case(name))
+        {
+            return false;
+        }
+
         // If exclude_tags is not empty, no tag must match
         if !self.exclude_tags.is_empty() {
             let has_excluded_tag = self
@@ -162,6 +310,75 @@ impl DecoderFilter {
 
         true
     }
+
+    /// Check if every decoder used along a path matches the filter.
+    /// Steps whose decoder is not registered (such as the default decoder) are allowed.
+    pub fn allows_path(&self, path: &[CrackResult]) -> bool {
+        path.iter().all(|step| {
+            find_decoder(step.decoder).is_none_or(|decoder| self.matches(decoder))
+        })
+    }
+}
+
+/// Drops the candidates an earlier result already has, so text several
+/// decoders agree on, like ROT13 and ROT18 of text without digits, is only
+/// searched once from a node. The searchers call this on every decoder's
+/// result before making nodes of them. Results left with no candidates are dropped,
+/// and ranked candidates keep their scores.
+pub fn dedup_candidates(results: &mut Vec<CrackResult>) {
+    let mut seen = HashSet::new();
+    results.retain_mut(|result| {
+        let Some(candidates) = result.unencrypted_text.take() else {
+            return true;
+        };
+        let mut scores = result.candidate_scores.take().map(Vec::into_iter);
+        let (kept, kept_scores): (Vec<Candidate>, Vec<Option<f64>>) = candidates
+            .into_iter()
+            .map(|candidate| (candidate, scores.as_mut().and_then(Iterator::next)))
+            .filter(|(candidate, _)| seen.insert(candidate.content().clone()))
+            .unzip();
+        if kept.is_empty() {
+            return false;
+        }
+        result.unencrypted_text = Some(kept);
+        result.candidate_scores = kept_scores.into_iter().collect();
+        true
+    });
+}
+
+thread_local! {
+    /// The decoder [`crack_in_span`] is running on this thread
+    static CURRENT_DECODER: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// The name of the decoder running on this thread, so checkers can tell which
+/// decoder produced the text they are checking. Empty outside of a decoder.
+pub fn current_decoder() -> String {
+    CURRENT_DECODER.with_borrow(Clone::clone)
+}
+
+/// Runs one decoder inside a `decoder` span, which records the decoder's name
+/// and how long it took in microseconds. The time is also kept in the result.
+pub fn crack_in_span(
+    decoder: &(dyn Crack + Sync + Send),
+    text: &Candidate,
+    checker: &CheckerTypes,
+    config: &Config,
+) -> CrackResult {
+    let span = trace_span!(
+        "decoder",
+        decoder = decoder.get_name(),
+        duration_us = tracing::field::Empty
+    )
+    .entered();
+    let previous = CURRENT_DECODER.replace(decoder.get_name().to_string());
+    let start = Instant::now();
+    let mut result = decoder.crack_candidate(text, checker, config);
+    let duration_us = start.elapsed().as_micros() as u64;
+    span.record("duration_us", duration_us);
+    CURRENT_DECODER.set(previous);
+    result.duration_us = Some(duration_us);
+    result
 }
 
 /// Get decoders with the "decoder" tag
@@ -203,18 +420,15 @@ pub fn get_all_decoders() -> Decoders {
     filter_and_get_decoders(&DecoderResult::default())
 }
 
-/// Uses the DECODER_MAP to get all decoders without re-instantiating them
+/// Uses the DECODER_MAP and registered decoders to get all decoders without re-instantiating them,
+/// ordered by [`Decoders::order_by_value`]
 pub fn filter_and_get_decoders(_text_struct: &DecoderResult) -> Decoders {
-    trace!("Getting all decoders from DECODER_MAP");
-    
-    // Iterate over DECODER_MAP and collect references
-    let components = DECODER_MAP.values()
-        .map(|decoder_box| decoder_box.get())
-        .collect();
-
-    Decoders {
-        components,
-    }
+    trace!("Getting all decoders from DECODER_MAP and registered decoders");
+    let mut decoders = Decoders {
+        components: all_decoders(),
+    };
+    decoders.order_by_value();
+    decoders
 }
 
 /// Get a specific decoder by name
@@ -245,9 +459,14 @@ mod tests {
     };
 
     use super::{
-        filter_and_get_decoders, filter_decoders_by_tags, get_decoder_by_name,
-        get_decoder_tagged_decoders, get_non_decoder_tagged_decoders, DecoderFilter,
+        crack_in_span, current_decoder, dedup_candidates, filter_and_get_decoders,
+        filter_decoders_by_tags,
+        get_all_decoders, get_decoder_by_name, get_decoder_tagged_decoders,
+        get_non_decoder_tagged_decoders, DecoderFilter, Decoders, MyResults,
     };
+    use crate::config::Config;
+    use crate::decoders::crack_results::CrackResult;
+    use crate::decoders::interface::Crack;
 
     #[test]
     fn it_works() {
@@ -255,13 +474,224 @@ mod tests {
         assert_eq!(2 + 2, 4);
     }
 
+    #[test]
+    fn crack_in_span_returns_the_decoders_result() {
+        let base64 = get_decoder_by_name("Base64").components[0];
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let result = crack_in_span(base64, &"aGVsbG8gdGhlcmU=".into(), &checker, &Config::default());
+        assert_eq!(result.decoder, "Base64");
+        assert_eq!(
+            result.unencrypted_text,
+            Some(vec!["hello there".into()])
+        );
+        assert!(result.duration_us.is_some());
+    }
+
+    #[test]
+    fn identified_decoders_are_ordered_first() {
+        let mut decoders = get_all_decoders();
+        let count = decoders.components.len();
+        decoders.order_by_identification("48656c6c6f20776f726c64", &Config::default());
+        assert_eq!(decoders.components.len(), count);
+        assert_eq!(decoders.components[0].get_name(), "Hexadecimal");
+    }
+
+    #[test]
+    #[cfg(feature = "classical")]
+    fn decoders_are_only_run_on_text_they_can_read() {
+        use super::can_read;
+        use crate::text_info::TextInfo;
+
+        let readable = |name: &str, text: &str| {
+            can_read(get_decoder_by_name(name).components[0], &TextInfo::new(text))
+        };
+        assert!(readable("Playfair", "bm od zb xd na be"));
+        assert!(!readable("Playfair", "bm od zb xd na b"));
+        assert!(!readable("Playfair", ""));
+        assert!(readable("Base32", "NBSWY3DPEB3W64TMMQ======"));
+        assert!(!readable("Base32", "nbswy3dp"));
+        assert!(!readable("Monoalphabetic", "too short to count letters"));
+        assert!(readable("caesar", ""));
+    }
+
+    #[test]
+    #[cfg(feature = "classical")]
+    fn cheap_likely_decoders_come_first() {
+        let decoders = get_all_decoders();
+        let position = |name: &str| {
+            decoders
+                .components
+                .iter()
+                .position(|decoder| decoder.get_name() == name)
+                .unwrap()
+        };
+        assert!(position("Base64") < position("caesar"));
+        assert!(position("Hexadecimal") < position("Vigenere"));
+        assert!(position("caesar") < position("HashCrack"));
+    }
+
+    /// Returns the decoder [`current_decoder`] says is running as its plaintext
+    struct SpyDecoder {
+        tags: Vec<&'static str>,
+    }
+
+    impl Crack for SpyDecoder {
+        fn new() -> Self {
+            SpyDecoder { tags: vec![] }
+        }
+        fn crack(&self, text: &str, _checker: &CheckerTypes, _config: &Config) -> CrackResult {
+            let mut result = CrackResult::from_decoder_info("Spy", "", "", text.to_string());
+            result.unencrypted_text = Some(vec![current_decoder().into()]);
+            result
+        }
+        fn get_tags(&self) -> &Vec<&str> {
+            &self.tags
+        }
+        fn get_name(&self) -> &str {
+            "Spy"
+        }
+        fn get_description(&self) -> &str {
+            ""
+        }
+        fn get_link(&self) -> &str {
+            ""
+        }
+    }
+
+    #[test]
+    fn current_decoder_is_only_set_while_a_decoder_runs() {
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        assert_eq!(current_decoder(), "");
+        let result = crack_in_span(&SpyDecoder::new(), &"text".into(), &checker, &Config::default());
+        assert_eq!(result.unencrypted_text, Some(vec!["Spy".into()]));
+        assert_eq!(current_decoder(), "");
+    }
+
+    /// Always succeeds, so every run of it finds a plaintext
+    struct FoundDecoder {
+        tags: Vec<&'static str>,
+    }
+
+    impl Crack for FoundDecoder {
+        fn new() -> Self {
+            FoundDecoder { tags: vec![] }
+        }
+        fn crack(&self, text: &str, _checker: &CheckerTypes, _config: &Config) -> CrackResult {
+            let mut result = CrackResult::from_decoder_info("Found", "", "", text);
+            result.unencrypted_text = Some(vec![text.into()]);
+            result.success = true;
+            result
+        }
+        fn get_tags(&self) -> &Vec<&str> {
+            &self.tags
+        }
+        fn get_name(&self) -> &str {
+            "Found"
+        }
+        fn get_description(&self) -> &str {
+            ""
+        }
+        fn get_link(&self) -> &str {
+            ""
+        }
+    }
+
+    #[test]
+    fn run_stops_after_the_first_success() {
+        let found: &'static FoundDecoder = Box::leak(Box::new(FoundDecoder::new()));
+        let decoders = Decoders {
+            components: vec![found; 8],
+        };
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let successes = |config: &Config| match pool.install(|| decoders.run(&"text".into(), &checker, config)) {
+            MyResults::Break(results) => results.len(),
+            MyResults::Continue(_) => 0,
+        };
+        assert_eq!(successes(&Config::default()), 1);
+        let config = Config {
+            top_results: true,
+            ..Default::default()
+        };
+        assert_eq!(successes(&config), 8);
+    }
+
+    /// Succeeds like [`FoundDecoder`], only slowly
+    struct SlowDecoder {
+        tags: Vec<&'static str>,
+    }
+
+    impl Crack for SlowDecoder {
+        fn new() -> Self {
+            SlowDecoder { tags: vec![] }
+        }
+        fn crack(&self, text: &str, _checker: &CheckerTypes, _config: &Config) -> CrackResult {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            let mut result = CrackResult::from_decoder_info("Slow", "", "", text);
+            result.unencrypted_text = Some(vec![text.into()]);
+            result.success = true;
+            result
+        }
+        fn get_tags(&self) -> &Vec<&str> {
+            &self.tags
+        }
+        fn get_name(&self) -> &str {
+            "Slow"
+        }
+        fn get_description(&self) -> &str {
+            ""
+        }
+        fn get_link(&self) -> &str {
+            ""
+        }
+    }
+
+    #[test]
+    fn successes_come_back_in_the_decoders_order() {
+        let slow: &'static SlowDecoder = Box::leak(Box::new(SlowDecoder::new()));
+        let found: &'static FoundDecoder = Box::leak(Box::new(FoundDecoder::new()));
+        let decoders = Decoders {
+            components: vec![slow, found],
+        };
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        // With a thread each, Found succeeds while Slow is still running
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let results = match pool.install(|| decoders.run(&"text".into(), &checker, &Config::default())) {
+            MyResults::Break(results) => results,
+            MyResults::Continue(_) => panic!("both decoders succeed"),
+        };
+        assert_eq!(results[0].decoder, "Slow");
+    }
+
+    #[test]
+    fn candidates_are_only_kept_from_the_first_decoder_giving_them() {
+        let result = |decoder, texts: &[&str]| {
+            let mut result = CrackResult::from_decoder_info(decoder, "", "", "uryyb");
+            result.unencrypted_text = Some(texts.iter().map(|&text| text.into()).collect());
+            result
+        };
+        let mut ranked = result("XOR", &[]);
+        ranked.set_ranked_candidates(vec![(2.0, "hello".into()), (1.0, "other".into())]);
+        let mut results = vec![
+            result("ROT13", &["hello"]),
+            result("ROT18", &["hello"]),
+            ranked,
+            CrackResult::from_decoder_info("Base64", "", "", "uryyb"),
+        ];
+        dedup_candidates(&mut results);
+        let decoders: Vec<&str> = results.iter().map(|result| result.decoder).collect();
+        assert_eq!(decoders, ["ROT13", "XOR", "Base64"]);
+        assert_eq!(results[1].unencrypted_text, Some(vec!["other".into()]));
+        assert_eq!(results[1].candidate_scores, Some(vec![1.0]));
+    }
+
     #[test]
     fn decoders_can_call_dot_run() {
         let decoders = filter_and_get_decoders(&DecoderResult::default());
         let athena_checker = Checker::<Athena>::new();
         let checker = CheckerTypes::CheckAthena(athena_checker);
         let config = crate::config::Config::default();
-        decoders.run("TXIgUm9ib3QgaXMgZ3JlYXQ=", &checker, &config);
+        decoders.run(&"TXIgUm9ib3QgaXMgZ3JlYXQ=".into(), &checker, &config);
         assert_eq!(true, true);
     }
 
@@ -409,4 +839,37 @@ mod tests {
             "Should return empty decoders for nonexistent name"
         );
     }
+
+    #[test]
+    #[cfg(feature = "classical")]
+    fn test_decoder_filter_names_and_tags() {
+        let filter = DecoderFilter::new().include_name("base64").include_tag("rot");
+        let mut decoders = get_all_decoders();
+        decoders.restrict(&filter);
+
+        let names: Vec<&str> = decoders.components.iter().map(|d| d.get_name()).collect();
+        assert!(names.contains(&"Base64"), "names should match case-insensitively");
+        assert!(names.contains(&"ROT5"));
+        assert!(!names.contains(&"Base32"));
+    }
+
+    #[test]
+    fn test_decoder_filter_from_config() {
+        assert!(DecoderFilter::from_config(&Config::default()).is_none());
+
+        let config = Config {
+            decoders_only: vec!["base".to_string()],
+            decoders_exclude: vec!["Base64".to_string()],
+            ..Config::default()
+        };
+        let filter = DecoderFilter::from_config(&config).unwrap();
+        let mut decoders = get_all_decoders();
+        decoders.restrict(&filter);
+
+        assert!(!decoders.components.is_empty());
+        for decoder in decoders.components.iter() {
+            assert_ne!(decoder.get_name(), "Base64");
+            assert!(decoder.get_tags().contains(&"base"));
+        }
+    }
 }
@@ -0,0 +1,122 @@
+//! Preprocessing for `--input-format`
+//!
+//! Binary data can't be passed on the command line as is, so users can hand it
+//! to ares as hex or Base64 instead. The input is decoded before the search
+//! starts and the decoding is recorded as the first step of the path, so the
+//! result still explains how the plaintext was reached.
+
+use crate::byte_text::Candidate;
+use crate::decoders::base64_decoder::Base64Decoder;
+use crate::decoders::crack_results::CrackResult;
+use crate::decoders::hexadecimal_decoder::HexadecimalDecoder;
+use crate::decoders::interface::{Crack, Decoder};
+use base64::{engine::general_purpose, Engine as _};
+
+/// How the input given to ares is encoded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum InputFormat {
+    /// Use the input as is
+    #[default]
+    Raw,
+    /// Decode the input from hex first, e.g. `48656c6c6f`
+    Hex,
+    /// Decode the input from Base64 first, e.g. `SGVsbG8=`
+    Base64,
+}
+
+/// The input after preprocessing
+#[derive(Debug, Clone)]
+pub struct PreprocessedInput {
+    /// The text or bytes to search on
+    pub text: Candidate,
+    /// The decoding that was applied, to put at the start of the path
+    pub step: Option<CrackResult>,
+}
+
+/// Builds the path step recording that `input` was decoded to `text` by `decoder`
+fn preprocessing_step<T>(decoder: &Decoder<T>, input: &str, text: &Candidate) -> CrackResult {
+    let mut step = CrackResult::new(decoder, input.to_string());
+    step.unencrypted_text = Some(vec![text.clone()]);
+    step
+}
+
+/// The input without whitespace, as wrapped hex dumps and Base64 often contain line breaks
+fn compact(input: &Candidate) -> String {
+    input.text().chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Decodes the input according to its format.
+///
+/// # Errors
+/// Returns an error if the input is not valid in the given format.
+pub fn preprocess_input(
+    input: &Candidate,
+    format: InputFormat,
+) -> Result<PreprocessedInput, String> {
+    let (text, step) = match format {
+        InputFormat::Raw => {
+            return Ok(PreprocessedInput {
+                text: input.clone(),
+                step: None,
+            })
+        }
+        InputFormat::Hex => {
+            let compact = compact(input);
+            let hex_digits = compact
+                .strip_prefix("0x")
+                .or_else(|| compact.strip_prefix("0X"))
+                .unwrap_or(&compact);
+            let bytes =
+                hex::decode(hex_digits).map_err(|e| format!("Input is not valid hex: {}", e))?;
+            let text = Candidate::from_bytes(bytes);
+            let step = preprocessing_step(&Decoder::<HexadecimalDecoder>::new(), &input.text(), &text);
+            (text, step)
+        }
+        InputFormat::Base64 => {
+            let compact = compact(input);
+            let bytes = general_purpose::STANDARD_NO_PAD
+                .decode(compact.trim_end_matches('='))
+                .map_err(|e| format!("Input is not valid Base64: {}", e))?;
+            let text = Candidate::from_bytes(bytes);
+            let step = preprocessing_step(&Decoder::<Base64Decoder>::new(), &input.text(), &text);
+            (text, step)
+        }
+    };
+    Ok(PreprocessedInput {
+        text,
+        step: Some(step),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_input_is_unchanged() {
+        let preprocessed = preprocess_input(&" hello ".into(), InputFormat::Raw).unwrap();
+        assert_eq!(preprocessed.text, " hello ");
+        assert!(preprocessed.step.is_none());
+    }
+
+    #[test]
+    fn hex_input_is_decoded_and_recorded() {
+        let preprocessed = preprocess_input(&"0x6869 2074 6865 7265".into(), InputFormat::Hex).unwrap();
+        assert_eq!(preprocessed.text, "hi there");
+        let step = preprocessed.step.unwrap();
+        assert_eq!(step.decoder, "Hexadecimal");
+        assert_eq!(&*step.encrypted_text, "0x6869 2074 6865 7265");
+        assert_eq!(step.unencrypted_text, Some(vec!["hi there".into()]));
+        assert!(preprocess_input(&"zz".into(), InputFormat::Hex).is_err());
+    }
+
+    #[test]
+    fn binary_base64_input_keeps_every_byte() {
+        let preprocessed = preprocess_input(&"/wBB\n".into(), InputFormat::Base64).unwrap();
+        assert_eq!(preprocessed.text.bytes(), [0xff, 0x00, b'A']);
+        assert!(preprocessed.text.is_bytes());
+        assert_eq!(preprocessed.step.unwrap().decoder, "Base64");
+        assert!(preprocess_input(&"not base64!".into(), InputFormat::Base64).is_err());
+    }
+}
@@ -7,15 +7,38 @@
     clippy::missing_panics_doc
 )]
 
-/// The main crate for the Ares project.
-/// This provides the library API interface for ares.
-mod api_library_input_struct;
 /// Checkers is a module that contains the functions that check if the input is plaintext
 pub mod checkers;
 /// CLI Arg Parsing library
+#[cfg(feature = "cli")]
 pub mod cli;
-/// CLI Input Parser parses the input from the CLI and returns a struct.
-mod cli_input_parser;
+/// Finds encoded tokens in larger documents and cracks each, for `ares extract`
+pub mod extract;
+/// Decodes hex or Base64 input before the search, for `--input-format`
+pub mod input_format;
+/// Removes invisible characters and ASCII lookalikes from the input before the search
+pub mod normalization;
+/// Restores spacing and case in plaintexts before they are shown, for `--readable`
+pub mod postprocess;
+/// Peels chunked encodings off very large inputs before the search, for `--stream`
+pub mod streaming;
+/// Known decodings applied in order, for `ares run-recipe`
+pub mod recipe;
+/// When a search has to stop, shared by everything it runs
+pub mod deadline;
+/// Scores how likely a result is to be the plaintext
+pub mod confidence;
+/// Re-encoding a result's path to check no step lost anything
+pub mod verify;
+/// Assertions for testing decoders the same way, for contributors and plugin authors
+pub mod testing;
+/// Errors explaining why a crack failed
+pub mod error;
+/// Everything a crack needs in one value, for [`perform_cracking_request`]
+pub mod request;
+pub use byte_text::Candidate;
+pub use error::AresError;
+pub use request::CrackRequest;
 /// CLI Pretty Printing module for consistent output formatting
 ///
 /// # Examples
@@ -42,26 +65,69 @@ pub mod filtration_system;
 /// The searcher is the thing which searches for the plaintext
 /// It is the core of the program.
 mod searchers;
+/// Progress events emitted by the searcher, for front-ends like the TUI
+pub use searchers::events;
+/// Counts of what a search did, for reasoning about why a run was slow
+pub use searchers::stats;
+/// Add custom checkers to Athena at runtime
+pub use checkers::custom::{register_checker, register_checker_fn};
+/// Replace the stdin prompt of the human checker
+pub use checkers::human_checker::{set_human_verifier, HumanVerifier};
+/// Add custom decoders to the search at runtime
+pub use decoders::register_decoder;
+/// Carries raw bytes through the text pipeline, one char per byte
+pub mod byte_text;
+/// What is worked out once about each candidate, for the decoders
+pub mod text_info;
+/// Cracking from async code without blocking the runtime
+#[cfg(feature = "tokio")]
+mod async_cracking;
+#[cfg(feature = "tokio")]
+pub use async_cracking::perform_cracking_async;
+/// A reusable cracking session for services which crack many texts
+pub mod session;
+pub use session::AresSession;
+/// JavaScript bindings for running Ares in the browser
+#[cfg(feature = "wasm")]
+pub mod wasm;
+/// Decoder and checker plugins loaded from shared libraries in `~/.ares/plugins`
+#[cfg(feature = "plugins")]
+pub mod plugins;
 /// Storage module for dictionaries and invisible characters
 pub mod storage;
-/// Cryptanalysis module for advanced cipher breaking
+/// Statistics and key searches for classical ciphers, for Ares and other tools
 pub mod cryptanalysis;
 /// Timer for internal use
 mod timer;
+/// Terminal interface showing a live view of the search
+#[cfg(feature = "cli")]
+pub mod tui;
 
 use checkers::{
     athena::Athena,
     checker_result::CheckResult,
     checker_type::{Check, Checker},
+    human_checker::HumanAnswers,
     wait_athena::WaitAthena,
+    CheckerTypes,
 };
 use log::debug;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use std::time::SystemTime;
+#[cfg(feature = "cache")]
+use std::sync::Mutex;
+
+use crate::timer::SystemTime;
+use crate::storage::wait_athena_storage::PlaintextResults;
+
+#[cfg(feature = "cache")]
+use crate::config::CacheMode;
 
 use crate::{
+    byte_text::Content,
     config::Config,
     decoders::interface::Decoder,
+    input_format::InputFormat,
 };
 
 use self::decoders::crack_results::CrackResult;
@@ -104,40 +170,348 @@ use self::decoders::crack_results::CrackResult;
 /// // If the program times out, or it cannot decode the text it will return None.
 /// assert!(result.is_none());
 /// ```
+/// When `config.input_format` is hex or Base64, the input is decoded first and
+/// that decoding is the first step of the returned path.
+///
+/// Use [`try_perform_cracking`] to find out why no plaintext was found.
 pub fn perform_cracking(text: &str, config: Config) -> Option<DecoderResult> {
+    try_perform_cracking(text, config).ok()
+}
+
+/// Like [`perform_cracking`], but says why no plaintext was found.
+///
+/// ```rust
+/// use ares::{try_perform_cracking, AresError};
+/// use ares::config::Config;
+/// # let _test_db = ares::TestDatabase::default();
+/// # ares::set_test_db_path();
+/// let config = Config { human_checker_on: false, ..Default::default() };
+/// let result = try_perform_cracking("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config.clone());
+/// assert_eq!(result.unwrap().text[0], "hello there general");
+///
+/// let result = try_perform_cracking("", config);
+/// assert!(matches!(result, Err(AresError::InvalidInput(_))));
+/// ```
+///
+/// # Errors
+/// Returns [`AresError::InvalidInput`] for empty input or input which doesn't match
+/// `config.input_format`, and [`AresError::Timeout`] or [`AresError::NoDecodingFound`]
+/// when the search doesn't find the plaintext. Failures are remembered, so searching
+/// the same input with the same settings again returns [`AresError::PreviouslyFailed`]
+/// at once unless `config.retry_failed` is set or the timeout is longer.
+pub fn try_perform_cracking(text: &str, config: Config) -> Result<DecoderResult, AresError> {
+    perform_cracking_request(CrackRequest::new(text).with_config(config))
+}
+
+/// Cracks what the request describes. The other `perform_cracking` functions
+/// and the CLI go through here, and servers or FFI bindings can read a
+/// [`CrackRequest`] from JSON and pass it straight in.
+///
+/// ```rust
+/// use ares::{perform_cracking_request, CrackRequest};
+/// # let _test_db = ares::TestDatabase::default();
+/// # ares::set_test_db_path();
+/// let json = r#"{"input": {"text": "aGVsbG8gdGhlcmUgZ2VuZXJhbA=="}}"#;
+/// let request: CrackRequest = serde_json::from_str(json).unwrap();
+/// let result = perform_cracking_request(request).unwrap();
+/// assert_eq!(result.text[0], "hello there general");
+/// ```
+///
+/// # Errors
+/// Returns [`AresError::InvalidInput`] if one of the request's wordlists can't
+/// be loaded, and the errors of [`try_perform_cracking`] otherwise.
+pub fn perform_cracking_request(request: CrackRequest) -> Result<DecoderResult, AresError> {
+    let (input, config) = request.resolve()?;
+    crack_input(input, config, CrackContext::default())
+}
+
+/// Like [`perform_cracking`], for input which may not be valid UTF-8, such as
+/// XOR'd or compressed data read from a file.
+/// Bytes which aren't UTF-8 are searched as bytes, see [`byte_text`].
+///
+/// ```rust
+/// use ares::perform_cracking_bytes;
+/// use ares::config::Config;
+/// # let _test_db = ares::TestDatabase::default();
+/// # ares::set_test_db_path();
+/// // XOR with 0xA5 leaves bytes which are not valid UTF-8
+/// let ciphertext: Vec<u8> = b"the quick brown fox jumps over the lazy dog".iter().map(|b| b ^ 0xa5).collect();
+/// assert!(std::str::from_utf8(&ciphertext).is_err());
+///
+/// let config = Config { human_checker_on: false, ..Default::default() };
+/// let result = perform_cracking_bytes(&ciphertext, config).unwrap();
+/// assert_eq!(result.text[0], "the quick brown fox jumps over the lazy dog");
+/// assert_eq!(result.path[0].key.as_deref(), Some("0xa5"));
+/// ```
+pub fn perform_cracking_bytes(bytes: &[u8], config: Config) -> Option<DecoderResult> {
+    perform_cracking_request(CrackRequest::from_bytes(bytes).with_config(config)).ok()
+}
+
+/// Like [`try_perform_cracking`], for input too big to hold in memory more
+/// than once, such as a 500 MB Base64 file. Base64, hex, quoted-printable and
+/// gzip layers are decoded in chunks first, until what is left is at most
+/// `config.stream_threshold` bytes. See [`streaming`].
+///
+/// ```rust
+/// use ares::try_perform_cracking_reader;
+/// use ares::config::Config;
+/// # let _test_db = ares::TestDatabase::default();
+/// # ares::set_test_db_path();
+/// let file = std::io::Cursor::new("68656c6c6f20776f726c64");
+/// let config = Config { human_checker_on: false, stream_threshold: 8, ..Default::default() };
+/// let result = try_perform_cracking_reader(file, config).unwrap();
+/// assert_eq!(result.text[0], "hello world");
+/// assert_eq!(result.path[0].decoder, "Hexadecimal");
+/// ```
+///
+/// # Errors
+/// Returns [`AresError::InvalidInput`] if the input can't be read or a layer
+/// isn't valid in the encoding it looked like, and the errors of
+/// [`try_perform_cracking`] otherwise.
+pub fn try_perform_cracking_reader(
+    reader: impl std::io::Read,
+    config: Config,
+) -> Result<DecoderResult, AresError> {
+    let peeled = streaming::peel(reader, config.stream_threshold)
+        .map_err(|e| AresError::InvalidInput(e.to_string()))?;
+    let mut steps = peeled.steps;
+    let mut result = crack_input(peeled.text, config, CrackContext::default())?;
+    // Peeling found the plaintext, so the search's placeholder step goes and
+    // the last peeled layer records the checker that recognised it
+    if let ([.., last], [only]) = (steps.as_mut_slice(), result.path.as_slice()) {
+        if only.decoder == Decoder::default().name {
+            last.success = true;
+            last.checker_name = only.checker_name;
+            last.checker_description = only.checker_description;
+            result.path.clear();
+        }
+    }
+    result.path.splice(0..0, steps);
+    result.confidence = confidence::score(&result);
+    Ok(result)
+}
+
+/// What a crack can borrow from whoever started it
+#[derive(Clone, Copy, Default)]
+struct CrackContext<'a> {
+    /// A connection kept open by an [`AresSession`]. Without one the database
+    /// is set up and a connection opened for this crack.
+    #[cfg(feature = "cache")]
+    db: Option<&'a Mutex<rusqlite::Connection>>,
+    /// Stops the search early when set, e.g. when an async caller gives up
+    cancel: Option<&'a AtomicBool>,
+}
+
+/// Cracks the input with [`crack_normalized`], keeping the run in the
+/// history when the cache may be written
+fn crack_input(
+    input: Candidate,
+    config: Config,
+    ctx: CrackContext,
+) -> Result<DecoderResult, AresError> {
+    #[cfg(feature = "cache")]
+    let run = start_run(&input.text(), &config);
+    let result = crack_normalized(input, config, ctx);
+    #[cfg(feature = "cache")]
+    record_run(run, &result, ctx);
+    result
+}
+
+/// Normalizes text input unless `config.normalize_input` is off, preprocesses
+/// it according to `config.input_format`, then cracks it
+fn crack_normalized(
+    input: Candidate,
+    config: Config,
+    ctx: CrackContext,
+) -> Result<DecoderResult, AresError> {
+    if input.is_empty() {
+        return Err(AresError::InvalidInput("the input is empty".to_string()));
+    }
+    let verify = config.verify;
+    let normalized = match input.content() {
+        Content::Text(text) if config.normalize_input => normalization::normalize_input(text),
+        _ => None,
+    };
+    let input = normalized.as_ref().map_or(input, |normalized| normalized.text.clone());
+    let mut result = if config.input_format == InputFormat::Raw {
+        crack_text(input, config, ctx)?
+    } else {
+        let preprocessed = input_format::preprocess_input(&input, config.input_format)
+            .map_err(AresError::InvalidInput)?;
+        let mut result = crack_text(preprocessed.text, config, ctx)?;
+        result.path.splice(0..0, preprocessed.step);
+        result
+    };
+    if let Some(normalized) = normalized {
+        result.path.splice(0..0, normalized.step);
+    }
+    if verify {
+        verify::verify_path(&mut result);
+    }
+    result.confidence = confidence::score(&result);
+    Ok(result)
+}
+
+/// Searches for the plaintext of input which needs no preprocessing
+fn crack_text(input: Candidate, config: Config, ctx: CrackContext) -> Result<DecoderResult, AresError> {
     let start_time = SystemTime::now();
     let mut config = config;
-    // If top_results...
-
+    config.human_answers.get_or_insert_with(HumanAnswers::default);
 
     // If top_results is enabled, ensure human_checker_on is disabled
     if config.top_results {
         config.human_checker_on = false;
-        // Clear any previous results when starting a new cracking session
-        storage::wait_athena_storage::clear_plaintext_results();
+        config.plaintext_results.get_or_insert_with(PlaintextResults::default);
     }
+    prepare_database(&config, ctx);
+    load_dictionary(&mut config, ctx);
     let config = Arc::new(config);
 
-    let text = text.to_string();
+    let text = input.text().into_owned();
+
+    if let Some(cached) = read_cached_result(&text, &config, ctx) {
+        return Ok(cached);
+    }
+    if let Some(seconds) = read_failed_attempt(&text, &config, ctx) {
+        return Err(AresError::PreviouslyFailed(seconds));
+    }
+
+    let initial_check_for_plaintext = check_if_input_text_is_plaintext(&text, &config);
+    if initial_check_for_plaintext.is_identified {
+        debug!(
+            "The input text provided to the program {} is the plaintext. Returning early.",
+            text
+        );
+        #[cfg(feature = "cli")]
+        cli_pretty_printing::return_early_because_input_text_is_plaintext(&config);
+
+        let mut crack_result = CrackResult::new(&Decoder::default(), text.to_string());
+        crack_result.checker_name = initial_check_for_plaintext.checker_name;
+
+        let output = DecoderResult {
+            text: vec![input],
+            path: vec![crack_result],
+            confidence: 0.0,
+        };
+
+        cache_result(&text, start_time, &output, &config, ctx);
+        return Ok(output);
+    }
 
-    /* Initializing database */
-    let db_result = storage::database::setup_database(&config);
+    // Build a new search tree
+    // This starts us with a node with no parents
+    // let search_tree = searchers::Tree::new(text.to_string());
+    log::debug!("Calling search_for_plaintext with text: {}", text);
+    // Perform the search algorithm
+    // It will either return a failure or success.
+    let result = searchers::search_for_plaintext(input, config.clone(), ctx.cancel);
+    log::debug!("Result from search_for_plaintext: {:?}", result.is_ok());
+    if let Ok(ref res) = result {
+        log::debug!("Result has {} decoders in path", res.path.len());
+    }
+
+    match &result {
+        Ok(output) => cache_result(&text, start_time, output, &config, ctx),
+        Err(AresError::Timeout | AresError::NoDecodingFound) => {
+            record_failed_attempt(&text, &config, ctx)
+        }
+        Err(_) => (),
+    }
+
+    result
+}
+
+/// Sets up the database if no session has, unless the cache is off
+#[cfg(feature = "cache")]
+fn prepare_database(config: &Config, ctx: CrackContext) {
+    if config.cache_mode == CacheMode::Off || ctx.db.is_some() {
+        return;
+    }
+    let db_result = storage::database::setup_database(config);
     match db_result {
         Ok(_) => (),
         Err(e) => {
             cli_pretty_printing::warning(&format!(
                 "DEBUG: lib.rs - SQLite database failed to initialize. Encountered error: {}",
                 e
-            ), &config);
+            ), config);
         }
     };
+}
+
+/// Without the cache feature there is no database
+#[cfg(not(feature = "cache"))]
+fn prepare_database(_config: &Config, _ctx: CrackContext) {}
+
+/// Adds the words and flag prefixes kept with `ares dict add` to the config,
+/// for the checkers and keyword attacks
+#[cfg(feature = "cache")]
+fn load_dictionary(config: &mut Config, ctx: CrackContext) {
+    if config.cache_mode == CacheMode::Off {
+        return;
+    }
+    let rows = match ctx.db {
+        Some(conn) => storage::database::read_dictionary_with(&session::lock(conn)),
+        None if config.cache_mode == CacheMode::ReadOnly => {
+            storage::database::open_read_only_connection()
+                .and_then(|conn| storage::database::read_dictionary_with(&conn))
+        }
+        None => storage::database::read_dictionary(),
+    };
+    match rows {
+        Ok(rows) => {
+            for row in rows {
+                if row.prefix {
+                    config.dictionary_prefixes.push(row.word);
+                } else {
+                    config.dictionary_words.push(row.word);
+                }
+            }
+        }
+        Err(e) => log::debug!("Could not read the dictionary: {}", e),
+    }
+}
+
+/// Without the cache feature there is no dictionary
+#[cfg(not(feature = "cache"))]
+fn load_dictionary(_config: &mut Config, _ctx: CrackContext) {}
+
+/// Looks the text up in the cache
+#[cfg(feature = "cache")]
+fn read_cached_result(text: &str, config: &Config, ctx: CrackContext) -> Option<DecoderResult> {
+    if config.cache_mode == CacheMode::Off {
+        return None;
+    }
+    // Stale rows are only removed and hits only recorded when the cache may be changed
+    let writable = config.cache_mode == CacheMode::ReadWrite;
 
     /*  Checks to see if the encoded text already exists in the cache
      *  returns cached result if so
      */
-    let cache_result = storage::database::read_cache(&text);
+    let text = text.to_string();
+    let cache_result = match ctx.db {
+        Some(conn) => storage::database::read_cache_with(&session::lock(conn), &text),
+        None if !writable => storage::database::open_read_only_connection()
+            .and_then(|conn| storage::database::read_cache_with(&conn, &text)),
+        None => storage::database::read_cache(&text),
+    };
     match cache_result {
         Ok(cache_row) => match cache_row {
+            Some(row) if !row.is_fresh(config.cache_ttl) && !writable => {
+                log::debug!("Cached result for {} is stale, ignoring it", text);
+            }
+            Some(row) if !row.is_fresh(config.cache_ttl) => {
+                log::debug!("Cached result for {} is stale, removing it", text);
+                let deleted = match ctx.db {
+                    Some(conn) => {
+                        storage::database::delete_cache_with(&mut session::lock(conn), &text)
+                    }
+                    None => storage::database::delete_cache(&text),
+                };
+                if let Err(e) = deleted {
+                    log::warn!("Could not remove stale cache entry: {}", e);
+                }
+            }
             Some(row) => {
                 log::debug!("Cache hit for text: {}", text);
                 let path_result: Result<Vec<CrackResult>, serde_json::Error> = row
@@ -154,15 +528,47 @@ pub fn perform_cracking(text: &str, config: Config) -> Option<DecoderResult> {
                         }
                     })
                     .collect();
-                if let Ok(path) = path_result {
-                    return Some(DecoderResult {
-                        text: vec![row.decoded_text],
-                        path,
-                    });
+                // A cached path may use decoders the user has ruled out with --only / --exclude
+                let allowed_by_filter = |path: &[CrackResult]| {
+                    filtration_system::DecoderFilter::from_config(config)
+                        .is_none_or(|filter| filter.allows_path(path))
+                };
+                // Large inputs aren't stored on the first step, see storage::database::is_large
+                let path_result = path_result.map(|mut path| {
+                    if let Some(first) = path.first_mut() {
+                        if first.encrypted_text.is_empty() {
+                            first.encrypted_text = text.as_str().into();
+                        }
+                    }
+                    path
+                });
+                match path_result {
+                    Ok(path) if !allowed_by_filter(&path) => {
+                        log::debug!("Cached path uses a filtered out decoder, ignoring cache");
+                    }
+                    Ok(path) => {
+                        searchers::stats::cache_hit(config);
+                        let touched = match ctx.db {
+                            _ if !writable => Ok(0),
+                            Some(conn) => {
+                                storage::database::touch_cache_with(&session::lock(conn), &text)
+                            }
+                            None => storage::database::touch_cache(&text),
+                        };
+                        if let Err(e) = touched {
+                            log::warn!("Could not record cache hit: {}", e);
+                        }
+                        return Some(DecoderResult {
+                            text: vec![row.decoded_text.into()],
+                            path,
+                            confidence: 0.0,
+                        });
+                    }
+                    Err(_) => (),
                 }
             }
             None => {
-                log::debug!("Did not find text \"{}\" in cache", text.clone());
+                log::debug!("Did not find text \"{}\" in cache", text);
             }
         },
         Err(e) => {
@@ -170,61 +576,251 @@ pub fn perform_cracking(text: &str, config: Config) -> Option<DecoderResult> {
         }
     }
 
-    let initial_check_for_plaintext = check_if_input_text_is_plaintext(&text, &config);
-    if initial_check_for_plaintext.is_identified {
-        debug!(
-            "The input text provided to the program {} is the plaintext. Returning early.",
-            text
-        );
-        cli_pretty_printing::return_early_because_input_text_is_plaintext(&config);
+    None
+}
 
-        let mut crack_result = CrackResult::new(&Decoder::default(), text.to_string());
-        crack_result.checker_name = initial_check_for_plaintext.checker_name;
+/// Without the cache feature nothing is cached
+#[cfg(not(feature = "cache"))]
+fn read_cached_result(_text: &str, _config: &Config, _ctx: CrackContext) -> Option<DecoderResult> {
+    None
+}
 
-        let output = DecoderResult {
-            text: vec![text.clone()],
-            path: vec![crack_result],
-        };
+/// Stores a successful result in the cache, logging any failure
+#[cfg(feature = "cache")]
+fn cache_result(
+    text: &str,
+    start_time: SystemTime,
+    output: &DecoderResult,
+    config: &Config,
+    ctx: CrackContext,
+) {
+    if config.cache_mode != CacheMode::ReadWrite {
+        return;
+    }
+    if let Err(e) = success_result_to_cache(text, start_time, output, config, ctx.db) {
+        log::warn!("Error inserting decoder result into cache table: {}", e);
+    }
+}
 
-        let cache_result = success_result_to_cache(&text, start_time, &output, &config);
-        match cache_result {
-            Ok(_) => (),
-            Err(e) => {
-                log::warn!("Error inserting decoder result into cache table: {}", e);
-            }
-        };
+/// Without the cache feature nothing is cached
+#[cfg(not(feature = "cache"))]
+fn cache_result(
+    _text: &str,
+    _start_time: SystemTime,
+    _output: &DecoderResult,
+    _config: &Config,
+    _ctx: CrackContext,
+) {
+}
 
-        return Some(output);
+/// Returns how long an earlier search of the text with the same settings ran
+/// before failing, unless searching again could do better
+#[cfg(feature = "cache")]
+fn read_failed_attempt(text: &str, config: &Config, ctx: CrackContext) -> Option<u32> {
+    if config.cache_mode == CacheMode::Off || config.retry_failed {
+        return None;
+    }
+    let failed = match ctx.db {
+        Some(conn) => {
+            storage::database::read_failed_attempt_with(&session::lock(conn), text, config)
+        }
+        None if config.cache_mode == CacheMode::ReadOnly => {
+            storage::database::open_read_only_connection()
+                .and_then(|conn| storage::database::read_failed_attempt_with(&conn, text, config))
+        }
+        None => storage::database::read_failed_attempt(text, config),
+    };
+    match failed {
+        Ok(Some(row)) if row.still_applies(config) => {
+            log::debug!("Input failed to crack before after {}s", row.timeout_secs);
+            Some(row.timeout_secs)
+        }
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("Error trying to read failed attempts: {}", e);
+            None
+        }
     }
+}
 
-    // Build a new search tree
-    // This starts us with a node with no parents
-    // let search_tree = searchers::Tree::new(text.to_string());
-    log::debug!("Calling search_for_plaintext with text: {}", text);
-    // Perform the search algorithm
-    // It will either return a failure or success.
-    let result = searchers::search_for_plaintext(text.clone(), config.clone());
-    log::debug!("Result from search_for_plaintext: {:?}", result.is_some());
-    if let Some(ref res) = result {
-        log::debug!("Result has {} decoders in path", res.path.len());
+/// Without the cache feature no failure is remembered
+#[cfg(not(feature = "cache"))]
+fn read_failed_attempt(_text: &str, _config: &Config, _ctx: CrackContext) -> Option<u32> {
+    None
+}
+
+/// Remembers that the search found nothing, so the next run can say so at once
+#[cfg(feature = "cache")]
+fn record_failed_attempt(text: &str, config: &Config, ctx: CrackContext) {
+    if config.cache_mode != CacheMode::ReadWrite {
+        return;
+    }
+    let row = storage::database::FailedAttemptRow::new(text, config);
+    let inserted = match ctx.db {
+        Some(conn) => storage::database::insert_failed_attempt_with(&session::lock(conn), &row),
+        None => storage::database::insert_failed_attempt(&row),
+    };
+    if let Err(e) = inserted {
+        log::warn!("Error recording failed attempt: {}", e);
     }
+}
 
-    if let Some(output) = &result {
-        let cache_result = success_result_to_cache(&text, start_time, output, &config);
-        match cache_result {
-            Ok(_) => (),
-            Err(e) => {
-                log::warn!("Error inserting decoder result into cache table: {}", e);
-            }
-        };
+/// Without the cache feature no failure is remembered
+#[cfg(not(feature = "cache"))]
+fn record_failed_attempt(_text: &str, _config: &Config, _ctx: CrackContext) {}
+
+/// A crack which [`record_run`] will keep in the runs table, with when it started
+#[cfg(feature = "cache")]
+type PendingRun = Option<(storage::database::RunRow, timer::Instant)>;
+
+/// Starts the history row for a crack of `text`, unless the cache is read only or off
+#[cfg(feature = "cache")]
+fn start_run(text: &str, config: &Config) -> PendingRun {
+    (config.cache_mode == CacheMode::ReadWrite)
+        .then(|| (storage::database::RunRow::start(text, config), timer::Instant::now()))
+}
+
+/// Keeps a finished crack in the runs table, for `ares history`
+#[cfg(feature = "cache")]
+fn record_run(run: PendingRun, result: &Result<DecoderResult, AresError>, ctx: CrackContext) {
+    let Some((mut row, start)) = run else {
+        return;
+    };
+    // Input rejected before the database was set up isn't kept
+    if ctx.db.is_none() && storage::database::DB_PATH.get().is_none() {
+        return;
+    }
+    row.finish(result, start.elapsed().as_millis().try_into().unwrap_or(i64::MAX));
+    let inserted = match ctx.db {
+        Some(conn) => storage::database::insert_run_with(&session::lock(conn), &row),
+        None => storage::database::insert_run(&row),
+    };
+    if let Err(e) = inserted {
+        log::warn!("Error recording run: {}", e);
     }
+}
 
-    result
+/// Runs a single decoder on the text without searching, like `ares decode --decoder base64`.
+///
+/// `decoder` is matched case-insensitively against decoder names first. If no decoder has
+/// that name, every decoder with that tag is run instead, so `base58` runs all Base58 variants.
+/// If a key is given it is passed to [`decoders::interface::Crack::decode_with_key`],
+/// otherwise each decoder returns all of its candidates.
+///
+/// ```rust
+/// use ares::perform_decoding;
+/// use ares::config::Config;
+///
+/// let results = perform_decoding("aGVsbG8gd29ybGQ=", "base64", None, &Config::default()).unwrap();
+/// assert_eq!(results[0].unencrypted_text.as_ref().unwrap()[0], "hello world");
+///
+/// let results = perform_decoding("uryyb", "caesar", Some("13"), &Config::default()).unwrap();
+/// assert_eq!(results[0].unencrypted_text.as_ref().unwrap()[0], "hello");
+/// ```
+///
+/// # Errors
+/// Returns a message if no decoder matches, or if none of the matching decoders accept the key.
+pub fn perform_decoding(
+    text: &str,
+    decoder: &str,
+    key: Option<&str>,
+    config: &Config,
+) -> Result<Vec<CrackResult>, String> {
+    use filtration_system::{get_all_decoders, DecoderFilter};
+
+    let mut decoders = get_all_decoders();
+    decoders.restrict(&DecoderFilter::new().include_name(decoder));
+    if decoders.components.is_empty() {
+        decoders = get_all_decoders();
+        decoders.restrict(&DecoderFilter::new().include_tag(decoder));
+    }
+    if decoders.components.is_empty() {
+        return Err(format!(
+            "No decoder named or tagged '{}'. Use --list-decoders to see what is available",
+            decoder
+        ));
+    }
+    // Keep the output stable when a tag matches several decoders
+    decoders.components.sort_by_key(|d| d.get_name());
+
+    let Some(key) = key else {
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        return Ok(decoders
+            .components
+            .iter()
+            .map(|d| crate::filtration_system::crack_in_span(*d, &text.into(), &checker, config))
+            .collect());
+    };
+
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for d in decoders.components {
+        match d.decode_with_key(text, key) {
+            Ok(plaintext) => results.push(CrackResult {
+                success: true,
+                encrypted_text: text.into(),
+                unencrypted_text: Some(vec![plaintext.into()]),
+                decoder: d.get_name(),
+                checker_name: "",
+                checker_description: "",
+                key: Some(key.to_string()),
+                description: d.get_description(),
+                link: d.get_link(),
+                intermediate_text: None,
+                inner_steps: Vec::new(),
+                candidate_scores: None,
+                verified: None,
+                duration_us: None,
+            }),
+            Err(e) => errors.push(e),
+        }
+    }
+    if results.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    Ok(results)
+}
+
+/// Encodes the text with a single reversible decoder, like `ares encode --encoder base64`.
+///
+/// `encoder` is matched case-insensitively against the names in [`decoders::ENCODER_MAP`].
+/// Keyed ciphers such as Caesar need a key, e.g. `caesar` with key `13` is ROT13.
+///
+/// ```rust
+/// use ares::perform_encoding;
+///
+/// assert_eq!(perform_encoding("hello world", "base64", None).unwrap(), "aGVsbG8gd29ybGQ=");
+/// assert_eq!(perform_encoding("hello", "caesar", Some("13")).unwrap(), "uryyb");
+/// ```
+///
+/// # Errors
+/// Returns a message if no encoder has that name, or if the encoder rejects the text or key.
+pub fn perform_encoding(text: &str, encoder: &str, key: Option<&str>) -> Result<String, String> {
+    let Some(encoder_box) = decoders::ENCODER_MAP
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(encoder))
+        .map(|(_, encoder_box)| encoder_box)
+    else {
+        let mut names: Vec<&str> = decoders::ENCODER_MAP.keys().copied().collect();
+        names.sort_by_key(|name| name.to_lowercase());
+        return Err(format!(
+            "No encoder named '{}'. Available encoders: {}",
+            encoder,
+            names.join(", ")
+        ));
+    };
+
+    match key {
+        Some(key) => encoder_box.get().encode_with_key(text, key),
+        None => encoder_box.get().encode(text),
+    }
 }
 
 /// Checks if the given input is plaintext or not
 /// Used at the start of the program to not waste CPU cycles
 fn check_if_input_text_is_plaintext(text: &str, config: &Config) -> CheckResult {
+    // Checked with the default config, which counts nothing
+    searchers::stats::checker_called(config);
     if config.top_results {
         let wait_athena_checker = Checker::<WaitAthena>::new();
         wait_athena_checker.check(text, &crate::config::Config::default())
@@ -235,11 +831,13 @@ fn check_if_input_text_is_plaintext(text: &str, config: &Config) -> CheckResult
 }
 
 /// Stores a successful DecoderResult into the cache table
+#[cfg(feature = "cache")]
 fn success_result_to_cache(
-    text: &String,
+    text: &str,
     start_time: SystemTime,
     result: &DecoderResult,
     config: &Config,
+    db: Option<&Mutex<rusqlite::Connection>>,
 ) -> Result<usize, rusqlite::Error> {
     let stop_time = SystemTime::now();
     let execution_time_ms: i64 = match stop_time.duration_since(start_time) {
@@ -255,48 +853,150 @@ fn success_result_to_cache(
         uuid: uuid::Uuid::new_v4(),
         encoded_text: String::from(text),
         decoded_text: match result.text.last() {
-            Some(d_text) => String::from(d_text),
+            Some(d_text) => d_text.to_string(),
             None => String::new(),
         },
         path: result.path.clone(),
         execution_time_ms,
     };
-    storage::database::insert_cache(&cache_entry)
+    match db {
+        Some(conn) => {
+            let mut conn = session::lock(conn);
+            let inserted = storage::database::insert_cache_with(&mut conn, &cache_entry)?;
+            if let Some(max_entries) = config.cache_max_entries {
+                storage::database::evict_cache_with(&conn, max_entries)?;
+            }
+            Ok(inserted)
+        }
+        None => {
+            let inserted = storage::database::insert_cache(&cache_entry)?;
+            if let Some(max_entries) = config.cache_max_entries {
+                storage::database::evict_cache(max_entries)?;
+            }
+            Ok(inserted)
+        }
+    }
 }
 
 /// DecoderResult is the result of decoders
 #[derive(Debug, Clone)]
 pub struct DecoderResult {
     /// The text we have from the decoder, as a vector
-    /// because the decoder might return more than 1 text (caesar)
-    pub text: Vec<String>,
+    /// because the decoder might return more than 1 text (caesar).
+    /// Bytes which aren't text are kept as they are, see [`Candidate`].
+    pub text: Vec<Candidate>,
     /// The list of decoders we have so far
     /// The CrackResult contains more than just each decoder, such as the keys used
     /// or the checkers used.
     pub path: Vec<CrackResult>,
+    /// How sure we are that the text is the plaintext, from 0 to 1.
+    /// Set once the crack finishes, see [`confidence::score`].
+    pub confidence: f32,
 }
 
 /// Creates a default DecoderResult with Default as the text / path
 impl Default for DecoderResult {
     fn default() -> Self {
         DecoderResult {
-            text: vec!["Default".to_string()],
+            text: vec!["Default".into()],
             path: vec![CrackResult::new(&Decoder::default(), "Default".to_string())],
+            confidence: 0.0,
         }
     }
 }
 
 /// Lets us create a new decoderResult with given text
 impl DecoderResult {
+    /// Describes how the plaintext was found, one numbered line per decoder
+    /// with the text going in and coming out, then the checker which
+    /// identified the plaintext.
+    ///
+    /// ```
+    /// use ares::perform_cracking;
+    /// use ares::config::{CacheMode, Config};
+    ///
+    /// let config = Config {
+    ///     human_checker_on: false,
+    ///     cache_mode: CacheMode::Off,
+    ///     ..Default::default()
+    /// };
+    /// let result = perform_cracking("aGVsbG8gd29ybGQ=", config).unwrap();
+    /// let explanation = result.explain();
+    /// assert!(explanation.starts_with("1. Base64: \"aGVsbG8gd29ybGQ=\" → \"hello world\""));
+    /// ```
+    pub fn explain(&self) -> String {
+        let mut lines = Vec::with_capacity(self.path.len() + 1);
+        for (i, step) in self.path.iter().enumerate() {
+            let output = self.step_output(i);
+            let key = match &step.key {
+                Some(key) => format!(" (key: {key})"),
+                None => String::new(),
+            };
+            // Only set with `Config.verify`
+            let verified = match step.verified {
+                Some(true) => " [verified]",
+                Some(false) => " [lossy]",
+                None => "",
+            };
+            lines.push(format!(
+                "{}. {}{}{}: \"{}\" → \"{}\"",
+                i + 1,
+                step.decoder,
+                key,
+                verified,
+                explain_snippet(&step.encrypted_text),
+                explain_snippet(&output)
+            ));
+        }
+        match self.path.last() {
+            Some(last) if !last.checker_name.is_empty() => lines.push(format!(
+                "Plaintext identified by the {} ({}) with {:.2} confidence",
+                last.checker_name, last.checker_description, self.confidence
+            )),
+            _ => lines.push("Plaintext identified".to_string()),
+        }
+        lines.join("\n")
+    }
+
+    /// What the step at `index` of the path produced. Without the kept
+    /// intermediate text, that is what the next step was given, and the
+    /// last step produced the plaintext.
+    pub fn step_output(&self, index: usize) -> std::borrow::Cow<'_, str> {
+        match (
+            self.path.get(index).and_then(|step| step.intermediate_text.as_deref()),
+            self.path.get(index + 1),
+        ) {
+            (Some(kept), _) => kept.into(),
+            (None, Some(next)) => (*next.encrypted_text).into(),
+            (None, None) => self.text.first().map(|text| text.text()).unwrap_or_default(),
+        }
+    }
+
     /// It's only used in tests so it thinks its dead code
     fn _new(text: &str) -> Self {
         DecoderResult {
-            text: vec![text.to_string()],
+            text: vec![text.into()],
             path: vec![CrackResult::new(&Decoder::default(), "Default".to_string())],
+            confidence: 0.0,
         }
     }
 }
 
+/// Shortens text for [`DecoderResult::explain`], keeping the start of long texts
+fn explain_snippet(text: &str) -> String {
+    /// How many chars of each text are shown
+    const SNIPPET_LENGTH: usize = 40;
+    let mut snippet: String = text
+        .chars()
+        .take(SNIPPET_LENGTH)
+        .flat_map(char::escape_debug)
+        .collect();
+    if text.chars().count() > SNIPPET_LENGTH {
+        snippet.push('…');
+    }
+    snippet
+}
+
 /// Gets the test directory path
 #[doc(hidden)]
 pub fn get_test_dir_path() -> std::path::PathBuf {
@@ -308,13 +1008,19 @@ pub fn get_test_dir_path() -> std::path::PathBuf {
 
 /// Sets the global database path
 #[doc(hidden)]
+#[cfg(feature = "cache")]
 pub fn set_test_db_path() {
     let mut path = get_test_dir_path();
     std::fs::create_dir_all(&path).expect("Could not create .ares directory");
     path.push("database.sqlite");
-    let _ = crate::storage::database::DB_PATH.set(Some(path));
+    let _ = crate::storage::database::set_database_path(path);
 }
 
+/// Without the cache feature there is no database to point at
+#[doc(hidden)]
+#[cfg(not(feature = "cache"))]
+pub fn set_test_db_path() {}
+
 /// Helper struct for testing database
 #[doc(hidden)]
 pub struct TestDatabase {
@@ -334,9 +1040,11 @@ impl Default for TestDatabase {
 #[doc(hidden)]
 impl Drop for TestDatabase {
     fn drop(&mut self) {
-        let mut db_file_path = self.path.as_path().to_path_buf();
-        db_file_path.push("database.sqlite");
-        let _ = std::fs::remove_file(&db_file_path);
+        #[cfg(feature = "cache")]
+        crate::storage::database::close_pooled_connections();
+        for file in ["database.sqlite", "database.sqlite-wal", "database.sqlite-shm"] {
+            let _ = std::fs::remove_file(self.path.join(file));
+        }
         let _ = std::fs::remove_dir(&self.path);
     }
 }
@@ -348,6 +1056,35 @@ mod tests {
     use crate::config::Config;
     use crate::{set_test_db_path, TestDatabase};
 
+    #[test]
+    fn explain_numbers_each_step() {
+        use crate::checkers::checker_result::CheckResult;
+        use crate::checkers::checker_type::{Check, Checker};
+        use crate::checkers::english::EnglishChecker;
+        use crate::decoders::crack_results::CrackResult;
+        use crate::DecoderResult;
+
+        let mut caesar =
+            CrackResult::from_decoder_info("caesar", "", "", "uryyb jbeyq".to_string());
+        caesar.key = Some("13".to_string());
+        let checker = Checker::<EnglishChecker>::new();
+        caesar.update_checker(&CheckResult::new(&checker));
+        let result = DecoderResult {
+            text: vec!["hello world".into()],
+            path: vec![
+                CrackResult::from_decoder_info("Base64", "", "", "dXJ5eWIgamJleXE=".to_string()),
+                caesar,
+            ],
+            confidence: 0.0,
+        };
+
+        let explanation = result.explain();
+        let lines: Vec<&str> = explanation.lines().collect();
+        assert_eq!(lines[0], "1. Base64: \"dXJ5eWIgamJleXE=\" → \"uryyb jbeyq\"");
+        assert_eq!(lines[1], "2. caesar (key: 13): \"uryyb jbeyq\" → \"hello world\"");
+        assert!(lines[2].starts_with("Plaintext identified by the English Checker"));
+    }
+
     #[test]
     fn test_perform_cracking_returns() {
         let _test_db = TestDatabase::default();
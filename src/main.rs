@@ -1,23 +1,184 @@
-use ares::cli::parse_cli_args;
+use ares::cli::{clipboard, exit_code, notify, parse_cli_args};
 use ares::cli_pretty_printing::program_exiting_successful_decoding;
-use ares::perform_cracking;
+use ares::cli_pretty_printing::progress::ProgressDisplay;
+use ares::cli_pretty_printing::report::write_report;
+use ares::cli_pretty_printing::{search_stats, statement};
+use ares::config::Config;
+use ares::byte_text::{Candidate, Content};
+use ares::{perform_cracking_request, try_perform_cracking_reader, AresError, CrackRequest};
+use ares::stats::StatsCounter;
+use ares::storage::artifacts;
+use ares::DecoderResult;
 use log::debug;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::Instant;
+
+#[cfg(feature = "mimalloc")]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+// mimalloc wins if both are enabled, e.g. with --all-features
+#[cfg(all(feature = "jemalloc", not(feature = "mimalloc")))]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
 fn main() {
+    // A panic anywhere, including in decoder threads, is an internal error
+    let default_panic_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_panic_hook(info);
+        std::process::exit(exit_code::INTERNAL_ERROR);
+    }));
+
     // Turn CLI arguments into a library object
-    let (text, config) = parse_cli_args();
-    let result = perform_cracking(&text, config.clone());
+    let (text, mut config) = parse_cli_args();
+    // Kept here as well, so the counts can be read whether or not the crack succeeds
+    let stats = StatsCounter::default();
+    config.stats = Some(stats.clone());
+    if config.output_path.is_some() || config.artifacts_dir.is_some() {
+        artifacts::enable_recording();
+    }
+    let start_time = Instant::now();
+    let result = if config.tui {
+        ares::tui::run(&text.text(), config.clone()).unwrap_or_else(|e| {
+            eprintln!("Could not start the terminal interface: {}", e);
+            std::process::exit(exit_code::INTERNAL_ERROR);
+        })
+    } else if let Some(format) = config.progress {
+        let progress = ProgressDisplay::start(config.timeout, format);
+        let result = crack(&text, &config);
+        progress.finish();
+        result
+    } else {
+        crack(&text, &config)
+    };
+    let duration = start_time.elapsed();
     
     debug!("Result from perform_cracking: {:?}", result.is_some());
-    
+
+    if let Some(report_path) = &config.report_path {
+        if let Err(e) = write_report(report_path, &text.text(), result.as_ref(), duration, &config) {
+            eprintln!("Failed to write report to '{}': {}", report_path, e);
+        }
+    }
+
+    if let Some(command) = &config.notify_command {
+        if let Err(e) = notify::notify(command, result.as_ref(), duration) {
+            eprintln!("{}", e);
+        }
+    }
+
+    if let (Some(output_path), Some(result)) = (&config.output_path, &result) {
+        write_output(output_path, result, &config);
+    }
+    if let (true, Some(result)) = (config.copy_to_clipboard, &result) {
+        copy_plaintext(result, &config);
+    }
+    if let Some(artifacts_dir) = &config.artifacts_dir {
+        save_artifacts(artifacts_dir, &config);
+    }
+
     match result {
         Some(result) => {
             debug!("Got successful result with {} decoders in path", result.path.len());
-            program_exiting_successful_decoding(result, &config);
+            if config.quiet {
+                println!("{}", result.text[0]);
+            } else {
+                program_exiting_successful_decoding(result, &config);
+            }
+            search_stats(&stats.snapshot(), &config);
+            std::process::exit(exit_code::CRACKED);
         }
         None => {
             debug!("Got None result, calling failed_to_decode");
-            ares::cli_pretty_printing::failed_to_decode(&config)
+            ares::cli_pretty_printing::failed_to_decode(&config);
+            search_stats(&stats.snapshot(), &config);
+            std::process::exit(exit_code::NOT_CRACKED);
         }
     }
 }
+
+/// Cracks the text, or the `--stream` file, exiting with [`exit_code::INVALID_INPUT`]
+/// if it can't be cracked at all
+fn crack(text: &Candidate, config: &Config) -> Option<DecoderResult> {
+    let result = match &config.stream_file {
+        Some(path) => match File::open(path) {
+            Ok(file) => try_perform_cracking_reader(BufReader::new(file), config.clone()),
+            Err(e) => {
+                eprintln!("Can't read input file '{}': {}", path, e);
+                std::process::exit(exit_code::INVALID_INPUT);
+            }
+        },
+        None => perform_cracking_request(CrackRequest {
+            input: text.clone().into(),
+            config: config.clone(),
+            ..Default::default()
+        }),
+    };
+    match result {
+        Ok(result) => Some(result),
+        Err(AresError::InvalidInput(reason)) => {
+            eprintln!("Invalid input: {}", reason);
+            std::process::exit(exit_code::INVALID_INPUT);
+        }
+        Err(e @ AresError::PreviouslyFailed(_)) => {
+            if !config.api_mode {
+                println!(
+                    "{}",
+                    ares::cli_pretty_printing::warning(&e.to_string(), config)
+                );
+            }
+            None
+        }
+        Err(e) => {
+            debug!("{}", e);
+            None
+        }
+    }
+}
+
+/// Writes the plaintext to `-o <path>`, using the original bytes if it was binary
+fn write_output(output_path: &str, result: &DecoderResult, config: &Config) {
+    let bytes = match result.text[0].content() {
+        Content::Bytes(bytes) => bytes.to_vec(),
+        Content::Text(text) => artifacts::plaintext_bytes(text),
+    };
+    match std::fs::write(output_path, bytes) {
+        Ok(()) if !config.api_mode => println!(
+            "{}",
+            statement(&format!("Plaintext written to {}", output_path), None, config)
+        ),
+        Ok(()) => (),
+        Err(e) => eprintln!("Failed to write plaintext to '{}': {}", output_path, e),
+    }
+}
+
+/// Copies the plaintext to the system clipboard for `--copy`
+fn copy_plaintext(result: &DecoderResult, config: &Config) {
+    match clipboard::write_clipboard(&result.text[0].text()) {
+        Ok(()) if !config.api_mode => println!(
+            "{}",
+            statement("Plaintext copied to the clipboard", None, config)
+        ),
+        Ok(()) => (),
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// Saves every binary blob found during the run into `--artifacts-dir`
+fn save_artifacts(artifacts_dir: &str, config: &Config) {
+    match artifacts::save_artifacts(Path::new(artifacts_dir)) {
+        Ok(paths) if !config.api_mode && !paths.is_empty() => println!(
+            "{}",
+            statement(
+                &format!("Saved {} artifacts to {}", paths.len(), artifacts_dir),
+                None,
+                config
+            )
+        ),
+        Ok(_) => (),
+        Err(e) => eprintln!("Failed to save artifacts to '{}': {}", artifacts_dir, e),
+    }
+}
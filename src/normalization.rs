@@ -0,0 +1,156 @@
+//! Cleaning up visually poisoned input before the search
+//!
+//! Text copied from web pages, chats and PDFs often carries characters which
+//! look like nothing or like plain ASCII, but aren't: zero-width spaces
+//! between Base64 characters, a fullwidth `Ａ`, curly quotes around a
+//! Vigenère ciphertext. They break decodings which would otherwise be simple,
+//! so before searching they are removed or replaced, in three stages:
+//!
+//! 1. Invisible characters from [`crate::storage::invisible_chars`] which
+//!    aren't whitespace are removed
+//! 2. Characters whose NFKC normal form is ASCII are replaced by it
+//! 3. Curly quotes and dashes are straightened
+//!
+//! If anything changed, it is recorded as the first step of the path, with
+//! what was done as the key. Input given as raw bytes which aren't UTF-8
+//! never gets here, as its chars are bytes.
+
+use crate::decoders::crack_results::CrackResult;
+use crate::input_format::PreprocessedInput;
+use crate::storage::invisible_chars::is_invisible;
+use unicode_normalization::UnicodeNormalization;
+
+/// The name of the path step recording the normalization
+pub const NORMALIZATION_STEP: &str = "Unicode normalization";
+
+/// Blank in Braille, which the Braille decoder reads as a space
+const BRAILLE_BLANK: char = '\u{2800}';
+
+/// How many characters each stage changed
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Changes {
+    /// Invisible characters removed
+    removed: usize,
+    /// Characters replaced by their NFKC normal form
+    compatibility: usize,
+    /// Curly quotes and dashes straightened
+    punctuation: usize,
+}
+
+impl Changes {
+    /// What was done, e.g. "removed 3 invisible characters"
+    fn describe(&self) -> String {
+        [
+            (self.removed, "removed", "invisible"),
+            (self.compatibility, "replaced", "compatibility"),
+            (self.punctuation, "straightened", "quote and dash"),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, verb, kind)| {
+            let plural = if count == 1 { "" } else { "s" };
+            format!("{verb} {count} {kind} character{plural}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// Whether `c` is invisible and should be removed. Whitespace is kept, so
+/// are Braille blanks, which aren't poison but Braille.
+fn is_removable(c: char) -> bool {
+    is_invisible(c) && !c.is_whitespace() && c != BRAILLE_BLANK
+}
+
+/// The straight version of a curly quote or dash
+fn straighten(c: char) -> Option<char> {
+    match c {
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => Some('\''),
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => Some('"'),
+        '\u{2010}'..='\u{2015}' | '\u{2212}' => Some('-'),
+        _ => None,
+    }
+}
+
+/// Normalizes the input, returning None if nothing needed changing
+///
+/// ```
+/// use ares::normalization::normalize_input;
+///
+/// // Zero-width spaces hidden in Base64, and a fullwidth letter
+/// let normalized = normalize_input("aGVs\u{200B}bG8\u{200B}gd29ybGＱ=").unwrap();
+/// assert_eq!(normalized.text, "aGVsbG8gd29ybGQ=");
+/// let step = normalized.step.unwrap();
+/// assert_eq!(
+///     step.key.as_deref(),
+///     Some("removed 2 invisible characters, replaced 1 compatibility character")
+/// );
+/// assert!(normalize_input("aGVsbG8gd29ybGQ=").is_none());
+/// ```
+pub fn normalize_input(input: &str) -> Option<PreprocessedInput> {
+    if input.is_ascii() {
+        return None;
+    }
+    let mut changes = Changes::default();
+    let mut text = String::with_capacity(input.len());
+    for c in input.chars() {
+        if c.is_ascii() {
+            text.push(c);
+        } else if is_removable(c) {
+            changes.removed += 1;
+        } else if let Some(straight) = straighten(c) {
+            changes.punctuation += 1;
+            text.push(straight);
+        } else {
+            let normal: String = std::iter::once(c).nfkc().collect();
+            if normal.is_ascii() && !normal.is_empty() {
+                changes.compatibility += 1;
+                text.push_str(&normal);
+            } else {
+                text.push(c);
+            }
+        }
+    }
+    // Input which is nothing but invisible characters may be the message itself
+    if changes == Changes::default() || text.is_empty() {
+        return None;
+    }
+    let mut step = CrackResult::from_decoder_info(
+        NORMALIZATION_STEP,
+        "Removes invisible characters and replaces lookalikes of ASCII characters, such as fullwidth letters and curly quotes, with them.",
+        "https://en.wikipedia.org/wiki/Unicode_equivalence",
+        input,
+    );
+    step.key = Some(changes.describe());
+    step.unencrypted_text = Some(vec![text.clone().into()]);
+    Some(PreprocessedInput {
+        text: text.into(),
+        step: Some(step),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_dashes_and_spaces_become_ascii() {
+        let normalized = normalize_input("\u{201C}don\u{2019}t\u{201D} \u{2014} ok\u{00A0}then").unwrap();
+        assert_eq!(normalized.text, "\"don't\" - ok then");
+        assert_eq!(
+            normalized.step.unwrap().key.as_deref(),
+            Some("replaced 1 compatibility character, straightened 4 quote and dash characters")
+        );
+    }
+
+    #[test]
+    fn other_scripts_and_braille_are_kept() {
+        assert!(normalize_input("⠓⠑⠀⠇⠇⠕ привет 你好").is_none());
+    }
+
+    #[test]
+    fn latin1_text_is_normalized_too() {
+        // Only chars below U+0100, but typed as text rather than given as bytes
+        assert_eq!(normalize_input("café x²").unwrap().text, "café x2");
+    }
+}
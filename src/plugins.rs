@@ -0,0 +1,463 @@
+//! Decoder and checker plugins loaded from shared libraries
+//!
+//! With the `plugins` feature, every shared library (`.so`, `.dylib` or `.dll`)
+//! in `~/.ares/plugins` is loaded when the CLI starts. The decoders it exports
+//! are added to the search with [`crate::register_decoder`], and the checkers
+//! with [`crate::register_checker_fn`], so Athena runs them on every candidate
+//! plaintext. This lets new ciphers and plaintext formats ship as plugins
+//! instead of patches to ares.
+//!
+//! A plugin is a `cdylib` exporting one or both of these C ABI entry points:
+//!
+//! ```c
+//! const AresDecoderPlugin *ares_plugin_decoders(size_t *count);
+//! const AresCheckerPlugin *ares_plugin_checkers(size_t *count);
+//! ```
+//!
+//! Each returns an array of `count` [`AresDecoderPlugin`] or
+//! [`AresCheckerPlugin`] descriptions, which must stay valid for as long as the
+//! plugin is loaded. Plugins are never unloaded.
+//!
+//! Plugins run native code inside ares with no sandboxing, so only install
+//! plugins you trust.
+
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::decoders::crack_results::CrackResult;
+use crate::decoders::interface::Crack;
+use libloading::{Library, Symbol};
+use log::{trace, warn};
+use std::ffi::{c_char, CStr, CString};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version of the plugin ABI. Plugins built for another version are not loaded.
+pub const ABI_VERSION: u32 = 1;
+
+/// Name of the function plugins export to add decoders
+const ENTRY_POINT: &[u8] = b"ares_plugin_decoders\0";
+
+/// Signature of [`ENTRY_POINT`]
+type EntryPoint = unsafe extern "C" fn(count: *mut usize) -> *const AresDecoderPlugin;
+
+/// Name of the function plugins export to add checkers
+const CHECKER_ENTRY_POINT: &[u8] = b"ares_plugin_checkers\0";
+
+/// Signature of [`CHECKER_ENTRY_POINT`]
+type CheckerEntryPoint = unsafe extern "C" fn(count: *mut usize) -> *const AresCheckerPlugin;
+
+/// A decoder exported by a plugin. Strings are NUL-terminated UTF-8.
+#[repr(C)]
+pub struct AresDecoderPlugin {
+    /// Must be [`ABI_VERSION`]
+    pub abi_version: u32,
+    /// Name of the decoder, which must not clash with any other decoder
+    pub name: *const c_char,
+    /// Short description, shown by `--list-decoders`
+    pub description: *const c_char,
+    /// Link to more information on the encoding
+    pub link: *const c_char,
+    /// Comma-separated tags, e.g. `"base,decoder"`
+    pub tags: *const c_char,
+    /// How common the encoding is, between 0.0 and 1.0
+    pub popularity: f32,
+    /// Decodes the input. Returns null if it can't be decoded, otherwise a string
+    /// which ares hands back to `free_string` once it has copied it.
+    pub decode: unsafe extern "C" fn(input: *const c_char) -> *mut c_char,
+    /// Frees a string returned by `decode`
+    pub free_string: unsafe extern "C" fn(output: *mut c_char),
+}
+
+/// A checker exported by a plugin. Strings are NUL-terminated UTF-8.
+#[repr(C)]
+pub struct AresCheckerPlugin {
+    /// Must be [`ABI_VERSION`]
+    pub abi_version: u32,
+    /// Name of the checker, which must not clash with any other checker
+    pub name: *const c_char,
+    /// What the checker accepts, shown with the plaintext it found
+    pub description: *const c_char,
+    /// Returns true if the input is the plaintext
+    pub check: unsafe extern "C" fn(input: *const c_char) -> bool,
+}
+
+/// A decoder backed by a plugin
+pub struct PluginDecoder {
+    /// Name of the decoder
+    name: &'static str,
+    /// Description of the decoder
+    description: &'static str,
+    /// Link to more information
+    link: &'static str,
+    /// Tags used to filter decoders
+    tags: Vec<&'static str>,
+    /// How common the encoding is
+    popularity: f32,
+    /// The plugin's decode function
+    decode: unsafe extern "C" fn(*const c_char) -> *mut c_char,
+    /// The plugin's function to free decoded strings
+    free_string: unsafe extern "C" fn(*mut c_char),
+}
+
+/// `decode` for [`PluginDecoder::new`], which has no plugin behind it
+unsafe extern "C" fn decode_nothing(_input: *const c_char) -> *mut c_char {
+    std::ptr::null_mut()
+}
+
+/// `free_string` for [`PluginDecoder::new`]
+unsafe extern "C" fn free_nothing(_output: *mut c_char) {}
+
+/// Copies a string from a plugin, leaking it as decoders keep their names for good
+///
+/// # Safety
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn plugin_str(ptr: *const c_char, field: &str) -> Result<&'static str, String> {
+    if ptr.is_null() {
+        return Err(format!("the {} is null", field));
+    }
+    let text = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+    Ok(Box::leak(text.into_boxed_str()))
+}
+
+impl PluginDecoder {
+    /// Builds a decoder from a plugin's description of it
+    ///
+    /// # Safety
+    /// The strings in `plugin` must be null or NUL-terminated, and its functions
+    /// must be safe to call from any thread for as long as the plugin is loaded.
+    ///
+    /// # Errors
+    /// Returns an error if the ABI version is wrong or the name is missing.
+    pub unsafe fn from_plugin(plugin: &AresDecoderPlugin) -> Result<Self, String> {
+        if plugin.abi_version != ABI_VERSION {
+            return Err(format!(
+                "it was built for plugin ABI {}, but ares uses {}",
+                plugin.abi_version, ABI_VERSION
+            ));
+        }
+        let name = plugin_str(plugin.name, "name")?;
+        let optional = |ptr, field| plugin_str(ptr, field).unwrap_or("");
+        Ok(PluginDecoder {
+            name,
+            description: optional(plugin.description, "description"),
+            link: optional(plugin.link, "link"),
+            tags: optional(plugin.tags, "tags")
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+            popularity: plugin.popularity.clamp(0.0, 1.0),
+            decode: plugin.decode,
+            free_string: plugin.free_string,
+        })
+    }
+
+    /// Runs the plugin's decode function
+    fn decode_text(&self, text: &str) -> Option<String> {
+        // Text with NUL bytes can't be passed as a C string
+        let input = CString::new(text).ok()?;
+        // SAFETY: `from_plugin` requires `decode` and `free_string` to be safe to call,
+        // and `output` is checked for null before use
+        unsafe {
+            let output = (self.decode)(input.as_ptr());
+            if output.is_null() {
+                return None;
+            }
+            let decoded = CStr::from_ptr(output).to_string_lossy().into_owned();
+            (self.free_string)(output);
+            Some(decoded)
+        }
+    }
+}
+
+impl Crack for PluginDecoder {
+    /// A decoder with no plugin behind it, which never decodes anything
+    fn new() -> Self {
+        PluginDecoder {
+            name: "Plugin",
+            description: "",
+            link: "",
+            tags: Vec::new(),
+            popularity: 0.0,
+            decode: decode_nothing,
+            free_string: free_nothing,
+        }
+    }
+
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        trace!("Running plugin decoder {}", self.name);
+        let mut result = CrackResult::from_decoder_info(
+            self.name,
+            self.description,
+            self.link,
+            text.to_string(),
+        );
+        let Some(decoded) = self.decode_text(text) else {
+            return result;
+        };
+        let checker_result = checker.check(&decoded, config);
+        result.unencrypted_text = Some(vec![decoded.into()]);
+        result.update_checker(&checker_result);
+        result
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+    /// Gets the description for the current decoder
+    fn get_description(&self) -> &str {
+        self.description
+    }
+    /// Gets the link for the current decoder
+    fn get_link(&self) -> &str {
+        self.link
+    }
+    /// Gets the popularity the plugin gave the decoder
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+}
+
+/// Registers a checker from a plugin's description of it.
+/// Returns the checker's name.
+///
+/// # Safety
+/// The strings in `plugin` must be null or NUL-terminated, and `check` must be
+/// safe to call from any thread for as long as the plugin is loaded.
+///
+/// # Errors
+/// Returns an error if the ABI version is wrong, the name is missing, or a
+/// checker with the same name exists.
+pub unsafe fn register_plugin_checker(plugin: &AresCheckerPlugin) -> Result<String, String> {
+    if plugin.abi_version != ABI_VERSION {
+        return Err(format!(
+            "it was built for plugin ABI {}, but ares uses {}",
+            plugin.abi_version, ABI_VERSION
+        ));
+    }
+    let name = plugin_str(plugin.name, "checker's name")?;
+    let description = plugin_str(plugin.description, "checker's description").unwrap_or("");
+    let check = plugin.check;
+    crate::register_checker_fn(name, description, move |text| {
+        // Text with NUL bytes can't be passed as a C string
+        let Ok(input) = CString::new(text) else {
+            return false;
+        };
+        // SAFETY: the caller of `register_plugin_checker` promised `check` is safe to call
+        unsafe { check(input.as_ptr()) }
+    })?;
+    Ok(name.to_string())
+}
+
+/// Returns `~/.ares/plugins`, where the CLI looks for plugins
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ares").join("plugins"))
+}
+
+/// Loads one plugin and registers its decoders and checkers.
+/// Returns the names of the decoders and checkers it added.
+///
+/// # Errors
+/// Returns an error if the library can't be loaded, has neither entry point,
+/// or one of its decoders or checkers is invalid or clashes with an existing
+/// one. Those registered before a clash stay registered.
+pub fn load_plugin(path: &Path) -> Result<Vec<String>, String> {
+    // SAFETY: loading a library runs its initialisers, which is why plugins must be trusted
+    let library = unsafe { Library::new(path) }.map_err(|e| e.to_string())?;
+    // SAFETY: the entry points have the documented signatures, and the arrays they
+    // return stay valid because the library is never unloaded once they are used
+    let (decoders, checkers) = unsafe {
+        let decoder_entry = library.get::<EntryPoint>(ENTRY_POINT).ok();
+        let checker_entry = library.get::<CheckerEntryPoint>(CHECKER_ENTRY_POINT).ok();
+        if decoder_entry.is_none() && checker_entry.is_none() {
+            return Err(
+                "it has neither an ares_plugin_decoders nor an ares_plugin_checkers function"
+                    .to_string(),
+            );
+        }
+        let decoders = match decoder_entry {
+            Some(entry) => plugin_array(&entry, "ares_plugin_decoders")?
+                .iter()
+                .map(|plugin| PluginDecoder::from_plugin(plugin))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+        let checkers = match checker_entry {
+            Some(entry) => plugin_array(&entry, "ares_plugin_checkers")?,
+            None => &[],
+        };
+        (decoders, checkers)
+    };
+    // The registered decoders and checkers call into the library for the rest of the process
+    std::mem::forget(library);
+    let mut names = Vec::with_capacity(decoders.len() + checkers.len());
+    for decoder in decoders {
+        let name = decoder.name.to_string();
+        crate::register_decoder(Box::new(decoder))?;
+        names.push(name);
+    }
+    for checker in checkers {
+        // SAFETY: plugins must only export checkers which are safe to call from any thread
+        names.push(unsafe { register_plugin_checker(checker) }?);
+    }
+    Ok(names)
+}
+
+/// Calls an entry point and returns the array it gives
+///
+/// # Safety
+/// `entry` must return null or an array of `count` items which stays valid
+/// for the rest of the process.
+unsafe fn plugin_array<T>(
+    entry: &Symbol<unsafe extern "C" fn(count: *mut usize) -> *const T>,
+    name: &str,
+) -> Result<&'static [T], String> {
+    let mut count = 0;
+    let plugins = entry(&mut count);
+    if plugins.is_null() {
+        return Err(format!("{} returned null", name));
+    }
+    Ok(std::slice::from_raw_parts(plugins, count))
+}
+
+/// Loads every shared library in `dir`, in name order.
+/// A missing directory simply means no plugins are installed.
+pub fn load_plugins(dir: &Path) -> Vec<(PathBuf, Result<Vec<String>, String>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == std::env::consts::DLL_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let loaded = load_plugin(&path);
+            (path, loaded)
+        })
+        .collect()
+}
+
+/// Loads the plugins in [`plugins_dir`], warning about any which fail to load
+pub fn load_default_plugins() {
+    let Some(dir) = plugins_dir() else {
+        return;
+    };
+    for (path, loaded) in load_plugins(&dir) {
+        match loaded {
+            Ok(names) => trace!("Loaded {} from plugin {}", names.join(", "), path.display()),
+            Err(e) => {
+                warn!("Could not load plugin {}: {}", path.display(), e);
+                eprintln!("Could not load plugin {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::athena::Athena;
+    use crate::checkers::checker_type::{Check, Checker};
+
+    /// Uppercases the input, or fails on empty input
+    unsafe extern "C" fn shout(input: *const c_char) -> *mut c_char {
+        let text = CStr::from_ptr(input).to_string_lossy().to_uppercase();
+        if text.is_empty() {
+            return std::ptr::null_mut();
+        }
+        CString::new(text).unwrap().into_raw()
+    }
+
+    unsafe extern "C" fn free_shout(output: *mut c_char) {
+        drop(CString::from_raw(output));
+    }
+
+    fn plugin(abi_version: u32) -> AresDecoderPlugin {
+        AresDecoderPlugin {
+            abi_version,
+            name: c"Shout plugin".as_ptr(),
+            description: c"Uppercases text".as_ptr(),
+            link: std::ptr::null(),
+            tags: c"shout, decoder".as_ptr(),
+            popularity: 2.0,
+            decode: shout,
+            free_string: free_shout,
+        }
+    }
+
+    #[test]
+    fn plugin_decoders_decode_through_the_c_abi() {
+        let decoder = unsafe { PluginDecoder::from_plugin(&plugin(ABI_VERSION)) }.unwrap();
+        assert_eq!(decoder.get_name(), "Shout plugin");
+        assert_eq!(decoder.get_tags(), &vec!["shout", "decoder"]);
+        assert_eq!(decoder.get_link(), "");
+        assert_eq!(decoder.get_popularity(), 1.0);
+
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let result = decoder.crack("hello", &checker, &Config::default());
+        assert_eq!(result.decoder, "Shout plugin");
+        assert_eq!(result.unencrypted_text, Some(vec!["HELLO".into()]));
+        assert!(decoder
+            .crack("", &checker, &Config::default())
+            .unencrypted_text
+            .is_none());
+
+        assert!(unsafe { PluginDecoder::from_plugin(&plugin(ABI_VERSION + 1)) }.is_err());
+    }
+
+    /// Accepts order numbers like `ORD-1234`
+    unsafe extern "C" fn is_order_number(input: *const c_char) -> bool {
+        CStr::from_ptr(input)
+            .to_str()
+            .is_ok_and(|text| text.strip_prefix("ORD-").is_some_and(|n| n.parse::<u32>().is_ok()))
+    }
+
+    #[test]
+    fn plugin_checkers_check_through_the_c_abi() {
+        let checker = |abi_version| AresCheckerPlugin {
+            abi_version,
+            name: c"Order number plugin".as_ptr(),
+            description: c"An order number".as_ptr(),
+            check: is_order_number,
+        };
+        assert!(unsafe { register_plugin_checker(&checker(ABI_VERSION + 1)) }.is_err());
+        let name = unsafe { register_plugin_checker(&checker(ABI_VERSION)) }.unwrap();
+        assert_eq!(name, "Order number plugin");
+        // Names can't clash
+        assert!(unsafe { register_plugin_checker(&checker(ABI_VERSION)) }.is_err());
+
+        let config = Config {
+            human_checker_on: false,
+            ..Default::default()
+        };
+        let result = Checker::<Athena>::new().check("ORD-1234", &config);
+        assert!(result.is_identified);
+        assert_eq!(result.checker_name, "Order number plugin");
+    }
+
+    #[test]
+    fn bad_plugin_files_are_reported() {
+        let dir = std::env::temp_dir().join(format!("ares-plugins-{}", uuid::Uuid::new_v4()));
+        assert!(load_plugins(&dir).is_empty());
+
+        fs::create_dir_all(&dir).unwrap();
+        let fake = dir.join(format!("fake.{}", std::env::consts::DLL_EXTENSION));
+        fs::write(&fake, "not a library").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        let loaded = load_plugins(&dir);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].0, fake);
+        assert!(loaded[0].1.is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+//! Making plaintexts readable before they are shown, for `--readable`
+//!
+//! Classical ciphers usually drop spaces and case, so a correct result can
+//! look like `THEQUICKBROWNFOX`. [`make_readable`] puts the spaces back by
+//! splitting runs of letters into the most likely words, then puts back
+//! sentence case. Only what is shown changes: results keep the text as it
+//! was decoded.
+//!
+//! The split is the one whose words are most probable together, taking a
+//! word's probability to fall with its rank in the language's list of common
+//! words, as Zipf's law has it. Letters which aren't part of any word cost
+//! more than any word, so a run is only split if most of it is words.
+
+use crate::cryptanalysis::{languages_or_english, Language};
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+
+/// Runs of letters shorter than this are left as they are
+const MIN_RUN_LEN: usize = 8;
+
+/// The share of a run's letters which must be in words for it to be split
+const MIN_WORD_COVERAGE: f64 = 0.8;
+
+/// The share of characters, not counting whitespace, which must be letters
+/// for text to look like the output of a classical cipher
+const MIN_LETTER_SHARE: f64 = 0.7;
+
+/// The cost of each letter not in a word, as a multiple of the cost of the
+/// rarest word
+const UNKNOWN_LETTER_FACTOR: f64 = 1.5;
+
+/// The cost of each common word, by language in the order of [`Language::ALL`]
+static WORD_COSTS: [OnceCell<HashMap<&'static str, f64>>; 5] = [
+    OnceCell::new(),
+    OnceCell::new(),
+    OnceCell::new(),
+    OnceCell::new(),
+    OnceCell::new(),
+];
+
+/// The negative log probability of each of the language's common words,
+/// with a word of rank `r` out of `n` having probability `1 / (r ln n)`
+fn word_costs(language: Language) -> &'static HashMap<&'static str, f64> {
+    let index = Language::ALL
+        .iter()
+        .position(|other| *other == language)
+        .unwrap_or(0);
+    WORD_COSTS[index].get_or_init(|| {
+        let words = language.words_by_frequency();
+        let log_n = (words.len().max(2) as f64).ln();
+        let mut costs = HashMap::with_capacity(words.len());
+        for (rank, word) in words.iter().enumerate() {
+            // The list may have a word twice, e.g. in two cases
+            costs
+                .entry(*word)
+                .or_insert_with(|| ((rank + 1) as f64 * log_n).ln());
+        }
+        costs
+    })
+}
+
+/// A run of letters split into words, and what the split cost
+struct Split {
+    /// Where each word after the first starts, in bytes
+    breaks: Vec<usize>,
+    /// The total cost
+    cost: f64,
+    /// How many letters aren't in words
+    unknown: usize,
+}
+
+/// Finds the cheapest split of a lowercase ASCII run into `language`'s words
+fn split_run(run: &str, language: Language) -> Split {
+    let costs = word_costs(language);
+    let dictionary = language.common_words();
+    let rarest = costs.values().copied().fold(0.0, f64::max);
+    let unknown_cost = rarest * UNKNOWN_LETTER_FACTOR;
+
+    // best[i] is the cheapest split of run[..i]: its cost, how many letters
+    // of it are unknown, and where its last word starts
+    let len = run.len();
+    let mut best = vec![(f64::INFINITY, 0, 0); len + 1];
+    best[0] = (0.0, 0, 0);
+    for start in 0..len {
+        let (cost, unknown, _) = best[start];
+        if cost.is_infinite() {
+            continue;
+        }
+        // A letter on its own, which joins the word before it
+        let skipped = (cost + unknown_cost, unknown + 1, start);
+        if skipped.0 < best[start + 1].0 {
+            best[start + 1] = skipped;
+        }
+        for word_len in dictionary.prefix_lengths(&run[start..]) {
+            let word = &run[start..start + word_len];
+            let Some(word_cost) = costs.get(word) else {
+                continue;
+            };
+            let end = start + word_len;
+            if cost + word_cost < best[end].0 {
+                best[end] = (cost + word_cost, unknown, start);
+            }
+        }
+    }
+
+    let (cost, unknown, _) = best[len];
+    let mut breaks = Vec::new();
+    let mut end = len;
+    while end > 0 {
+        let (_, end_unknown, start) = best[end];
+        // Unknown letters stay attached to the word they follow
+        let is_word = end - start > 1 || best[start].1 == end_unknown;
+        if is_word && start > 0 {
+            breaks.push(start);
+        }
+        end = start;
+    }
+    breaks.reverse();
+    Split {
+        breaks,
+        cost,
+        unknown,
+    }
+}
+
+/// Puts spaces between the words of long runs of letters, in whichever of
+/// `languages` splits them best. Runs which aren't mostly words are kept.
+///
+/// ```
+/// use ares::postprocess::restore_spacing;
+///
+/// assert_eq!(restore_spacing("thequickbrownfox", &[]), "the quick brown fox");
+/// assert_eq!(restore_spacing("xqzvwkpltrbn", &[]), "xqzvwkpltrbn");
+/// ```
+pub fn restore_spacing(text: &str, languages: &[Language]) -> String {
+    let mut spaced = String::with_capacity(text.len() + text.len() / 4);
+    let mut rest = text;
+    while !rest.is_empty() {
+        let run_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (run, after) = rest.split_at(run_len);
+        if run.len() >= MIN_RUN_LEN {
+            let lowercase = run.to_ascii_lowercase();
+            let split = languages_or_english(languages)
+                .iter()
+                .map(|language| split_run(&lowercase, *language))
+                .min_by(|a, b| a.cost.total_cmp(&b.cost));
+            match split {
+                Some(split)
+                    if split.unknown as f64 <= run.len() as f64 * (1.0 - MIN_WORD_COVERAGE) =>
+                {
+                    let mut previous = 0;
+                    for position in split.breaks {
+                        spaced.push_str(&run[previous..position]);
+                        spaced.push(' ');
+                        previous = position;
+                    }
+                    spaced.push_str(&run[previous..]);
+                }
+                _ => spaced.push_str(run),
+            }
+        } else {
+            spaced.push_str(run);
+        }
+        let Some(next) = after.chars().next() else {
+            break;
+        };
+        spaced.push(next);
+        rest = &after[next.len_utf8()..];
+    }
+    spaced
+}
+
+/// Puts back sentence case in text which is all one case: a capital at the
+/// start of each sentence and for the English "I". Text which already mixes
+/// cases is kept.
+///
+/// ```
+/// use ares::postprocess::restore_case;
+///
+/// assert_eq!(restore_case("ATTACK AT DAWN. I WILL BE THERE"), "Attack at dawn. I will be there");
+/// assert_eq!(restore_case("Meet at the Eiffel Tower"), "Meet at the Eiffel Tower");
+/// ```
+pub fn restore_case(text: &str) -> String {
+    let has_upper = text.chars().any(char::is_uppercase);
+    let has_lower = text.chars().any(char::is_lowercase);
+    if has_upper && has_lower {
+        return text.to_string();
+    }
+    let lowercase = text.to_lowercase();
+    let mut cased = String::with_capacity(lowercase.len());
+    let mut sentence_start = true;
+    for (i, c) in lowercase.char_indices() {
+        let alone = |position: usize| {
+            !lowercase[..position]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric)
+                && !lowercase[position + c.len_utf8()..]
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_alphanumeric)
+        };
+        if c.is_alphabetic() && (sentence_start || (c == 'i' && alone(i))) {
+            cased.extend(c.to_uppercase());
+        } else {
+            cased.push(c);
+        }
+        if matches!(c, '.' | '!' | '?') {
+            sentence_start = true;
+        } else if c.is_alphanumeric() {
+            sentence_start = false;
+        }
+    }
+    cased
+}
+
+/// The plaintext as it should be shown with `--readable`: spaced with
+/// [`restore_spacing`] and cased with [`restore_case`]. None if that changes
+/// nothing, or if the text is too few letters to be classical cipher output.
+///
+/// ```
+/// use ares::postprocess::make_readable;
+///
+/// assert_eq!(
+///     make_readable("WEAREDISCOVEREDFLEEATONCE", &[]).as_deref(),
+///     Some("We are discovered flee at once")
+/// );
+/// assert_eq!(make_readable("Already fine.", &[]), None);
+/// assert_eq!(make_readable("{\"ID\":42,\"OK\":1}", &[]), None);
+/// ```
+pub fn make_readable(text: &str, languages: &[Language]) -> Option<String> {
+    let visible = text.chars().filter(|c| !c.is_whitespace()).count();
+    let letters = text.chars().filter(|c| c.is_alphabetic()).count();
+    if (letters as f64) < visible as f64 * MIN_LETTER_SHARE {
+        return None;
+    }
+    let readable = restore_case(&restore_spacing(text, languages));
+    (readable != text).then_some(readable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spacing_keeps_punctuation_and_short_runs() {
+        assert_eq!(
+            restore_spacing("sendhelpquickly, bringthemap!", &[]),
+            "send help quickly, bring the map!"
+        );
+        assert_eq!(restore_spacing("ab cd", &[]), "ab cd");
+    }
+
+    #[test]
+    fn spacing_keeps_the_original_case() {
+        assert_eq!(restore_spacing("HelloWorldAgain", &[]), "Hello World Again");
+    }
+
+    #[test]
+    fn spacing_uses_the_best_language() {
+        assert_eq!(
+            restore_spacing("lachatestsurlatable", &[Language::English, Language::French]),
+            "la chat est sur la table"
+        );
+    }
+
+    #[test]
+    fn a_stray_letter_stays_with_its_word() {
+        let spaced = restore_spacing("thequickbrownfoxq", &[]);
+        assert_eq!(spaced, "the quick brown foxq");
+    }
+}
@@ -0,0 +1,356 @@
+//! Recipes: known decodings applied in order, without searching
+//!
+//! Once a puzzle has been solved, the same steps can be replayed on new input
+//! with `ares run-recipe <file> <input>`. Recipes are YAML, each step a decoder
+//! name, or a decoder and a key:
+//!
+//! ```yaml
+//! steps:
+//!   - base64
+//!   - decoder: caesar
+//!     key: 13
+//! ```
+//!
+//! CyberChef recipes saved as JSON (`[{"op": "From Base64", "args": [...]}]`)
+//! can be run too, as long as each operation has an Ares decoder.
+
+use crate::byte_text::Candidate;
+use crate::config::Config;
+use crate::decoders::crack_results::CrackResult;
+use crate::{perform_decoding, DecoderResult};
+use serde::Deserialize;
+use serde_yaml_ng::Value;
+use std::path::Path;
+
+/// One decoding in a recipe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipeStep {
+    /// Decoder name or tag, as for `ares decode --decoder`
+    pub decoder: String,
+    /// Key for keyed decoders, as for `ares decode --key`
+    pub key: Option<String>,
+}
+
+/// A list of decodings to apply in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recipe {
+    /// The steps, first to last
+    pub steps: Vec<RecipeStep>,
+}
+
+/// The shapes a recipe file can have
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RecipeFile {
+    /// `steps:` followed by the list of steps
+    Native {
+        /// The steps
+        steps: Vec<NativeStep>,
+    },
+    /// A CyberChef recipe exported as JSON
+    CyberChef(Vec<CyberChefOperation>),
+    /// Just the list of steps
+    NativeList(Vec<NativeStep>),
+}
+
+/// A step written in Ares' own format
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NativeStep {
+    /// Only a decoder name
+    Name(String),
+    /// A decoder and maybe a key
+    Full {
+        /// Decoder name or tag
+        decoder: String,
+        /// Key, which may be written as a number
+        key: Option<Value>,
+    },
+}
+
+/// An operation in a CyberChef recipe
+#[derive(Deserialize)]
+struct CyberChefOperation {
+    /// Name of the operation, e.g. "From Base64"
+    op: String,
+    /// The operation's arguments, in the order CyberChef shows them
+    #[serde(default)]
+    args: Vec<Value>,
+}
+
+impl Recipe {
+    /// Parses a recipe in either format.
+    ///
+    /// ```rust
+    /// use ares::recipe::Recipe;
+    ///
+    /// let recipe = Recipe::parse(r#"[{"op": "From Base64", "args": ["A-Za-z0-9+/=", true]}, {"op": "ROT13", "args": [true, true, false, 13]}]"#).unwrap();
+    /// let result = recipe.run("dXJ5eWIgZ3VyZXI=", &Default::default()).unwrap();
+    /// assert_eq!(result.text[0], "hello there");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the recipe isn't valid YAML or JSON, or uses a
+    /// CyberChef operation Ares has no decoder for.
+    pub fn parse(text: &str) -> Result<Recipe, String> {
+        let file: RecipeFile = serde_yaml_ng::from_str(text).map_err(|e| {
+            format!(
+                "Not a recipe, expected a list of steps or a CyberChef recipe: {}",
+                e
+            )
+        })?;
+        let steps = match file {
+            RecipeFile::Native { steps } | RecipeFile::NativeList(steps) => {
+                steps.into_iter().map(NativeStep::into_step).collect()
+            }
+            RecipeFile::CyberChef(operations) => operations
+                .iter()
+                .map(CyberChefOperation::to_step)
+                .collect::<Result<_, _>>()?,
+        };
+        Ok(Recipe { steps })
+    }
+
+    /// Reads and parses a recipe file
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read or isn't a recipe.
+    pub fn from_file(path: &Path) -> Result<Recipe, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Can't read recipe '{}': {}", path.display(), e))?;
+        Recipe::parse(&contents)
+    }
+
+    /// Applies every step to the text in order.
+    /// The result's path has one entry per step.
+    ///
+    /// # Errors
+    /// Returns an error naming the first step which fails, e.g. because its
+    /// decoder doesn't exist, rejects the key or can't decode the text.
+    pub fn run(&self, text: &str, config: &Config) -> Result<DecoderResult, String> {
+        if self.steps.is_empty() {
+            return Err("The recipe has no steps".to_string());
+        }
+        let mut text = Candidate::from(text);
+        let mut path = Vec::with_capacity(self.steps.len());
+        for (number, step) in self.steps.iter().enumerate() {
+            let result = run_step(&text.text(), step, config)
+                .map_err(|e| format!("Step {} ({}) failed: {}", number + 1, step.decoder, e))?;
+            text = result
+                .unencrypted_text
+                .as_ref()
+                .and_then(|texts| texts.first())
+                .cloned()
+                .unwrap_or_default();
+            path.push(result);
+        }
+        Ok(DecoderResult {
+            text: vec![text],
+            path,
+            confidence: 0.0,
+        })
+    }
+}
+
+/// Runs one step, which must give exactly one output
+fn run_step(text: &str, step: &RecipeStep, config: &Config) -> Result<CrackResult, String> {
+    let mut results = perform_decoding(text, &step.decoder, step.key.as_deref(), config)?;
+    if results.len() > 1 {
+        let names: Vec<&str> = results.iter().map(|r| r.decoder).collect();
+        return Err(format!(
+            "'{}' matches several decoders ({}), name one",
+            step.decoder,
+            names.join(", ")
+        ));
+    }
+    let result = results.remove(0);
+    match result.unencrypted_text.as_deref() {
+        Some([_]) => Ok(result),
+        Some(texts) if texts.len() > 1 => Err(format!(
+            "{} gave {} candidates, give it a key",
+            result.decoder,
+            texts.len()
+        )),
+        _ => Err(format!("{} could not decode the text", result.decoder)),
+    }
+}
+
+impl NativeStep {
+    /// Turns the step into a decoder and key
+    fn into_step(self) -> RecipeStep {
+        match self {
+            NativeStep::Name(decoder) => RecipeStep { decoder, key: None },
+            NativeStep::Full { decoder, key } => RecipeStep {
+                decoder,
+                key: key.as_ref().map(value_to_string),
+            },
+        }
+    }
+}
+
+impl CyberChefOperation {
+    /// Finds the Ares decoder which does what this operation does
+    fn to_step(&self) -> Result<RecipeStep, String> {
+        let arg = |index: usize| self.args.get(index).map(value_to_string);
+        let (decoder, key) = match self.op.as_str() {
+            "From Base32" => ("Base32", None),
+            "From Base45" => ("Base45", None),
+            "From Base58" => match arg(0) {
+                Some(alphabet) if alphabet.starts_with("rpshnaf") => ("Base58 Ripple", None),
+                _ => ("Base58 Bitcoin", None),
+            },
+            "From Base62" => ("Base62", None),
+            "From Base64" => ("Base64", None),
+            "From Base85" => match arg(0) {
+                Some(alphabet) if alphabet.starts_with("0-9a-zA-Z") => ("Z85", None),
+                _ => ("Ascii85", None),
+            },
+            "From Binary" => ("Binary", None),
+            "From Braille" => ("Braille", None),
+            "From Decimal" => ("Decimal", None),
+            "From Hex" => ("Hexadecimal", None),
+            "From HTML Entity" => ("HTML Entity", None),
+            "From Morse Code" => ("Morse Code", None),
+            "From Octal" => ("Octal", None),
+            "From Punycode" => ("Punycode", None),
+            "From Quoted Printable" => ("Quoted-Printable", None),
+            "URL Decode" => ("URL", None),
+            "JWT Decode" => ("JWT", None),
+            "Citrix CTX1 Decode" => ("Citrix Ctx1", None),
+            "Reverse" => ("Reverse", None),
+            "Atbash Cipher" => ("atbash", None),
+            "A1Z26 Cipher Decode" => ("a1z26", None),
+            "Bacon Cipher Decode" => ("Bacon Cipher", None),
+            "ROT47" => match arg(0).as_deref() {
+                None | Some("47") => ("rot47", None),
+                Some(amount) => {
+                    return Err(format!("ROT47 by {} is not supported, only 47", amount))
+                }
+            },
+            "ROT13" => {
+                // CyberChef shifts forward by the amount, so decode by the rest of the alphabet
+                let amount = arg(3).unwrap_or_else(|| "13".to_string());
+                let amount: u32 = amount
+                    .parse()
+                    .map_err(|_| format!("ROT13 amount must be a number, got '{}'", amount))?;
+                ("caesar", Some(((26 - amount % 26) % 26).to_string()))
+            }
+            "Vigenère Decode" => ("Vigenere", arg(0)),
+            "Affine Cipher Decode" => (
+                "Affine Cipher",
+                Some(format!(
+                    "{},{}",
+                    arg(0).unwrap_or_default(),
+                    arg(1).unwrap_or_default()
+                )),
+            ),
+            "Rail Fence Cipher Decode" => (
+                "railfence",
+                Some(format!(
+                    "{},{}",
+                    arg(0).unwrap_or_default(),
+                    arg(1).unwrap_or_else(|| "0".to_string())
+                )),
+            ),
+            "XOR" => ("XOR", Some(cyberchef_xor_key(self.args.first())?)),
+            op => return Err(format!("CyberChef operation '{}' has no Ares decoder", op)),
+        };
+        Ok(RecipeStep {
+            decoder: decoder.to_string(),
+            key,
+        })
+    }
+}
+
+/// Turns CyberChef's XOR key, e.g. `{"option": "Hex", "string": "41"}`, into an XOR decoder key
+fn cyberchef_xor_key(key: Option<&Value>) -> Result<String, String> {
+    let field = |name: &str| {
+        key.and_then(|key| key.get(name))
+            .map(value_to_string)
+            .unwrap_or_default()
+    };
+    let string = field("string");
+    match field("option").as_str() {
+        "Hex" => Ok(format!("0x{}", string.trim_start_matches("0x"))),
+        "Decimal" => Ok(string),
+        "UTF8" | "Latin1" => match string.chars().collect::<Vec<_>>()[..] {
+            [c] if u32::from(c) <= 0xff => Ok(u32::from(c).to_string()),
+            _ => Err(format!("XOR key must be a single byte, got '{}'", string)),
+        },
+        option => Err(format!("XOR key format '{}' is not supported", option)),
+    }
+}
+
+/// Writes a YAML scalar the way a user would type it on the command line
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => String::new(),
+        other => serde_yaml_ng::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native_recipes_parse() {
+        let recipe =
+            Recipe::parse("steps:\n  - base64\n  - decoder: caesar\n    key: 13\n").unwrap();
+        assert_eq!(
+            recipe.steps,
+            vec![
+                RecipeStep {
+                    decoder: "base64".to_string(),
+                    key: None
+                },
+                RecipeStep {
+                    decoder: "caesar".to_string(),
+                    key: Some("13".to_string())
+                },
+            ]
+        );
+        // The `steps:` key is optional
+        assert_eq!(
+            Recipe::parse("- base64\n- reverse\n").unwrap().steps.len(),
+            2
+        );
+    }
+
+    #[test]
+    fn cyberchef_recipes_map_to_decoders() {
+        let recipe = Recipe::parse(
+            r#"[
+                {"op": "From Hex", "args": ["Auto"]},
+                {"op": "XOR", "args": [{"option": "Hex", "string": "20"}, "Standard", false]},
+                {"op": "ROT13", "args": [true, true, false, 3]}
+            ]"#,
+        )
+        .unwrap();
+        let keys: Vec<_> = recipe.steps.iter().map(|s| s.key.as_deref()).collect();
+        assert_eq!(keys, vec![None, Some("0x20"), Some("23")]);
+        assert!(Recipe::parse(r#"[{"op": "AES Decrypt", "args": []}]"#)
+            .unwrap_err()
+            .contains("AES Decrypt"));
+    }
+
+    #[test]
+    fn run_applies_steps_in_order() {
+        let recipe = Recipe::parse("- base64\n- reverse\n").unwrap();
+        // "olleh" in Base64
+        let result = recipe.run("b2xsZWg=", &Config::default()).unwrap();
+        assert_eq!(result.text[0], "hello");
+        assert_eq!(result.path.len(), 2);
+
+        // Caesar without a key gives every shift unless one is plaintext, so the step fails
+        let recipe = Recipe::parse("- caesar\n").unwrap();
+        let error = recipe.run("xkqzvbpt", &Config::default()).unwrap_err();
+        assert!(error.starts_with("Step 1 (caesar) failed"));
+    }
+}
@@ -0,0 +1,186 @@
+//! Everything a crack needs in one value, for [`crate::perform_cracking_request`]
+//!
+//! The CLI turns its arguments into a [`CrackRequest`], and anything else
+//! driving Ares, like a server or FFI bindings, can build one the same way
+//! or read it from JSON. In JSON the config only needs the keys which
+//! differ from the defaults:
+//!
+//! ```rust
+//! use ares::request::{CrackInput, CrackRequest};
+//! let request: CrackRequest = serde_json::from_str(r#"{
+//!     "input": {"text": "aGVsbG8gd29ybGQ="},
+//!     "config": {"timeout": 10},
+//!     "crib": "hello",
+//!     "words": ["zorblax"]
+//! }"#).unwrap();
+//! assert_eq!(request.input, CrackInput::Text("aGVsbG8gd29ybGQ=".to_string()));
+//! assert_eq!(request.config.timeout, 10);
+//! assert!(!request.config.human_checker_on);
+//! ```
+
+use crate::byte_text::{Candidate, Content};
+use crate::config::{load_wordlist, Config};
+use crate::storage::wordlists;
+use crate::AresError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The ciphertext, as text or as bytes which may not be valid UTF-8
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrackInput {
+    /// Text, such as Base64 pasted into a terminal
+    Text(String),
+    /// Bytes, such as XOR'd or compressed data read from a file. Bytes which
+    /// aren't UTF-8 are searched as bytes, see [`crate::byte_text`].
+    Bytes(Vec<u8>),
+}
+
+impl From<Candidate> for CrackInput {
+    fn from(candidate: Candidate) -> Self {
+        match candidate.content() {
+            Content::Text(text) => CrackInput::Text(text.to_string()),
+            Content::Bytes(bytes) => CrackInput::Bytes(bytes.to_vec()),
+        }
+    }
+}
+
+impl Default for CrackInput {
+    fn default() -> Self {
+        CrackInput::Text(String::new())
+    }
+}
+
+/// What to crack and how
+///
+/// ```rust
+/// use ares::perform_cracking_request;
+/// use ares::request::CrackRequest;
+/// # let _test_db = ares::TestDatabase::default();
+/// # ares::set_test_db_path();
+/// let request = CrackRequest::new("aGVsbG8gdGhlcmUgZ2VuZXJhbA==").with_crib("general");
+/// let result = perform_cracking_request(request).unwrap();
+/// assert_eq!(result.text[0], "hello there general");
+/// ```
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrackRequest {
+    /// The ciphertext
+    pub input: CrackInput,
+    /// The settings to crack with
+    pub config: Config,
+    /// Text known to be in the plaintext, like `flag{`. Replaces `config.crib` when set.
+    pub crib: Option<String>,
+    /// Wordlists to load, as paths or names of ones added with `ares wordlist add`.
+    /// Their words are added to `config.wordlist`.
+    pub wordlists: Vec<String>,
+    /// Words the wordlist checker accepts, on top of those in the wordlists
+    pub words: Vec<String>,
+}
+
+impl CrackRequest {
+    /// A request to crack `text` with the default config
+    pub fn new(text: impl Into<String>) -> Self {
+        CrackRequest {
+            input: CrackInput::Text(text.into()),
+            ..Default::default()
+        }
+    }
+
+    /// A request to crack `bytes` with the default config
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        CrackRequest {
+            input: CrackInput::Bytes(bytes.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Cracks with `config` instead of the default
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets text known to be in the plaintext
+    pub fn with_crib(mut self, crib: impl Into<String>) -> Self {
+        self.crib = Some(crib.into());
+        self
+    }
+
+    /// Adds a wordlist, by path or by the name it was added with
+    pub fn with_wordlist(mut self, name_or_path: impl Into<String>) -> Self {
+        self.wordlists.push(name_or_path.into());
+        self
+    }
+
+    /// Adds words the wordlist checker accepts
+    pub fn with_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.words.extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns the text to search and the config with the crib and wordlists applied
+    ///
+    /// # Errors
+    /// Returns [`AresError::InvalidInput`] if a wordlist can't be loaded.
+    pub(crate) fn resolve(self) -> Result<(Candidate, Config), AresError> {
+        let text = match self.input {
+            CrackInput::Text(text) => text.into(),
+            CrackInput::Bytes(bytes) => Candidate::from_bytes(bytes),
+        };
+        let mut config = self.config;
+        if self.crib.is_some() {
+            config.crib = self.crib;
+        }
+        if !self.wordlists.is_empty() || !self.words.is_empty() {
+            let wordlist = config.wordlist.get_or_insert_with(HashSet::new);
+            for name in &self.wordlists {
+                let words = load_wordlist(wordlists::resolve_default(name)).map_err(|e| {
+                    AresError::InvalidInput(format!("Can't load wordlist '{}': {}", name, e))
+                })?;
+                wordlist.extend(words);
+            }
+            wordlist.extend(
+                self.words
+                    .into_iter()
+                    .map(|word| word.trim().to_string())
+                    .filter(|word| !word.is_empty()),
+            );
+        }
+        Ok((text, config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_crib_and_words_are_applied() {
+        let request = CrackRequest::from_bytes(vec![b'h', b'i', 0xff])
+            .with_config(Config {
+                crib: Some("old".to_string()),
+                ..Default::default()
+            })
+            .with_crib("hi")
+            .with_words(["zorblax", "  "]);
+        let (text, config) = request.resolve().unwrap();
+        assert_eq!(text.bytes(), [b'h', b'i', 0xff]);
+        assert!(text.is_bytes());
+        assert_eq!(config.crib.as_deref(), Some("hi"));
+        assert_eq!(
+            config.wordlist,
+            Some(HashSet::from(["zorblax".to_string()]))
+        );
+    }
+
+    #[test]
+    fn missing_wordlists_are_invalid_input() {
+        let request = CrackRequest::new("hello").with_wordlist("/no/such/wordlist.txt");
+        assert!(matches!(request.resolve(), Err(AresError::InvalidInput(_))));
+    }
+}
@@ -33,13 +33,20 @@
 //! - Batch processing extracts multiple nodes from the priority queue
 //! - Special result nodes handle successful decodings in a thread-safe manner
 
+use crate::byte_text::Candidate;
 use crate::cli_pretty_printing;
+use crate::deadline;
+#[cfg(feature = "cli")]
 use crate::cli_pretty_printing::decoded_how_many_times;
+use crate::cryptanalysis::fitness_score;
 use crate::filtration_system::get_all_decoders;
-use crate::filtration_system::{get_decoder_by_name, get_decoder_tagged_decoders, MyResults};
+use crate::filtration_system::{
+    crack_in_span, dedup_candidates, get_decoder_by_name, get_decoder_tagged_decoders,
+    DecoderFilter, MyResults,
+};
 use crossbeam::channel::Sender;
 
-use log::{debug, trace};
+use tracing::{debug, debug_span, trace};
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering as AtomicOrdering};
@@ -53,12 +60,16 @@ use rayon::prelude::*;
     use crate::checkers::checker_type::{Check, Checker};
     use crate::checkers::CheckerTypes;
     use crate::config::Config;
-    use crate::decoders::DECODER_MAP;
+    use crate::decoders::find_decoder;
+    use crate::searchers::events::{self, SearchEvent};
+    use crate::searchers::stats;
     use crate::searchers::helper_functions::{
-        calculate_string_worth, check_if_string_cant_be_decoded, generate_heuristic,
-        update_decoder_stats,
+        candidates_to_follow, check_if_string_cant_be_decoded,
+        generate_heuristic,
+        push_path_step, update_decoder_stats,
     };
 use crate::storage::wait_athena_storage;
+use crate::timer::Instant;
 use crate::DecoderResult;
 
 /// Threshold for pruning the seen_strings HashSet to prevent excessive memory usage
@@ -71,8 +82,17 @@ const INITIAL_PRUNE_THRESHOLD: usize = PRUNE_THRESHOLD;
 /// Number of nodes to process in parallel
 const PARALLEL_BATCH_SIZE: usize = 10;
 
+/// Added to the cost of each ranked candidate per candidate ranked above it,
+/// so a decoder's better candidates are expanded before its worse ones
+const RANKED_CANDIDATE_COST: f32 = 0.05;
+
+/// The popularity of the decoder named `decoder`, or 0.5 if it isn't one
+fn popularity(decoder: &str) -> f32 {
+    find_decoder(decoder).map_or(0.5, |decoder| decoder.get_popularity())
+}
+
 /// Calculate a hash for a string to use in the seen_strings set
-fn calculate_hash(text: &str) -> u64 {
+fn calculate_hash(text: &Candidate) -> u64 {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
@@ -191,6 +211,15 @@ impl ThreadSafePriorityQueue {
     }
 }
 
+/// Names of the decoders used to reach a node, for search events
+fn decoder_names(state: &DecoderResult) -> Vec<String> {
+    state
+        .path
+        .iter()
+        .map(|step| step.decoder.to_string())
+        .collect()
+}
+
 /// Expands a single node and returns a vector of new nodes
 fn expand_node(
     current_node: &AStarNode,
@@ -207,6 +236,14 @@ fn expand_node(
         return new_nodes;
     }
 
+    stats::node_expanded(config);
+    events::emit(|| SearchEvent::NodeExpanded {
+        depth: current_node.cost,
+        path: decoder_names(&current_node.state),
+        text: current_node.state.text[0].to_string(),
+        fitness: fitness_score(&current_node.state.text[0].text()),
+    });
+
     // Determine which decoders to use based on next_decoder_name
     let mut decoders;
     if let Some(decoder_name) = &current_node.next_decoder_name {
@@ -222,6 +259,16 @@ fn expand_node(
         decoders = get_decoder_tagged_decoders(&current_node.state);
     }
 
+    // Respect the user's --only / --exclude lists
+    let filter = DecoderFilter::from_config(config);
+    if let Some(filter) = &filter {
+        decoders.restrict(filter);
+    }
+
+    // The decoders for what the text looks like go first, the rest stay
+    // ordered by value. The first success stops the others from starting.
+    decoders.order_by_identification(&current_node.state.text[0].text(), config);
+
     // Prevent reciprocal decoders from being applied consecutively
     if let Some(last_decoder) = current_node.state.path.last() {
         if last_decoder.checker_description.contains("reciprocal") {
@@ -247,122 +294,53 @@ fn expand_node(
         // we are cheating and just run that one decoder lol
         let decoder_results = decoders.run(&current_node.state.text[0], checker, config);
 
-        // Process decoder results
-        match decoder_results {
-            MyResults::Break(results) => {
-                // Handle successful decoding
-                for res in results {
-                    if res.success {
-                        let decoders_used = current_node.state.path.clone();
-                        let text = res.unencrypted_text.clone().unwrap_or_default();
-                        // If we have multiple texts, we should probably take the first one or iterate?
-                        // For success, usually we take the one that matched.
-                        // CrackResult unencrypted_text is Option<Vec<String>>.
-                        // If it was successful, it probably contains the matched string(s).
-                        // We'll process all of them just in case.
+        // Decoders::run only gives back successes. The candidates of the
+        // decoders which did not succeed are followed by the fallback below.
+        if let MyResults::Break(results) = decoder_results {
+            // Handle successful decoding
+            for res in results {
+                events::emit(|| SearchEvent::DecoderRan {
+                    decoder: res.decoder.to_string(),
+                    produced_output: true,
+                });
+                if res.success {
+                    let decoders_used = current_node.state.path.clone();
+                    let text = res.unencrypted_text.clone().unwrap_or_default();
+                    // If we have multiple texts, we should probably take the first one or iterate?
+                    // For success, usually we take the one that matched.
+                    // CrackResult unencrypted_text is Option<Vec<String>>.
+                    // If it was successful, it probably contains the matched string(s).
+                    // We'll process all of them just in case.
+                    
+                    for t in text {
+                        let mut path = decoders_used.clone();
+                        // We need to reconstruct the CrackResult for this specific text if there are multiple?
+                        // But res corresponds to all of them?
+                        // If res.success is true, it means at least one matched.
+                        // But usually if success is true, unencrypted_text contains ONLY the matched ones.
+                        // So we can just use res.
                         
-                        for t in text {
-                            let mut path = decoders_used.clone();
-                            // We need to reconstruct the CrackResult for this specific text if there are multiple?
-                            // But res corresponds to all of them?
-                            // If res.success is true, it means at least one matched.
-                            // But usually if success is true, unencrypted_text contains ONLY the matched ones.
-                            // So we can just use res.
-                            
-                            // Wait, we need to push res to path.
-                            path.push(res.clone());
-
-                            // Calculate popularity bonus
-                            let popularity = if let Some(decoder_box) = DECODER_MAP.get(res.decoder) {
-                                decoder_box.get().get_popularity()
-                            } else {
-                                0.5
-                            };
+                        // Wait, we need to push res to path.
+                        push_path_step(&mut path, &res, &t.text(), config);
 
-                            // Create a special "result" node with a very low total_cost to ensure it's processed first
-                            // Add popularity bonus to break ties (higher popularity = lower cost)
-                            let result_node = AStarNode {
-                                state: DecoderResult {
-                                    text: vec![t.clone()],
-                                    path,
-                                },
-                                cost: current_node.cost + 1,
-                                heuristic: -1000.0, 
-                                total_cost: -1000.0 - (popularity * 10.0), // Higher popularity -> Lower cost
-                                next_decoder_name: Some("__RESULT__".to_string()), // Special marker
-                            };
+                        // Calculate popularity bonus
+                        let popularity = popularity(res.decoder);
 
-                            new_nodes.push(result_node);
-                        }
-                    }
-                }
-            }
-            MyResults::Continue(results) => {
-                // Process each result
-                for r in results {
-                    // Skip if stop signal is set
-                    if stop.load(AtomicOrdering::Relaxed) {
-                        break;
-                    }
-
-                    // Clone path to avoid modifying the original
-                    let decoders_used_base = current_node.state.path.clone();
-
-                    // Get decoded text candidates
-                    let texts = r.unencrypted_text.clone().unwrap_or_default();
-
-                    // Iterate over ALL candidates, not just the first one
-                    for text in texts {
-                        // Skip if text is empty
-                        if text.is_empty() {
-                            update_decoder_stats(r.decoder, false);
-                            continue;
-                        }
-
-                        // Check if string is worth being decoded
-                        // uses string heuristics. if heuristic is too low, it goes bye bye!
-                        if !calculate_string_worth(&text) {
-                            update_decoder_stats(r.decoder, false);
-                            continue;
-                        }
-
-                        // Check if the string cannot be decoded (aggressive pruning)
-                        if check_if_string_cant_be_decoded(&text) {
-                            update_decoder_stats(r.decoder, false);
-                            continue;
-                        }
-
-                        // Check if we've seen this string before to prevent cycles
-                        let text_hash = calculate_hash(&text);
-                        if !seen_strings.insert(text_hash) {
-                            update_decoder_stats(r.decoder, false);
-                            continue;
-                        }
-
-                        let mut decoders_used = decoders_used_base.clone();
-                        decoders_used.push(r.clone());
-
-                        // Create new node with updated cost and heuristic
-                        let cost = current_node.cost + 1;
-                        let heuristic = generate_heuristic(&text, &decoders_used, &None);
-                        let total_cost = cost as f32 + heuristic;
-
-                        let new_node = AStarNode {
+                        // Create a special "result" node with a very low total_cost to ensure it's processed first
+                        // Add popularity bonus to break ties (higher popularity = lower cost)
+                        let result_node = AStarNode {
                             state: DecoderResult {
-                                text: vec![text.clone()], // Store as vector with single element
-                                path: decoders_used,
+                                text: vec![t.clone()],
+                                path,
+                                confidence: 0.0,
                             },
-                            cost,
-                            heuristic,
-                            total_cost,
-                            next_decoder_name: Some(r.decoder.to_string()),
+                            cost: current_node.cost + 1,
+                            heuristic: -1000.0, 
+                            total_cost: -1000.0 - (popularity * 10.0), // Higher popularity -> Lower cost
+                            next_decoder_name: Some("__RESULT__".to_string()), // Special marker
                         };
 
-                        // Add to new nodes
-                        new_nodes.push(new_node);
-
-                        // Update decoder stats - mark as successful since it produced valid output
-                        update_decoder_stats(r.decoder, true);
+                        new_nodes.push(result_node);
                     }
                 }
             }
@@ -375,10 +353,17 @@ fn expand_node(
         // This part remains similar to the original implementation
         // but adapted to return nodes instead of adding them to open_set
 
-        // Get all decoders
-        let all_decoders = get_all_decoders();
+        // Get all decoders which can read the text, the ones for what it
+        // looks like first
+        let mut all_decoders = get_all_decoders();
+        all_decoders.retain_readable(&current_node.state.text[0]);
+        if let Some(filter) = &filter {
+            all_decoders.restrict(filter);
+        }
+        all_decoders.order_by_identification(&current_node.state.text[0].text(), config);
 
-        // Process each decoder
+        // Run each decoder, keeping the results with any candidates
+        let mut results = Vec::new();
         for decoder in all_decoders.components {
             // Skip if stop signal is set
             if stop.load(AtomicOrdering::Relaxed) {
@@ -398,59 +383,109 @@ fn expand_node(
             }
 
             // Run the decoder
-            let result = decoder.crack(&current_node.state.text[0], checker, config);
-
-            // Process the result
-            if let Some(decoded_text) = &result.unencrypted_text {
-                if let Some(first_text) = decoded_text.first() {
-                    // Skip if text is empty
-                    if first_text.is_empty() {
-                        update_decoder_stats(decoder.get_name(), false);
-                        continue;
-                    }
+            let result = crack_in_span(decoder, &current_node.state.text[0], checker, config);
+            let produced_output = result
+                .unencrypted_text
+                .as_ref()
+                .is_some_and(|texts| !texts.is_empty());
+            events::emit(|| SearchEvent::DecoderRan {
+                decoder: decoder.get_name().to_string(),
+                produced_output,
+            });
+
+            if produced_output {
+                results.push(result);
+            } else {
+                update_decoder_stats(decoder.get_name(), false);
+            }
+        }
 
-                    // Check if the string cannot be decoded (aggressive pruning)
-                    if check_if_string_cant_be_decoded(first_text) {
-                        update_decoder_stats(decoder.get_name(), false);
-                        continue;
-                    }
+        // Text several decoders agree on is only followed once. A plaintext
+        // a cipher checked itself is kept over the same text unchecked.
+        results.sort_by_key(|result| !result.success);
+        dedup_candidates(&mut results);
+
+        // Process each result
+        for result in &results {
+            let candidates = candidates_to_follow(result);
+            // Ciphers like XOR check their own candidates, so a success is
+            // already the plaintext and only it is followed
+            let candidates = if result.success {
+                &candidates[..1]
+            } else {
+                candidates
+            };
+            let ranked = result.candidate_scores.is_some();
+            for (rank, text) in candidates.iter().enumerate() {
+                // Skip if text is empty
+                if text.is_empty() {
+                    update_decoder_stats(result.decoder, false);
+                    continue;
+                }
 
-                    // Check if we've seen this string before
-                    let text_hash = calculate_hash(first_text);
-                    if !seen_strings.insert(text_hash) {
-                        update_decoder_stats(decoder.get_name(), false);
-                        continue;
-                    }
+                // Check if the string cannot be decoded (aggressive pruning)
+                let view = text.text();
+                if check_if_string_cant_be_decoded(&view) {
+                    update_decoder_stats(result.decoder, false);
+                    continue;
+                }
 
-                    // Create decoder result
-                    let mut decoders_used = current_node.state.path.clone();
-                    decoders_used.push(result.clone());
+                // Check if we've seen this string before
+                let text_hash = calculate_hash(text);
+                if !seen_strings.insert(text_hash) {
+                    stats::cache_hit(config);
+                    update_decoder_stats(result.decoder, false);
+                    continue;
+                }
 
-                    // Create new node
-                    let cost = current_node.cost + 1;
-                    let heuristic = generate_heuristic(first_text, &decoders_used, &None);
-                    let total_cost = cost as f32 + heuristic;
+                // Create decoder result
+                let mut decoders_used = current_node.state.path.clone();
+                push_path_step(&mut decoders_used, result, &view, config);
 
-                    let new_node = AStarNode {
+                if result.success {
+                    new_nodes.push(AStarNode {
                         state: DecoderResult {
-                            text: decoded_text.clone(),
+                            text: vec![text.clone()],
                             path: decoders_used,
+                            confidence: 0.0,
                         },
-                        cost,
-                        heuristic,
-                        total_cost,
-                        next_decoder_name: Some(decoder.get_name().to_string()),
-                    };
-
-                    // Add to new nodes
-                    new_nodes.push(new_node);
-
-                    // Update decoder stats
-                    update_decoder_stats(decoder.get_name(), true);
+                        cost: current_node.cost + 1,
+                        heuristic: -1000.0,
+                        total_cost: -1000.0 - (popularity(result.decoder) * 10.0),
+                        next_decoder_name: Some("__RESULT__".to_string()),
+                    });
+                    update_decoder_stats(result.decoder, true);
+                    continue;
                 }
-            } else {
-                // Update decoder stats for failed decoding
-                update_decoder_stats(decoder.get_name(), false);
+
+                // Create new node
+                let cost = current_node.cost + 1;
+                let heuristic = generate_heuristic(&view, &decoders_used, &None);
+                // Ranked candidates are expanded in order, best first
+                let rank_cost = if ranked {
+                    rank as f32 * RANKED_CANDIDATE_COST
+                } else {
+                    0.0
+                };
+                let total_cost = cost as f32 + heuristic + rank_cost;
+
+                let new_node = AStarNode {
+                    state: DecoderResult {
+                        text: vec![text.clone()], // Store as vector with single element
+                        path: decoders_used,
+                        confidence: 0.0,
+                    },
+                    cost,
+                    heuristic,
+                    total_cost,
+                    next_decoder_name: Some(result.decoder.to_string()),
+                };
+
+                // Add to new nodes
+                new_nodes.push(new_node);
+
+                // Update decoder stats
+                update_decoder_stats(result.decoder, true);
             }
         }
     }
@@ -488,18 +523,20 @@ fn expand_node(
 /// - `input`: The initial text to decode
 /// - `result_sender`: Channel to send the result when found
 /// - `stop`: Atomic boolean to signal when to stop the search
+/// - `config`: Its `deadline`, if any, says when to give up
 pub fn astar(
-    input: String,
+    input: Candidate,
     result_sender: Sender<Option<DecoderResult>>,
     stop: Arc<AtomicBool>,
     config: Arc<Config>,
 ) {
     // Calculate heuristic before moving input
-    let initial_heuristic = generate_heuristic(&input, &[], &None);
+    let initial_heuristic = generate_heuristic(&input.text(), &[], &None);
 
     let initial = DecoderResult {
         text: vec![input],
         path: vec![],
+        confidence: 0.0,
     };
 
     // Thread-safe set to track visited states to prevent cycles
@@ -528,11 +565,16 @@ pub fn astar(
 
     // Main A* loop
     while !open_set.is_empty() && !stop.load(AtomicOrdering::Relaxed) {
+        if deadline::expired(config.deadline.as_ref()) {
+            stop.store(true, AtomicOrdering::Relaxed);
+            break;
+        }
         trace!(
             "Current depth is {:?}, open set size: {}",
             curr_depth.load(AtomicOrdering::Relaxed),
             open_set.len()
         );
+        stats::frontier(&config, open_set.len());
 
         // Extract a batch of nodes to process in parallel
         let batch_size = std::cmp::min(PARALLEL_BATCH_SIZE, open_set.len());
@@ -544,14 +586,24 @@ pub fn astar(
         let new_nodes: Vec<AStarNode> = batch
             .par_iter()
             .flat_map(|node| {
-                expand_node(
+                let span = debug_span!(
+                    "search_node",
+                    depth = node.cost,
+                    decoder = node.next_decoder_name.as_deref().unwrap_or("any"),
+                    duration_us = tracing::field::Empty
+                )
+                .entered();
+                let start = Instant::now();
+                let nodes = expand_node(
                     node,
                     &seen_strings,
                     &stop,
                     prune_threshold.load(AtomicOrdering::Relaxed),
                     &config,
                     &checker,
-                )
+                );
+                span.record("duration_us", start.elapsed().as_micros() as u64);
+                nodes
             })
             .collect();
 
@@ -572,7 +624,23 @@ pub fn astar(
                     }
 
                     debug!("DEBUG: Found result node with text: {:?}", node.state.text);
+                    events::emit(|| SearchEvent::PlaintextFound {
+                        text: node
+                            .state
+                            .text
+                            .first()
+                            .map(ToString::to_string)
+                            .unwrap_or_default(),
+                        path: decoder_names(&node.state),
+                        checker: node
+                            .state
+                            .path
+                            .last()
+                            .map(|step| step.checker_name.to_string())
+                            .unwrap_or_default(),
+                    });
                     // Found a result node
+                    #[cfg(feature = "cli")]
                     decoded_how_many_times(curr_depth.load(AtomicOrdering::Relaxed), &config);
 
                     cli_pretty_printing::success(&format!(
@@ -604,14 +672,15 @@ pub fn astar(
 
                             // Only store results that have a valid checker name
                             if !checker_name.is_empty() && checker_name != "Unknown" {
-                                log::trace!(
+                                trace!(
                                     "Storing plaintext in WaitAthena storage: {} (decoder: {}, checker: {})",
                                     plaintext,
                                     decoder_name,
                                     checker_name
                                 );
                                 wait_athena_storage::add_plaintext_result(
-                                    plaintext.clone(),
+                                    &config,
+                                    plaintext.to_string(),
                                     format!(
                                         "Decoded successfully at depth {}",
                                         curr_depth.load(AtomicOrdering::Relaxed)
@@ -691,7 +760,7 @@ mod tests {
         let config = Config::default();
 
         // Run A* with empty input
-        astar("".to_string(), sender, stop, config.into());
+        astar("".into(), sender, stop, config.into());
 
         // Should receive None since there's nothing to decode
         let result = receiver.recv().unwrap();
@@ -705,7 +774,7 @@ mod tests {
         let config = Config::default();
 
         // Run A* with input that could cause cycles
-        astar("AAAA".to_string(), sender, stop, config.into());
+        astar("AAAA".into(), sender, stop, config.into());
 
         // Should eventually complete without hanging
         let _ = receiver.recv().unwrap();
@@ -721,7 +790,7 @@ mod tests {
         let config = Config::default();
 
         // Run A* in a separate thread with Base64 encoded "Hello World"
-        let input = "SGVsbG8gV29ybGQ=".to_string();
+        let input = "SGVsbG8gV29ybGQ=".into();
         let stop_clone = stop.clone();
 
         std::thread::spawn(move || {
@@ -737,4 +806,49 @@ mod tests {
             assert!(!decoder_result.path.is_empty());
         }
     }
+
+    #[test]
+    #[cfg(feature = "classical")]
+    fn only_the_best_ranked_candidates_become_nodes() {
+        let config = Config {
+            decoders_only: vec!["caesar".to_string()],
+            ..Default::default()
+        };
+        let root = AStarNode {
+            state: DecoderResult::_new("qxjvwzpkqlxmvbzrtyqwplkjhgfdsazxcvbnm"),
+            cost: 0,
+            heuristic: 0.0,
+            total_cost: 0.0,
+            next_decoder_name: None,
+        };
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let stop = Arc::new(AtomicBool::new(false));
+        let nodes = expand_node(&root, &DashSet::new(), &stop, 0, &config, &checker);
+
+        // Caesar gives 25 shifts, ranked by how much they read like English
+        assert_eq!(nodes.len(), crate::searchers::helper_functions::MAX_RANKED_CANDIDATES);
+        assert!(nodes
+            .iter()
+            .all(|node| node.next_decoder_name.as_deref() == Some("caesar")));
+    }
+
+    #[test]
+    fn astar_never_uses_excluded_decoders() {
+        let (sender, receiver) = bounded::<Option<DecoderResult>>(1);
+        let stop = Arc::new(AtomicBool::new(false));
+        // Only the quick encodings, so the search soon runs out of nodes
+        let config = Config {
+            decoders_only: vec!["decoder".to_string()],
+            decoders_exclude: vec!["Base64".to_string()],
+            deadline: Some(crate::deadline::Deadline::after_secs(5)),
+            ..Default::default()
+        };
+
+        // "hello there general kenobi" in Base64
+        astar("aGVsbG8gdGhlcmUgZ2VuZXJhbCBrZW5vYmk=".into(), sender, stop, config.into());
+
+        if let Ok(Some(result)) = receiver.try_recv() {
+            assert!(result.path.iter().all(|step| step.decoder != "Base64"));
+        }
+    }
 }
@@ -1,5 +1,8 @@
+use crate::byte_text::Candidate;
+#[cfg(feature = "cli")]
 use crate::cli_pretty_printing::decoded_how_many_times;
-use crate::filtration_system::MyResults;
+use crate::filtration_system::{dedup_candidates, MyResults};
+use crate::searchers::helper_functions::{candidates_to_follow, push_path_step};
 use crossbeam::channel::Sender;
 
 use log::trace;
@@ -12,10 +15,11 @@ use crate::DecoderResult;
 /// Breadth first search is our search algorithm
 /// https://en.wikipedia.org/wiki/Breadth-first_search
 #[allow(dead_code)]
-pub fn bfs(input: String, result_sender: Sender<Option<DecoderResult>>, stop: Arc<AtomicBool>, config: Arc<crate::config::Config>) {
+pub fn bfs(input: Candidate, result_sender: Sender<Option<DecoderResult>>, stop: Arc<AtomicBool>, config: Arc<crate::config::Config>) {
     let initial = DecoderResult {
         text: vec![input],
         path: vec![],
+        confidence: 0.0,
     };
     let mut seen_strings = HashSet::new();
     // all strings to search through
@@ -40,12 +44,15 @@ pub fn bfs(input: String, result_sender: Sender<Option<DecoderResult>>, stop: Ar
                     if let Some(res) = results.first() {
                         let mut decoders_used = current_string.path;
                         let text = res.unencrypted_text.clone().unwrap_or_default();
-                        decoders_used.push(res.clone());
+                        let plaintext = text.first().map(|text| text.text()).unwrap_or_default();
+                        push_path_step(&mut decoders_used, res, &plaintext, &config);
                         let result_text = DecoderResult {
                             text,
                             path: decoders_used,
+                            confidence: 0.0,
                         };
 
+                        #[cfg(feature = "cli")]
                         decoded_how_many_times(curr_depth, &config);
                         result_sender
                             .send(Some(result_text))
@@ -59,21 +66,25 @@ pub fn bfs(input: String, result_sender: Sender<Option<DecoderResult>>, stop: Ar
                         Some(())
                     }
                 }
-                MyResults::Continue(results_vec) => {
-                    new_strings.extend(results_vec.into_iter().flat_map(|mut r| {
+                MyResults::Continue(_) => {
+                    // Only successes come back from running the decoders, so
+                    // follow what each of them gave instead
+                    let mut results_vec = super::decode_all(&current_string, &config);
+                    dedup_candidates(&mut results_vec);
+                    new_strings.extend(results_vec.into_iter().flat_map(|r| {
                         let mut decoders_used = current_string.path.clone();
-                        // text is a vector of strings
-                        let mut text = r.unencrypted_text.take().unwrap_or_default();
+                        // text is a vector of strings, only the best few if they're ranked
+                        let mut text = candidates_to_follow(&r).to_vec();
 
                         text.retain(|s| {
-                            !check_if_string_cant_be_decoded(s) && seen_strings.insert(s.clone())
+                            !check_if_string_cant_be_decoded(&s.text()) && seen_strings.insert(s.content().clone())
                         });
 
                         if text.is_empty() {
                             return None;
                         }
 
-                        decoders_used.push(r);
+                        push_path_step(&mut decoders_used, &r, &text[0].text(), &config);
                         Some(DecoderResult {
                             // and this is a vector of strings
                             // TODO we should probably loop through all `text` and create Text structs for each one
@@ -82,6 +93,7 @@ pub fn bfs(input: String, result_sender: Sender<Option<DecoderResult>>, stop: Ar
                             // and just create more of them....
                             text,
                             path: decoders_used.to_vec(),
+                            confidence: 0.0,
                         })
                     }));
                     Some(()) // indicate we want to continue processing
@@ -0,0 +1,140 @@
+//! Progress events emitted while searching for the plaintext
+//!
+//! Front-ends such as the TUI call [`subscribe`] before starting a search and
+//! receive a stream of [`SearchEvent`]s describing what the searcher is doing.
+//! When nobody is subscribed, emitting an event is a single atomic load, so the
+//! searcher pays nothing for it.
+
+use super::stats::SearchStats;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Something that happened during a search
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchEvent {
+    /// A search has started
+    Started {
+        /// The text being cracked
+        input: String,
+        /// How long the search may run for, in seconds
+        timeout: u32,
+    },
+    /// A node is being expanded, i.e. every decoder is about to be run on its text
+    NodeExpanded {
+        /// Number of decoders applied to reach this node
+        depth: u32,
+        /// Names of the decoders applied to reach this node
+        path: Vec<String>,
+        /// The text at this node
+        text: String,
+        /// How English-like the text is, see [`crate::cryptanalysis::fitness_score`]
+        fitness: f64,
+    },
+    /// A decoder was run on a node
+    DecoderRan {
+        /// Name of the decoder
+        decoder: String,
+        /// Whether the decoder produced any new text
+        produced_output: bool,
+    },
+    /// A checker identified a plaintext
+    PlaintextFound {
+        /// The plaintext
+        text: String,
+        /// Names of the decoders used to reach it
+        path: Vec<String>,
+        /// Name of the checker which identified it
+        checker: String,
+    },
+    /// The search has finished
+    Finished {
+        /// Whether a plaintext was found
+        success: bool,
+        /// What the search did
+        stats: SearchStats,
+    },
+}
+
+/// Senders for every current subscriber
+static SUBSCRIBERS: Mutex<Vec<Sender<SearchEvent>>> = Mutex::new(Vec::new());
+/// Fast path so the searcher doesn't lock the mutex when nobody is listening
+static HAS_SUBSCRIBERS: AtomicBool = AtomicBool::new(false);
+
+/// Subscribes to search events.
+/// Events are delivered until the receiver is dropped or [`unsubscribe_all`] is called.
+///
+/// # Panics
+/// Panics if the subscriber list mutex is poisoned.
+pub fn subscribe() -> Receiver<SearchEvent> {
+    let (sender, receiver) = unbounded();
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.push(sender);
+    HAS_SUBSCRIBERS.store(true, Ordering::Release);
+    receiver
+}
+
+/// Removes every subscriber
+///
+/// # Panics
+/// Panics if the subscriber list mutex is poisoned.
+pub fn unsubscribe_all() {
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.clear();
+    HAS_SUBSCRIBERS.store(false, Ordering::Release);
+}
+
+/// Returns true if anyone is listening, so callers can skip building expensive events
+pub(crate) fn has_subscribers() -> bool {
+    HAS_SUBSCRIBERS.load(Ordering::Acquire)
+}
+
+/// Sends an event to every subscriber.
+/// The event is only built if someone is listening.
+pub(crate) fn emit<F: FnOnce() -> SearchEvent>(make_event: F) {
+    if !has_subscribers() {
+        return;
+    }
+    let event = make_event();
+    let Ok(mut subscribers) = SUBSCRIBERS.lock() else {
+        return;
+    };
+    // Drop subscribers whose receiver has gone away
+    subscribers.retain(|sender| sender.send(event.clone()).is_ok());
+    if subscribers.is_empty() {
+        HAS_SUBSCRIBERS.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn subscribers_receive_events() {
+        let receiver = subscribe();
+        let finished = SearchEvent::Finished {
+            success: true,
+            stats: SearchStats::default(),
+        };
+        emit(|| finished.clone());
+        // Other tests may be searching in parallel, so look for our event among theirs
+        assert!(receiver.try_iter().any(|event| event == finished));
+        unsubscribe_all();
+        assert!(!has_subscribers());
+    }
+
+    #[test]
+    #[serial]
+    fn dropped_receivers_are_removed() {
+        unsubscribe_all();
+        drop(subscribe());
+        emit(|| SearchEvent::Finished {
+            success: false,
+            stats: SearchStats::default(),
+        });
+        assert!(!has_subscribers());
+    }
+}
@@ -3,6 +3,8 @@
 //! This module contains helper functions used by the A* search algorithm
 //! for decoding encrypted or encoded text.
 
+use crate::byte_text::Candidate;
+use crate::config::Config;
 use crate::decoders::interface::Crack;
 use crate::CrackResult;
 use once_cell::sync::Lazy;
@@ -31,6 +33,52 @@ pub fn update_decoder_stats(decoder: &str, success: bool) {
     // TODO: Write this data to a file for persistence
 }
 
+/// Adds a decoder's result to the end of a path.
+/// With `config.keep_intermediates` on, the step also keeps `text`,
+/// the candidate the path carries on with.
+/// A decoder which took several steps at once adds each of its inner steps.
+pub fn push_path_step(
+    path: &mut Vec<CrackResult>,
+    step: &CrackResult,
+    text: &str,
+    config: &Config,
+) {
+    if let Some((last, earlier)) = step.inner_steps.split_last() {
+        for inner in earlier {
+            let inner_text = inner
+                .unencrypted_text
+                .as_ref()
+                .and_then(|texts| texts.first())
+                .map(|candidate| candidate.text().into_owned())
+                .unwrap_or_default();
+            push_path_step(path, inner, &inner_text, config);
+        }
+        push_path_step(path, last, text, config);
+        return;
+    }
+    let mut step = step.clone();
+    if config.keep_intermediates {
+        step.intermediate_text = Some(text.into());
+    }
+    path.push(step);
+}
+
+/// The most candidates followed from a decoder which ranks its candidates,
+/// like the 255 keys XOR tries
+pub const MAX_RANKED_CANDIDATES: usize = 10;
+
+/// The candidates of a decoder's result the search should follow.
+/// Ranked candidates come best first and are cut to the best
+/// [`MAX_RANKED_CANDIDATES`], others are all followed.
+pub fn candidates_to_follow(result: &CrackResult) -> &[Candidate] {
+    let candidates = result.unencrypted_text.as_deref().unwrap_or_default();
+    if result.candidate_scores.is_some() {
+        &candidates[..candidates.len().min(MAX_RANKED_CANDIDATES)]
+    } else {
+        candidates
+    }
+}
+
 /// Get the success rate of a decoder
 ///
 /// # Arguments
@@ -135,16 +183,6 @@ pub fn calculate_string_quality(s: &str) -> f32 {
     }
 }
 
-/// Check if string is worth being decoded
-pub fn calculate_string_worth(s: &str) -> bool {
-    // check if string is less than 3 chars
-    if calculate_string_quality(s) < 0.2 {
-        return false;
-    }
-
-    true
-}
-
 /// Calculate the ratio of non-printable characters in a string
 /// Returns a value between 0.0 (all printable) and 1.0 (all non-printable)
 pub fn calculate_non_printable_ratio(text: &str) -> f32 {
@@ -269,6 +307,34 @@ mod tests {
     use super::*;
     use crate::Decoder;
 
+    #[test]
+    fn push_path_step_keeps_text_only_when_asked() {
+        let step = CrackResult::new(&Decoder::default(), "aGk=".to_string());
+        let mut path = vec![];
+        push_path_step(&mut path, &step, "hi", &Config::default());
+        assert_eq!(path[0].intermediate_text, None);
+
+        let config = Config {
+            keep_intermediates: true,
+            ..Default::default()
+        };
+        push_path_step(&mut path, &step, "hi", &config);
+        assert_eq!(path[1].intermediate_text.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn push_path_step_adds_inner_steps() {
+        let mut step = CrackResult::new(&Decoder::default(), "outer".to_string());
+        step.inner_steps = vec![
+            CrackResult::new(&Decoder::default(), "first".to_string()),
+            CrackResult::new(&Decoder::default(), "second".to_string()),
+        ];
+        let mut path = vec![];
+        push_path_step(&mut path, &step, "done", &Config::default());
+        let inputs: Vec<&str> = path.iter().map(|step| &*step.encrypted_text).collect();
+        assert_eq!(inputs, ["first", "second"]);
+    }
+
     #[test]
     fn test_generate_heuristic() {
         // Create some CrackResults for path testing
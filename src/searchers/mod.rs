@@ -5,22 +5,41 @@
 
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
 use std::thread;
 
+#[cfg(not(target_arch = "wasm32"))]
 use crossbeam::channel::bounded;
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::{ThreadPool, ThreadPoolBuilder};
 
+use crate::byte_text::Candidate;
 use crate::checkers::athena::Athena;
 use crate::checkers::checker_type::{Check, Checker};
 use crate::checkers::CheckerTypes;
 use crate::config::Config;
-use crate::filtration_system::{filter_and_get_decoders, MyResults};
-use crate::{timer, DecoderResult};
+use crate::deadline::Deadline;
+use crate::decoders::crack_results::CrackResult;
+use crate::filtration_system::{crack_in_span, filter_and_get_decoders, MyResults};
+use crate::error::AresError;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "cli")]
+use crate::cli_pretty_printing::{countdown_until_program_ends, top_result_found};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::storage::wait_athena_storage::normalize_for_dedup;
+#[cfg(all(feature = "cli", not(target_arch = "wasm32")))]
+use crate::timer;
+use crate::DecoderResult;
 /// This module provides access to the A* search algorithm
 /// which uses a heuristic to prioritize decoders.
 mod astar;
 /// This module provides access to the breadth first search
 /// which searches for the plaintext.
 mod bfs;
+/// Progress events front-ends can subscribe to while a search runs
+pub mod events;
+/// Counts of what a search did, for reasoning about why a run was slow
+pub mod stats;
 /// This module contains helper functions used by the A* search algorithm.
 mod helper_functions;
 
@@ -41,9 +60,67 @@ mod helper_functions;
 ///    We can return an Option? An Enum? And then match on that
 ///    So if we return CrackSuccess we return
 ///    Else if we return an array, we add it to the children and go again.
-pub fn search_for_plaintext(input: String, config: Arc<Config>) -> Option<DecoderResult> {
-    let timeout = config.timeout;
-    let timer = timer::start(timeout, config.clone());
+///
+/// The search gives up early if `cancel` is set.
+///
+/// # Errors
+/// Returns [`AresError::Timeout`], [`AresError::NoDecodingFound`] or
+/// [`AresError::Cancelled`] if no plaintext was found.
+pub fn search_for_plaintext(
+    input: Candidate,
+    config: Arc<Config>,
+    cancel: Option<&AtomicBool>,
+) -> Result<DecoderResult, AresError> {
+    events::emit(|| events::SearchEvent::Started {
+        input: input.text().into_owned(),
+        timeout: config.timeout,
+    });
+    let config = with_stats(config);
+    let stats = config.stats.clone().unwrap_or_default();
+    let result = search_until_timeout(input, config, cancel);
+    events::emit(|| events::SearchEvent::Finished {
+        success: result.is_ok(),
+        stats: stats.snapshot(),
+    });
+    result
+}
+
+/// The config counting into a new [`stats::StatsCounter`], unless the
+/// caller gave it one
+fn with_stats(config: Arc<Config>) -> Arc<Config> {
+    if config.stats.is_some() {
+        return config;
+    }
+    let mut config = (*config).clone();
+    config.stats = Some(stats::StatsCounter::default());
+    Arc::new(config)
+}
+
+/// The config with a deadline `config.timeout` from now, unless the caller
+/// gave it one
+fn with_deadline(config: Arc<Config>) -> (Arc<Config>, Deadline) {
+    if let Some(deadline) = &config.deadline {
+        return (config.clone(), deadline.clone());
+    }
+    let deadline = Deadline::after_secs(config.timeout);
+    let mut config = (*config).clone();
+    config.deadline = Some(deadline.clone());
+    (Arc::new(config), deadline)
+}
+
+/// Runs the A* search in a background thread until it returns, the deadline
+/// passes or it is cancelled
+#[cfg(not(target_arch = "wasm32"))]
+fn search_until_timeout(
+    input: Candidate,
+    config: Arc<Config>,
+    cancel: Option<&AtomicBool>,
+) -> Result<DecoderResult, AresError> {
+    let (config, deadline) = with_deadline(config);
+    #[cfg(feature = "cli")]
+    let timeout = u32::try_from(deadline.budget().as_secs()).unwrap_or(u32::MAX);
+    #[cfg(feature = "cli")]
+    let mut seconds_shown = 0;
 
     let (result_sender, result_recv) = bounded::<Option<DecoderResult>>(1);
     // For stopping the thread
@@ -52,7 +129,12 @@ pub fn search_for_plaintext(input: String, config: Arc<Config>) -> Option<Decode
     
     // Use A* search algorithm instead of BFS
     let config_clone = config.clone();
-    let handle = thread::spawn(move || astar::astar(input, result_sender, s, config_clone));
+    let pool = build_thread_pool(config.threads);
+    let handle = thread::spawn(move || match pool {
+        // Decoders run with par_iter, which uses whichever pool it is installed in
+        Some(pool) => pool.install(|| astar::astar(input, result_sender, s, config_clone)),
+        None => astar::astar(input, result_sender, s, config_clone),
+    });
 
     // In top_results mode, we don't need to return a result immediately
     // as the timer will display all results when it expires
@@ -61,39 +143,79 @@ pub fn search_for_plaintext(input: String, config: Arc<Config>) -> Option<Decode
     // If we're in top_results mode, we'll store the first result to return
     // at the end of the timer
     let mut first_result = None;
+    // The different plaintexts found so far, for `config.max_results`
+    let mut plaintexts_found = std::collections::HashSet::new();
 
     loop {
         if let Ok(res) = result_recv.try_recv() {
             log::info!("Found potential plaintext result");
             log::trace!("Result details: {:?}", res);
 
-            // In top_results mode, we store the first result but don't stop the search
+            // In top_results mode, we show each result as it comes and store
+            // the first, but don't stop the search
             if top_results_mode {
+                if let Some(found) = &res {
+                    #[cfg(feature = "cli")]
+                    top_result_found(found, deadline.elapsed(), &config);
+                    if let Some(plaintext) = found.text.first() {
+                        plaintexts_found.insert(normalize_for_dedup(&plaintext.text()));
+                    }
+                }
                 if first_result.is_none() {
                     first_result = res;
                 }
+                if config.max_results.is_some_and(|max| plaintexts_found.len() >= max) {
+                    stop.store(true, std::sync::atomic::Ordering::Relaxed);
+                    log::info!("Found {} plaintexts, stopping the search", plaintexts_found.len());
+                    handle.join().unwrap();
+                    #[cfg(feature = "cli")]
+                    timer::display_results(&config);
+                    return first_result.ok_or(AresError::NoDecodingFound);
+                }
                 // Continue searching for more results
             } else {
                 // In normal mode, we stop the search and return the result
                 stop.store(true, std::sync::atomic::Ordering::Relaxed);
                 // Wait for the thread to finish
                 handle.join().unwrap();
-                return res;
+                // The search only gives up without a result once it has run out of nodes
+                return res.ok_or(AresError::NoDecodingFound);
+            }
+        }
+
+        #[cfg(feature = "cli")]
+        {
+            let seconds = u32::try_from(deadline.elapsed().as_secs()).unwrap_or(u32::MAX);
+            if seconds > seconds_shown {
+                seconds_shown = seconds;
+                countdown_until_program_ends(seconds, timeout, &config);
             }
         }
 
-        if timer.try_recv().is_ok() {
+        let cancelled = cancel_requested(cancel) || deadline.is_cancelled();
+        if deadline.expired() && !cancelled {
             stop.store(true, std::sync::atomic::Ordering::Relaxed);
-            log::info!("Search timer expired");
+            log::info!("Search deadline passed");
             // Wait for the thread to finish to ensure any ongoing human checker interaction completes
             handle.join().unwrap();
 
-            // In top_results mode, return the first result we found (if any)
+            // In top_results mode, show every result and return the first one we found (if any)
             if top_results_mode {
-                return first_result;
+                #[cfg(feature = "cli")]
+                timer::display_results(&config);
+                return first_result.ok_or(AresError::Timeout);
             }
 
-            return None;
+            return Err(AresError::Timeout);
+        }
+
+        if cancelled {
+            // Decoders checking the deadline stop too
+            deadline.cancel();
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+            log::info!("Search cancelled");
+            handle.join().unwrap();
+            return first_result.ok_or(AresError::Cancelled);
         }
 
         // Small sleep to prevent CPU spinning
@@ -101,6 +223,51 @@ pub fn search_for_plaintext(input: String, config: Arc<Config>) -> Option<Decode
     }
 }
 
+/// Whether the caller asked for the search to stop
+#[cfg(not(target_arch = "wasm32"))]
+fn cancel_requested(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|cancel| cancel.load(std::sync::atomic::Ordering::Relaxed))
+}
+
+/// Runs the A* search on the calling thread until it returns or the deadline passes.
+/// Browsers can't spawn threads, so nothing else can stop it and there is no cancelling.
+#[cfg(target_arch = "wasm32")]
+fn search_until_timeout(
+    input: Candidate,
+    config: Arc<Config>,
+    _cancel: Option<&AtomicBool>,
+) -> Result<DecoderResult, AresError> {
+    let (config, deadline) = with_deadline(config);
+    // Unbounded, as nothing reads the results until the search returns
+    let (result_sender, result_recv) = crossbeam::channel::unbounded();
+    let stop = Arc::new(AtomicBool::new(false));
+    astar::astar(input, result_sender, stop, config);
+    match result_recv.try_iter().flatten().next() {
+        Some(result) => Ok(result),
+        None if deadline.expired() => Err(AresError::Timeout),
+        None => Err(AresError::NoDecodingFound),
+    }
+}
+
+/// Builds a rayon pool with the configured number of threads.
+/// Returns None to use the global pool when no thread count is set,
+/// or if the pool cannot be built.
+#[cfg(not(target_arch = "wasm32"))]
+fn build_thread_pool(threads: Option<usize>) -> Option<ThreadPool> {
+    let threads = threads.filter(|threads| *threads > 0)?;
+    match ThreadPoolBuilder::new().num_threads(threads).build() {
+        Ok(pool) => Some(pool),
+        Err(e) => {
+            log::warn!(
+                "Could not build a pool of {} threads, using the default: {}",
+                threads,
+                e
+            );
+            None
+        }
+    }
+}
+
 /// Performs the decodings by getting all of the decoders
 /// and calling `.run` which in turn loops through them and calls
 /// `.crack()`.
@@ -112,10 +279,36 @@ fn perform_decoding(text: &DecoderResult, config: &Config) -> MyResults {
     decoders.run(&text.text[0], &checker, config)
 }
 
+/// Runs every decoder which can read the text, giving the results with any
+/// candidates. [`Decoders::run`] only gives back successes, so this is what
+/// is followed when none succeeded.
+///
+/// [`Decoders::run`]: crate::filtration_system::Decoders::run
+#[allow(dead_code)]
+fn decode_all(text: &DecoderResult, config: &Config) -> Vec<CrackResult> {
+    let mut decoders = filter_and_get_decoders(text);
+    decoders.retain_readable(&text.text[0]);
+    let athena_checker = Checker::<Athena>::new();
+    let checker = CheckerTypes::CheckAthena(athena_checker);
+    decoders
+        .components
+        .iter()
+        .map(|decoder| crack_in_span(*decoder, &text.text[0], &checker, config))
+        .filter(|result| result.unencrypted_text.as_ref().is_some_and(|texts| !texts.is_empty()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn thread_pool_uses_configured_size() {
+        assert!(build_thread_pool(None).is_none());
+        assert!(build_thread_pool(Some(0)).is_none());
+        assert_eq!(build_thread_pool(Some(2)).unwrap().current_num_threads(), 2);
+    }
+
     // https://github.com/bee-san/ciphey/pull/14/files#diff-b8829c7e292562666c7fa5934de7b478c4a5de46d92e42c46215ac4d9ff89db2R37
     // Only used for tests!
     fn exit_condition(input: &str, config: &Config) -> bool {
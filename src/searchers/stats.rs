@@ -0,0 +1,145 @@
+//! How much work a search did, for reasoning about why a run was slow
+//!
+//! A [`StatsCounter`] travels with the search in
+//! [`Config::stats`](crate::config::Config::stats) like the deadline does,
+//! so the searcher, the filtration system, the checkers and the cache can all
+//! count what they do. Its [`snapshot`](StatsCounter::snapshot) is what is
+//! printed in verbose mode and sent with [`super::events::SearchEvent::Finished`].
+
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// What a search did, counted over the whole run
+///
+/// ```
+/// use ares::config::Config;
+/// use ares::perform_cracking;
+/// use ares::stats::StatsCounter;
+/// # let _test_db = ares::TestDatabase::default();
+/// # ares::set_test_db_path();
+///
+/// let stats = StatsCounter::default();
+/// let config = Config { human_checker_on: false, stats: Some(stats.clone()), ..Default::default() };
+/// perform_cracking("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config).unwrap();
+/// assert!(stats.snapshot().checker_calls > 0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchStats {
+    /// Nodes whose text had every decoder run on it
+    pub nodes_expanded: u64,
+    /// Times a decoder was run on a text
+    pub decoders_run: u64,
+    /// Times a checker was asked whether a text is the plaintext
+    pub checker_calls: u64,
+    /// Work skipped because it was done before: texts the search had already
+    /// seen, and inputs whose plaintext was in the cache
+    pub cache_hits: u64,
+    /// The most nodes waiting to be expanded at once
+    pub peak_frontier: u64,
+}
+
+/// Counts a search's [`SearchStats`], shared between threads. Clones share
+/// the same counts.
+#[derive(Debug, Clone, Default)]
+pub struct StatsCounter {
+    /// The counts, shared by every clone
+    inner: Arc<Counts>,
+}
+
+/// The shared state of a [`StatsCounter`]
+#[derive(Debug, Default)]
+struct Counts {
+    /// See [`SearchStats::nodes_expanded`]
+    nodes_expanded: AtomicU64,
+    /// See [`SearchStats::decoders_run`]
+    decoders_run: AtomicU64,
+    /// See [`SearchStats::checker_calls`]
+    checker_calls: AtomicU64,
+    /// See [`SearchStats::cache_hits`]
+    cache_hits: AtomicU64,
+    /// See [`SearchStats::peak_frontier`]
+    peak_frontier: AtomicU64,
+}
+
+impl StatsCounter {
+    /// The counts so far
+    pub fn snapshot(&self) -> SearchStats {
+        let counts = &self.inner;
+        SearchStats {
+            nodes_expanded: counts.nodes_expanded.load(Ordering::Relaxed),
+            decoders_run: counts.decoders_run.load(Ordering::Relaxed),
+            checker_calls: counts.checker_calls.load(Ordering::Relaxed),
+            cache_hits: counts.cache_hits.load(Ordering::Relaxed),
+            peak_frontier: counts.peak_frontier.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Counts a node being expanded, if the config is counting
+pub(crate) fn node_expanded(config: &Config) {
+    add(config, |counts| &counts.nodes_expanded);
+}
+
+/// Counts a decoder being run, if the config is counting
+pub(crate) fn decoder_ran(config: &Config) {
+    add(config, |counts| &counts.decoders_run);
+}
+
+/// Counts a checker being asked about a text, if the config is counting
+pub(crate) fn checker_called(config: &Config) {
+    add(config, |counts| &counts.checker_calls);
+}
+
+/// Counts work skipped because it was done before, if the config is counting
+pub(crate) fn cache_hit(config: &Config) {
+    add(config, |counts| &counts.cache_hits);
+}
+
+/// Records how many nodes are waiting to be expanded, if the config is counting
+pub(crate) fn frontier(config: &Config, size: usize) {
+    if let Some(stats) = &config.stats {
+        let size = u64::try_from(size).unwrap_or(u64::MAX);
+        stats.inner.peak_frontier.fetch_max(size, Ordering::Relaxed);
+    }
+}
+
+/// Adds one to the count `field` picks, if the config is counting
+fn add(config: &Config, field: impl FnOnce(&Counts) -> &AtomicU64) {
+    if let Some(stats) = &config.stats {
+        field(&stats.inner).fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_their_counts() {
+        let stats = StatsCounter::default();
+        let config = Config {
+            stats: Some(stats.clone()),
+            ..Default::default()
+        };
+        node_expanded(&config);
+        decoder_ran(&config.clone());
+        decoder_ran(&config);
+        frontier(&config, 7);
+        frontier(&config, 3);
+        assert_eq!(
+            stats.snapshot(),
+            SearchStats {
+                nodes_expanded: 1,
+                decoders_run: 2,
+                checker_calls: 0,
+                cache_hits: 0,
+                peak_frontier: 7,
+            }
+        );
+        // Without a counter nothing is counted
+        node_expanded(&Config::default());
+        assert_eq!(stats.snapshot().nodes_expanded, 1);
+    }
+}
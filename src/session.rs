@@ -0,0 +1,174 @@
+//! A cracking session which keeps its setup between cracks
+//!
+//! [`crate::perform_cracking`] sets up the database and opens a connection on
+//! every call, and the first call also pays for building the decoder map and
+//! loading the word lists and n-gram tables. Services cracking thousands of
+//! strings can create one [`AresSession`] instead, which does all of that once.
+
+use crate::checkers::athena::Athena;
+use crate::checkers::checker_type::{Check, Checker};
+#[cfg(feature = "cache")]
+use crate::config::CacheMode;
+use crate::config::Config;
+use crate::error::AresError;
+use crate::DecoderResult;
+#[cfg(feature = "cache")]
+use log::warn;
+use once_cell::sync::Lazy;
+#[cfg(feature = "cache")]
+use std::sync::{Mutex, MutexGuard};
+
+/// Locks the session's connection, recovering from a poisoned mutex
+#[cfg(feature = "cache")]
+pub(crate) fn lock(conn: &Mutex<rusqlite::Connection>) -> MutexGuard<'_, rusqlite::Connection> {
+    match conn.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            warn!("Mutex was poisoned, recovering");
+            poisoned.into_inner()
+        }
+    }
+}
+
+/// Owns a config and a database connection, and cracks any number of texts with them.
+/// Can be shared between threads, cracks from different threads share the connection.
+///
+/// ```rust
+/// use ares::config::Config;
+/// use ares::AresSession;
+/// # let _test_db = ares::TestDatabase::default();
+/// # ares::set_test_db_path();
+///
+/// let mut config = Config::default();
+/// config.human_checker_on = false;
+/// let session = AresSession::new(config);
+/// for text in ["aGVsbG8gdGhlcmUgZ2VuZXJhbA==", "68656c6c6f207468657265206b656e6f6269"] {
+///     let result = session.crack(text);
+///     assert!(result.is_some());
+/// }
+/// ```
+pub struct AresSession {
+    /// Config used for every crack
+    config: Config,
+    /// Connection to the cache database, or None if it could not be opened
+    #[cfg(feature = "cache")]
+    db: Option<Mutex<rusqlite::Connection>>,
+}
+
+impl AresSession {
+    /// Opens the database `config.database_path` names and loads everything
+    /// the search needs up front. Sessions with different database paths each
+    /// keep their own cache, unlike [`crate::perform_cracking`], which uses
+    /// the first path set up in the process.
+    #[cfg(feature = "cache")]
+    pub fn new(config: Config) -> Self {
+        if config.cache_mode == CacheMode::Off {
+            return Self::with_connection(config, None);
+        }
+        // The human checker's answers go to the process's database, so the
+        // first session's path becomes it when nothing else has set one up
+        if crate::storage::database::DB_PATH.get().is_none() {
+            if let Err(e) = crate::storage::database::setup_database(&config) {
+                warn!("SQLite database failed to initialize: {}", e);
+            }
+        }
+        let db = match Self::open_connection(&config) {
+            Ok(conn) => Some(Mutex::new(conn)),
+            Err(e) => {
+                warn!(
+                    "Could not open the database, every crack will open its own: {}",
+                    e
+                );
+                None
+            }
+        };
+        Self::with_connection(config, db)
+    }
+
+    /// Loads everything the search needs up front
+    #[cfg(not(feature = "cache"))]
+    pub fn new(config: Config) -> Self {
+        Self::warm_up();
+        AresSession { config }
+    }
+
+    /// Like [`AresSession::new`], but fails instead of carrying on without the cache
+    ///
+    /// # Errors
+    /// Returns [`AresError::DatabaseError`] if the database can't be set up or opened.
+    #[cfg(feature = "cache")]
+    pub fn try_new(config: Config) -> Result<Self, AresError> {
+        if config.cache_mode == CacheMode::Off {
+            return Ok(Self::with_connection(config, None));
+        }
+        if crate::storage::database::DB_PATH.get().is_none() {
+            crate::storage::database::setup_database(&config)?;
+        }
+        let conn = Self::open_connection(&config)?;
+        Ok(Self::with_connection(config, Some(Mutex::new(conn))))
+    }
+
+    /// Opens the config's database the way `config.cache_mode` allows
+    #[cfg(feature = "cache")]
+    fn open_connection(config: &Config) -> Result<rusqlite::Connection, rusqlite::Error> {
+        match config.cache_mode {
+            CacheMode::ReadOnly => crate::storage::database::open_read_only_connection_for(config),
+            _ => crate::storage::database::open_connection_for(config),
+        }
+    }
+
+    /// Like [`AresSession::new`]. Without the cache feature there is no database to fail.
+    ///
+    /// # Errors
+    /// Never returns an error without the cache feature.
+    #[cfg(not(feature = "cache"))]
+    pub fn try_new(config: Config) -> Result<Self, AresError> {
+        Ok(Self::new(config))
+    }
+
+    /// Loads everything the search needs, keeping the given connection
+    #[cfg(feature = "cache")]
+    fn with_connection(config: Config, db: Option<Mutex<rusqlite::Connection>>) -> Self {
+        Self::warm_up();
+        AresSession { config, db }
+    }
+
+    /// Builds the decoder map and loads the word lists and models
+    fn warm_up() {
+        Lazy::force(&crate::decoders::DECODER_MAP);
+        Lazy::force(&crate::checkers::CHECKER_MAP);
+        Lazy::force(&crate::cryptanalysis::COMMON_ENGLISH_SET);
+        Lazy::force(&crate::cryptanalysis::ENGLISH_BIGRAM_SCORES);
+        Lazy::force(&crate::cryptanalysis::ENGLISH_QUADGRAMS);
+        Lazy::force(&crate::storage::invisible_chars::INVISIBLE_CHARS);
+        // Running Athena once loads the checkers' own models and word lists
+        let warm_up_config = Config {
+            human_checker_on: false,
+            ..Config::default()
+        };
+        Checker::<Athena>::new().check("warming up the checkers", &warm_up_config);
+    }
+
+    /// The config every crack uses
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Cracks the text like [`crate::perform_cracking`], reusing the session's setup
+    pub fn crack(&self, text: &str) -> Option<DecoderResult> {
+        self.try_crack(text).ok()
+    }
+
+    /// Cracks the text like [`crate::try_perform_cracking`], reusing the session's setup
+    ///
+    /// # Errors
+    /// Returns the reason no plaintext was found, see [`AresError`].
+    pub fn try_crack(&self, text: &str) -> Result<DecoderResult, AresError> {
+        let ctx = crate::CrackContext {
+            #[cfg(feature = "cache")]
+            db: self.db.as_ref(),
+            cancel: None,
+        };
+        crate::crack_input(text.into(), self.config.clone(), ctx)
+    }
+}
@@ -0,0 +1,186 @@
+//! Binary blobs found while decoding
+//!
+//! Decoders which produce raw bytes (Base64, Hexadecimal, ...) only pass text on
+//! to the next step, so a ZIP or PNG hidden under an encoding is normally thrown
+//! away. When recording is enabled, those decoders hand their bytes to [`inspect`]
+//! first, and any blob whose magic bytes identify a known file type is kept so
+//! `--artifacts-dir` can save it and `-o` can write binary plaintexts byte for byte.
+
+use lazy_static::lazy_static;
+use log::{trace, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// A binary blob produced by a decoder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    /// Name of the decoder which produced the bytes
+    pub decoder_name: String,
+    /// File extension matching the magic bytes, e.g. `zip`
+    pub extension: &'static str,
+    /// The raw bytes
+    pub bytes: Vec<u8>,
+}
+
+/// Magic bytes at the start of common file types, with their extension
+const SIGNATURES: [(&[u8], &str); 10] = [
+    (b"PK\x03\x04", "zip"),
+    (b"\x1f\x8b", "gz"),
+    (b"BZh", "bz2"),
+    (b"\xfd7zXZ\x00", "xz"),
+    (b"7z\xbc\xaf\x27\x1c", "7z"),
+    (b"\x89PNG\r\n\x1a\n", "png"),
+    (b"\xff\xd8\xff", "jpg"),
+    (b"GIF8", "gif"),
+    (b"%PDF-", "pdf"),
+    (b"\x7fELF", "elf"),
+];
+
+/// Recording is off unless the CLI asks for it, so decoders pay nothing by default
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref ARTIFACTS: Mutex<Vec<Artifact>> = Mutex::new(Vec::new());
+}
+
+/// Locks the artifact list, recovering from a poisoned mutex
+fn artifacts() -> MutexGuard<'static, Vec<Artifact>> {
+    match ARTIFACTS.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            warn!("Mutex was poisoned, recovering");
+            poisoned.into_inner()
+        }
+    }
+}
+
+/// Starts recording binary blobs produced by decoders
+pub fn enable_recording() {
+    RECORDING.store(true, Ordering::Release);
+}
+
+/// Returns the file extension for bytes starting with a known signature
+pub fn detect_extension(bytes: &[u8]) -> Option<&'static str> {
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+        .map(|(_, extension)| *extension)
+}
+
+/// Called by decoders with the raw bytes they decoded.
+/// Keeps the bytes if recording is enabled and they look like a known file type.
+pub fn inspect(decoder_name: &str, bytes: &[u8]) {
+    if !RECORDING.load(Ordering::Acquire) {
+        return;
+    }
+    let Some(extension) = detect_extension(bytes) else {
+        return;
+    };
+    let mut artifacts = artifacts();
+    if artifacts.iter().any(|artifact| artifact.bytes == bytes) {
+        return;
+    }
+    trace!(
+        "Recording {} byte .{} artifact from {}",
+        bytes.len(),
+        extension,
+        decoder_name
+    );
+    artifacts.push(Artifact {
+        decoder_name: decoder_name.to_string(),
+        extension,
+        bytes: bytes.to_vec(),
+    });
+}
+
+/// Returns every recorded artifact
+pub fn get_artifacts() -> Vec<Artifact> {
+    artifacts().clone()
+}
+
+/// Clears every recorded artifact
+pub fn clear_artifacts() {
+    artifacts().clear();
+}
+
+/// Returns the bytes to write for a plaintext.
+/// If the plaintext is a lossy rendering of a recorded artifact, the artifact's
+/// original bytes are returned, otherwise the plaintext's own bytes.
+pub fn plaintext_bytes(plaintext: &str) -> Vec<u8> {
+    artifacts()
+        .iter()
+        .find(|artifact| String::from_utf8_lossy(&artifact.bytes) == plaintext)
+        .map_or_else(
+            || plaintext.as_bytes().to_vec(),
+            |artifact| artifact.bytes.clone(),
+        )
+}
+
+/// Writes every recorded artifact into `dir`, creating it if needed.
+/// Files are named `<index>-<decoder>.<extension>`, e.g. `1-base64.zip`.
+///
+/// # Errors
+/// Returns an error if the directory or a file cannot be written.
+pub fn save_artifacts(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let artifacts = get_artifacts();
+    if artifacts.is_empty() {
+        return Ok(Vec::new());
+    }
+    fs::create_dir_all(dir)?;
+    let mut paths = Vec::with_capacity(artifacts.len());
+    for (index, artifact) in artifacts.iter().enumerate() {
+        let decoder: String = artifact
+            .decoder_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let path = dir.join(format!("{}-{}.{}", index + 1, decoder, artifact.extension));
+        fs::write(&path, &artifact.bytes)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    fn detects_known_signatures() {
+        assert_eq!(detect_extension(b"PK\x03\x04rest of zip"), Some("zip"));
+        assert_eq!(detect_extension(b"\x89PNG\r\n\x1a\n...."), Some("png"));
+        assert_eq!(detect_extension(b"hello world"), None);
+    }
+
+    #[test]
+    #[serial]
+    fn records_and_saves_binary_blobs() {
+        clear_artifacts();
+        enable_recording();
+        let zip = b"PK\x03\x04\xff\xfe binary".to_vec();
+        inspect("Base64", &zip);
+        inspect("Base64", &zip);
+        inspect("Base64", b"plain text is ignored");
+        assert_eq!(get_artifacts().len(), 1);
+
+        // A lossy plaintext maps back to the original bytes
+        assert_eq!(plaintext_bytes(&String::from_utf8_lossy(&zip)), zip);
+        assert_eq!(plaintext_bytes("hello"), b"hello".to_vec());
+
+        let dir = std::env::temp_dir().join(format!("ares-artifacts-{}", uuid::Uuid::new_v4()));
+        let paths = save_artifacts(&dir).unwrap();
+        assert_eq!(paths, vec![dir.join("1-base64.zip")]);
+        assert_eq!(fs::read(&paths[0]).unwrap(), zip);
+        fs::remove_dir_all(&dir).unwrap();
+        clear_artifacts();
+    }
+}
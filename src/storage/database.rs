@@ -6,11 +6,54 @@
 
 use super::super::CheckResult;
 use super::super::CrackResult;
-use chrono::DateTime;
-use std::sync::OnceLock;
+use crate::error::AresError;
+use chrono::{DateTime, Local, NaiveDateTime};
+use r2d2_sqlite::SqliteConnectionManager;
+use std::collections::BTreeSet;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
 use uuid::Uuid;
 
-/// Holds the global path to the database
+/// The version of Ares which wrote a cache row. Rows from other versions
+/// are ignored, as their decoders may have behaved differently.
+pub const CACHE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// How much of the encoded text a cache row keeps. Rows are found by the
+/// hash of the whole text, so large inputs don't bloat the database.
+pub const CACHE_PREVIEW_CHARS: usize = 200;
+
+/// Environment variable which moves the database when `Config.database_path` isn't set
+pub const DB_PATH_ENV: &str = "ARES_DB_PATH";
+
+/// `Config.database_path` (or `ARES_DB_PATH`) which keeps the database in memory,
+/// for CI and other environments where nothing should be written to disk
+pub const IN_MEMORY_DATABASE: &str = ":memory:";
+
+/// The in-memory database every connection in the process shares
+const SHARED_MEMORY_URI: &str = "file:ares?mode=memory&cache=shared";
+
+/// SQLite drops a shared in-memory database when its last connection closes,
+/// so one connection is kept open for the rest of the process
+static MEMORY_DATABASE: OnceLock<Mutex<rusqlite::Connection>> = OnceLock::new();
+
+/// How long a connection waits for another process to release a lock
+/// before giving up with `SQLITE_BUSY`
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How many times a write which still fails with `SQLITE_BUSY` is tried again
+const BUSY_RETRIES: u32 = 5;
+
+/// The most connections the pool keeps open
+const POOL_SIZE: u32 = 4;
+
+/// A connection borrowed from the pool, returned to it when dropped
+pub type PooledConnection = r2d2::PooledConnection<SqliteConnectionManager>;
+
+/// Connections to the database at [`DB_PATH`], made once the path is set
+static POOL: Mutex<Option<r2d2::Pool<SqliteConnectionManager>>> = Mutex::new(None);
+
+/// Holds the global path to the database. Set by the first call to
+/// [`setup_database`], so every crack in a process shares one database.
 pub static DB_PATH: OnceLock<Option<std::path::PathBuf>> = OnceLock::new();
 
 #[derive(Debug)]
@@ -40,7 +83,7 @@ impl PartialEq for HumanRejectionRow {
 pub struct CacheRow {
     /// UUID of the cache entry
     pub uuid: Uuid,
-    /// Text before it is decoded
+    /// Text before it is decoded, cut to [`CACHE_PREVIEW_CHARS`] characters
     pub encoded_text: String,
     /// Text after it is decoded
     pub decoded_text: String,
@@ -52,6 +95,34 @@ pub struct CacheRow {
     pub execution_time_ms: i64,
     /// When the decoding was run
     pub timestamp: String,
+    /// The version of Ares which ran the decoding
+    pub ares_version: String,
+}
+
+impl CacheRow {
+    /// Returns true if the row was written by this version of Ares and,
+    /// with a `ttl` in seconds, is younger than it
+    pub fn is_fresh(&self, ttl: Option<u64>) -> bool {
+        is_fresh(&self.ares_version, &self.timestamp, ttl)
+    }
+}
+
+/// Returns true if a row written at `timestamp` by `ares_version` can still be used
+fn is_fresh(ares_version: &str, timestamp: &str, ttl: Option<u64>) -> bool {
+    if ares_version != CACHE_VERSION {
+        return false;
+    }
+    let Some(ttl) = ttl else {
+        return true;
+    };
+    let written = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+        .ok()
+        .and_then(|written| written.and_local_timezone(Local).earliest());
+    match written {
+        Some(written) => (Local::now() - written).num_seconds() < ttl as i64,
+        // A row whose age can't be told can't be trusted to be fresh
+        None => false,
+    }
 }
 
 impl PartialEq for CacheRow {
@@ -63,9 +134,179 @@ impl PartialEq for CacheRow {
             && self.successful == other.successful
             && self.execution_time_ms == other.execution_time_ms
             && self.timestamp == other.timestamp
+            && self.ares_version == other.ares_version
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// Struct representing a row in the failed_attempt table, a search which
+/// found nothing
+pub struct FailedAttemptRow {
+    /// SHA-256 of the text which could not be cracked, see [`hash_input`]
+    pub input_hash: String,
+    /// The settings the search ran with, see [`config_fingerprint`]
+    pub config_fingerprint: String,
+    /// How many seconds the search was given
+    pub timeout_secs: u32,
+    /// When the search was run
+    pub timestamp: String,
+    /// The version of Ares which ran the search
+    pub ares_version: String,
+}
+
+impl FailedAttemptRow {
+    /// Builds a row for a search on `text` which just failed with `config`
+    pub fn new(text: &str, config: &crate::config::Config) -> Self {
+        FailedAttemptRow {
+            input_hash: hash_input(text),
+            config_fingerprint: config_fingerprint(config),
+            timeout_secs: config.timeout,
+            timestamp: get_timestamp(),
+            ares_version: CACHE_VERSION.to_string(),
+        }
+    }
+
+    /// Returns true if searching again with `config` can't be expected to do
+    /// better: the row is fresh and the search had at least as long
+    pub fn still_applies(&self, config: &crate::config::Config) -> bool {
+        is_fresh(&self.ares_version, &self.timestamp, config.cache_ttl)
+            && self.timeout_secs >= config.timeout
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Struct representing a row in the runs table, one crack and how it went
+pub struct RunRow {
+    /// Uuid for the run
+    pub uuid: String,
+    /// SHA-256 of the text which was cracked, see [`hash_input`]
+    pub input_hash: String,
+    /// The start of the text which was cracked
+    pub input_preview: String,
+    /// The settings the crack ran with as JSON, see [`config_snapshot`]
+    pub config: String,
+    /// `success`, or why it failed, see [`AresError::kind`]
+    pub outcome: String,
+    /// How long the crack took in milliseconds
+    pub duration_ms: i64,
+    /// The names of the decoders which led to the plaintext
+    pub path: Vec<String>,
+    /// The start of the plaintext, if one was found
+    pub plaintext: Option<String>,
+    /// When the crack was run
+    pub timestamp: String,
+    /// The version of Ares which ran the crack
+    pub ares_version: String,
+}
+
+impl RunRow {
+    /// Starts a row for a crack of `text` with `config`, which
+    /// [`RunRow::finish`] fills in once it has ended
+    pub fn start(text: &str, config: &crate::config::Config) -> Self {
+        RunRow {
+            uuid: Uuid::new_v4().to_string(),
+            input_hash: hash_input(text),
+            input_preview: preview(text),
+            config: config_snapshot(config).to_string(),
+            outcome: String::new(),
+            duration_ms: 0,
+            path: Vec::new(),
+            plaintext: None,
+            timestamp: get_timestamp(),
+            ares_version: CACHE_VERSION.to_string(),
+        }
+    }
+
+    /// Records how the crack went: `result` after `duration_ms`
+    pub fn finish(&mut self, result: &Result<crate::DecoderResult, AresError>, duration_ms: i64) {
+        self.duration_ms = duration_ms;
+        match result {
+            Ok(found) => {
+                self.outcome = "success".to_string();
+                self.path = found.path.iter().map(|step| step.decoder.to_string()).collect();
+                self.plaintext = found.text.first().map(|text| preview(&text.text()));
+            }
+            Err(e) => self.outcome = e.kind().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Struct representing a row in the dictionary table, a word added with
+/// `ares dict add`
+pub struct DictionaryRow {
+    /// The word, or the start of a flag like `flag{`
+    pub word: String,
+    /// Whether plaintexts are recognised by starting with it rather than being it
+    pub prefix: bool,
+}
+
+/// Hashes text to find it in the cache and failed_attempt tables, so large
+/// inputs aren't stored in full
+pub fn hash_input(text: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Returns true if the text is too long to keep in a cache row in full
+pub fn is_large(text: &str) -> bool {
+    text.chars().nth(CACHE_PREVIEW_CHARS).is_some()
+}
+
+/// The start of the text, marked with … if it was cut short
+fn preview(text: &str) -> String {
+    match text.char_indices().nth(CACHE_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Hashes the settings which change what a search can find. A failure with
+/// one decoder filter, crib or wordlist says nothing about another.
+pub fn config_fingerprint(config: &crate::config::Config) -> String {
+    hash_input(&search_settings(config).to_string())
+}
+
+/// The settings a run is kept with in the runs table: those which change what
+/// a search can find, and how long and how it searched
+pub fn config_snapshot(config: &crate::config::Config) -> serde_json::Value {
+    let mut settings = search_settings(config);
+    settings["timeout"] = config.timeout.into();
+    settings["top_results"] = config.top_results.into();
+    settings["human_checker_on"] = config.human_checker_on.into();
+    settings
+}
+
+/// The settings [`config_fingerprint`] hashes. Word sets are sorted, so the
+/// same words always give the same fingerprint.
+fn search_settings(config: &crate::config::Config) -> serde_json::Value {
+    let wordlist: Option<BTreeSet<&String>> =
+        config.wordlist.as_ref().map(|words| words.iter().collect());
+    let dictionary_words: BTreeSet<&String> = config.dictionary_words.iter().collect();
+    let dictionary_prefixes: BTreeSet<&String> = config.dictionary_prefixes.iter().collect();
+    serde_json::json!({
+        "decoders_only": config.decoders_only,
+        "decoders_exclude": config.decoders_exclude,
+        "regex": config.regex,
+        "crib": config.crib,
+        "letter_map": config.letter_map,
+        "custom_alphabet": config.custom_alphabet,
+        "languages": config.languages,
+        "seed": config.seed,
+        "wordlist_path": config.wordlist_path,
+        "wordlist": wordlist,
+        "dictionary_words": dictionary_words,
+        "dictionary_prefixes": dictionary_prefixes,
+        "enhanced_detection": config.enhanced_detection,
+        "codebook": config.codebook,
+        "lemmeknow_min_rarity": config.lemmeknow_min_rarity,
+        "lemmeknow_max_rarity": config.lemmeknow_max_rarity,
+        "lemmeknow_tags": config.lemmeknow_tags,
+        "lemmeknow_exclude_tags": config.lemmeknow_exclude_tags,
+        "lemmeknow_boundaryless": config.lemmeknow_boundaryless,
+    })
+}
+
 #[derive(Debug)]
 /// Represents an entry into the cache table
 pub struct CacheEntry {
@@ -81,33 +322,210 @@ pub struct CacheEntry {
     pub execution_time_ms: i64,
 }
 
+/// How timestamps are written to the database, in local time
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %T";
+
 /// Helper function get a DateTime formatted timestamp
 fn get_timestamp() -> String {
     let timestamp: DateTime<chrono::Local> = std::time::SystemTime::now().into();
-    timestamp.format("%Y-%m-%d %T").to_string()
+    timestamp.format(TIMESTAMP_FORMAT).to_string()
 }
 
-/// Returns the path to the database file
-fn get_database_path() -> std::path::PathBuf {
+/// Returns the path to the database file, from the config, then
+/// [`DB_PATH_ENV`], then `~/.ares/database.sqlite`
+fn get_database_path(config: &crate::config::Config) -> std::path::PathBuf {
+    choose_database_path(
+        config.database_path.as_deref(),
+        std::env::var_os(DB_PATH_ENV),
+    )
+}
+
+/// Picks the first of the configured and environment paths which is set
+fn choose_database_path(
+    configured: Option<&str>,
+    from_env: Option<std::ffi::OsString>,
+) -> std::path::PathBuf {
+    let path: Option<std::path::PathBuf> = match configured.filter(|path| !path.is_empty()) {
+        Some(path) => Some(path.into()),
+        None => from_env.filter(|path| !path.is_empty()).map(Into::into),
+    };
+    if let Some(path) = path {
+        if path.as_os_str() == IN_MEMORY_DATABASE {
+            return SHARED_MEMORY_URI.into();
+        }
+        return path;
+    }
     let mut path = dirs::home_dir().expect("Could not find home directory");
     path.push(".ares");
     path.push("database.sqlite");
     path
 }
 
-/// Opens and returns a Connection to the SQLite database
+/// Sets the path every connection opens. Only the first call has an effect,
+/// later ones return the path which is already in use.
+///
+/// # Errors
+///
+/// Returns the path already in use if it was set before
+pub fn set_database_path(path: std::path::PathBuf) -> Result<(), Option<std::path::PathBuf>> {
+    DB_PATH
+        .set(Some(path))
+        .map_err(|_| DB_PATH.get().cloned().flatten())
+}
+
+/// Borrows a connection to the SQLite database from the pool
+///
+/// If a path is specified in DB_PATH, connects to that path
+/// Otherwise, connects to an in-memory database
+fn get_db_connection() -> Result<PooledConnection, rusqlite::Error> {
+    let manager = match DB_PATH.get() {
+        Some(Some(path)) => SqliteConnectionManager::file(path),
+        _ => SqliteConnectionManager::memory(),
+    };
+    let pool = match DB_PATH.get() {
+        // The path can't change once set, so the pool is kept
+        Some(_) => {
+            let mut pool = POOL.lock().unwrap();
+            match &*pool {
+                Some(pool) => pool.clone(),
+                None => pool.insert(build_pool(manager)?).clone(),
+            }
+        }
+        None => build_pool(manager)?,
+    };
+    pool.get().map_err(|e| pool_error(&e))
+}
+
+/// Lets go of the pool, so its connections close once they are returned.
+/// The next connection starts a new pool. Needed before the database file
+/// is deleted or replaced, e.g. between tests.
+///
+/// # Panics
 ///
-/// If a path is specified in DB_PATH, returns a Connection to that path
-/// Otherwise, opens a Connection to an in-memory database
-fn get_db_connection() -> Result<rusqlite::Connection, rusqlite::Error> {
+/// Panics if another thread panicked while holding the pool
+pub fn close_pooled_connections() {
+    POOL.lock().unwrap().take();
+}
+
+/// Makes a pool which configures every connection with [`configure_connection`]
+fn build_pool(
+    manager: SqliteConnectionManager,
+) -> Result<r2d2::Pool<SqliteConnectionManager>, rusqlite::Error> {
+    r2d2::Pool::builder()
+        .max_size(POOL_SIZE)
+        .min_idle(Some(0))
+        .connection_timeout(BUSY_TIMEOUT)
+        .build(manager.with_init(configure_connection))
+        .map_err(|e| pool_error(&e))
+}
+
+/// The pool only fails when it can't hand out a connection in time
+fn pool_error(e: &r2d2::Error) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CANTOPEN),
+        Some(e.to_string()),
+    )
+}
+
+/// Opens a connection to the database at DB_PATH which isn't in the pool,
+/// for callers which keep it
+fn open_unpooled_connection() -> Result<rusqlite::Connection, rusqlite::Error> {
+    let mut conn = match DB_PATH.get() {
+        Some(Some(path)) => rusqlite::Connection::open(path)?,
+        _ => rusqlite::Connection::open_in_memory()?,
+    };
+    configure_connection(&mut conn)?;
+    Ok(conn)
+}
+
+/// Lets several Ares processes use the database at once. In WAL mode readers
+/// don't block the writer, and a connection waits up to [`BUSY_TIMEOUT`] for
+/// a lock instead of failing straight away.
+fn configure_connection(conn: &mut rusqlite::Connection) -> Result<(), rusqlite::Error> {
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    // In-memory databases stay in "memory" mode, which is fine
+    retry_on_busy(|| {
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))
+    })?;
+    conn.pragma_update(None, "synchronous", "NORMAL")
+}
+
+/// Runs `f` again while it fails with `SQLITE_BUSY` or `SQLITE_LOCKED`, waiting
+/// a little longer each time. SQLite reports some conflicts, like two
+/// connections upgrading to a write at once, without waiting for the busy
+/// timeout, so writes need this on top of it.
+///
+/// # Errors
+///
+/// Returns the last error if every try fails, or any other error straight away
+pub fn retry_on_busy<T>(
+    mut f: impl FnMut() -> Result<T, rusqlite::Error>,
+) -> Result<T, rusqlite::Error> {
+    let mut attempt = 0;
+    loop {
+        match f() {
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if attempt < BUSY_RETRIES
+                    && matches!(
+                        e.code,
+                        rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+                    ) =>
+            {
+                std::thread::sleep(Duration::from_millis(20 << attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Opens a Connection which can't change the database, for
+/// [`crate::config::CacheMode::ReadOnly`]. Fails if the database doesn't exist yet.
+///
+/// # Errors
+///
+/// Returns rusqlite::Error if the database can't be opened
+pub fn open_read_only_connection() -> Result<rusqlite::Connection, rusqlite::Error> {
     match DB_PATH.get() {
-        Some(Some(path)) => rusqlite::Connection::open(path),
+        Some(Some(path)) => open_read_only_connection_at(path),
         _ => rusqlite::Connection::open_in_memory(),
     }
 }
 
+/// Opens the database `config.database_path` names without changing it,
+/// whichever database the rest of the process uses. Without a
+/// `database_path` it opens the process's, like [`open_read_only_connection`].
+///
+/// # Errors
+///
+/// Returns rusqlite::Error if the database can't be opened
+pub fn open_read_only_connection_for(
+    config: &crate::config::Config,
+) -> Result<rusqlite::Connection, rusqlite::Error> {
+    if !names_database(config) {
+        return open_read_only_connection();
+    }
+    open_read_only_connection_at(&get_database_path(config))
+}
+
+/// Opens the database at `path` without changing it
+fn open_read_only_connection_at(
+    path: &std::path::Path,
+) -> Result<rusqlite::Connection, rusqlite::Error> {
+    use rusqlite::OpenFlags;
+    let conn = rusqlite::Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI,
+    )?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    Ok(conn)
+}
+
 /// Public wrapper for setting up database
 ///
+/// Only read-write cache modes create or migrate the tables, other modes
+/// just set the path.
+///
 /// # Errors
 ///
 /// On error setting up the database, returns a rusqlite::Error
@@ -115,22 +533,22 @@ fn get_db_connection() -> Result<rusqlite::Connection, rusqlite::Error> {
 /// to console and continues with the default DB_PATH
 pub fn setup_database(config: &crate::config::Config) -> Result<(), rusqlite::Error> {
     match DB_PATH.get() {
-        Some(_path) => (),
-        None => {
-            let path = get_database_path();
-            if let Some(parent) = path.parent() {
-                if !parent.exists() {
-                    if let Err(e) = std::fs::create_dir_all(parent) {
-                        crate::cli_pretty_printing::warning(
-                            &format!("Error creating database directory: {}", e),
-                            config,
-                        );
-                    }
-                }
+        Some(Some(path)) => {
+            if names_database(config) && *path != get_database_path(config) {
+                crate::cli_pretty_printing::warning(
+                    &format!(
+                        "The database is already open at {}, ignoring database_path",
+                        path.display()
+                    ),
+                    config,
+                );
             }
-
-            let db_result: Result<(), Option<std::path::PathBuf>> = DB_PATH.set(Some(path));
-            match db_result {
+        }
+        Some(None) => (),
+        None => {
+            let path = get_database_path(config);
+            prepare_database_path(&path, config)?;
+            match set_database_path(path) {
                 Ok(_) => (),
                 Err(_e) => {
                     crate::cli_pretty_printing::warning("Error setting database path", config);
@@ -138,13 +556,91 @@ pub fn setup_database(config: &crate::config::Config) -> Result<(), rusqlite::Er
             }
         }
     };
-    init_database()?;
+    if config.cache_mode == crate::config::CacheMode::ReadWrite {
+        init_database()?;
+    }
     Ok(())
 }
 
+/// Whether the config names a database of its own
+fn names_database(config: &crate::config::Config) -> bool {
+    config.database_path.as_deref().is_some_and(|path| !path.is_empty())
+}
+
+/// Makes sure a database can be opened at `path`: creates its directory, or
+/// keeps the shared in-memory database open
+fn prepare_database_path(
+    path: &std::path::Path,
+    config: &crate::config::Config,
+) -> Result<(), rusqlite::Error> {
+    if path.as_os_str() == SHARED_MEMORY_URI {
+        keep_memory_database_open()?;
+    } else if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                crate::cli_pretty_printing::warning(
+                    &format!("Error creating database directory: {}", e),
+                    config,
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Opens the shared in-memory database once, so it lives as long as the process
+fn keep_memory_database_open() -> Result<(), rusqlite::Error> {
+    if MEMORY_DATABASE.get().is_none() {
+        let conn = rusqlite::Connection::open(SHARED_MEMORY_URI)?;
+        let _ = MEMORY_DATABASE.set(Mutex::new(conn));
+    }
+    Ok(())
+}
+
+/// Opens a connection to the database at DB_PATH, creating the tables if needed.
+/// Lets [`crate::AresSession`] keep one connection for every crack instead of
+/// opening a new one each time.
+///
+/// # Errors
+///
+/// Returns rusqlite::Error if the database can't be opened or initialized
+pub fn open_connection() -> Result<rusqlite::Connection, rusqlite::Error> {
+    let conn = open_unpooled_connection()?;
+    retry_on_busy(|| create_tables(&conn))?;
+    Ok(conn)
+}
+
+/// Opens a connection to the database `config.database_path` names, creating
+/// it and its tables if needed, whichever database the rest of the process
+/// uses. Lets each [`crate::AresSession`] keep the database its config asks for.
+/// Without a `database_path` it opens the process's, like [`open_connection`].
+///
+/// # Errors
+///
+/// Returns rusqlite::Error if the database can't be opened or initialized
+pub fn open_connection_for(
+    config: &crate::config::Config,
+) -> Result<rusqlite::Connection, rusqlite::Error> {
+    if !names_database(config) {
+        return open_connection();
+    }
+    let path = get_database_path(config);
+    prepare_database_path(&path, config)?;
+    let mut conn = rusqlite::Connection::open(path)?;
+    configure_connection(&mut conn)?;
+    retry_on_busy(|| create_tables(&conn))?;
+    Ok(conn)
+}
+
 /// Initializes database with default schema
-fn init_database() -> Result<rusqlite::Connection, rusqlite::Error> {
+fn init_database() -> Result<PooledConnection, rusqlite::Error> {
     let conn = get_db_connection()?;
+    retry_on_busy(|| create_tables(&conn))?;
+    Ok(conn)
+}
+
+/// Creates or migrates the tables, so the database has the current schema
+fn create_tables(conn: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
     // Initializing cache table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS cache (
@@ -154,13 +650,19 @@ fn init_database() -> Result<rusqlite::Connection, rusqlite::Error> {
             path JSON NOT NULL,
             successful BOOLEAN NOT NULL DEFAULT true,
             execution_time_ms INTEGER NOT NULL,
-            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP
+            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+            ares_version TEXT NOT NULL DEFAULT '',
+            last_hit DATETIME,
+            input_hash TEXT
     );",
         (),
     )?;
+    migrate_cache_table(conn)?;
+    // Rows are looked up by hash, the encoded text is only a preview
+    conn.execute("DROP INDEX IF EXISTS idx_cache_encoded_text;", ())?;
     conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_cache_encoded_text
-            ON cache(encoded_text);",
+        "CREATE INDEX IF NOT EXISTS idx_cache_input_hash
+            ON cache(input_hash);",
         (),
     )?;
 
@@ -170,16 +672,105 @@ fn init_database() -> Result<rusqlite::Connection, rusqlite::Error> {
             uuid TEXT PRIMARY KEY NOT NULL,
             plaintext TEXT NOT NULL,
             checker TEXT NOT NULL,
-            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP
+            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+            decoder TEXT NOT NULL DEFAULT '',
+            features JSON
     );",
         (),
     )?;
+    add_missing_columns(conn, "human_rejection", &ADDED_HUMAN_REJECTION_COLUMNS)?;
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_stats_plaintext ON human_rejection(plaintext);",
         (),
     )?;
 
-    Ok(conn)
+    // Initializing failed attempt table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS failed_attempt (
+            input_hash TEXT NOT NULL,
+            config_fingerprint TEXT NOT NULL,
+            timeout_secs INTEGER NOT NULL,
+            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+            ares_version TEXT NOT NULL,
+            PRIMARY KEY (input_hash, config_fingerprint)
+    );",
+        (),
+    )?;
+
+    // Initializing runs table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS runs (
+            uuid TEXT PRIMARY KEY NOT NULL,
+            input_hash TEXT NOT NULL,
+            input_preview TEXT NOT NULL,
+            config JSON NOT NULL,
+            outcome TEXT NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            path JSON NOT NULL,
+            plaintext TEXT,
+            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+            ares_version TEXT NOT NULL
+    );",
+        (),
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_runs_input_hash ON runs(input_hash);",
+        (),
+    )?;
+
+    // Initializing dictionary table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS dictionary (
+            word TEXT NOT NULL,
+            prefix BOOLEAN NOT NULL,
+            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (word, prefix)
+    );",
+        (),
+    )?;
+
+    Ok(())
+}
+
+/// Columns added to the cache table since it was first made, with their definitions
+const ADDED_CACHE_COLUMNS: [(&str, &str); 3] = [
+    ("ares_version", "TEXT NOT NULL DEFAULT ''"),
+    ("last_hit", "DATETIME"),
+    ("input_hash", "TEXT"),
+];
+
+/// Adds the columns which cache tables made by older versions of Ares lack.
+/// Their rows get an empty version, so they are never fresh, and no hash,
+/// so they are never found.
+fn migrate_cache_table(conn: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
+    add_missing_columns(conn, "cache", &ADDED_CACHE_COLUMNS)
+}
+
+/// Columns added to the human_rejection table since it was first made, with their definitions
+const ADDED_HUMAN_REJECTION_COLUMNS: [(&str, &str); 2] = [
+    ("decoder", "TEXT NOT NULL DEFAULT ''"),
+    ("features", "JSON"),
+];
+
+/// Adds the given columns to a table made by an older version of Ares which lacks them
+fn add_missing_columns(
+    conn: &rusqlite::Connection,
+    table: &str,
+    added_columns: &[(&str, &str)],
+) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table});"))?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<usize, String>(1))?
+        .collect::<Result<_, _>>()?;
+    for (name, definition) in added_columns {
+        if !columns.iter().any(|column| column == name) {
+            conn.execute(
+                &format!("ALTER TABLE {table} ADD COLUMN {name} {definition}"),
+                (),
+            )?;
+        }
+    }
+    Ok(())
 }
 
 /// Adds a new cache record to the cache table
@@ -194,10 +785,36 @@ fn init_database() -> Result<rusqlite::Connection, rusqlite::Error> {
 ///
 /// Panics if the decoding path could not be serialized
 pub fn insert_cache(cache_entry: &CacheEntry) -> Result<usize, rusqlite::Error> {
+    retry_on_busy(|| insert_cache_with(&mut *get_db_connection()?, cache_entry))
+}
+
+/// Like [`insert_cache`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+///
+/// # Panics
+///
+/// Panics if the decoding path could not be serialized
+pub fn insert_cache_with(
+    conn: &mut rusqlite::Connection,
+    cache_entry: &CacheEntry,
+) -> Result<usize, rusqlite::Error> {
     let path: Vec<String> = cache_entry
         .path
         .iter()
-        .map(|crack_result| crack_result.get_json().unwrap_or_default())
+        .enumerate()
+        .map(|(i, crack_result)| {
+            // The first step's input is the whole encoded text. Large ones are
+            // left out and put back from the text the cache is read with.
+            if i == 0 && is_large(&crack_result.encrypted_text) {
+                let mut crack_result = crack_result.clone();
+                crack_result.encrypted_text = "".into();
+                return crack_result.get_json().unwrap_or_default();
+            }
+            crack_result.get_json().unwrap_or_default()
+        })
         .collect();
 
     let last_crack_result = cache_entry.path.last();
@@ -207,7 +824,6 @@ pub fn insert_cache(cache_entry: &CacheEntry) -> Result<usize, rusqlite::Error>
     };
 
     let path_json = serde_json::to_string(&path).unwrap();
-    let mut conn = get_db_connection()?;
     let transaction = conn.transaction()?;
     let conn_result = transaction.execute(
         "INSERT INTO cache (
@@ -217,16 +833,20 @@ pub fn insert_cache(cache_entry: &CacheEntry) -> Result<usize, rusqlite::Error>
             path,
             successful,
             execution_time_ms,
-            timestamp)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            timestamp,
+            ares_version,
+            input_hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
         (
             cache_entry.uuid.to_string(),
-            cache_entry.encoded_text.clone(),
+            preview(&cache_entry.encoded_text),
             cache_entry.decoded_text.clone(),
             path_json,
             successful,
             cache_entry.execution_time_ms,
             get_timestamp(),
+            CACHE_VERSION,
+            hash_input(&cache_entry.encoded_text),
         ),
     );
     transaction.commit()?;
@@ -242,10 +862,21 @@ pub fn insert_cache(cache_entry: &CacheEntry) -> Result<usize, rusqlite::Error>
 /// # Errors
 ///
 /// Returns a ``rusqlite::Error``
-pub fn read_cache(encoded_text: &String) -> Result<Option<CacheRow>, rusqlite::Error> {
-    let conn = get_db_connection()?;
-    let mut stmt = conn.prepare("SELECT * FROM cache WHERE encoded_text IS $1")?;
-    let mut query = stmt.query_map([encoded_text], |row| {
+pub fn read_cache(encoded_text: &str) -> Result<Option<CacheRow>, rusqlite::Error> {
+    read_cache_with(&*get_db_connection()?, encoded_text)
+}
+
+/// Like [`read_cache`], using an open connection
+///
+/// # Errors
+///
+/// Returns a ``rusqlite::Error``
+pub fn read_cache_with(
+    conn: &rusqlite::Connection,
+    encoded_text: &str,
+) -> Result<Option<CacheRow>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT * FROM cache WHERE input_hash = $1")?;
+    let mut query = stmt.query_map([hash_input(encoded_text)], |row| {
         let path_str = row.get_unwrap::<usize, String>(3).to_owned();
         let crack_json_vec: Vec<String> =
             serde_json::from_str(&path_str.clone()).unwrap_or_default();
@@ -258,6 +889,8 @@ pub fn read_cache(encoded_text: &String) -> Result<Option<CacheRow>, rusqlite::E
             successful: row.get_unwrap(4),
             execution_time_ms: row.get_unwrap(5),
             timestamp: row.get_unwrap(6),
+            // Read-only runs can't add the column to tables from older versions
+            ares_version: row.get(7).unwrap_or_default(),
         })
     })?;
     let row = query.next();
@@ -267,6 +900,65 @@ pub fn read_cache(encoded_text: &String) -> Result<Option<CacheRow>, rusqlite::E
     }
 }
 
+/// Records that the cache row for the encoded text was just used,
+/// so it is among the last to be evicted
+///
+/// Returns the number of rows updated on success
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn touch_cache(encoded_text: &str) -> Result<usize, rusqlite::Error> {
+    retry_on_busy(|| touch_cache_with(&*get_db_connection()?, encoded_text))
+}
+
+/// Like [`touch_cache`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn touch_cache_with(
+    conn: &rusqlite::Connection,
+    encoded_text: &str,
+) -> Result<usize, rusqlite::Error> {
+    conn.execute(
+        "UPDATE cache SET last_hit = $1 WHERE input_hash = $2",
+        (get_timestamp(), hash_input(encoded_text)),
+    )
+}
+
+/// Deletes the least recently used cache rows until at most `max_entries` are left.
+/// A row was last used when it was last read from the cache, or when it was
+/// written if it never has been.
+///
+/// Returns the number of rows deleted on success
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn evict_cache(max_entries: usize) -> Result<usize, rusqlite::Error> {
+    retry_on_busy(|| evict_cache_with(&*get_db_connection()?, max_entries))
+}
+
+/// Like [`evict_cache`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn evict_cache_with(
+    conn: &rusqlite::Connection,
+    max_entries: usize,
+) -> Result<usize, rusqlite::Error> {
+    conn.execute(
+        "DELETE FROM cache WHERE uuid IN (
+            SELECT uuid FROM cache
+            ORDER BY COALESCE(last_hit, timestamp), rowid
+            LIMIT max(0, (SELECT COUNT(*) FROM cache) - $1)
+        )",
+        (i64::try_from(max_entries).unwrap_or(i64::MAX),),
+    )
+}
+
 /// Removes the cache row corresponding to the given encoded_text
 ///
 /// Returns number of successfully deleted rows on success
@@ -275,16 +967,273 @@ pub fn read_cache(encoded_text: &String) -> Result<Option<CacheRow>, rusqlite::E
 ///
 /// Returns sqlite::Error on error
 pub fn delete_cache(encoded_text: &str) -> Result<usize, rusqlite::Error> {
-    let mut conn = get_db_connection()?;
+    retry_on_busy(|| delete_cache_with(&mut *get_db_connection()?, encoded_text))
+}
+
+/// Like [`delete_cache`], using an open connection
+///
+/// # Errors
+///
+/// Returns sqlite::Error on error
+pub fn delete_cache_with(
+    conn: &mut rusqlite::Connection,
+    encoded_text: &str,
+) -> Result<usize, rusqlite::Error> {
     let transaction = conn.transaction()?;
     let conn_result = transaction.execute(
-        "DELETE FROM cache WHERE encoded_text = $1",
-        (encoded_text.to_owned(),),
+        "DELETE FROM cache WHERE input_hash = $1",
+        (hash_input(encoded_text),),
     );
     transaction.commit()?;
     conn_result
 }
 
+/// Records a failed search, replacing any earlier failure of the same
+/// input with the same settings
+///
+/// Returns the number of successfully inserted rows on success
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn insert_failed_attempt(row: &FailedAttemptRow) -> Result<usize, rusqlite::Error> {
+    retry_on_busy(|| insert_failed_attempt_with(&*get_db_connection()?, row))
+}
+
+/// Like [`insert_failed_attempt`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn insert_failed_attempt_with(
+    conn: &rusqlite::Connection,
+    row: &FailedAttemptRow,
+) -> Result<usize, rusqlite::Error> {
+    conn.execute(
+        "INSERT OR REPLACE INTO failed_attempt (
+            input_hash,
+            config_fingerprint,
+            timeout_secs,
+            timestamp,
+            ares_version)
+        VALUES ($1, $2, $3, $4, $5)",
+        (
+            &row.input_hash,
+            &row.config_fingerprint,
+            row.timeout_secs,
+            &row.timestamp,
+            &row.ares_version,
+        ),
+    )
+}
+
+/// Looks up an earlier failed search of `text` with the same settings as `config`
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn read_failed_attempt(
+    text: &str,
+    config: &crate::config::Config,
+) -> Result<Option<FailedAttemptRow>, rusqlite::Error> {
+    read_failed_attempt_with(&*get_db_connection()?, text, config)
+}
+
+/// Like [`read_failed_attempt`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn read_failed_attempt_with(
+    conn: &rusqlite::Connection,
+    text: &str,
+    config: &crate::config::Config,
+) -> Result<Option<FailedAttemptRow>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT * FROM failed_attempt WHERE input_hash = $1 AND config_fingerprint = $2",
+    )?;
+    let mut query = stmt.query_map((hash_input(text), config_fingerprint(config)), |row| {
+        Ok(FailedAttemptRow {
+            input_hash: row.get(0)?,
+            config_fingerprint: row.get(1)?,
+            timeout_secs: row.get(2)?,
+            timestamp: row.get(3)?,
+            ares_version: row.get(4)?,
+        })
+    })?;
+    query.next().transpose()
+}
+
+/// Records a crack in the runs table
+///
+/// Returns the number of successfully inserted rows on success
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn insert_run(row: &RunRow) -> Result<usize, rusqlite::Error> {
+    retry_on_busy(|| insert_run_with(&*get_db_connection()?, row))
+}
+
+/// Like [`insert_run`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn insert_run_with(conn: &rusqlite::Connection, row: &RunRow) -> Result<usize, rusqlite::Error> {
+    conn.execute(
+        "INSERT INTO runs (
+            uuid,
+            input_hash,
+            input_preview,
+            config,
+            outcome,
+            duration_ms,
+            path,
+            plaintext,
+            timestamp,
+            ares_version)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        (
+            &row.uuid,
+            &row.input_hash,
+            &row.input_preview,
+            &row.config,
+            &row.outcome,
+            row.duration_ms,
+            serde_json::to_string(&row.path).unwrap_or_default(),
+            &row.plaintext,
+            &row.timestamp,
+            &row.ares_version,
+        ),
+    )
+}
+
+/// Reads the latest `limit` runs, newest first, or only the runs on `input`
+/// if it is given
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn read_runs(input: Option<&str>, limit: usize) -> Result<Vec<RunRow>, rusqlite::Error> {
+    read_runs_with(&*get_db_connection()?, input, limit)
+}
+
+/// Like [`read_runs`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn read_runs_with(
+    conn: &rusqlite::Connection,
+    input: Option<&str>,
+    limit: usize,
+) -> Result<Vec<RunRow>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT uuid, input_hash, input_preview, config, outcome, duration_ms, path,
+            plaintext, timestamp, ares_version
+        FROM runs
+        WHERE $1 IS NULL OR input_hash = $1
+        ORDER BY timestamp DESC, rowid DESC
+        LIMIT $2",
+    )?;
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows = stmt.query_map((input.map(hash_input), limit), |row| {
+        let path: String = row.get(6)?;
+        Ok(RunRow {
+            uuid: row.get(0)?,
+            input_hash: row.get(1)?,
+            input_preview: row.get(2)?,
+            config: row.get(3)?,
+            outcome: row.get(4)?,
+            duration_ms: row.get(5)?,
+            path: serde_json::from_str(&path).unwrap_or_default(),
+            plaintext: row.get(7)?,
+            timestamp: row.get(8)?,
+            ares_version: row.get(9)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Adds words to the dictionary, skipping those already in it
+///
+/// Returns the number of words which were new
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn insert_dictionary_words(words: &[DictionaryRow]) -> Result<usize, rusqlite::Error> {
+    retry_on_busy(|| insert_dictionary_words_with(&mut *get_db_connection()?, words))
+}
+
+/// Like [`insert_dictionary_words`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn insert_dictionary_words_with(
+    conn: &mut rusqlite::Connection,
+    words: &[DictionaryRow],
+) -> Result<usize, rusqlite::Error> {
+    // One transaction, so importing a large list is a single write
+    let transaction = conn.transaction()?;
+    let mut inserted = 0;
+    {
+        let mut stmt = transaction.prepare(
+            "INSERT OR IGNORE INTO dictionary (word, prefix, timestamp) VALUES ($1, $2, $3)",
+        )?;
+        let timestamp = get_timestamp();
+        for row in words {
+            inserted += stmt.execute((&row.word, row.prefix, &timestamp))?;
+        }
+    }
+    transaction.commit()?;
+    Ok(inserted)
+}
+
+/// Reads every word in the dictionary, in the order they were added
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn read_dictionary() -> Result<Vec<DictionaryRow>, rusqlite::Error> {
+    read_dictionary_with(&*get_db_connection()?)
+}
+
+/// Like [`read_dictionary`], using an open connection
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn read_dictionary_with(
+    conn: &rusqlite::Connection,
+) -> Result<Vec<DictionaryRow>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT word, prefix FROM dictionary ORDER BY rowid")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(DictionaryRow {
+            word: row.get(0)?,
+            prefix: row.get(1)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Removes words from the dictionary, whether they were added as words or prefixes
+///
+/// Returns the number of rows removed
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn delete_dictionary_words(words: &[String]) -> Result<usize, rusqlite::Error> {
+    retry_on_busy(|| {
+        let conn = get_db_connection()?;
+        let mut stmt = conn.prepare("DELETE FROM dictionary WHERE word = $1")?;
+        words.iter().map(|word| stmt.execute([word])).sum()
+    })
+}
+
 /// Updates the values in a cache row corresponding to the encoded_text in
 /// the given cache entry
 ///
@@ -308,28 +1257,32 @@ pub fn update_cache(cache_entry: &CacheEntry) -> Result<usize, rusqlite::Error>
 
     let path_json = serde_json::to_string(&path).unwrap_or_default();
     let mut conn = get_db_connection()?;
-    let transaction = conn.transaction()?;
-    let conn_result = transaction.execute(
-        "UPDATE cache SET 
-            uuid = $1,
-            decoded_text = $2,
-            path = $3,
-            successful = $4,
-            execution_time_ms = $5,
-            timestamp = $6
-            WHERE encoded_text = $7;",
-        (
-            cache_entry.uuid.to_string(),
-            cache_entry.decoded_text.clone(),
-            path_json,
-            successful,
-            cache_entry.execution_time_ms,
-            get_timestamp(),
-            cache_entry.encoded_text.clone(),
-        ),
-    );
-    transaction.commit()?;
-    conn_result
+    retry_on_busy(|| {
+        let transaction = conn.transaction()?;
+        let conn_result = transaction.execute(
+            "UPDATE cache SET 
+                uuid = $1,
+                decoded_text = $2,
+                path = $3,
+                successful = $4,
+                execution_time_ms = $5,
+                timestamp = $6,
+                ares_version = $7
+                WHERE input_hash = $8;",
+            (
+                cache_entry.uuid.to_string(),
+                cache_entry.decoded_text.clone(),
+                &path_json,
+                successful,
+                cache_entry.execution_time_ms,
+                get_timestamp(),
+                CACHE_VERSION,
+                hash_input(&cache_entry.encoded_text),
+            ),
+        );
+        transaction.commit()?;
+        conn_result
+    })
 }
 
 /// Adds a new decode failure record to the human_rejection table
@@ -343,25 +1296,61 @@ pub fn insert_human_rejection(
     uuid: Uuid,
     plaintext: &str,
     check_result: &CheckResult,
+) -> Result<usize, rusqlite::Error> {
+    insert_human_feedback(uuid, plaintext, check_result, "", &serde_json::Value::Null)
+}
+
+/// Like [`insert_human_rejection`], also recording the decoder which produced
+/// the plaintext and a summary of the text, so false positives can be studied
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn insert_human_feedback(
+    uuid: Uuid,
+    plaintext: &str,
+    check_result: &CheckResult,
+    decoder: &str,
+    features: &serde_json::Value,
 ) -> Result<usize, rusqlite::Error> {
     let mut conn = get_db_connection()?;
-    let transaction = conn.transaction()?;
-    let conn_result = transaction.execute(
-        "INSERT INTO human_rejection (
-            uuid,
-            plaintext,
-            checker,
-            timestamp)
-        VALUES ($1, $2, $3, $4)",
-        (
-            uuid.to_string(),
-            plaintext.to_owned(),
-            check_result.checker_name,
-            get_timestamp(),
-        ),
-    );
-    transaction.commit()?;
-    conn_result
+    retry_on_busy(|| {
+        let transaction = conn.transaction()?;
+        let conn_result = transaction.execute(
+            "INSERT INTO human_rejection (
+                uuid,
+                plaintext,
+                checker,
+                timestamp,
+                decoder,
+                features)
+            VALUES ($1, $2, $3, $4, $5, $6)",
+            (
+                uuid.to_string(),
+                plaintext.to_owned(),
+                check_result.checker_name,
+                get_timestamp(),
+                decoder,
+                (!features.is_null()).then(|| features.to_string()),
+            ),
+        );
+        transaction.commit()?;
+        conn_result
+    })
+}
+
+/// Returns true if a human has rejected this plaintext when the given checker found it
+///
+/// # Errors
+///
+/// Returns rusqlite::Error on error
+pub fn was_rejected(plaintext: &str, checker: &str) -> Result<bool, rusqlite::Error> {
+    let conn = get_db_connection()?;
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM human_rejection WHERE plaintext = $1 AND checker = $2)",
+        (plaintext, checker),
+        |row| row.get(0),
+    )
 }
 
 /// Searches the database for a human_rejection table row that matches the given plaintext
@@ -405,22 +1394,24 @@ pub fn update_human_rejection(
     check_result: &CheckResult,
 ) -> Result<usize, rusqlite::Error> {
     let mut conn = get_db_connection()?;
-    let transaction = conn.transaction()?;
-    let conn_result = transaction.execute(
-        "UPDATE human_rejection SET 
-            uuid = $1,
-            checker = $2,
-            timestamp = $3
-            WHERE plaintext = $4;",
-        (
-            uuid.to_string(),
-            check_result.checker_name,
-            get_timestamp(),
-            plaintext.to_owned(),
-        ),
-    );
-    transaction.commit()?;
-    conn_result
+    retry_on_busy(|| {
+        let transaction = conn.transaction()?;
+        let conn_result = transaction.execute(
+            "UPDATE human_rejection SET 
+                uuid = $1,
+                checker = $2,
+                timestamp = $3
+                WHERE plaintext = $4;",
+            (
+                uuid.to_string(),
+                check_result.checker_name,
+                get_timestamp(),
+                plaintext.to_owned(),
+            ),
+        );
+        transaction.commit()?;
+        conn_result
+    })
 }
 
 /// Removes the human_rejection row corresponding to the given plaintext
@@ -432,13 +1423,15 @@ pub fn update_human_rejection(
 /// Returns sqlite::Error on error
 pub fn delete_human_rejection(plaintext: &str) -> Result<usize, rusqlite::Error> {
     let mut conn = get_db_connection()?;
-    let transaction = conn.transaction()?;
-    let conn_result = transaction.execute(
-        "DELETE FROM human_rejection WHERE plaintext = $1",
-        (plaintext.to_owned(),),
-    );
-    transaction.commit()?;
-    conn_result
+    retry_on_busy(|| {
+        let transaction = conn.transaction()?;
+        let conn_result = transaction.execute(
+            "DELETE FROM human_rejection WHERE plaintext = $1",
+            (plaintext.to_owned(),),
+        );
+        transaction.commit()?;
+        conn_result
+    })
 }
 
 #[cfg(test)]
@@ -454,8 +1447,252 @@ mod tests {
         english::EnglishChecker,
         CheckerTypes,
     };
+    use std::collections::HashSet;
     use uuid::Uuid;
 
+    #[test]
+    fn failed_attempts_apply_to_the_same_settings_and_timeout() {
+        let config = crate::config::Config {
+            timeout: 5,
+            ..Default::default()
+        };
+        let row = FailedAttemptRow::new("uncrackable", &config);
+        assert!(row.still_applies(&config));
+
+        let longer = crate::config::Config {
+            timeout: 10,
+            ..Default::default()
+        };
+        assert!(!row.still_applies(&longer));
+
+        let with_crib = crate::config::Config {
+            regex: Some("flag".to_string()),
+            ..Default::default()
+        };
+        assert_ne!(row.config_fingerprint, config_fingerprint(&with_crib));
+        assert_eq!(row.config_fingerprint, config_fingerprint(&longer));
+    }
+
+    /// Whether `config` fingerprints differently from the default config
+    fn changes_fingerprint(config: crate::config::Config) -> bool {
+        config_fingerprint(&config) != config_fingerprint(&crate::config::Config::default())
+    }
+
+    #[test]
+    fn the_crib_changes_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            crib: Some("attack".to_string()),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn the_letter_map_changes_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            letter_map: Some("Z=T".to_string()),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn the_custom_alphabet_changes_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            custom_alphabet: Some("ZYXWVUTSRQPONMLKJIHGFEDCBA".to_string()),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn the_languages_change_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            languages: vec![crate::cryptanalysis::Language::French],
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn the_seed_changes_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            seed: Some(7),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn the_wordlist_changes_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            wordlist: Some(HashSet::from(["kenobi".to_string()])),
+            ..Default::default()
+        }));
+        // However the set happens to be ordered
+        let words: Vec<String> = (0..50).map(|i| format!("word{i}")).collect();
+        let wordlist = |words: &[String]| crate::config::Config {
+            wordlist: Some(words.iter().cloned().collect()),
+            ..Default::default()
+        };
+        let reversed: Vec<String> = words.iter().rev().cloned().collect();
+        assert_eq!(
+            config_fingerprint(&wordlist(&words)),
+            config_fingerprint(&wordlist(&reversed))
+        );
+    }
+
+    #[test]
+    fn the_dictionary_words_change_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            dictionary_words: vec!["kenobi".to_string()],
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn the_dictionary_prefixes_change_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            dictionary_prefixes: vec!["flag{".to_string()],
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn enhanced_detection_changes_the_fingerprint() {
+        assert!(changes_fingerprint(crate::config::Config {
+            enhanced_detection: true,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn runs_are_read_back_newest_first() {
+        set_test_db_path();
+        let config = crate::config::Config::default();
+        let found = crate::DecoderResult {
+            text: vec!["hello".into()],
+            path: vec![CrackResult::from_decoder_info("Base64", "", "", "aGVsbG8=")],
+            confidence: 0.0,
+        };
+        let mut run = RunRow::start("aGVsbG8=", &config);
+        run.finish(&Ok(found), 12);
+        insert_run(&run).unwrap();
+        let mut run = RunRow::start("xkqzvbpt", &config);
+        run.finish(&Err(AresError::Timeout), 5000);
+        insert_run(&run).unwrap();
+
+        let runs = read_runs(None, 10).unwrap();
+        let outcomes: Vec<&str> = runs.iter().map(|run| run.outcome.as_str()).collect();
+        assert_eq!(outcomes, ["timeout", "success"]);
+        assert_eq!(runs[1].path, ["Base64"]);
+        assert_eq!(runs[1].plaintext.as_deref(), Some("hello"));
+
+        let runs = read_runs(Some("xkqzvbpt"), 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].duration_ms, 5000);
+        assert_eq!(read_runs(None, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn dictionary_words_are_kept_once_and_can_be_removed() {
+        set_test_db_path();
+        let row = |word: &str, prefix| DictionaryRow {
+            word: word.to_string(),
+            prefix,
+        };
+        let words = [row("kestrel", false), row("flag{", true), row("kestrel", false)];
+        assert_eq!(insert_dictionary_words(&words).unwrap(), 2);
+        assert_eq!(read_dictionary().unwrap(), [row("kestrel", false), row("flag{", true)]);
+
+        assert_eq!(delete_dictionary_words(&["flag{".to_string()]).unwrap(), 1);
+        assert_eq!(read_dictionary().unwrap(), [row("kestrel", false)]);
+    }
+
+    #[test]
+    fn concurrent_writers_share_a_wal_database() {
+        let path = std::env::temp_dir().join(format!("ares-wal-{}.sqlite", Uuid::new_v4()));
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let mut conn = rusqlite::Connection::open(&path).unwrap();
+                    configure_connection(&mut conn).unwrap();
+                    retry_on_busy(|| create_tables(&conn)).unwrap();
+                    for row in 0..20 {
+                        let text = format!("writer {writer} row {row}");
+                        let (_, _, entry) = generate_cache_row(Uuid::new_v4(), &text, "decoded");
+                        retry_on_busy(|| insert_cache_with(&mut conn, &entry)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        let mode: String = conn
+            .query_row("PRAGMA journal_mode", (), |row| row.get(0))
+            .unwrap();
+        let rows: usize = conn
+            .query_row("SELECT COUNT(*) FROM cache", (), |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+        assert_eq!(rows, 80);
+        drop(conn);
+        for suffix in ["", "-wal", "-shm"] {
+            let mut file = path.clone().into_os_string();
+            file.push(suffix);
+            let _ = std::fs::remove_file(file);
+        }
+    }
+
+    #[test]
+    fn busy_errors_are_retried() {
+        let busy = || {
+            rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+                None,
+            )
+        };
+        let mut calls = 0;
+        let result = retry_on_busy(|| {
+            calls += 1;
+            if calls < 3 {
+                Err(busy())
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: Result<(), _> = retry_on_busy(|| {
+            calls += 1;
+            Err(rusqlite::Error::InvalidQuery)
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn database_path_prefers_config_then_env() {
+        let env = Some(std::ffi::OsString::from("/env/ares.sqlite"));
+        assert_eq!(
+            choose_database_path(Some("/config/ares.sqlite"), env.clone()),
+            std::path::PathBuf::from("/config/ares.sqlite")
+        );
+        assert_eq!(
+            choose_database_path(None, env.clone()),
+            std::path::PathBuf::from("/env/ares.sqlite")
+        );
+        assert_eq!(
+            choose_database_path(Some(""), env),
+            std::path::PathBuf::from("/env/ares.sqlite")
+        );
+        assert!(choose_database_path(None, None).ends_with(".ares/database.sqlite"));
+        assert_eq!(
+            choose_database_path(None, Some(IN_MEMORY_DATABASE.into())),
+            std::path::PathBuf::from(SHARED_MEMORY_URI)
+        );
+    }
+
     struct MockDecoder;
     impl Crack for Decoder<MockDecoder> {
         fn new() -> Decoder<MockDecoder> {
@@ -471,8 +1708,8 @@ mod tests {
 
         /// Mocked cracking function
         fn crack(&self, text: &str, _checker: &CheckerTypes, _config: &crate::config::Config) -> CrackResult {
-            let mut results = CrackResult::new(self, text.to_string());
-            results.unencrypted_text = Some(vec![String::from("mock decoded text")]);
+            let mut results = CrackResult::new(self, text);
+            results.unencrypted_text = Some(vec![String::from("mock decoded text").into()]);
             results
         }
 
@@ -497,6 +1734,14 @@ mod tests {
     fn set_test_db_path() {
         let path = std::path::PathBuf::from(String::from("file::memory:?cache=shared"));
         let _ = DB_PATH.set(Some(path));
+        // The pool keeps the shared database open between tests, so empty it
+        init_database()
+            .unwrap()
+            .execute_batch(
+                "DELETE FROM cache; DELETE FROM human_rejection; DELETE FROM failed_attempt;
+                DELETE FROM runs; DELETE FROM dictionary;",
+            )
+            .unwrap();
     }
 
     /// Helper function for generating a cache row
@@ -508,7 +1753,7 @@ mod tests {
         let mock_decoder = Decoder::<MockDecoder>::new();
         let mut mock_crack_result = CrackResult::new(&mock_decoder, encoded_text.to_owned());
         mock_crack_result.success = true;
-        mock_crack_result.unencrypted_text = Some(vec![decoded_text.to_owned()]);
+        mock_crack_result.unencrypted_text = Some(vec![decoded_text.to_owned().into()]);
 
         let expected_cache_row = CacheRow {
             uuid,
@@ -521,6 +1766,7 @@ mod tests {
             successful: true,
             execution_time_ms: 100,
             timestamp: String::new(),
+            ares_version: CACHE_VERSION.to_string(),
         };
 
         let cache_entry = CacheEntry {
@@ -599,6 +1845,9 @@ mod tests {
         assert_eq!(name_list[4], "successful");
         assert_eq!(name_list[5], "execution_time_ms");
         assert_eq!(name_list[6], "timestamp");
+        assert_eq!(name_list[7], "ares_version");
+        assert_eq!(name_list[8], "last_hit");
+        assert_eq!(name_list[9], "input_hash");
 
         let type_result = stmt.query_map([], |row| row.get::<usize, String>(2));
         assert!(type_result.is_ok());
@@ -611,6 +1860,115 @@ mod tests {
         assert_eq!(type_list[4], "BOOLEAN");
         assert_eq!(type_list[5], "INTEGER");
         assert_eq!(type_list[6], "DATETIME");
+        assert_eq!(type_list[7], "TEXT");
+        assert_eq!(type_list[8], "DATETIME");
+        assert_eq!(type_list[9], "TEXT");
+    }
+
+    #[test]
+    fn old_cache_tables_get_a_version_column() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE cache (uuid TEXT PRIMARY KEY NOT NULL, encoded_text TEXT NOT NULL);",
+            (),
+        )
+        .unwrap();
+        conn.execute("INSERT INTO cache VALUES ('a', 'b');", ())
+            .unwrap();
+        migrate_cache_table(&conn).unwrap();
+        // Running it again leaves the table alone
+        migrate_cache_table(&conn).unwrap();
+
+        let (version, last_hit): (String, Option<String>) = conn
+            .query_row("SELECT ares_version, last_hit FROM cache;", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(version, "");
+        assert_eq!(last_hit, None);
+    }
+
+    #[test]
+    fn eviction_keeps_the_most_recently_used_rows() {
+        // A private database, as eviction empties the shared test one
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE cache (
+                uuid TEXT PRIMARY KEY NOT NULL,
+                encoded_text TEXT NOT NULL,
+                decoded_text TEXT NOT NULL,
+                path JSON NOT NULL,
+                successful BOOLEAN NOT NULL DEFAULT true,
+                execution_time_ms INTEGER NOT NULL,
+                timestamp DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            (),
+        )
+        .unwrap();
+        migrate_cache_table(&conn).unwrap();
+        for text in ["first", "second", "third"] {
+            let (_, _, entry) = generate_cache_row(Uuid::new_v4(), text, "decoded");
+            insert_cache_with(&mut conn, &entry).unwrap();
+        }
+        // Reading "first" makes "second" the least recently used
+        conn.execute(
+            "UPDATE cache SET last_hit = '9999-01-01 00:00:00' WHERE encoded_text = 'first'",
+            (),
+        )
+        .unwrap();
+
+        assert_eq!(evict_cache_with(&conn, 2).unwrap(), 1);
+        assert_eq!(evict_cache_with(&conn, 2).unwrap(), 0);
+        assert!(read_cache_with(&conn, "second").unwrap().is_none());
+        assert!(read_cache_with(&conn, "first").unwrap().is_some());
+        assert!(touch_cache_with(&conn, "third").unwrap() == 1);
+    }
+
+    #[test]
+    fn large_inputs_are_found_by_hash_and_stored_as_a_preview() {
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE cache (
+                uuid TEXT PRIMARY KEY NOT NULL,
+                encoded_text TEXT NOT NULL,
+                decoded_text TEXT NOT NULL,
+                path JSON NOT NULL,
+                successful BOOLEAN NOT NULL DEFAULT true,
+                execution_time_ms INTEGER NOT NULL,
+                timestamp DATETIME DEFAULT CURRENT_TIMESTAMP
+            );",
+            (),
+        )
+        .unwrap();
+        migrate_cache_table(&conn).unwrap();
+        let large = "é".repeat(1_000_000);
+        let (_, _, entry) = generate_cache_row(Uuid::new_v4(), &large, "decoded");
+        insert_cache_with(&mut conn, &entry).unwrap();
+
+        let row = read_cache_with(&conn, &large).unwrap().unwrap();
+        assert_eq!(row.encoded_text.chars().count(), CACHE_PREVIEW_CHARS + 1);
+        assert!(row.encoded_text.ends_with('…'));
+        assert!(!row.path[0].contains(&large));
+        assert!(read_cache_with(&conn, &"é".repeat(CACHE_PREVIEW_CHARS))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn cache_rows_go_stale() {
+        let (_, mut row, _) = generate_cache_row(Uuid::new_v4(), "aGk=", "hi");
+        row.timestamp = get_timestamp();
+        assert!(row.is_fresh(None));
+        assert!(row.is_fresh(Some(60)));
+
+        row.timestamp = (Local::now() - chrono::TimeDelta::hours(2))
+            .format(TIMESTAMP_FORMAT)
+            .to_string();
+        assert!(!row.is_fresh(Some(60)));
+        assert!(row.is_fresh(None));
+
+        row.ares_version = "0.0.1".to_string();
+        assert!(!row.is_fresh(None));
     }
 
     #[test]
@@ -630,6 +1988,8 @@ mod tests {
         assert_eq!(name_list[1], "plaintext");
         assert_eq!(name_list[2], "checker");
         assert_eq!(name_list[3], "timestamp");
+        assert_eq!(name_list[4], "decoder");
+        assert_eq!(name_list[5], "features");
 
         let type_result = stmt.query_map([], |row| row.get::<usize, String>(2));
         assert!(type_result.is_ok());
@@ -639,6 +1999,8 @@ mod tests {
         assert_eq!(type_list[1], "TEXT");
         assert_eq!(type_list[2], "TEXT");
         assert_eq!(type_list[3], "DATETIME");
+        assert_eq!(type_list[4], "TEXT");
+        assert_eq!(type_list[5], "JSON");
     }
 
     #[test]
@@ -661,6 +2023,7 @@ mod tests {
                 successful: row.get_unwrap(4),
                 execution_time_ms: row.get_unwrap(5),
                 timestamp: row.get_unwrap(6),
+                ares_version: row.get_unwrap(7),
             })
         });
         assert!(query_result.is_ok());
@@ -697,6 +2060,7 @@ mod tests {
                 successful: row.get_unwrap(4),
                 execution_time_ms: row.get_unwrap(5),
                 timestamp: row.get_unwrap(6),
+                ares_version: row.get_unwrap(7),
             })
         });
         assert!(query_result.is_ok());
@@ -744,6 +2108,7 @@ mod tests {
                 successful: row.get_unwrap(4),
                 execution_time_ms: row.get_unwrap(5),
                 timestamp: row.get_unwrap(6),
+                ares_version: row.get_unwrap(7),
             })
         });
         let mut query = query_result.unwrap();
@@ -1156,6 +2521,37 @@ mod tests {
         assert_eq!(row, expected_row);
     }
 
+    #[test]
+    fn human_feedback_is_remembered_per_checker() {
+        set_test_db_path();
+        let conn = init_database().unwrap();
+
+        let plaintext = "rejected feedback plaintext";
+        let (check_result, _) =
+            generate_human_rejection_row(Uuid::new_v4(), plaintext, Checker::<Athena>::new());
+        let features = serde_json::json!({ "length": plaintext.len() });
+        insert_human_feedback(
+            Uuid::new_v4(),
+            plaintext,
+            &check_result,
+            "Base64",
+            &features,
+        )
+        .unwrap();
+
+        let (decoder, stored): (String, String) = conn
+            .query_row(
+                "SELECT decoder, features FROM human_rejection WHERE plaintext = $1",
+                [plaintext],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(decoder, "Base64");
+        assert_eq!(stored, features.to_string());
+        assert!(was_rejected(plaintext, check_result.checker_name).unwrap());
+        assert!(!was_rejected(plaintext, "English Checker").unwrap());
+    }
+
     #[test]
     fn human_rejection_insert_2_success() {
         set_test_db_path();
@@ -0,0 +1,197 @@
+//! Word lists stored as finite state transducers
+//!
+//! A [`Dictionary`] shares the prefixes and suffixes of its words, so a large
+//! list takes a fraction of the memory of a `HashSet`, and can answer prefix
+//! queries: which words start with some letters, and which words some text
+//! starts with. [`Dictionary::segment`] uses the latter to find the words in
+//! text without spaces, such as the output of Playfair.
+
+use fst::automaton::{Automaton, Str};
+use fst::raw::Node;
+use fst::{IntoStreamer, Set, Streamer};
+
+/// A sorted set of words with prefix queries
+#[derive(Clone)]
+pub struct Dictionary {
+    /// The words, as an FST
+    set: Set<Vec<u8>>,
+}
+
+impl Dictionary {
+    /// Builds a dictionary from words in any order. Duplicates are kept once.
+    ///
+    /// ```
+    /// use ares::storage::dictionary::Dictionary;
+    ///
+    /// let dictionary = Dictionary::new(["the", "quick", "brown", "fox", "the"]);
+    /// assert_eq!(dictionary.len(), 4);
+    /// assert!(dictionary.contains("quick"));
+    /// assert!(!dictionary.contains("qui"));
+    /// ```
+    ///
+    /// # Panics
+    /// Never, as the words are sorted and deduplicated before the FST is built.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut words: Vec<S> = words.into_iter().collect();
+        words.sort_unstable_by(|a, b| a.as_ref().cmp(b.as_ref()));
+        words.dedup_by(|a, b| a.as_ref() == b.as_ref());
+        let set = Set::from_iter(words.iter().map(|word| word.as_ref()))
+            .expect("sorted and deduplicated words always build an FST");
+        Dictionary { set }
+    }
+
+    /// Whether `word` is in the dictionary
+    pub fn contains(&self, word: &str) -> bool {
+        self.set.contains(word)
+    }
+
+    /// How many words there are
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Whether there are no words
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Whether any word starts with `prefix`
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        let fst = self.set.as_fst();
+        let mut node = fst.root();
+        for byte in prefix.bytes() {
+            match step(fst, node, byte) {
+                Some(next) => node = next,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// The words which start with `prefix`, in sorted order
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut stream = self
+            .set
+            .search(Str::new(prefix).starts_with())
+            .into_stream();
+        let mut words = Vec::new();
+        while let Some(word) = stream.next() {
+            words.push(String::from_utf8_lossy(word).into_owned());
+        }
+        words
+    }
+
+    /// The lengths in bytes of the words `text` starts with, shortest first
+    ///
+    /// ```
+    /// use ares::storage::dictionary::Dictionary;
+    ///
+    /// let dictionary = Dictionary::new(["the", "there", "then"]);
+    /// assert_eq!(dictionary.prefix_lengths("thereafter"), vec![3, 5]);
+    /// ```
+    pub fn prefix_lengths(&self, text: &str) -> Vec<usize> {
+        let fst = self.set.as_fst();
+        let mut node = fst.root();
+        let mut lengths = Vec::new();
+        for (i, byte) in text.bytes().enumerate() {
+            match step(fst, node, byte) {
+                Some(next) => node = next,
+                None => break,
+            }
+            if node.is_final() {
+                lengths.push(i + 1);
+            }
+        }
+        lengths
+    }
+
+    /// Splits text without spaces into the words of at least `min_len` bytes
+    /// which cover the most of it. Letters which aren't part of a word are
+    /// left out, so the total length of the words is how much was recognised.
+    ///
+    /// ```
+    /// use ares::storage::dictionary::Dictionary;
+    ///
+    /// let dictionary = Dictionary::new(["the", "there", "quick", "red", "fox"]);
+    /// assert_eq!(dictionary.segment("thequickxfox", 3), vec!["the", "quick", "fox"]);
+    /// ```
+    pub fn segment<'a>(&self, text: &'a str, min_len: usize) -> Vec<&'a str> {
+        // covered[i] is the most of text[i..] words can cover, and next[i]
+        // the length of the word starting at i on the way to it, if any
+        let len = text.len();
+        let mut covered = vec![0; len + 1];
+        let mut next = vec![0; len + 1];
+        let starts: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        for (position, &start) in starts.iter().enumerate().rev() {
+            let skip = starts.get(position + 1).copied().unwrap_or(len);
+            covered[start] = covered[skip];
+            for word_len in self.prefix_lengths(&text[start..]) {
+                if word_len >= min_len && word_len + covered[start + word_len] > covered[start] {
+                    covered[start] = word_len + covered[start + word_len];
+                    next[start] = word_len;
+                }
+            }
+        }
+
+        let mut words = Vec::new();
+        let mut position = 0;
+        while position < starts.len() {
+            let start = starts[position];
+            if next[start] > 0 {
+                words.push(&text[start..start + next[start]]);
+                let end = start + next[start];
+                position = starts.partition_point(|&i| i < end);
+            } else {
+                position += 1;
+            }
+        }
+        words
+    }
+}
+
+impl std::fmt::Debug for Dictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dictionary")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Follows the transition for `byte` out of `node`, if there is one
+fn step<'f>(fst: &'f fst::raw::Fst<Vec<u8>>, node: Node<'f>, byte: u8) -> Option<Node<'f>> {
+    node.find_input(byte)
+        .map(|i| fst.node(node.transition(i).addr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_queries_find_words_and_prefixes() {
+        let dictionary = Dictionary::new(["car", "card", "care", "cat", "dog"]);
+        assert!(dictionary.has_prefix("car"));
+        assert!(dictionary.has_prefix(""));
+        assert!(!dictionary.has_prefix("cb"));
+        assert_eq!(
+            dictionary.words_with_prefix("car"),
+            vec!["car", "card", "care"]
+        );
+        assert!(dictionary.words_with_prefix("x").is_empty());
+        assert_eq!(dictionary.prefix_lengths("cards"), vec![3, 4]);
+    }
+
+    #[test]
+    fn segmentation_covers_as_much_text_as_possible() {
+        let dictionary = Dictionary::new(["he", "hell", "hello", "low", "lower", "world", "or"]);
+        // Taking "hell" first would leave "oworld" only partly covered
+        assert_eq!(dictionary.segment("helloworld", 2), vec!["hello", "world"]);
+        assert_eq!(dictionary.segment("zzhellozz", 3), vec!["hello"]);
+        assert!(dictionary.segment("", 1).is_empty());
+        assert!(dictionary.segment("héllo", 1).is_empty());
+    }
+}
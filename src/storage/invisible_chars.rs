@@ -0,0 +1,232 @@
+//! Invisible characters, for spotting and removing Unicode steganography
+//!
+//! The built-in list is extended by `~/.ares/invisible_chars.txt`, which
+//! uses the same format: one character per line, as its code point and name,
+//! e.g. `U+E0020 TAG SPACE`. Lines starting with `#` are ignored. New
+//! tricks turn up all the time, so they can be added to the file, or with
+//! [`add`], without waiting for a release.
+
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// The characters Ares ships with
+const BUILT_IN: &str = include_str!("invisible_chars/chars.txt");
+
+/// Every invisible character, built in or from [`user_file`]
+pub static INVISIBLE_CHARS: Lazy<RwLock<HashSet<char>>> = Lazy::new(|| {
+    let mut chars: HashSet<char> = parse(BUILT_IN).map(|(c, _)| c).collect();
+    if let Some(content) = user_file().and_then(|path| fs::read_to_string(path).ok()) {
+        chars.extend(parse(&content).map(|(c, _)| c));
+    }
+    RwLock::new(chars)
+});
+
+/// An invisible character and where it is listed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvisibleChar {
+    /// The character
+    pub char: char,
+    /// Its Unicode name, or whatever the user called it
+    pub name: String,
+    /// Whether Ares ships with it, rather than it being added by the user
+    pub built_in: bool,
+}
+
+/// Returns `~/.ares/invisible_chars.txt`, where users list their own invisible characters
+pub fn user_file() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ares").join("invisible_chars.txt"))
+}
+
+/// Whether `c` is an invisible character
+pub fn is_invisible(c: char) -> bool {
+    match INVISIBLE_CHARS.read() {
+        Ok(chars) => chars.contains(&c),
+        Err(poisoned) => poisoned.into_inner().contains(&c),
+    }
+}
+
+/// Returns the built-in characters followed by the ones listed in `file`,
+/// in the order they are listed
+///
+/// # Errors
+/// Returns an error if `file` exists but can't be read.
+pub fn list(file: &Path) -> Result<Vec<InvisibleChar>, String> {
+    let mut chars: Vec<InvisibleChar> = parse(BUILT_IN)
+        .map(|(char, name)| InvisibleChar {
+            char,
+            name,
+            built_in: true,
+        })
+        .collect();
+    for (char, name) in parse(&read_user_file(file)?) {
+        if !chars.iter().any(|listed| listed.char == char) {
+            chars.push(InvisibleChar {
+                char,
+                name,
+                built_in: false,
+            });
+        }
+    }
+    Ok(chars)
+}
+
+/// Lists `c` in `file` as `name`, and treats it as invisible from now on
+///
+/// # Errors
+/// Returns an error if `c` is already invisible, the name spans several
+/// lines, or `file` can't be written.
+pub fn add(file: &Path, c: char, name: &str) -> Result<InvisibleChar, String> {
+    let name = name.trim();
+    if name.contains('\n') {
+        return Err("The name must fit on one line".to_string());
+    }
+    if is_invisible(c) {
+        return Err(format!("{} is already invisible", code_point(c)));
+    }
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    }
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file)
+        .and_then(|mut f| writeln!(f, "{} {}", code_point(c), name))
+        .map_err(|e| format!("Can't write {}: {}", file.display(), e))?;
+    write_lock().insert(c);
+    Ok(InvisibleChar {
+        char: c,
+        name: name.to_string(),
+        built_in: false,
+    })
+}
+
+/// Removes `c` from `file`, so it is no longer treated as invisible
+///
+/// # Errors
+/// Returns an error if `c` is built in or isn't listed in `file`, or `file`
+/// can't be written.
+pub fn remove(file: &Path, c: char) -> Result<(), String> {
+    if parse(BUILT_IN).any(|(built_in, _)| built_in == c) {
+        return Err(format!(
+            "{} is built in and can't be removed",
+            code_point(c)
+        ));
+    }
+    let content = read_user_file(file)?;
+    let kept: Vec<&str> = content
+        .lines()
+        .filter(|line| parse(line).next().map(|(listed, _)| listed) != Some(c))
+        .collect();
+    if kept.len() == content.lines().count() {
+        return Err(format!(
+            "{} isn't listed in {}",
+            code_point(c),
+            file.display()
+        ));
+    }
+    let mut kept = kept.join("\n");
+    if !kept.is_empty() {
+        kept.push('\n');
+    }
+    fs::write(file, kept).map_err(|e| format!("Can't write {}: {}", file.display(), e))?;
+    write_lock().remove(&c);
+    Ok(())
+}
+
+/// Formats `c` as its code point, e.g. `U+200B`
+pub fn code_point(c: char) -> String {
+    format!("U+{:04X}", c as u32)
+}
+
+/// The characters and names in a list. Each line starts with a code point
+/// like `U+200B` and the rest of it is the name, which in the built-in
+/// list is followed by the character itself.
+fn parse(content: &str) -> impl Iterator<Item = (char, String)> + '_ {
+    content.lines().filter_map(|line| {
+        let line = line.trim_start();
+        if line.starts_with('#') {
+            return None;
+        }
+        let (code, name) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let c = char::from_u32(u32::from_str_radix(code.strip_prefix("U+")?, 16).ok()?)?;
+        let name = name.trim_matches(|x: char| x.is_whitespace() || x == c);
+        Some((c, name.to_string()))
+    })
+}
+
+/// Reads `file`, which is empty if it doesn't exist yet
+fn read_user_file(file: &Path) -> Result<String, String> {
+    match fs::read_to_string(file) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("Can't read {}: {}", file.display(), e)),
+    }
+}
+
+/// Locks the characters for changing, even if another thread panicked with them
+fn write_lock() -> std::sync::RwLockWriteGuard<'static, HashSet<char>> {
+    match INVISIBLE_CHARS.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invisible_chars_loaded() {
+        // Verify that the built-in list is loaded
+        assert!(!INVISIBLE_CHARS.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invisible_chars_contains_space() {
+        // Verify that the space character (U+0020) is invisible
+        assert!(is_invisible(' '));
+    }
+
+    #[test]
+    fn test_invisible_chars_contains_zero_width_space() {
+        // Verify that the zero width space (U+200B) is invisible
+        // This is a common invisible character
+        let zero_width_space = char::from_u32(0x200B).unwrap();
+        assert!(is_invisible(zero_width_space));
+    }
+
+    #[test]
+    fn user_characters_can_be_added_listed_and_removed() {
+        let file =
+            std::env::temp_dir().join(format!("ares-invisible-{}.txt", uuid::Uuid::new_v4()));
+        // A private use character, which no other test relies on
+        let c = '\u{F8FE}';
+        assert!(!is_invisible(c));
+
+        add(&file, c, "MY STEGO MARK").unwrap();
+        assert!(is_invisible(c));
+        assert!(add(&file, c, "AGAIN").is_err());
+        let listed = list(&file).unwrap();
+        assert!(listed
+            .iter()
+            .any(|i| i.char == ' ' && i.name == "SPACE" && i.built_in));
+        assert_eq!(
+            listed.last(),
+            Some(&InvisibleChar {
+                char: c,
+                name: "MY STEGO MARK".to_string(),
+                built_in: false
+            })
+        );
+
+        remove(&file, c).unwrap();
+        assert!(!is_invisible(c));
+        assert!(remove(&file, c).is_err());
+        assert!(remove(&file, ' ').is_err());
+        fs::remove_file(&file).unwrap();
+    }
+}
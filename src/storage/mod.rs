@@ -1,10 +1,18 @@
-use once_cell::sync::Lazy;
-use std::collections::HashSet;
-
+/// Binary blobs found while decoding, for `--artifacts-dir` and `-o`
+pub mod artifacts;
 /// Module housing functions for managing SQLite database
+#[cfg(feature = "cache")]
 pub mod database;
+/// Word lists with prefix queries, for recognising words in plaintext
+pub mod dictionary;
+/// Invisible characters, built in and listed in `~/.ares/invisible_chars.txt`
+pub mod invisible_chars;
+/// N-gram models kept in `~/.ares/models`, for cryptanalysis scoring
+pub mod models;
 /// Module for storing WaitAthena results
 pub mod wait_athena_storage;
+/// Wordlists kept in `~/.ares/wordlists`, referred to by name
+pub mod wordlists;
 
 /// English letter frequency distribution (A-Z)
 /// Used for frequency analysis in various decoders
@@ -14,56 +22,3 @@ pub const ENGLISH_FREQS: [f64; 26] = [
     0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056, 0.02758, // O-U
     0.00978, 0.02360, 0.00150, 0.01974, 0.00074, // V-Z
 ];
-
-/// Loads invisible character list into a HashSet
-pub static INVISIBLE_CHARS: Lazy<HashSet<char>> = Lazy::new(|| {
-    let mut entries: HashSet<char> = HashSet::new();
-
-    // Embed the file content
-    let content = include_str!("invisible_chars/chars.txt");
-
-    let content_lines = content.split('\n');
-    for line in content_lines {
-        if line.is_empty() {
-            continue;
-        }
-        let unicode_line_split: Vec<&str> = line.split_ascii_whitespace().collect();
-        if unicode_line_split.is_empty() {
-            continue;
-        }
-        let unicode_literal = unicode_line_split[0].trim_start_matches("U+");
-        if let Ok(unicode_value) = u32::from_str_radix(unicode_literal, 16) {
-            if let Some(unicode_char) = char::from_u32(unicode_value) {
-                entries.insert(unicode_char);
-            }
-        }
-    }
-
-    entries
-});
-
-// Rust tests
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_invisible_chars_loaded() {
-        // Verify that the INVISIBLE_CHARS HashSet is not empty
-        assert!(!INVISIBLE_CHARS.is_empty());
-    }
-
-    #[test]
-    fn test_invisible_chars_contains_space() {
-        // Verify that the space character (U+0020) is in the HashSet
-        assert!(INVISIBLE_CHARS.contains(&' '));
-    }
-
-    #[test]
-    fn test_invisible_chars_contains_zero_width_space() {
-        // Verify that the zero width space (U+200B) is in the HashSet
-        // This is a common invisible character
-        let zero_width_space = char::from_u32(0x200B).unwrap();
-        assert!(INVISIBLE_CHARS.contains(&zero_width_space));
-    }
-}
@@ -0,0 +1,233 @@
+//! N-gram models kept in `~/.ares/models`, for `ares models`
+//!
+//! Each model is a file named `<name>.txt` with one n-gram and its count per
+//! line, the format of `storage/ngrams/english_bigrams.txt`. Next to it is
+//! `<name>.txt.sha256`, the checksum it was published with, which is checked
+//! every time it is loaded so a truncated or edited file is never used.
+//! [`crate::cryptanalysis::ngram_model`] falls back to the embedded tables
+//! when a model isn't downloaded or fails its check.
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Models which `ares models download` can fetch
+pub const CATALOGUE: [&str; 7] = [
+    "english-bigrams",
+    "english-trigrams",
+    "english-quadgrams",
+    "french-quadgrams",
+    "german-quadgrams",
+    "italian-quadgrams",
+    "spanish-quadgrams",
+];
+
+/// Where models are downloaded from, unless [`MODELS_URL_ENV`] is set
+pub const DEFAULT_MODELS_URL: &str =
+    "https://raw.githubusercontent.com/JackkySpice/Ares/main/models";
+
+/// Environment variable pointing downloads at a mirror of [`DEFAULT_MODELS_URL`]
+pub const MODELS_URL_ENV: &str = "ARES_MODELS_URL";
+
+/// A model in the models directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelInfo {
+    /// The name it is referred to by
+    pub name: String,
+    /// Where the file is
+    pub path: PathBuf,
+    /// How many n-grams it has
+    pub ngrams: usize,
+    /// Whether it matches its checksum
+    pub verified: bool,
+}
+
+/// Returns `~/.ares/models`, where downloaded models are kept
+pub fn models_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ares").join("models"))
+}
+
+/// Downloads the model called `name` and its checksum into `dir`
+///
+/// # Errors
+/// Returns an error if the model isn't in the [`CATALOGUE`], can't be
+/// downloaded, doesn't match its checksum or can't be saved.
+#[cfg(feature = "download")]
+pub fn download(dir: &Path, name: &str) -> Result<ModelInfo, String> {
+    check_name(name)?;
+    let base = std::env::var(MODELS_URL_ENV).unwrap_or_else(|_| DEFAULT_MODELS_URL.to_string());
+    let url = format!("{}/{}.txt", base.trim_end_matches('/'), name);
+    let fetch = |url: &str| {
+        ureq::get(url)
+            .call()
+            .map_err(|e| format!("Can't download {}: {}", url, e))?
+            .into_string()
+            .map_err(|e| format!("Can't read {}: {}", url, e))
+    };
+    let text = fetch(&url)?;
+    let published = fetch(&format!("{}.sha256", url))?;
+    let expected = published.split_whitespace().next().unwrap_or_default();
+    if !checksum(&text).eq_ignore_ascii_case(expected) {
+        return Err(format!("{} doesn't match its checksum, not saving it", url));
+    }
+    fs::create_dir_all(dir).map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    let path = file_for(dir, name);
+    fs::write(&path, &text).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+    fs::write(checksum_file_for(&path), checksum(&text))
+        .map_err(|e| format!("Can't write the checksum of {}: {}", path.display(), e))?;
+    info(name, path)
+}
+
+/// Without the download feature models can't be fetched
+///
+/// # Errors
+/// Always, saying how to get the feature.
+#[cfg(not(feature = "download"))]
+pub fn download(_dir: &Path, name: &str) -> Result<ModelInfo, String> {
+    check_name(name)?;
+    Err(format!(
+        "Downloading '{}' needs Ares built with the download feature",
+        name
+    ))
+}
+
+/// Lists the models in `dir`, sorted by name
+///
+/// # Errors
+/// Returns an error if the directory exists but can't be read.
+pub fn list(dir: &Path) -> Result<Vec<ModelInfo>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(dir).map_err(|e| format!("Can't read {}: {}", dir.display(), e))?;
+    let mut models = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            info(&name, path).ok()
+        })
+        .collect::<Vec<_>>();
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(models)
+}
+
+/// Deletes the model called `name` and its checksum from `dir`
+///
+/// # Errors
+/// Returns an error if there is no such model or it can't be deleted.
+pub fn remove(dir: &Path, name: &str) -> Result<(), String> {
+    let path = file_for(dir, name);
+    if !path.is_file() {
+        return Err(format!("There is no downloaded model called '{}'", name));
+    }
+    fs::remove_file(&path).map_err(|e| format!("Can't remove {}: {}", path.display(), e))?;
+    let _ = fs::remove_file(checksum_file_for(&path));
+    Ok(())
+}
+
+/// Reads the model called `name` from `dir`, if it matches its checksum
+///
+/// # Errors
+/// Returns an error if it isn't downloaded, can't be read or fails its check.
+pub fn load(dir: &Path, name: &str) -> Result<String, String> {
+    let path = file_for(dir, name);
+    let text =
+        fs::read_to_string(&path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+    let expected = fs::read_to_string(checksum_file_for(&path))
+        .map_err(|e| format!("Can't read the checksum of {}: {}", path.display(), e))?;
+    if checksum(&text) != expected.trim() {
+        return Err(format!(
+            "{} doesn't match its checksum, download it again",
+            path.display()
+        ));
+    }
+    Ok(text)
+}
+
+/// The SHA-256 of a model file, as lowercase hex
+pub fn checksum(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Where the model called `name` is kept
+fn file_for(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.txt", name))
+}
+
+/// Where the checksum of the model at `path` is kept
+fn checksum_file_for(path: &Path) -> PathBuf {
+    let mut file = path.as_os_str().to_owned();
+    file.push(".sha256");
+    PathBuf::from(file)
+}
+
+/// Only models in the [`CATALOGUE`] can be downloaded
+fn check_name(name: &str) -> Result<(), String> {
+    if CATALOGUE.contains(&name) {
+        Ok(())
+    } else {
+        Err(format!(
+            "There is no model called '{}', try one of: {}",
+            name,
+            CATALOGUE.join(", ")
+        ))
+    }
+}
+
+/// Describes the model at `path`, counting its n-grams
+fn info(name: &str, path: PathBuf) -> Result<ModelInfo, String> {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let (ngrams, verified) = match load(dir, name) {
+        Ok(text) => (text.lines().filter(|l| !l.trim().is_empty()).count(), true),
+        Err(_) => {
+            let text = fs::read_to_string(&path)
+                .map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+            (text.lines().filter(|l| !l.trim().is_empty()).count(), false)
+        }
+    };
+    Ok(ModelInfo {
+        name: name.to_string(),
+        path,
+        ngrams,
+        verified,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn models_are_only_loaded_when_they_match_their_checksum() {
+        let dir = std::env::temp_dir().join(format!("ares-models-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let text = "TION 100\nNTHE 50\n";
+        let path = file_for(&dir, "english-quadgrams");
+        fs::write(&path, text).unwrap();
+        fs::write(checksum_file_for(&path), checksum(text)).unwrap();
+
+        assert_eq!(load(&dir, "english-quadgrams").unwrap(), text);
+        let listed = list(&dir).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].ngrams, 2);
+        assert!(listed[0].verified);
+
+        fs::write(&path, "TION 100\n").unwrap();
+        assert!(load(&dir, "english-quadgrams").is_err());
+        assert!(!list(&dir).unwrap()[0].verified);
+
+        remove(&dir, "english-quadgrams").unwrap();
+        assert!(list(&dir).unwrap().is_empty());
+        assert!(!checksum_file_for(&path).exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn only_catalogued_models_can_be_downloaded() {
+        let dir = std::env::temp_dir();
+        let error = download(&dir, "../escape").unwrap_err();
+        assert!(error.contains("english-quadgrams"), "{error}");
+    }
+}
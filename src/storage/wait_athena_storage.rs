@@ -1,6 +1,10 @@
-use lazy_static::lazy_static;
+use crate::config::Config;
+use crate::confidence::checker_and_fitness;
+use crate::cryptanalysis::best_fitness_score;
 use log::{trace, warn};
-use std::sync::Mutex;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// Represents a plaintext result with its description, checker name, and decoder name
 #[derive(Debug, Clone)]
@@ -15,24 +19,46 @@ pub struct PlaintextResult {
     pub decoder_name: String,
 }
 
-lazy_static! {
-    static ref PLAINTEXT_RESULTS: Mutex<Vec<PlaintextResult>> = Mutex::new(Vec::new());
+/// The plaintexts a `top_results` crack has found, kept per crack so cracks
+/// running at the same time don't see each other's. Clones share the same
+/// results.
+#[derive(Debug, Clone, Default)]
+pub struct PlaintextResults {
+    /// The results, shared by every clone
+    inner: Arc<Mutex<Vec<PlaintextResult>>>,
 }
 
-/// Adds a plaintext result to the storage
+impl PlaintextResults {
+    /// Every result found so far, in the order they were found
+    pub fn get(&self) -> Vec<PlaintextResult> {
+        let results = self.lock();
+        trace!("Retrieving {} plaintext results", results.len());
+        results.clone()
+    }
+
+    /// The results, recovering them if a thread panicked while adding one
+    fn lock(&self) -> MutexGuard<'_, Vec<PlaintextResult>> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                warn!("Mutex was poisoned, recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
+}
+
+/// Adds a plaintext result to the crack's results, if it is collecting them
 pub fn add_plaintext_result(
+    config: &Config,
     text: String,
     description: String,
     checker_name: String,
     decoder_name: String,
 ) {
-    let result = PlaintextResult {
-        text: text.clone(),
-        description: description.clone(),
-        checker_name: checker_name.clone(),
-        decoder_name: decoder_name.clone(),
+    let Some(plaintext_results) = &config.plaintext_results else {
+        return;
     };
-
     trace!(
         "Adding plaintext result: [{}] {} (decoder: {})",
         checker_name,
@@ -40,42 +66,113 @@ pub fn add_plaintext_result(
         decoder_name
     );
 
-    let mut results = match PLAINTEXT_RESULTS.lock() {
-        Ok(guard) => guard,
-        Err(poisoned) => {
-            warn!("Mutex was poisoned, recovering");
-            poisoned.into_inner()
-        }
-    };
-
-    results.push(result);
+    let mut results = plaintext_results.lock();
+    results.push(PlaintextResult {
+        text,
+        description,
+        checker_name,
+        decoder_name,
+    });
     trace!("Storage now has {} results", results.len());
 }
 
-/// Retrieves all plaintext results from the storage
-pub fn get_plaintext_results() -> Vec<PlaintextResult> {
-    let results = match PLAINTEXT_RESULTS.lock() {
-        Ok(guard) => guard,
-        Err(poisoned) => {
-            warn!("Mutex was poisoned, recovering");
-            poisoned.into_inner()
-        }
-    };
+/// The plaintext results ranked best first, without near-duplicates and cut
+/// to `config.top_results_count`
+pub fn get_ranked_plaintext_results(config: &Config) -> Vec<PlaintextResult> {
+    let results = config
+        .plaintext_results
+        .as_ref()
+        .map(PlaintextResults::get)
+        .unwrap_or_default();
+    rank_results(results, config)
+}
 
-    trace!("Retrieving {} plaintext results", results.len());
-    results.clone()
+/// Sorts results by how much their checker is trusted blended with how much
+/// they look like the plaintext languages, as in [`crate::confidence`], keeps
+/// the best of those which differ only in case or whitespace, and keeps
+/// `config.top_results_count`
+fn rank_results(results: Vec<PlaintextResult>, config: &Config) -> Vec<PlaintextResult> {
+    let mut scored: Vec<(f32, f64, PlaintextResult)> = results
+        .into_iter()
+        .map(|result| {
+            let fitness = best_fitness_score(&result.text, &config.languages);
+            let confidence = checker_and_fitness(&result.checker_name, fitness);
+            (confidence, fitness, result)
+        })
+        .collect();
+    // A stable sort, so equally good results stay in the order they were found
+    scored.sort_by(|(confidence_a, fitness_a, _), (confidence_b, fitness_b, _)| {
+        confidence_b
+            .partial_cmp(confidence_a)
+            .unwrap_or(Ordering::Equal)
+            .then(fitness_b.partial_cmp(fitness_a).unwrap_or(Ordering::Equal))
+    });
+
+    let mut seen = HashSet::new();
+    let ranked: Vec<PlaintextResult> = scored
+        .into_iter()
+        .map(|(_, _, result)| result)
+        .filter(|result| seen.insert(normalize_for_dedup(&result.text)))
+        .take(config.top_results_count.unwrap_or(usize::MAX))
+        .collect();
+    trace!("Ranked down to {} plaintext results", ranked.len());
+    ranked
 }
 
-/// Clears all plaintext results from the storage
-pub fn clear_plaintext_results() {
-    let mut results = match PLAINTEXT_RESULTS.lock() {
-        Ok(guard) => guard,
-        Err(poisoned) => {
-            warn!("Mutex was poisoned, recovering");
-            poisoned.into_inner()
+/// The text lowercased with runs of whitespace collapsed to one space, so
+/// results which only differ in case or spacing are counted once
+pub(crate) fn normalize_for_dedup(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(text: &str, checker_name: &str) -> PlaintextResult {
+        PlaintextResult {
+            text: text.to_string(),
+            description: String::new(),
+            checker_name: checker_name.to_string(),
+            decoder_name: "Base64".to_string(),
         }
-    };
+    }
+
+    #[test]
+    fn ranks_by_checker_and_fitness_and_drops_near_duplicates() {
+        let results = vec![
+            result("xkqzvbpt qwmn", "English Checker"),
+            result("cIXudI:ifIjneoWi\\4Xw\\ZLjdEDt", "LemmeKnow Checker"),
+            result("hello there general kenobi", "English Checker"),
+            result("Hello  there general Kenobi", "English Checker"),
+        ];
+        let ranked = rank_results(results, &Config::default());
+        let texts: Vec<&str> = ranked.iter().map(|result| result.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "hello there general kenobi",
+                "cIXudI:ifIjneoWi\\4Xw\\ZLjdEDt",
+                "xkqzvbpt qwmn"
+            ]
+        );
+    }
 
-    trace!("Clearing plaintext results (had {} results)", results.len());
-    results.clear();
+    #[test]
+    fn keeps_at_most_top_results_count() {
+        let config = Config {
+            top_results_count: Some(1),
+            ..Config::default()
+        };
+        let results = vec![
+            result("xkqzvbpt qwmn", "English Checker"),
+            result("hello there general kenobi", "English Checker"),
+        ];
+        let ranked = rank_results(results, &config);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].text, "hello there general kenobi");
+    }
 }
@@ -0,0 +1,251 @@
+//! Wordlists kept in `~/.ares/wordlists`, for `ares wordlist`
+//!
+//! Each wordlist is a file named `<name>.txt`, so `--wordlist rockyou` or
+//! `wordlist_path = "rockyou"` in the config file can refer to it by name
+//! instead of by path. Lists are imported from a file, or with the `download`
+//! feature, fetched from one of the [`CURATED`] sources.
+
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// Wordlists which `ares wordlist add <name>` can download without a file,
+/// with where they are fetched from
+pub const CURATED: [(&str, &str); 2] = [
+    (
+        "english",
+        "https://raw.githubusercontent.com/dwyl/english-words/master/words_alpha.txt",
+    ),
+    (
+        "common-passwords",
+        "https://raw.githubusercontent.com/danielmiessler/SecLists/master/Passwords/Common-Credentials/10k-most-common.txt",
+    ),
+];
+
+/// A wordlist in the wordlists directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordlistInfo {
+    /// The name it is referred to by
+    pub name: String,
+    /// Where the file is
+    pub path: PathBuf,
+    /// How many non-empty lines it has
+    pub words: usize,
+}
+
+/// Returns `~/.ares/wordlists`, where managed wordlists are kept
+pub fn wordlists_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ares").join("wordlists"))
+}
+
+/// Returns the path of the wordlist called `name_or_path` if there is one,
+/// otherwise `name_or_path` itself, so paths keep working where names are allowed
+pub fn resolve(dir: &Path, name_or_path: &str) -> PathBuf {
+    if is_valid_name(name_or_path) {
+        let managed = file_for(dir, name_or_path);
+        if managed.is_file() {
+            return managed;
+        }
+    }
+    PathBuf::from(name_or_path)
+}
+
+/// Like [`resolve`], looking in [`wordlists_dir`]
+pub fn resolve_default(name_or_path: &str) -> PathBuf {
+    match wordlists_dir() {
+        Some(dir) => resolve(&dir, name_or_path),
+        None => PathBuf::from(name_or_path),
+    }
+}
+
+/// Copies the wordlist at `source` into `dir` as `name`, replacing any list
+/// with that name
+///
+/// # Errors
+/// Returns an error if the name isn't valid or the file can't be copied.
+pub fn add(dir: &Path, name: &str, source: &Path) -> Result<WordlistInfo, String> {
+    check_name(name)?;
+    fs::create_dir_all(dir).map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    let path = file_for(dir, name);
+    fs::copy(source, &path).map_err(|e| format!("Can't copy '{}': {}", source.display(), e))?;
+    info(name, path)
+}
+
+/// Downloads the curated wordlist called `name` into `dir`
+///
+/// # Errors
+/// Returns an error if there is no curated list with that name, or it can't be
+/// downloaded or saved.
+#[cfg(feature = "download")]
+pub fn download(dir: &Path, name: &str) -> Result<WordlistInfo, String> {
+    let url = curated_url(name)?;
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| format!("Can't download {}: {}", url, e))?;
+    let text = response
+        .into_string()
+        .map_err(|e| format!("Can't read {}: {}", url, e))?;
+    fs::create_dir_all(dir).map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    let path = file_for(dir, name);
+    fs::write(&path, text).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+    info(name, path)
+}
+
+/// Without the download feature curated lists can't be fetched
+///
+/// # Errors
+/// Always, saying how to get the feature or import the list from a file instead.
+#[cfg(not(feature = "download"))]
+pub fn download(_dir: &Path, name: &str) -> Result<WordlistInfo, String> {
+    curated_url(name)?;
+    Err(format!(
+        "Downloading needs Ares built with the download feature. Add '{}' from a file instead",
+        name
+    ))
+}
+
+/// Downloads the curated wordlists in `dir` again, or only `name` if given
+///
+/// # Errors
+/// Returns an error if `name` isn't an installed curated list, or a download fails.
+pub fn update(dir: &Path, name: Option<&str>) -> Result<Vec<WordlistInfo>, String> {
+    let names: Vec<&str> = match name {
+        Some(name) => {
+            curated_url(name)?;
+            if !file_for(dir, name).is_file() {
+                return Err(format!("'{}' isn't installed, add it first", name));
+            }
+            vec![name]
+        }
+        None => CURATED
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| file_for(dir, name).is_file())
+            .collect(),
+    };
+    names.into_iter().map(|name| download(dir, name)).collect()
+}
+
+/// Lists the wordlists in `dir`, sorted by name
+///
+/// # Errors
+/// Returns an error if the directory exists but can't be read.
+pub fn list(dir: &Path) -> Result<Vec<WordlistInfo>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(dir).map_err(|e| format!("Can't read {}: {}", dir.display(), e))?;
+    let mut wordlists = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            info(&name, path).ok()
+        })
+        .collect::<Vec<_>>();
+    wordlists.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(wordlists)
+}
+
+/// Deletes the wordlist called `name` from `dir`
+///
+/// # Errors
+/// Returns an error if there is no such wordlist or it can't be deleted.
+pub fn remove(dir: &Path, name: &str) -> Result<(), String> {
+    check_name(name)?;
+    let path = file_for(dir, name);
+    if !path.is_file() {
+        return Err(format!("There is no wordlist called '{}'", name));
+    }
+    fs::remove_file(&path).map_err(|e| format!("Can't remove {}: {}", path.display(), e))
+}
+
+/// Where the wordlist called `name` is kept
+fn file_for(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.txt", name))
+}
+
+/// Names become file names, so only letters, digits, `-` and `_` are allowed
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Like [`is_valid_name`], saying what is wrong
+fn check_name(name: &str) -> Result<(), String> {
+    if is_valid_name(name) {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' isn't a valid wordlist name, use only letters, digits, '-' and '_'",
+            name
+        ))
+    }
+}
+
+/// Returns the URL of the curated list called `name`
+fn curated_url(name: &str) -> Result<&'static str, String> {
+    CURATED
+        .iter()
+        .find(|(curated, _)| *curated == name)
+        .map(|(_, url)| *url)
+        .ok_or_else(|| {
+            let names: Vec<&str> = CURATED.iter().map(|(name, _)| *name).collect();
+            format!(
+                "There is no curated wordlist called '{}', try one of: {}",
+                name,
+                names.join(", ")
+            )
+        })
+}
+
+/// Describes the wordlist at `path`, counting its words
+fn info(name: &str, path: PathBuf) -> Result<WordlistInfo, String> {
+    let file =
+        fs::File::open(&path).map_err(|e| format!("Can't open {}: {}", path.display(), e))?;
+    let words = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .count();
+    Ok(WordlistInfo {
+        name: name.to_string(),
+        path,
+        words,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wordlists_can_be_added_listed_resolved_and_removed() {
+        let dir = std::env::temp_dir().join(format!("ares-wordlists-{}", uuid::Uuid::new_v4()));
+        let source = std::env::temp_dir().join(format!("ares-words-{}.txt", uuid::Uuid::new_v4()));
+        fs::write(&source, "hello\n\nthere\ngeneral\n").unwrap();
+
+        let added = add(&dir, "greetings", &source).unwrap();
+        assert_eq!(added.words, 3);
+        assert_eq!(list(&dir).unwrap(), vec![added.clone()]);
+        assert_eq!(resolve(&dir, "greetings"), added.path);
+        assert_eq!(resolve(&dir, "words.txt"), PathBuf::from("words.txt"));
+
+        remove(&dir, "greetings").unwrap();
+        assert!(list(&dir).unwrap().is_empty());
+        assert!(remove(&dir, "greetings").is_err());
+        let _ = fs::remove_file(source);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn names_must_be_safe_file_names() {
+        let dir = std::env::temp_dir();
+        assert!(add(&dir, "../escape", Path::new("words.txt")).is_err());
+        assert!(add(&dir, "", Path::new("words.txt")).is_err());
+        assert!(update(&dir, Some("not-curated")).is_err());
+    }
+}
@@ -0,0 +1,411 @@
+//! Decoding very large inputs in chunks, for `--stream`
+//!
+//! The search holds every candidate in memory, often several at once, so a
+//! 500 MB Base64 file would need gigabytes. The encodings which can be
+//! decoded a chunk at a time (Base64, hex, quoted-printable and, with the
+//! `compression` feature, gzip) are
+//! instead peeled off here, one layer at a time, from a reader into a
+//! temporary file. Once what is left is no bigger than
+//! `config.stream_threshold` it is searched as usual. Above the threshold the
+//! search skips every decoder which can't stream, see [`StreamEncoding::of_decoder`].
+//!
+//! Each peeled layer is put on the path like any other step, but only keeps
+//! the start of the text it decoded from and to.
+
+use crate::byte_text::{bytes_to_text, Candidate};
+use crate::decoders::base64_decoder::Base64Decoder;
+use crate::decoders::crack_results::CrackResult;
+#[cfg(feature = "compression")]
+use crate::decoders::gzip_decoder::GzipDecoder;
+use crate::decoders::hexadecimal_decoder::HexadecimalDecoder;
+use crate::decoders::interface::{Crack, Decoder};
+use crate::decoders::quoted_printable_decoder::QuotedPrintableDecoder;
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+#[cfg(feature = "compression")]
+use flate2::read::MultiGzDecoder;
+use log::debug;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Cursor, Read, Write};
+use std::path::PathBuf;
+
+/// How much of the input is looked at to tell which encoding it is in
+const SNIFF_LEN: usize = 4096;
+
+/// How much of each layer's input and output is kept on its path step
+const PREVIEW_LEN: usize = 1024;
+
+/// The most layers peeled off, so a gzip bomb of gzips can't run forever
+const MAX_LAYERS: usize = 32;
+
+/// An encoding which can be decoded a chunk at a time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamEncoding {
+    /// Standard or URL-safe Base64, with or without line breaks
+    Base64,
+    /// Hex digits, with or without whitespace between them
+    Hexadecimal,
+    /// Quoted-printable, as used in email
+    QuotedPrintable,
+    /// gzip compressed data
+    #[cfg(feature = "compression")]
+    Gzip,
+}
+
+impl StreamEncoding {
+    /// The encoding `head`, the start of an input, looks like it is in
+    ///
+    /// ```
+    /// use ares::streaming::StreamEncoding;
+    ///
+    /// assert_eq!(StreamEncoding::sniff(b"68656c6c6f"), Some(StreamEncoding::Hexadecimal));
+    /// assert_eq!(StreamEncoding::sniff(b"aGVsbG8gd29ybGQ="), Some(StreamEncoding::Base64));
+    /// assert_eq!(StreamEncoding::sniff(b"hello world"), None);
+    /// ```
+    pub fn sniff(head: &[u8]) -> Option<Self> {
+        #[cfg(feature = "compression")]
+        if head.starts_with(&[0x1f, 0x8b]) {
+            return Some(StreamEncoding::Gzip);
+        }
+        let compact: Vec<u8> = head
+            .iter()
+            .copied()
+            .filter(|b| !b.is_ascii_whitespace())
+            .collect();
+        if compact.len() < 8 {
+            return None;
+        }
+        if compact.iter().all(u8::is_ascii_hexdigit) {
+            Some(StreamEncoding::Hexadecimal)
+        } else if head
+            .iter()
+            // Base64 is wrapped into lines, but never has spaces
+            .all(|b| b.is_ascii_alphanumeric() || b"+/-_=\r\n".contains(b))
+        {
+            Some(StreamEncoding::Base64)
+        } else if looks_quoted_printable(head) {
+            Some(StreamEncoding::QuotedPrintable)
+        } else {
+            None
+        }
+    }
+
+    /// The streaming encoding a decoder of the search decodes, if it has one
+    pub fn of_decoder(name: &str) -> Option<Self> {
+        match name {
+            "Base64" => Some(StreamEncoding::Base64),
+            "Hexadecimal" => Some(StreamEncoding::Hexadecimal),
+            "Quoted-Printable" => Some(StreamEncoding::QuotedPrintable),
+            _ => None,
+        }
+    }
+
+    /// Decodes everything `reader` gives into `writer`, a chunk at a time,
+    /// returning how many bytes were written
+    ///
+    /// # Errors
+    /// Returns an error if reading or writing fails, or if the input turns
+    /// out not to be in this encoding after all.
+    pub fn decode(self, reader: impl Read, writer: &mut impl Write) -> io::Result<u64> {
+        match self {
+            StreamEncoding::Base64 => decode_base64(reader, writer),
+            StreamEncoding::Hexadecimal => decode_hex(reader, writer),
+            StreamEncoding::QuotedPrintable => decode_quoted_printable(reader, writer),
+            #[cfg(feature = "compression")]
+            StreamEncoding::Gzip => io::copy(&mut MultiGzDecoder::new(reader), writer),
+        }
+    }
+
+    /// The path step recording that this encoding was peeled off
+    fn step(self, input: &[u8]) -> CrackResult {
+        let input = preview(input);
+        match self {
+            StreamEncoding::Base64 => CrackResult::new(&Decoder::<Base64Decoder>::new(), input),
+            StreamEncoding::Hexadecimal => {
+                CrackResult::new(&Decoder::<HexadecimalDecoder>::new(), input)
+            }
+            StreamEncoding::QuotedPrintable => {
+                CrackResult::new(&Decoder::<QuotedPrintableDecoder>::new(), input)
+            }
+            #[cfg(feature = "compression")]
+            StreamEncoding::Gzip => CrackResult::new(&Decoder::<GzipDecoder>::new(), input),
+        }
+    }
+}
+
+/// Whether text has `=XX` escapes and otherwise only printable ASCII
+fn looks_quoted_printable(head: &[u8]) -> bool {
+    let printable = head
+        .iter()
+        .all(|b| b.is_ascii_graphic() || b.is_ascii_whitespace());
+    let has_escape = head.windows(3).any(|window| {
+        window[0] == b'='
+            && window[1..]
+                .iter()
+                .all(|b| b.is_ascii_digit() || (b'A'..=b'F').contains(b))
+    });
+    printable && has_escape
+}
+
+/// What is left of an input once every streaming layer is peeled off
+#[derive(Debug, Clone)]
+pub struct Peeled {
+    /// The text or bytes to search on
+    pub text: Candidate,
+    /// A step for each layer peeled off, outermost first
+    pub steps: Vec<CrackResult>,
+}
+
+/// Peels streaming encodings off `reader` until what is left is at most
+/// `threshold` bytes, or isn't in an encoding which streams
+///
+/// At most `threshold` bytes of the input are held in memory while peeling,
+/// the layers are written to temporary files. What is left is read into
+/// memory whatever its size, for the search to check.
+///
+/// ```
+/// use ares::streaming::peel;
+///
+/// // "hello world" in hex, then Base64
+/// let peeled = peel(&b"Njg2NTZjNmM2ZjIwNzc2ZjcyNmM2NA=="[..], 8).unwrap();
+/// assert_eq!(peeled.text, "hello world");
+/// assert_eq!(peeled.steps.len(), 2);
+/// assert_eq!(peeled.steps[0].decoder, "Base64");
+/// assert_eq!(peeled.steps[1].decoder, "Hexadecimal");
+/// ```
+///
+/// # Errors
+/// Returns an error if the input can't be read, the temporary files can't be
+/// written, or a layer turns out not to be in the encoding it looked like.
+pub fn peel<'a>(reader: impl Read + 'a, threshold: usize) -> io::Result<Peeled> {
+    let mut source: Box<dyn Read + 'a> = Box::new(reader);
+    // The layer being read from, deleted once the next one is written
+    let mut _layer: Option<TempFile> = None;
+    let mut steps: Vec<CrackResult> = Vec::new();
+    loop {
+        let mut buffered = Vec::new();
+        source
+            .by_ref()
+            .take(threshold.max(SNIFF_LEN) as u64 + 1)
+            .read_to_end(&mut buffered)?;
+        if let Some(step) = steps.last_mut() {
+            step.unencrypted_text = Some(vec![preview(&buffered).into()]);
+        }
+        let encoding = StreamEncoding::sniff(&buffered[..buffered.len().min(SNIFF_LEN)]);
+        let encoding = match encoding {
+            Some(encoding) if buffered.len() > threshold && steps.len() < MAX_LAYERS => encoding,
+            _ => {
+                source.read_to_end(&mut buffered)?;
+                return Ok(Peeled {
+                    text: Candidate::from_bytes(strip_trailing_newline(&buffered).to_vec()),
+                    steps,
+                });
+            }
+        };
+        debug!("Streaming input looks like {:?}, decoding it in chunks", encoding);
+        steps.push(encoding.step(&buffered));
+
+        let next = TempFile::new();
+        let mut writer = BufWriter::new(File::create(&next.0)?);
+        encoding
+            .decode(Cursor::new(buffered).chain(source), &mut writer)
+            .map_err(|e| io::Error::new(e.kind(), format!("not valid {:?}: {}", encoding, e)))?;
+        writer.flush()?;
+        source = Box::new(BufReader::new(File::open(&next.0)?));
+        _layer = Some(next);
+    }
+}
+
+/// The start of `bytes` as text, with "..." if there is more
+fn preview(bytes: &[u8]) -> String {
+    if bytes.len() > PREVIEW_LEN {
+        bytes_to_text(&bytes[..PREVIEW_LEN]) + "..."
+    } else {
+        bytes_to_text(bytes)
+    }
+}
+
+/// Drops the newline text editors leave at the end of files
+fn strip_trailing_newline(bytes: &[u8]) -> &[u8] {
+    bytes.strip_suffix(b"\n").unwrap_or(bytes)
+}
+
+/// A file in the temporary directory, deleted when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// A new path to write a layer to
+    fn new() -> Self {
+        TempFile(std::env::temp_dir().join(format!("ares-stream-{}", uuid::Uuid::new_v4())))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Reads through to another reader, leaving out ASCII whitespace
+struct SkipWhitespace<R>(R);
+
+impl<R: Read> Read for SkipWhitespace<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read = self.0.read(buf)?;
+            if read == 0 {
+                return Ok(0);
+            }
+            let mut kept = 0;
+            for i in 0..read {
+                let byte = buf[i];
+                if !byte.is_ascii_whitespace() {
+                    buf[kept] = byte;
+                    kept += 1;
+                }
+            }
+            // A chunk of only whitespace isn't the end of the input
+            if kept > 0 {
+                return Ok(kept);
+            }
+        }
+    }
+}
+
+/// Decodes Base64 in chunks, picking the URL-safe alphabet if the first
+/// chunk uses it
+fn decode_base64(reader: impl Read, writer: &mut impl Write) -> io::Result<u64> {
+    let mut reader = BufReader::new(SkipWhitespace(reader));
+    let head = reader.fill_buf()?;
+    let url_safe = head.iter().any(|b| b"-_".contains(b));
+    let config = GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    let engine = if url_safe {
+        GeneralPurpose::new(&alphabet::URL_SAFE, config)
+    } else {
+        GeneralPurpose::new(&alphabet::STANDARD, config)
+    };
+    io::copy(&mut base64::read::DecoderReader::new(reader, &engine), writer)
+}
+
+/// Decodes hex in chunks, carrying an odd digit over to the next chunk
+fn decode_hex(reader: impl Read, writer: &mut impl Write) -> io::Result<u64> {
+    let mut reader = SkipWhitespace(reader);
+    let mut chunk = vec![0; 64 * 1024];
+    let mut carried: Option<u8> = None;
+    let mut written = 0;
+    loop {
+        let read = reader.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        let digits: Vec<u8> = carried.take().into_iter().chain(chunk[..read].iter().copied()).collect();
+        let (pairs, rest) = digits.split_at(digits.len() - digits.len() % 2);
+        carried = rest.first().copied();
+        let bytes =
+            hex::decode(pairs).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writer.write_all(&bytes)?;
+        written += bytes.len() as u64;
+    }
+    if carried.is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "odd number of hex digits",
+        ));
+    }
+    Ok(written)
+}
+
+/// Decodes quoted-printable a line at a time. A line ending in `=` is a soft
+/// line break, which joins it to the next.
+fn decode_quoted_printable(reader: impl Read, writer: &mut impl Write) -> io::Result<u64> {
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    let mut written = 0;
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        let content = line.strip_suffix(b"\n").unwrap_or(&line);
+        let content = content.strip_suffix(b"\r").unwrap_or(content);
+        let ending = &line[content.len()..];
+        let (content, ending) = match content.strip_suffix(b"=") {
+            Some(joined) => (joined, &b""[..]),
+            None => (content, ending),
+        };
+        let mut bytes = quoted_printable::decode(content, quoted_printable::ParseMode::Robust)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        bytes.extend_from_slice(ending);
+        writer.write_all(&bytes)?;
+        written += bytes.len() as u64;
+        line.clear();
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "compression")]
+    use flate2::write::GzEncoder;
+    #[cfg(feature = "compression")]
+    use flate2::Compression;
+
+    /// Base64 with a line break every 76 characters, as in email
+    #[cfg(feature = "compression")]
+    fn wrapped_base64(bytes: &[u8]) -> Vec<u8> {
+        use base64::Engine as _;
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+        let lines: Vec<&str> = encoded
+            .as_bytes()
+            .chunks(76)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect();
+        lines.join("\n").into_bytes()
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn peels_gzip_under_wrapped_base64_above_the_threshold() {
+        let plaintext = "the quick brown fox jumps over the lazy dog\n".repeat(1000);
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(plaintext.as_bytes()).unwrap();
+        let input = wrapped_base64(&gzip.finish().unwrap());
+
+        let peeled = peel(&input[..], 64).unwrap();
+        let decoders: Vec<&str> = peeled.steps.iter().map(|step| step.decoder).collect();
+        assert_eq!(decoders, ["Base64", "Gzip"]);
+        assert_eq!(peeled.text, plaintext.trim_end());
+    }
+
+    #[test]
+    fn input_under_the_threshold_is_left_to_the_search() {
+        let peeled = peel(&b"68656c6c6f"[..], 1024).unwrap();
+        assert_eq!(peeled.text, "68656c6c6f");
+        assert!(peeled.steps.is_empty());
+    }
+
+    #[test]
+    fn hex_split_across_chunks_decodes() {
+        let plaintext = vec![0xab; 100_000];
+        let mut input = hex::encode(&plaintext).into_bytes();
+        input.insert(65_537, b'\n');
+        let mut decoded = Vec::new();
+        StreamEncoding::Hexadecimal
+            .decode(&input[..], &mut decoded)
+            .unwrap();
+        assert_eq!(decoded, plaintext);
+    }
+
+    #[test]
+    fn quoted_printable_soft_line_breaks_join() {
+        let mut decoded = Vec::new();
+        StreamEncoding::QuotedPrintable
+            .decode(&b"caf=C3=A9 au=\nlait\n"[..], &mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "café aulait\n".as_bytes());
+    }
+
+    #[test]
+    fn a_broken_layer_is_an_error() {
+        // Looks like hex for as far as it is sniffed
+        let input = "ab".repeat(SNIFF_LEN) + "zz";
+        assert!(peel(input.as_bytes(), 4).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! Helpers for testing decoders, for contributors and plugin authors
+//!
+//! Every decoder is expected to behave the same way however it works inside:
+//! it names itself in its results, only claims success with a plaintext, and
+//! copes with any text it is given, as the search hands it everything. The
+//! `assert_*` functions here check that, and panic with what went wrong like
+//! `assert!` does, so they can be called straight from `#[test]`s:
+//!
+//! - [`assert_decodes`] checks one input decodes to what it should
+//! - [`assert_round_trips`] and [`assert_round_trips_with_key`] check that
+//!   what an [`Encode`] decoder encodes decodes back
+//! - [`assert_round_trips_random`] does that for many random plaintexts
+//! - [`assert_conforms`] and [`assert_encoder_conforms`] check everything a
+//!   decoder or encoder should do, which the built in ones are tested with
+//!   in `tests/decoder_conformance.rs`
+//!
+//! ```rust
+//! use ares::decoders::base64_decoder::Base64Decoder;
+//! use ares::decoders::interface::{Crack, Decoder};
+//! use ares::testing::{assert_decodes, assert_encoder_conforms, assert_round_trips};
+//!
+//! let base64 = Decoder::<Base64Decoder>::new();
+//! assert_decodes(&base64, "aGVsbG8gd29ybGQ=", "hello world");
+//! assert_round_trips(&base64, "hello world");
+//! assert_encoder_conforms(&base64);
+//! ```
+
+use crate::checkers::athena::Athena;
+use crate::checkers::checker_type::{Check, Checker};
+use crate::checkers::regex_checker::RegexChecker;
+use crate::checkers::CheckerTypes;
+use crate::config::Config;
+use crate::decoders::crack_results::CrackResult;
+use crate::decoders::interface::{Crack, Encode};
+use crate::verify::reproduces;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Inputs every decoder must cope with: nothing, whitespace, a single
+/// letter, English, non-ASCII and control characters, and something long
+const AWKWARD_INPUTS: [&str; 7] = [
+    "",
+    "  \n\t",
+    "a",
+    "hello world",
+    "ÿ€ 😀 日本語",
+    "\0\u{1b}[0m\r\n",
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZ abcdefghijklmnopqrstuvwxyz 0123456789 !\"#$%&'()*+,-./:;<=>?@[]^_`{|}~",
+];
+
+/// The seed of [`assert_round_trips_random`]'s plaintexts, so a failure can
+/// be reproduced
+const SEED: u64 = 0x0a2e5;
+
+/// Runs the decoder on `input` as the search would, with the human checker
+/// off
+pub fn crack<D: Crack + ?Sized>(decoder: &D, input: &str) -> CrackResult {
+    let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+    let config = Config {
+        human_checker_on: false,
+        ..Default::default()
+    };
+    decoder.crack(input, &checker, &config)
+}
+
+/// Runs the decoder on `input` with a checker which only accepts
+/// `plaintext`, ignoring case and whitespace, like a crib does. Decoders
+/// which try many keys stop at the first the checker accepts, which for
+/// random plaintexts is often not the right one.
+fn crack_expecting<D: Crack + ?Sized>(decoder: &D, input: &str, plaintext: &str) -> CrackResult {
+    let characters: Vec<String> = plaintext
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| regex::escape(&c.to_string()))
+        .collect();
+    let checker = CheckerTypes::CheckRegex(Checker::<RegexChecker>::new());
+    let config = Config {
+        human_checker_on: false,
+        regex: Some(format!(r"(?i)^\s*{}\s*$", characters.join(r"\s*"))),
+        ..Default::default()
+    };
+    decoder.crack(input, &checker, &config)
+}
+
+/// What the decoder decoded `input` to, every candidate if it gives several
+/// like Caesar does
+pub fn decode<D: Crack + ?Sized>(decoder: &D, input: &str) -> Vec<String> {
+    crack(decoder, input)
+        .unencrypted_text
+        .unwrap_or_default()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Asserts the decoder decodes `input` to `expected`, or to candidates one
+/// of which is `expected`
+///
+/// # Panics
+/// Panics if none of what the decoder gave is `expected`.
+#[track_caller]
+pub fn assert_decodes<D: Crack + ?Sized>(decoder: &D, input: &str, expected: &str) {
+    let decoded = decode(decoder, input);
+    assert!(
+        decoded.iter().any(|text| text == expected),
+        "{} decoded {:?} to {:?}, expected {:?}",
+        decoder.get_name(),
+        input,
+        decoded,
+        expected
+    );
+}
+
+/// Asserts that what the encoder encodes `plaintext` to decodes back to it.
+/// Case and whitespace may differ, as encodings like Morse code and A1Z26
+/// don't keep them.
+///
+/// # Panics
+/// Panics if the plaintext can't be encoded, or doesn't decode back.
+#[track_caller]
+pub fn assert_round_trips<E: Encode + ?Sized>(encoder: &E, plaintext: &str) {
+    let encoded = encoder.encode(plaintext).unwrap_or_else(|e| {
+        panic!("{} could not encode {:?}: {}", encoder.get_name(), plaintext, e)
+    });
+    assert_decodes_back(encoder, plaintext, &encoded);
+}
+
+/// Asserts that what the encoder encodes `plaintext` to with `key` decodes
+/// back to it with the same key, ignoring case and whitespace as
+/// [`assert_round_trips`] does
+///
+/// # Panics
+/// Panics if the plaintext can't be encoded or decoded with the key, or
+/// doesn't decode back.
+#[track_caller]
+pub fn assert_round_trips_with_key<E: Encode + ?Sized>(encoder: &E, plaintext: &str, key: &str) {
+    let name = encoder.get_name();
+    let encoded = encoder.encode_with_key(plaintext, key).unwrap_or_else(|e| {
+        panic!("{} could not encode {:?} with key {:?}: {}", name, plaintext, key, e)
+    });
+    let decoded = encoder.decode_with_key(&encoded, key).unwrap_or_else(|e| {
+        panic!("{} could not decode {:?} with key {:?}: {}", name, encoded, key, e)
+    });
+    assert!(
+        reproduces(&decoded, plaintext),
+        "{} encoded {:?} with key {:?} to {:?}, which decoded back to {:?}",
+        name,
+        plaintext,
+        key,
+        encoded,
+        decoded
+    );
+}
+
+/// Asserts `samples` random plaintexts round trip as in
+/// [`assert_round_trips`]. The plaintexts are words of ASCII letters, some
+/// with digits or punctuation. Those the encoder can't encode, like digits
+/// for A1Z26, are skipped, as are those the decoder declines, like ROT18
+/// does text without digits. They are the same every run.
+///
+/// # Panics
+/// Panics if a plaintext which was encoded decodes to something else, or if
+/// none of them round tripped.
+#[track_caller]
+pub fn assert_round_trips_random<E: Encode + ?Sized>(encoder: &E, samples: usize) {
+    let mut round_tripped = false;
+    for plaintext in random_plaintexts(samples, SEED) {
+        let Ok(encoded) = encoder.encode(&plaintext) else {
+            continue;
+        };
+        if encoded == plaintext {
+            continue;
+        }
+        let decoded = decode_expecting(encoder, &encoded, &plaintext);
+        if !decoded.is_empty() {
+            assert_reproduces(encoder, &plaintext, &encoded, &decoded);
+            round_tripped = true;
+        }
+    }
+    assert!(
+        round_tripped || samples == 0,
+        "{} round tripped none of the {} random plaintexts",
+        encoder.get_name(),
+        samples
+    );
+}
+
+/// `count` random plaintexts from `seed`, as [`assert_round_trips_random`]
+/// uses
+pub fn random_plaintexts(count: usize, seed: u64) -> Vec<String> {
+    const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    const EXTRAS: &[u8] = b"0123456789.,!?'-";
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| {
+            // Only some have digits or punctuation, so encoders of letters get plenty
+            let extras = rng.random_bool(0.3);
+            let words: Vec<String> = (0..rng.random_range(1..=6))
+                .map(|_| {
+                    (0..rng.random_range(1..=8))
+                        .map(|_| {
+                            let alphabet = if extras && rng.random_bool(0.2) { EXTRAS } else { LETTERS };
+                            char::from(alphabet[rng.random_range(0..alphabet.len())])
+                        })
+                        .collect()
+                })
+                .collect();
+            words.join(" ")
+        })
+        .collect()
+}
+
+/// Asserts the decoder behaves as the search expects of every decoder:
+///
+/// - it has a name and a description, a popularity from 0 to 1 and a
+///   positive expected cost
+/// - its results are named after it, as paths and `--only` go by the name
+/// - it never claims success without a plaintext, or with the empty input
+/// - it copes with awkward input such as non-ASCII and control characters
+///   without panicking
+///
+/// # Panics
+/// Panics with the first of these the decoder doesn't do.
+#[track_caller]
+pub fn assert_conforms<D: Crack + ?Sized>(decoder: &D) {
+    let name = decoder.get_name();
+    assert!(!name.is_empty(), "a decoder has no name");
+    assert!(!decoder.get_description().is_empty(), "{} has no description", name);
+    let popularity = decoder.get_popularity();
+    assert!(
+        (0.0..=1.0).contains(&popularity),
+        "{} has a popularity of {}, which is not from 0 to 1",
+        name,
+        popularity
+    );
+    let cost = decoder.expected_cost();
+    assert!(
+        cost.is_finite() && cost > 0.0,
+        "{} has an expected cost of {}, which is not positive",
+        name,
+        cost
+    );
+
+    for input in AWKWARD_INPUTS {
+        let result = crack(decoder, input);
+        assert_eq!(
+            result.decoder, name,
+            "{} named its result of {:?} {:?}",
+            name, input, result.decoder
+        );
+        if result.success {
+            assert!(
+                !input.is_empty(),
+                "{} claimed the empty input was a plaintext",
+                name
+            );
+            assert!(
+                result.unencrypted_text.as_ref().is_some_and(|texts| !texts.is_empty()),
+                "{} claimed success on {:?} without a plaintext",
+                name,
+                input
+            );
+        }
+    }
+}
+
+/// Asserts the encoder conforms as a decoder, see [`assert_conforms`], and
+/// that unless it needs a key, 100 random plaintexts round trip, see
+/// [`assert_round_trips_random`]
+///
+/// # Panics
+/// Panics with the first of these the encoder doesn't do.
+#[track_caller]
+pub fn assert_encoder_conforms<E: Encode + ?Sized>(encoder: &E) {
+    assert_conforms(encoder);
+    // Keyed ciphers can't encode without one, so are checked with their keys
+    if encoder.encode("hello").is_ok() {
+        assert_round_trips_random(encoder, 100);
+    }
+}
+
+/// Asserts `encoded` decodes back to `plaintext`, ignoring case and whitespace
+#[track_caller]
+fn assert_decodes_back<E: Encode + ?Sized>(encoder: &E, plaintext: &str, encoded: &str) {
+    // Decoders give nothing for text they would leave as it is, like URL
+    // decoding plain words
+    if encoded == plaintext {
+        return;
+    }
+    let decoded = decode_expecting(encoder, encoded, plaintext);
+    assert_reproduces(encoder, plaintext, encoded, &decoded);
+}
+
+/// What the decoder decoded `input` to, with a checker which only accepts
+/// `plaintext`, see [`crack_expecting`]
+fn decode_expecting<D: Crack + ?Sized>(decoder: &D, input: &str, plaintext: &str) -> Vec<String> {
+    crack_expecting(decoder, input, plaintext)
+        .unencrypted_text
+        .unwrap_or_default()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Asserts one of what `encoded` was decoded to is `plaintext`, ignoring
+/// case and whitespace
+#[track_caller]
+fn assert_reproduces<E: Encode + ?Sized>(encoder: &E, plaintext: &str, encoded: &str, decoded: &[String]) {
+    assert!(
+        decoded.iter().any(|text| reproduces(text, plaintext)),
+        "{} encoded {:?} to {:?}, which decoded back to {:?}",
+        encoder.get_name(),
+        plaintext,
+        encoded,
+        decoded
+    );
+}
@@ -0,0 +1,143 @@
+//! What is worked out once about a candidate, for the decoders
+//!
+//! Most classical decoders start the same way: uppercase the text, keep its
+//! letters, check which characters it uses. Run over every candidate by every
+//! decoder, that adds up on deep searches. A [`TextInfo`] does it once, and
+//! [`Candidate::info`] keeps it with the candidate so all the decoders run on
+//! it share one. Decoders get it by overriding [`Crack::crack_candidate`].
+//!
+//! [`Candidate::info`]: crate::byte_text::Candidate::info
+//! [`Crack::crack_candidate`]: crate::decoders::interface::Crack::crack_candidate
+
+/// The characters a text uses: a bit for each ASCII character, and whether
+/// there is anything else
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Charset {
+    /// Bit `c` is set if the ASCII character `c` is in the text
+    ascii: u128,
+    /// Whether the text has characters outside ASCII
+    non_ascii: bool,
+}
+
+impl Charset {
+    /// The characters `text` uses
+    pub fn of(text: &str) -> Self {
+        let mut charset = Charset::default();
+        for c in text.chars() {
+            charset.insert(c);
+        }
+        charset
+    }
+
+    /// Adds a character
+    fn insert(&mut self, c: char) {
+        if c.is_ascii() {
+            self.ascii |= 1 << u32::from(c);
+        } else {
+            self.non_ascii = true;
+        }
+    }
+
+    /// Whether the text uses `c`. Characters outside ASCII aren't told apart,
+    /// so any of them is there if the text has one.
+    pub fn contains(&self, c: char) -> bool {
+        if c.is_ascii() {
+            self.ascii & (1 << u32::from(c)) != 0
+        } else {
+            self.non_ascii
+        }
+    }
+
+    /// Whether every character of the text is in `alphabet`
+    ///
+    /// ```
+    /// use ares::text_info::Charset;
+    ///
+    /// let charset = Charset::of("deadbeef");
+    /// assert!(charset.is_within("0123456789abcdef"));
+    /// assert!(!charset.is_within("0123456789"));
+    /// ```
+    pub fn is_within(&self, alphabet: &str) -> bool {
+        let allowed = Charset::of(alphabet);
+        self.ascii & !allowed.ascii == 0 && (!self.non_ascii || allowed.non_ascii)
+    }
+
+    /// Whether the text is all ASCII
+    pub fn is_ascii(&self) -> bool {
+        !self.non_ascii
+    }
+}
+
+/// What the decoders want to know about a text, worked out in one pass
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TextInfo {
+    /// The text in lowercase
+    pub lowercase: String,
+    /// The ASCII letters of the text, in order and as they are
+    pub letters: String,
+    /// The ASCII letters of the text, in order and uppercased
+    pub uppercase_letters: String,
+    /// The characters the text uses
+    pub charset: Charset,
+    /// How many characters there are
+    pub chars: usize,
+    /// How many of them are digits
+    pub digits: usize,
+    /// How many of them are whitespace
+    pub whitespace: usize,
+}
+
+impl TextInfo {
+    /// Works out everything about `text`
+    ///
+    /// ```
+    /// use ares::text_info::TextInfo;
+    ///
+    /// let info = TextInfo::new("Attack at 0600!");
+    /// assert_eq!(info.letters, "Attackat");
+    /// assert_eq!(info.uppercase_letters, "ATTACKAT");
+    /// assert_eq!(info.lowercase, "attack at 0600!");
+    /// assert_eq!((info.chars, info.digits, info.whitespace), (15, 4, 2));
+    /// ```
+    pub fn new(text: &str) -> Self {
+        let mut info = TextInfo {
+            lowercase: text.to_lowercase(),
+            ..Default::default()
+        };
+        for c in text.chars() {
+            info.chars += 1;
+            info.charset.insert(c);
+            if c.is_ascii_alphabetic() {
+                info.letters.push(c);
+                info.uppercase_letters.push(c.to_ascii_uppercase());
+            } else if c.is_ascii_digit() {
+                info.digits += 1;
+            } else if c.is_whitespace() {
+                info.whitespace += 1;
+            }
+        }
+        info
+    }
+
+    /// How many ASCII letters there are
+    pub fn letter_count(&self) -> usize {
+        self.letters.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charsets_tell_ascii_apart_and_lump_the_rest() {
+        let charset = Charset::of("héllo");
+        assert!(charset.contains('h'));
+        assert!(!charset.contains('x'));
+        assert!(charset.contains('é'));
+        assert!(charset.contains('ü'));
+        assert!(!charset.is_ascii());
+        assert!(!charset.is_within("ehlo"));
+        assert!(Charset::of("").is_within(""));
+    }
+}
@@ -1,64 +1,26 @@
-use crossbeam::channel::{bounded, Receiver};
-use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
-use std::{
-    sync::atomic::AtomicBool,
-    thread::{self, sleep},
-    time::Duration,
-};
 
-use crate::cli_pretty_printing::{countdown_until_program_ends, display_top_results};
-use crate::config::Config;
-use crate::storage::wait_athena_storage;
+/// The clock. Browsers don't give std one, so wasm builds ask JavaScript for the time
+#[cfg(target_arch = "wasm32")]
+pub use web_time::{Instant, SystemTime};
+/// The clock. Browsers don't give std one, so wasm builds ask JavaScript for the time
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::{Instant, SystemTime};
 
 /// Indicate whether timer is paused
 static PAUSED: AtomicBool = AtomicBool::new(false);
 
-/// Start the timer with duration in seconds
-pub fn start(duration: u32, config: Arc<Config>) -> Receiver<()> {
-    let (sender, recv) = bounded(1);
-    thread::spawn(move || {
-        let mut time_spent = 0;
+/// Rank and display the plaintext results collected by WaitAthena, once a
+/// top results search has hit its deadline or `config.max_results`.
+/// The browser's search has no timer to call this, and without the cli
+/// feature there is nothing to display them on.
+#[cfg(all(feature = "cli", not(target_arch = "wasm32")))]
+pub(crate) fn display_results(config: &crate::config::Config) {
+    use crate::cli_pretty_printing::display_top_results;
+    use crate::storage::wait_athena_storage;
 
-        while time_spent < duration {
-            if !PAUSED.load(Relaxed) {
-                sleep(Duration::from_secs(1));
-                time_spent += 1;
-                // Some pretty printing support
-                countdown_until_program_ends(time_spent, duration, &config);
-            }
-        }
-
-        // When the timer expires, display all collected plaintext results
-        // Only if we're in top_results mode
-        log::trace!("Timer expired. top_results mode: {}", config.top_results);
-
-        if config.top_results {
-            log::info!("Displaying all collected plaintext results");
-            filter_and_display_results(&config);
-        } else {
-            log::info!("Not in top_results mode, skipping display_wait_athena_results()");
-        }
-
-        // Replace the existing expect with a match that logs errors in case of send failure
-        match sender.send(()) {
-            Ok(_) => log::debug!("Timer signal sent successfully"),
-            Err(e) => {
-                // Just log the error instead of panicking
-                log::warn!(
-                    "Failed to send timer signal: {:?}. This is expected in benchmarks.",
-                    e
-                );
-            }
-        }
-    });
-
-    recv
-}
-
-/// Filter and display all plaintext results collected by WaitAthena
-fn filter_and_display_results(config: &Config) {
-    let results = wait_athena_storage::get_plaintext_results();
+    let results = wait_athena_storage::get_ranked_plaintext_results(config);
 
     log::trace!(
         "Retrieved {} results from wait_athena_storage",
@@ -78,3 +40,9 @@ pub fn pause() {
 pub fn resume() {
     PAUSED.store(false, Relaxed);
 }
+
+/// Returns true while the timer is paused, e.g. while the human checker is asking the user
+#[cfg(feature = "cli")]
+pub fn is_paused() -> bool {
+    PAUSED.load(Relaxed)
+}
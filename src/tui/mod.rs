@@ -0,0 +1,118 @@
+//! Terminal interface for watching a search, `ares --tui`
+//!
+//! The search runs on a worker thread while this module draws what it is doing
+//! from [`crate::searchers::events`]: the tree of decoder paths expanded so far,
+//! how often each decoder ran, and the best candidates by fitness score.
+//! When the human checker wants to confirm a plaintext, the question appears in
+//! the footer and is answered with a key press instead of a line on stdin.
+
+mod state;
+mod ui;
+
+use crate::checkers::human_checker::set_prompt_handler;
+use crate::config::Config;
+use crate::searchers::events::{self, SearchEvent};
+use crate::DecoderResult;
+use crossbeam::channel::{bounded, unbounded, Receiver};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::DefaultTerminal;
+use state::{Prompt, TuiState};
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait for a key press before redrawing
+const TICK: Duration = Duration::from_millis(100);
+
+/// Cracks `text` while showing the search in the terminal.
+/// Returns the result of the search, or None if it failed or the user quit.
+///
+/// # Errors
+/// Returns an error if the terminal cannot be drawn to or read from.
+pub fn run(text: &str, config: Config) -> std::io::Result<Option<DecoderResult>> {
+    let search_events = events::subscribe();
+    let (prompt_sender, prompts) = unbounded::<Prompt>();
+    set_prompt_handler(Some(Box::new(move |check| {
+        let (reply, answer) = bounded(1);
+        let prompt = Prompt {
+            text: check.text.clone(),
+            description: check.description.clone(),
+            reply,
+        };
+        // If the TUI has gone away there is nobody to ask
+        prompt_sender.send(prompt).is_ok() && answer.recv().unwrap_or(false)
+    })));
+
+    let mut state = TuiState::new(text, config.timeout);
+    // Nothing may print to stdout while the TUI owns the terminal
+    let mut worker_config = config;
+    worker_config.api_mode = true;
+    let (done_sender, done) = bounded(1);
+    let worker_text = text.to_string();
+    thread::spawn(move || {
+        let result = crate::perform_cracking(&worker_text, worker_config);
+        let _ = done_sender.send(result);
+    });
+
+    let mut terminal = ratatui::init();
+    let outcome = event_loop(&mut terminal, &mut state, &search_events, &prompts, &done);
+    ratatui::restore();
+
+    // Unblock the searcher if it is still waiting on an answer
+    state.answer_prompt(false);
+    set_prompt_handler(None);
+    events::unsubscribe_all();
+    outcome
+}
+
+/// Draws and handles input until the search is done and acknowledged, or the user quits
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    state: &mut TuiState,
+    search_events: &Receiver<SearchEvent>,
+    prompts: &Receiver<Prompt>,
+    done: &Receiver<Option<DecoderResult>>,
+) -> std::io::Result<Option<DecoderResult>> {
+    let mut result = None;
+    loop {
+        for event in search_events.try_iter() {
+            state.apply(event);
+        }
+        if state.prompt.is_none() {
+            state.prompt = prompts.try_recv().ok();
+        }
+        if result.is_none() {
+            if let Ok(search_result) = done.try_recv() {
+                // Set here too, as a cache hit finishes without the searcher emitting anything
+                state.finished = Some(search_result.is_some());
+                result = Some(search_result);
+            }
+        }
+
+        terminal.draw(|frame| ui::draw(frame, state))?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let quit = key.code == KeyCode::Char('q')
+            || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL));
+        if quit {
+            return Ok(None);
+        }
+        if state.prompt.is_some() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => state.answer_prompt(true),
+                KeyCode::Char('n') | KeyCode::Esc => state.answer_prompt(false),
+                _ => (),
+            }
+        } else if let Some(search_result) = result.take() {
+            // The search is over and the user has seen the result
+            return Ok(search_result);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! Everything the TUI displays, built up from search events
+
+use crate::searchers::events::SearchEvent;
+use crossbeam::channel::Sender;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+/// How many candidates the candidate list keeps
+const MAX_CANDIDATES: usize = 50;
+
+/// A tree of every decoder path the searcher has expanded
+#[derive(Debug, Default)]
+pub struct SearchTree {
+    /// Child nodes, keyed by the decoder used to reach them
+    children: BTreeMap<String, SearchTree>,
+    /// How many times a node at exactly this path was expanded
+    visits: usize,
+}
+
+impl SearchTree {
+    /// Records a visit to the node reached by `path`
+    fn insert(&mut self, path: &[String]) {
+        match path.split_first() {
+            Some((decoder, rest)) => self
+                .children
+                .entry(decoder.clone())
+                .or_default()
+                .insert(rest),
+            None => self.visits += 1,
+        }
+    }
+
+    /// Flattens the tree depth first into `(depth, decoder, visits)` rows, at most `limit` long
+    pub fn rows(&self, limit: usize) -> Vec<(usize, &str, usize)> {
+        let mut rows = Vec::new();
+        self.collect_rows(0, limit, &mut rows);
+        rows
+    }
+
+    /// Helper for `rows`
+    fn collect_rows<'a>(
+        &'a self,
+        depth: usize,
+        limit: usize,
+        rows: &mut Vec<(usize, &'a str, usize)>,
+    ) {
+        for (decoder, child) in &self.children {
+            if rows.len() >= limit {
+                return;
+            }
+            rows.push((depth, decoder.as_str(), child.visits));
+            child.collect_rows(depth + 1, limit, rows);
+        }
+    }
+}
+
+/// How often a decoder has been run and how often it produced text
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DecoderActivity {
+    /// Number of times the decoder was run
+    pub runs: usize,
+    /// Number of runs which produced new text
+    pub outputs: usize,
+}
+
+/// A text the searcher reached, with how English-like it looks
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    /// The text
+    pub text: String,
+    /// Decoders used to reach it
+    pub path: Vec<String>,
+    /// Fitness score, higher is more English-like
+    pub fitness: f64,
+    /// Whether a checker identified this text as plaintext
+    pub identified: bool,
+}
+
+/// The human checker asking the user about a possible plaintext
+pub struct Prompt {
+    /// The possible plaintext
+    pub text: String,
+    /// Why the checker thinks it is plaintext
+    pub description: String,
+    /// Where to send the user's answer
+    pub reply: Sender<bool>,
+}
+
+/// Everything shown on screen
+pub struct TuiState {
+    /// The text being cracked
+    pub input: String,
+    /// Timeout of the search in seconds
+    pub timeout: u32,
+    /// When the TUI started
+    started: Instant,
+    /// Number of nodes expanded so far
+    pub nodes_expanded: usize,
+    /// Deepest node expanded so far
+    pub max_depth: u32,
+    /// Every expanded decoder path
+    pub tree: SearchTree,
+    /// Per-decoder activity
+    pub activity: HashMap<String, DecoderActivity>,
+    /// Best texts seen so far, sorted by fitness
+    pub candidates: Vec<Candidate>,
+    /// A pending question for the user
+    pub prompt: Option<Prompt>,
+    /// Set once the search has finished, with whether it succeeded
+    pub finished: Option<bool>,
+}
+
+impl TuiState {
+    /// Creates the state for cracking `input`
+    pub fn new(input: &str, timeout: u32) -> Self {
+        TuiState {
+            input: input.to_string(),
+            timeout,
+            started: Instant::now(),
+            nodes_expanded: 0,
+            max_depth: 0,
+            tree: SearchTree::default(),
+            activity: HashMap::new(),
+            candidates: Vec::new(),
+            prompt: None,
+            finished: None,
+        }
+    }
+
+    /// Time since the TUI started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Updates the state with an event from the searcher
+    pub fn apply(&mut self, event: SearchEvent) {
+        match event {
+            SearchEvent::Started { input, timeout } => {
+                self.input = input;
+                self.timeout = timeout;
+            }
+            SearchEvent::NodeExpanded {
+                depth,
+                path,
+                text,
+                fitness,
+            } => {
+                self.nodes_expanded += 1;
+                self.max_depth = self.max_depth.max(depth);
+                self.tree.insert(&path);
+                // The input itself is not a candidate
+                if !path.is_empty() {
+                    self.add_candidate(Candidate {
+                        text,
+                        path,
+                        fitness,
+                        identified: false,
+                    });
+                }
+            }
+            SearchEvent::DecoderRan {
+                decoder,
+                produced_output,
+            } => {
+                let activity = self.activity.entry(decoder).or_default();
+                activity.runs += 1;
+                if produced_output {
+                    activity.outputs += 1;
+                }
+            }
+            SearchEvent::PlaintextFound { text, path, .. } => {
+                let fitness = crate::cryptanalysis::fitness_score(&text);
+                self.add_candidate(Candidate {
+                    text,
+                    path,
+                    fitness,
+                    identified: true,
+                });
+            }
+            SearchEvent::Finished { success, .. } => self.finished = Some(success),
+        }
+    }
+
+    /// Adds a candidate, keeping the list unique, sorted and bounded.
+    /// Identified plaintexts always sort before everything else.
+    fn add_candidate(&mut self, candidate: Candidate) {
+        if let Some(existing) = self
+            .candidates
+            .iter_mut()
+            .find(|existing| existing.text == candidate.text)
+        {
+            existing.identified |= candidate.identified;
+        } else {
+            self.candidates.push(candidate);
+        }
+        self.candidates.sort_by(|a, b| {
+            b.identified
+                .cmp(&a.identified)
+                .then(b.fitness.total_cmp(&a.fitness))
+        });
+        self.candidates.truncate(MAX_CANDIDATES);
+    }
+
+    /// Decoders sorted by how often they ran, most active first
+    pub fn activity_rows(&self) -> Vec<(&str, DecoderActivity)> {
+        let mut rows: Vec<(&str, DecoderActivity)> = self
+            .activity
+            .iter()
+            .map(|(decoder, activity)| (decoder.as_str(), *activity))
+            .collect();
+        rows.sort_by(|a, b| b.1.runs.cmp(&a.1.runs).then(a.0.cmp(b.0)));
+        rows
+    }
+
+    /// Answers the pending prompt, if there is one
+    pub fn answer_prompt(&mut self, accepted: bool) {
+        if let Some(prompt) = self.prompt.take() {
+            // The searcher may have timed out and stopped listening
+            let _ = prompt.reply.send(accepted);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expanded(path: &[&str], text: &str, fitness: f64) -> SearchEvent {
+        SearchEvent::NodeExpanded {
+            depth: path.len() as u32,
+            path: path.iter().map(|p| p.to_string()).collect(),
+            text: text.to_string(),
+            fitness,
+        }
+    }
+
+    #[test]
+    fn builds_tree_and_candidates() {
+        let mut state = TuiState::new("input", 5);
+        state.apply(expanded(&[], "input", 0.0));
+        state.apply(expanded(&["Base64"], "uryyb", 0.1));
+        state.apply(expanded(&["Base64", "caesar"], "hello", 0.9));
+        state.apply(expanded(&["Hexadecimal"], "zzzz", 0.0));
+
+        assert_eq!(state.nodes_expanded, 4);
+        assert_eq!(state.max_depth, 2);
+        assert_eq!(
+            state.tree.rows(10),
+            vec![(0, "Base64", 1), (1, "caesar", 1), (0, "Hexadecimal", 1)]
+        );
+        assert_eq!(state.tree.rows(2).len(), 2);
+        let texts: Vec<&str> = state.candidates.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["hello", "uryyb", "zzzz"]);
+    }
+
+    #[test]
+    fn identified_plaintexts_sort_first() {
+        let mut state = TuiState::new("input", 5);
+        state.apply(expanded(&["Base64"], "hello world", 0.9));
+        state.apply(SearchEvent::PlaintextFound {
+            text: "xyz".to_string(),
+            path: vec!["Base64".to_string()],
+            checker: "Athena Checker".to_string(),
+        });
+        assert_eq!(state.candidates[0].text, "xyz");
+        assert!(state.candidates[0].identified);
+    }
+
+    #[test]
+    fn counts_decoder_activity() {
+        let mut state = TuiState::new("input", 5);
+        for produced_output in [true, false, false] {
+            state.apply(SearchEvent::DecoderRan {
+                decoder: "Base64".to_string(),
+                produced_output,
+            });
+        }
+        state.apply(SearchEvent::DecoderRan {
+            decoder: "caesar".to_string(),
+            produced_output: true,
+        });
+        let rows = state.activity_rows();
+        assert_eq!(
+            rows[0],
+            (
+                "Base64",
+                DecoderActivity {
+                    runs: 3,
+                    outputs: 1
+                }
+            )
+        );
+        assert_eq!(
+            rows[1],
+            (
+                "caesar",
+                DecoderActivity {
+                    runs: 1,
+                    outputs: 1
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn answering_a_prompt_replies_and_clears_it() {
+        let mut state = TuiState::new("input", 5);
+        let (reply, answer) = crossbeam::channel::bounded(1);
+        state.prompt = Some(Prompt {
+            text: "hello".to_string(),
+            description: "English".to_string(),
+            reply,
+        });
+        state.answer_prompt(true);
+        assert!(state.prompt.is_none());
+        assert_eq!(answer.recv(), Ok(true));
+    }
+}
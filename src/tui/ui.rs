@@ -0,0 +1,207 @@
+//! Draws the TUI
+
+use super::state::TuiState;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+/// Draws every panel of the TUI
+pub fn draw(frame: &mut Frame, state: &TuiState) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(5),
+        ])
+        .split(frame.area());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[1]);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(columns[1]);
+
+    draw_header(frame, rows[0], state);
+    draw_tree(frame, columns[0], state);
+    draw_activity(frame, right[0], state);
+    draw_candidates(frame, right[1], state);
+    draw_footer(frame, rows[2], state);
+}
+
+/// Input, elapsed time and search statistics
+fn draw_header(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let status = match state.finished {
+        None => "searching",
+        Some(true) => "plaintext found",
+        Some(false) => "no plaintext found",
+    };
+    let line = Line::from(vec![
+        Span::styled(
+            truncate(&state.input, 40),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            "  {}s / {}s  nodes {}  depth {}  {}",
+            state.elapsed().as_secs(),
+            state.timeout,
+            state.nodes_expanded,
+            state.max_depth,
+            status
+        )),
+    ]);
+    frame.render_widget(
+        Paragraph::new(line).block(Block::default().borders(Borders::ALL).title("Ares")),
+        area,
+    );
+}
+
+/// Decoder paths the searcher has expanded
+fn draw_tree(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let lines: Vec<Line> = state
+        .tree
+        .rows(area.height as usize)
+        .into_iter()
+        .map(|(depth, decoder, visits)| {
+            Line::from(format!("{}{} ({})", "  ".repeat(depth), decoder, visits))
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Search tree")),
+        area,
+    );
+}
+
+/// How often each decoder ran and produced text
+fn draw_activity(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let lines: Vec<Line> = state
+        .activity_rows()
+        .into_iter()
+        .map(|(decoder, activity)| {
+            Line::from(format!(
+                "{:<24} {:>6} runs {:>6} outputs",
+                truncate(decoder, 24),
+                activity.runs,
+                activity.outputs
+            ))
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Decoder activity"),
+        ),
+        area,
+    );
+}
+
+/// Best texts so far, with plaintexts the checkers identified highlighted
+fn draw_candidates(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let lines: Vec<Line> = state
+        .candidates
+        .iter()
+        .map(|candidate| {
+            let style = if candidate.identified {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(format!("{:>6.2} ", candidate.fitness), style),
+                Span::styled(
+                    format!("{:<30} ", truncate(&candidate.path.join(" -> "), 30)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(truncate(&candidate.text, 80), style),
+            ])
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Candidates")),
+        area,
+    );
+}
+
+/// The pending prompt, or the key bindings
+fn draw_footer(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let (title, lines) = match (&state.prompt, state.finished) {
+        (Some(prompt), _) => (
+            "Is this the plaintext?",
+            vec![
+                Line::from(Span::styled(
+                    prompt.text.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::from(format!(
+                    "{}    [y/Enter] accept  [n/Esc] reject  [q] quit",
+                    prompt.description
+                )),
+            ],
+        ),
+        (None, Some(_)) => ("Done", vec![Line::from("Press any key to exit")]),
+        (None, None) => ("Keys", vec![Line::from("[q] quit")]),
+    };
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(title)),
+        area,
+    );
+}
+
+/// Shortens text to at most `max` characters, marking that it was cut
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut shortened: String = text.chars().take(max.saturating_sub(1)).collect();
+    shortened.push('…');
+    shortened
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::searchers::events::SearchEvent;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn renders_every_panel() {
+        let mut state = TuiState::new("aGVsbG8=", 5);
+        state.apply(SearchEvent::NodeExpanded {
+            depth: 1,
+            path: vec!["Base64".to_string()],
+            text: "hello".to_string(),
+            fitness: 1.5,
+        });
+        state.apply(SearchEvent::DecoderRan {
+            decoder: "Base64".to_string(),
+            produced_output: true,
+        });
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|frame| draw(frame, &state)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        for expected in ["aGVsbG8=", "Search tree", "Base64 (1)", "hello", "[q] quit"] {
+            assert!(screen.contains(expected), "missing {}", expected);
+        }
+    }
+
+    #[test]
+    fn truncates_long_text() {
+        assert_eq!(truncate("hello", 10), "hello");
+        assert_eq!(truncate("hello world", 6), "hello…");
+    }
+}
@@ -0,0 +1,92 @@
+//! Checking a result by re-encoding it, for `--verify`
+//!
+//! Every step whose decoder can also encode, like Base64 or Caesar with the
+//! key it found, is run backwards on what it produced. If that gives back
+//! what the step was given, the step is `verified`. If not, the step lost
+//! something on the way, like stray characters Base64 skipped or bytes
+//! which weren't valid text, and the plaintext may not be all there was.
+//!
+//! Steps which can't be re-encoded, like hashes or ciphers without an
+//! encoder, are left unchecked.
+
+use crate::decoders::ENCODER_MAP;
+use crate::DecoderResult;
+
+/// Marks each step of the result's path `verified` if re-encoding what it
+/// produced gives back its input, or not if it gives something else. Steps
+/// without an encoder, or whose key the encoder can't use, are left as they
+/// are.
+pub fn verify_path(result: &mut DecoderResult) {
+    let outputs: Vec<String> = (0..result.path.len())
+        .map(|i| result.step_output(i).into_owned())
+        .collect();
+    for (step, output) in result.path.iter_mut().zip(outputs) {
+        let Some(encoder) = ENCODER_MAP.get(step.decoder) else {
+            continue;
+        };
+        let encoded = match &step.key {
+            Some(key) => encoder.get().encode_with_key(&output, key),
+            None => encoder.get().encode(&output),
+        };
+        if let Ok(encoded) = encoded {
+            step.verified = Some(reproduces(&encoded, &step.encrypted_text));
+        }
+    }
+}
+
+/// Whether re-encoding gave back the step's input. Whitespace is ignored,
+/// as encodings are often wrapped or spaced and decoders skip it, and so is
+/// case, as hexadecimal and Base32 are read in either.
+pub(crate) fn reproduces(encoded: &str, input: &str) -> bool {
+    let mut encoded = encoded.chars().filter(|c| !c.is_whitespace());
+    let mut input = input.chars().filter(|c| !c.is_whitespace());
+    loop {
+        match (encoded.next(), input.next()) {
+            (None, None) => return true,
+            (Some(a), Some(b)) if a.eq_ignore_ascii_case(&b) => continue,
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoders::crack_results::CrackResult;
+
+    /// A step of `decoder` given `input`
+    fn step(decoder: &'static str, input: &str) -> CrackResult {
+        CrackResult::from_decoder_info(decoder, "", "", input)
+    }
+
+    #[test]
+    #[cfg(feature = "classical")]
+    fn reversible_steps_are_verified() {
+        let mut caesar = step("caesar", "Uryyb jbeyq");
+        caesar.key = Some("13".to_string());
+        let mut result = DecoderResult {
+            text: vec!["hello world".into()],
+            path: vec![
+                step("Hexadecimal", "56584A3565574967616D4A6C6558453D"),
+                step("Base64", "VXJ5eWIgamJleXE="),
+                caesar,
+            ],
+            confidence: 0.0,
+        };
+        verify_path(&mut result);
+        let verified: Vec<Option<bool>> = result.path.iter().map(|step| step.verified).collect();
+        assert_eq!(verified, [Some(true), Some(true), Some(true)]);
+    }
+
+    #[test]
+    fn lossy_and_unknown_steps_are_flagged() {
+        let mut result = DecoderResult {
+            text: vec!["hello".into()],
+            path: vec![step("SHA256", "2cf24d"), step("Base64", "aGVs!bG8=")],
+            confidence: 0.0,
+        };
+        verify_path(&mut result);
+        assert_eq!(result.path[0].verified, None);
+        assert_eq!(result.path[1].verified, Some(false));
+    }
+}
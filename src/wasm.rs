@@ -0,0 +1,82 @@
+//! JavaScript bindings, with the `wasm` feature
+//!
+//! Build for the browser without the default features, which need SQLite,
+//! the terminal and threads:
+//!
+//! ```text
+//! RUSTFLAGS='--cfg getrandom_backend="wasm_js"' \
+//!     wasm-pack build --target web -- --no-default-features --features wasm,decoders
+//! ```
+//!
+//! Results are returned as JSON strings, so JavaScript can `JSON.parse` them.
+//! There is no cache and no human checker in the browser, and the LemmeKnow
+//! checker finds nothing, see [`crate::checkers`].
+
+use crate::config::Config;
+use crate::request::CrackRequest;
+use crate::stats::StatsCounter;
+use crate::{perform_cracking_request, perform_decoding, try_perform_cracking, DecoderResult};
+use wasm_bindgen::prelude::*;
+
+/// Searches for the plaintext for up to `timeout` seconds.
+/// Returns `{"text": "...", "path": [...], "confidence": 0.9, "stats": {...}}` where each step
+/// is a decoder's result and `stats` are the search's [`SearchStats`](crate::stats::SearchStats).
+///
+/// # Errors
+/// Throws the reason no plaintext was found.
+#[wasm_bindgen]
+pub fn crack(text: &str, timeout: u32) -> Result<String, JsError> {
+    let stats = StatsCounter::default();
+    let config = Config {
+        timeout,
+        human_checker_on: false,
+        stats: Some(stats.clone()),
+        ..Default::default()
+    };
+    let result = try_perform_cracking(text, config)?;
+    Ok(result_json(&result, &stats))
+}
+
+/// Cracks a [`CrackRequest`] given as JSON, such as
+/// `{"input": {"text": "..."}, "config": {"timeout": 10}, "crib": "flag{"}`.
+/// Returns the same JSON as [`crack`]. Wordlists can't be read in the
+/// browser, so words are given inline with `"words"`.
+///
+/// # Errors
+/// Throws if the request isn't valid JSON, or the reason no plaintext was found.
+#[wasm_bindgen]
+pub fn crack_request(request: &str) -> Result<String, JsError> {
+    let mut request: CrackRequest = serde_json::from_str(request)?;
+    request.config.human_checker_on = false;
+    let stats = StatsCounter::default();
+    request.config.stats = Some(stats.clone());
+    let result = perform_cracking_request(request)?;
+    Ok(result_json(&result, &stats))
+}
+
+/// The plaintext, path and confidence of a result, and what the search did, as JSON
+fn result_json(result: &DecoderResult, stats: &StatsCounter) -> String {
+    serde_json::json!({
+        "text": result.text.first(),
+        "path": result.path,
+        "confidence": result.confidence,
+        "stats": stats.snapshot(),
+    })
+    .to_string()
+}
+
+/// Runs one decoder, or every decoder with that tag, without searching.
+/// Returns a JSON list of the decoders' results.
+///
+/// # Errors
+/// Throws if no decoder matches or none of them accept the key.
+#[wasm_bindgen]
+pub fn decode(text: &str, decoder: &str, key: Option<String>) -> Result<String, JsError> {
+    let config = Config {
+        human_checker_on: false,
+        ..Default::default()
+    };
+    let results =
+        perform_decoding(text, decoder, key.as_deref(), &config).map_err(|e| JsError::new(&e))?;
+    Ok(serde_json::to_string(&results)?)
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "tokio")]
+
+use ares::config::Config;
+use ares::perform_cracking_async;
+use ares::{set_test_db_path, TestDatabase};
+use serial_test::serial;
+use std::time::{Duration, Instant};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+}
+
+#[test]
+#[serial]
+fn async_cracking_finds_the_plaintext() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        ..Default::default()
+    };
+    let result = runtime().block_on(perform_cracking_async(
+        "aGVsbG8gdGhlcmUgZ2VuZXJhbA==",
+        config,
+    ));
+    assert_eq!(result.unwrap().text[0], "hello there general");
+}
+
+#[test]
+#[serial]
+fn dropping_the_future_cancels_the_search() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        timeout: 30,
+        ..Default::default()
+    };
+    let start = Instant::now();
+    let outcome = runtime().block_on(async {
+        tokio::time::timeout(
+            Duration::from_millis(200),
+            perform_cracking_async("qZ3vK9xWm2Lp8RtY7nBc4HgF", config),
+        )
+        .await
+    });
+    assert!(outcome.is_err());
+    // The runtime waits for the blocking task on shutdown, so this only returns
+    // quickly if the search noticed it was cancelled
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
@@ -0,0 +1,18 @@
+//! Every built in decoder and encoder against the checks of `ares::testing`
+
+use ares::decoders::{all_decoders, ENCODER_MAP};
+use ares::testing::{assert_conforms, assert_encoder_conforms};
+
+#[test]
+fn every_decoder_conforms() {
+    for decoder in all_decoders() {
+        assert_conforms(decoder);
+    }
+}
+
+#[test]
+fn every_encoder_conforms() {
+    for encoder in ENCODER_MAP.values() {
+        assert_encoder_conforms(encoder.get());
+    }
+}
@@ -0,0 +1,23 @@
+//! The in-memory database sets DB_PATH for the whole process, so it is
+//! tested in its own binary
+#![cfg(feature = "cache")]
+
+use ares::config::Config;
+use ares::perform_cracking;
+use ares::storage::database::{self, IN_MEMORY_DATABASE};
+
+#[test]
+fn in_memory_database_keeps_results_between_cracks() {
+    let config = || Config {
+        human_checker_on: false,
+        database_path: Some(IN_MEMORY_DATABASE.to_string()),
+        ..Default::default()
+    };
+    let text = "aGVsbG8gdGhlcmUgZ2VuZXJhbA==";
+    let first = perform_cracking(text, config()).unwrap();
+    assert_eq!(first.text[0], "hello there general");
+    assert!(database::read_cache(text).unwrap().is_some());
+
+    let second = perform_cracking(text, config()).unwrap();
+    assert_eq!(second.text, first.text);
+}
@@ -122,7 +122,7 @@ fn test_affine_cipher_decoding() {
     // IHHWVC SWFRCP -> AFFINE CIPHER (a=5, b=8)
     let result = decoder.crack("IHHWVC SWFRCP", &get_athena_checker(), &ares::config::Config::default());
     let results = result.unencrypted_text.unwrap();
-    assert!(results.contains(&"AFFINE CIPHER".to_string()));
+    assert!(results.contains(&"AFFINE CIPHER".into()));
 }
 
 #[test]
@@ -131,7 +131,7 @@ fn test_xor_decoding() {
     // "HELLO" XOR 32 = "hello"
     let result = decoder.crack("HELLO", &get_athena_checker(), &ares::config::Config::default());
     let results = result.unencrypted_text.unwrap();
-    assert!(results.contains(&"hello".to_string()));
+    assert!(results.contains(&"hello".into()));
 }
 
 // Classical cipher decoder tests
@@ -1,15 +1,23 @@
-use ares::checkers::checker_result::CheckResult;
-use ares::checkers::checker_type::{Check, Checker};
-use ares::checkers::english::EnglishChecker;
-use ares::config::Config;
+use ares::checkers::CheckerTypes;
+use ares::config::{CacheMode, Config};
 use ares::decoders::base64_decoder::Base64Decoder;
 use ares::decoders::crack_results::CrackResult;
+use ares::decoders::find_decoder;
 use ares::decoders::interface::{Crack, Decoder};
-use ares::perform_cracking;
-use ares::storage::database;
+use ares::input_format::InputFormat;
+use ares::storage::wait_athena_storage::PlaintextResults;
+use ares::{perform_cracking, register_decoder, set_human_verifier, try_perform_cracking, AresError};
 use ares::{set_test_db_path, TestDatabase};
 use serial_test::serial;
-use uuid::Uuid;
+#[cfg(feature = "cache")]
+use {
+    ares::checkers::checker_result::CheckResult,
+    ares::checkers::checker_type::{Check, Checker},
+    ares::checkers::english::EnglishChecker,
+    ares::storage::database,
+    ares::AresSession,
+    uuid::Uuid,
+};
 
 // TODO Below fails because Library API is broken.
 // https://github.com/bee-san/ciphey/issues/48
@@ -69,6 +77,7 @@ fn test_program_parses_files_with_new_line_and_cracks() {
 
 #[test]
 #[serial]
+#[cfg(feature = "cache")]
 fn test_cache_miss_simple_base64() {
     let _test_db = TestDatabase::default();
     set_test_db_path();
@@ -91,7 +100,7 @@ fn test_cache_miss_simple_base64() {
     let base64_decoder = Decoder::<Base64Decoder>::new();
     let mut expected_crack_result: CrackResult =
         CrackResult::new(&base64_decoder, encoded_text_1.clone());
-    expected_crack_result.unencrypted_text = Some(vec![decoded_text_1.clone()]);
+    expected_crack_result.unencrypted_text = Some(vec![decoded_text_1.clone().into()]);
     let expected_checker = Checker::<EnglishChecker>::new();
     let mut expected_check_result = CheckResult::new(&expected_checker);
     expected_check_result.is_identified = true;
@@ -106,6 +115,7 @@ fn test_cache_miss_simple_base64() {
 
 #[test]
 #[serial]
+#[cfg(feature = "cache")]
 fn test_cache_hit_simple_base64() {
     let _test_db = TestDatabase::default();
     set_test_db_path();
@@ -116,7 +126,7 @@ fn test_cache_hit_simple_base64() {
     let base64_decoder = Decoder::<Base64Decoder>::new();
     let mut expected_crack_result: CrackResult =
         CrackResult::new(&base64_decoder, encoded_text_1.clone());
-    expected_crack_result.unencrypted_text = Some(vec![decoded_text_1.clone()]);
+    expected_crack_result.unencrypted_text = Some(vec![decoded_text_1.clone().into()]);
     let expected_checker = Checker::<EnglishChecker>::new();
     let mut expected_check_result = CheckResult::new(&expected_checker);
     expected_check_result.is_identified = true;
@@ -147,3 +157,367 @@ fn test_cache_hit_simple_base64() {
     assert_eq!(row.path, expected_path);
     assert!(row.successful);
 }
+
+/// A decoder only known to the tests, looking up a single code word
+struct CodebookDecoder {
+    tags: Vec<&'static str>,
+}
+
+impl Crack for CodebookDecoder {
+    fn new() -> Self {
+        CodebookDecoder {
+            tags: vec!["codebook", "decoder"],
+        }
+    }
+    fn crack(&self, text: &str, checker: &CheckerTypes, config: &Config) -> CrackResult {
+        let mut result =
+            CrackResult::from_decoder_info("Test codebook", "Looks up code words", "", text.to_string());
+        if text == "zqx-7f3a-vvk" {
+            let plaintext = "hello there general".to_string();
+            result.update_checker(&checker.check(&plaintext, config));
+            result.unencrypted_text = Some(vec![plaintext.into()]);
+        }
+        result
+    }
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    fn get_name(&self) -> &str {
+        "Test codebook"
+    }
+    fn get_description(&self) -> &str {
+        "Looks up code words"
+    }
+    fn get_link(&self) -> &str {
+        ""
+    }
+}
+
+#[test]
+#[serial]
+fn registered_decoders_join_the_search() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    register_decoder(Box::new(CodebookDecoder::new())).unwrap();
+    assert!(find_decoder("Test codebook").is_some());
+    assert!(register_decoder(Box::new(CodebookDecoder::new())).is_err());
+    assert!(register_decoder(Box::new(Decoder::<Base64Decoder>::new())).is_err());
+
+    let config = Config {
+        human_checker_on: false,
+        ..Default::default()
+    };
+    let result = perform_cracking("zqx-7f3a-vvk", config).unwrap();
+    assert_eq!(result.text[0], "hello there general");
+    assert_eq!(result.path.last().unwrap().decoder, "Test codebook");
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "cache")]
+fn session_reuses_its_connection_for_the_cache() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let session = AresSession::new(Config {
+        human_checker_on: false,
+        ..Default::default()
+    });
+    let text = "aGVsbG8gdGhlcmUgZ2VuZXJhbA==";
+    let first = session.crack(text).unwrap();
+    assert_eq!(first.text[0], "hello there general");
+
+    // The second crack is answered from the cache written by the first
+    assert!(database::read_cache(text).unwrap().is_some());
+    let second = session.crack(text).unwrap();
+    assert_eq!(second.text, first.text);
+    assert_eq!(second.path.len(), first.path.len());
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "cache")]
+fn only_read_write_cache_mode_stores_results() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let crack_with = |text: &str, cache_mode| {
+        let config = Config {
+            human_checker_on: false,
+            cache_mode,
+            ..Default::default()
+        };
+        let result = perform_cracking(text, config).unwrap();
+        assert!(result.text[0].text().starts_with("hello there general"));
+        database::read_cache(text).unwrap_or_default()
+    };
+
+    assert!(crack_with("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", CacheMode::ReadWrite).is_some());
+    let hex = "68656c6c6f2074686572652067656e6572616c";
+    assert!(crack_with(hex, CacheMode::ReadOnly).is_none());
+    assert!(crack_with("aGVsbG8gdGhlcmUgZ2VuZXJhbCE=", CacheMode::Off).is_none());
+    // Read-only runs still reuse what is already cached
+    assert!(crack_with("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", CacheMode::ReadOnly).is_some());
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "cache")]
+fn failed_cracks_are_remembered_until_retried() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        timeout: 1,
+        ..Default::default()
+    };
+    let text = "zqxjkvbwpfmglrtyhdcsunoeai";
+    let result = try_perform_cracking(text, config.clone());
+    assert_eq!(result.unwrap_err(), AresError::Timeout);
+    let result = try_perform_cracking(text, config.clone());
+    assert_eq!(result.unwrap_err(), AresError::PreviouslyFailed(1));
+    // A failure says nothing about a search with a crib
+    let with_crib = Config {
+        crib: Some("attack".to_string()),
+        ..config.clone()
+    };
+    let result = try_perform_cracking(text, with_crib);
+    assert_eq!(result.unwrap_err(), AresError::Timeout);
+
+    let retry = Config {
+        retry_failed: true,
+        ..config
+    };
+    let result = try_perform_cracking(text, retry);
+    assert_eq!(result.unwrap_err(), AresError::Timeout);
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "cache")]
+fn large_inputs_come_back_whole_from_the_cache() {
+    use base64::Engine;
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let session = AresSession::new(Config {
+        human_checker_on: false,
+        ..Default::default()
+    });
+    let plaintext = "hello there general, the quick brown fox jumps over the lazy dog. ".repeat(8);
+    let text = base64::engine::general_purpose::STANDARD.encode(&plaintext);
+    let first = session.crack(&text).unwrap();
+    assert_eq!(first.text[0], plaintext);
+
+    let row = database::read_cache(&text).unwrap().unwrap();
+    assert!(row.encoded_text.len() < text.len());
+    let second = session.crack(&text).unwrap();
+    assert_eq!(second.text[0], plaintext);
+    assert_eq!(&*second.path[0].encrypted_text, text);
+}
+
+#[test]
+#[serial]
+fn try_perform_cracking_says_why_it_failed() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        timeout: 1,
+        ..Default::default()
+    };
+    let result = try_perform_cracking("qxjvwzpkqlxmvbzrtyqwplkjhgfdsazxcvbnm", config.clone());
+    // Without the classical ciphers there can be too few decoders to last the timeout
+    assert!(matches!(
+        result,
+        Err(AresError::Timeout | AresError::NoDecodingFound)
+    ));
+
+    let hex_config = Config {
+        input_format: InputFormat::Hex,
+        ..config
+    };
+    let result = try_perform_cracking("not hex", hex_config);
+    assert!(matches!(result, Err(AresError::InvalidInput(_))));
+}
+
+#[test]
+#[serial]
+fn keep_intermediates_records_each_steps_output() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        keep_intermediates: true,
+        ..Default::default()
+    };
+    let result = perform_cracking("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config).unwrap();
+    let last = result.path.last().unwrap();
+    assert_eq!(
+        last.intermediate_text.as_deref(),
+        Some("hello there general")
+    );
+    assert!(result.explain().contains("→ \"hello there general\""));
+}
+
+#[test]
+#[serial]
+fn verify_re_encodes_each_step() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        verify: true,
+        ..Default::default()
+    };
+    let result = perform_cracking("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config).unwrap();
+    assert_eq!(result.path.last().unwrap().verified, Some(true));
+    assert!(result.explain().contains("Base64 [verified]"));
+}
+
+#[test]
+#[serial]
+fn concurrent_cracks_keep_their_own_config() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let crack_with_crib = |text: &'static str, crib: &'static str| {
+        std::thread::spawn(move || {
+            let config = Config {
+                human_checker_on: false,
+                timeout: 2,
+                regex: Some(crib.to_string()),
+                ..Default::default()
+            };
+            try_perform_cracking(text, config)
+        })
+    };
+    let found = crack_with_crib("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", "^hello");
+    let not_found = crack_with_crib("aGVsbG8gdGhlcmUga2Vub2Jp", "^goodbye");
+
+    assert_eq!(
+        found.join().unwrap().unwrap().text[0],
+        "hello there general"
+    );
+    assert!(not_found.join().unwrap().is_err());
+}
+
+#[test]
+#[serial]
+fn results_carry_a_confidence() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        ..Default::default()
+    };
+    let result = perform_cracking("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config).unwrap();
+    assert!(result.confidence >= 0.8, "{}", result.confidence);
+}
+
+#[test]
+#[serial]
+fn top_results_stop_at_max_results() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        top_results: true,
+        max_results: Some(1),
+        timeout: 30,
+        ..Default::default()
+    };
+    let start = std::time::Instant::now();
+    let result = perform_cracking("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config);
+    assert!(result.is_some());
+    // Without max_results the search would run until the timeout
+    assert!(start.elapsed() < std::time::Duration::from_secs(20), "{:?}", start.elapsed());
+}
+
+#[test]
+#[serial]
+fn concurrent_top_results_cracks_keep_their_own_results() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let collect_top_results = |text: &'static str| {
+        std::thread::spawn(move || {
+            let results = PlaintextResults::default();
+            let config = Config {
+                human_checker_on: false,
+                top_results: true,
+                max_results: Some(1),
+                timeout: 10,
+                cache_mode: CacheMode::Off,
+                plaintext_results: Some(results.clone()),
+                ..Default::default()
+            };
+            perform_cracking(text, config);
+            results.get()
+        })
+    };
+    let general = collect_top_results("aGVsbG8gdGhlcmUgZ2VuZXJhbA==");
+    let kenobi = collect_top_results("aGVsbG8gdGhlcmUga2Vub2Jp");
+
+    let general = general.join().unwrap();
+    let kenobi = kenobi.join().unwrap();
+    assert!(!general.is_empty() && !kenobi.is_empty());
+    assert!(general.iter().all(|result| result.text == "hello there general"));
+    assert!(kenobi.iter().all(|result| result.text == "hello there kenobi"));
+}
+
+#[test]
+#[serial]
+fn url_encoded_text_is_decoded_as_url() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let config = Config {
+        human_checker_on: false,
+        ..Default::default()
+    };
+    // ROT47 reads any printable text and once gave a false positive here
+    // before URL got to run
+    let result = perform_cracking("hello%20there%20general%20kenobi", config).unwrap();
+    assert_eq!(result.text[0], "hello there general kenobi");
+    assert_eq!(result.path.last().unwrap().decoder, "URL");
+}
+
+#[test]
+#[serial]
+fn url_encoded_text_is_decoded_the_same_on_any_number_of_threads() {
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    for threads in [1, 2, 8] {
+        let config = Config {
+            human_checker_on: false,
+            cache_mode: CacheMode::Off,
+            threads: Some(threads),
+            ..Default::default()
+        };
+        let result = perform_cracking("hello%20there%20general%20kenobi", config).unwrap();
+        assert_eq!(result.text[0], "hello there general kenobi", "{threads} threads");
+    }
+}
+
+#[test]
+#[serial]
+fn every_crack_asks_the_human_again() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let _test_db = TestDatabase::default();
+    set_test_db_path();
+    let asked = Arc::new(AtomicUsize::new(0));
+    let counter = asked.clone();
+    set_human_verifier(Some(Box::new(move |_: &ares::checkers::checker_result::CheckResult| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        true
+    })));
+    let config = Config {
+        human_checker_on: true,
+        cache_mode: CacheMode::Off,
+        ..Default::default()
+    };
+    // A "yes" in the first crack used to accept everything after it unasked
+    for cracks in 1..=2 {
+        let result = perform_cracking("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config.clone()).unwrap();
+        assert_eq!(result.text[0], "hello there general");
+        assert_eq!(asked.load(Ordering::Relaxed), cracks);
+    }
+    set_human_verifier(None);
+}
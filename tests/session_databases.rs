@@ -0,0 +1,63 @@
+//! Sessions keep the database their config names, whichever one the process
+//! set up first, so they are tested in their own binary
+#![cfg(feature = "cache")]
+
+use ares::config::Config;
+use ares::storage::database;
+use ares::AresSession;
+use std::path::PathBuf;
+
+/// A database file in the temp dir, deleted with its WAL files when dropped
+struct TempDatabase(PathBuf);
+
+impl TempDatabase {
+    fn new(name: &str) -> Self {
+        let file = format!("ares-{}-{}.sqlite", name, std::process::id());
+        TempDatabase(std::env::temp_dir().join(file))
+    }
+
+    fn config(&self) -> Config {
+        Config {
+            human_checker_on: false,
+            database_path: Some(self.0.to_string_lossy().into_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn has_cached(&self, text: &str) -> bool {
+        let conn = database::open_read_only_connection_for(&self.config()).unwrap();
+        database::read_cache_with(&conn, text).unwrap().is_some()
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = self.0.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[test]
+fn sessions_with_different_database_paths_crack_at_once() {
+    let first = TempDatabase::new("first");
+    let second = TempDatabase::new("second");
+    let first_session = AresSession::new(first.config());
+    let second_session = AresSession::new(second.config());
+    let base64 = "aGVsbG8gdGhlcmUgZ2VuZXJhbA==";
+    let hex = "68656c6c6f207468657265206b656e6f6269";
+
+    std::thread::scope(|scope| {
+        let first_crack = scope.spawn(|| first_session.crack(base64));
+        let second_crack = scope.spawn(|| second_session.crack(hex));
+        assert_eq!(first_crack.join().unwrap().unwrap().text[0], "hello there general");
+        assert_eq!(second_crack.join().unwrap().unwrap().text[0], "hello there kenobi");
+    });
+
+    assert!(first.has_cached(base64));
+    assert!(!first.has_cached(hex));
+    assert!(second.has_cached(hex));
+    assert!(!second.has_cached(base64));
+}